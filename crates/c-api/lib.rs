@@ -26,7 +26,7 @@ pub enum resvg_error {
     FILE_OPEN_FAILED,
     /// Compressed SVG must use the GZip algorithm.
     MALFORMED_GZIP,
-    /// We do not allow SVG with more than 1_000_000 elements for security reasons.
+    /// The number of elements after `use` resolving exceeds the limit.
     ELEMENTS_LIMIT_REACHED,
     /// SVG doesn't have a valid size.
     ///
@@ -36,6 +36,16 @@ pub enum resvg_error {
     INVALID_SIZE,
     /// Failed to parse an SVG data.
     PARSING_FAILED,
+    /// `use` elements nesting exceeds the limit.
+    USE_DEPTH_LIMIT_REACHED,
+    /// A filter region area exceeds the limit.
+    FILTER_REGION_LIMIT_REACHED,
+    /// A pattern requires more tiles than the limit.
+    PATTERN_TILES_LIMIT_REACHED,
+    /// SVG images nesting exceeds the limit.
+    NESTED_IMAGES_LIMIT_REACHED,
+    /// A raster image has more pixels than the limit.
+    IMAGE_PIXELS_LIMIT_REACHED,
 }
 
 /// @brief A rectangle representation.
@@ -857,6 +867,11 @@ fn convert_error(e: usvg::Error) -> resvg_error {
         usvg::Error::ElementsLimitReached => resvg_error::ELEMENTS_LIMIT_REACHED,
         usvg::Error::InvalidSize => resvg_error::INVALID_SIZE,
         usvg::Error::ParsingFailed(_) => resvg_error::PARSING_FAILED,
+        usvg::Error::UseDepthLimitReached => resvg_error::USE_DEPTH_LIMIT_REACHED,
+        usvg::Error::FilterRegionLimitReached => resvg_error::FILTER_REGION_LIMIT_REACHED,
+        usvg::Error::PatternTilesLimitReached => resvg_error::PATTERN_TILES_LIMIT_REACHED,
+        usvg::Error::NestedImagesLimitReached => resvg_error::NESTED_IMAGES_LIMIT_REACHED,
+        usvg::Error::ImagePixelsLimitReached => resvg_error::IMAGE_PIXELS_LIMIT_REACHED,
     }
 }
 
@@ -39,7 +39,7 @@ typedef enum {
      */
     RESVG_ERROR_MALFORMED_GZIP,
     /**
-     * We do not allow SVG with more than 1_000_000 elements for security reasons.
+     * The number of elements after `use` resolving exceeds the limit.
      */
     RESVG_ERROR_ELEMENTS_LIMIT_REACHED,
     /**
@@ -54,6 +54,26 @@ typedef enum {
      * Failed to parse an SVG data.
      */
     RESVG_ERROR_PARSING_FAILED,
+    /**
+     * `use` elements nesting exceeds the limit.
+     */
+    RESVG_ERROR_USE_DEPTH_LIMIT_REACHED,
+    /**
+     * A filter region area exceeds the limit.
+     */
+    RESVG_ERROR_FILTER_REGION_LIMIT_REACHED,
+    /**
+     * A pattern requires more tiles than the limit.
+     */
+    RESVG_ERROR_PATTERN_TILES_LIMIT_REACHED,
+    /**
+     * SVG images nesting exceeds the limit.
+     */
+    RESVG_ERROR_NESTED_IMAGES_LIMIT_REACHED,
+    /**
+     * A raster image has more pixels than the limit.
+     */
+    RESVG_ERROR_IMAGE_PIXELS_LIMIT_REACHED,
 } resvg_error;
 
 /**
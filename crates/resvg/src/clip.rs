@@ -5,6 +5,7 @@ use crate::render::Context;
 
 pub fn apply(
     clip: &usvg::ClipPath,
+    ctx: &Context,
     transform: tiny_skia::Transform,
     pixmap: &mut tiny_skia::Pixmap,
 ) {
@@ -14,12 +15,13 @@ pub fn apply(
     draw_children(
         clip.root(),
         tiny_skia::BlendMode::Clear,
+        ctx,
         transform.pre_concat(clip.transform()),
         &mut clip_pixmap.as_mut(),
     );
 
     if let Some(clip) = clip.clip_path() {
-        apply(clip, transform, pixmap);
+        apply(clip, ctx, transform, pixmap);
     }
 
     let mut mask = tiny_skia::Mask::from_pixmap(clip_pixmap.as_ref(), tiny_skia::MaskType::Alpha);
@@ -30,6 +32,7 @@ pub fn apply(
 fn draw_children(
     parent: &usvg::Group,
     mode: tiny_skia::BlendMode,
+    ctx: &Context,
     transform: tiny_skia::Transform,
     pixmap: &mut tiny_skia::PixmapMut,
 ) {
@@ -40,24 +43,17 @@ fn draw_children(
                     continue;
                 }
 
-                // We could use any values here. They will not be used anyway.
-                let ctx = Context {
-                    max_bbox: tiny_skia::IntRect::from_xywh(0, 0, 1, 1).unwrap(),
-                };
-
-                crate::path::fill_path(path, mode, &ctx, transform, pixmap);
+                crate::path::fill_path(path, mode, ctx, transform, pixmap);
             }
-            // Rectangle, Ellipse, and Polygon nodes are converted to paths for clipping.
+            // Primitive shape nodes are converted to paths for clipping.
+            // `line` is never a part of a `clipPath`, because it has no fill.
             // This maintains compatibility with the existing clipping pipeline.
             usvg::Node::Rectangle(ref rect) => {
                 if !rect.is_visible() {
                     continue;
                 }
                 if let Some(path) = crate::path::rect_to_path(rect) {
-                    let ctx = Context {
-                        max_bbox: tiny_skia::IntRect::from_xywh(0, 0, 1, 1).unwrap(),
-                    };
-                    crate::path::fill_path(&path, mode, &ctx, transform, pixmap);
+                    crate::path::fill_path(&path, mode, ctx, transform, pixmap);
                 }
             }
             usvg::Node::Ellipse(ref ellipse) => {
@@ -65,10 +61,7 @@ fn draw_children(
                     continue;
                 }
                 if let Some(path) = crate::path::ellipse_to_path(ellipse) {
-                    let ctx = Context {
-                        max_bbox: tiny_skia::IntRect::from_xywh(0, 0, 1, 1).unwrap(),
-                    };
-                    crate::path::fill_path(&path, mode, &ctx, transform, pixmap);
+                    crate::path::fill_path(&path, mode, ctx, transform, pixmap);
                 }
             }
             usvg::Node::Polygon(ref polygon) => {
@@ -76,14 +69,27 @@ fn draw_children(
                     continue;
                 }
                 if let Some(path) = crate::path::polygon_to_path(polygon) {
-                    let ctx = Context {
-                        max_bbox: tiny_skia::IntRect::from_xywh(0, 0, 1, 1).unwrap(),
-                    };
-                    crate::path::fill_path(&path, mode, &ctx, transform, pixmap);
+                    crate::path::fill_path(&path, mode, ctx, transform, pixmap);
+                }
+            }
+            usvg::Node::Circle(ref circle) => {
+                if !circle.is_visible() {
+                    continue;
+                }
+                if let Some(path) = crate::path::circle_to_path(circle) {
+                    crate::path::fill_path(&path, mode, ctx, transform, pixmap);
+                }
+            }
+            usvg::Node::Polyline(ref polyline) => {
+                if !polyline.is_visible() {
+                    continue;
+                }
+                if let Some(path) = crate::path::polyline_to_path(polyline) {
+                    crate::path::fill_path(&path, mode, ctx, transform, pixmap);
                 }
             }
             usvg::Node::Text(ref text) => {
-                draw_children(text.flattened(), mode, transform, pixmap);
+                draw_children(text.flattened(), mode, ctx, transform, pixmap);
             }
             usvg::Node::Group(ref group) => {
                 let transform = transform.pre_concat(group.transform());
@@ -92,9 +98,9 @@ fn draw_children(
                     // If a `clipPath` child also has a `clip-path`
                     // then we should render this child on a new canvas,
                     // clip it, and only then draw it to the `clipPath`.
-                    clip_group(group, clip, transform, pixmap);
+                    clip_group(group, clip, ctx, transform, pixmap);
                 } else {
-                    draw_children(group, mode, transform, pixmap);
+                    draw_children(group, mode, ctx, transform, pixmap);
                 }
             }
             _ => {}
@@ -105,6 +111,7 @@ fn draw_children(
 fn clip_group(
     children: &usvg::Group,
     clip: &usvg::ClipPath,
+    ctx: &Context,
     transform: tiny_skia::Transform,
     pixmap: &mut tiny_skia::PixmapMut,
 ) -> Option<()> {
@@ -113,10 +120,11 @@ fn clip_group(
     draw_children(
         children,
         tiny_skia::BlendMode::SourceOver,
+        ctx,
         transform,
         &mut clip_pixmap.as_mut(),
     );
-    apply(clip, transform, &mut clip_pixmap);
+    apply(clip, ctx, transform, &mut clip_pixmap);
 
     let mut paint = tiny_skia::PixmapPaint::default();
     paint.blend_mode = tiny_skia::BlendMode::Xor;
@@ -33,6 +33,18 @@ pub fn apply(sigma_x: f64, sigma_y: f64, mut src: ImageRefMut) {
     }
 }
 
+/// Returns how far the blur spreads pixels.
+pub fn margin(sigma_x: f64, sigma_y: f64) -> u32 {
+    let radii = |sigma: f64| -> i32 {
+        create_box_gauss(sigma as f32)
+            .iter()
+            .map(|size| (size - 1) / 2)
+            .sum()
+    };
+
+    radii(sigma_x).max(radii(sigma_y)) as u32
+}
+
 #[inline(never)]
 fn create_box_gauss(sigma: f32) -> [i32; STEPS] {
     if sigma > 0.0 {
@@ -92,118 +104,48 @@ fn box_blur_vert(blur_radius: usize, backbuf: &ImageRefMut, frontbuf: &mut Image
     let height = backbuf.height as usize;
 
     let iarr = 1.0 / (blur_radius + blur_radius + 1) as f32;
-    let blur_radius_prev = blur_radius as isize - height as isize;
-    let blur_radius_next = blur_radius as isize + 1;
-
-    for i in 0..width {
-        let col_start = i; //inclusive
-        let col_end = i + width * (height - 1); //inclusive
-        let mut ti = i;
-        let mut li = ti;
-        let mut ri = ti + blur_radius * width;
-
-        let fv = RGBA8::default();
-        let lv = RGBA8::default();
-
-        let mut val_r = blur_radius_next * (fv.r as isize);
-        let mut val_g = blur_radius_next * (fv.g as isize);
-        let mut val_b = blur_radius_next * (fv.b as isize);
-        let mut val_a = blur_radius_next * (fv.a as isize);
-
-        // Get the pixel at the specified index, or the first pixel of the column
-        // if the index is beyond the top edge of the image
-        let get_top = |i| {
-            if i < col_start {
-                fv
-            } else {
-                backbuf.data[i]
-            }
-        };
-
-        // Get the pixel at the specified index, or the last pixel of the column
-        // if the index is beyond the bottom edge of the image
-        let get_bottom = |i| {
-            if i > col_end {
-                lv
-            } else {
-                backbuf.data[i]
+    let src = &*backbuf.data;
+
+    // Columns are processed row by row, so the image can be split into chunks of rows.
+    // Pixels beyond the top and bottom edges of the image are transparent.
+    crate::parallel::for_each_rows(frontbuf.data, width, |first_row, rows| {
+        // The sum of each column inside a box centered at the current row.
+        let mut sums = vec![[0isize; 4]; width];
+        let top = first_row.saturating_sub(blur_radius);
+        let bottom = cmp::min(first_row + blur_radius + 1, height);
+        for y in top..bottom {
+            for (sum, bb) in sums.iter_mut().zip(&src[y * width..(y + 1) * width]) {
+                add(sum, *bb);
             }
-        };
-
-        for j in 0..cmp::min(blur_radius, height) {
-            let bb = backbuf.data[ti + j * width];
-            val_r += bb.r as isize;
-            val_g += bb.g as isize;
-            val_b += bb.b as isize;
-            val_a += bb.a as isize;
-        }
-        if blur_radius > height {
-            val_r += blur_radius_prev * (lv.r as isize);
-            val_g += blur_radius_prev * (lv.g as isize);
-            val_b += blur_radius_prev * (lv.b as isize);
-            val_a += blur_radius_prev * (lv.a as isize);
-        }
-
-        for _ in 0..cmp::min(height, blur_radius + 1) {
-            let bb = get_bottom(ri);
-            ri += width;
-            val_r += sub(bb.r, fv.r);
-            val_g += sub(bb.g, fv.g);
-            val_b += sub(bb.b, fv.b);
-            val_a += sub(bb.a, fv.a);
-
-            frontbuf.data[ti] = RGBA8 {
-                r: round(val_r as f32 * iarr) as u8,
-                g: round(val_g as f32 * iarr) as u8,
-                b: round(val_b as f32 * iarr) as u8,
-                a: round(val_a as f32 * iarr) as u8,
-            };
-            ti += width;
         }
 
-        if height <= blur_radius {
-            // otherwise `(height - blur_radius)` will underflow
-            continue;
-        }
+        for (i, row) in rows.chunks_mut(width).enumerate() {
+            let y = first_row + i;
+            for (pixel, sum) in row.iter_mut().zip(sums.iter()) {
+                *pixel = RGBA8 {
+                    r: round(sum[0] as f32 * iarr) as u8,
+                    g: round(sum[1] as f32 * iarr) as u8,
+                    b: round(sum[2] as f32 * iarr) as u8,
+                    a: round(sum[3] as f32 * iarr) as u8,
+                };
+            }
 
-        for _ in (blur_radius + 1)..(height - blur_radius) {
-            let bb1 = backbuf.data[ri];
-            ri += width;
-            let bb2 = backbuf.data[li];
-            li += width;
-
-            val_r += sub(bb1.r, bb2.r);
-            val_g += sub(bb1.g, bb2.g);
-            val_b += sub(bb1.b, bb2.b);
-            val_a += sub(bb1.a, bb2.a);
-
-            frontbuf.data[ti] = RGBA8 {
-                r: round(val_r as f32 * iarr) as u8,
-                g: round(val_g as f32 * iarr) as u8,
-                b: round(val_b as f32 * iarr) as u8,
-                a: round(val_a as f32 * iarr) as u8,
-            };
-            ti += width;
-        }
+            // Move the box one row down.
+            let next = y + blur_radius + 1;
+            if next < height {
+                for (sum, bb) in sums.iter_mut().zip(&src[next * width..(next + 1) * width]) {
+                    add(sum, *bb);
+                }
+            }
 
-        for _ in 0..cmp::min(height - blur_radius - 1, blur_radius) {
-            let bb = get_top(li);
-            li += width;
-
-            val_r += sub(lv.r, bb.r);
-            val_g += sub(lv.g, bb.g);
-            val_b += sub(lv.b, bb.b);
-            val_a += sub(lv.a, bb.a);
-
-            frontbuf.data[ti] = RGBA8 {
-                r: round(val_r as f32 * iarr) as u8,
-                g: round(val_g as f32 * iarr) as u8,
-                b: round(val_b as f32 * iarr) as u8,
-                a: round(val_a as f32 * iarr) as u8,
-            };
-            ti += width;
+            if y >= blur_radius {
+                let prev = y - blur_radius;
+                for (sum, bb) in sums.iter_mut().zip(&src[prev * width..(prev + 1) * width]) {
+                    remove(sum, *bb);
+                }
+            }
         }
-    }
+    });
 }
 
 #[inline]
@@ -214,124 +156,135 @@ fn box_blur_horz(blur_radius: usize, backbuf: &ImageRefMut, frontbuf: &mut Image
     }
 
     let width = backbuf.width as usize;
-    let height = backbuf.height as usize;
+    let src = &*backbuf.data;
+
+    // Rows are independent.
+    crate::parallel::for_each_rows(frontbuf.data, width, |first_row, rows| {
+        for (i, row) in rows.chunks_mut(width).enumerate() {
+            let start = (first_row + i) * width;
+            box_blur_row(blur_radius, &src[start..start + width], row);
+        }
+    });
+}
+
+#[inline]
+fn box_blur_row(blur_radius: usize, backbuf: &[RGBA8], frontbuf: &mut [RGBA8]) {
+    let width = backbuf.len();
 
     let iarr = 1.0 / (blur_radius + blur_radius + 1) as f32;
     let blur_radius_prev = blur_radius as isize - width as isize;
     let blur_radius_next = blur_radius as isize + 1;
 
-    for i in 0..height {
-        let row_start = i * width; // inclusive
-        let row_end = (i + 1) * width - 1; // inclusive
-        let mut ti = i * width; // VERTICAL: $i;
-        let mut li = ti;
-        let mut ri = ti + blur_radius;
-
-        let fv = RGBA8::default();
-        let lv = RGBA8::default();
-
-        let mut val_r = blur_radius_next * (fv.r as isize);
-        let mut val_g = blur_radius_next * (fv.g as isize);
-        let mut val_b = blur_radius_next * (fv.b as isize);
-        let mut val_a = blur_radius_next * (fv.a as isize);
-
-        // Get the pixel at the specified index, or the first pixel of the row
-        // if the index is beyond the left edge of the image
-        let get_left = |i| {
-            if i < row_start {
-                fv
-            } else {
-                backbuf.data[i]
-            }
-        };
-
-        // Get the pixel at the specified index, or the last pixel of the row
-        // if the index is beyond the right edge of the image
-        let get_right = |i| {
-            if i > row_end {
-                lv
-            } else {
-                backbuf.data[i]
-            }
-        };
-
-        for j in 0..cmp::min(blur_radius, width) {
-            let bb = backbuf.data[ti + j]; // VERTICAL: ti + j * width
-            val_r += bb.r as isize;
-            val_g += bb.g as isize;
-            val_b += bb.b as isize;
-            val_a += bb.a as isize;
+    let row_start = 0; // inclusive
+    let row_end = width - 1; // inclusive
+    let mut ti = 0;
+    let mut li = ti;
+    let mut ri = ti + blur_radius;
+
+    let fv = RGBA8::default();
+    let lv = RGBA8::default();
+
+    let mut val_r = blur_radius_next * (fv.r as isize);
+    let mut val_g = blur_radius_next * (fv.g as isize);
+    let mut val_b = blur_radius_next * (fv.b as isize);
+    let mut val_a = blur_radius_next * (fv.a as isize);
+
+    // Get the pixel at the specified index, or the first pixel of the row
+    // if the index is beyond the left edge of the image
+    let get_left = |i| {
+        if i < row_start {
+            fv
+        } else {
+            backbuf[i]
         }
-        if blur_radius > width {
-            val_r += blur_radius_prev * (lv.r as isize);
-            val_g += blur_radius_prev * (lv.g as isize);
-            val_b += blur_radius_prev * (lv.b as isize);
-            val_a += blur_radius_prev * (lv.a as isize);
+    };
+
+    // Get the pixel at the specified index, or the last pixel of the row
+    // if the index is beyond the right edge of the image
+    let get_right = |i| {
+        if i > row_end {
+            lv
+        } else {
+            backbuf[i]
         }
+    };
+
+    for j in 0..cmp::min(blur_radius, width) {
+        let bb = backbuf[ti + j];
+        val_r += bb.r as isize;
+        val_g += bb.g as isize;
+        val_b += bb.b as isize;
+        val_a += bb.a as isize;
+    }
+    if blur_radius > width {
+        val_r += blur_radius_prev * (lv.r as isize);
+        val_g += blur_radius_prev * (lv.g as isize);
+        val_b += blur_radius_prev * (lv.b as isize);
+        val_a += blur_radius_prev * (lv.a as isize);
+    }
 
-        // Process the left side where we need pixels from beyond the left edge
-        for _ in 0..cmp::min(width, blur_radius + 1) {
-            let bb = get_right(ri);
-            ri += 1;
-            val_r += sub(bb.r, fv.r);
-            val_g += sub(bb.g, fv.g);
-            val_b += sub(bb.b, fv.b);
-            val_a += sub(bb.a, fv.a);
-
-            frontbuf.data[ti] = RGBA8 {
-                r: round(val_r as f32 * iarr) as u8,
-                g: round(val_g as f32 * iarr) as u8,
-                b: round(val_b as f32 * iarr) as u8,
-                a: round(val_a as f32 * iarr) as u8,
-            };
-            ti += 1; // VERTICAL : ti += width, same with the other areas
-        }
+    // Process the left side where we need pixels from beyond the left edge
+    for _ in 0..cmp::min(width, blur_radius + 1) {
+        let bb = get_right(ri);
+        ri += 1;
+        val_r += sub(bb.r, fv.r);
+        val_g += sub(bb.g, fv.g);
+        val_b += sub(bb.b, fv.b);
+        val_a += sub(bb.a, fv.a);
+
+        frontbuf[ti] = RGBA8 {
+            r: round(val_r as f32 * iarr) as u8,
+            g: round(val_g as f32 * iarr) as u8,
+            b: round(val_b as f32 * iarr) as u8,
+            a: round(val_a as f32 * iarr) as u8,
+        };
+        ti += 1;
+    }
 
-        if width <= blur_radius {
-            // otherwise `(width - blur_radius)` will underflow
-            continue;
-        }
+    if width <= blur_radius {
+        // otherwise `(width - blur_radius)` will underflow
+        return;
+    }
 
-        // Process the middle where we know we won't bump into borders
-        // without the extra indirection of get_left/get_right. This is faster.
-        for _ in (blur_radius + 1)..(width - blur_radius) {
-            let bb1 = backbuf.data[ri];
-            ri += 1;
-            let bb2 = backbuf.data[li];
-            li += 1;
-
-            val_r += sub(bb1.r, bb2.r);
-            val_g += sub(bb1.g, bb2.g);
-            val_b += sub(bb1.b, bb2.b);
-            val_a += sub(bb1.a, bb2.a);
-
-            frontbuf.data[ti] = RGBA8 {
-                r: round(val_r as f32 * iarr) as u8,
-                g: round(val_g as f32 * iarr) as u8,
-                b: round(val_b as f32 * iarr) as u8,
-                a: round(val_a as f32 * iarr) as u8,
-            };
-            ti += 1;
-        }
+    // Process the middle where we know we won't bump into borders
+    // without the extra indirection of get_left/get_right. This is faster.
+    for _ in (blur_radius + 1)..(width - blur_radius) {
+        let bb1 = backbuf[ri];
+        ri += 1;
+        let bb2 = backbuf[li];
+        li += 1;
+
+        val_r += sub(bb1.r, bb2.r);
+        val_g += sub(bb1.g, bb2.g);
+        val_b += sub(bb1.b, bb2.b);
+        val_a += sub(bb1.a, bb2.a);
+
+        frontbuf[ti] = RGBA8 {
+            r: round(val_r as f32 * iarr) as u8,
+            g: round(val_g as f32 * iarr) as u8,
+            b: round(val_b as f32 * iarr) as u8,
+            a: round(val_a as f32 * iarr) as u8,
+        };
+        ti += 1;
+    }
 
-        // Process the right side where we need pixels from beyond the right edge
-        for _ in 0..cmp::min(width - blur_radius - 1, blur_radius) {
-            let bb = get_left(li);
-            li += 1;
-
-            val_r += sub(lv.r, bb.r);
-            val_g += sub(lv.g, bb.g);
-            val_b += sub(lv.b, bb.b);
-            val_a += sub(lv.a, bb.a);
-
-            frontbuf.data[ti] = RGBA8 {
-                r: round(val_r as f32 * iarr) as u8,
-                g: round(val_g as f32 * iarr) as u8,
-                b: round(val_b as f32 * iarr) as u8,
-                a: round(val_a as f32 * iarr) as u8,
-            };
-            ti += 1;
-        }
+    // Process the right side where we need pixels from beyond the right edge
+    for _ in 0..cmp::min(width - blur_radius - 1, blur_radius) {
+        let bb = get_left(li);
+        li += 1;
+
+        val_r += sub(lv.r, bb.r);
+        val_g += sub(lv.g, bb.g);
+        val_b += sub(lv.b, bb.b);
+        val_a += sub(lv.a, bb.a);
+
+        frontbuf[ti] = RGBA8 {
+            r: round(val_r as f32 * iarr) as u8,
+            g: round(val_g as f32 * iarr) as u8,
+            b: round(val_b as f32 * iarr) as u8,
+            a: round(val_a as f32 * iarr) as u8,
+        };
+        ti += 1;
     }
 }
 
@@ -350,3 +303,19 @@ fn round(mut x: f32) -> f32 {
 fn sub(c1: u8, c2: u8) -> isize {
     c1 as isize - c2 as isize
 }
+
+#[inline]
+fn add(sum: &mut [isize; 4], c: RGBA8) {
+    sum[0] += c.r as isize;
+    sum[1] += c.g as isize;
+    sum[2] += c.b as isize;
+    sum[3] += c.a as isize;
+}
+
+#[inline]
+fn remove(sum: &mut [isize; 4], c: RGBA8) {
+    sum[0] -= c.r as isize;
+    sum[1] -= c.g as isize;
+    sum[2] -= c.b as isize;
+    sum[3] -= c.a as isize;
+}
@@ -45,11 +45,8 @@ struct BlurData {
 /// # Allocations
 ///
 /// This method will allocate a 2x `src` buffer.
+/// With the `parallel` feature, all channels can be blurred at once, requiring an 8x buffer.
 pub fn apply(sigma_x: f64, sigma_y: f64, src: ImageRefMut) {
-    let buf_size = (src.width * src.height) as usize;
-    let mut buf = vec![0.0; buf_size];
-    let buf = &mut buf;
-
     let d = BlurData {
         width: src.width as usize,
         height: src.height as usize,
@@ -59,6 +56,30 @@ pub fn apply(sigma_x: f64, sigma_y: f64, src: ImageRefMut) {
     };
 
     let data = src.data.as_mut_slice();
+
+    // Channels are independent.
+    #[cfg(feature = "parallel")]
+    {
+        if crate::parallel::threads_count() > 1 {
+            let bufs = crate::parallel::map(&[0, 1, 2, 3], |channel| {
+                let mut buf = vec![0.0; data.len() / 4];
+                load_channel(data, *channel, &mut buf);
+                gaussianiir2d(&d, &mut buf);
+                buf
+            });
+
+            for (channel, buf) in bufs.iter().enumerate() {
+                store_channel(buf, channel, data);
+            }
+
+            return;
+        }
+    }
+
+    let buf_size = d.width * d.height;
+    let mut buf = vec![0.0; buf_size];
+    let buf = &mut buf;
+
     gaussian_channel(data, &d, 0, buf);
     gaussian_channel(data, &d, 1, buf);
     gaussian_channel(data, &d, 2, buf);
@@ -66,12 +87,18 @@ pub fn apply(sigma_x: f64, sigma_y: f64, src: ImageRefMut) {
 }
 
 fn gaussian_channel(data: &mut [u8], d: &BlurData, channel: usize, buf: &mut [f64]) {
+    load_channel(data, channel, buf);
+    gaussianiir2d(d, buf);
+    store_channel(buf, channel, data);
+}
+
+fn load_channel(data: &[u8], channel: usize, buf: &mut [f64]) {
     for i in 0..data.len() / 4 {
         buf[i] = data[i * 4 + channel] as f64 / 255.0;
     }
+}
 
-    gaussianiir2d(d, buf);
-
+fn store_channel(buf: &[f64], channel: usize, data: &mut [u8]) {
     for i in 0..data.len() / 4 {
         data[i * 4 + channel] = (buf[i] * 255.0) as u8;
     }
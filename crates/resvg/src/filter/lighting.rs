@@ -232,20 +232,19 @@ fn apply(
     light_source: LightSource,
     surface_scale: f32,
     lighting_color: Color,
-    light_factor: &dyn Fn(Normal, Vector3) -> f32,
+    light_factor: &(dyn Fn(Normal, Vector3) -> f32 + Sync),
     calc_alpha: fn(u8, u8, u8) -> u8,
     src: ImageRef,
-    mut dest: ImageRefMut,
+    dest: ImageRefMut,
 ) {
     if src.width < 3 || src.height < 3 {
         return;
     }
 
     let width = src.width;
-    let height = src.height;
 
     // `feDistantLight` has a fixed vector, so calculate it beforehand.
-    let mut light_vector = match light_source {
+    let distant_light_vector = match light_source {
         LightSource::DistantLight(light) => {
             let azimuth = light.azimuth.to_radians();
             let elevation = light.elevation.to_radians();
@@ -258,25 +257,25 @@ fn apply(
         _ => Vector3::new(1.0, 1.0, 1.0),
     };
 
-    let mut calc = |nx, ny, normal: Normal| {
-        match light_source {
-            LightSource::DistantLight(_) => {}
+    let calc = |nx, ny| {
+        let light_vector = match light_source {
+            LightSource::DistantLight(_) => distant_light_vector,
             LightSource::PointLight(ref light) => {
                 let nz = src.alpha_at(nx, ny) as f32 / 255.0 * surface_scale;
                 let origin = Vector3::new(light.x, light.y, light.z);
                 let v = origin - Vector3::new(nx as f32, ny as f32, nz);
-                light_vector = v.normalized().unwrap_or(v);
+                v.normalized().unwrap_or(v)
             }
             LightSource::SpotLight(ref light) => {
                 let nz = src.alpha_at(nx, ny) as f32 / 255.0 * surface_scale;
                 let origin = Vector3::new(light.x, light.y, light.z);
                 let v = origin - Vector3::new(nx as f32, ny as f32, nz);
-                light_vector = v.normalized().unwrap_or(v);
+                v.normalized().unwrap_or(v)
             }
-        }
+        };
 
         let light_color = light_color(&light_source, lighting_color, light_vector);
-        let factor = light_factor(normal, light_vector);
+        let factor = light_factor(normal_at(src, nx, ny), light_vector);
 
         let compute = |x| (f32_bound(0.0, x as f32 * factor, 255.0) + 0.5) as u8;
 
@@ -285,28 +284,34 @@ fn apply(
         let b = compute(light_color.blue);
         let a = calc_alpha(r, g, b);
 
-        *dest.pixel_at_mut(nx, ny) = RGBA8 { b, g, r, a };
+        RGBA8 { b, g, r, a }
     };
 
-    calc(0, 0, top_left_normal(src));
-    calc(width - 1, 0, top_right_normal(src));
-    calc(0, height - 1, bottom_left_normal(src));
-    calc(width - 1, height - 1, bottom_right_normal(src));
-
-    for x in 1..width - 1 {
-        calc(x, 0, top_row_normal(src, x));
-        calc(x, height - 1, bottom_row_normal(src, x));
-    }
-
-    for y in 1..height - 1 {
-        calc(0, y, left_column_normal(src, y));
-        calc(width - 1, y, right_column_normal(src, y));
-    }
-
-    for y in 1..height - 1 {
-        for x in 1..width - 1 {
-            calc(x, y, interior_normal(src, x, y));
+    // Each pixel is independent.
+    crate::parallel::for_each_rows(dest.data, width as usize, |first_row, rows| {
+        for (y, row) in rows.chunks_mut(width as usize).enumerate() {
+            let y = first_row as u32 + y as u32;
+            for (x, pixel) in row.iter_mut().enumerate() {
+                *pixel = calc(x as u32, y);
+            }
         }
+    });
+}
+
+/// Calculates a surface normal depending on the pixel position.
+fn normal_at(img: ImageRef, x: u32, y: u32) -> Normal {
+    let last_x = img.width - 1;
+    let last_y = img.height - 1;
+    match (x, y) {
+        (0, 0) => top_left_normal(img),
+        (x, 0) if x == last_x => top_right_normal(img),
+        (0, y) if y == last_y => bottom_left_normal(img),
+        (x, y) if x == last_x && y == last_y => bottom_right_normal(img),
+        (x, 0) => top_row_normal(img, x),
+        (x, y) if y == last_y => bottom_row_normal(img, x),
+        (0, y) => left_column_normal(img, y),
+        (x, y) if x == last_x => right_column_normal(img, y),
+        (x, y) => interior_normal(img, x, y),
     }
 }
 
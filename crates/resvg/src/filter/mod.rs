@@ -87,6 +87,7 @@ impl<'a> ImageRefMut<'a> {
 pub(crate) enum Error {
     InvalidRegion,
     NoResults,
+    Cancelled,
 }
 
 trait PixmapExt: Sized {
@@ -334,10 +335,11 @@ struct FilterResult {
 
 pub fn apply(
     filter: &usvg::filter::Filter,
+    ctx: &crate::render::Context,
     ts: tiny_skia::Transform,
     source: &mut tiny_skia::Pixmap,
 ) {
-    let result = apply_inner(filter, ts, source);
+    let result = apply_inner(filter, ctx, ts, source);
     let result = result.and_then(|image| apply_to_canvas(image, source));
 
     // Clear on error.
@@ -351,23 +353,110 @@ pub fn apply(
             log::warn!("Filter has an invalid region.");
         }
         Err(Error::NoResults) => {}
+        // The render will be reported as cancelled by the caller.
+        Err(Error::Cancelled) => {}
+    }
+}
+
+/// Returns how many pixels around a layer part are required to filter it.
+///
+/// `layer` is the whole layer rect. Returns `None` when the whole layer is required.
+pub fn margin(
+    filters: &[std::sync::Arc<usvg::filter::Filter>],
+    ts: usvg::Transform,
+    layer: IntRect,
+) -> Option<u32> {
+    let mut margin = 0;
+    for filter in filters {
+        // The source is placed at the filter region origin,
+        // so only filters that cover the whole layer can be cropped.
+        if filter.rect().transform(ts)?.to_int_rect() != layer {
+            return None;
+        }
+
+        for primitive in filter.primitives() {
+            margin += primitive_margin(primitive.kind(), ts)?;
+        }
+    }
+
+    Some(margin)
+}
+
+fn primitive_margin(kind: &usvg::filter::Kind, ts: usvg::Transform) -> Option<u32> {
+    let offset_margin = |dx: f32, dy: f32| {
+        let (dx, dy) = scale_coordinates(dx, dy, ts)?;
+        // `draw_pixmap` shifts pixmaps drawn at a negative position by one more pixel.
+        Some(dx.abs().max(dy.abs()).ceil() as u32 + 1)
+    };
+
+    let blur_margin = |std_dx: f32, std_dy: f32| {
+        match resolve_std_dev(std_dx, std_dy, ts) {
+            Some((std_dx, std_dy, true)) => Some(box_blur::margin(std_dx, std_dy)),
+            // An IIR blur affects the whole image.
+            Some((_, _, false)) => None,
+            None => Some(0),
+        }
+    };
+
+    match kind {
+        usvg::filter::Kind::GaussianBlur(ref fe) => {
+            blur_margin(fe.std_dev_x().get(), fe.std_dev_y().get())
+        }
+        usvg::filter::Kind::DropShadow(ref fe) => {
+            let blur = blur_margin(fe.std_dev_x().get(), fe.std_dev_y().get())?;
+            Some(blur + offset_margin(fe.dx(), fe.dy())?)
+        }
+        usvg::filter::Kind::Offset(ref fe) => offset_margin(fe.dx(), fe.dy()),
+        usvg::filter::Kind::Morphology(ref fe) => {
+            let (rx, ry) = scale_coordinates(fe.radius_x().get(), fe.radius_y().get(), ts)?;
+            // Twice the radius, so the matrix is never clamped by the image size differently.
+            Some(rx.max(ry).max(0.0).ceil() as u32 * 2)
+        }
+        usvg::filter::Kind::ConvolveMatrix(ref fe) => match fe.edge_mode() {
+            usvg::filter::EdgeMode::Wrap => None,
+            _ => Some(fe.matrix().columns().max(fe.matrix().rows())),
+        },
+        usvg::filter::Kind::Blend(..)
+        | usvg::filter::Kind::Composite(..)
+        | usvg::filter::Kind::Merge(..)
+        | usvg::filter::Kind::Flood(..)
+        | usvg::filter::Kind::ComponentTransfer(..)
+        | usvg::filter::Kind::ColorMatrix(..)
+        | usvg::filter::Kind::Turbulence(..) => Some(0),
+        // Lighting and displacement use positions relative to the filter region,
+        // which are not preserved exactly after cropping.
+        usvg::filter::Kind::DiffuseLighting(..)
+        | usvg::filter::Kind::SpecularLighting(..)
+        | usvg::filter::Kind::DisplacementMap(..)
+        | usvg::filter::Kind::Tile(..)
+        | usvg::filter::Kind::Image(..) => None,
     }
 }
 
 fn apply_inner(
     filter: &usvg::filter::Filter,
+    ctx: &crate::render::Context,
     ts: usvg::Transform,
     source: &mut tiny_skia::Pixmap,
 ) -> Result<Image, Error> {
-    let region = filter
-        .rect()
-        .transform(ts)
-        .map(|r| r.to_int_rect())
-        .ok_or(Error::InvalidRegion)?;
+    let region = match ctx.region {
+        // The source stores only a part of the layer, see `filter::margin`.
+        Some(ref region) => region.rect,
+        None => filter
+            .rect()
+            .transform(ts)
+            .map(|r| r.to_int_rect())
+            .ok_or(Error::InvalidRegion)?,
+    };
 
     let mut results: Vec<FilterResult> = Vec::new();
 
     for primitive in filter.primitives() {
+        // Each primitive can take a while, so check between them.
+        if ctx.is_cancelled() {
+            return Err(Error::Cancelled);
+        }
+
         let mut subregion = primitive
             .rect()
             .transform(ts)
@@ -414,7 +503,7 @@ fn apply_inner(
                 let input = get_input(fe.input(), region, source, &results)?;
                 apply_tile(input, region)
             }
-            usvg::filter::Kind::Image(ref fe) => apply_image(fe, region, subregion, ts),
+            usvg::filter::Kind::Image(ref fe) => apply_image(fe, ctx, region, subregion, ts),
             usvg::filter::Kind::ComponentTransfer(ref fe) => {
                 let input = get_input(fe.input(), region, source, &results)?;
                 apply_component_transfer(fe, cs, input)
@@ -853,6 +942,7 @@ fn apply_tile(input: Image, region: IntRect) -> Result<Image, Error> {
 
 fn apply_image(
     fe: &usvg::filter::Image,
+    ctx: &crate::render::Context,
     region: IntRect,
     subregion: IntRect,
     ts: usvg::Transform,
@@ -871,6 +961,8 @@ fn apply_image(
 
     let ctx = crate::render::Context {
         max_bbox: tiny_skia::IntRect::from_xywh(0, 0, region.width(), region.height()).unwrap(),
+        region: None,
+        ..ctx.detached()
     };
 
     crate::render::render_nodes(fe.root(), &ctx, transform, &mut pixmap.as_mut());
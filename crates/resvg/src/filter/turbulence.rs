@@ -46,48 +46,52 @@ pub fn apply(
     let (lattice_selector, gradient) = init(seed);
     let width = dest.width;
     let height = dest.height;
-    let mut x = 0;
-    let mut y = 0;
-    for pixel in dest.data.iter_mut() {
-        let turb = |channel| {
-            let (tx, ty) = ((x as f64 + offset_x) / sx, (y as f64 + offset_y) / sy);
-            let n = turbulence(
-                channel,
-                tx,
-                ty,
-                x as f64,
-                y as f64,
-                width as f64,
-                height as f64,
-                base_frequency_x,
-                base_frequency_y,
-                num_octaves,
-                fractal_noise,
-                stitch_tiles,
-                &lattice_selector,
-                &gradient,
-            );
-
-            let n = if fractal_noise {
-                (n * 255.0 + 255.0) / 2.0
-            } else {
-                n * 255.0
-            };
 
-            (f32_bound(0.0, n as f32, 255.0) + 0.5) as u8
-        };
+    // Each pixel is independent.
+    crate::parallel::for_each_rows(dest.data, width as usize, |first_row, rows| {
+        let mut x = 0;
+        let mut y = first_row as u32;
+        for pixel in rows.iter_mut() {
+            let turb = |channel| {
+                let (tx, ty) = ((x as f64 + offset_x) / sx, (y as f64 + offset_y) / sy);
+                let n = turbulence(
+                    channel,
+                    tx,
+                    ty,
+                    x as f64,
+                    y as f64,
+                    width as f64,
+                    height as f64,
+                    base_frequency_x,
+                    base_frequency_y,
+                    num_octaves,
+                    fractal_noise,
+                    stitch_tiles,
+                    &lattice_selector,
+                    &gradient,
+                );
+
+                let n = if fractal_noise {
+                    (n * 255.0 + 255.0) / 2.0
+                } else {
+                    n * 255.0
+                };
+
+                (f32_bound(0.0, n as f32, 255.0) + 0.5) as u8
+            };
 
-        pixel.r = turb(0);
-        pixel.g = turb(1);
-        pixel.b = turb(2);
-        pixel.a = turb(3);
+            pixel.r = turb(0);
+            pixel.g = turb(1);
+            pixel.b = turb(2);
+            pixel.a = turb(3);
 
-        x += 1;
-        if x == dest.width {
-            x = 0;
-            y += 1;
+            x += 1;
+            if x == width {
+                x = 0;
+                y += 1;
+            }
         }
-    }
+    });
 }
 
 fn init(mut seed: i32) -> (Vec<usize>, Vec<Vec<Vec<f64>>>) {
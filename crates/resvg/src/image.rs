@@ -3,6 +3,7 @@
 
 pub fn render(
     image: &usvg::Image,
+    ctx: &crate::render::Context,
     transform: tiny_skia::Transform,
     pixmap: &mut tiny_skia::PixmapMut,
 ) {
@@ -10,22 +11,23 @@ pub fn render(
         return;
     }
 
-    render_inner(image.kind(), transform, image.rendering_mode(), pixmap);
+    render_inner(image.kind(), ctx, transform, image.rendering_mode(), pixmap);
 }
 
 pub fn render_inner(
     image_kind: &usvg::ImageKind,
+    ctx: &crate::render::Context,
     transform: tiny_skia::Transform,
     #[allow(unused_variables)] rendering_mode: usvg::ImageRendering,
     pixmap: &mut tiny_skia::PixmapMut,
 ) {
     match image_kind {
         usvg::ImageKind::SVG(ref tree) => {
-            render_vector(tree, transform, pixmap);
+            render_vector(tree, ctx, transform, pixmap);
         }
         #[cfg(feature = "raster-images")]
         _ => {
-            raster_images::render_raster(image_kind, transform, rendering_mode, pixmap);
+            raster_images::render_raster(image_kind, ctx, transform, rendering_mode, pixmap);
         }
         #[cfg(not(feature = "raster-images"))]
         _ => {
@@ -36,11 +38,17 @@ pub fn render_inner(
 
 fn render_vector(
     tree: &usvg::Tree,
+    ctx: &crate::render::Context,
     transform: tiny_skia::Transform,
     pixmap: &mut tiny_skia::PixmapMut,
 ) -> Option<()> {
     let mut sub_pixmap = tiny_skia::Pixmap::new(pixmap.width(), pixmap.height()).unwrap();
-    crate::render(tree, transform, &mut sub_pixmap.as_mut());
+    crate::render::render_nodes(
+        tree.root(),
+        &ctx.detached(),
+        transform,
+        &mut sub_pixmap.as_mut(),
+    );
     pixmap.draw_pixmap(
         0,
         0,
@@ -53,13 +61,16 @@ fn render_vector(
     Some(())
 }
 
+#[cfg(all(feature = "raster-images", feature = "pdf"))]
+pub(crate) use raster_images::decode_raster;
+
 #[cfg(feature = "raster-images")]
 mod raster_images {
     use crate::OptionLog;
     use std::io::Cursor;
     use usvg::ImageRendering;
 
-    fn decode_raster(image: &usvg::ImageKind) -> Option<tiny_skia::Pixmap> {
+    pub(crate) fn decode_raster(image: &usvg::ImageKind) -> Option<tiny_skia::Pixmap> {
         match image {
             usvg::ImageKind::SVG(_) => None,
             usvg::ImageKind::JPEG(ref data) => {
@@ -172,6 +183,7 @@ mod raster_images {
 
     pub(crate) fn render_raster(
         image: &usvg::ImageKind,
+        ctx: &crate::render::Context,
         transform: tiny_skia::Transform,
         rendering_mode: usvg::ImageRendering,
         pixmap: &mut tiny_skia::PixmapMut,
@@ -200,7 +212,7 @@ mod raster_images {
         let mut paint = tiny_skia::Paint::default();
         paint.shader = pattern;
 
-        pixmap.fill_rect(rect, &paint, transform, None);
+        crate::region::fill_rect(pixmap, rect, &paint, transform, ctx.region);
 
         Some(())
     }
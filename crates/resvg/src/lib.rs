@@ -14,6 +14,9 @@
 #![allow(clippy::upper_case_acronyms)]
 #![allow(clippy::wrong_self_convention)]
 
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
 pub use tiny_skia;
 pub use usvg;
 
@@ -22,9 +25,85 @@ mod filter;
 mod geom;
 mod image;
 mod mask;
+mod parallel;
 mod path;
+#[cfg(feature = "pdf")]
+pub mod pdf;
+mod region;
 mod render;
 
+/// A list of rendering errors.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Error {
+    /// The rendering was cancelled via a [`CancellationToken`].
+    ///
+    /// The pixmap contains a partially rendered image and should be discarded.
+    Cancelled,
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            Error::Cancelled => {
+                write!(f, "rendering was cancelled")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// A token that can be used to cancel rendering from another thread.
+///
+/// Clones of a token share the same state.
+#[derive(Clone, Default, Debug)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Creates a new, not cancelled token.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests the rendering to stop.
+    ///
+    /// The rendering stops at the next node or filter primitive.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Checks that cancellation was requested.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Rendering options.
+#[derive(Clone, Default)]
+pub struct RenderOptions<'a> {
+    /// A token that can be used to cancel rendering.
+    ///
+    /// Default: None
+    pub cancellation_token: Option<CancellationToken>,
+
+    /// A callback that receives the rendering progress in a 0..=1 range.
+    ///
+    /// Called after each rendered node.
+    /// With the `parallel` build feature, can be called from multiple threads.
+    ///
+    /// Default: None
+    pub progress: Option<&'a (dyn Fn(f32) + Sync)>,
+}
+
+impl std::fmt::Debug for RenderOptions<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("RenderOptions")
+            .field("cancellation_token", &self.cancellation_token)
+            .field("progress", &self.progress.is_some())
+            .finish()
+    }
+}
+
 /// Renders a tree onto the pixmap.
 ///
 /// `transform` will be used as a root transform.
@@ -37,16 +116,34 @@ pub fn render(
     pixmap: &mut tiny_skia::PixmapMut,
 ) {
     let target_size = tiny_skia::IntSize::from_wh(pixmap.width(), pixmap.height()).unwrap();
-    let max_bbox = tiny_skia::IntRect::from_xywh(
-        -(target_size.width() as i32) * 2,
-        -(target_size.height() as i32) * 2,
-        target_size.width() * 5,
-        target_size.height() * 5,
-    )
-    .unwrap();
-
-    let ctx = render::Context { max_bbox };
+    let ctx = render::Context::new(target_size);
+    render::render_nodes(tree.root(), &ctx, transform, pixmap);
+}
+
+/// Renders a tree onto the pixmap, with cancellation and progress reporting.
+///
+/// Produces the same image as [`render`].
+///
+/// Returns [`Error::Cancelled`] when the rendering was cancelled
+/// via [`RenderOptions::cancellation_token`].
+pub fn render_with_options(
+    tree: &usvg::Tree,
+    transform: tiny_skia::Transform,
+    options: &RenderOptions,
+    pixmap: &mut tiny_skia::PixmapMut,
+) -> Result<(), Error> {
+    let target_size = tiny_skia::IntSize::from_wh(pixmap.width(), pixmap.height()).unwrap();
+    let control = render::Control::new(tree, options);
+    let mut ctx = render::Context::new(target_size);
+    ctx.control = Some(&control);
+    ctx.report_progress = true;
     render::render_nodes(tree.root(), &ctx, transform, pixmap);
+
+    if control.is_cancelled() {
+        return Err(Error::Cancelled);
+    }
+
+    Ok(())
 }
 
 /// Renders a node onto the pixmap.
@@ -67,22 +164,54 @@ pub fn render_node(
     let bbox = node.abs_layer_bounding_box()?;
 
     let target_size = tiny_skia::IntSize::from_wh(pixmap.width(), pixmap.height()).unwrap();
-    let max_bbox = tiny_skia::IntRect::from_xywh(
-        -(target_size.width() as i32) * 2,
-        -(target_size.height() as i32) * 2,
-        target_size.width() * 5,
-        target_size.height() * 5,
-    )
-    .unwrap();
 
     transform = transform.pre_translate(-bbox.x(), -bbox.y());
 
-    let ctx = render::Context { max_bbox };
+    let ctx = render::Context::new(target_size);
     render::render_node(node, &ctx, transform, pixmap);
 
     Some(())
 }
 
+/// Renders a region of a tree onto the pixmap.
+///
+/// Produces the same image as rendering the whole tree with [`render`]
+/// onto a pixmap of `canvas_size` and copying `region` out of it,
+/// while only requiring a pixmap of the region size.
+/// Can be used to render huge images tile by tile.
+///
+/// `transform` will be used as a root transform, just like in [`render`].
+///
+/// Groups with filters are rendered only around the region,
+/// unless a filter depends on the whole filter region, like `feTile`.
+///
+/// The produced content is in the sRGB color space.
+///
+/// # Panics
+///
+/// When the `pixmap` size is not equal to the `region` size.
+pub fn render_region(
+    tree: &usvg::Tree,
+    transform: tiny_skia::Transform,
+    canvas_size: tiny_skia::IntSize,
+    region: tiny_skia::IntRect,
+    pixmap: &mut tiny_skia::PixmapMut,
+) {
+    assert_eq!(
+        region.size(),
+        tiny_skia::IntSize::from_wh(pixmap.width(), pixmap.height()).unwrap(),
+        "pixmap size must be equal to the region size"
+    );
+
+    let mut ctx = render::Context::new(canvas_size);
+    ctx.region = Some(region::Region {
+        canvas: canvas_size,
+        rect: region,
+    });
+
+    render::render_nodes(tree.root(), &ctx, transform, pixmap);
+}
+
 pub(crate) trait OptionLog {
     fn log_none<F: FnOnce()>(self, f: F) -> Self;
 }
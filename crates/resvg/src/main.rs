@@ -91,6 +91,12 @@ fn process() -> Result<(), String> {
         });
     }
 
+    if let Some(frames) = args.frames {
+        return timed(args.perf, "Frames rendering", || {
+            render_frames(&mut args, &xml_tree, frames)
+        });
+    }
+
     let tree = timed(args.perf, "SVG Parsing", || {
         usvg::Tree::from_xmltree(&xml_tree, &args.usvg).map_err(|e| e.to_string())
     })?;
@@ -99,6 +105,12 @@ fn process() -> Result<(), String> {
         return query_all(&tree);
     }
 
+    if let Some(tile_size) = args.tile_size {
+        return timed(args.perf, "Tiled rendering", || {
+            render_tiles(&args, &tree, tile_size)
+        });
+    }
+
     // Render.
     let img = render_svg(&args, &tree)?;
 
@@ -205,6 +217,19 @@ OPTIONS:
 
   --export-area-drawing         Use drawing's tight bounding box instead of image size.
                                 Used during normal rendering and not during --export-id
+  --tile-size SIZE              Renders the image in SIZE x SIZE pixels tiles and
+                                streams them into the output PNG.
+                                Keeps memory usage low for very large images.
+                                Not used during --export-id
+  --export-tiles                Saves each tile into a separate PNG instead.
+                                Tiles are named <out-png>-<column>-<row>.png
+
+  --time SEC                    Samples SVG animations at the specified time
+                                in seconds. Animations are ignored by default
+  --frames COUNT                Renders COUNT animation frames starting at --time.
+                                Frames are named <out-png>-<index>.png
+  --fps FPS                     Sets the frame rate of --frames [default: 30]
+  --apng                        Saves frames into a single animated PNG instead
 
   --perf                        Prints performance stats
   --quiet                       Disables warnings
@@ -246,6 +271,13 @@ struct CliArgs {
     export_area_page: bool,
 
     export_area_drawing: bool,
+    tile_size: Option<u32>,
+    export_tiles: bool,
+
+    time: Option<f32>,
+    frames: Option<u32>,
+    fps: u32,
+    apng: bool,
 
     perf: bool,
     quiet: bool,
@@ -310,6 +342,13 @@ fn collect_args() -> Result<CliArgs, pico_args::Error> {
 
         export_area_drawing: input.contains("--export-area-drawing"),
         style_sheet: input.opt_value_from_str("--stylesheet").unwrap_or_default(),
+        tile_size: input.opt_value_from_fn("--tile-size", parse_length)?,
+        export_tiles: input.contains("--export-tiles"),
+
+        time: input.opt_value_from_fn("--time", parse_time)?,
+        frames: input.opt_value_from_fn("--frames", parse_length)?,
+        fps: input.opt_value_from_fn("--fps", parse_fps)?.unwrap_or(30),
+        apng: input.contains("--apng"),
 
         perf: input.contains("--perf"),
         quiet: input.contains("--quiet"),
@@ -339,6 +378,26 @@ fn parse_length(s: &str) -> Result<u32, String> {
     }
 }
 
+fn parse_time(s: &str) -> Result<f32, String> {
+    let n: f32 = s.parse().map_err(|_| "invalid time")?;
+
+    if n.is_finite() && n >= 0.0 {
+        Ok(n)
+    } else {
+        Err("time cannot be negative".to_string())
+    }
+}
+
+fn parse_fps(s: &str) -> Result<u32, String> {
+    let n: u32 = s.parse().map_err(|_| "invalid number")?;
+
+    if (1..=1000).contains(&n) {
+        Ok(n)
+    } else {
+        Err("FPS out of bounds".to_string())
+    }
+}
+
 fn parse_zoom(s: &str) -> Result<f32, String> {
     let n: f32 = s.parse().map_err(|_| "invalid zoom factor")?;
 
@@ -461,6 +520,11 @@ struct Args {
     export_id: Option<String>,
     export_area_page: bool,
     export_area_drawing: bool,
+    tile_size: Option<u32>,
+    export_tiles: bool,
+    frames: Option<u32>,
+    fps: u32,
+    apng: bool,
     perf: bool,
     quiet: bool,
     usvg: usvg::Options<'static>,
@@ -520,7 +584,41 @@ fn parse_args() -> Result<Args, String> {
         eprintln!("Warning: --export-area-drawing has no effect when --export-id is set.");
     }
 
+    if args.export_tiles && args.tile_size.is_none() {
+        return Err("--export-tiles requires --tile-size".to_string());
+    }
+
+    if args.export_tiles && out_png == Some(OutputTo::Stdout) {
+        return Err("--export-tiles cannot be used with -c".to_string());
+    }
+
+    if args.tile_size.is_some() && args.export_id.is_some() {
+        eprintln!("Warning: --tile-size has no effect when --export-id is set.");
+    }
+
+    if args.tile_size.is_some() && args.export_area_drawing {
+        eprintln!("Warning: --export-area-drawing has no effect when --tile-size is set.");
+    }
+
+    if args.apng && args.frames.is_none() {
+        return Err("--apng requires --frames".to_string());
+    }
+
+    if args.frames.is_some() && !args.apng && out_png == Some(OutputTo::Stdout) {
+        return Err("--frames cannot be used with -c without --apng".to_string());
+    }
+
+    if args.frames.is_some() && args.query_all {
+        return Err("--frames cannot be used with --query-all".to_string());
+    }
+
+    if args.frames.is_some() && args.tile_size.is_some() {
+        eprintln!("Warning: --tile-size has no effect when --frames is set.");
+    }
+
     let export_id = args.export_id.as_ref().map(|v| v.to_string());
+    // Tiles are rendered only for the whole image.
+    let tile_size = args.tile_size.filter(|_| export_id.is_none());
 
     let mut fit_to = FitTo::Original;
     let mut default_size = usvg::Size::from_wh(100.0, 100.0).unwrap();
@@ -578,6 +676,8 @@ fn parse_args() -> Result<Args, String> {
         font_resolver: usvg::FontResolver::default(),
         fontdb: Arc::new(fontdb::Database::new()),
         style_sheet,
+        limits: usvg::Limits::default(),
+        animation_time: args.time,
     };
 
     Ok(Args {
@@ -587,6 +687,11 @@ fn parse_args() -> Result<Args, String> {
         export_id,
         export_area_page: args.export_area_page,
         export_area_drawing: args.export_area_drawing,
+        tile_size,
+        export_tiles: args.export_tiles,
+        frames: args.frames,
+        fps: args.fps,
+        apng: args.apng,
         perf: args.perf,
         quiet: args.quiet,
         usvg,
@@ -756,6 +861,177 @@ fn render_svg(args: &Args, tree: &usvg::Tree) -> Result<tiny_skia::Pixmap, Strin
     Ok(img)
 }
 
+/// Renders an image tile by tile.
+///
+/// Tiles are either saved separately or streamed into a single PNG,
+/// so the whole image never has to be allocated.
+fn render_tiles(args: &Args, tree: &usvg::Tree, tile_size: u32) -> Result<(), String> {
+    let size = args
+        .fit_to
+        .fit_to_size(tree.size().to_int_size())
+        .ok_or_else(|| "target size is zero".to_string())?;
+
+    let ts = args.fit_to.fit_to_transform(tree.size().to_int_size());
+
+    let render_tile = |x: u32, y: u32| -> Result<tiny_skia::Pixmap, String> {
+        let width = tile_size.min(size.width() - x);
+        let height = tile_size.min(size.height() - y);
+        let mut pixmap = tiny_skia::Pixmap::new(width, height)
+            .ok_or_else(|| "failed to allocate a tile".to_string())?;
+
+        if let Some(background) = args.background {
+            pixmap.fill(svg_to_skia_color(background));
+        }
+
+        // Unwrap is safe, because the tile is inside the image.
+        let region = tiny_skia::IntRect::from_xywh(x as i32, y as i32, width, height).unwrap();
+        resvg::render_region(tree, ts, size, region, &mut pixmap.as_mut());
+        Ok(pixmap)
+    };
+
+    let out_png = args.out_png.as_ref().unwrap();
+
+    if args.export_tiles {
+        let path = match out_png {
+            OutputTo::File(ref path) => path,
+            OutputTo::Stdout => unreachable!(),
+        };
+
+        let stem = path.with_extension("");
+        for (row, y) in (0..size.height()).step_by(tile_size as usize).enumerate() {
+            for (column, x) in (0..size.width()).step_by(tile_size as usize).enumerate() {
+                let tile = render_tile(x, y)?;
+                let mut name = stem.clone().into_os_string();
+                name.push(format!("-{}-{}.png", column, row));
+                tile.save_png(name).map_err(|e| e.to_string())?;
+            }
+        }
+
+        return Ok(());
+    }
+
+    let writer: Box<dyn std::io::Write> = match out_png {
+        OutputTo::Stdout => Box::new(std::io::stdout().lock()),
+        OutputTo::File(ref path) => {
+            Box::new(std::fs::File::create(path).map_err(|_| "failed to create the output file")?)
+        }
+    };
+
+    let mut encoder =
+        png::Encoder::new(std::io::BufWriter::new(writer), size.width(), size.height());
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(|e| e.to_string())?;
+    let mut stream = writer.stream_writer().map_err(|e| e.to_string())?;
+
+    // Tiles are rendered a row at a time and then written line by line.
+    let mut line = vec![0; size.width() as usize * 4];
+    for y in (0..size.height()).step_by(tile_size as usize) {
+        let tiles = (0..size.width())
+            .step_by(tile_size as usize)
+            .map(|x| render_tile(x, y))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for tile_y in 0..tiles[0].height() {
+            let mut offset = 0;
+            for tile in &tiles {
+                let width = tile.width() as usize;
+                let start = tile_y as usize * width;
+                for pixel in &tile.pixels()[start..start + width] {
+                    let c = pixel.demultiply();
+                    line[offset..offset + 4].copy_from_slice(&[
+                        c.red(),
+                        c.green(),
+                        c.blue(),
+                        c.alpha(),
+                    ]);
+                    offset += 4;
+                }
+            }
+
+            std::io::Write::write_all(&mut stream, &line).map_err(|e| e.to_string())?;
+        }
+    }
+
+    stream.finish().map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Renders an animation frame by frame.
+///
+/// Frames are either saved separately or combined into a single animated PNG.
+fn render_frames(
+    args: &mut Args,
+    xml_tree: &usvg::roxmltree::Document,
+    frames: u32,
+) -> Result<(), String> {
+    let start = args.usvg.animation_time.unwrap_or(0.0);
+    let out_png = args
+        .out_png
+        .clone()
+        .ok_or_else(|| "<out-png> must be set".to_string())?;
+
+    let mut encoder = None;
+    for index in 0..frames {
+        args.usvg.animation_time = Some(start + index as f32 / args.fps as f32);
+        let tree = usvg::Tree::from_xmltree(xml_tree, &args.usvg).map_err(|e| e.to_string())?;
+        let img = render_svg(args, &tree)?;
+
+        if !args.apng {
+            let path = match out_png {
+                OutputTo::File(ref path) => path,
+                OutputTo::Stdout => unreachable!(),
+            };
+
+            let mut name = path.with_extension("").into_os_string();
+            name.push(format!("-{}.png", index));
+            img.save_png(name).map_err(|e| e.to_string())?;
+            continue;
+        }
+
+        if encoder.is_none() {
+            let writer: Box<dyn std::io::Write> = match out_png {
+                OutputTo::Stdout => Box::new(std::io::stdout().lock()),
+                OutputTo::File(ref path) => Box::new(
+                    std::fs::File::create(path).map_err(|_| "failed to create the output file")?,
+                ),
+            };
+
+            let mut apng =
+                png::Encoder::new(std::io::BufWriter::new(writer), img.width(), img.height());
+            apng.set_color(png::ColorType::Rgba);
+            apng.set_depth(png::BitDepth::Eight);
+            apng.set_animated(frames, 0).map_err(|e| e.to_string())?;
+            apng.set_frame_delay(1, args.fps as u16)
+                .map_err(|e| e.to_string())?;
+            encoder = Some((
+                apng.write_header().map_err(|e| e.to_string())?,
+                img.width(),
+                img.height(),
+            ));
+        }
+
+        // Unwrap is safe, because the encoder was created above.
+        let (writer, width, height) = encoder.as_mut().unwrap();
+        if img.width() != *width || img.height() != *height {
+            return Err("animation frames have different sizes".to_string());
+        }
+
+        let mut data = Vec::with_capacity(img.data().len());
+        for pixel in img.pixels() {
+            let c = pixel.demultiply();
+            data.extend_from_slice(&[c.red(), c.green(), c.blue(), c.alpha()]);
+        }
+        writer.write_image_data(&data).map_err(|e| e.to_string())?;
+    }
+
+    if let Some((writer, _, _)) = encoder {
+        writer.finish().map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
+
 fn trim_pixmap(
     tree: &usvg::Tree,
     transform: tiny_skia::Transform,
@@ -4,6 +4,66 @@
 use crate::render::Context;
 
 pub fn apply(
+    layers: &[usvg::MaskLayer],
+    ctx: &Context,
+    transform: tiny_skia::Transform,
+    pixmap: &mut tiny_skia::Pixmap,
+) {
+    if let [layer] = layers {
+        apply_mask(layer.mask(), ctx, transform, pixmap);
+        return;
+    }
+
+    let size = tiny_skia::IntSize::from_wh(pixmap.width(), pixmap.height()).unwrap();
+    if let Some(mask) = render_layers(layers, ctx, transform, size) {
+        pixmap.apply_mask(&mask);
+    }
+}
+
+/// Renders mask layers into a single mask.
+///
+/// Layers are composited starting from the bottommost one,
+/// using the compositing operator of the layer above.
+pub(crate) fn render_layers(
+    layers: &[usvg::MaskLayer],
+    ctx: &Context,
+    transform: tiny_skia::Transform,
+    size: tiny_skia::IntSize,
+) -> Option<tiny_skia::Mask> {
+    let mut result: Option<Vec<u8>> = None;
+    for layer in layers.iter().rev() {
+        // Masking an opaque layer leaves us with the mask coverage in the alpha channel.
+        let mut layer_pixmap = tiny_skia::Pixmap::new(size.width(), size.height())?;
+        layer_pixmap.fill(tiny_skia::Color::WHITE);
+        apply_mask(layer.mask(), ctx, transform, &mut layer_pixmap);
+        let src = layer_pixmap.pixels().iter().map(|p| p.alpha());
+
+        result = Some(match result {
+            Some(mut dst) => {
+                for (d, s) in dst.iter_mut().zip(src) {
+                    *d = composite(s, *d, layer.composite());
+                }
+                dst
+            }
+            None => src.collect(),
+        });
+    }
+
+    tiny_skia::Mask::from_vec(result?, size)
+}
+
+/// Composites two coverage values.
+fn composite(src: u8, dst: u8, operator: usvg::MaskComposite) -> u8 {
+    let mul = |a: u8, b: u8| ((a as u16 * b as u16 + 127) / 255) as u8;
+    match operator {
+        usvg::MaskComposite::Add => src + mul(dst, 255 - src),
+        usvg::MaskComposite::Subtract => mul(src, 255 - dst),
+        usvg::MaskComposite::Intersect => mul(src, dst),
+        usvg::MaskComposite::Exclude => mul(src, 255 - dst).saturating_add(mul(dst, 255 - src)),
+    }
+}
+
+fn apply_mask(
     mask: &usvg::Mask,
     ctx: &Context,
     transform: tiny_skia::Transform,
@@ -20,20 +80,27 @@ pub fn apply(
         // TODO: only when needed
         // Mask has to be clipped by mask.region
         let mut alpha_mask = tiny_skia::Mask::new(pixmap.width(), pixmap.height()).unwrap();
-        alpha_mask.fill_path(
+        crate::region::fill_mask_path(
+            &mut alpha_mask,
             &tiny_skia::PathBuilder::from_rect(mask.rect().to_rect()),
             tiny_skia::FillRule::Winding,
             true,
             transform,
+            ctx.region,
         );
 
-        crate::render::render_nodes(mask.root(), ctx, transform, &mut mask_pixmap.as_mut());
+        crate::render::render_nodes(
+            mask.root(),
+            &ctx.detached(),
+            transform,
+            &mut mask_pixmap.as_mut(),
+        );
 
         mask_pixmap.apply_mask(&alpha_mask);
     }
 
     if let Some(mask) = mask.mask() {
-        self::apply(mask, ctx, transform, pixmap);
+        self::apply_mask(mask, ctx, transform, pixmap);
     }
 
     let mask_type = match mask.kind() {
@@ -0,0 +1,102 @@
+// Copyright 2025 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Splitting of independent work between threads.
+//!
+//! Without the `parallel` build feature, everything runs on the current thread.
+
+/// The minimal number of rows processed by a single thread.
+///
+/// Spawning a thread for just a couple of rows is slower than processing them in place.
+#[cfg(feature = "parallel")]
+const MIN_ROWS_PER_THREAD: usize = 32;
+
+#[cfg(feature = "parallel")]
+thread_local! {
+    /// Whether the current thread was spawned by this module.
+    static IS_WORKER: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+}
+
+/// Returns the number of threads available for splitting work.
+///
+/// Work is never split again inside a worker thread,
+/// otherwise the number of threads and allocated buffers would grow with nesting.
+#[cfg(feature = "parallel")]
+pub fn threads_count() -> usize {
+    if IS_WORKER.with(|w| w.get()) {
+        return 1;
+    }
+
+    std::thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/// Runs `f` marking the current thread as a worker.
+#[cfg(feature = "parallel")]
+fn worker<R>(f: impl FnOnce() -> R) -> R {
+    IS_WORKER.with(|w| w.set(true));
+    f()
+}
+
+/// Calls `f` for chunks of image rows.
+///
+/// `f` receives the index of the first row in a chunk and the chunk itself.
+/// A chunk always contains whole rows.
+pub fn for_each_rows<T, F>(data: &mut [T], width: usize, f: F)
+where
+    T: Send,
+    F: Fn(usize, &mut [T]) + Sync,
+{
+    if width == 0 {
+        return;
+    }
+
+    #[cfg(feature = "parallel")]
+    {
+        let height = data.len() / width;
+        let threads = threads_count().min(height / MIN_ROWS_PER_THREAD);
+        if threads > 1 {
+            let rows_per_thread = height.div_ceil(threads);
+            let f = &f;
+            std::thread::scope(|s| {
+                for (i, chunk) in data.chunks_mut(rows_per_thread * width).enumerate() {
+                    s.spawn(move || worker(|| f(i * rows_per_thread, chunk)));
+                }
+            });
+
+            return;
+        }
+    }
+
+    f(0, data);
+}
+
+/// Maps items into a vector of results, preserving the order.
+#[cfg(feature = "parallel")]
+pub fn map<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let threads = threads_count().min(items.len());
+    if threads < 2 {
+        return items.iter().map(f).collect();
+    }
+
+    let items_per_thread = items.len().div_ceil(threads);
+    let f = &f;
+    std::thread::scope(|s| {
+        let handles: Vec<_> = items
+            .chunks(items_per_thread)
+            .map(|chunk| s.spawn(move || worker(|| chunk.iter().map(f).collect::<Vec<_>>())))
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| match handle.join() {
+                Ok(results) => results,
+                Err(e) => std::panic::resume_unwind(e),
+            })
+            .collect()
+    })
+}
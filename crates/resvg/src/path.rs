@@ -44,6 +44,7 @@ pub fn fill_path(
     };
 
     let pattern_pixmap;
+    let mut paint_pixmap = None;
     let mut paint = tiny_skia::Paint::default();
     match fill.paint() {
         usvg::Paint::Color(c) => {
@@ -53,7 +54,29 @@ pub fn fill_path(
             paint.shader = convert_linear_gradient(lg, fill.opacity())?;
         }
         usvg::Paint::RadialGradient(ref rg) => {
-            paint.shader = convert_radial_gradient(rg, fill.opacity())?;
+            let region = paint_region(path.bounding_box(), transform, ctx, pixmap)?;
+            paint.shader =
+                convert_radial_gradient(rg, fill.opacity(), region, transform, &mut paint_pixmap)?;
+        }
+        usvg::Paint::ConicGradient(ref cg) => {
+            let region = paint_region(path.bounding_box(), transform, ctx, pixmap)?;
+            let conic_pixmap = paint_pixmap.insert(render_conic_gradient(
+                cg,
+                fill.opacity(),
+                region,
+                transform,
+            )?);
+            paint.shader = region_pattern(conic_pixmap, usvg::Opacity::ONE, region, transform)?;
+        }
+        usvg::Paint::MeshGradient(ref mg) => {
+            let region = paint_region(path.bounding_box(), transform, ctx, pixmap)?;
+            let mesh_pixmap = paint_pixmap.insert(render_mesh_gradient(mg, region, transform)?);
+            paint.shader = region_pattern(mesh_pixmap, fill.opacity(), region, transform)?;
+        }
+        usvg::Paint::Hatch(ref hatch) => {
+            let region = paint_region(path.bounding_box(), transform, ctx, pixmap)?;
+            let hatch_pixmap = paint_pixmap.insert(render_hatch(hatch, ctx, region, transform)?);
+            paint.shader = region_pattern(hatch_pixmap, fill.opacity(), region, transform)?;
         }
         usvg::Paint::Pattern(ref pattern) => {
             let (patt_pix, patt_ts) = render_pattern_pixmap(pattern, ctx, transform)?;
@@ -71,7 +94,7 @@ pub fn fill_path(
     paint.anti_alias = path.rendering_mode().use_shape_antialiasing();
     paint.blend_mode = blend_mode;
 
-    pixmap.fill_path(path.data(), &paint, rule, transform, None);
+    crate::region::fill_path(pixmap, path.data(), &paint, rule, transform, ctx.region);
     Some(())
 }
 
@@ -84,6 +107,7 @@ fn stroke_path(
 ) -> Option<()> {
     let stroke = path.stroke()?;
     let pattern_pixmap;
+    let mut paint_pixmap = None;
     let mut paint = tiny_skia::Paint::default();
     match stroke.paint() {
         usvg::Paint::Color(c) => {
@@ -93,7 +117,34 @@ fn stroke_path(
             paint.shader = convert_linear_gradient(lg, stroke.opacity())?;
         }
         usvg::Paint::RadialGradient(ref rg) => {
-            paint.shader = convert_radial_gradient(rg, stroke.opacity())?;
+            let region = paint_region(path.stroke_bounding_box(), transform, ctx, pixmap)?;
+            paint.shader = convert_radial_gradient(
+                rg,
+                stroke.opacity(),
+                region,
+                transform,
+                &mut paint_pixmap,
+            )?;
+        }
+        usvg::Paint::ConicGradient(ref cg) => {
+            let region = paint_region(path.stroke_bounding_box(), transform, ctx, pixmap)?;
+            let conic_pixmap = paint_pixmap.insert(render_conic_gradient(
+                cg,
+                stroke.opacity(),
+                region,
+                transform,
+            )?);
+            paint.shader = region_pattern(conic_pixmap, usvg::Opacity::ONE, region, transform)?;
+        }
+        usvg::Paint::MeshGradient(ref mg) => {
+            let region = paint_region(path.stroke_bounding_box(), transform, ctx, pixmap)?;
+            let mesh_pixmap = paint_pixmap.insert(render_mesh_gradient(mg, region, transform)?);
+            paint.shader = region_pattern(mesh_pixmap, stroke.opacity(), region, transform)?;
+        }
+        usvg::Paint::Hatch(ref hatch) => {
+            let region = paint_region(path.stroke_bounding_box(), transform, ctx, pixmap)?;
+            let hatch_pixmap = paint_pixmap.insert(render_hatch(hatch, ctx, region, transform)?);
+            paint.shader = region_pattern(hatch_pixmap, stroke.opacity(), region, transform)?;
         }
         usvg::Paint::Pattern(ref pattern) => {
             let (patt_pix, patt_ts) = render_pattern_pixmap(pattern, ctx, transform)?;
@@ -111,7 +162,29 @@ fn stroke_path(
     paint.anti_alias = path.rendering_mode().use_shape_antialiasing();
     paint.blend_mode = blend_mode;
 
-    pixmap.stroke_path(path.data(), &paint, &stroke.to_tiny_skia(), transform, None);
+    if path.vector_effect() == usvg::VectorEffect::NonScalingStroke {
+        // Stroke in device space, so the stroke width is not affected by the transform.
+        // Paint servers are still defined in the user space.
+        let data = path.data().clone().transform(transform)?;
+        paint.shader.transform(transform);
+        crate::region::stroke_path(
+            pixmap,
+            &data,
+            &paint,
+            &stroke.to_tiny_skia(),
+            tiny_skia::Transform::default(),
+            ctx.region,
+        );
+    } else {
+        crate::region::stroke_path(
+            pixmap,
+            path.data(),
+            &paint,
+            &stroke.to_tiny_skia(),
+            transform,
+            ctx.region,
+        );
+    }
 
     Some(())
 }
@@ -133,10 +206,25 @@ fn convert_linear_gradient(
     Some(shader)
 }
 
-fn convert_radial_gradient(
+/// Converts a radial gradient into a shader.
+///
+/// `tiny-skia` supports only gradients with a zero focal radius.
+/// Otherwise, the gradient is rendered into `pixmap` for the `region` in canvas coordinates
+/// and is used as a pattern.
+fn convert_radial_gradient<'a>(
     gradient: &usvg::RadialGradient,
     opacity: usvg::Opacity,
-) -> Option<tiny_skia::Shader<'_>> {
+    region: tiny_skia::IntRect,
+    transform: tiny_skia::Transform,
+    pixmap: &'a mut Option<tiny_skia::Pixmap>,
+) -> Option<tiny_skia::Shader<'a>> {
+    if gradient.fr().get() > 0.0 {
+        let pixmap = pixmap.insert(render_two_point_conical_gradient(
+            gradient, opacity, region, transform,
+        )?);
+        return region_pattern(pixmap, usvg::Opacity::ONE, region, transform);
+    }
+
     let (mode, points) = convert_base_gradient(gradient, opacity)?;
 
     let shader = tiny_skia::RadialGradient::new(
@@ -151,6 +239,241 @@ fn convert_radial_gradient(
     Some(shader)
 }
 
+/// Returns the canvas region covered by a painted bounding box.
+///
+/// When only a part of the canvas is rendered, the region is still computed
+/// for the whole canvas, so paint servers are rendered exactly the same way.
+fn paint_region(
+    bbox: usvg::Rect,
+    transform: tiny_skia::Transform,
+    ctx: &Context,
+    pixmap: &tiny_skia::PixmapMut,
+) -> Option<tiny_skia::IntRect> {
+    let canvas = match ctx.region {
+        Some(ref region) => region.canvas_rect(),
+        None => tiny_skia::IntRect::from_xywh(0, 0, pixmap.width(), pixmap.height())?,
+    };
+    let region = bbox.transform(transform)?.round_out()?;
+    crate::geom::fit_to_rect(region, canvas)
+}
+
+/// Creates a shader from a paint server rendered into `pixmap` for the `region`
+/// in canvas coordinates.
+fn region_pattern(
+    pixmap: &tiny_skia::Pixmap,
+    opacity: usvg::Opacity,
+    region: tiny_skia::IntRect,
+    transform: tiny_skia::Transform,
+) -> Option<tiny_skia::Shader<'_>> {
+    let ts = transform
+        .invert()?
+        .pre_translate(region.x() as f32, region.y() as f32);
+    Some(tiny_skia::Pattern::new(
+        pixmap.as_ref(),
+        tiny_skia::SpreadMode::Pad,
+        tiny_skia::FilterQuality::Nearest,
+        opacity.get(),
+        ts,
+    ))
+}
+
+/// Renders a two-point conical gradient.
+///
+/// The gradient is a family of circles interpolated from the focal circle at offset 0
+/// to the gradient circle at offset 1. Each pixel uses the largest offset of a circle
+/// with a non-negative radius that passes through it. Pixels not covered by any circle,
+/// like the ones outside the cone when the focal circle is outside the gradient circle,
+/// are left transparent.
+///
+/// https://www.w3.org/TR/SVG2/pservers.html#RadialGradientNotes
+fn render_two_point_conical_gradient(
+    gradient: &usvg::RadialGradient,
+    opacity: usvg::Opacity,
+    region: tiny_skia::IntRect,
+    transform: tiny_skia::Transform,
+) -> Option<tiny_skia::Pixmap> {
+    let ts = transform.pre_concat(gradient.transform()).invert()?;
+    let stops = GradientStops::new(gradient, opacity);
+    let mut pixmap = tiny_skia::Pixmap::new(region.width(), region.height())?;
+
+    let (fx, fy, fr) = (gradient.fx(), gradient.fy(), gradient.fr().get());
+    let (cdx, cdy, dr) = (
+        gradient.cx() - fx,
+        gradient.cy() - fy,
+        gradient.r().get() - fr,
+    );
+    // Coefficients of `a*t^2 - 2*b*t + c = 0`, where `c` and `b` depend on the pixel.
+    let a = cdx * cdx + cdy * cdy - dr * dr;
+
+    let width = region.width() as usize;
+    for (idx, pixel) in pixmap.pixels_mut().iter_mut().enumerate() {
+        let mut p = tiny_skia::Point::from_xy(
+            (region.x() + (idx % width) as i32) as f32 + 0.5,
+            (region.y() + (idx / width) as i32) as f32 + 0.5,
+        );
+        ts.map_point(&mut p);
+
+        let (pdx, pdy) = (p.x - fx, p.y - fy);
+        let b = pdx * cdx + pdy * cdy + fr * dr;
+        let c = pdx * pdx + pdy * pdy - fr * fr;
+        // Allow a rounding error for circles with a zero radius.
+        let is_valid = |t: f32| t.is_finite() && fr + t * dr >= -(fr + dr.abs()) * 1e-5;
+
+        let t = if a.abs() < f32::EPSILON {
+            Some(c / (2.0 * b)).filter(|t| is_valid(*t))
+        } else {
+            let discriminant = b * b - a * c;
+            if discriminant < 0.0 {
+                None
+            } else {
+                let sqrt = discriminant.sqrt();
+                let t1 = (b + sqrt) / a;
+                let t2 = (b - sqrt) / a;
+                let (t1, t2) = if t1 > t2 { (t1, t2) } else { (t2, t1) };
+                if is_valid(t1) {
+                    Some(t1)
+                } else {
+                    Some(t2).filter(|t| is_valid(*t))
+                }
+            }
+        };
+
+        if let Some(t) = t {
+            *pixel = stops.color_at(t);
+        }
+    }
+
+    Some(pixmap)
+}
+
+/// Renders a conic gradient.
+///
+/// The offset of each pixel is its angle around the center, mapped from the
+/// `[start_angle, end_angle]` range to `[0, 1]`. Angles are in the `[0, 360)` range,
+/// so the spread method affects only the angles outside of the gradient range.
+fn render_conic_gradient(
+    gradient: &usvg::ConicGradient,
+    opacity: usvg::Opacity,
+    region: tiny_skia::IntRect,
+    transform: tiny_skia::Transform,
+) -> Option<tiny_skia::Pixmap> {
+    let ts = transform.pre_concat(gradient.transform()).invert()?;
+    let stops = GradientStops::new(gradient, opacity);
+    let mut pixmap = tiny_skia::Pixmap::new(region.width(), region.height())?;
+
+    let width = region.width() as usize;
+    for (idx, pixel) in pixmap.pixels_mut().iter_mut().enumerate() {
+        let mut p = tiny_skia::Point::from_xy(
+            (region.x() + (idx % width) as i32) as f32 + 0.5,
+            (region.y() + (idx / width) as i32) as f32 + 0.5,
+        );
+        ts.map_point(&mut p);
+
+        let angle = (p.y - gradient.cy())
+            .atan2(p.x - gradient.cx())
+            .to_degrees()
+            .rem_euclid(360.0);
+        *pixel = stops.color_at(conic_offset(gradient, angle));
+    }
+
+    Some(pixmap)
+}
+
+/// Returns the gradient offset for an angle in degrees.
+pub(crate) fn conic_offset(gradient: &usvg::ConicGradient, angle: f32) -> f32 {
+    let start = gradient.start_angle();
+    let sweep = gradient.end_angle() - start;
+
+    // A zero sweep is a hard transition at the start angle.
+    if sweep.abs() < f32::EPSILON {
+        if angle < start {
+            0.0
+        } else {
+            1.0
+        }
+    } else {
+        (angle - start) / sweep
+    }
+}
+
+/// Gradient stops that can be sampled at any offset.
+pub(crate) struct GradientStops {
+    spread_method: usvg::SpreadMethod,
+    stops: Vec<(f32, [f32; 4])>,
+}
+
+impl GradientStops {
+    pub(crate) fn new(gradient: &usvg::BaseGradient, opacity: usvg::Opacity) -> Self {
+        let stops = gradient
+            .stops()
+            .iter()
+            .map(|stop| {
+                let c = stop.color();
+                let alpha = (stop.opacity() * opacity).get();
+                let rgba = [
+                    c.red as f32 / 255.0,
+                    c.green as f32 / 255.0,
+                    c.blue as f32 / 255.0,
+                    alpha,
+                ];
+                (stop.offset().get(), rgba)
+            })
+            .collect();
+
+        GradientStops {
+            spread_method: gradient.spread_method(),
+            stops,
+        }
+    }
+
+    fn color_at(&self, t: f32) -> tiny_skia::PremultipliedColorU8 {
+        let rgba = self.rgba_at(t);
+        let a = rgba[3];
+        let to_u8 = |v: f32| (v * 255.0 + 0.5) as u8;
+        tiny_skia::PremultipliedColorU8::from_rgba(
+            to_u8(rgba[0] * a),
+            to_u8(rgba[1] * a),
+            to_u8(rgba[2] * a),
+            to_u8(a),
+        )
+        .unwrap_or(tiny_skia::PremultipliedColorU8::TRANSPARENT)
+    }
+
+    /// Returns a non-premultiplied color in the 0..1 range.
+    pub(crate) fn rgba_at(&self, t: f32) -> [f32; 4] {
+        let t = match self.spread_method {
+            usvg::SpreadMethod::Pad => t.clamp(0.0, 1.0),
+            usvg::SpreadMethod::Repeat => t - t.floor(),
+            usvg::SpreadMethod::Reflect => {
+                let t = t.rem_euclid(2.0);
+                if t > 1.0 {
+                    2.0 - t
+                } else {
+                    t
+                }
+            }
+        };
+
+        // A gradient has at least two stops.
+        let idx = self.stops.partition_point(|(offset, _)| *offset <= t);
+        if idx == 0 {
+            self.stops[0].1
+        } else if idx == self.stops.len() {
+            self.stops[idx - 1].1
+        } else {
+            let (offset1, c1) = self.stops[idx - 1];
+            let (offset2, c2) = self.stops[idx];
+            let k = (t - offset1) / (offset2 - offset1);
+            [
+                c1[0] + (c2[0] - c1[0]) * k,
+                c1[1] + (c2[1] - c1[1]) * k,
+                c1[2] + (c2[2] - c1[2]) * k,
+                c1[3] + (c2[3] - c1[3]) * k,
+            ]
+        }
+    }
+}
+
 fn convert_base_gradient(
     gradient: &usvg::BaseGradient,
     opacity: usvg::Opacity,
@@ -193,8 +516,14 @@ fn render_pattern_pixmap(
     )?;
     let mut pixmap = tiny_skia::Pixmap::new(img_size.width(), img_size.height())?;
 
+    // A pattern tile is a separate canvas and must not depend on the current one.
+    let ctx = Context {
+        max_bbox: Context::new(img_size).max_bbox,
+        region: None,
+        ..ctx.detached()
+    };
     let transform = tiny_skia::Transform::from_scale(sx, sy);
-    crate::render::render_nodes(pattern.root(), ctx, transform, &mut pixmap.as_mut());
+    crate::render::render_nodes(pattern.root(), &ctx, transform, &mut pixmap.as_mut());
 
     let mut ts = tiny_skia::Transform::default();
     ts = ts.pre_concat(pattern.transform());
@@ -204,7 +533,437 @@ fn render_pattern_pixmap(
     Some((pixmap, ts))
 }
 
-// Note: The following functions provide optional support for Rectangle, Ellipse, and Polygon nodes.
+/// Renders a mesh gradient for the `region` in canvas coordinates.
+///
+/// Each Coons patch is subdivided into small quads in canvas coordinates, which are then
+/// rasterized as pairs of triangles. Patch coordinates are interpolated across triangles
+/// and colors are computed per pixel. Later patches are painted over the earlier ones.
+///
+/// https://www.w3.org/TR/2016/CR-SVG2-20160915/pservers.html#MeshGradients
+fn render_mesh_gradient(
+    gradient: &usvg::MeshGradient,
+    region: tiny_skia::IntRect,
+    transform: tiny_skia::Transform,
+) -> Option<tiny_skia::Pixmap> {
+    let ts = tiny_skia::Transform::from_translate(-region.x() as f32, -region.y() as f32)
+        .pre_concat(transform)
+        .pre_concat(gradient.transform());
+    let mut pixmap = tiny_skia::Pixmap::new(region.width(), region.height())?;
+
+    let colors = MeshColors::new(gradient);
+    for (row_idx, row) in gradient.rows().iter().enumerate() {
+        for (column_idx, patch) in row.iter().enumerate() {
+            let mut points = *patch.points();
+            ts.map_points(&mut points);
+            let patch_colors = colors.patch(row_idx, column_idx);
+            rasterize_mesh_patch(&points, &patch_colors, &mut pixmap);
+        }
+    }
+
+    Some(pixmap)
+}
+
+/// Mesh corners colors on a grid.
+///
+/// Colors are not premultiplied.
+struct MeshColors {
+    kind: usvg::MeshGradientType,
+    columns: usize,
+    nodes: Vec<[f32; 4]>,
+}
+
+impl MeshColors {
+    fn new(gradient: &usvg::MeshGradient) -> Self {
+        let rows = gradient.rows();
+        let columns = rows[0].len();
+
+        let to_rgba = |patch: &usvg::MeshPatch, corner: usize| {
+            let c = patch.colors()[corner];
+            [
+                c.red as f32 / 255.0,
+                c.green as f32 / 255.0,
+                c.blue as f32 / 255.0,
+                patch.opacities()[corner].get(),
+            ]
+        };
+
+        let mut nodes = Vec::with_capacity((rows.len() + 1) * (columns + 1));
+        for r in 0..=rows.len() {
+            for c in 0..=columns {
+                // Nodes on the bottom and right borders are taken from the last patches.
+                let patch = &rows[r.min(rows.len() - 1)][c.min(columns - 1)];
+                let corner = match (r == rows.len(), c == columns) {
+                    (false, false) => 0,
+                    (false, true) => 1,
+                    (true, true) => 2,
+                    (true, false) => 3,
+                };
+                nodes.push(to_rgba(patch, corner));
+            }
+        }
+
+        MeshColors {
+            kind: gradient.kind(),
+            columns,
+            nodes,
+        }
+    }
+
+    fn node(&self, row: usize, column: usize) -> [f32; 4] {
+        self.nodes[row * (self.columns + 1) + column]
+    }
+
+    /// Returns the color derivatives in the column and row directions.
+    ///
+    /// Bilinear meshes use differences to the next node, which makes bicubic interpolation
+    /// bilinear. Bicubic meshes use central differences, so colors are smooth across patches.
+    fn derivatives(
+        &self,
+        row: usize,
+        column: usize,
+        patch_row: usize,
+        patch_column: usize,
+    ) -> [[f32; 4]; 2] {
+        let rows = self.nodes.len() / (self.columns + 1) - 1;
+        let (c0, c1, r0, r1) = match self.kind {
+            usvg::MeshGradientType::Bilinear => {
+                (patch_column, patch_column + 1, patch_row, patch_row + 1)
+            }
+            usvg::MeshGradientType::Bicubic => (
+                column.saturating_sub(1),
+                (column + 1).min(self.columns),
+                row.saturating_sub(1),
+                (row + 1).min(rows),
+            ),
+        };
+
+        let diff = |a: [f32; 4], b: [f32; 4], steps: usize| {
+            let steps = steps as f32;
+            [
+                (b[0] - a[0]) / steps,
+                (b[1] - a[1]) / steps,
+                (b[2] - a[2]) / steps,
+                (b[3] - a[3]) / steps,
+            ]
+        };
+
+        [
+            diff(self.node(row, c0), self.node(row, c1), c1 - c0),
+            diff(self.node(r0, column), self.node(r1, column), r1 - r0),
+        ]
+    }
+
+    fn patch(&self, row: usize, column: usize) -> PatchColors {
+        // Corners in the top-left, top-right, bottom-right and bottom-left order.
+        let corners = [
+            (row, column),
+            (row, column + 1),
+            (row + 1, column + 1),
+            (row + 1, column),
+        ];
+
+        let mut colors = PatchColors {
+            colors: [[0.0; 4]; 4],
+            du: [[0.0; 4]; 4],
+            dv: [[0.0; 4]; 4],
+        };
+        for (i, (r, c)) in corners.into_iter().enumerate() {
+            colors.colors[i] = self.node(r, c);
+            let [du, dv] = self.derivatives(r, c, row, column);
+            colors.du[i] = du;
+            colors.dv[i] = dv;
+        }
+
+        colors
+    }
+}
+
+/// Patch corners colors and their derivatives.
+struct PatchColors {
+    colors: [[f32; 4]; 4],
+    du: [[f32; 4]; 4],
+    dv: [[f32; 4]; 4],
+}
+
+impl PatchColors {
+    /// Returns a color at the specified patch coordinates using bicubic Hermite interpolation.
+    fn color_at(&self, u: f32, v: f32) -> tiny_skia::PremultipliedColorU8 {
+        let h = |t: f32| {
+            [
+                2.0 * t * t * t - 3.0 * t * t + 1.0,
+                -2.0 * t * t * t + 3.0 * t * t,
+            ]
+        };
+        let g = |t: f32| [t * t * t - 2.0 * t * t + t, t * t * t - t * t];
+        let (hu, hv, gu, gv) = (h(u), h(v), g(u), g(v));
+
+        let mut rgba = [0.0; 4];
+        for (i, (a, b)) in [(0, 0), (1, 0), (1, 1), (0, 1)].into_iter().enumerate() {
+            for (channel, value) in rgba.iter_mut().enumerate() {
+                *value += hu[a] * hv[b] * self.colors[i][channel]
+                    + gu[a] * hv[b] * self.du[i][channel]
+                    + hu[a] * gv[b] * self.dv[i][channel];
+            }
+        }
+
+        let a = rgba[3].clamp(0.0, 1.0);
+        let to_u8 = |v: f32| (v.clamp(0.0, 1.0) * a * 255.0 + 0.5) as u8;
+        tiny_skia::PremultipliedColorU8::from_rgba(
+            to_u8(rgba[0]),
+            to_u8(rgba[1]),
+            to_u8(rgba[2]),
+            (a * 255.0 + 0.5) as u8,
+        )
+        .unwrap_or(tiny_skia::PremultipliedColorU8::TRANSPARENT)
+    }
+}
+
+/// Rasterizes a Coons patch defined by the points in canvas coordinates.
+fn rasterize_mesh_patch(
+    points: &[tiny_skia::Point; 12],
+    colors: &PatchColors,
+    pixmap: &mut tiny_skia::Pixmap,
+) {
+    // A cubic curve is never longer than its control polygon.
+    let edge_len = |edge: usize| {
+        (0..3)
+            .map(|i| {
+                let p1 = points[edge * 3 + i];
+                let p2 = points[(edge * 3 + i + 1) % 12];
+                p1.distance(p2)
+            })
+            .sum::<f32>()
+    };
+    // Aim for quads smaller than 4 pixels.
+    let steps = |len: f32| ((len / 4.0).ceil() as usize).clamp(1, 256);
+    let columns = steps(edge_len(0).max(edge_len(2)));
+    let rows = steps(edge_len(1).max(edge_len(3)));
+
+    let mut vertices = Vec::with_capacity((rows + 1) * (columns + 1));
+    for row in 0..=rows {
+        let v = row as f32 / rows as f32;
+        for column in 0..=columns {
+            let u = column as f32 / columns as f32;
+            vertices.push((coons_point(points, u, v), u, v));
+        }
+    }
+
+    for row in 0..rows {
+        for column in 0..columns {
+            let idx = row * (columns + 1) + column;
+            let p00 = vertices[idx];
+            let p10 = vertices[idx + 1];
+            let p01 = vertices[idx + columns + 1];
+            let p11 = vertices[idx + columns + 2];
+            rasterize_mesh_triangle([p00, p10, p11], colors, pixmap);
+            rasterize_mesh_triangle([p00, p11, p01], colors, pixmap);
+        }
+    }
+}
+
+/// Evaluates a Coons patch at the specified patch coordinates.
+fn coons_point(p: &[tiny_skia::Point; 12], u: f32, v: f32) -> tiny_skia::Point {
+    let cubic = |p0: tiny_skia::Point,
+                 p1: tiny_skia::Point,
+                 p2: tiny_skia::Point,
+                 p3: tiny_skia::Point,
+                 t: f32| {
+        let mt = 1.0 - t;
+        let (a, b, c, d) = (mt * mt * mt, 3.0 * mt * mt * t, 3.0 * mt * t * t, t * t * t);
+        tiny_skia::Point::from_xy(
+            a * p0.x + b * p1.x + c * p2.x + d * p3.x,
+            a * p0.y + b * p1.y + c * p2.y + d * p3.y,
+        )
+    };
+
+    let top = cubic(p[0], p[1], p[2], p[3], u);
+    let bottom = cubic(p[9], p[8], p[7], p[6], u);
+    let left = cubic(p[0], p[11], p[10], p[9], v);
+    let right = cubic(p[3], p[4], p[5], p[6], v);
+
+    let (mu, mv) = (1.0 - u, 1.0 - v);
+    let coord =
+        |top: f32, bottom: f32, left: f32, right: f32, c0: f32, c1: f32, c2: f32, c3: f32| {
+            mv * top + v * bottom + mu * left + u * right
+                - (mu * mv * c0 + u * mv * c1 + u * v * c2 + mu * v * c3)
+        };
+
+    tiny_skia::Point::from_xy(
+        coord(
+            top.x, bottom.x, left.x, right.x, p[0].x, p[3].x, p[6].x, p[9].x,
+        ),
+        coord(
+            top.y, bottom.y, left.y, right.y, p[0].y, p[3].y, p[6].y, p[9].y,
+        ),
+    )
+}
+
+/// Rasterizes a triangle with vertices in canvas coordinates and patch coordinates.
+///
+/// Pixels are covered when their centers are inside the triangle.
+/// Edges are included, so adjacent triangles do not leave gaps.
+fn rasterize_mesh_triangle(
+    vertices: [(tiny_skia::Point, f32, f32); 3],
+    colors: &PatchColors,
+    pixmap: &mut tiny_skia::Pixmap,
+) {
+    let [(p0, u0, v0), (p1, u1, v1), (p2, u2, v2)] = vertices;
+
+    let area = (p1.x - p0.x) * (p2.y - p0.y) - (p2.x - p0.x) * (p1.y - p0.y);
+    if !area.is_finite() || area.abs() < f32::EPSILON {
+        return;
+    }
+
+    let width = pixmap.width() as i32;
+    let height = pixmap.height() as i32;
+    let min_x = (p0.x.min(p1.x).min(p2.x).floor() as i32).max(0);
+    let min_y = (p0.y.min(p1.y).min(p2.y).floor() as i32).max(0);
+    let max_x = (p0.x.max(p1.x).max(p2.x).ceil() as i32).min(width - 1);
+    let max_y = (p0.y.max(p1.y).max(p2.y).ceil() as i32).min(height - 1);
+
+    const EPSILON: f32 = 1e-4;
+    let pixels = pixmap.pixels_mut();
+    for y in min_y..=max_y {
+        for x in min_x..=max_x {
+            let (px, py) = (x as f32 + 0.5, y as f32 + 0.5);
+            let w0 = ((p1.x - px) * (p2.y - py) - (p2.x - px) * (p1.y - py)) / area;
+            let w1 = ((p2.x - px) * (p0.y - py) - (p0.x - px) * (p2.y - py)) / area;
+            let w2 = 1.0 - w0 - w1;
+            if w0 < -EPSILON || w1 < -EPSILON || w2 < -EPSILON {
+                continue;
+            }
+
+            let u = w0 * u0 + w1 * u1 + w2 * u2;
+            let v = w0 * v0 + w1 * v1 + w2 * v2;
+            pixels[(y * width + x) as usize] =
+                colors.color_at(u.clamp(0.0, 1.0), v.clamp(0.0, 1.0));
+        }
+    }
+}
+
+/// The maximum number of hatch path instances that will be rendered.
+const MAX_HATCH_INSTANCES: i64 = 100_000;
+
+/// Renders a hatch for the `region` in canvas coordinates.
+///
+/// Only hatch path instances that intersect the region are rendered.
+fn render_hatch(
+    hatch: &usvg::Hatch,
+    ctx: &Context,
+    region: tiny_skia::IntRect,
+    transform: tiny_skia::Transform,
+) -> Option<tiny_skia::Pixmap> {
+    let ts = tiny_skia::Transform::from_translate(-region.x() as f32, -region.y() as f32)
+        .pre_concat(transform)
+        .pre_concat(hatch.hatch_transform());
+    let mut pixmap = tiny_skia::Pixmap::new(region.width(), region.height())?;
+
+    // The region in the hatch coordinates.
+    let rect = tiny_skia::Rect::from_xywh(0.0, 0.0, region.width() as f32, region.height() as f32)?
+        .transform(ts.invert()?)?;
+
+    // A hatch is a separate canvas and must not depend on the current one.
+    let ctx = Context {
+        max_bbox: Context::new(region.size()).max_bbox,
+        region: None,
+        ..ctx.detached()
+    };
+
+    let pitch = hatch.pitch().get();
+    for hatch_path in hatch.paths() {
+        let Some(data) = hatch_path_instances(hatch_path, pitch, rect) else {
+            continue;
+        };
+
+        let path = usvg::Path::new(
+            String::new(),
+            true,
+            None,
+            Some(hatch_path.stroke().clone()),
+            usvg::PaintOrder::default(),
+            usvg::ShapeRendering::default(),
+            std::sync::Arc::new(data),
+            tiny_skia::Transform::default(),
+        );
+        if let Some(path) = path {
+            stroke_path(
+                &path,
+                tiny_skia::BlendMode::SourceOver,
+                &ctx,
+                ts,
+                &mut pixmap.as_mut(),
+            );
+        }
+    }
+
+    Some(pixmap)
+}
+
+/// Returns all the hatch path instances that can be visible inside `rect`,
+/// which is in the hatch coordinates.
+pub(crate) fn hatch_path_instances(
+    hatch_path: &usvg::HatchPath,
+    pitch: f32,
+    rect: tiny_skia::Rect,
+) -> Option<tiny_skia::Path> {
+    use usvg::tiny_skia_path::PathBuilder;
+
+    let stroke = hatch_path.stroke();
+    // Enough to include joins and caps of the instances outside `rect`.
+    let margin = stroke.width().get() * stroke.miterlimit().get().max(1.5);
+
+    let data = match hatch_path.data() {
+        Some(data) => data.clone(),
+        None => {
+            // An infinite vertical line. Its start is aligned to the dash pattern,
+            // so dashes do not depend on `rect`.
+            let dash_len: f32 = stroke.dasharray().map_or(0.0, |list| list.iter().sum());
+            let mut top = rect.top() - margin;
+            if dash_len > 0.0 {
+                top = (top / dash_len).floor() * dash_len;
+            }
+
+            let mut builder = PathBuilder::new();
+            builder.move_to(0.0, top);
+            builder.line_to(0.0, rect.bottom() + margin);
+            builder.finish()?
+        }
+    };
+
+    let bounds = data.bounds();
+    let x = hatch_path.offset();
+    let first_column = ((rect.left() - margin - x - bounds.right()) / pitch).floor() as i64;
+    let last_column = ((rect.right() + margin - x - bounds.left()) / pitch).ceil() as i64;
+    let (first_row, last_row, period) = match hatch_path.period() {
+        Some(period) => (
+            ((rect.top() - margin - bounds.bottom()) / period).floor() as i64,
+            ((rect.bottom() + margin - bounds.top()) / period).ceil() as i64,
+            period,
+        ),
+        None => (0, 0, 0.0),
+    };
+
+    let instances = (last_column - first_column + 1).saturating_mul(last_row - first_row + 1);
+    if instances > MAX_HATCH_INSTANCES {
+        log::warn!("Too many hatch path instances. Skipped.");
+        return None;
+    }
+
+    let mut builder = PathBuilder::new();
+    for column in first_column..=last_column {
+        for row in first_row..=last_row {
+            let ts = tiny_skia::Transform::from_translate(
+                x + column as f32 * pitch,
+                row as f32 * period,
+            );
+            builder.push_path(&data.clone().transform(ts)?);
+        }
+    }
+
+    builder.finish()
+}
+
+// Note: The following functions provide support for primitive shape nodes, like Rectangle and Circle.
 // These shapes are converted to paths for rendering, maintaining compatibility with the existing
 // rendering pipeline while preserving primitive shape information in the usvg tree.
 
@@ -274,6 +1033,15 @@ impl PathBuilderExt for usvg::tiny_skia_path::PathBuilder {
     }
 }
 
+fn with_vector_effect(mut path: usvg::Path, vector_effect: usvg::VectorEffect) -> usvg::Path {
+    // Avoid recalculating bounding boxes in the common case.
+    if vector_effect != usvg::VectorEffect::None {
+        path.set_vector_effect(vector_effect);
+    }
+
+    path
+}
+
 /// Converts a Rectangle node to a Path for rendering.
 ///
 /// This function converts a `Rectangle` primitive node to a `Path` node so it can be rendered
@@ -325,6 +1093,7 @@ pub(crate) fn rect_to_path(rect: &usvg::Rectangle) -> Option<usvg::Path> {
         Arc::new(path_data),
         rect.abs_transform(),
     )
+    .map(|path| with_vector_effect(path, rect.vector_effect()))
 }
 
 /// Converts an Ellipse node to a Path for rendering.
@@ -365,6 +1134,7 @@ pub(crate) fn ellipse_to_path(ellipse: &usvg::Ellipse) -> Option<usvg::Path> {
         Arc::new(path_data),
         ellipse.abs_transform(),
     )
+    .map(|path| with_vector_effect(path, ellipse.vector_effect()))
 }
 
 /// Converts a Polygon node to a Path for rendering.
@@ -402,4 +1172,96 @@ pub(crate) fn polygon_to_path(polygon: &usvg::Polygon) -> Option<usvg::Path> {
         Arc::new(path_data),
         polygon.abs_transform(),
     )
+    .map(|path| with_vector_effect(path, polygon.vector_effect()))
+}
+
+/// Converts a Circle node to a Path for rendering.
+///
+/// The circle is converted using four arc segments, just like an ellipse.
+pub(crate) fn circle_to_path(circle: &usvg::Circle) -> Option<usvg::Path> {
+    use std::sync::Arc;
+    use usvg::tiny_skia_path::PathBuilder;
+
+    let cx = circle.cx();
+    let cy = circle.cy();
+    let r = circle.r();
+
+    let mut builder = PathBuilder::new();
+    builder.move_to(cx + r, cy);
+    builder.arc_to(r, r, 0.0, false, true, cx, cy + r);
+    builder.arc_to(r, r, 0.0, false, true, cx - r, cy);
+    builder.arc_to(r, r, 0.0, false, true, cx, cy - r);
+    builder.arc_to(r, r, 0.0, false, true, cx + r, cy);
+    builder.close();
+
+    let path_data = builder.finish()?;
+
+    usvg::Path::new(
+        circle.id().to_string(),
+        circle.is_visible(),
+        circle.fill().cloned(),
+        circle.stroke().cloned(),
+        circle.paint_order(),
+        circle.rendering_mode(),
+        Arc::new(path_data),
+        circle.abs_transform(),
+    )
+    .map(|path| with_vector_effect(path, circle.vector_effect()))
+}
+
+/// Converts a Line node to a Path for rendering.
+pub(crate) fn line_to_path(line: &usvg::Line) -> Option<usvg::Path> {
+    use std::sync::Arc;
+    use usvg::tiny_skia_path::PathBuilder;
+
+    let mut builder = PathBuilder::new();
+    builder.move_to(line.x1(), line.y1());
+    builder.line_to(line.x2(), line.y2());
+
+    let path_data = builder.finish()?;
+
+    usvg::Path::new(
+        line.id().to_string(),
+        line.is_visible(),
+        line.fill().cloned(),
+        line.stroke().cloned(),
+        line.paint_order(),
+        line.rendering_mode(),
+        Arc::new(path_data),
+        line.abs_transform(),
+    )
+    .map(|path| with_vector_effect(path, line.vector_effect()))
+}
+
+/// Converts a Polyline node to a Path for rendering.
+///
+/// Unlike a polygon, the resulting path is left open.
+pub(crate) fn polyline_to_path(polyline: &usvg::Polyline) -> Option<usvg::Path> {
+    use std::sync::Arc;
+    use usvg::tiny_skia_path::PathBuilder;
+
+    let mut builder = PathBuilder::new();
+    let points = polyline.points();
+    if points.is_empty() {
+        return None;
+    }
+
+    builder.move_to(points[0].0, points[0].1);
+    for &(x, y) in &points[1..] {
+        builder.line_to(x, y);
+    }
+
+    let path_data = builder.finish()?;
+
+    usvg::Path::new(
+        polyline.id().to_string(),
+        polyline.is_visible(),
+        polyline.fill().cloned(),
+        polyline.stroke().cloned(),
+        polyline.paint_order(),
+        polyline.rendering_mode(),
+        Arc::new(path_data),
+        polyline.abs_transform(),
+    )
+    .map(|path| with_vector_effect(path, polyline.vector_effect()))
 }
@@ -0,0 +1,200 @@
+// Copyright 2025 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use pdf_writer::types::MaskType;
+use pdf_writer::{Filter, Finish, Ref};
+
+use super::{Canvas, Context};
+use crate::OptionLog;
+
+/// The maximum width or height of a rasterized layer.
+const MAX_RASTER_SIZE: f32 = 8192.0;
+
+pub fn render(
+    image: &usvg::Image,
+    ctx: &mut Context,
+    canvas: &mut Canvas,
+    transform: tiny_skia::Transform,
+) {
+    if !image.is_visible() {
+        return;
+    }
+
+    match image.kind() {
+        usvg::ImageKind::SVG(ref tree) => {
+            // Glyphs of a nested tree reference its own font database.
+            #[cfg(feature = "text")]
+            let fontdb = std::mem::replace(&mut ctx.fontdb, tree.fontdb().clone());
+
+            super::render_nodes(tree.root(), ctx, canvas, transform);
+
+            #[cfg(feature = "text")]
+            {
+                ctx.fontdb = fontdb;
+            }
+        }
+        #[cfg(feature = "raster-images")]
+        kind => {
+            if let Some(raster) = crate::image::decode_raster(kind) {
+                let interpolate = !matches!(
+                    image.rendering_mode(),
+                    usvg::ImageRendering::OptimizeSpeed
+                        | usvg::ImageRendering::CrispEdges
+                        | usvg::ImageRendering::Pixelated
+                );
+                let id = write_image(&raster, interpolate, ctx);
+                let rect =
+                    usvg::Rect::from_xywh(0.0, 0.0, raster.width() as f32, raster.height() as f32);
+                if let Some(rect) = rect {
+                    draw_image(id, rect, canvas);
+                }
+            }
+        }
+        #[cfg(not(feature = "raster-images"))]
+        _ => {
+            log::warn!("Images decoding was disabled by a build feature.");
+        }
+    }
+}
+
+/// Rasterizes a group with filters and draws it as an image.
+///
+/// Only the group content and filters are rasterized.
+/// Clip paths, masks and opacity are still applied by the caller.
+pub fn render_filtered_group(
+    group: &usvg::Group,
+    ctx: &mut Context,
+    canvas: &mut Canvas,
+) -> Option<()> {
+    let bbox = group.layer_bounding_box();
+    let (size, transform) = raster_layer_size(group, ctx)?;
+    let mut pixmap = tiny_skia::Pixmap::new(size.width(), size.height()).log_none(|| {
+        log::warn!(
+            "Failed to allocate a filter layer: {}x{}.",
+            size.width(),
+            size.height()
+        )
+    })?;
+
+    let render_ctx = crate::render::Context::new(size);
+    crate::render::render_nodes(group, &render_ctx, transform, &mut pixmap.as_mut());
+    for filter in group.filters() {
+        crate::filter::apply(filter, &render_ctx, transform, &mut pixmap);
+    }
+
+    let id = write_image(&pixmap, true, ctx);
+    draw_image(id, bbox.to_rect(), canvas);
+
+    Some(())
+}
+
+/// Rasterizes group's mask layers into a single alpha soft mask.
+///
+/// PDF soft masks cannot be composited, unlike CSS mask layers.
+pub fn write_mask_layers(group: &usvg::Group, ctx: &mut Context) -> Option<Ref> {
+    let bbox = group.layer_bounding_box().to_rect();
+    let (size, transform) = raster_layer_size(group, ctx)?;
+
+    let render_ctx = crate::render::Context::new(size);
+    let mask =
+        crate::mask::render_layers(group.masks(), &render_ctx, transform, size).log_none(|| {
+            log::warn!(
+                "Failed to allocate a mask layer: {}x{}.",
+                size.width(),
+                size.height()
+            )
+        })?;
+
+    let mut pixmap = tiny_skia::Pixmap::new(size.width(), size.height())?;
+    for (pixel, a) in pixmap.pixels_mut().iter_mut().zip(mask.data()) {
+        *pixel = tiny_skia::PremultipliedColorU8::from_rgba(*a, *a, *a, *a)?;
+    }
+
+    let id = write_image(&pixmap, true, ctx);
+    let mut canvas = Canvas::new();
+    draw_image(id, bbox, &mut canvas);
+
+    let form = super::write_form(ctx, canvas, bbox);
+    Some(super::write_soft_mask(ctx, form, MaskType::Alpha))
+}
+
+/// Returns the raster size of a group layer and the transform from group's coordinates.
+fn raster_layer_size(
+    group: &usvg::Group,
+    ctx: &Context,
+) -> Option<(tiny_skia::IntSize, tiny_skia::Transform)> {
+    let bbox = group.layer_bounding_box();
+
+    let (sx, sy) = group.abs_transform().get_scale();
+    let mut scale = sx.max(sy) * ctx.options.raster_dpi / ctx.options.dpi;
+    // Make sure our layer is not too big.
+    scale = scale
+        .min(MAX_RASTER_SIZE / bbox.width())
+        .min(MAX_RASTER_SIZE / bbox.height());
+
+    let width = (bbox.width() * scale).ceil() as u32;
+    let height = (bbox.height() * scale).ceil() as u32;
+    let transform =
+        tiny_skia::Transform::from_scale(scale, scale).pre_translate(-bbox.x(), -bbox.y());
+
+    Some((tiny_skia::IntSize::from_wh(width, height)?, transform))
+}
+
+/// Draws an image XObject into `rect`.
+fn draw_image(id: Ref, rect: usvg::Rect, canvas: &mut Canvas) {
+    canvas.content.save_state();
+    // Images are drawn into a unit square with a bottom-left origin.
+    canvas.content.transform([
+        rect.width(),
+        0.0,
+        0.0,
+        -rect.height(),
+        rect.x(),
+        rect.y() + rect.height(),
+    ]);
+    canvas.draw_x_object(id);
+    canvas.content.restore_state();
+}
+
+/// Writes a pixmap as an RGB image XObject with an optional alpha soft mask.
+fn write_image(pixmap: &tiny_skia::Pixmap, interpolate: bool, ctx: &mut Context) -> Ref {
+    let mut rgb = Vec::with_capacity(pixmap.pixels().len() * 3);
+    let mut alpha = Vec::with_capacity(pixmap.pixels().len());
+    for pixel in pixmap.pixels() {
+        let c = pixel.demultiply();
+        rgb.extend_from_slice(&[c.red(), c.green(), c.blue()]);
+        alpha.push(c.alpha());
+    }
+
+    let soft_mask = if alpha.iter().any(|a| *a != 255) {
+        let id = ctx.alloc_ref();
+        let data = super::deflate(&alpha);
+        let mut image = ctx.chunk.image_xobject(id, &data);
+        image.filter(Filter::FlateDecode);
+        image.width(pixmap.width() as i32);
+        image.height(pixmap.height() as i32);
+        image.color_space().device_gray();
+        image.bits_per_component(8);
+        image.interpolate(interpolate);
+        image.finish();
+        Some(id)
+    } else {
+        None
+    };
+
+    let id = ctx.alloc_ref();
+    let data = super::deflate(&rgb);
+    let mut image = ctx.chunk.image_xobject(id, &data);
+    image.filter(Filter::FlateDecode);
+    image.width(pixmap.width() as i32);
+    image.height(pixmap.height() as i32);
+    image.color_space().device_rgb();
+    image.bits_per_component(8);
+    image.interpolate(interpolate);
+    if let Some(soft_mask) = soft_mask {
+        image.s_mask(soft_mask);
+    }
+    image.finish();
+
+    id
+}
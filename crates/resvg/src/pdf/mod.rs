@@ -0,0 +1,532 @@
+// Copyright 2025 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+/*!
+PDF output.
+
+Walks the same [`usvg::Tree`] as the raster renderer, but emits native PDF constructs instead:
+paths, gradients, patterns, clip paths, masks, group opacity and blend modes are all preserved
+as vector data and text is embedded using real fonts.
+
+Filters cannot be expressed in PDF, so groups with filters are rasterized
+and embedded as images.
+*/
+
+use pdf_writer::types::{BlendMode, MaskType};
+use pdf_writer::{Chunk, Content, Filter, Finish, Name, Pdf, Rect, Ref};
+
+mod image;
+mod paint;
+mod path;
+#[cfg(feature = "text")]
+mod text;
+
+/// PDF output options.
+#[derive(Clone, Debug)]
+pub struct Options {
+    /// The resolution of the SVG user space.
+    ///
+    /// Used to convert SVG user units into PDF points.
+    ///
+    /// Default: 96
+    pub dpi: f32,
+    /// The resolution used for content that cannot be represented in PDF
+    /// and has to be rasterized, like filters.
+    ///
+    /// Default: 300
+    pub raster_dpi: f32,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            dpi: 96.0,
+            raster_dpi: 300.0,
+        }
+    }
+}
+
+/// Renders a tree into a single page PDF document.
+///
+/// The page has the size of the tree, converted from user units to points using `Options::dpi`.
+pub fn render(tree: &usvg::Tree, options: &Options) -> Vec<u8> {
+    let mut ctx = Context::new(tree, options);
+
+    let catalog_id = ctx.alloc_ref();
+    let page_tree_id = ctx.alloc_ref();
+    let page_id = ctx.alloc_ref();
+    let content_id = ctx.alloc_ref();
+
+    let scale = 72.0 / options.dpi;
+    let width = tree.size().width() * scale;
+    let height = tree.size().height() * scale;
+
+    // PDF has a bottom-left origin, so flip the y-axis to be able to use SVG coordinates as is.
+    let transform = tiny_skia::Transform::from_row(scale, 0.0, 0.0, -scale, 0.0, height);
+
+    let mut canvas = Canvas::new();
+    canvas.transform(transform);
+    render_nodes(tree.root(), &mut ctx, &mut canvas, transform);
+
+    #[cfg(feature = "text")]
+    text::write_fonts(&mut ctx);
+
+    let mut pdf = Pdf::new();
+    pdf.catalog(catalog_id).pages(page_tree_id);
+    pdf.pages(page_tree_id).kids([page_id]).count(1);
+
+    let mut page = pdf.page(page_id);
+    page.media_box(Rect::new(0.0, 0.0, width, height))
+        .parent(page_tree_id)
+        .contents(content_id);
+    canvas.resources.write(page.resources());
+    page.finish();
+
+    let data = deflate(&canvas.content.finish());
+    pdf.stream(content_id, &data).filter(Filter::FlateDecode);
+
+    pdf.extend(&ctx.chunk);
+    pdf.finish()
+}
+
+struct Context<'a> {
+    chunk: Chunk,
+    next_ref: i32,
+    options: &'a Options,
+    #[cfg(feature = "text")]
+    fontdb: std::sync::Arc<usvg::fontdb::Database>,
+    #[cfg(feature = "text")]
+    fonts: text::FontCache,
+}
+
+impl<'a> Context<'a> {
+    #[allow(unused_variables)]
+    fn new(tree: &usvg::Tree, options: &'a Options) -> Self {
+        Context {
+            chunk: Chunk::new(),
+            next_ref: 1,
+            options,
+            #[cfg(feature = "text")]
+            fontdb: tree.fontdb().clone(),
+            #[cfg(feature = "text")]
+            fonts: text::FontCache::default(),
+        }
+    }
+
+    fn alloc_ref(&mut self) -> Ref {
+        let id = Ref::new(self.next_ref);
+        self.next_ref += 1;
+        id
+    }
+}
+
+/// A content stream together with the resources it uses.
+struct Canvas {
+    content: Content,
+    resources: Resources,
+}
+
+impl Canvas {
+    fn new() -> Self {
+        Canvas {
+            content: Content::new(),
+            resources: Resources::default(),
+        }
+    }
+
+    fn transform(&mut self, ts: tiny_skia::Transform) {
+        if !ts.is_identity() {
+            self.content.transform(convert_transform(ts));
+        }
+    }
+
+    fn draw_x_object(&mut self, id: Ref) {
+        let name = Resources::insert(&mut self.resources.x_objects, "x", id);
+        self.content.x_object(Name(name.as_bytes()));
+    }
+
+    fn set_ext_g_state(&mut self, id: Ref) {
+        let name = Resources::insert(&mut self.resources.ext_g_states, "g", id);
+        self.content.set_parameters(Name(name.as_bytes()));
+    }
+
+    fn set_pattern(&mut self, id: Ref, stroke: bool) {
+        let name = Resources::insert(&mut self.resources.patterns, "p", id);
+        if stroke {
+            self.content
+                .set_stroke_color_space(pdf_writer::types::ColorSpaceOperand::Pattern);
+            self.content.set_stroke_pattern(None, Name(name.as_bytes()));
+        } else {
+            self.content
+                .set_fill_color_space(pdf_writer::types::ColorSpaceOperand::Pattern);
+            self.content.set_fill_pattern(None, Name(name.as_bytes()));
+        }
+    }
+
+    #[cfg(feature = "text")]
+    fn set_font(&mut self, id: Ref, size: f32) {
+        let name = Resources::insert(&mut self.resources.fonts, "f", id);
+        self.content.set_font(Name(name.as_bytes()), size);
+    }
+}
+
+#[derive(Default)]
+struct Resources {
+    x_objects: Vec<Ref>,
+    ext_g_states: Vec<Ref>,
+    patterns: Vec<Ref>,
+    fonts: Vec<Ref>,
+}
+
+impl Resources {
+    /// Adds a resource to the list and returns its name.
+    fn insert(list: &mut Vec<Ref>, prefix: &str, id: Ref) -> String {
+        let idx = match list.iter().position(|v| *v == id) {
+            Some(idx) => idx,
+            None => {
+                list.push(id);
+                list.len() - 1
+            }
+        };
+
+        format!("{}{}", prefix, idx)
+    }
+
+    fn write(&self, mut resources: pdf_writer::writers::Resources) {
+        let lists = [
+            (&self.x_objects, "x"),
+            (&self.ext_g_states, "g"),
+            (&self.patterns, "p"),
+            (&self.fonts, "f"),
+        ];
+
+        for (list, prefix) in lists {
+            if list.is_empty() {
+                continue;
+            }
+
+            let mut dict = match prefix {
+                "x" => resources.x_objects(),
+                "g" => resources.ext_g_states(),
+                "p" => resources.patterns(),
+                _ => resources.fonts(),
+            };
+
+            for (idx, id) in list.iter().enumerate() {
+                let name = format!("{}{}", prefix, idx);
+                dict.pair(Name(name.as_bytes()), *id);
+            }
+        }
+    }
+}
+
+fn render_nodes(
+    parent: &usvg::Group,
+    ctx: &mut Context,
+    canvas: &mut Canvas,
+    transform: tiny_skia::Transform,
+) {
+    for node in parent.children() {
+        render_node(node, ctx, canvas, transform);
+    }
+}
+
+/// Renders a node.
+///
+/// `transform` maps the current user space into the base space of the current content stream
+/// and is used to position paint servers.
+fn render_node(
+    node: &usvg::Node,
+    ctx: &mut Context,
+    canvas: &mut Canvas,
+    transform: tiny_skia::Transform,
+) {
+    match node {
+        usvg::Node::Group(ref group) => {
+            render_group(group, ctx, canvas, transform);
+        }
+        usvg::Node::Path(ref path) => {
+            path::render(path, ctx, canvas, transform);
+        }
+        usvg::Node::Rectangle(ref rect) => {
+            if let Some(path) = crate::path::rect_to_path(rect) {
+                path::render(&path, ctx, canvas, transform);
+            }
+        }
+        usvg::Node::Ellipse(ref ellipse) => {
+            if let Some(path) = crate::path::ellipse_to_path(ellipse) {
+                path::render(&path, ctx, canvas, transform);
+            }
+        }
+        usvg::Node::Polygon(ref polygon) => {
+            if let Some(path) = crate::path::polygon_to_path(polygon) {
+                path::render(&path, ctx, canvas, transform);
+            }
+        }
+        usvg::Node::Circle(ref circle) => {
+            if let Some(path) = crate::path::circle_to_path(circle) {
+                path::render(&path, ctx, canvas, transform);
+            }
+        }
+        usvg::Node::Line(ref line) => {
+            if let Some(path) = crate::path::line_to_path(line) {
+                path::render(&path, ctx, canvas, transform);
+            }
+        }
+        usvg::Node::Polyline(ref polyline) => {
+            if let Some(path) = crate::path::polyline_to_path(polyline) {
+                path::render(&path, ctx, canvas, transform);
+            }
+        }
+        usvg::Node::Image(ref image) => {
+            image::render(image, ctx, canvas, transform);
+        }
+        usvg::Node::Text(ref text) => {
+            #[cfg(feature = "text")]
+            {
+                text::render(text, ctx, canvas, transform);
+            }
+
+            #[cfg(not(feature = "text"))]
+            {
+                render_group(text.flattened(), ctx, canvas, transform);
+            }
+        }
+    }
+}
+
+fn render_group(
+    group: &usvg::Group,
+    ctx: &mut Context,
+    canvas: &mut Canvas,
+    transform: tiny_skia::Transform,
+) {
+    canvas.content.save_state();
+    canvas.transform(group.transform());
+    let transform = transform.pre_concat(group.transform());
+
+    if group.should_isolate() {
+        render_isolated_group(group, ctx, canvas);
+    } else {
+        render_nodes(group, ctx, canvas, transform);
+    }
+
+    canvas.content.restore_state();
+}
+
+/// Renders a group as a transparency group XObject.
+///
+/// Clip paths and masks are applied as nested soft masks.
+fn render_isolated_group(group: &usvg::Group, ctx: &mut Context, canvas: &mut Canvas) {
+    let bbox = group.layer_bounding_box().to_rect();
+
+    let mut content = Canvas::new();
+    if group.filters().is_empty() {
+        render_nodes(group, ctx, &mut content, tiny_skia::Transform::identity());
+    } else {
+        image::render_filtered_group(group, ctx, &mut content);
+    }
+
+    let mut form = write_form(ctx, content, bbox);
+
+    let mut clip_path = group.clip_path();
+    while let Some(clip) = clip_path {
+        let gs = write_clip_path(clip, ctx, bbox);
+        form = wrap_form(form, gs, ctx, bbox);
+        clip_path = clip.clip_path();
+    }
+
+    if let [layer] = group.masks() {
+        let mut mask = Some(layer.mask());
+        while let Some(m) = mask {
+            let gs = write_mask(m, ctx, bbox);
+            form = wrap_form(form, gs, ctx, bbox);
+            mask = m.mask();
+        }
+    } else if !group.masks().is_empty() {
+        if let Some(gs) = image::write_mask_layers(group, ctx) {
+            form = wrap_form(form, gs, ctx, bbox);
+        }
+    }
+
+    if group.opacity() != usvg::Opacity::ONE || group.blend_mode() != usvg::BlendMode::Normal {
+        let id = ctx.alloc_ref();
+        let mut gs = ctx.chunk.ext_graphics(id);
+        gs.non_stroking_alpha(group.opacity().get())
+            .stroking_alpha(group.opacity().get());
+        if group.blend_mode() != usvg::BlendMode::Normal {
+            gs.blend_mode(convert_blend_mode(group.blend_mode()));
+        }
+        gs.finish();
+        canvas.set_ext_g_state(id);
+    }
+
+    canvas.draw_x_object(form);
+}
+
+/// Writes a canvas as an isolated transparency group XObject.
+fn write_form(ctx: &mut Context, canvas: Canvas, bbox: usvg::Rect) -> Ref {
+    let id = ctx.alloc_ref();
+    let data = deflate(&canvas.content.finish());
+
+    let mut form = ctx.chunk.form_xobject(id, &data);
+    form.filter(Filter::FlateDecode);
+    form.bbox(convert_rect(bbox));
+    form.group()
+        .transparency()
+        .isolated(true)
+        .color_space()
+        .device_rgb();
+    canvas.resources.write(form.resources());
+    form.finish();
+
+    id
+}
+
+/// Draws `form` into a new group with the `gs` graphics state applied.
+fn wrap_form(form: Ref, gs: Ref, ctx: &mut Context, bbox: usvg::Rect) -> Ref {
+    let mut canvas = Canvas::new();
+    canvas.set_ext_g_state(gs);
+    canvas.draw_x_object(form);
+    write_form(ctx, canvas, bbox)
+}
+
+/// Writes a clip path as a graphics state with an alpha soft mask.
+fn write_clip_path(clip: &usvg::ClipPath, ctx: &mut Context, bbox: usvg::Rect) -> Ref {
+    let mut canvas = Canvas::new();
+    canvas.content.set_fill_gray(0.0);
+    canvas.transform(clip.transform());
+    draw_clip_children(clip.root(), ctx, &mut canvas);
+
+    let group = write_form(ctx, canvas, bbox);
+    write_soft_mask(ctx, group, MaskType::Alpha)
+}
+
+fn draw_clip_children(parent: &usvg::Group, ctx: &mut Context, canvas: &mut Canvas) {
+    for child in parent.children() {
+        match child {
+            usvg::Node::Path(ref path) => {
+                path::fill_clip_path(path, canvas);
+            }
+            // `line` is never a part of a `clipPath`, because it has no fill.
+            usvg::Node::Rectangle(ref rect) => {
+                if let Some(path) = crate::path::rect_to_path(rect) {
+                    path::fill_clip_path(&path, canvas);
+                }
+            }
+            usvg::Node::Ellipse(ref ellipse) => {
+                if let Some(path) = crate::path::ellipse_to_path(ellipse) {
+                    path::fill_clip_path(&path, canvas);
+                }
+            }
+            usvg::Node::Polygon(ref polygon) => {
+                if let Some(path) = crate::path::polygon_to_path(polygon) {
+                    path::fill_clip_path(&path, canvas);
+                }
+            }
+            usvg::Node::Circle(ref circle) => {
+                if let Some(path) = crate::path::circle_to_path(circle) {
+                    path::fill_clip_path(&path, canvas);
+                }
+            }
+            usvg::Node::Polyline(ref polyline) => {
+                if let Some(path) = crate::path::polyline_to_path(polyline) {
+                    path::fill_clip_path(&path, canvas);
+                }
+            }
+            usvg::Node::Text(ref text) => {
+                draw_clip_children(text.flattened(), ctx, canvas);
+            }
+            usvg::Node::Group(ref group) => {
+                canvas.content.save_state();
+                canvas.transform(group.transform());
+
+                if let Some(clip) = group.clip_path() {
+                    // If a `clipPath` child also has a `clip-path`
+                    // then we should render this child into a separate group and clip it.
+                    let bbox = group.layer_bounding_box().to_rect();
+                    let mut sub_canvas = Canvas::new();
+                    sub_canvas.content.set_fill_gray(0.0);
+                    draw_clip_children(group, ctx, &mut sub_canvas);
+                    let form = write_form(ctx, sub_canvas, bbox);
+                    let gs = write_clip_path(clip, ctx, bbox);
+                    canvas.set_ext_g_state(gs);
+                    canvas.draw_x_object(form);
+                } else {
+                    draw_clip_children(group, ctx, canvas);
+                }
+
+                canvas.content.restore_state();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Writes a mask as a graphics state with a soft mask.
+fn write_mask(mask: &usvg::Mask, ctx: &mut Context, bbox: usvg::Rect) -> Ref {
+    let mut canvas = Canvas::new();
+    let rect = mask.rect();
+    canvas
+        .content
+        .rect(rect.x(), rect.y(), rect.width(), rect.height())
+        .clip_nonzero()
+        .end_path();
+    render_nodes(
+        mask.root(),
+        ctx,
+        &mut canvas,
+        tiny_skia::Transform::identity(),
+    );
+
+    let group = write_form(ctx, canvas, bbox);
+    let kind = match mask.kind() {
+        usvg::MaskType::Luminance => MaskType::Luminosity,
+        usvg::MaskType::Alpha => MaskType::Alpha,
+    };
+
+    write_soft_mask(ctx, group, kind)
+}
+
+fn write_soft_mask(ctx: &mut Context, group: Ref, kind: MaskType) -> Ref {
+    let id = ctx.alloc_ref();
+    ctx.chunk
+        .ext_graphics(id)
+        .soft_mask()
+        .subtype(kind)
+        .group(group);
+    id
+}
+
+fn deflate(data: &[u8]) -> Vec<u8> {
+    miniz_oxide::deflate::compress_to_vec_zlib(data, 6)
+}
+
+fn convert_transform(ts: tiny_skia::Transform) -> [f32; 6] {
+    [ts.sx, ts.ky, ts.kx, ts.sy, ts.tx, ts.ty]
+}
+
+fn convert_rect(rect: usvg::Rect) -> Rect {
+    Rect::new(rect.left(), rect.top(), rect.right(), rect.bottom())
+}
+
+fn convert_blend_mode(mode: usvg::BlendMode) -> BlendMode {
+    match mode {
+        usvg::BlendMode::Normal => BlendMode::Normal,
+        usvg::BlendMode::Multiply => BlendMode::Multiply,
+        usvg::BlendMode::Screen => BlendMode::Screen,
+        usvg::BlendMode::Overlay => BlendMode::Overlay,
+        usvg::BlendMode::Darken => BlendMode::Darken,
+        usvg::BlendMode::Lighten => BlendMode::Lighten,
+        usvg::BlendMode::ColorDodge => BlendMode::ColorDodge,
+        usvg::BlendMode::ColorBurn => BlendMode::ColorBurn,
+        usvg::BlendMode::HardLight => BlendMode::HardLight,
+        usvg::BlendMode::SoftLight => BlendMode::SoftLight,
+        usvg::BlendMode::Difference => BlendMode::Difference,
+        usvg::BlendMode::Exclusion => BlendMode::Exclusion,
+        usvg::BlendMode::Hue => BlendMode::Hue,
+        usvg::BlendMode::Saturation => BlendMode::Saturation,
+        usvg::BlendMode::Color => BlendMode::Color,
+        usvg::BlendMode::Luminosity => BlendMode::Luminosity,
+    }
+}
@@ -0,0 +1,736 @@
+// Copyright 2025 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use pdf_writer::types::{FunctionShadingType, MaskType, PaintType, TilingType};
+use pdf_writer::writers::StreamShadingType;
+use pdf_writer::{Filter, Finish, Ref};
+
+use super::{Canvas, Context};
+
+/// The maximum number of periods a repeated or reflected gradient can have.
+const MAX_GRADIENT_PERIODS: f32 = 1024.0;
+
+/// The maximum angle of a single conic gradient wedge, in degrees.
+const MAX_CONIC_WEDGE_ANGLE: f32 = 15.0;
+
+pub fn set_fill(
+    fill: &usvg::Fill,
+    bbox: usvg::Rect,
+    ctx: &mut Context,
+    canvas: &mut Canvas,
+    transform: tiny_skia::Transform,
+) {
+    set_paint(
+        fill.paint(),
+        fill.opacity(),
+        false,
+        bbox,
+        ctx,
+        canvas,
+        transform,
+    );
+}
+
+pub fn set_stroke(
+    stroke: &usvg::Stroke,
+    bbox: usvg::Rect,
+    ctx: &mut Context,
+    canvas: &mut Canvas,
+    transform: tiny_skia::Transform,
+) {
+    super::path::set_stroke_properties(stroke, &mut canvas.content);
+    set_paint(
+        stroke.paint(),
+        stroke.opacity(),
+        true,
+        bbox,
+        ctx,
+        canvas,
+        transform,
+    );
+}
+
+/// Sets the paint and the paint opacity.
+///
+/// Gradients with transparent stops are split into a color shading
+/// and a luminosity soft mask that covers `bbox`.
+fn set_paint(
+    paint: &usvg::Paint,
+    opacity: usvg::Opacity,
+    stroke: bool,
+    bbox: usvg::Rect,
+    ctx: &mut Context,
+    canvas: &mut Canvas,
+    transform: tiny_skia::Transform,
+) {
+    let mut soft_mask = None;
+    match paint {
+        usvg::Paint::Color(c) => {
+            let (r, g, b) = (
+                c.red as f32 / 255.0,
+                c.green as f32 / 255.0,
+                c.blue as f32 / 255.0,
+            );
+            if stroke {
+                canvas.content.set_stroke_rgb(r, g, b);
+            } else {
+                canvas.content.set_fill_rgb(r, g, b);
+            }
+        }
+        usvg::Paint::LinearGradient(ref lg) => {
+            let gradient = Gradient::Linear(lg);
+            let id = write_gradient(gradient, transform, bbox, false, ctx);
+            canvas.set_pattern(id, stroke);
+            if has_transparent_stops(lg) {
+                soft_mask = Some(write_gradient_mask(gradient, bbox, ctx));
+            }
+        }
+        usvg::Paint::RadialGradient(ref rg) => {
+            let gradient = Gradient::Radial(rg);
+            let id = write_gradient(gradient, transform, bbox, false, ctx);
+            canvas.set_pattern(id, stroke);
+            if has_transparent_stops(rg) {
+                soft_mask = Some(write_gradient_mask(gradient, bbox, ctx));
+            }
+        }
+        usvg::Paint::ConicGradient(ref cg) => {
+            // PDF has no conic shadings, so a mesh of wedges is used instead.
+            if let Some(mg) = conic_to_mesh_gradient(cg, bbox) {
+                soft_mask = set_mesh_gradient(&mg, stroke, bbox, ctx, canvas, transform);
+            }
+        }
+        usvg::Paint::Pattern(ref pattern) => {
+            let id = write_pattern(pattern, transform, ctx);
+            canvas.set_pattern(id, stroke);
+        }
+        usvg::Paint::MeshGradient(ref mg) => {
+            soft_mask = set_mesh_gradient(mg, stroke, bbox, ctx, canvas, transform);
+        }
+        usvg::Paint::Hatch(ref hatch) => {
+            let id = write_hatch(hatch, transform, bbox, ctx);
+            canvas.set_pattern(id, stroke);
+        }
+    }
+
+    if opacity == usvg::Opacity::ONE && soft_mask.is_none() {
+        return;
+    }
+
+    let id = ctx.alloc_ref();
+    let mut gs = ctx.chunk.ext_graphics(id);
+    if stroke {
+        gs.stroking_alpha(opacity.get());
+    } else {
+        gs.non_stroking_alpha(opacity.get());
+    }
+
+    if let Some(group) = soft_mask {
+        gs.soft_mask().subtype(MaskType::Luminosity).group(group);
+    }
+
+    gs.finish();
+    canvas.set_ext_g_state(id);
+}
+
+#[derive(Clone, Copy)]
+enum Gradient<'a> {
+    Linear(&'a usvg::LinearGradient),
+    Radial(&'a usvg::RadialGradient),
+}
+
+impl Gradient<'_> {
+    fn base(&self) -> &usvg::BaseGradient {
+        match self {
+            Gradient::Linear(lg) => lg,
+            Gradient::Radial(rg) => rg,
+        }
+    }
+}
+
+fn has_transparent_stops(gradient: &usvg::BaseGradient) -> bool {
+    gradient
+        .stops()
+        .iter()
+        .any(|stop| stop.opacity() != usvg::Opacity::ONE)
+}
+
+/// Writes a gradient as a shading pattern.
+///
+/// When `alpha` is set, the shading contains stop opacities as gray values instead of colors.
+fn write_gradient(
+    gradient: Gradient,
+    transform: tiny_skia::Transform,
+    bbox: usvg::Rect,
+    alpha: bool,
+    ctx: &mut Context,
+) -> Ref {
+    let base = gradient.base();
+    let matrix = transform.pre_concat(base.transform());
+
+    let stops: Vec<(f32, Vec<f32>)> = base
+        .stops()
+        .iter()
+        .map(|stop| {
+            let color = if alpha {
+                vec![stop.opacity().get()]
+            } else {
+                vec![
+                    stop.color().red as f32 / 255.0,
+                    stop.color().green as f32 / 255.0,
+                    stop.color().blue as f32 / 255.0,
+                ]
+            };
+
+            (stop.offset().get(), color)
+        })
+        .collect();
+    let function = write_stops_function(&stops, ctx);
+
+    // PDF shadings can only be padded.
+    // Repeated and reflected gradients are emulated by stretching the shading over the whole
+    // bounding box and repeating the stops function in each period.
+    let (t0, t1) = match base.spread_method() {
+        usvg::SpreadMethod::Pad => (0.0, 1.0),
+        _ => gradient_range(gradient, bbox),
+    };
+    let function = write_periodic_function(function, t0, t1, base.spread_method(), ctx);
+
+    let (kind, coords) = match gradient {
+        Gradient::Linear(lg) => {
+            let dx = lg.x2() - lg.x1();
+            let dy = lg.y2() - lg.y1();
+            (
+                FunctionShadingType::Axial,
+                vec![
+                    lg.x1() + dx * t0,
+                    lg.y1() + dy * t0,
+                    lg.x1() + dx * t1,
+                    lg.y1() + dy * t1,
+                ],
+            )
+        }
+        Gradient::Radial(rg) => {
+            let dx = rg.cx() - rg.fx();
+            let dy = rg.cy() - rg.fy();
+            let fr = rg.fr().get();
+            (
+                FunctionShadingType::Radial,
+                vec![
+                    rg.fx(),
+                    rg.fy(),
+                    fr,
+                    rg.fx() + dx * t1,
+                    rg.fy() + dy * t1,
+                    fr + (rg.r().get() - fr) * t1,
+                ],
+            )
+        }
+    };
+
+    let id = ctx.alloc_ref();
+    let mut pattern = ctx.chunk.shading_pattern(id);
+    pattern.matrix(super::convert_transform(matrix));
+    let mut shading = pattern.function_shading();
+    shading.shading_type(kind);
+    if alpha {
+        shading.color_space().device_gray();
+    } else {
+        shading.color_space().device_rgb();
+    }
+    shading.function(function);
+    shading.coords(coords);
+    shading.extend([true, true]);
+    shading.finish();
+    pattern.finish();
+
+    id
+}
+
+/// Returns the range of the gradient vector that covers the whole bounding box.
+fn gradient_range(gradient: Gradient, bbox: usvg::Rect) -> (f32, f32) {
+    let ts = match gradient.base().transform().invert() {
+        Some(v) => v,
+        None => return (0.0, 1.0),
+    };
+
+    let mut corners = [
+        tiny_skia::Point::from_xy(bbox.left(), bbox.top()),
+        tiny_skia::Point::from_xy(bbox.right(), bbox.top()),
+        tiny_skia::Point::from_xy(bbox.right(), bbox.bottom()),
+        tiny_skia::Point::from_xy(bbox.left(), bbox.bottom()),
+    ];
+    ts.map_points(&mut corners);
+
+    let (t0, t1) = match gradient {
+        Gradient::Linear(lg) => {
+            let dx = lg.x2() - lg.x1();
+            let dy = lg.y2() - lg.y1();
+            let len2 = dx * dx + dy * dy;
+            if len2 == 0.0 {
+                return (0.0, 1.0);
+            }
+
+            let mut t0 = 0.0f32;
+            let mut t1 = 1.0f32;
+            for p in corners {
+                let t = ((p.x - lg.x1()) * dx + (p.y - lg.y1()) * dy) / len2;
+                t0 = t0.min(t);
+                t1 = t1.max(t);
+            }
+
+            (t0, t1)
+        }
+        Gradient::Radial(rg) => {
+            // Assume that the focal circle is inside the circle,
+            // so each circle fully contains all the previous ones.
+            let fr = rg.fr().get();
+            let focal_offset = (rg.cx() - rg.fx()).hypot(rg.cy() - rg.fy());
+            let growth = (rg.r().get() - fr - focal_offset).max(rg.r().get() * 0.001);
+
+            let mut t1 = 1.0f32;
+            for p in corners {
+                let dist = (p.x - rg.fx()).hypot(p.y - rg.fy());
+                t1 = t1.max((dist - fr) / growth);
+            }
+
+            (0.0, t1)
+        }
+    };
+
+    let t0 = t0.floor().max(-MAX_GRADIENT_PERIODS);
+    let t1 = t1.ceil().min(t0 + MAX_GRADIENT_PERIODS);
+    (t0, t1)
+}
+
+/// Writes a luminosity soft mask group with the gradient opacity.
+fn write_gradient_mask(gradient: Gradient, bbox: usvg::Rect, ctx: &mut Context) -> Ref {
+    let mut canvas = Canvas::new();
+    let id = write_gradient(gradient, tiny_skia::Transform::identity(), bbox, true, ctx);
+    canvas.set_pattern(id, false);
+    canvas
+        .content
+        .rect(bbox.x(), bbox.y(), bbox.width(), bbox.height())
+        .fill_nonzero();
+
+    super::write_form(ctx, canvas, bbox)
+}
+
+/// Writes a function that interpolates between gradient stops in the 0..1 range.
+fn write_stops_function(stops: &[(f32, Vec<f32>)], ctx: &mut Context) -> Ref {
+    let mut stops = stops.to_vec();
+    if let Some(first) = stops.first().cloned() {
+        if first.0 > 0.0 {
+            stops.insert(0, (0.0, first.1));
+        }
+    }
+    if let Some(last) = stops.last().cloned() {
+        if last.0 < 1.0 {
+            stops.push((1.0, last.1));
+        }
+    }
+    if stops.len() == 1 {
+        stops.push(stops[0].clone());
+    }
+
+    let mut functions = Vec::with_capacity(stops.len() - 1);
+    for pair in stops.windows(2) {
+        let id = ctx.alloc_ref();
+        ctx.chunk
+            .exponential_function(id)
+            .domain([0.0, 1.0])
+            .c0(pair[0].1.iter().copied())
+            .c1(pair[1].1.iter().copied())
+            .n(1.0);
+        functions.push(id);
+    }
+
+    if functions.len() == 1 {
+        return functions[0];
+    }
+
+    let id = ctx.alloc_ref();
+    let bounds = stops[1..stops.len() - 1].iter().map(|s| s.0);
+    let encode = functions.iter().flat_map(|_| [0.0, 1.0]);
+    ctx.chunk
+        .stitching_function(id)
+        .domain([0.0, 1.0])
+        .functions(functions.iter().copied())
+        .bounds(bounds)
+        .encode(encode);
+
+    id
+}
+
+/// Repeats `function` over the `t0..t1` periods, mapped into the 0..1 range.
+fn write_periodic_function(
+    function: Ref,
+    t0: f32,
+    t1: f32,
+    spread_method: usvg::SpreadMethod,
+    ctx: &mut Context,
+) -> Ref {
+    let periods = (t1 - t0).round() as i32;
+    if periods <= 1 {
+        return function;
+    }
+
+    let bounds = (1..periods).map(|i| i as f32 / periods as f32);
+    let encode = (0..periods).flat_map(|i| {
+        let reversed =
+            spread_method == usvg::SpreadMethod::Reflect && (t0 as i32 + i).rem_euclid(2) == 1;
+        if reversed {
+            [1.0, 0.0]
+        } else {
+            [0.0, 1.0]
+        }
+    });
+
+    let id = ctx.alloc_ref();
+    ctx.chunk
+        .stitching_function(id)
+        .domain([0.0, 1.0])
+        .functions((0..periods).map(|_| function))
+        .bounds(bounds)
+        .encode(encode);
+
+    id
+}
+
+/// Writes a pattern as a colored tiling pattern.
+fn write_pattern(
+    pattern: &usvg::Pattern,
+    transform: tiny_skia::Transform,
+    ctx: &mut Context,
+) -> Ref {
+    let rect = pattern.rect();
+
+    let mut canvas = Canvas::new();
+    super::render_nodes(
+        pattern.root(),
+        ctx,
+        &mut canvas,
+        tiny_skia::Transform::identity(),
+    );
+
+    let matrix = transform
+        .pre_concat(pattern.transform())
+        .pre_translate(rect.x(), rect.y());
+
+    let id = ctx.alloc_ref();
+    let data = super::deflate(&canvas.content.finish());
+    let mut tiling = ctx.chunk.tiling_pattern(id, &data);
+    tiling.filter(Filter::FlateDecode);
+    tiling
+        .paint_type(PaintType::Colored)
+        .tiling_type(TilingType::ConstantSpacing)
+        .bbox(pdf_writer::Rect::new(0.0, 0.0, rect.width(), rect.height()))
+        .x_step(rect.width())
+        .y_step(rect.height())
+        .matrix(super::convert_transform(matrix));
+    canvas.resources.write(tiling.resources());
+    tiling.finish();
+
+    id
+}
+
+/// Sets a mesh gradient as the paint.
+///
+/// Returns a soft mask when the mesh has transparent corners.
+fn set_mesh_gradient(
+    gradient: &usvg::MeshGradient,
+    stroke: bool,
+    bbox: usvg::Rect,
+    ctx: &mut Context,
+    canvas: &mut Canvas,
+    transform: tiny_skia::Transform,
+) -> Option<Ref> {
+    let id = write_mesh_gradient(gradient, transform, false, ctx);
+    canvas.set_pattern(id, stroke);
+
+    let has_transparent_corners = gradient
+        .rows()
+        .iter()
+        .flatten()
+        .any(|patch| patch.opacities().iter().any(|o| *o != usvg::Opacity::ONE));
+    if has_transparent_corners {
+        Some(write_mesh_gradient_mask(gradient, bbox, ctx))
+    } else {
+        None
+    }
+}
+
+/// Converts a conic gradient into a single row mesh gradient.
+///
+/// Each patch is a wedge from the gradient center to an arc that covers `bbox`.
+/// Wedges are split at angles of the gradient stops, so hard transitions are preserved,
+/// and are limited by `MAX_CONIC_WEDGE_ANGLE`.
+fn conic_to_mesh_gradient(
+    gradient: &usvg::ConicGradient,
+    bbox: usvg::Rect,
+) -> Option<usvg::MeshGradient> {
+    let (cx, cy) = (gradient.cx(), gradient.cy());
+    let start = gradient.start_angle();
+    let sweep = gradient.end_angle() - start;
+
+    let mut corners = [
+        tiny_skia::Point::from_xy(bbox.left(), bbox.top()),
+        tiny_skia::Point::from_xy(bbox.right(), bbox.top()),
+        tiny_skia::Point::from_xy(bbox.right(), bbox.bottom()),
+        tiny_skia::Point::from_xy(bbox.left(), bbox.bottom()),
+    ];
+    gradient.transform().invert()?.map_points(&mut corners);
+    let radius = corners
+        .iter()
+        .map(|p| (p.x - cx).hypot(p.y - cy))
+        .fold(0.0f32, f32::max)
+        + 1.0;
+
+    let mut angles = Vec::new();
+    let mut angle = 0.0;
+    while angle < 360.0 {
+        angles.push(angle);
+        angle += MAX_CONIC_WEDGE_ANGLE;
+    }
+    angles.push(360.0);
+
+    if sweep.abs() < f32::EPSILON {
+        angles.push(start);
+    } else {
+        // Offsets are repeated in each period for the repeat and reflect spread methods.
+        let t0 = -start / sweep;
+        let t1 = (360.0 - start) / sweep;
+        let (t0, t1) = (t0.min(t1).floor(), t0.max(t1).ceil());
+        let t0 = t0.max(-MAX_GRADIENT_PERIODS);
+        let t1 = t1.min(t0 + MAX_GRADIENT_PERIODS);
+
+        let mut period = t0;
+        while period <= t1 {
+            for stop in gradient.stops() {
+                let offset = stop.offset().get();
+                angles.push(start + sweep * (period + offset));
+                angles.push(start + sweep * (period - offset));
+            }
+            period += 1.0;
+        }
+    }
+
+    angles.retain(|a| (0.0..=360.0).contains(a));
+    angles.sort_by(|a, b| a.total_cmp(b));
+    angles.dedup_by(|a, b| (*a - *b).abs() < 0.001);
+
+    let stops = crate::path::GradientStops::new(gradient, usvg::Opacity::ONE);
+    // Colors are sampled slightly inside each wedge to preserve hard transitions.
+    let sample = |angle: f32| {
+        let rgba = stops.rgba_at(crate::path::conic_offset(gradient, angle));
+        let to_u8 = |v: f32| (v * 255.0 + 0.5) as u8;
+        (
+            usvg::Color::new_rgb(to_u8(rgba[0]), to_u8(rgba[1]), to_u8(rgba[2])),
+            usvg::Opacity::new_clamped(rgba[3]),
+        )
+    };
+    let arc_point = |angle: f32| {
+        let (sin, cos) = angle.to_radians().sin_cos();
+        tiny_skia::Point::from_xy(cx + radius * cos, cy + radius * sin)
+    };
+
+    let center = tiny_skia::Point::from_xy(cx, cy);
+    let mut patches = Vec::with_capacity(angles.len());
+    for pair in angles.windows(2) {
+        let (a0, a1) = (pair[0], pair[1]);
+        let (c0, o0) = sample(a0 + 0.0001);
+        let (c1, o1) = sample(a1 - 0.0001);
+
+        // A cubic approximation of the arc.
+        let k = 4.0 / 3.0 * ((a1 - a0).to_radians() / 4.0).tan() * radius;
+        let (p0, p3) = (arc_point(a0), arc_point(a1));
+        let (sin0, cos0) = a0.to_radians().sin_cos();
+        let (sin1, cos1) = a1.to_radians().sin_cos();
+        let p1 = tiny_skia::Point::from_xy(p0.x - k * sin0, p0.y + k * cos0);
+        let p2 = tiny_skia::Point::from_xy(p3.x + k * sin1, p3.y - k * cos1);
+
+        // The top edge is collapsed into the center.
+        let lerp = |a: tiny_skia::Point, b: tiny_skia::Point, t: f32| {
+            tiny_skia::Point::from_xy(a.x + (b.x - a.x) * t, a.y + (b.y - a.y) * t)
+        };
+        let points = [
+            center,
+            center,
+            center,
+            center,
+            lerp(center, p3, 1.0 / 3.0),
+            lerp(center, p3, 2.0 / 3.0),
+            p3,
+            p2,
+            p1,
+            p0,
+            lerp(p0, center, 1.0 / 3.0),
+            lerp(p0, center, 2.0 / 3.0),
+        ];
+        patches.push(usvg::MeshPatch::new(
+            points,
+            [c0, c1, c1, c0],
+            [o0, o1, o1, o0],
+        ));
+    }
+
+    let mut mesh = usvg::MeshGradient::new(
+        gradient.id().to_string(),
+        usvg::MeshGradientType::Bilinear,
+        vec![patches],
+    )?;
+    mesh.set_transform(gradient.transform());
+    Some(mesh)
+}
+
+/// Writes a mesh gradient as a Coons patch mesh shading pattern.
+///
+/// When `alpha` is set, the shading contains corner opacities as gray values instead of colors.
+/// PDF supports only bilinear colors interpolation, which is used for bicubic meshes as well.
+fn write_mesh_gradient(
+    gradient: &usvg::MeshGradient,
+    transform: tiny_skia::Transform,
+    alpha: bool,
+    ctx: &mut Context,
+) -> Ref {
+    let patches: Vec<&usvg::MeshPatch> = gradient.rows().iter().flatten().collect();
+
+    let (mut min_x, mut min_y) = (f32::MAX, f32::MAX);
+    let (mut max_x, mut max_y) = (f32::MIN, f32::MIN);
+    for p in patches.iter().flat_map(|patch| patch.points()) {
+        min_x = min_x.min(p.x);
+        min_y = min_y.min(p.y);
+        max_x = max_x.max(p.x);
+        max_y = max_y.max(p.y);
+    }
+    // Decode ranges cannot be empty.
+    max_x = max_x.max(min_x + 1.0);
+    max_y = max_y.max(min_y + 1.0);
+
+    let encode_coord = |v: f32, min: f32, max: f32| {
+        (((v - min) / (max - min)).clamp(0.0, 1.0) as f64 * u32::MAX as f64).round() as u32
+    };
+    let encode_component = |v: f32| (v.clamp(0.0, 1.0) * u16::MAX as f32).round() as u16;
+
+    // Each patch has a flag, all 12 points and 4 corner colors.
+    let mut data = Vec::new();
+    for patch in &patches {
+        data.push(0u8);
+        for p in patch.points() {
+            data.extend_from_slice(&encode_coord(p.x, min_x, max_x).to_be_bytes());
+            data.extend_from_slice(&encode_coord(p.y, min_y, max_y).to_be_bytes());
+        }
+
+        for (color, opacity) in patch.colors().iter().zip(patch.opacities()) {
+            if alpha {
+                data.extend_from_slice(&encode_component(opacity.get()).to_be_bytes());
+            } else {
+                for c in [color.red, color.green, color.blue] {
+                    data.extend_from_slice(&encode_component(c as f32 / 255.0).to_be_bytes());
+                }
+            }
+        }
+    }
+
+    let components = if alpha { 1 } else { 3 };
+    let decode = [min_x, max_x, min_y, max_y]
+        .into_iter()
+        .chain((0..components).flat_map(|_| [0.0, 1.0]));
+
+    let data = super::deflate(&data);
+    let shading_id = ctx.alloc_ref();
+    let mut shading = ctx.chunk.stream_shading(shading_id, &data);
+    shading.filter(Filter::FlateDecode);
+    shading.shading_type(StreamShadingType::CoonsPatch);
+    if alpha {
+        shading.color_space().device_gray();
+    } else {
+        shading.color_space().device_rgb();
+    }
+    shading
+        .bits_per_coordinate(32)
+        .bits_per_component(16)
+        .bits_per_flag(8)
+        .decode(decode);
+    shading.finish();
+
+    let id = ctx.alloc_ref();
+    let mut pattern = ctx.chunk.shading_pattern(id);
+    pattern.matrix(super::convert_transform(
+        transform.pre_concat(gradient.transform()),
+    ));
+    pattern.shading_ref(shading_id);
+    pattern.finish();
+
+    id
+}
+
+/// Writes a luminosity soft mask group with the mesh gradient opacity.
+fn write_mesh_gradient_mask(
+    gradient: &usvg::MeshGradient,
+    bbox: usvg::Rect,
+    ctx: &mut Context,
+) -> Ref {
+    let mut canvas = Canvas::new();
+    let id = write_mesh_gradient(gradient, tiny_skia::Transform::identity(), true, ctx);
+    canvas.set_pattern(id, false);
+    canvas
+        .content
+        .rect(bbox.x(), bbox.y(), bbox.width(), bbox.height())
+        .fill_nonzero();
+
+    super::write_form(ctx, canvas, bbox)
+}
+
+/// Writes a hatch as a colored tiling pattern.
+///
+/// The pattern has a single cell that covers `bbox` with all the visible hatch path instances.
+fn write_hatch(
+    hatch: &usvg::Hatch,
+    transform: tiny_skia::Transform,
+    bbox: usvg::Rect,
+    ctx: &mut Context,
+) -> Ref {
+    let hatch_ts = hatch.hatch_transform();
+    let rect = hatch_ts
+        .invert()
+        .and_then(|ts| bbox.transform(ts))
+        .unwrap_or(bbox);
+
+    let mut canvas = Canvas::new();
+    for hatch_path in hatch.paths() {
+        let data = match crate::path::hatch_path_instances(hatch_path, hatch.pitch().get(), rect) {
+            Some(v) => v,
+            None => continue,
+        };
+
+        let path = usvg::Path::new(
+            String::new(),
+            true,
+            None,
+            Some(hatch_path.stroke().clone()),
+            usvg::PaintOrder::default(),
+            usvg::ShapeRendering::default(),
+            std::sync::Arc::new(data),
+            tiny_skia::Transform::default(),
+        );
+        if let Some(ref path) = path {
+            super::path::render(path, ctx, &mut canvas, tiny_skia::Transform::identity());
+        }
+    }
+
+    let id = ctx.alloc_ref();
+    let data = super::deflate(&canvas.content.finish());
+    let mut tiling = ctx.chunk.tiling_pattern(id, &data);
+    tiling.filter(Filter::FlateDecode);
+    tiling
+        .paint_type(PaintType::Colored)
+        .tiling_type(TilingType::ConstantSpacing)
+        .bbox(super::convert_rect(rect))
+        .x_step(rect.width().max(1.0))
+        .y_step(rect.height().max(1.0))
+        .matrix(super::convert_transform(transform.pre_concat(hatch_ts)));
+    canvas.resources.write(tiling.resources());
+    tiling.finish();
+
+    id
+}
@@ -0,0 +1,137 @@
+// Copyright 2025 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use pdf_writer::types::{LineCapStyle, LineJoinStyle};
+use pdf_writer::Content;
+use usvg::tiny_skia_path::PathSegment;
+
+use super::{Canvas, Context};
+
+pub fn render(
+    path: &usvg::Path,
+    ctx: &mut Context,
+    canvas: &mut Canvas,
+    transform: tiny_skia::Transform,
+) {
+    if !path.is_visible() {
+        return;
+    }
+
+    if path.paint_order() == usvg::PaintOrder::FillAndStroke {
+        fill_path(path, ctx, canvas, transform);
+        stroke_path(path, ctx, canvas, transform);
+    } else {
+        stroke_path(path, ctx, canvas, transform);
+        fill_path(path, ctx, canvas, transform);
+    }
+}
+
+fn fill_path(
+    path: &usvg::Path,
+    ctx: &mut Context,
+    canvas: &mut Canvas,
+    transform: tiny_skia::Transform,
+) -> Option<()> {
+    let fill = path.fill()?;
+
+    // Horizontal and vertical lines cannot be filled. Skip.
+    if path.data().bounds().width() == 0.0 || path.data().bounds().height() == 0.0 {
+        return None;
+    }
+
+    canvas.content.save_state();
+    super::paint::set_fill(fill, path.bounding_box(), ctx, canvas, transform);
+    draw_path(path.data(), &mut canvas.content);
+    match fill.rule() {
+        usvg::FillRule::NonZero => canvas.content.fill_nonzero(),
+        usvg::FillRule::EvenOdd => canvas.content.fill_even_odd(),
+    };
+    canvas.content.restore_state();
+
+    Some(())
+}
+
+fn stroke_path(
+    path: &usvg::Path,
+    ctx: &mut Context,
+    canvas: &mut Canvas,
+    transform: tiny_skia::Transform,
+) -> Option<()> {
+    let stroke = path.stroke()?;
+
+    canvas.content.save_state();
+    super::paint::set_stroke(stroke, path.stroke_bounding_box(), ctx, canvas, transform);
+    draw_path(path.data(), &mut canvas.content);
+    canvas.content.stroke();
+    canvas.content.restore_state();
+
+    Some(())
+}
+
+/// Fills a clip path child. The paint is ignored, since only the coverage matters.
+pub fn fill_clip_path(path: &usvg::Path, canvas: &mut Canvas) {
+    if !path.is_visible() || path.fill().is_none() {
+        return;
+    }
+
+    draw_path(path.data(), &mut canvas.content);
+    match path.fill().map(|f| f.rule()) {
+        Some(usvg::FillRule::EvenOdd) => canvas.content.fill_even_odd(),
+        _ => canvas.content.fill_nonzero(),
+    };
+}
+
+/// Sets stroke properties except the paint.
+pub fn set_stroke_properties(stroke: &usvg::Stroke, content: &mut Content) {
+    content.set_line_width(stroke.width().get());
+    content.set_line_cap(match stroke.linecap() {
+        usvg::LineCap::Butt => LineCapStyle::ButtCap,
+        usvg::LineCap::Round => LineCapStyle::RoundCap,
+        usvg::LineCap::Square => LineCapStyle::ProjectingSquareCap,
+    });
+    content.set_line_join(match stroke.linejoin() {
+        usvg::LineJoin::Miter | usvg::LineJoin::MiterClip => LineJoinStyle::MiterJoin,
+        usvg::LineJoin::Round => LineJoinStyle::RoundJoin,
+        usvg::LineJoin::Bevel => LineJoinStyle::BevelJoin,
+    });
+    content.set_miter_limit(stroke.miterlimit().get());
+
+    if let Some(dasharray) = stroke.dasharray() {
+        content.set_dash_pattern(dasharray.iter().copied(), stroke.dashoffset());
+    }
+}
+
+pub fn draw_path(path: &usvg::tiny_skia_path::Path, content: &mut Content) {
+    let mut start = tiny_skia::Point::zero();
+    let mut last = tiny_skia::Point::zero();
+    for segment in path.segments() {
+        match segment {
+            PathSegment::MoveTo(p) => {
+                content.move_to(p.x, p.y);
+                start = p;
+                last = p;
+            }
+            PathSegment::LineTo(p) => {
+                content.line_to(p.x, p.y);
+                last = p;
+            }
+            PathSegment::QuadTo(p1, p) => {
+                // PDF has no quadratic curves. Convert into a cubic one.
+                let c1x = last.x + 2.0 / 3.0 * (p1.x - last.x);
+                let c1y = last.y + 2.0 / 3.0 * (p1.y - last.y);
+                let c2x = p.x + 2.0 / 3.0 * (p1.x - p.x);
+                let c2y = p.y + 2.0 / 3.0 * (p1.y - p.y);
+                content.cubic_to(c1x, c1y, c2x, c2y, p.x, p.y);
+                last = p;
+            }
+            PathSegment::CubicTo(p1, p2, p) => {
+                content.cubic_to(p1.x, p1.y, p2.x, p2.y, p.x, p.y);
+                last = p;
+            }
+            PathSegment::Close => {
+                content.close_path();
+                last = start;
+            }
+        }
+    }
+}
@@ -0,0 +1,339 @@
+// Copyright 2025 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use std::collections::{BTreeMap, HashMap};
+
+use pdf_writer::types::{CidFontType, FontFlags, SystemInfo, TextRenderingMode, UnicodeCmap};
+use pdf_writer::{Filter, Finish, Name, Ref, Str};
+use usvg::fontdb;
+
+use super::{Canvas, Context};
+
+/// Fonts used by the document.
+///
+/// Fonts are written only after the whole tree was rendered,
+/// because widths and the ToUnicode map depend on the used glyphs.
+#[derive(Default)]
+pub struct FontCache {
+    /// Maps a font database and a face ID to an index in `fonts`.
+    ///
+    /// `None` indicates that the face cannot be embedded.
+    indices: HashMap<(usize, fontdb::ID), Option<usize>>,
+    fonts: Vec<Font>,
+}
+
+struct Font {
+    reference: Ref,
+    data: Vec<u8>,
+    is_cff: bool,
+    units_per_em: u16,
+    /// Used glyphs with the text they represent.
+    glyphs: BTreeMap<u16, String>,
+}
+
+pub fn render(
+    text: &usvg::Text,
+    ctx: &mut Context,
+    canvas: &mut Canvas,
+    transform: tiny_skia::Transform,
+) {
+    // Color, bitmap and SVG glyphs cannot be represented by a PDF font.
+    // Neither can variable font instances, since only the default one is embedded.
+    // Render such text as paths instead.
+    let mut embeddable = true;
+    for span in text.layouted() {
+        for glyph in &span.positioned_glyphs {
+            embeddable &= glyph.variations().is_empty() && font_index(glyph.font, ctx).is_some();
+        }
+    }
+
+    if !embeddable {
+        super::render_group(text.flattened(), ctx, canvas, transform);
+        return;
+    }
+
+    for span in text.layouted() {
+        if !span.visible {
+            continue;
+        }
+
+        if let Some(ref path) = span.underline {
+            super::path::render(path, ctx, canvas, transform);
+        }
+
+        if let Some(ref path) = span.overline {
+            super::path::render(path, ctx, canvas, transform);
+        }
+
+        if span.paint_order == usvg::PaintOrder::FillAndStroke {
+            fill_glyphs(span, text, ctx, canvas, transform);
+            stroke_glyphs(span, text, ctx, canvas, transform);
+        } else {
+            stroke_glyphs(span, text, ctx, canvas, transform);
+            fill_glyphs(span, text, ctx, canvas, transform);
+        }
+
+        if let Some(ref path) = span.line_through {
+            super::path::render(path, ctx, canvas, transform);
+        }
+    }
+}
+
+fn fill_glyphs(
+    span: &usvg::layout::Span,
+    text: &usvg::Text,
+    ctx: &mut Context,
+    canvas: &mut Canvas,
+    transform: tiny_skia::Transform,
+) -> Option<()> {
+    let fill = span.fill.as_ref()?;
+
+    canvas.content.save_state();
+    super::paint::set_fill(fill, text.bounding_box(), ctx, canvas, transform);
+    canvas
+        .content
+        .set_text_rendering_mode(TextRenderingMode::Fill);
+    show_glyphs(span, ctx, canvas);
+    canvas.content.restore_state();
+
+    Some(())
+}
+
+fn stroke_glyphs(
+    span: &usvg::layout::Span,
+    text: &usvg::Text,
+    ctx: &mut Context,
+    canvas: &mut Canvas,
+    transform: tiny_skia::Transform,
+) -> Option<()> {
+    let stroke = span.stroke.as_ref()?;
+
+    canvas.content.save_state();
+    super::paint::set_stroke(stroke, text.stroke_bounding_box(), ctx, canvas, transform);
+    canvas
+        .content
+        .set_text_rendering_mode(TextRenderingMode::Stroke);
+    show_glyphs(span, ctx, canvas);
+    canvas.content.restore_state();
+
+    Some(())
+}
+
+fn show_glyphs(span: &usvg::layout::Span, ctx: &mut Context, canvas: &mut Canvas) {
+    canvas.content.begin_text();
+
+    let mut current_font = None;
+    for glyph in &span.positioned_glyphs {
+        let Some(idx) = font_index(glyph.font, ctx) else {
+            continue;
+        };
+
+        let font = &mut ctx.fonts.fonts[idx];
+        font.glyphs
+            .entry(glyph.id.0)
+            .or_insert_with(|| glyph.text.clone());
+
+        if current_font != Some(idx) {
+            canvas.set_font(font.reference, 1.0);
+            current_font = Some(idx);
+        }
+
+        // Text space is measured in ems, while the glyph transform expects font units.
+        let units_per_em = font.units_per_em as f32;
+        let ts = glyph
+            .outline_transform()
+            .pre_scale(units_per_em, units_per_em);
+        canvas.content.set_text_matrix(super::convert_transform(ts));
+        canvas.content.show(Str(&glyph.id.0.to_be_bytes()));
+    }
+
+    canvas.content.end_text();
+}
+
+/// Returns an index of the font in the cache, loading it if needed.
+///
+/// Returns `None` when the font cannot be embedded.
+fn font_index(id: fontdb::ID, ctx: &mut Context) -> Option<usize> {
+    let key = (std::sync::Arc::as_ptr(&ctx.fontdb) as usize, id);
+    if let Some(idx) = ctx.fonts.indices.get(&key) {
+        return *idx;
+    }
+
+    let font = ctx.fontdb.with_face_data(id, |data, index| {
+        // Only standalone fonts can be embedded. Not collections.
+        if index != 0 {
+            return None;
+        }
+
+        let face = ttf_parser::Face::parse(data, index).ok()?;
+        let tables = face.tables();
+        if tables.colr.is_some()
+            || tables.svg.is_some()
+            || tables.sbix.is_some()
+            || tables.cbdt.is_some()
+        {
+            return None;
+        }
+
+        let is_cff = match (tables.glyf.is_some(), tables.cff.is_some()) {
+            (true, _) => false,
+            (false, true) => true,
+            (false, false) => return None,
+        };
+
+        Some((data.to_vec(), is_cff, face.units_per_em()))
+    });
+
+    let idx = match font.flatten() {
+        Some((data, is_cff, units_per_em)) => {
+            let font = Font {
+                reference: ctx.alloc_ref(),
+                data,
+                is_cff,
+                units_per_em,
+                glyphs: BTreeMap::new(),
+            };
+            ctx.fonts.fonts.push(font);
+            Some(ctx.fonts.fonts.len() - 1)
+        }
+        None => {
+            log::warn!("Failed to embed a font. Text will be rendered as paths.");
+            None
+        }
+    };
+
+    ctx.fonts.indices.insert(key, idx);
+    idx
+}
+
+fn system_info() -> SystemInfo<'static> {
+    SystemInfo {
+        registry: Str(b"Adobe"),
+        ordering: Str(b"Identity"),
+        supplement: 0,
+    }
+}
+
+/// Writes all used fonts as Type 0 fonts with the Identity-H encoding.
+///
+/// Character IDs are glyph IDs.
+pub fn write_fonts(ctx: &mut Context) {
+    let fonts = std::mem::take(&mut ctx.fonts.fonts);
+    for (idx, font) in fonts.iter().enumerate() {
+        let Ok(face) = ttf_parser::Face::parse(&font.data, 0) else {
+            continue;
+        };
+
+        let cid_ref = ctx.alloc_ref();
+        let descriptor_ref = ctx.alloc_ref();
+        let file_ref = ctx.alloc_ref();
+        let cmap_ref = ctx.alloc_ref();
+
+        let name = postscript_name(&face).unwrap_or_else(|| format!("Font{}", idx));
+        let base_font = Name(name.as_bytes());
+        let to_pdf_units = |v: f32| v * 1000.0 / font.units_per_em as f32;
+
+        ctx.chunk
+            .type0_font(font.reference)
+            .base_font(base_font)
+            .encoding_predefined(Name(b"Identity-H"))
+            .descendant_font(cid_ref)
+            .to_unicode(cmap_ref);
+
+        let mut cid_font = ctx.chunk.cid_font(cid_ref);
+        cid_font
+            .subtype(if font.is_cff {
+                CidFontType::Type0
+            } else {
+                CidFontType::Type2
+            })
+            .base_font(base_font)
+            .system_info(system_info())
+            .font_descriptor(descriptor_ref)
+            .default_width(0.0);
+        if !font.is_cff {
+            cid_font.cid_to_gid_map_predefined(Name(b"Identity"));
+        }
+        {
+            let mut widths = cid_font.widths();
+            for id in font.glyphs.keys() {
+                let advance = face
+                    .glyph_hor_advance(ttf_parser::GlyphId(*id))
+                    .unwrap_or(0);
+                widths.consecutive(*id, [to_pdf_units(advance as f32)]);
+            }
+        }
+        cid_font.finish();
+
+        let bbox = face.global_bounding_box();
+        let mut flags = FontFlags::SYMBOLIC;
+        if face.is_italic() {
+            flags |= FontFlags::ITALIC;
+        }
+
+        let mut descriptor = ctx.chunk.font_descriptor(descriptor_ref);
+        descriptor
+            .name(base_font)
+            .flags(flags)
+            .bbox(pdf_writer::Rect::new(
+                to_pdf_units(bbox.x_min as f32),
+                to_pdf_units(bbox.y_min as f32),
+                to_pdf_units(bbox.x_max as f32),
+                to_pdf_units(bbox.y_max as f32),
+            ))
+            .italic_angle(face.italic_angle())
+            .ascent(to_pdf_units(face.ascender() as f32))
+            .descent(to_pdf_units(face.descender() as f32))
+            .cap_height(to_pdf_units(
+                face.capital_height().unwrap_or(face.ascender()) as f32,
+            ))
+            .stem_v(80.0);
+        if font.is_cff {
+            descriptor.font_file3(file_ref);
+        } else {
+            descriptor.font_file2(file_ref);
+        }
+        descriptor.finish();
+
+        let data = super::deflate(&font.data);
+        let mut stream = ctx.chunk.stream(file_ref, &data);
+        stream.filter(Filter::FlateDecode);
+        if font.is_cff {
+            stream.pair(Name(b"Subtype"), Name(b"OpenType"));
+        }
+        stream.finish();
+
+        let mut cmap = UnicodeCmap::new(Name(b"Custom"), system_info());
+        for (id, text) in &font.glyphs {
+            if !text.is_empty() {
+                cmap.pair_with_multiple(*id, text.chars());
+            }
+        }
+        let data = super::deflate(&cmap.finish());
+        ctx.chunk
+            .cmap(cmap_ref, &data)
+            .name(Name(b"Custom"))
+            .system_info(system_info())
+            .filter(Filter::FlateDecode);
+    }
+}
+
+/// Returns a font PostScript name that can be used as a PDF name.
+fn postscript_name(face: &ttf_parser::Face) -> Option<String> {
+    let name = face
+        .names()
+        .into_iter()
+        .filter(|name| name.name_id == ttf_parser::name_id::POST_SCRIPT_NAME)
+        .find_map(|name| name.to_string())?;
+
+    let name: String = name
+        .chars()
+        .filter(|c| c.is_ascii_graphic() && !"()<>[]{}/%#".contains(*c))
+        .collect();
+
+    if name.is_empty() {
+        None
+    } else {
+        Some(name)
+    }
+}
@@ -0,0 +1,1382 @@
+// Copyright 2025 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// Path edges as built and walked by the tiny-skia scan converter.
+//
+// Based on tiny-skia (https://github.com/linebender/tiny-skia),
+// which is based on Skia.
+//
+// Licensed under 'BSD 3-Clause License'.
+//
+// The results have to match tiny-skia bit for bit,
+// therefore the code mirrors the original one as close as possible.
+
+use tiny_skia_path::path_geometry::{find_unit_quad_roots, new_t_values};
+use tiny_skia_path::{NormalizedF32Exclusive, Path, PathVerb, Point, Rect, Scalar};
+
+use super::geometry;
+
+/// A 26.6 fixed point.
+type FDot6 = i32;
+
+/// A 16.16 fixed point.
+type FDot16 = i32;
+
+/// We store 1<<shift in a (signed) byte, so its maximum value is 1<<6 == 64.
+const MAX_COEFF_SHIFT: i32 = 6;
+
+fn left_shift(value: i32, shift: i32) -> i32 {
+    ((value as u32) << shift) as i32
+}
+
+mod fdot6 {
+    use super::*;
+
+    pub fn round(n: FDot6) -> FDot6 {
+        (n + 32) >> 6
+    }
+
+    pub fn to_fdot16(n: FDot6) -> FDot16 {
+        left_shift(n, 10)
+    }
+
+    pub fn div(a: FDot6, b: FDot6) -> FDot16 {
+        if i16::try_from(a).is_ok() {
+            left_shift(a, 16) / b
+        } else {
+            fdot16::div(a, b)
+        }
+    }
+}
+
+mod fdot16 {
+    use super::*;
+
+    pub fn round_to_i32(x: FDot16) -> i32 {
+        x.wrapping_add(1 << 15) >> 16
+    }
+
+    pub fn mul(a: FDot16, b: FDot16) -> FDot16 {
+        ((i64::from(a) * i64::from(b)) >> 16) as FDot16
+    }
+
+    pub fn div(numer: FDot6, denom: FDot6) -> FDot16 {
+        let v = ((numer as i64 as u64) << 16) as i64 / denom as i64;
+        v.clamp(i32::MIN as i64, i32::MAX as i64) as i32
+    }
+}
+
+/// A path edge that is walked line by line.
+#[derive(Clone, Debug)]
+pub enum Edge {
+    Line(LineEdge),
+    Quadratic(QuadraticEdge),
+    Cubic(CubicEdge),
+}
+
+impl Edge {
+    fn as_line(&self) -> &LineEdge {
+        match self {
+            Edge::Line(line) => line,
+            Edge::Quadratic(quad) => &quad.line,
+            Edge::Cubic(cubic) => &cubic.line,
+        }
+    }
+
+    /// Walks the edge like the tiny-skia scan converter does.
+    ///
+    /// `start_y..stop_y` are the scanlines walked by the scan converter
+    /// and `from_y..to_y` are the scanlines `f` is called for.
+    /// `f` receives a scanline, the rounded edge X on it and the edge winding.
+    pub fn walk<F>(mut self, start_y: i32, stop_y: i32, from_y: i32, to_y: i32, mut f: F)
+    where
+        F: FnMut(i32, i32, i8),
+    {
+        let stop_y = stop_y.min(to_y);
+        let winding = self.as_line().winding;
+
+        // An edge is picked up by the scan converter on the first walked scanline.
+        let mut y = self.as_line().first_y.max(start_y);
+        loop {
+            let line = self.as_line();
+            // An edge that ends before it is picked up is never removed.
+            let last_y = if line.last_y >= y {
+                line.last_y
+            } else {
+                i32::MAX
+            };
+
+            for row in y.max(from_y)..=last_y.min(stop_y - 1) {
+                let x = line.x.wrapping_add(line.dx.wrapping_mul(row - y));
+                f(row, fdot16::round_to_i32(x), winding);
+            }
+
+            if last_y >= stop_y {
+                return;
+            }
+
+            y = last_y + 1;
+            let updated = match self {
+                Edge::Line(_) => false,
+                Edge::Quadratic(ref mut quad) => quad.curve_count > 0 && quad.update(),
+                Edge::Cubic(ref mut cubic) => cubic.curve_count < 0 && cubic.update(),
+            };
+
+            if !updated {
+                return;
+            }
+        }
+    }
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct LineEdge {
+    x: FDot16,
+    dx: FDot16,
+    first_y: i32,
+    last_y: i32,
+    winding: i8, // 1 or -1
+}
+
+impl LineEdge {
+    fn new(p0: Point, p1: Point, shift: i32) -> Option<Self> {
+        let scale = (1 << (shift + 6)) as f32;
+        let mut x0 = (p0.x * scale) as i32;
+        let mut y0 = (p0.y * scale) as i32;
+        let mut x1 = (p1.x * scale) as i32;
+        let mut y1 = (p1.y * scale) as i32;
+
+        let mut winding = 1;
+
+        if y0 > y1 {
+            std::mem::swap(&mut x0, &mut x1);
+            std::mem::swap(&mut y0, &mut y1);
+            winding = -1;
+        }
+
+        let top = fdot6::round(y0);
+        let bottom = fdot6::round(y1);
+
+        // Zero-height line.
+        if top == bottom {
+            return None;
+        }
+
+        let slope = fdot6::div(x1 - x0, y1 - y0);
+        let dy = compute_dy(top, y0);
+
+        Some(LineEdge {
+            x: fdot6::to_fdot16(x0 + fdot16::mul(slope, dy)),
+            dx: slope,
+            first_y: top,
+            last_y: bottom - 1,
+            winding,
+        })
+    }
+
+    fn is_vertical(&self) -> bool {
+        self.dx == 0
+    }
+
+    fn update(&mut self, mut x0: FDot16, mut y0: FDot16, mut x1: FDot16, mut y1: FDot16) -> bool {
+        y0 >>= 10;
+        y1 >>= 10;
+
+        let top = fdot6::round(y0);
+        let bottom = fdot6::round(y1);
+
+        // Zero-height line.
+        if top == bottom {
+            return false;
+        }
+
+        x0 >>= 10;
+        x1 >>= 10;
+
+        let slope = fdot6::div(x1 - x0, y1 - y0);
+        let dy = compute_dy(top, y0);
+
+        self.x = fdot6::to_fdot16(x0 + fdot16::mul(slope, dy));
+        self.dx = slope;
+        self.first_y = top;
+        self.last_y = bottom - 1;
+
+        true
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct QuadraticEdge {
+    line: LineEdge,
+    curve_count: i8,
+    curve_shift: u8, // applied to all dx/ddx/dddx
+    qx: FDot16,
+    qy: FDot16,
+    qdx: FDot16,
+    qdy: FDot16,
+    qddx: FDot16,
+    qddy: FDot16,
+    q_last_x: FDot16,
+    q_last_y: FDot16,
+}
+
+impl QuadraticEdge {
+    fn new(points: &[Point], shift: i32) -> Option<Self> {
+        let mut quad = Self::new2(points, shift)?;
+        if quad.update() {
+            Some(quad)
+        } else {
+            None
+        }
+    }
+
+    fn new2(points: &[Point], mut shift: i32) -> Option<Self> {
+        let scale = (1 << (shift + 6)) as f32;
+        let mut x0 = (points[0].x * scale) as i32;
+        let mut y0 = (points[0].y * scale) as i32;
+        let x1 = (points[1].x * scale) as i32;
+        let y1 = (points[1].y * scale) as i32;
+        let mut x2 = (points[2].x * scale) as i32;
+        let mut y2 = (points[2].y * scale) as i32;
+
+        let mut winding = 1;
+        if y0 > y2 {
+            std::mem::swap(&mut x0, &mut x2);
+            std::mem::swap(&mut y0, &mut y2);
+            winding = -1;
+        }
+
+        let top = fdot6::round(y0);
+        let bottom = fdot6::round(y2);
+
+        // Zero-height quad.
+        if top == bottom {
+            return None;
+        }
+
+        // Compute number of steps needed (1 << shift).
+        {
+            let dx = (left_shift(x1, 1) - x0 - x2) >> 2;
+            let dy = (left_shift(y1, 1) - y0 - y2) >> 2;
+            // Before this line, shift is the scale up factor for AA;
+            // after this line, shift is the curve shift.
+            shift = diff_to_shift(dx, dy, shift);
+        }
+
+        // Need at least 1 subdivision for our bias trick.
+        if shift == 0 {
+            shift = 1;
+        } else if shift > MAX_COEFF_SHIFT {
+            shift = MAX_COEFF_SHIFT;
+        }
+
+        let curve_count = (1 << shift) as i8;
+
+        // A and B are stored at 1/2 of their actual value to guard against overflow.
+        let curve_shift = (shift - 1) as u8;
+
+        let mut a = fdot6_to_fixed_div2(x0 - x1 - x1 + x2);
+        let mut b = fdot6::to_fdot16(x1 - x0);
+
+        let qx = fdot6::to_fdot16(x0);
+        let qdx = b + (a >> shift);
+        let qddx = a >> (shift - 1);
+
+        a = fdot6_to_fixed_div2(y0 - y1 - y1 + y2);
+        b = fdot6::to_fdot16(y1 - y0);
+
+        let qy = fdot6::to_fdot16(y0);
+        let qdy = b + (a >> shift);
+        let qddy = a >> (shift - 1);
+
+        let q_last_x = fdot6::to_fdot16(x2);
+        let q_last_y = fdot6::to_fdot16(y2);
+
+        Some(QuadraticEdge {
+            line: LineEdge {
+                winding,
+                ..LineEdge::default()
+            },
+            curve_count,
+            curve_shift,
+            qx,
+            qy,
+            qdx,
+            qdy,
+            qddx,
+            qddy,
+            q_last_x,
+            q_last_y,
+        })
+    }
+
+    fn update(&mut self) -> bool {
+        let mut success;
+        let mut count = self.curve_count;
+        let mut oldx = self.qx;
+        let mut oldy = self.qy;
+        let mut dx = self.qdx;
+        let mut dy = self.qdy;
+        let mut newx;
+        let mut newy;
+        let shift = self.curve_shift;
+
+        loop {
+            count -= 1;
+            if count > 0 {
+                newx = oldx + (dx >> shift);
+                dx += self.qddx;
+                newy = oldy + (dy >> shift);
+                dy += self.qddy;
+            } else {
+                // Last segment.
+                newx = self.q_last_x;
+                newy = self.q_last_y;
+            }
+            success = self.line.update(oldx, oldy, newx, newy);
+            oldx = newx;
+            oldy = newy;
+
+            if count == 0 || success {
+                break;
+            }
+        }
+
+        self.qx = newx;
+        self.qy = newy;
+        self.qdx = dx;
+        self.qdy = dy;
+        self.curve_count = count;
+
+        success
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CubicEdge {
+    line: LineEdge,
+    curve_count: i8,
+    curve_shift: u8, // applied to all dx/ddx/dddx except for dshift exception
+    dshift: u8,      // applied to cdx and cdy
+    cx: FDot16,
+    cy: FDot16,
+    cdx: FDot16,
+    cdy: FDot16,
+    cddx: FDot16,
+    cddy: FDot16,
+    cdddx: FDot16,
+    cdddy: FDot16,
+    c_last_x: FDot16,
+    c_last_y: FDot16,
+}
+
+impl CubicEdge {
+    fn new(points: &[Point], shift: i32) -> Option<Self> {
+        let mut cubic = Self::new2(points, shift)?;
+        if cubic.update() {
+            Some(cubic)
+        } else {
+            None
+        }
+    }
+
+    fn new2(points: &[Point], mut shift: i32) -> Option<Self> {
+        let scale = (1 << (shift + 6)) as f32;
+        let mut x0 = (points[0].x * scale) as i32;
+        let mut y0 = (points[0].y * scale) as i32;
+        let mut x1 = (points[1].x * scale) as i32;
+        let mut y1 = (points[1].y * scale) as i32;
+        let mut x2 = (points[2].x * scale) as i32;
+        let mut y2 = (points[2].y * scale) as i32;
+        let mut x3 = (points[3].x * scale) as i32;
+        let mut y3 = (points[3].y * scale) as i32;
+
+        let mut winding = 1;
+        if y0 > y3 {
+            std::mem::swap(&mut x0, &mut x3);
+            std::mem::swap(&mut x1, &mut x2);
+            std::mem::swap(&mut y0, &mut y3);
+            std::mem::swap(&mut y1, &mut y2);
+            winding = -1;
+        }
+
+        let top = fdot6::round(y0);
+        let bot = fdot6::round(y3);
+
+        // Zero-height cubic.
+        if top == bot {
+            return None;
+        }
+
+        // Compute number of steps needed (1 << shift).
+        {
+            // Can't use (center of curve - center of baseline), since center-of-curve
+            // need not be the max delta from the baseline (it could even be coincident)
+            // so we try just looking at the two off-curve points.
+            let dx = cubic_delta_from_line(x0, x1, x2, x3);
+            let dy = cubic_delta_from_line(y0, y1, y2, y3);
+            // Add 1 (by observation).
+            shift = diff_to_shift(dx, dy, 2) + 1;
+        }
+        // Need at least 1 subdivision for our bias trick.
+        if shift > MAX_COEFF_SHIFT {
+            shift = MAX_COEFF_SHIFT;
+        }
+
+        // Since our in coming data is initially shifted down by 10 (or 8 in
+        // antialias). That means the most we can shift up is 8. However, we
+        // compute coefficients with a 3*, so the safest upshift is really 6.
+        let mut up_shift = 6;
+        let mut down_shift = shift + up_shift - 10;
+        if down_shift < 0 {
+            down_shift = 0;
+            up_shift = 10 - shift;
+        }
+
+        let curve_count = left_shift(-1, shift) as i8;
+        let curve_shift = shift as u8;
+        let dshift = down_shift as u8;
+
+        let mut b = left_shift(3 * (x1 - x0), up_shift);
+        let mut c = left_shift(3 * (x0 - x1 - x1 + x2), up_shift);
+        let mut d = left_shift(x3 + 3 * (x1 - x2) - x0, up_shift);
+
+        let cx = fdot6::to_fdot16(x0);
+        let cdx = b + (c >> shift) + (d >> (2 * shift));
+        let cddx = 2 * c + ((3 * d) >> (shift - 1));
+        let cdddx = (3 * d) >> (shift - 1);
+
+        b = left_shift(3 * (y1 - y0), up_shift);
+        c = left_shift(3 * (y0 - y1 - y1 + y2), up_shift);
+        d = left_shift(y3 + 3 * (y1 - y2) - y0, up_shift);
+
+        let cy = fdot6::to_fdot16(y0);
+        let cdy = b + (c >> shift) + (d >> (2 * shift));
+        let cddy = 2 * c + ((3 * d) >> (shift - 1));
+        let cdddy = (3 * d) >> (shift - 1);
+
+        let c_last_x = fdot6::to_fdot16(x3);
+        let c_last_y = fdot6::to_fdot16(y3);
+
+        Some(CubicEdge {
+            line: LineEdge {
+                winding,
+                ..LineEdge::default()
+            },
+            curve_count,
+            curve_shift,
+            dshift,
+            cx,
+            cy,
+            cdx,
+            cdy,
+            cddx,
+            cddy,
+            cdddx,
+            cdddy,
+            c_last_x,
+            c_last_y,
+        })
+    }
+
+    fn update(&mut self) -> bool {
+        let mut success;
+        let mut count = self.curve_count;
+        let mut oldx = self.cx;
+        let mut oldy = self.cy;
+        let mut newx;
+        let mut newy;
+        let ddshift = self.curve_shift;
+        let dshift = self.dshift;
+
+        loop {
+            count += 1;
+            if count < 0 {
+                newx = oldx + (self.cdx >> dshift);
+                self.cdx += self.cddx >> ddshift;
+                self.cddx += self.cdddx;
+
+                newy = oldy + (self.cdy >> dshift);
+                self.cdy += self.cddy >> ddshift;
+                self.cddy += self.cdddy;
+            } else {
+                // Last segment.
+                newx = self.c_last_x;
+                newy = self.c_last_y;
+            }
+
+            // Our finite fixed point doesn't always keep the curve monotonic,
+            // so we have to explicitly pin it here.
+            if newy < oldy {
+                newy = oldy;
+            }
+
+            success = self.line.update(oldx, oldy, newx, newy);
+            oldx = newx;
+            oldy = newy;
+
+            if count == 0 || success {
+                break;
+            }
+        }
+
+        self.cx = newx;
+        self.cy = newy;
+        self.curve_count = count;
+
+        success
+    }
+}
+
+// This correctly favors the lower-pixel when y0 is on a 1/2 pixel boundary.
+fn compute_dy(top: FDot6, y0: FDot6) -> FDot6 {
+    left_shift(top, 6) + 32 - y0
+}
+
+fn diff_to_shift(dx: FDot6, dy: FDot6, shift_aa: i32) -> i32 {
+    // Cheap calc of distance from center of p0-p2 to the center of the curve.
+    let mut dist = cheap_distance(dx, dy);
+
+    // Shift down dist (it is currently in dot6).
+    // Down by 3 should give us 1/8 pixel accuracy (assuming our dist is accurate...).
+    dist = (dist + (1 << 4)) >> (3 + shift_aa);
+
+    // Each subdivision (shift value) cuts this dist (error) by 1/4.
+    (32 - dist.leading_zeros() as i32) >> 1
+}
+
+fn cheap_distance(mut dx: FDot6, mut dy: FDot6) -> FDot6 {
+    dx = dx.abs();
+    dy = dy.abs();
+    // Return max + min/2.
+    if dx > dy {
+        dx + (dy >> 1)
+    } else {
+        dy + (dx >> 1)
+    }
+}
+
+fn fdot6_to_fixed_div2(value: FDot6) -> FDot16 {
+    // We want to return (value >> 1) converted to a fixed point,
+    // but we don't want to throw away data in value, so just perform a modify up-shift.
+    left_shift(value, 16 - 6 - 1)
+}
+
+// Uses 16/512 to approximate 1/27.
+fn cubic_delta_from_line(a: FDot6, b: FDot6, c: FDot6, d: FDot6) -> FDot6 {
+    // Since our parameters may be negative, we don't use <<.
+    let one_third = ((a * 8 - b * 15 + 6 * c + d) * 19) >> 9;
+    let two_third = ((a + 6 * b - c * 15 + d * 8) * 19) >> 9;
+
+    one_third.abs().max(two_third.abs())
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum Combine {
+    No,
+    Partial,
+    Total,
+}
+
+/// Builds path edges.
+///
+/// `clip` is set when the path is not contained in the clip rect.
+/// `shift` is 2 for anti-aliased paths and 0 otherwise.
+///
+/// Returns `None` when there is nothing to fill.
+pub fn build_edges(path: &Path, clip: Option<&Rect>, shift: i32) -> Option<Vec<Edge>> {
+    let mut builder = EdgeBuilder {
+        edges: Vec::new(),
+        shift,
+    };
+
+    if let Some(clip) = clip {
+        for edge in edge_iter(path) {
+            let mut clipper = EdgeClipper {
+                clip: *clip,
+                edges: Vec::new(),
+            };
+
+            match edge {
+                PathEdge::Line(p0, p1) => clipper.clip_line(p0, p1),
+                PathEdge::Quad(p0, p1, p2) => clipper.clip_quad(p0, p1, p2),
+                PathEdge::Cubic(p0, p1, p2, p3) => clipper.clip_cubic(p0, p1, p2, p3),
+            }
+
+            for edge in clipper.edges {
+                match edge {
+                    PathEdge::Line(p0, p1) => {
+                        if !p0.is_finite() || !p1.is_finite() {
+                            return None;
+                        }
+
+                        builder.push_line(p0, p1);
+                    }
+                    PathEdge::Quad(p0, p1, p2) => {
+                        if !p0.is_finite() || !p1.is_finite() || !p2.is_finite() {
+                            return None;
+                        }
+
+                        builder.push_quad(&[p0, p1, p2]);
+                    }
+                    PathEdge::Cubic(p0, p1, p2, p3) => {
+                        if !p0.is_finite() || !p1.is_finite() || !p2.is_finite() || !p3.is_finite()
+                        {
+                            return None;
+                        }
+
+                        builder.push_cubic(&[p0, p1, p2, p3]);
+                    }
+                }
+            }
+        }
+    } else {
+        for edge in edge_iter(path) {
+            match edge {
+                PathEdge::Line(p0, p1) => builder.push_line(p0, p1),
+                PathEdge::Quad(p0, p1, p2) => {
+                    let mut mono_y = [Point::zero(); 5];
+                    let n = geometry::chop_quad_at_y_extrema(&[p0, p1, p2], &mut mono_y);
+                    for i in 0..=n {
+                        builder.push_quad(&mono_y[i * 2..]);
+                    }
+                }
+                PathEdge::Cubic(p0, p1, p2, p3) => {
+                    let mut mono_y = [Point::zero(); 10];
+                    let n = geometry::chop_cubic_at_y_extrema(&[p0, p1, p2, p3], &mut mono_y);
+                    for i in 0..=n {
+                        builder.push_cubic(&mono_y[i * 3..]);
+                    }
+                }
+            }
+        }
+    }
+
+    if builder.edges.len() < 2 {
+        return None;
+    }
+
+    Some(builder.edges)
+}
+
+struct EdgeBuilder {
+    edges: Vec<Edge>,
+    shift: i32,
+}
+
+impl EdgeBuilder {
+    fn push_line(&mut self, p0: Point, p1: Point) {
+        let Some(edge) = LineEdge::new(p0, p1, self.shift) else {
+            return;
+        };
+
+        let combine = match self.edges.last_mut() {
+            Some(Edge::Line(last)) if edge.is_vertical() => combine_vertical(&edge, last),
+            _ => Combine::No,
+        };
+
+        match combine {
+            Combine::Total => {
+                self.edges.pop();
+            }
+            Combine::Partial => {}
+            Combine::No => self.edges.push(Edge::Line(edge)),
+        }
+    }
+
+    fn push_quad(&mut self, points: &[Point]) {
+        if let Some(edge) = QuadraticEdge::new(points, self.shift) {
+            self.edges.push(Edge::Quadratic(edge));
+        }
+    }
+
+    fn push_cubic(&mut self, points: &[Point]) {
+        if let Some(edge) = CubicEdge::new(points, self.shift) {
+            self.edges.push(Edge::Cubic(edge));
+        }
+    }
+}
+
+fn combine_vertical(edge: &LineEdge, last: &mut LineEdge) -> Combine {
+    if last.dx != 0 || edge.x != last.x {
+        return Combine::No;
+    }
+
+    if edge.winding == last.winding {
+        return if edge.last_y + 1 == last.first_y {
+            last.first_y = edge.first_y;
+            Combine::Partial
+        } else if edge.first_y == last.last_y + 1 {
+            last.last_y = edge.last_y;
+            Combine::Partial
+        } else {
+            Combine::No
+        };
+    }
+
+    if edge.first_y == last.first_y {
+        return if edge.last_y == last.last_y {
+            Combine::Total
+        } else if edge.last_y < last.last_y {
+            last.first_y = edge.last_y + 1;
+            Combine::Partial
+        } else {
+            last.first_y = last.last_y + 1;
+            last.last_y = edge.last_y;
+            last.winding = edge.winding;
+            Combine::Partial
+        };
+    }
+
+    if edge.last_y == last.last_y {
+        if edge.first_y > last.first_y {
+            last.last_y = edge.first_y - 1;
+        } else {
+            last.last_y = last.first_y - 1;
+            last.first_y = edge.first_y;
+            last.winding = edge.winding;
+        }
+
+        return Combine::Partial;
+    }
+
+    Combine::No
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum PathEdge {
+    Line(Point, Point),
+    Quad(Point, Point, Point),
+    Cubic(Point, Point, Point, Point),
+}
+
+/// Iterates over path segments, closing each contour.
+fn edge_iter(path: &Path) -> impl Iterator<Item = PathEdge> + '_ {
+    let points = path.points();
+    let mut verbs = path.verbs().iter();
+    let mut index = 0;
+    let mut move_to = Point::zero();
+    let mut needs_close_line = false;
+
+    std::iter::from_fn(move || loop {
+        let Some(verb) = verbs.next() else {
+            if needs_close_line {
+                needs_close_line = false;
+                return Some(PathEdge::Line(points[index - 1], move_to));
+            }
+
+            return None;
+        };
+
+        match verb {
+            PathVerb::Move => {
+                let close = needs_close_line.then(|| PathEdge::Line(points[index - 1], move_to));
+                needs_close_line = false;
+                move_to = points[index];
+                index += 1;
+                if close.is_some() {
+                    return close;
+                }
+            }
+            PathVerb::Close => {
+                if needs_close_line {
+                    needs_close_line = false;
+                    return Some(PathEdge::Line(points[index - 1], move_to));
+                }
+            }
+            PathVerb::Line => {
+                needs_close_line = true;
+                index += 1;
+                return Some(PathEdge::Line(points[index - 2], points[index - 1]));
+            }
+            PathVerb::Quad => {
+                needs_close_line = true;
+                index += 2;
+                return Some(PathEdge::Quad(
+                    points[index - 3],
+                    points[index - 2],
+                    points[index - 1],
+                ));
+            }
+            PathVerb::Cubic => {
+                needs_close_line = true;
+                index += 3;
+                return Some(PathEdge::Cubic(
+                    points[index - 4],
+                    points[index - 3],
+                    points[index - 2],
+                    points[index - 1],
+                ));
+            }
+        }
+    })
+}
+
+/// Clips path segments for the scan converter.
+///
+/// Segments above and below the clip are removed and segments to the left
+/// and to the right are turned into vertical lines at the clip edges.
+struct EdgeClipper {
+    clip: Rect,
+    edges: Vec<PathEdge>,
+}
+
+impl EdgeClipper {
+    fn clip_line(&mut self, p0: Point, p1: Point) {
+        let mut points = [Point::zero(); 4];
+        let points = clip_line(&[p0, p1], &self.clip, &mut points);
+        for pair in points.windows(2) {
+            self.edges.push(PathEdge::Line(pair[0], pair[1]));
+        }
+    }
+
+    fn push_vline(&mut self, x: f32, mut y0: f32, mut y1: f32, reverse: bool) {
+        if reverse {
+            std::mem::swap(&mut y0, &mut y1);
+        }
+
+        self.edges
+            .push(PathEdge::Line(Point::from_xy(x, y0), Point::from_xy(x, y1)));
+    }
+
+    fn clip_quad(&mut self, p0: Point, p1: Point, p2: Point) {
+        let pts = [p0, p1, p2];
+        let Some(bounds) = Rect::from_points(&pts) else {
+            return;
+        };
+
+        if bounds.top() >= self.clip.bottom() || bounds.bottom() <= self.clip.top() {
+            return;
+        }
+
+        let mut mono_y = [Point::zero(); 5];
+        let count_y = geometry::chop_quad_at_y_extrema(&pts, &mut mono_y);
+        for y in 0..=count_y {
+            let y_points = [mono_y[y * 2], mono_y[y * 2 + 1], mono_y[y * 2 + 2]];
+            let mut mono_x = [Point::zero(); 5];
+            let count_x = geometry::chop_quad_at_x_extrema(&y_points, &mut mono_x);
+            for x in 0..=count_x {
+                self.clip_mono_quad(&[mono_x[x * 2], mono_x[x * 2 + 1], mono_x[x * 2 + 2]]);
+            }
+        }
+    }
+
+    // `src` must be monotonic in X and Y.
+    fn clip_mono_quad(&mut self, src: &[Point; 3]) {
+        let mut pts = [Point::zero(); 3];
+        let mut reverse = sort_increasing_y(src, &mut pts);
+
+        // Completely above or below.
+        if pts[2].y <= self.clip.top() || pts[0].y >= self.clip.bottom() {
+            return;
+        }
+
+        // Chop so that pts is contained within clip in Y.
+        chop_quad_in_y(&self.clip, &mut pts);
+
+        if pts[0].x > pts[2].x {
+            pts.swap(0, 2);
+            reverse = !reverse;
+        }
+
+        // Chop in X as needed, and record the segments.
+
+        if pts[2].x <= self.clip.left() {
+            // Wholly to the left.
+            self.push_vline(self.clip.left(), pts[0].y, pts[2].y, reverse);
+            return;
+        }
+
+        if pts[0].x >= self.clip.right() {
+            // Wholly to the right.
+            self.push_vline(self.clip.right(), pts[0].y, pts[2].y, reverse);
+            return;
+        }
+
+        let mut tmp = [Point::zero(); 5];
+
+        // Partially to the left.
+        if pts[0].x < self.clip.left() {
+            if let Some(t) = chop_mono_quad_at(pts[0].x, pts[1].x, pts[2].x, self.clip.left()) {
+                geometry::chop_quad_at(&pts, t, &mut tmp);
+                self.push_vline(self.clip.left(), tmp[0].y, tmp[2].y, reverse);
+                // Clamp to clean up imprecise numerics in the chop.
+                tmp[2].x = self.clip.left();
+                tmp[3].x = tmp[3].x.max(self.clip.left());
+
+                pts[0] = tmp[2];
+                pts[1] = tmp[3];
+            } else {
+                // Inexact numerics, so just clamp against the left.
+                self.push_vline(self.clip.left(), pts[0].y, pts[2].y, reverse);
+                return;
+            }
+        }
+
+        // Partially to the right.
+        if pts[2].x > self.clip.right() {
+            if let Some(t) = chop_mono_quad_at(pts[0].x, pts[1].x, pts[2].x, self.clip.right()) {
+                geometry::chop_quad_at(&pts, t, &mut tmp);
+                // Clamp to clean up imprecise numerics in the chop.
+                tmp[1].x = tmp[1].x.min(self.clip.right());
+                tmp[2].x = self.clip.right();
+
+                self.push_quad(&[tmp[0], tmp[1], tmp[2]], reverse);
+                self.push_vline(self.clip.right(), tmp[2].y, tmp[4].y, reverse);
+            } else {
+                // Inexact numerics, so just clamp against the right.
+                pts[1].x = pts[1].x.min(self.clip.right());
+                pts[2].x = pts[2].x.min(self.clip.right());
+                self.push_quad(&pts, reverse);
+            }
+        } else {
+            // Wholly inside the clip.
+            self.push_quad(&pts, reverse);
+        }
+    }
+
+    fn push_quad(&mut self, pts: &[Point; 3], reverse: bool) {
+        if reverse {
+            self.edges.push(PathEdge::Quad(pts[2], pts[1], pts[0]));
+        } else {
+            self.edges.push(PathEdge::Quad(pts[0], pts[1], pts[2]));
+        }
+    }
+
+    fn clip_cubic(&mut self, p0: Point, p1: Point, p2: Point, p3: Point) {
+        let pts = [p0, p1, p2, p3];
+        let Some(bounds) = Rect::from_points(&pts) else {
+            return;
+        };
+
+        // Clipped out vertically.
+        if bounds.bottom() <= self.clip.top() || bounds.top() >= self.clip.bottom() {
+            return;
+        }
+
+        if too_big_for_reliable_float_math(&bounds) {
+            // Can't safely clip the cubic, so draw a line instead.
+            self.clip_line(p0, p3);
+            return;
+        }
+
+        let mut mono_y = [Point::zero(); 10];
+        let count_y = geometry::chop_cubic_at_y_extrema(&pts, &mut mono_y);
+        for y in 0..=count_y {
+            let y_points = [
+                mono_y[y * 3],
+                mono_y[y * 3 + 1],
+                mono_y[y * 3 + 2],
+                mono_y[y * 3 + 3],
+            ];
+            let mut mono_x = [Point::zero(); 10];
+            let count_x = geometry::chop_cubic_at_x_extrema(&y_points, &mut mono_x);
+            for x in 0..=count_x {
+                self.clip_mono_cubic(&[
+                    mono_x[x * 3],
+                    mono_x[x * 3 + 1],
+                    mono_x[x * 3 + 2],
+                    mono_x[x * 3 + 3],
+                ]);
+            }
+        }
+    }
+
+    // `src` must be monotonic in X and Y.
+    fn clip_mono_cubic(&mut self, src: &[Point; 4]) {
+        let mut pts = [Point::zero(); 4];
+        let mut reverse = sort_increasing_y(src, &mut pts);
+
+        // Completely above or below.
+        if pts[3].y <= self.clip.top() || pts[0].y >= self.clip.bottom() {
+            return;
+        }
+
+        // Chop so that pts is contained within clip in Y.
+        chop_cubic_in_y(&self.clip, &mut pts);
+
+        if pts[0].x > pts[3].x {
+            pts.swap(0, 3);
+            pts.swap(1, 2);
+            reverse = !reverse;
+        }
+
+        // Chop in X as needed, and record the segments.
+
+        if pts[3].x <= self.clip.left() {
+            // Wholly to the left.
+            self.push_vline(self.clip.left(), pts[0].y, pts[3].y, reverse);
+            return;
+        }
+
+        if pts[0].x >= self.clip.right() {
+            // Wholly to the right.
+            self.push_vline(self.clip.right(), pts[0].y, pts[3].y, reverse);
+            return;
+        }
+
+        // Partially to the left.
+        if pts[0].x < self.clip.left() {
+            let mut tmp = [Point::zero(); 7];
+            geometry::chop_mono_cubic_at_x(&pts, self.clip.left(), &mut tmp);
+            self.push_vline(self.clip.left(), tmp[0].y, tmp[3].y, reverse);
+
+            // We can't trust the numerics of the chopper, so force the X values to be inside.
+            tmp[3].x = self.clip.left();
+            tmp[4].x = tmp[4].x.max(self.clip.left());
+
+            pts[0] = tmp[3];
+            pts[1] = tmp[4];
+            pts[2] = tmp[5];
+        }
+
+        // Partially to the right.
+        if pts[3].x > self.clip.right() {
+            let mut tmp = [Point::zero(); 7];
+            geometry::chop_mono_cubic_at_x(&pts, self.clip.right(), &mut tmp);
+            tmp[3].x = self.clip.right();
+            tmp[2].x = tmp[2].x.min(self.clip.right());
+
+            self.push_cubic(&[tmp[0], tmp[1], tmp[2], tmp[3]], reverse);
+            self.push_vline(self.clip.right(), tmp[3].y, tmp[6].y, reverse);
+        } else {
+            // Wholly inside the clip.
+            self.push_cubic(&pts, reverse);
+        }
+    }
+
+    fn push_cubic(&mut self, pts: &[Point; 4], reverse: bool) {
+        if reverse {
+            self.edges
+                .push(PathEdge::Cubic(pts[3], pts[2], pts[1], pts[0]));
+        } else {
+            self.edges
+                .push(PathEdge::Cubic(pts[0], pts[1], pts[2], pts[3]));
+        }
+    }
+}
+
+// `src` must be monotonic in Y. Copies `src` into `dst` sorted to be increasing in Y.
+// Returns true when the order was reversed.
+fn sort_increasing_y(src: &[Point], dst: &mut [Point]) -> bool {
+    if src[0].y > src[src.len() - 1].y {
+        for (d, p) in dst.iter_mut().zip(src.iter().rev()) {
+            *d = *p;
+        }
+
+        true
+    } else {
+        dst[0..src.len()].copy_from_slice(src);
+        false
+    }
+}
+
+/// Modifies `pts` in place so that it is clipped in Y to the clip rect.
+fn chop_quad_in_y(clip: &Rect, pts: &mut [Point; 3]) {
+    let mut tmp = [Point::zero(); 5];
+
+    // Partially above.
+    if pts[0].y < clip.top() {
+        if let Some(t) = chop_mono_quad_at(pts[0].y, pts[1].y, pts[2].y, clip.top()) {
+            // Take the 2nd chopped quad.
+            geometry::chop_quad_at(pts, t, &mut tmp);
+            // Clamp to clean up imprecise numerics in the chop.
+            tmp[2].y = clip.top();
+            tmp[3].y = tmp[3].y.max(clip.top());
+
+            pts[0] = tmp[2];
+            pts[1] = tmp[3];
+        } else {
+            // Inexact numerics, so just clamp against the top.
+            for p in pts.iter_mut() {
+                if p.y < clip.top() {
+                    p.y = clip.top();
+                }
+            }
+        }
+    }
+
+    // Partially below.
+    if pts[2].y > clip.bottom() {
+        if let Some(t) = chop_mono_quad_at(pts[0].y, pts[1].y, pts[2].y, clip.bottom()) {
+            geometry::chop_quad_at(pts, t, &mut tmp);
+            // Clamp to clean up imprecise numerics in the chop.
+            tmp[1].y = tmp[1].y.min(clip.bottom());
+            tmp[2].y = clip.bottom();
+
+            pts[1] = tmp[1];
+            pts[2] = tmp[2];
+        } else {
+            // Inexact numerics, so just clamp against the bottom.
+            for p in pts.iter_mut() {
+                if p.y > clip.bottom() {
+                    p.y = clip.bottom();
+                }
+            }
+        }
+    }
+}
+
+fn chop_mono_quad_at(c0: f32, c1: f32, c2: f32, target: f32) -> Option<NormalizedF32Exclusive> {
+    // Solve F(t) = y where F(t) := [0](1-t)^2 + 2[1]t(1-t) + [2]t^2
+    let a = c0 - c1 - c1 + c2;
+    let b = 2.0 * (c1 - c0);
+    let c = c0 - target;
+
+    let mut roots = new_t_values();
+    let count = find_unit_quad_roots(a, b, c, &mut roots);
+    (count != 0).then_some(roots[0])
+}
+
+fn too_big_for_reliable_float_math(r: &Rect) -> bool {
+    // Chosen by experiment in Skia.
+    let limit = (1 << 22) as f32;
+    r.left() < -limit || r.top() < -limit || r.right() > limit || r.bottom() > limit
+}
+
+/// Modifies `pts` in place so that it is clipped in Y to the clip rect.
+fn chop_cubic_in_y(clip: &Rect, pts: &mut [Point; 4]) {
+    // Partially above.
+    if pts[0].y < clip.top() {
+        let mut tmp = [Point::zero(); 7];
+        geometry::chop_mono_cubic_at_y(pts, clip.top(), &mut tmp);
+
+        // For a large range in the points, the chop can leave the lower cubic partly
+        // above the clip. When the first 3 Ys are above the top, re-chop the lower cubic.
+        if tmp[3].y < clip.top() && tmp[4].y < clip.top() && tmp[5].y < clip.top() {
+            let tmp2 = [tmp[3], tmp[4], tmp[5], tmp[6]];
+            geometry::chop_mono_cubic_at_y(&tmp2, clip.top(), &mut tmp);
+        }
+
+        // We can't trust the numerics of the chopper, so force the Y values to be inside.
+        tmp[3].y = clip.top();
+        tmp[4].y = tmp[4].y.max(clip.top());
+
+        pts[0] = tmp[3];
+        pts[1] = tmp[4];
+        pts[2] = tmp[5];
+    }
+
+    // Partially below.
+    if pts[3].y > clip.bottom() {
+        let mut tmp = [Point::zero(); 7];
+        geometry::chop_mono_cubic_at_y(pts, clip.bottom(), &mut tmp);
+        tmp[3].y = clip.bottom();
+        tmp[2].y = tmp[2].y.min(clip.bottom());
+
+        pts[1] = tmp[1];
+        pts[2] = tmp[2];
+        pts[3] = tmp[3];
+    }
+}
+
+/// Clips a line against the clip, turning the parts to the left and to the right
+/// into vertical lines at the clip edges.
+///
+/// Returns the line points, sequentially.
+fn clip_line<'a>(src: &[Point; 2], clip: &Rect, points: &'a mut [Point; 4]) -> &'a [Point] {
+    let (mut index0, mut index1) = if src[0].y < src[1].y { (0, 1) } else { (1, 0) };
+
+    // Completely above or below.
+    if src[index1].y <= clip.top() || src[index0].y >= clip.bottom() {
+        return &[];
+    }
+
+    // Chop in Y to produce a single segment.
+    let mut tmp = *src;
+    if src[index0].y < clip.top() {
+        tmp[index0] = Point::from_xy(sect_with_horizontal(src, clip.top()), clip.top());
+    }
+
+    if tmp[index1].y > clip.bottom() {
+        tmp[index1] = Point::from_xy(sect_with_horizontal(src, clip.bottom()), clip.bottom());
+    }
+
+    // Chop it into 1..3 segments that are wholly within the clip in X.
+    let mut result_storage = [Point::zero(); 4];
+    let mut line_count = 1;
+    let mut reverse;
+
+    if src[0].x < src[1].x {
+        index0 = 0;
+        index1 = 1;
+        reverse = false;
+    } else {
+        index0 = 1;
+        index1 = 0;
+        reverse = true;
+    }
+
+    let result: &[Point] = if tmp[index1].x <= clip.left() {
+        // Wholly to the left.
+        tmp[0].x = clip.left();
+        tmp[1].x = clip.left();
+        reverse = false;
+        &tmp
+    } else if tmp[index0].x >= clip.right() {
+        // Wholly to the right.
+        tmp[0].x = clip.right();
+        tmp[1].x = clip.right();
+        reverse = false;
+        &tmp
+    } else {
+        let mut offset = 0;
+
+        if tmp[index0].x < clip.left() {
+            result_storage[offset] = Point::from_xy(clip.left(), tmp[index0].y);
+            offset += 1;
+            result_storage[offset] =
+                Point::from_xy(clip.left(), sect_clamp_with_vertical(&tmp, clip.left()));
+        } else {
+            result_storage[offset] = tmp[index0];
+        }
+        offset += 1;
+
+        if tmp[index1].x > clip.right() {
+            result_storage[offset] =
+                Point::from_xy(clip.right(), sect_clamp_with_vertical(&tmp, clip.right()));
+            offset += 1;
+            result_storage[offset] = Point::from_xy(clip.right(), tmp[index1].y);
+        } else {
+            result_storage[offset] = tmp[index1];
+        }
+
+        line_count = offset;
+        &result_storage
+    };
+
+    if reverse {
+        // Copy the points in reverse order to maintain winding order.
+        for i in 0..=line_count {
+            points[line_count - i] = result[i];
+        }
+    } else {
+        points[0..=line_count].copy_from_slice(&result[0..=line_count]);
+    }
+
+    &points[0..=line_count]
+}
+
+/// Intersects a line with the clip.
+///
+/// Returns `None` when the line is outside the clip.
+pub fn intersect_line(src: &[Point; 2], clip: &Rect) -> Option<[Point; 2]> {
+    fn nested_lt(a: f32, b: f32, dim: f32) -> bool {
+        a <= b && (a < b || dim > 0.0)
+    }
+
+    let bounds = Rect::from_ltrb(
+        src[0].x.min(src[1].x),
+        src[0].y.min(src[1].y),
+        src[0].x.max(src[1].x),
+        src[0].y.max(src[1].y),
+    );
+
+    if let Some(bounds) = bounds {
+        if clip.left() <= bounds.left()
+            && clip.top() <= bounds.top()
+            && clip.right() >= bounds.right()
+            && clip.bottom() >= bounds.bottom()
+        {
+            return Some(*src);
+        }
+
+        // Check for no overlap, and only permit coincident edges if the line
+        // and the edge are colinear.
+        if nested_lt(bounds.right(), clip.left(), bounds.width())
+            || nested_lt(clip.right(), bounds.left(), bounds.width())
+            || nested_lt(bounds.bottom(), clip.top(), bounds.height())
+            || nested_lt(clip.bottom(), bounds.top(), bounds.height())
+        {
+            return None;
+        }
+    }
+
+    let (index0, index1) = if src[0].y < src[1].y { (0, 1) } else { (1, 0) };
+
+    let mut tmp = *src;
+
+    // Compute Y intersections.
+    if tmp[index0].y < clip.top() {
+        tmp[index0] = Point::from_xy(sect_with_horizontal(src, clip.top()), clip.top());
+    }
+
+    if tmp[index1].y > clip.bottom() {
+        tmp[index1] = Point::from_xy(sect_with_horizontal(src, clip.bottom()), clip.bottom());
+    }
+
+    let (index0, index1) = if tmp[0].x < tmp[1].x { (0, 1) } else { (1, 0) };
+
+    // Check for quick-reject in X again, now that we may have been chopped.
+    if (tmp[index1].x <= clip.left() || tmp[index0].x >= clip.right())
+        && (tmp[0].x != tmp[1].x || tmp[0].x < clip.left() || tmp[0].x > clip.right())
+    {
+        // A vertical line coincident with the clip is kept.
+        return None;
+    }
+
+    if tmp[index0].x < clip.left() {
+        tmp[index0] = Point::from_xy(clip.left(), sect_with_vertical(src, clip.left()));
+    }
+
+    if tmp[index1].x > clip.right() {
+        tmp[index1] = Point::from_xy(clip.right(), sect_with_vertical(src, clip.right()));
+    }
+
+    Some(tmp)
+}
+
+/// Returns X coordinate of intersection with horizontal line at Y.
+fn sect_with_horizontal(src: &[Point; 2], y: f32) -> f32 {
+    let dy = src[1].y - src[0].y;
+    if dy.is_nearly_zero() {
+        src[0].x.ave(src[1].x)
+    } else {
+        // Need the extra precision so we don't compute a value that exceeds our original limits.
+        let x0 = f64::from(src[0].x);
+        let y0 = f64::from(src[0].y);
+        let x1 = f64::from(src[1].x);
+        let y1 = f64::from(src[1].y);
+        let result = x0 + (f64::from(y) - y0) * (x1 - x0) / (y1 - y0);
+
+        // The computed X value might still exceed [X0..X1] due to quantum flux
+        // when the doubles were added and subtracted, so we have to pin the answer.
+        pin_unsorted(result, x0, x1) as f32
+    }
+}
+
+fn sect_clamp_with_vertical(src: &[Point; 2], x: f32) -> f32 {
+    let y = sect_with_vertical(src, x);
+    // The Y value might be slightly outside of [Y0..Y1], so we have to pin the answer.
+    pin_unsorted(f64::from(y), f64::from(src[0].y), f64::from(src[1].y)) as f32
+}
+
+/// Returns Y coordinate of intersection with vertical line at X.
+fn sect_with_vertical(src: &[Point; 2], x: f32) -> f32 {
+    let dx = src[1].x - src[0].x;
+    if dx.is_nearly_zero() {
+        src[0].y.ave(src[1].y)
+    } else {
+        // Need the extra precision so we don't compute a value that exceeds our original limits.
+        let x0 = f64::from(src[0].x);
+        let y0 = f64::from(src[0].y);
+        let x1 = f64::from(src[1].x);
+        let y1 = f64::from(src[1].y);
+        let result = y0 + (f64::from(x) - x0) * (y1 - y0) / (x1 - x0);
+        result as f32
+    }
+}
+
+fn pin_unsorted(value: f64, mut limit0: f64, mut limit1: f64) -> f64 {
+    if limit1 < limit0 {
+        std::mem::swap(&mut limit0, &mut limit1);
+    }
+
+    if value < limit0 {
+        limit0
+    } else if value > limit1 {
+        limit1
+    } else {
+        value
+    }
+}
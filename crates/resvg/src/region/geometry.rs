@@ -0,0 +1,884 @@
+// Copyright 2025 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// Curve chopping used by the tiny-skia scan converter.
+//
+// Based on tiny-skia (https://github.com/linebender/tiny-skia),
+// which is based on Skia.
+//
+// Licensed under 'BSD 3-Clause License'.
+//
+// The results have to match tiny-skia bit for bit,
+// therefore the code mirrors the original one as close as possible.
+
+use tiny_skia_path::path_geometry::{
+    chop_cubic_at2, find_cubic_max_curvature, find_unit_quad_roots, new_t_values, valid_unit_divide,
+};
+use tiny_skia_path::{NormalizedF32, NormalizedF32Exclusive, Point, Scalar, SCALAR_MAX};
+
+pub use tiny_skia_path::path_geometry::chop_quad_at;
+
+/// Chops a quad at its X extrema.
+///
+/// Returns 0 for one quad and 1 for two quads, either way the answer is stored in `dst`.
+pub fn chop_quad_at_x_extrema(src: &[Point; 3], dst: &mut [Point; 5]) -> usize {
+    let a = src[0].x;
+    let mut b = src[1].x;
+    let c = src[2].x;
+
+    if is_not_monotonic(a, b, c) {
+        if let Some(t_value) = valid_unit_divide(a - b, a - b - b + c) {
+            chop_quad_at(src, t_value, dst);
+
+            // Flatten double quad extrema.
+            dst[1].x = dst[2].x;
+            dst[3].x = dst[2].x;
+
+            return 1;
+        }
+
+        // Force `dst` to be monotonic, even though we couldn't compute a unit divide value.
+        b = if (a - b).abs() < (b - c).abs() { a } else { c };
+    }
+
+    dst[0] = Point::from_xy(a, src[0].y);
+    dst[1] = Point::from_xy(b, src[1].y);
+    dst[2] = Point::from_xy(c, src[2].y);
+    0
+}
+
+/// Chops a quad at its Y extrema.
+///
+/// Returns 0 for one quad and 1 for two quads, either way the answer is stored in `dst`.
+pub fn chop_quad_at_y_extrema(src: &[Point; 3], dst: &mut [Point; 5]) -> usize {
+    let a = src[0].y;
+    let mut b = src[1].y;
+    let c = src[2].y;
+
+    if is_not_monotonic(a, b, c) {
+        if let Some(t_value) = valid_unit_divide(a - b, a - b - b + c) {
+            chop_quad_at(src, t_value, dst);
+
+            // Flatten double quad extrema.
+            dst[1].y = dst[2].y;
+            dst[3].y = dst[2].y;
+
+            return 1;
+        }
+
+        // Force `dst` to be monotonic, even though we couldn't compute a unit divide value.
+        b = if (a - b).abs() < (b - c).abs() { a } else { c };
+    }
+
+    dst[0] = Point::from_xy(src[0].x, a);
+    dst[1] = Point::from_xy(src[1].x, b);
+    dst[2] = Point::from_xy(src[2].x, c);
+    0
+}
+
+fn is_not_monotonic(a: f32, b: f32, c: f32) -> bool {
+    let ab = a - b;
+    let mut bc = b - c;
+    if ab < 0.0 {
+        bc = -bc;
+    }
+
+    ab == 0.0 || bc < 0.0
+}
+
+/// Chops a cubic at its X extrema.
+///
+/// Returns the number of chops. The cubics are stored in `dst` sharing the end points.
+pub fn chop_cubic_at_x_extrema(src: &[Point; 4], dst: &mut [Point; 10]) -> usize {
+    let mut t_values = new_t_values();
+    let t_values = find_cubic_extrema(src[0].x, src[1].x, src[2].x, src[3].x, &mut t_values);
+
+    chop_cubic_at(src, t_values, dst);
+    if !t_values.is_empty() {
+        // Make sure X extrema are flat.
+        dst[2].x = dst[3].x;
+        dst[4].x = dst[3].x;
+        if t_values.len() == 2 {
+            dst[5].x = dst[6].x;
+            dst[7].x = dst[6].x;
+        }
+    }
+
+    t_values.len()
+}
+
+/// Chops a cubic at its Y extrema.
+///
+/// Returns the number of chops. The cubics are stored in `dst` sharing the end points.
+pub fn chop_cubic_at_y_extrema(src: &[Point; 4], dst: &mut [Point; 10]) -> usize {
+    let mut t_values = new_t_values();
+    let t_values = find_cubic_extrema(src[0].y, src[1].y, src[2].y, src[3].y, &mut t_values);
+
+    chop_cubic_at(src, t_values, dst);
+    if !t_values.is_empty() {
+        // Make sure Y extrema are flat.
+        dst[2].y = dst[3].y;
+        dst[4].y = dst[3].y;
+        if t_values.len() == 2 {
+            dst[5].y = dst[6].y;
+            dst[7].y = dst[6].y;
+        }
+    }
+
+    t_values.len()
+}
+
+fn find_cubic_extrema(
+    a: f32,
+    b: f32,
+    c: f32,
+    d: f32,
+    t_values: &mut [NormalizedF32Exclusive; 3],
+) -> &[NormalizedF32Exclusive] {
+    // Cubic'(t) = At^2 + Bt + C, divided by 3.
+    let na = d - a + 3.0 * (b - c);
+    let nb = 2.0 * (a - b - b + c);
+    let nc = b - a;
+
+    let roots = find_unit_quad_roots(na, nb, nc, t_values);
+    &t_values[0..roots]
+}
+
+fn chop_cubic_at(src: &[Point; 4], t_values: &[NormalizedF32Exclusive], dst: &mut [Point]) {
+    if t_values.is_empty() {
+        dst[0..4].copy_from_slice(src);
+        return;
+    }
+
+    let mut t = t_values[0];
+    let mut tmp = [Point::zero(); 4];
+    let mut src = src;
+    let mut dst_offset = 0;
+    for i in 0..t_values.len() {
+        chop_cubic_at2(src, t, &mut dst[dst_offset..]);
+        if i == t_values.len() - 1 {
+            break;
+        }
+
+        dst_offset += 3;
+        // Continue with the remaining cubic.
+        tmp.copy_from_slice(&dst[dst_offset..dst_offset + 4]);
+        src = &tmp;
+
+        // The renormalized t can be out of range.
+        match valid_unit_divide(
+            t_values[i + 1].get() - t_values[i].get(),
+            1.0 - t_values[i].get(),
+        ) {
+            Some(n) => t = n,
+            None => {
+                // Create a degenerate cubic.
+                dst[dst_offset + 4] = src[3];
+                dst[dst_offset + 5] = src[3];
+                dst[dst_offset + 6] = src[3];
+                break;
+            }
+        }
+    }
+}
+
+/// Chops a cubic at its max curvature points.
+///
+/// Returns the number of cubics stored in `dst`.
+pub fn chop_cubic_at_max_curvature(src: &[Point; 4], dst: &mut [Point; 13]) -> usize {
+    let mut roots = [NormalizedF32::ZERO; 3];
+    let roots = find_cubic_max_curvature(src, &mut roots);
+
+    let mut t_values = new_t_values();
+    let mut count = 0;
+    for root in roots {
+        if 0.0 < root.get() && root.get() < 1.0 {
+            t_values[count] = NormalizedF32Exclusive::new_bounded(root.get());
+            count += 1;
+        }
+    }
+
+    if count == 0 {
+        dst[0..4].copy_from_slice(src);
+    } else {
+        chop_cubic_at(src, &t_values[0..count], dst);
+    }
+
+    count + 1
+}
+
+/// Chops a monotonic cubic at the specified X.
+pub fn chop_mono_cubic_at_x(src: &[Point; 4], x: f32, dst: &mut [Point; 7]) {
+    if cubic_dchop_at_intercept(src, x, true, dst) {
+        return;
+    }
+
+    let values = [src[0].x, src[1].x, src[2].x, src[3].x];
+    chop_cubic_at2(src, mono_cubic_closest_t(&values, x), dst);
+}
+
+/// Chops a monotonic cubic at the specified Y.
+pub fn chop_mono_cubic_at_y(src: &[Point; 4], y: f32, dst: &mut [Point; 7]) {
+    if cubic_dchop_at_intercept(src, y, false, dst) {
+        return;
+    }
+
+    let values = [src[0].y, src[1].y, src[2].y, src[3].y];
+    chop_cubic_at2(src, mono_cubic_closest_t(&values, y), dst);
+}
+
+fn mono_cubic_closest_t(src: &[f32; 4], mut x: f32) -> NormalizedF32Exclusive {
+    let mut t = 0.5;
+    let mut best_t = t;
+    let mut step = 0.25;
+    let d = src[0];
+    let a = src[3] + 3.0 * (src[1] - src[2]) - d;
+    let b = 3.0 * (src[2] - src[1] - src[1] + d);
+    let c = 3.0 * (src[1] - d);
+    x -= d;
+    let mut closest = SCALAR_MAX;
+    loop {
+        let loc = ((a * t + b) * t + c) * t;
+        let dist = (loc - x).abs();
+        if closest > dist {
+            closest = dist;
+            best_t = t;
+        }
+
+        let last_t = t;
+        t += if loc < x { step } else { -step };
+        step *= 0.5;
+
+        if !(closest > 0.25 && last_t != t) {
+            break;
+        }
+    }
+
+    NormalizedF32Exclusive::new_bounded(best_t)
+}
+
+fn cubic_dchop_at_intercept(
+    src: &[Point; 4],
+    intercept: f32,
+    is_vertical: bool,
+    dst: &mut [Point; 7],
+) -> bool {
+    let cubic = Cubic64 {
+        points: src.map(|p| Point64 {
+            x: f64::from(p.x),
+            y: f64::from(p.y),
+        }),
+    };
+
+    let mut roots = [0.0; 3];
+    let count = cubic.intersect(f64::from(intercept), is_vertical, &mut roots);
+    if count == 0 {
+        return false;
+    }
+
+    let points = cubic.chop_at(roots[0]);
+    for (d, p) in dst.iter_mut().zip(points) {
+        *d = Point::from_xy(p.x as f32, p.y as f32);
+    }
+
+    true
+}
+
+// The code below is from Skia's path ops.
+
+const DBL_EPSILON_ERR: f64 = f64::EPSILON * 4.0;
+const FLT_EPSILON_HALF: f64 = (f32::EPSILON / 2.0) as f64;
+const FLT_EPSILON_CUBED: f64 = (f32::EPSILON * f32::EPSILON * f32::EPSILON) as f64;
+const FLT_EPSILON_INVERSE: f64 = 1.0 / f32::EPSILON as f64;
+
+trait Scalar64 {
+    fn bound(self, min: Self, max: Self) -> Self;
+    fn between(self, a: f64, b: f64) -> bool;
+    fn precisely_zero(self) -> bool;
+    fn approximately_zero_or_more(self) -> bool;
+    fn approximately_one_or_less(self) -> bool;
+    fn approximately_zero(self) -> bool;
+    fn approximately_zero_inverse(self) -> bool;
+    fn approximately_zero_cubed(self) -> bool;
+    fn approximately_zero_half(self) -> bool;
+    fn approximately_zero_when_compared_to(self, other: Self) -> bool;
+    fn approximately_equal(self, other: Self) -> bool;
+    fn approximately_equal_half(self, other: Self) -> bool;
+    fn almost_dequal_ulps(self, other: Self) -> bool;
+}
+
+impl Scalar64 for f64 {
+    fn bound(self, min: Self, max: Self) -> Self {
+        max.min(self).max(min)
+    }
+
+    fn between(self, a: f64, b: f64) -> bool {
+        debug_assert!(
+            ((a <= self && self <= b) || (a >= self && self >= b))
+                == ((a - self) * (b - self) <= 0.0)
+                || (a.precisely_zero() && self.precisely_zero() && b.precisely_zero())
+        );
+
+        (a - self) * (b - self) <= 0.0
+    }
+
+    fn precisely_zero(self) -> bool {
+        self.abs() < DBL_EPSILON_ERR
+    }
+
+    fn approximately_zero_or_more(self) -> bool {
+        self > -f64::EPSILON
+    }
+
+    fn approximately_one_or_less(self) -> bool {
+        self < 1.0 + f64::EPSILON
+    }
+
+    fn approximately_zero(self) -> bool {
+        self.abs() < f64::EPSILON
+    }
+
+    fn approximately_zero_inverse(self) -> bool {
+        self.abs() > FLT_EPSILON_INVERSE
+    }
+
+    fn approximately_zero_cubed(self) -> bool {
+        self.abs() < FLT_EPSILON_CUBED
+    }
+
+    fn approximately_zero_half(self) -> bool {
+        self < FLT_EPSILON_HALF
+    }
+
+    fn approximately_zero_when_compared_to(self, other: Self) -> bool {
+        self == 0.0 || self.abs() < (other * (f32::EPSILON as f64)).abs()
+    }
+
+    fn approximately_equal(self, other: Self) -> bool {
+        (self - other).approximately_zero()
+    }
+
+    fn approximately_equal_half(self, other: Self) -> bool {
+        (self - other).approximately_zero_half()
+    }
+
+    fn almost_dequal_ulps(self, other: Self) -> bool {
+        if self.abs() < SCALAR_MAX as f64 && other.abs() < SCALAR_MAX as f64 {
+            (self as f32).almost_dequal_ulps(other as f32)
+        } else {
+            (self - other).abs() / self.abs().max(other.abs()) < (f32::EPSILON * 16.0) as f64
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+struct Point64 {
+    x: f64,
+    y: f64,
+}
+
+impl Point64 {
+    fn axis_coord(&self, is_vertical: bool) -> f64 {
+        if is_vertical {
+            self.x
+        } else {
+            self.y
+        }
+    }
+}
+
+struct Cubic64 {
+    points: [Point64; 4],
+}
+
+impl Cubic64 {
+    fn as_f64_slice(&self) -> [f64; 8] {
+        [
+            self.points[0].x,
+            self.points[0].y,
+            self.points[1].x,
+            self.points[1].y,
+            self.points[2].x,
+            self.points[2].y,
+            self.points[3].x,
+            self.points[3].y,
+        ]
+    }
+
+    /// Finds the intersections with a vertical or a horizontal line.
+    fn intersect(&self, axis_intercept: f64, is_vertical: bool, roots: &mut [f64; 3]) -> usize {
+        let values = self.as_f64_slice();
+        // Y coordinates start from the second value.
+        let values = if is_vertical {
+            &values[..]
+        } else {
+            &values[1..]
+        };
+
+        let (a, b, c, mut d) = cubic_coefficients(values);
+        d -= axis_intercept;
+        let mut count = cubic_roots_valid_t(a, b, c, d, roots);
+        let mut index = 0;
+        while index < count {
+            let calc_pt = self.point_at_t(roots[index]);
+            if !calc_pt
+                .axis_coord(is_vertical)
+                .approximately_equal(axis_intercept)
+            {
+                let mut extreme_ts = [0.0; 6];
+                let extrema = cubic_find_extrema(values, &mut extreme_ts);
+                count =
+                    self.search_roots(extrema, axis_intercept, is_vertical, &mut extreme_ts, roots);
+                break;
+            }
+
+            index += 1;
+        }
+
+        count
+    }
+
+    fn point_at_t(&self, t: f64) -> Point64 {
+        if t == 0.0 {
+            return self.points[0];
+        }
+
+        if t == 1.0 {
+            return self.points[3];
+        }
+
+        let one_t = 1.0 - t;
+        let one_t2 = one_t * one_t;
+        let a = one_t2 * one_t;
+        let b = 3.0 * one_t2 * t;
+        let t2 = t * t;
+        let c = 3.0 * one_t * t2;
+        let d = t2 * t;
+        Point64 {
+            x: a * self.points[0].x
+                + b * self.points[1].x
+                + c * self.points[2].x
+                + d * self.points[3].x,
+            y: a * self.points[0].y
+                + b * self.points[1].y
+                + c * self.points[2].y
+                + d * self.points[3].y,
+        }
+    }
+
+    fn search_roots(
+        &self,
+        mut extrema: usize,
+        axis_intercept: f64,
+        is_vertical: bool,
+        extreme_ts: &mut [f64; 6],
+        valid_roots: &mut [f64],
+    ) -> usize {
+        extrema += self.find_inflections(&mut extreme_ts[extrema..]);
+        extreme_ts[extrema] = 0.0;
+        extrema += 1;
+        extreme_ts[extrema] = 1.0;
+        debug_assert!(extrema < 6);
+        // Skia doesn't care about NaN during sorting.
+        extreme_ts[0..extrema]
+            .sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        let mut valid_count = 0;
+        let mut index = 0;
+        while index < extrema {
+            let min = extreme_ts[index];
+            index += 1;
+            let max = extreme_ts[index];
+            if min == max {
+                continue;
+            }
+
+            let new_t = self.binary_search(min, max, axis_intercept, is_vertical);
+            if new_t >= 0.0 {
+                if valid_count >= 3 {
+                    return 0;
+                }
+
+                valid_roots[valid_count] = new_t;
+                valid_count += 1;
+            }
+        }
+
+        valid_count
+    }
+
+    fn find_inflections(&self, t_values: &mut [f64]) -> usize {
+        let p = &self.points;
+        let ax = p[1].x - p[0].x;
+        let ay = p[1].y - p[0].y;
+        let bx = p[2].x - 2.0 * p[1].x + p[0].x;
+        let by = p[2].y - 2.0 * p[1].y + p[0].y;
+        let cx = p[3].x + 3.0 * (p[1].x - p[2].x) - p[0].x;
+        let cy = p[3].y + 3.0 * (p[1].y - p[2].y) - p[0].y;
+        quad_roots_valid_t(
+            bx * cy - by * cx,
+            ax * cy - ay * cx,
+            ax * by - ay * bx,
+            t_values,
+        )
+    }
+
+    // Gives up when changing t no longer moves the point.
+    fn binary_search(&self, min: f64, max: f64, axis_intercept: f64, is_vertical: bool) -> f64 {
+        let mut t = (min + max) / 2.0;
+        let mut step = (t - min) / 2.0;
+        let mut cubic_at_t = self.point_at_t(t);
+        let mut calc_pos = cubic_at_t.axis_coord(is_vertical);
+        let mut calc_dist = calc_pos - axis_intercept;
+        loop {
+            let prior_t = min.max(t - step);
+            let less_pt = self.point_at_t(prior_t);
+            if less_pt.x.approximately_equal_half(cubic_at_t.x)
+                && less_pt.y.approximately_equal_half(cubic_at_t.y)
+            {
+                return -1.0;
+            }
+
+            let less_dist = less_pt.axis_coord(is_vertical) - axis_intercept;
+            let last_step = step;
+            step /= 2.0;
+            let ok = if calc_dist > 0.0 {
+                calc_dist > less_dist
+            } else {
+                calc_dist < less_dist
+            };
+            if ok {
+                t = prior_t;
+            } else {
+                let next_t = t + last_step;
+                if next_t > max {
+                    return -1.0;
+                }
+
+                let more_pt = self.point_at_t(next_t);
+                if more_pt.x.approximately_equal_half(cubic_at_t.x)
+                    && more_pt.y.approximately_equal_half(cubic_at_t.y)
+                {
+                    return -1.0;
+                }
+
+                let more_dist = more_pt.axis_coord(is_vertical) - axis_intercept;
+                let ok = if calc_dist > 0.0 {
+                    calc_dist <= more_dist
+                } else {
+                    calc_dist >= more_dist
+                };
+                if ok {
+                    continue;
+                }
+
+                t = next_t;
+            }
+
+            cubic_at_t = self.point_at_t(t);
+            calc_pos = cubic_at_t.axis_coord(is_vertical);
+            calc_dist = calc_pos - axis_intercept;
+
+            if calc_pos.approximately_equal(axis_intercept) {
+                break;
+            }
+        }
+
+        t
+    }
+
+    fn chop_at(&self, t: f64) -> [Point64; 7] {
+        let p = &self.points;
+        let mut dst = [Point64::default(); 7];
+        if t == 0.5 {
+            dst[0] = p[0];
+            dst[1].x = (p[0].x + p[1].x) / 2.0;
+            dst[1].y = (p[0].y + p[1].y) / 2.0;
+            dst[2].x = (p[0].x + 2.0 * p[1].x + p[2].x) / 4.0;
+            dst[2].y = (p[0].y + 2.0 * p[1].y + p[2].y) / 4.0;
+            dst[3].x = (p[0].x + 3.0 * (p[1].x + p[2].x) + p[3].x) / 8.0;
+            dst[3].y = (p[0].y + 3.0 * (p[1].y + p[2].y) + p[3].y) / 8.0;
+            dst[4].x = (p[1].x + 2.0 * p[2].x + p[3].x) / 4.0;
+            dst[4].y = (p[1].y + 2.0 * p[2].y + p[3].y) / 4.0;
+            dst[5].x = (p[2].x + p[3].x) / 2.0;
+            dst[5].y = (p[2].y + p[3].y) / 2.0;
+            dst[6] = p[3];
+        } else {
+            let xs = interp_cubic_coords([p[0].x, p[1].x, p[2].x, p[3].x], t);
+            let ys = interp_cubic_coords([p[0].y, p[1].y, p[2].y, p[3].y], t);
+            for (d, (x, y)) in dst.iter_mut().zip(xs.into_iter().zip(ys)) {
+                *d = Point64 { x, y };
+            }
+        }
+
+        dst
+    }
+}
+
+fn cubic_coefficients(src: &[f64]) -> (f64, f64, f64, f64) {
+    let mut a = src[6]; // d
+    let mut b = src[4] * 3.0; // 3*c
+    let mut c = src[2] * 3.0; // 3*b
+    let d = src[0]; // a
+    a -= d - c + b; // A =   -a + 3*b - 3*c + d
+    b += 3.0 * d - 2.0 * c; // B =  3*a - 6*b + 3*c
+    c -= 3.0 * d; // C = -3*a + 3*b
+
+    (a, b, c, d)
+}
+
+fn cubic_roots_valid_t(a: f64, b: f64, c: f64, d: f64, t: &mut [f64; 3]) -> usize {
+    let mut s = [0.0; 3];
+    let real_roots = cubic_roots_real(a, b, c, d, &mut s);
+    let mut found_roots = push_valid_ts(&s, real_roots, t);
+    'outer: for &t_value in &s[..real_roots] {
+        if !t_value.approximately_one_or_less() && t_value.between(1.0, 1.00005) {
+            for &t2 in &t[..found_roots] {
+                if t2.approximately_equal(1.0) {
+                    continue 'outer;
+                }
+            }
+
+            t[found_roots] = 1.0;
+            found_roots += 1;
+        } else if !t_value.approximately_zero_or_more() && t_value.between(-0.00005, 0.0) {
+            for &t2 in &t[..found_roots] {
+                if t2.approximately_equal(0.0) {
+                    continue 'outer;
+                }
+            }
+
+            t[found_roots] = 0.0;
+            found_roots += 1;
+        }
+    }
+
+    found_roots
+}
+
+fn cubic_roots_real(a: f64, b: f64, c: f64, d: f64, s: &mut [f64; 3]) -> usize {
+    if a.approximately_zero()
+        && a.approximately_zero_when_compared_to(b)
+        && a.approximately_zero_when_compared_to(c)
+        && a.approximately_zero_when_compared_to(d)
+    {
+        // Just a quadratic.
+        return quad_roots_real(b, c, d, s);
+    }
+
+    if d.approximately_zero_when_compared_to(a)
+        && d.approximately_zero_when_compared_to(b)
+        && d.approximately_zero_when_compared_to(c)
+    {
+        // 0 is one root.
+        let mut num = quad_roots_real(a, b, c, s);
+        if s[..num].iter().any(|v| v.approximately_zero()) {
+            return num;
+        }
+
+        s[num] = 0.0;
+        num += 1;
+        return num;
+    }
+
+    if (a + b + c + d).approximately_zero() {
+        // 1 is one root.
+        let mut num = quad_roots_real(a, a + b, -d, s);
+        if s[..num].iter().any(|v| v.almost_dequal_ulps(1.0)) {
+            return num;
+        }
+
+        s[num] = 1.0;
+        num += 1;
+        return num;
+    }
+
+    let (a, b, c) = {
+        let inv_a = 1.0 / a;
+        (b * inv_a, c * inv_a, d * inv_a)
+    };
+
+    let a2 = a * a;
+    let q = (a2 - b * 3.0) / 9.0;
+    let r = (2.0 * a2 * a - 9.0 * a * b + 27.0 * c) / 54.0;
+    let r2 = r * r;
+    let q3 = q * q * q;
+    let r2_minus_q3 = r2 - q3;
+    let adiv3 = a / 3.0;
+    let mut offset = 0;
+    if r2_minus_q3 < 0.0 {
+        // Three real roots.
+        // The divide/root can, due to finite precisions, be slightly outside of -1...1
+        let theta = (r / q3.sqrt()).bound(-1.0, 1.0).acos();
+        let neg2_root_q = -2.0 * q.sqrt();
+
+        let mut rr = neg2_root_q * (theta / 3.0).cos() - adiv3;
+        s[offset] = rr;
+        offset += 1;
+
+        rr = neg2_root_q * ((theta + 2.0 * std::f64::consts::PI) / 3.0).cos() - adiv3;
+        if !s[0].almost_dequal_ulps(rr) {
+            s[offset] = rr;
+            offset += 1;
+        }
+
+        rr = neg2_root_q * ((theta - 2.0 * std::f64::consts::PI) / 3.0).cos() - adiv3;
+        if !s[0].almost_dequal_ulps(rr) && (offset == 1 || !s[1].almost_dequal_ulps(rr)) {
+            s[offset] = rr;
+            offset += 1;
+        }
+    } else {
+        // One real root.
+        let sqrt_r2_minus_q3 = r2_minus_q3.sqrt();
+        let mut a = r.abs() + sqrt_r2_minus_q3;
+        a = cube_root(a);
+        if r > 0.0 {
+            a = -a;
+        }
+
+        if a != 0.0 {
+            a += q / a;
+        }
+
+        let mut r2 = a - adiv3;
+        s[offset] = r2;
+        offset += 1;
+        if r2.almost_dequal_ulps(q3) {
+            r2 = -a / 2.0 - adiv3;
+            if !s[0].almost_dequal_ulps(r2) {
+                s[offset] = r2;
+                offset += 1;
+            }
+        }
+    }
+
+    offset
+}
+
+fn cubic_find_extrema(src: &[f64], t_values: &mut [f64]) -> usize {
+    // Cubic'(t) = At^2 + Bt + C, divided by 3.
+    let a = src[0];
+    let b = src[2];
+    let c = src[4];
+    let d = src[6];
+    let a2 = d - a + 3.0 * (b - c);
+    let b2 = 2.0 * (a - b - b + c);
+    let c2 = b - a;
+
+    quad_roots_valid_t(a2, b2, c2, t_values)
+}
+
+fn interp_cubic_coords(src: [f64; 4], t: f64) -> [f64; 7] {
+    let ab = interp(src[0], src[1], t);
+    let bc = interp(src[1], src[2], t);
+    let cd = interp(src[2], src[3], t);
+    let abc = interp(ab, bc, t);
+    let bcd = interp(bc, cd, t);
+    let abcd = interp(abc, bcd, t);
+    [src[0], ab, abc, abcd, bcd, cd, src[3]]
+}
+
+fn interp(a: f64, b: f64, t: f64) -> f64 {
+    a + (b - a) * t
+}
+
+fn push_valid_ts(s: &[f64], real_roots: usize, t: &mut [f64]) -> usize {
+    let mut found_roots = 0;
+    'outer: for &t_value in &s[..real_roots] {
+        if t_value.approximately_zero_or_more() && t_value.approximately_one_or_less() {
+            let t_value = t_value.bound(0.0, 1.0);
+            for &t2 in &t[..found_roots] {
+                if t2.approximately_equal(t_value) {
+                    continue 'outer;
+                }
+            }
+
+            t[found_roots] = t_value;
+            found_roots += 1;
+        }
+    }
+
+    found_roots
+}
+
+fn quad_roots_valid_t(a: f64, b: f64, c: f64, t: &mut [f64]) -> usize {
+    let mut s = [0.0; 3];
+    let real_roots = quad_roots_real(a, b, c, &mut s);
+    push_valid_ts(&s, real_roots, t)
+}
+
+// Doesn't discard real roots <= 0 or >= 1.
+fn quad_roots_real(a: f64, b: f64, c: f64, s: &mut [f64; 3]) -> usize {
+    fn handle_zero(b: f64, c: f64, s: &mut [f64; 3]) -> usize {
+        if b.approximately_zero() {
+            s[0] = 0.0;
+            (c == 0.0) as usize
+        } else {
+            s[0] = -c / b;
+            1
+        }
+    }
+
+    if a == 0.0 {
+        return handle_zero(b, c, s);
+    }
+
+    let p = b / (2.0 * a);
+    let q = c / a;
+    if a.approximately_zero() && (p.approximately_zero_inverse() || q.approximately_zero_inverse())
+    {
+        return handle_zero(b, c, s);
+    }
+
+    // Normal form: x^2 + px + q = 0
+    let p2 = p * p;
+    if !p2.almost_dequal_ulps(q) && p2 < q {
+        return 0;
+    }
+
+    let mut sqrt_d = 0.0;
+    if p2 > q {
+        sqrt_d = (p2 - q).sqrt();
+    }
+
+    s[0] = sqrt_d - p;
+    s[1] = -sqrt_d - p;
+    1 + usize::from(!s[0].almost_dequal_ulps(s[1]))
+}
+
+fn cube_root(x: f64) -> f64 {
+    if x.approximately_zero_cubed() {
+        return 0.0;
+    }
+
+    let result = halley_cbrt3d(x.abs());
+    if x < 0.0 {
+        -result
+    } else {
+        result
+    }
+}
+
+// Cube root approximation using 3 iterations of Halley's method.
+fn halley_cbrt3d(d: f64) -> f64 {
+    let mut a = cbrt_5d(d);
+    a = cbrta_halleyd(a, d);
+    a = cbrta_halleyd(a, d);
+    cbrta_halleyd(a, d)
+}
+
+// Cube root approximation using a bit hack, adapted from Kahan's cbrt.
+fn cbrt_5d(d: f64) -> f64 {
+    const B1: u32 = 715094163;
+
+    // Treat the value as two native-endian `u32` and set the second one only.
+    let bytes = d.to_ne_bytes();
+    let hi = u32::from_ne_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+    let mut t = [0; 8];
+    t[4..].copy_from_slice(&(hi / 3 + B1).to_ne_bytes());
+    f64::from_ne_bytes(t)
+}
+
+fn cbrta_halleyd(a: f64, r: f64) -> f64 {
+    let a3 = a * a * a;
+    a * (a3 + r + r) / (a3 + a3 + r)
+}
@@ -0,0 +1,540 @@
+// Copyright 2025 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// Anti-aliased hairlines split into lines as done by the tiny-skia hairline stroker.
+//
+// Based on tiny-skia (https://github.com/linebender/tiny-skia),
+// which is based on Skia.
+//
+// Licensed under 'BSD 3-Clause License'.
+//
+// The results have to match tiny-skia bit for bit,
+// therefore the code mirrors the original one as close as possible.
+
+use tiny_skia::{IntRect, LineCap};
+use tiny_skia_path::path_geometry::{CubicCoeff, QuadCoeff};
+use tiny_skia_path::{f32x2, Path, PathSegment, PathVerb, Point, Rect, SaturateCast, Scalar};
+
+use super::edges;
+use super::geometry;
+
+const MAX_CUBIC_SUBDIVIDE_LEVEL: u8 = 9;
+const MAX_QUAD_SUBDIVIDE_LEVEL: u8 = 5;
+
+/// A line passed to the anti-aliased hairline blitter.
+#[derive(Clone, Copy, Debug)]
+pub struct HairLine {
+    /// Line points in the 26.6 fixed point format.
+    pub points: [i32; 4],
+    /// Pixels the line can affect.
+    ///
+    /// When smaller than the line bounds, the line is clipped by the blitter.
+    pub rect: IntRect,
+}
+
+/// Splits a path into lines like the anti-aliased hairline stroker does.
+///
+/// `clip` is the pixmap rect. `f` receives lines in the drawing order.
+pub fn stroke_path<F>(path: &Path, line_cap: LineCap, clip: &IntRect, f: &mut F)
+where
+    F: FnMut(HairLine),
+{
+    let mut inset_clip = None;
+    let mut outset_clip = None;
+
+    {
+        let cap_out = if line_cap == LineCap::Butt { 1.0 } else { 2.0 };
+        let Some(ibounds) = path
+            .bounds()
+            .outset(cap_out, cap_out)
+            .and_then(|r| r.round_out())
+        else {
+            return;
+        };
+        if clip.intersect(&ibounds).is_none() {
+            return;
+        }
+
+        if !clip.contains(&ibounds) {
+            // Used to cull segments, which can draw up to 1 pixel beyond their bounds.
+            match clip.make_outset(1, 1) {
+                Some(v) => outset_clip = Some(v),
+                None => return,
+            }
+            match clip.inset(1, 1) {
+                Some(v) => inset_clip = Some(v),
+                None => return,
+            }
+        }
+    }
+
+    let clip = Some(clip);
+    let mut prev_verb = PathVerb::Move;
+    let mut first_pt = Point::zero();
+    let mut last_pt = Point::zero();
+
+    let mut iter = path.segments();
+    while let Some(segment) = iter.next() {
+        let verb = iter.curr_verb();
+        let next_verb = iter.next_verb();
+        let last_pt2;
+        match segment {
+            PathSegment::MoveTo(p) => {
+                first_pt = p;
+                last_pt = p;
+                last_pt2 = p;
+            }
+            PathSegment::LineTo(p) => {
+                let mut points = [last_pt, p];
+                if line_cap != LineCap::Butt {
+                    extend_pts(line_cap, prev_verb, next_verb, &mut points);
+                }
+
+                hair_line_rgn(&points, clip, f);
+                last_pt = p;
+                last_pt2 = points[0];
+            }
+            PathSegment::QuadTo(p0, p1) => {
+                let mut points = [last_pt, p0, p1];
+                if line_cap != LineCap::Butt {
+                    extend_pts(line_cap, prev_verb, next_verb, &mut points);
+                }
+
+                hair_quad(
+                    &points,
+                    clip,
+                    inset_clip.as_ref(),
+                    outset_clip.as_ref(),
+                    compute_quad_level(&points),
+                    f,
+                );
+
+                last_pt = p1;
+                last_pt2 = points[0];
+            }
+            PathSegment::CubicTo(p0, p1, p2) => {
+                let mut points = [last_pt, p0, p1, p2];
+                if line_cap != LineCap::Butt {
+                    extend_pts(line_cap, prev_verb, next_verb, &mut points);
+                }
+
+                hair_cubic(&points, clip, inset_clip.as_ref(), outset_clip.as_ref(), f);
+
+                last_pt = p2;
+                last_pt2 = points[0];
+            }
+            PathSegment::Close => {
+                let mut points = [last_pt, first_pt];
+                if line_cap != LineCap::Butt && prev_verb == PathVerb::Move {
+                    // Cap moveTo/close to match SVG expectations for degenerate segments.
+                    extend_pts(line_cap, prev_verb, next_verb, &mut points);
+                }
+                hair_line_rgn(&points, clip, f);
+                last_pt2 = points[0];
+            }
+        }
+
+        if line_cap != LineCap::Butt {
+            if prev_verb == PathVerb::Move
+                && matches!(verb, PathVerb::Line | PathVerb::Quad | PathVerb::Cubic)
+            {
+                // The curve moved the initial point, so close to it instead.
+                first_pt = last_pt2;
+            }
+
+            prev_verb = verb;
+        }
+    }
+}
+
+/// Extends the points in the direction of the starting or ending tangent by 1/2 unit
+/// to account for a round or square cap.
+fn extend_pts(
+    line_cap: LineCap,
+    prev_verb: PathVerb,
+    next_verb: Option<PathVerb>,
+    points: &mut [Point],
+) {
+    // The area of a circle is PI*R*R. For a unit circle, R=1/2, and the cap covers half of that.
+    let cap_outset = if line_cap == LineCap::Square {
+        0.5
+    } else {
+        std::f32::consts::PI / 8.0
+    };
+    if prev_verb == PathVerb::Move {
+        let first = points[0];
+        let mut offset = 0;
+        let mut controls = points.len() - 1;
+        let mut tangent;
+        loop {
+            offset += 1;
+            tangent = first - points[offset];
+
+            if !tangent.is_zero() {
+                break;
+            }
+
+            controls -= 1;
+            if controls == 0 {
+                break;
+            }
+        }
+
+        if tangent.is_zero() {
+            tangent = Point::from_xy(1.0, 0.0);
+            // If all points are equal, move all but one.
+            controls = points.len() - 1;
+        } else {
+            tangent.normalize();
+        }
+
+        offset = 0;
+        loop {
+            // If the end point and control points are equal, loop to move them in tandem.
+            points[offset].x += tangent.x * cap_outset;
+            points[offset].y += tangent.y * cap_outset;
+
+            offset += 1;
+            controls += 1;
+            if controls >= points.len() {
+                break;
+            }
+        }
+    }
+
+    if matches!(
+        next_verb,
+        Some(PathVerb::Move) | Some(PathVerb::Close) | None
+    ) {
+        let last = points[points.len() - 1];
+        let mut offset = points.len() - 1;
+        let mut controls = points.len() - 1;
+        let mut tangent;
+        loop {
+            offset -= 1;
+            tangent = last - points[offset];
+
+            if !tangent.is_zero() {
+                break;
+            }
+
+            controls -= 1;
+            if controls == 0 {
+                break;
+            }
+        }
+
+        if tangent.is_zero() {
+            tangent = Point::from_xy(-1.0, 0.0);
+            controls = points.len() - 1;
+        } else {
+            tangent.normalize();
+        }
+
+        offset = points.len() - 1;
+        loop {
+            points[offset].x += tangent.x * cap_outset;
+            points[offset].y += tangent.y * cap_outset;
+
+            offset -= 1;
+            controls += 1;
+            if controls >= points.len() {
+                break;
+            }
+        }
+    }
+}
+
+fn hair_quad<F>(
+    points: &[Point; 3],
+    mut clip: Option<&IntRect>,
+    inset_clip: Option<&IntRect>,
+    outset_clip: Option<&IntRect>,
+    level: u8,
+    f: &mut F,
+) where
+    F: FnMut(HairLine),
+{
+    if let Some(inset_clip) = inset_clip {
+        let inset_clip = inset_clip.to_rect();
+        let Some(outset_clip) = outset_clip.map(|r| r.to_rect()) else {
+            return;
+        };
+
+        let Some(bounds) = Rect::from_points(points) else {
+            return;
+        };
+        if !geometric_overlap(&outset_clip, &bounds) {
+            return;
+        } else if geometric_contains(&inset_clip, &bounds) {
+            clip = None;
+        }
+    }
+
+    hair_quad2(points, clip, level, f);
+}
+
+fn geometric_overlap(a: &Rect, b: &Rect) -> bool {
+    a.left() < b.right() && b.left() < a.right() && a.top() < b.bottom() && b.top() < a.bottom()
+}
+
+fn geometric_contains(outer: &Rect, inner: &Rect) -> bool {
+    inner.right() <= outer.right()
+        && inner.left() >= outer.left()
+        && inner.bottom() <= outer.bottom()
+        && inner.top() >= outer.top()
+}
+
+fn hair_quad2<F>(points: &[Point; 3], clip: Option<&IntRect>, level: u8, f: &mut F)
+where
+    F: FnMut(HairLine),
+{
+    let coeff = QuadCoeff::from_points(points);
+
+    const MAX_POINTS: usize = (1 << MAX_QUAD_SUBDIVIDE_LEVEL) + 1;
+    let lines = 1 << level;
+
+    let mut tmp = [Point::zero(); MAX_POINTS];
+    tmp[0] = points[0];
+
+    let mut t = f32x2::default();
+    let dt = f32x2::splat(1.0 / lines as f32);
+    for p in tmp.iter_mut().take(lines).skip(1) {
+        t = t + dt;
+        let v = (coeff.a * t + coeff.b) * t + coeff.c;
+        *p = Point::from_xy(v.x(), v.y());
+    }
+
+    tmp[lines] = points[2];
+    hair_line_rgn(&tmp[0..lines + 1], clip, f);
+}
+
+fn compute_quad_level(points: &[Point; 3]) -> u8 {
+    let d = compute_int_quad_dist(points);
+    // Quadratics approach the line connecting their start and end points
+    // 4x closer with each subdivision, so we compute the number of
+    // subdivisions to be the minimum need to get that distance to be less
+    // than a pixel.
+    let level = (33 - d.leading_zeros()) >> 1;
+    level.min(u32::from(MAX_QUAD_SUBDIVIDE_LEVEL)) as u8
+}
+
+fn compute_int_quad_dist(points: &[Point; 3]) -> u32 {
+    // Compute the vector between the control point ([1]) and the middle of the
+    // line connecting the start and end ([0] and [2]).
+    let dx = ((points[0].x + points[2].x).half() - points[1].x).abs();
+    let dy = ((points[0].y + points[2].y).half() - points[1].y).abs();
+
+    // Convert to whole pixel values (use ceiling to be conservative).
+    let idx = i32::saturate_from(dx.ceil()) as u32;
+    let idy = i32::saturate_from(dy.ceil()) as u32;
+
+    // Use the cheap approx for distance.
+    if idx > idy {
+        idx + (idy >> 1)
+    } else {
+        idy + (idx >> 1)
+    }
+}
+
+fn hair_cubic<F>(
+    points: &[Point; 4],
+    mut clip: Option<&IntRect>,
+    inset_clip: Option<&IntRect>,
+    outset_clip: Option<&IntRect>,
+    f: &mut F,
+) where
+    F: FnMut(HairLine),
+{
+    if let Some(inset_clip) = inset_clip {
+        let inset_clip = inset_clip.to_rect();
+        let Some(outset_clip) = outset_clip.map(|r| r.to_rect()) else {
+            return;
+        };
+
+        let Some(bounds) = Rect::from_points(points) else {
+            return;
+        };
+        if !geometric_overlap(&outset_clip, &bounds) {
+            return;
+        } else if geometric_contains(&inset_clip, &bounds) {
+            clip = None;
+        }
+    }
+
+    if quick_cubic_niceness_check(points) {
+        hair_cubic2(points, clip, f);
+    } else {
+        let mut tmp = [Point::zero(); 13];
+        let count = geometry::chop_cubic_at_max_curvature(points, &mut tmp);
+        for i in 0..count {
+            let offset = i * 3;
+            let new_points = [
+                tmp[offset],
+                tmp[offset + 1],
+                tmp[offset + 2],
+                tmp[offset + 3],
+            ];
+            hair_cubic2(&new_points, clip, f);
+        }
+    }
+}
+
+// The off-curve points are "inside" the limits of the on-curve points.
+fn quick_cubic_niceness_check(points: &[Point; 4]) -> bool {
+    lt_90(points[1], points[0], points[3])
+        && lt_90(points[2], points[0], points[3])
+        && lt_90(points[1], points[3], points[0])
+        && lt_90(points[2], points[3], points[0])
+}
+
+fn lt_90(p0: Point, pivot: Point, p2: Point) -> bool {
+    (p0 - pivot).dot(p2 - pivot) >= 0.0
+}
+
+fn hair_cubic2<F>(points: &[Point; 4], clip: Option<&IntRect>, f: &mut F)
+where
+    F: FnMut(HairLine),
+{
+    let lines = compute_cubic_segments(points);
+    if lines == 1 {
+        hair_line_rgn(&[points[0], points[3]], clip, f);
+        return;
+    }
+
+    let coeff = CubicCoeff::from_points(points);
+
+    const MAX_POINTS: usize = (1 << MAX_CUBIC_SUBDIVIDE_LEVEL) + 1;
+    let mut tmp = [Point::zero(); MAX_POINTS];
+
+    let dt = f32x2::splat(1.0 / lines as f32);
+    let mut t = f32x2::default();
+
+    tmp[0] = points[0];
+    for p in tmp.iter_mut().take(lines).skip(1) {
+        t = t + dt;
+        *p = Point::from_f32x2(((coeff.a * t + coeff.b) * t + coeff.c) * t + coeff.d);
+    }
+
+    // Non-finite points are not drawn.
+    if tmp.iter().all(|p| p.is_finite()) {
+        tmp[lines] = points[3];
+        hair_line_rgn(&tmp[0..lines + 1], clip, f);
+    }
+}
+
+fn compute_cubic_segments(points: &[Point; 4]) -> usize {
+    let p0 = points[0].to_f32x2();
+    let p1 = points[1].to_f32x2();
+    let p2 = points[2].to_f32x2();
+    let p3 = points[3].to_f32x2();
+
+    let one_third = f32x2::splat(1.0 / 3.0);
+    let two_third = f32x2::splat(2.0 / 3.0);
+
+    let p13 = one_third * p3 + two_third * p0;
+    let p23 = one_third * p0 + two_third * p3;
+
+    let diff = (p1 - p13).abs().max((p2 - p23).abs()).max_component();
+    let mut tol = 1.0 / 8.0;
+
+    for i in 0..MAX_CUBIC_SUBDIVIDE_LEVEL {
+        if diff < tol {
+            return 1 << i;
+        }
+
+        tol *= 4.0;
+    }
+
+    1 << MAX_CUBIC_SUBDIVIDE_LEVEL
+}
+
+fn hair_line_rgn<F>(points: &[Point], clip: Option<&IntRect>, f: &mut F)
+where
+    F: FnMut(HairLine),
+{
+    let max = 32767.0;
+    let fixed_bounds = Rect::from_ltrb(-max, -max, max, max).unwrap();
+
+    // Anti-aliased hairlines can draw up to 1/2 of a pixel outside of their bounds,
+    // so the clip is outset by a whole pixel.
+    let clip_bounds = clip.and_then(|clip| clip.to_rect().outset(1.0, 1.0));
+
+    for pair in points.windows(2) {
+        // We have to pre-clip the line to fit in a fixed point.
+        let Some(mut pts) = edges::intersect_line(&[pair[0], pair[1]], &fixed_bounds) else {
+            continue;
+        };
+
+        if let Some(clip_bounds) = clip_bounds {
+            match edges::intersect_line(&pts, &clip_bounds) {
+                Some(v) => pts = v,
+                None => continue,
+            }
+        }
+
+        let x0 = (pts[0].x * 64.0) as i32;
+        let y0 = (pts[0].y * 64.0) as i32;
+        let x1 = (pts[1].x * 64.0) as i32;
+        let y1 = (pts[1].y * 64.0) as i32;
+
+        let Some(ir) = IntRect::from_ltrb(
+            (x0.min(x1) >> 6) - 1,
+            (y0.min(y1) >> 6) - 1,
+            ((x0.max(x1) + 63) >> 6) + 1,
+            ((y0.max(y1) + 63) >> 6) + 1,
+        ) else {
+            if clip.is_some() {
+                return;
+            }
+
+            continue;
+        };
+
+        let rect = match clip {
+            Some(clip) => match clip.intersect(&ir) {
+                Some(v) => v,
+                None => continue,
+            },
+            None => ir,
+        };
+
+        do_anti_hairline(x0, y0, x1, y1, rect, f);
+    }
+}
+
+fn do_anti_hairline<F>(x0: i32, y0: i32, x1: i32, y1: i32, rect: IntRect, f: &mut F)
+where
+    F: FnMut(HairLine),
+{
+    // Check for integer NaN (0x80000000) which we can't handle.
+    if [x0, y0, x1, y1].contains(&i32::MIN) {
+        return;
+    }
+
+    if (x1 - x0).abs() > 511 * 64 || (y1 - y0).abs() > 511 * 64 {
+        // Huge lines are split in two.
+        let hx = (x0 >> 1) + (x1 >> 1);
+        let hy = (y0 >> 1) + (y1 >> 1);
+        do_anti_hairline(x0, y0, hx, hy, rect, f);
+        do_anti_hairline(hx, hy, x1, y1, rect, f);
+        return;
+    }
+
+    // The blitter draws only inside the line bounds outset by 1 pixel.
+    let Some(bounds) = IntRect::from_ltrb(
+        (x0.min(x1) >> 6) - 1,
+        (y0.min(y1) >> 6) - 1,
+        ((x0.max(x1) + 63) >> 6) + 1,
+        ((y0.max(y1) + 63) >> 6) + 1,
+    ) else {
+        return;
+    };
+
+    if let Some(rect) = bounds.intersect(&rect) {
+        f(HairLine {
+            points: [x0, y0, x1, y1],
+            rect,
+        });
+    }
+}
@@ -0,0 +1,697 @@
+// Copyright 2025 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Drawing onto a pixmap that stores only a part of a canvas.
+//!
+//! tiny-skia clips everything to the pixmap bounds, and clipping changes how curves
+//! are split into edges. Therefore a path crossing the region edges cannot be drawn
+//! onto the region pixmap directly. Instead, the scan conversion of the whole canvas
+//! is replicated and the scanline spans inside the region are drawn as a path
+//! made of vertical steps, which is not affected by clipping.
+//! Hairlines are split into lines the same way and the lines crossing the region
+//! are drawn onto small temporary pixmaps covering everything they can affect.
+//!
+//! This way the region pixels are identical to the ones of the whole canvas,
+//! while the memory usage depends only on the region size.
+
+mod edges;
+mod geometry;
+mod hairline;
+
+use std::borrow::Cow;
+
+use tiny_skia::{IntRect, Transform};
+use tiny_skia_path::Scalar;
+
+/// Canvases larger than this are drawn in tiles by tiny-skia.
+const MAX_TILE_SIZE: u32 = 8192 - 1;
+
+/// Anti-aliased paths are scan converted with 4 scanlines per pixel.
+const SUPERSAMPLE_SHIFT: i32 = 2;
+
+/// The maximum temporary pixmap size used for hairlines.
+const MAX_TEMPORARY_SIZE: u32 = 1024;
+
+/// A part of a canvas that is stored in a pixmap.
+#[derive(Clone, Copy, Debug)]
+pub struct Region {
+    /// The whole canvas size.
+    pub canvas: tiny_skia::IntSize,
+    /// The canvas part stored in the pixmap.
+    pub rect: IntRect,
+}
+
+impl Region {
+    /// Returns the whole canvas rect.
+    pub fn canvas_rect(&self) -> IntRect {
+        self.canvas.to_int_rect(0, 0)
+    }
+
+    /// Returns a transform from the canvas coordinates to the pixmap ones.
+    fn origin_ts(&self) -> Transform {
+        Transform::from_translate(-self.rect.x() as f32, -self.rect.y() as f32)
+    }
+
+    /// Returns the region part that is inside the canvas.
+    fn visible_rect(&self) -> Option<IntRect> {
+        self.rect.intersect(&self.canvas_rect())
+    }
+
+    /// Checks that tiny-skia draws onto the canvas in tiles.
+    fn is_tiled(&self) -> bool {
+        self.canvas.width() > MAX_TILE_SIZE || self.canvas.height() > MAX_TILE_SIZE
+    }
+
+    /// Returns the tiles tiny-skia draws onto the canvas in, in the drawing order.
+    fn tiles(&self) -> Vec<IntRect> {
+        let (width, height) = (self.canvas.width(), self.canvas.height());
+
+        let mut tiles = Vec::new();
+        for y in (0..height).step_by(MAX_TILE_SIZE as usize) {
+            for x in (0..width).step_by(MAX_TILE_SIZE as usize) {
+                tiles.extend(IntRect::from_xywh(
+                    x as i32,
+                    y as i32,
+                    (width - x).min(MAX_TILE_SIZE),
+                    (height - y).min(MAX_TILE_SIZE),
+                ));
+            }
+        }
+
+        tiles
+    }
+}
+
+/// Fills a path like `PixmapMut::fill_path`.
+///
+/// `region` is the canvas part stored in `pixmap`. `None` means the whole canvas.
+pub fn fill_path(
+    pixmap: &mut tiny_skia::PixmapMut,
+    path: &tiny_skia::Path,
+    paint: &tiny_skia::Paint,
+    fill_rule: tiny_skia::FillRule,
+    transform: Transform,
+    region: Option<Region>,
+) {
+    let Some(region) = region else {
+        pixmap.fill_path(path, paint, fill_rule, transform, None);
+        return;
+    };
+
+    let mut paint = paint.clone();
+    let Some(path) = to_canvas(path, &mut paint, transform) else {
+        return;
+    };
+
+    if let Some(path) = region_path(&region, &path, paint.anti_alias) {
+        paint.shader.transform(region.origin_ts());
+        pixmap.fill_path(&path, &paint, fill_rule, Transform::identity(), None);
+    }
+}
+
+/// Strokes a path like `PixmapMut::stroke_path`.
+///
+/// `region` is the canvas part stored in `pixmap`. `None` means the whole canvas.
+pub fn stroke_path(
+    pixmap: &mut tiny_skia::PixmapMut,
+    path: &tiny_skia::Path,
+    paint: &tiny_skia::Paint,
+    stroke: &tiny_skia::Stroke,
+    transform: Transform,
+    region: Option<Region>,
+) {
+    let Some(region) = region else {
+        pixmap.stroke_path(path, paint, stroke, transform, None);
+        return;
+    };
+
+    if stroke.width < 0.0 {
+        return;
+    }
+
+    let res_scale = tiny_skia::PathStroker::compute_resolution_scale(&transform);
+
+    let dashed;
+    let path = match stroke.dash {
+        Some(ref dash) => {
+            let Some(path) = path.dash(dash, res_scale) else {
+                return;
+            };
+            dashed = path;
+            &dashed
+        }
+        None => path,
+    };
+
+    let Some(coverage) = hairline_coverage(paint, stroke, transform) else {
+        // Thick strokes are filled.
+        if let Some(path) = path.stroke(stroke, res_scale) {
+            fill_path(
+                pixmap,
+                &path,
+                paint,
+                tiny_skia::FillRule::Winding,
+                transform,
+                Some(region),
+            );
+        }
+        return;
+    };
+
+    // Only zero-width strokes are aliased hairlines and usvg never produces them.
+    if !paint.anti_alias {
+        log::warn!("Aliased hairlines are not supported in regions.");
+        return;
+    }
+
+    let mut paint = paint.clone();
+    if coverage != 1.0 && pre_scales_coverage(paint.blend_mode) {
+        let scale = (coverage * 256.0) as i32;
+        let new_alpha = (255 * scale) >> 8;
+        paint.shader.apply_opacity(new_alpha as f32 / 255.0);
+    }
+
+    let Some(path) = to_canvas(path, &mut paint, transform) else {
+        return;
+    };
+
+    stroke_hairline(pixmap, &region, &path, &paint, stroke.line_cap);
+}
+
+/// Fills a rect like `PixmapMut::fill_rect`.
+///
+/// `region` is the canvas part stored in `pixmap`. `None` means the whole canvas.
+#[cfg(feature = "raster-images")]
+pub fn fill_rect(
+    pixmap: &mut tiny_skia::PixmapMut,
+    rect: tiny_skia::Rect,
+    paint: &tiny_skia::Paint,
+    transform: Transform,
+    region: Option<Region>,
+) {
+    let Some(region) = region else {
+        pixmap.fill_rect(rect, paint, transform, None);
+        return;
+    };
+
+    // Just like tiny-skia, fills a path when the rect cannot be drawn directly.
+    if !transform.is_identity() || region.is_tiled() {
+        let path = tiny_skia::PathBuilder::from_rect(rect);
+        fill_path(
+            pixmap,
+            &path,
+            paint,
+            tiny_skia::FillRule::Winding,
+            transform,
+            Some(region),
+        );
+        return;
+    }
+
+    // Rects are clipped exactly, so only the origin has to be moved.
+    let ts = region.origin_ts();
+    let Some(rect) = tiny_skia::Rect::from_ltrb(
+        rect.left() + ts.tx,
+        rect.top() + ts.ty,
+        rect.right() + ts.tx,
+        rect.bottom() + ts.ty,
+    ) else {
+        return;
+    };
+
+    let mut paint = paint.clone();
+    paint.shader.transform(ts);
+    pixmap.fill_rect(rect, &paint, Transform::identity(), None);
+}
+
+/// Fills a path like `Mask::fill_path`.
+///
+/// `region` is the canvas part stored in `mask`. `None` means the whole canvas.
+pub fn fill_mask_path(
+    mask: &mut tiny_skia::Mask,
+    path: &tiny_skia::Path,
+    fill_rule: tiny_skia::FillRule,
+    anti_alias: bool,
+    transform: Transform,
+    region: Option<Region>,
+) {
+    let Some(region) = region else {
+        mask.fill_path(path, fill_rule, anti_alias, transform);
+        return;
+    };
+
+    let mut path = Cow::Borrowed(path);
+    if !transform.is_identity() {
+        match path.as_ref().clone().transform(transform) {
+            Some(v) => path = Cow::Owned(v),
+            None => return,
+        }
+    }
+
+    if let Some(path) = region_path(&region, &path, anti_alias) {
+        mask.fill_path(&path, fill_rule, anti_alias, Transform::identity());
+    }
+}
+
+/// Transforms a path into the canvas coordinates the same way tiny-skia does.
+fn to_canvas<'a>(
+    path: &'a tiny_skia::Path,
+    paint: &mut tiny_skia::Paint,
+    transform: Transform,
+) -> Option<Cow<'a, tiny_skia::Path>> {
+    if transform.is_identity() {
+        return Some(Cow::Borrowed(path));
+    }
+
+    paint.shader.transform(transform);
+    path.clone().transform(transform).map(Cow::Owned)
+}
+
+/// Calls `f` with the path in the coordinates of each tile tiny-skia draws it in.
+///
+/// The path is moved exactly like in tiny-skia, including the rounding errors.
+fn for_each_tile<F>(region: &Region, path: &tiny_skia::Path, mut f: F)
+where
+    F: FnMut(&tiny_skia::Path, IntRect),
+{
+    if !region.is_tiled() {
+        f(path, region.canvas_rect());
+        return;
+    }
+
+    let mut path = path.clone();
+    for tile in region.tiles() {
+        let ts = Transform::from_translate(-tile.x() as f32, -tile.y() as f32);
+        let Some(moved) = path.transform(ts) else {
+            return;
+        };
+
+        f(&moved, tile);
+
+        let ts = Transform::from_translate(tile.x() as f32, tile.y() as f32);
+        match moved.transform(ts) {
+            Some(v) => path = v,
+            None => return,
+        }
+    }
+}
+
+/// Returns a path in the pixmap coordinates, that produces the same region pixels
+/// as filling `path` onto the whole canvas.
+///
+/// Returns `None` when the path cannot affect the region.
+fn region_path(
+    region: &Region,
+    path: &tiny_skia::Path,
+    anti_alias: bool,
+) -> Option<tiny_skia::Path> {
+    let visible = region.visible_rect()?;
+
+    // Anti-aliasing and rounding can affect one more pixel on each side.
+    let bounds = path.bounds();
+    let bounds = IntRect::from_ltrb(
+        bounds.left().floor() as i32 - 1,
+        bounds.top().floor() as i32 - 1,
+        bounds.right().ceil() as i32 + 1,
+        bounds.bottom().ceil() as i32 + 1,
+    )?;
+    bounds.intersect(&visible)?;
+
+    // Lines are converted into the same edges after moving the origin,
+    // unlike curves, which are interpolated in floating point.
+    if visible.contains(&bounds) && !region.is_tiled() && is_line_only(path) {
+        return path.clone().transform(region.origin_ts());
+    }
+
+    steps_path(region, visible, path, anti_alias)
+}
+
+/// Replicates the scan conversion of the whole canvas and returns the spans inside
+/// `visible` as a path in the pixmap coordinates.
+///
+/// Each edge becomes a contour that goes along the edge spans ends, scanline by scanline,
+/// and is closed along the right side of `visible`. The contour has the winding of the edge,
+/// therefore it has the same effect on the spans as the edge itself.
+fn steps_path(
+    region: &Region,
+    visible: IntRect,
+    path: &tiny_skia::Path,
+    anti_alias: bool,
+) -> Option<tiny_skia::Path> {
+    // The same checks as in `PixmapMut::fill_path`.
+    let bounds = path.bounds();
+    if bounds.width().is_nearly_zero() || bounds.height().is_nearly_zero() {
+        return None;
+    }
+
+    if is_too_big_for_math(path) {
+        return None;
+    }
+
+    let shift = if anti_alias { SUPERSAMPLE_SHIFT } else { 0 };
+    let scale = 1.0 / (1 << shift) as f32;
+
+    let mut builder = tiny_skia::PathBuilder::new();
+    for_each_tile(region, path, |path, tile| {
+        let Some(rect) = visible.intersect(&tile) else {
+            return;
+        };
+
+        let clip = tile.size().to_int_rect(0, 0);
+        let Some((start_y, stop_y, contained)) = scanlines(path, &clip, anti_alias) else {
+            return;
+        };
+
+        let clip_rect = clip.to_rect();
+        let Some(edges) = edges::build_edges(path, (!contained).then_some(&clip_rect), shift)
+        else {
+            return;
+        };
+
+        // The region part in the scanlines and columns of the tile.
+        let left = (rect.left() - tile.x()) << shift;
+        let top = (rect.top() - tile.y()) << shift;
+        let right = (rect.right() - tile.x()) << shift;
+        let bottom = (rect.bottom() - tile.y()) << shift;
+
+        // From the tile scanlines and columns to the pixmap coordinates.
+        let dx = (tile.x() - region.rect.x()) << shift;
+        let dy = (tile.y() - region.rect.y()) << shift;
+        let to_pixmap = |x: i32, y: i32| ((x + dx) as f32 * scale, (y + dy) as f32 * scale);
+
+        let mut points = Vec::new();
+        for edge in edges {
+            // Scanline ranges with the same span end, like `(top, bottom, x)`.
+            let mut steps: Vec<(i32, i32, i32)> = Vec::new();
+            let mut winding = 0;
+            edge.walk(start_y, stop_y, top, bottom, |y, x, w| {
+                winding = w;
+                let x = x.clamp(left, right);
+                match steps.last_mut() {
+                    Some(step) if step.1 == y && step.2 == x => step.1 = y + 1,
+                    _ => steps.push((y, y + 1, x)),
+                }
+            });
+
+            let (Some(first), Some(last)) = (steps.first(), steps.last()) else {
+                continue;
+            };
+
+            points.clear();
+            points.push(to_pixmap(right, first.0));
+            for &(y0, y1, x) in &steps {
+                points.push(to_pixmap(x, y0));
+                points.push(to_pixmap(x, y1));
+            }
+            points.push(to_pixmap(right, last.1));
+
+            // Edges going up are closed in the opposite direction.
+            if winding < 0 {
+                points.reverse();
+            }
+
+            builder.move_to(points[0].0, points[0].1);
+            for p in &points[1..] {
+                builder.line_to(p.0, p.1);
+            }
+            builder.close();
+        }
+    });
+
+    builder.finish()
+}
+
+/// Returns the scanlines walked by the tiny-skia scan converter
+/// and whether the path is contained in the clip.
+fn scanlines(path: &tiny_skia::Path, clip: &IntRect, anti_alias: bool) -> Option<(i32, i32, bool)> {
+    let bounds = path.bounds();
+    let (ir, shift) = if anti_alias {
+        let ir = tiny_skia::Rect::from_ltrb(
+            bounds.left().floor(),
+            bounds.top().floor(),
+            bounds.right().ceil(),
+            bounds.bottom().ceil(),
+        )?
+        .round_out()?;
+        ir.intersect(clip)?;
+        (ir, SUPERSAMPLE_SHIFT)
+    } else {
+        // Conservative rounding, to account for the scan converter numeric drift.
+        const BIAS: f64 = 0.5 + 1.5 / 64.0;
+        let ir = IntRect::from_ltrb(
+            (f64::from(bounds.left()) - BIAS).ceil() as i32,
+            (f64::from(bounds.top()) - BIAS).ceil() as i32,
+            (f64::from(bounds.right()) + BIAS).floor() as i32,
+            (f64::from(bounds.bottom()) + BIAS).floor() as i32,
+        )?;
+        (ir, 0)
+    };
+
+    let contained = clip.contains(&ir);
+
+    let mut start_y = ir.top() << shift;
+    let mut stop_y = ir.bottom() << shift;
+    if !contained {
+        start_y = start_y.max(clip.top() << shift);
+        stop_y = stop_y.min(clip.bottom() << shift);
+    }
+
+    if start_y < 0 || stop_y < 0 {
+        return None;
+    }
+
+    Some((start_y, stop_y, contained))
+}
+
+/// Strokes a hairline in the canvas coordinates.
+///
+/// The hairline is split into lines just like in tiny-skia and lines crossing the region
+/// are drawn onto temporary pixmaps, that contain all the pixels they can affect.
+fn stroke_hairline(
+    pixmap: &mut tiny_skia::PixmapMut,
+    region: &Region,
+    path: &tiny_skia::Path,
+    paint: &tiny_skia::Paint,
+    line_cap: tiny_skia::LineCap,
+) {
+    let Some(visible) = region.visible_rect() else {
+        return;
+    };
+
+    // Lines in the tile coordinates and the canvas rect they can affect.
+    let mut lines: Vec<[i32; 4]> = Vec::new();
+    let mut lines_rect: Option<IntRect> = None;
+
+    for_each_tile(region, path, |path, tile| {
+        let clip = tile.size().to_int_rect(0, 0);
+        hairline::stroke_path(path, line_cap, &clip, &mut |line| {
+            let Some(rect) = line.rect.translate(tile.x(), tile.y()) else {
+                return;
+            };
+
+            if rect.intersect(&visible).is_none() {
+                return;
+            }
+
+            let joined = match lines_rect {
+                Some(r) => IntRect::from_ltrb(
+                    r.left().min(rect.left()),
+                    r.top().min(rect.top()),
+                    r.right().max(rect.right()),
+                    r.bottom().max(rect.bottom()),
+                ),
+                None => Some(rect),
+            };
+
+            match joined {
+                Some(r) if r.width() <= MAX_TEMPORARY_SIZE && r.height() <= MAX_TEMPORARY_SIZE => {
+                    lines_rect = Some(r);
+                }
+                _ => {
+                    if let Some(r) = lines_rect {
+                        draw_hairlines(pixmap, region, paint, &lines, tile, r);
+                    }
+
+                    lines.clear();
+                    lines_rect = Some(rect);
+                }
+            }
+
+            lines.push(line.points);
+        });
+
+        // Lines from different tiles are clipped differently.
+        if let Some(r) = lines_rect.take() {
+            draw_hairlines(pixmap, region, paint, &lines, tile, r);
+        }
+        lines.clear();
+    });
+}
+
+/// Draws hairline lines onto a temporary pixmap covering `rect`
+/// and copies the result into the region.
+///
+/// Lines are in the 26.6 fixed point format, in the coordinates of `tile`.
+fn draw_hairlines(
+    pixmap: &mut tiny_skia::PixmapMut,
+    region: &Region,
+    paint: &tiny_skia::Paint,
+    lines: &[[i32; 4]],
+    tile: IntRect,
+    rect: IntRect,
+) {
+    let Some(shared) = rect.intersect(&region.rect) else {
+        return;
+    };
+
+    // The fixed point coordinates are converted back exactly.
+    let dx = (rect.x() - tile.x()) * 64;
+    let dy = (rect.y() - tile.y()) * 64;
+    let mut builder = tiny_skia::PathBuilder::new();
+    for p in lines {
+        builder.move_to((p[0] - dx) as f32 / 64.0, (p[1] - dy) as f32 / 64.0);
+        builder.line_to((p[2] - dx) as f32 / 64.0, (p[3] - dy) as f32 / 64.0);
+    }
+
+    let Some(path) = builder.finish() else {
+        return;
+    };
+
+    let Some(mut temp) = tiny_skia::Pixmap::new(rect.width(), rect.height()) else {
+        log::warn!("Failed to allocate a temporary pixmap for: {:?}.", rect);
+        return;
+    };
+
+    let bpp = tiny_skia::BYTES_PER_PIXEL;
+    copy_pixels(
+        pixmap.data_mut(),
+        region.rect,
+        temp.data_mut(),
+        rect,
+        shared,
+        bpp,
+    );
+
+    let mut paint = paint.clone();
+    paint.shader.transform(Transform::from_translate(
+        -rect.x() as f32,
+        -rect.y() as f32,
+    ));
+
+    // The lines are already split and clipped, so they are drawn as is.
+    let hairline = tiny_skia::Stroke {
+        width: 0.0,
+        ..tiny_skia::Stroke::default()
+    };
+    temp.stroke_path(&path, &paint, &hairline, Transform::identity(), None);
+
+    copy_pixels(
+        temp.data(),
+        rect,
+        pixmap.data_mut(),
+        region.rect,
+        shared,
+        bpp,
+    );
+}
+
+/// Copies `rect` between two buffers placed at `src_rect` and `dst_rect` on the canvas.
+fn copy_pixels(
+    src: &[u8],
+    src_rect: IntRect,
+    dst: &mut [u8],
+    dst_rect: IntRect,
+    rect: IntRect,
+    bytes_per_pixel: usize,
+) {
+    let src_stride = src_rect.width() as usize * bytes_per_pixel;
+    let dst_stride = dst_rect.width() as usize * bytes_per_pixel;
+    let row_len = rect.width() as usize * bytes_per_pixel;
+    let src_x = (rect.x() - src_rect.x()) as usize * bytes_per_pixel;
+    let dst_x = (rect.x() - dst_rect.x()) as usize * bytes_per_pixel;
+
+    for y in rect.top()..rect.bottom() {
+        let src_start = (y - src_rect.y()) as usize * src_stride + src_x;
+        let dst_start = (y - dst_rect.y()) as usize * dst_stride + dst_x;
+        dst[dst_start..dst_start + row_len].copy_from_slice(&src[src_start..src_start + row_len]);
+    }
+}
+
+/// Checks that a path has no curves.
+fn is_line_only(path: &tiny_skia::Path) -> bool {
+    path.segments().all(|segment| {
+        !matches!(
+            segment,
+            tiny_skia::PathSegment::QuadTo(..) | tiny_skia::PathSegment::CubicTo(..)
+        )
+    })
+}
+
+/// Checks that path coordinates are too big to be drawn.
+///
+/// The same as in tiny-skia.
+fn is_too_big_for_math(path: &tiny_skia::Path) -> bool {
+    const MAX: f32 = f32::MAX * 0.25;
+
+    let b = path.bounds();
+
+    // Also true when bounds contain NaN.
+    !(b.left() >= -MAX && b.top() >= -MAX && b.right() <= MAX && b.bottom() <= MAX)
+}
+
+/// Returns the hairline coverage when a stroke is drawn as a hairline.
+///
+/// The same as in `PixmapMut::stroke_path`.
+fn hairline_coverage(
+    paint: &tiny_skia::Paint,
+    stroke: &tiny_skia::Stroke,
+    mut ts: Transform,
+) -> Option<f32> {
+    fn fast_len(p: tiny_skia::Point) -> f32 {
+        let (x, y) = (p.x.abs(), p.y.abs());
+        x.max(y) + x.min(y) / 2.0
+    }
+
+    if stroke.width == 0.0 {
+        return Some(1.0);
+    }
+
+    if !paint.anti_alias {
+        return None;
+    }
+
+    ts.tx = 0.0;
+    ts.ty = 0.0;
+
+    let mut points = [
+        tiny_skia::Point::from_xy(stroke.width, 0.0),
+        tiny_skia::Point::from_xy(0.0, stroke.width),
+    ];
+    ts.map_points(&mut points);
+
+    let len0 = fast_len(points[0]);
+    let len1 = fast_len(points[1]);
+    if len0 <= 1.0 && len1 <= 1.0 {
+        return Some((len0 + len1) / 2.0);
+    }
+
+    None
+}
+
+/// Checks that a blend mode allows applying the hairline coverage as opacity.
+///
+/// The same as in tiny-skia.
+fn pre_scales_coverage(mode: tiny_skia::BlendMode) -> bool {
+    use tiny_skia::BlendMode;
+
+    matches!(
+        mode,
+        BlendMode::Destination
+            | BlendMode::DestinationOver
+            | BlendMode::Plus
+            | BlendMode::DestinationOut
+            | BlendMode::SourceAtop
+            | BlendMode::SourceOver
+            | BlendMode::Xor
+    )
+}
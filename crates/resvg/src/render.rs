@@ -1,10 +1,113 @@
 // Copyright 2018 the Resvg Authors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::region::Region;
 use crate::OptionLog;
 
-pub struct Context {
+#[derive(Clone, Copy)]
+pub struct Context<'a> {
     pub max_bbox: tiny_skia::IntRect,
+    /// The canvas part stored in the current pixmap.
+    ///
+    /// `None` when the pixmap stores the whole canvas.
+    pub region: Option<Region>,
+    /// Cancellation and progress reporting.
+    pub control: Option<&'a Control<'a>>,
+    /// Whether rendered nodes are counted by the progress.
+    ///
+    /// Nodes outside the main tree, like masks and patterns, are not counted.
+    pub report_progress: bool,
+}
+
+impl<'a> Context<'a> {
+    /// Creates a context for a canvas of the specified size.
+    ///
+    /// Layers are not allowed to be bigger than 4x the canvas size.
+    pub fn new(size: tiny_skia::IntSize) -> Self {
+        let max_bbox = tiny_skia::IntRect::from_xywh(
+            -(size.width() as i32) * 2,
+            -(size.height() as i32) * 2,
+            size.width() * 5,
+            size.height() * 5,
+        )
+        .unwrap();
+
+        Context {
+            max_bbox,
+            region: None,
+            control: None,
+            report_progress: false,
+        }
+    }
+
+    /// Returns a context for rendering nodes that are not a part of the main tree.
+    pub fn detached(&self) -> Self {
+        Context {
+            report_progress: false,
+            ..*self
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.control.is_some_and(|control| control.is_cancelled())
+    }
+
+    fn node_rendered(&self) {
+        if self.report_progress {
+            if let Some(control) = self.control {
+                control.node_rendered();
+            }
+        }
+    }
+}
+
+/// Tracks the rendering progress and cancellation.
+pub struct Control<'a> {
+    options: &'a crate::RenderOptions<'a>,
+    total: usize,
+    rendered: AtomicUsize,
+}
+
+impl<'a> Control<'a> {
+    pub fn new(tree: &usvg::Tree, options: &'a crate::RenderOptions<'a>) -> Self {
+        Control {
+            options,
+            total: nodes_count(tree.root()),
+            rendered: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.options
+            .cancellation_token
+            .as_ref()
+            .is_some_and(|token| token.is_cancelled())
+    }
+
+    fn node_rendered(&self) {
+        // A node can be rendered only partially after cancellation.
+        if self.is_cancelled() {
+            return;
+        }
+
+        let rendered = self.rendered.fetch_add(1, Ordering::Relaxed) + 1;
+        if let Some(progress) = self.options.progress {
+            progress(rendered.min(self.total) as f32 / self.total as f32);
+        }
+    }
+}
+
+fn nodes_count(parent: &usvg::Group) -> usize {
+    parent
+        .children()
+        .iter()
+        .map(|node| match node {
+            usvg::Node::Group(ref group) => 1 + nodes_count(group),
+            _ => 1,
+        })
+        .sum()
 }
 
 pub fn render_nodes(
@@ -13,8 +116,58 @@ pub fn render_nodes(
     transform: tiny_skia::Transform,
     pixmap: &mut tiny_skia::PixmapMut,
 ) {
+    #[cfg(feature = "parallel")]
+    {
+        // Isolated groups are rendered onto separate layers first,
+        // therefore multiple layers can be rendered concurrently.
+        // Layers are still drawn in order, so the result is the same.
+        let is_layer = |node: &&usvg::Node| match node {
+            usvg::Node::Group(ref group) => group.should_isolate(),
+            _ => false,
+        };
+
+        let threads = crate::parallel::threads_count();
+        if threads > 1 && parent.children().iter().filter(is_layer).count() > 1 {
+            // A layer can be as large as the canvas, so only one layer per thread
+            // is allocated at a time.
+            for nodes in parent.children().chunks(threads) {
+                let layers = crate::parallel::map(nodes, |node| match node {
+                    usvg::Node::Group(ref group) if group.should_isolate() => {
+                        let transform = transform.pre_concat(group.transform());
+                        render_layer(group, ctx, transform)
+                    }
+                    _ => None,
+                });
+
+                for (node, layer) in nodes.iter().zip(layers) {
+                    if ctx.is_cancelled() {
+                        return;
+                    }
+
+                    match node {
+                        usvg::Node::Group(ref group) if group.should_isolate() => {
+                            if let Some(layer) = layer {
+                                draw_layer(group, &layer, ctx, pixmap);
+                            }
+                        }
+                        _ => render_node(node, ctx, transform, pixmap),
+                    }
+
+                    ctx.node_rendered();
+                }
+            }
+
+            return;
+        }
+    }
+
     for node in parent.children() {
+        if ctx.is_cancelled() {
+            return;
+        }
+
         render_node(node, ctx, transform, pixmap);
+        ctx.node_rendered();
     }
 }
 
@@ -37,7 +190,7 @@ pub fn render_node(
                 pixmap,
             );
         }
-        // Rectangle, Ellipse, and Polygon nodes are converted to paths for rendering.
+        // Primitive shape nodes are converted to paths for rendering.
         // This maintains compatibility with the existing rendering pipeline while preserving
         // primitive shape information in the usvg tree structure.
         usvg::Node::Rectangle(ref rect) => {
@@ -76,11 +229,44 @@ pub fn render_node(
                 );
             }
         }
+        usvg::Node::Circle(ref circle) => {
+            if let Some(path) = crate::path::circle_to_path(circle) {
+                crate::path::render(
+                    &path,
+                    tiny_skia::BlendMode::SourceOver,
+                    ctx,
+                    transform,
+                    pixmap,
+                );
+            }
+        }
+        usvg::Node::Line(ref line) => {
+            if let Some(path) = crate::path::line_to_path(line) {
+                crate::path::render(
+                    &path,
+                    tiny_skia::BlendMode::SourceOver,
+                    ctx,
+                    transform,
+                    pixmap,
+                );
+            }
+        }
+        usvg::Node::Polyline(ref polyline) => {
+            if let Some(path) = crate::path::polyline_to_path(polyline) {
+                crate::path::render(
+                    &path,
+                    tiny_skia::BlendMode::SourceOver,
+                    ctx,
+                    transform,
+                    pixmap,
+                );
+            }
+        }
         usvg::Node::Image(ref image) => {
-            crate::image::render(image, transform, pixmap);
+            crate::image::render(image, ctx, transform, pixmap);
         }
         usvg::Node::Text(ref text) => {
-            render_group(text.flattened(), ctx, transform, pixmap);
+            render_group(text.flattened(), &ctx.detached(), transform, pixmap);
         }
     }
 }
@@ -98,6 +284,30 @@ fn render_group(
         return Some(());
     }
 
+    let layer = render_layer(group, ctx, transform)?;
+    draw_layer(group, &layer, ctx, pixmap);
+
+    Some(())
+}
+
+/// An isolated group rendered onto a separate pixmap.
+struct Layer {
+    /// The layer position on the canvas.
+    rect: tiny_skia::IntRect,
+    /// The layer part stored in the pixmap, in layer coordinates.
+    stored: tiny_skia::IntRect,
+    pixmap: tiny_skia::Pixmap,
+}
+
+/// Renders an isolated group onto a new layer.
+///
+/// When only a region of the canvas is rendered, only the part of the layer
+/// that affects the region is rendered as well.
+fn render_layer(
+    group: &usvg::Group,
+    ctx: &Context,
+    transform: tiny_skia::Transform,
+) -> Option<Layer> {
     let bbox = group.layer_bounding_box().transform(transform)?;
 
     let mut ibbox = if group.filters().is_empty() {
@@ -139,35 +349,144 @@ fn render_group(
 
     let transform = shift_ts.pre_concat(transform);
 
-    let mut sub_pixmap = tiny_skia::Pixmap::new(ibbox.width(), ibbox.height())
+    let whole = ibbox.size().to_int_rect(0, 0);
+    let stored = match ctx.region {
+        Some(ref region) => layer_part(group, ibbox, region, transform)?,
+        None => whole,
+    };
+
+    // A layer is a canvas of its own.
+    let ctx = Context {
+        region: Some(Region {
+            canvas: ibbox.size(),
+            rect: stored,
+        })
+        .filter(|_| stored != whole),
+        ..*ctx
+    };
+
+    let mut sub_pixmap = tiny_skia::Pixmap::new(stored.width(), stored.height())
         .log_none(|| log::warn!("Failed to allocate a group layer for: {:?}.", ibbox))?;
 
-    render_nodes(group, ctx, transform, &mut sub_pixmap.as_mut());
+    render_nodes(group, &ctx, transform, &mut sub_pixmap.as_mut());
 
     if !group.filters().is_empty() {
         for filter in group.filters() {
-            crate::filter::apply(filter, transform, &mut sub_pixmap);
+            crate::filter::apply(filter, &ctx, transform, &mut sub_pixmap);
         }
     }
 
     if let Some(clip_path) = group.clip_path() {
-        crate::clip::apply(clip_path, transform, &mut sub_pixmap);
+        crate::clip::apply(clip_path, &ctx, transform, &mut sub_pixmap);
     }
 
-    if let Some(mask) = group.mask() {
-        crate::mask::apply(mask, ctx, transform, &mut sub_pixmap);
+    if !group.masks().is_empty() {
+        crate::mask::apply(group.masks(), &ctx, transform, &mut sub_pixmap);
     }
 
+    Some(Layer {
+        rect: ibbox,
+        stored,
+        pixmap: sub_pixmap,
+    })
+}
+
+/// Returns the layer part that has to be rendered to draw the layer onto the region.
+///
+/// Filters use pixels around the ones they produce, so the part is extended
+/// by the filters margin. When the margin is unknown, the whole layer is rendered.
+fn layer_part(
+    group: &usvg::Group,
+    rect: tiny_skia::IntRect,
+    region: &Region,
+    transform: tiny_skia::Transform,
+) -> Option<tiny_skia::IntRect> {
+    let whole = rect.size().to_int_rect(0, 0);
+
+    let visible = drawn_rect(rect)?
+        .intersect(&region.canvas_rect())?
+        .intersect(&region.rect)?;
+
+    // The pixels drawn past the layer repeat its last row and column.
+    let part = tiny_skia::IntRect::from_ltrb(
+        visible.left() - rect.x(),
+        visible.top() - rect.y(),
+        (visible.right() - rect.x()).min(whole.right()),
+        (visible.bottom() - rect.y()).min(whole.bottom()),
+    )?;
+
+    if group.filters().is_empty() {
+        return Some(part);
+    }
+
+    let part = crate::filter::margin(group.filters(), transform, whole)
+        .and_then(|margin| part.make_outset(margin as i32, margin as i32))
+        .and_then(|part| part.intersect(&whole));
+    Some(part.unwrap_or(whole))
+}
+
+/// Returns the canvas rect affected by `PixmapMut::draw_pixmap` for a layer.
+///
+/// A layer drawn at a negative position is shifted by one pixel, because of rounding.
+fn drawn_rect(rect: tiny_skia::IntRect) -> Option<tiny_skia::IntRect> {
+    let shift = |v: i32| if v < 0 { v + 1 } else { v };
+    tiny_skia::IntRect::from_xywh(
+        shift(rect.x()),
+        shift(rect.y()),
+        rect.width(),
+        rect.height(),
+    )
+}
+
+fn draw_layer(
+    group: &usvg::Group,
+    layer: &Layer,
+    ctx: &Context,
+    pixmap: &mut tiny_skia::PixmapMut,
+) -> Option<()> {
     let paint = tiny_skia::PixmapPaint {
         opacity: group.opacity().get(),
         blend_mode: convert_blend_mode(group.blend_mode()),
         quality: tiny_skia::FilterQuality::Nearest,
     };
 
-    pixmap.draw_pixmap(
-        ibbox.x(),
-        ibbox.y(),
-        sub_pixmap.as_ref(),
+    let Some(ref region) = ctx.region else {
+        pixmap.draw_pixmap(
+            layer.rect.x(),
+            layer.rect.y(),
+            layer.pixmap.as_ref(),
+            &paint,
+            tiny_skia::Transform::identity(),
+            None,
+        );
+        return Some(());
+    };
+
+    // Draw exactly the same pixels as `draw_pixmap` would draw onto the whole canvas.
+    let visible = drawn_rect(layer.rect)?
+        .intersect(&region.canvas_rect())?
+        .intersect(&region.rect)?;
+
+    let pattern_ts = tiny_skia::Transform::from_translate(
+        (layer.rect.x() + layer.stored.x() - region.rect.x()) as f32,
+        (layer.rect.y() + layer.stored.y() - region.rect.y()) as f32,
+    );
+    let paint = tiny_skia::Paint {
+        shader: tiny_skia::Pattern::new(
+            layer.pixmap.as_ref(),
+            tiny_skia::SpreadMode::Pad,
+            paint.quality,
+            paint.opacity,
+            pattern_ts,
+        ),
+        blend_mode: paint.blend_mode,
+        anti_alias: false,
+        force_hq_pipeline: false,
+    };
+
+    let rect = visible.translate(-region.rect.x(), -region.rect.y())?;
+    pixmap.fill_rect(
+        rect.to_rect(),
         &paint,
         tiny_skia::Transform::identity(),
         None,
@@ -0,0 +1,230 @@
+#!/usr/bin/env python3
+
+# Generates ColrSweep.ttf, a minimal COLRv1 font with PaintSweepGradient glyphs.
+#
+# All glyphs fill the same square with a sweep gradient centered at (500, 400)
+# in the Y-up font space. Angles go counter-clockwise, starting at the positive X axis.
+#
+# A: 0..360 degrees, the whole circle
+# B: 90..270 degrees, the left half
+# C: 0..90 degrees, the top-right quarter
+# D: 0..90 degrees with the reflect extend mode
+
+import struct
+
+UNITS_PER_EM = 1000
+ASCENDER = 800
+DESCENDER = -200
+FAMILY = 'Colr Sweep'
+
+# (extend, start angle, end angle) in degrees.
+SWEEPS = [
+    (0, 0, 360),
+    (0, 90, 270),
+    (0, 0, 90),
+    (2, 0, 90),
+]
+
+# BGRA
+PALETTE = [
+    (0, 0, 255, 255),  # red
+    (0, 255, 255, 255),  # yellow
+    (0, 128, 0, 255),  # green
+    (255, 0, 0, 255),  # blue
+]
+
+# .notdef, A, B, C, D, the gradient clip square
+GLYPHS_COUNT = 2 + len(SWEEPS)
+SQUARE_GLYPH = GLYPHS_COUNT - 1
+
+
+def f2dot14(v):
+    return round(v * 16384)
+
+
+def angle(degrees):
+    # Angles are stored with a bias of 180 degrees per 1.0.
+    return f2dot14(degrees / 180.0 - 1.0)
+
+
+def square():
+    x_min, y_min, x_max, y_max = 100, 0, 900, 800
+    points = [(x_min, y_min), (x_min, y_max), (x_max, y_max), (x_max, y_min)]
+    data = struct.pack('>hhhhh', 1, x_min, y_min, x_max, y_max)
+    data += struct.pack('>HH', len(points) - 1, 0)
+    data += bytes([0x01] * len(points))
+    prev = 0
+    for x, _ in points:
+        data += struct.pack('>h', x - prev)
+        prev = x
+    prev = 0
+    for _, y in points:
+        data += struct.pack('>h', y - prev)
+        prev = y
+    return data
+
+
+def glyf_loca():
+    glyf = b''
+    offsets = [0]
+    for gid in range(GLYPHS_COUNT):
+        if gid != 0:
+            glyf += square()
+            glyf += b'\0' * (-len(glyf) % 4)
+        offsets.append(len(glyf))
+    loca = b''.join(struct.pack('>I', o) for o in offsets)
+    return glyf, loca
+
+
+def cmap():
+    first = ord('A')
+    last = first + len(SWEEPS) - 1
+    seg_count = 2
+    subtable = struct.pack('>HHHHHHH', 4, 0, 0, seg_count * 2, 4, 1, 0)
+    subtable += struct.pack('>HH', last, 0xFFFF)
+    subtable += struct.pack('>H', 0)
+    subtable += struct.pack('>HH', first, 0xFFFF)
+    subtable += struct.pack('>HH', (1 - first) % 0x10000, 1)
+    subtable += struct.pack('>HH', 0, 0)
+    subtable = subtable[:2] + struct.pack('>H', len(subtable)) + subtable[4:]
+    return struct.pack('>HHHHI', 0, 1, 3, 1, 12) + subtable
+
+
+def colr():
+    def color_line(extend):
+        stops = [(0.0, 0), (0.25, 1), (0.5, 2), (0.75, 3), (1.0, 0)]
+        data = struct.pack('>BH', extend, len(stops))
+        for offset, index in stops:
+            data += struct.pack('>hHh', f2dot14(offset), index, f2dot14(1.0))
+        return data
+
+    def paint(extend, start, end):
+        # PaintGlyph -> PaintSweepGradient -> ColorLine
+        sweep = struct.pack('>B', 8) + (12).to_bytes(3, 'big')
+        sweep += struct.pack('>hhhh', 500, 400, angle(start), angle(end))
+        glyph = struct.pack('>B', 10) + (6).to_bytes(3, 'big') + struct.pack('>H', SQUARE_GLYPH)
+        return glyph + sweep + color_line(extend)
+
+    paints = [paint(*sweep) for sweep in SWEEPS]
+
+    header_size = 34
+    list_size = 4 + 6 * len(paints)
+    base_glyph_list = struct.pack('>I', len(paints))
+    offset = list_size
+    for i, data in enumerate(paints):
+        base_glyph_list += struct.pack('>HI', i + 1, offset)
+        offset += len(data)
+    base_glyph_list += b''.join(paints)
+
+    header = struct.pack('>HHIIHIIIII', 1, 0, 0, 0, 0, header_size, 0, 0, 0, 0)
+    return header + base_glyph_list
+
+
+def cpal():
+    header = struct.pack('>HHHHIH', 0, len(PALETTE), 1, len(PALETTE), 14, 0)
+    return header + b''.join(bytes(c) for c in PALETTE)
+
+
+def head():
+    return struct.pack(
+        '>IIIIHHqqhhhhHHhhh',
+        0x00010000, 0x00010000, 0, 0x5F0F3CF5, 0x000B, UNITS_PER_EM,
+        0, 0, 100, 0, 900, 800, 0, 8, 2, 1, 0,
+    )
+
+
+def hhea():
+    return struct.pack(
+        '>IhhhHhhhhhhhhhhhH',
+        0x00010000, ASCENDER, DESCENDER, 0, 1000, 100, 100, 900,
+        1, 0, 0, 0, 0, 0, 0, 0, GLYPHS_COUNT,
+    )
+
+
+def hmtx():
+    return b''.join(struct.pack('>Hh', 1000, 0 if gid == 0 else 100) for gid in range(GLYPHS_COUNT))
+
+
+def maxp():
+    return struct.pack('>IHHHHHHHHHHHHHH', 0x00010000, GLYPHS_COUNT, 4, 1, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 0)
+
+
+def name():
+    records = [(1, FAMILY), (2, 'Regular'), (4, FAMILY), (6, FAMILY.replace(' ', ''))]
+    strings = b''
+    data = b''
+    for name_id, value in records:
+        encoded = value.encode('utf-16-be')
+        data += struct.pack('>HHHHHH', 3, 1, 0x409, name_id, len(encoded), len(strings))
+        strings += encoded
+    return struct.pack('>HHH', 0, len(records), 6 + 12 * len(records)) + data + strings
+
+
+def os2():
+    data = struct.pack('>HhHHH', 4, 1000, 400, 5, 0)
+    data += struct.pack('>hhhhhhhhhh', 650, 600, 0, 75, 650, 600, 0, 350, 50, 300)
+    data += struct.pack('>h', 0)
+    data += bytes(10)
+    data += struct.pack('>IIII', 0, 0, 0, 0)
+    data += b'NONE'
+    data += struct.pack('>HHH', 0x40, ord('A'), ord('A') + len(SWEEPS) - 1)
+    data += struct.pack('>hhhHH', ASCENDER, DESCENDER, 0, ASCENDER, -DESCENDER)
+    data += struct.pack('>II', 1, 0)
+    data += struct.pack('>hhHHH', 500, 700, 0, 32, 0)
+    assert len(data) == 96
+    return data
+
+
+def post():
+    return struct.pack('>IihhIIIII', 0x00030000, 0, -100, 50, 0, 0, 0, 0, 0)
+
+
+def checksum(data):
+    data += b'\0' * (-len(data) % 4)
+    return sum(struct.unpack('>%dI' % (len(data) // 4), data)) & 0xFFFFFFFF
+
+
+def build():
+    glyf, loca = glyf_loca()
+    tables = {
+        'COLR': colr(),
+        'CPAL': cpal(),
+        'OS/2': os2(),
+        'cmap': cmap(),
+        'glyf': glyf,
+        'head': head(),
+        'hhea': hhea(),
+        'hmtx': hmtx(),
+        'loca': loca,
+        'maxp': maxp(),
+        'name': name(),
+        'post': post(),
+    }
+
+    count = len(tables)
+    entry_selector = count.bit_length() - 1
+    search_range = (1 << entry_selector) * 16
+    font = struct.pack('>IHHHH', 0x00010000, count, search_range, entry_selector,
+                       count * 16 - search_range)
+
+    offset = 12 + 16 * count
+    records = b''
+    data = b''
+    for tag in sorted(tables):
+        table = tables[tag]
+        records += struct.pack('>4sIII', tag.encode(), checksum(table), offset, len(table))
+        table += b'\0' * (-len(table) % 4)
+        data += table
+        offset += len(table)
+
+    font = bytearray(font + records + data)
+
+    # Fix the head table checksum adjustment.
+    head_offset = struct.unpack('>I', font[font.index(b'head') + 8:][:4])[0]
+    adjustment = (0xB1B0AFBA - checksum(bytes(font))) & 0xFFFFFFFF
+    font[head_offset + 8:head_offset + 12] = struct.pack('>I', adjustment)
+    return bytes(font)
+
+
+with open('ColrSweep.ttf', 'wb') as f:
+    f.write(build())
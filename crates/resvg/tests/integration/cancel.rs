@@ -0,0 +1,118 @@
+// Copyright 2025 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use std::sync::Mutex;
+
+use crate::GLOBAL_FONTDB;
+
+fn load(name: &str) -> usvg::Tree {
+    let path = format!("tests/tests/{}.svg", name);
+    let opt = usvg::Options {
+        resources_dir: Some(std::path::PathBuf::from(&path).parent().unwrap().to_owned()),
+        fontdb: GLOBAL_FONTDB.clone(),
+        ..usvg::Options::default()
+    };
+    usvg::Tree::from_data(&std::fs::read(&path).unwrap(), &opt).unwrap()
+}
+
+fn new_pixmap(tree: &usvg::Tree) -> tiny_skia::Pixmap {
+    let size = tree.size().to_int_size();
+    tiny_skia::Pixmap::new(size.width(), size.height()).unwrap()
+}
+
+const FILTERS: &str = "
+<svg viewBox='0 0 200 200' xmlns='http://www.w3.org/2000/svg'>
+    <filter id='filter'>
+        <feGaussianBlur stdDeviation='5'/>
+        <feOffset dx='10' dy='10'/>
+    </filter>
+    <g filter='url(#filter)'>
+        <rect x='20' y='20' width='80' height='80' fill='green'/>
+        <rect x='100' y='100' width='80' height='80' fill='blue'/>
+    </g>
+    <rect x='20' y='120' width='60' height='60' fill='red'/>
+</svg>
+";
+
+#[test]
+fn progress() {
+    let tree = load("structure/style/attribute-selector");
+
+    let mut expected = new_pixmap(&tree);
+    resvg::render(
+        &tree,
+        tiny_skia::Transform::default(),
+        &mut expected.as_mut(),
+    );
+
+    let values = Mutex::new(Vec::new());
+    let progress = |v: f32| values.lock().unwrap().push(v);
+    let options = resvg::RenderOptions {
+        progress: Some(&progress),
+        ..resvg::RenderOptions::default()
+    };
+
+    let mut pixmap = new_pixmap(&tree);
+    let result = resvg::render_with_options(
+        &tree,
+        tiny_skia::Transform::default(),
+        &options,
+        &mut pixmap.as_mut(),
+    );
+    assert_eq!(result, Ok(()));
+    assert!(pixmap == expected);
+
+    let values = values.into_inner().unwrap();
+    assert!(!values.is_empty());
+    assert!(values.iter().all(|v| *v > 0.0 && *v <= 1.0));
+    assert_eq!(values.iter().copied().fold(0.0, f32::max), 1.0);
+}
+
+#[test]
+fn cancelled_before_rendering() {
+    let tree = load("structure/style/attribute-selector");
+
+    let token = resvg::CancellationToken::new();
+    token.cancel();
+    let options = resvg::RenderOptions {
+        cancellation_token: Some(token),
+        ..resvg::RenderOptions::default()
+    };
+
+    let mut pixmap = new_pixmap(&tree);
+    let result = resvg::render_with_options(
+        &tree,
+        tiny_skia::Transform::default(),
+        &options,
+        &mut pixmap.as_mut(),
+    );
+    assert_eq!(result, Err(resvg::Error::Cancelled));
+    assert!(pixmap.pixels().iter().all(|p| p.alpha() == 0));
+}
+
+#[test]
+fn cancelled_inside_filter() {
+    let tree = usvg::Tree::from_str(FILTERS, &usvg::Options::default()).unwrap();
+
+    // Cancel right after the first node inside the filtered group.
+    let token = resvg::CancellationToken::new();
+    let calls = Mutex::new(0);
+    let progress = |_| {
+        *calls.lock().unwrap() += 1;
+        token.cancel();
+    };
+    let options = resvg::RenderOptions {
+        cancellation_token: Some(token.clone()),
+        progress: Some(&progress),
+    };
+
+    let mut pixmap = new_pixmap(&tree);
+    let result = resvg::render_with_options(
+        &tree,
+        tiny_skia::Transform::default(),
+        &options,
+        &mut pixmap.as_mut(),
+    );
+    assert_eq!(result, Err(resvg::Error::Cancelled));
+    assert_eq!(calls.into_inner().unwrap(), 1);
+}
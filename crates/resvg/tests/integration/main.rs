@@ -14,7 +14,11 @@ use usvg::fontdb;
 #[rustfmt::skip]
 mod render;
 
+mod cancel;
 mod extra;
+#[cfg(feature = "pdf")]
+mod pdf;
+mod tiles;
 
 const IMAGE_SIZE: u32 = 300;
 
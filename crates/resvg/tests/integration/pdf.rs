@@ -0,0 +1,141 @@
+// Copyright 2025 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use crate::GLOBAL_FONTDB;
+
+fn render_pdf(svg: &str) -> String {
+    let opt = usvg::Options {
+        fontdb: GLOBAL_FONTDB.clone(),
+        ..usvg::Options::default()
+    };
+    let tree = usvg::Tree::from_str(svg, &opt).unwrap();
+    let data = resvg::pdf::render(&tree, &resvg::pdf::Options::default());
+    assert!(data.starts_with(b"%PDF-"));
+
+    // Object dictionaries are not compressed, only streams.
+    String::from_utf8_lossy(&data).into_owned()
+}
+
+#[test]
+fn page_size() {
+    let pdf = render_pdf(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="96" height="48">
+            <rect width="10" height="10"/>
+        </svg>"#,
+    );
+
+    assert!(pdf.contains("/MediaBox [0 0 72 36]"));
+}
+
+#[test]
+fn gradients_and_patterns() {
+    let pdf = render_pdf(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="200">
+            <linearGradient id="lg" spreadMethod="reflect" x2="0.5">
+                <stop offset="0" stop-color="red"/>
+                <stop offset="1" stop-color="blue" stop-opacity="0.5"/>
+            </linearGradient>
+            <radialGradient id="rg">
+                <stop offset="0" stop-color="green"/>
+                <stop offset="1" stop-color="yellow"/>
+            </radialGradient>
+            <pattern id="patt" width="20" height="20" patternUnits="userSpaceOnUse">
+                <rect width="10" height="10"/>
+            </pattern>
+            <rect width="100" height="100" fill="url(#lg)"/>
+            <rect x="100" width="100" height="100" fill="url(#rg)"/>
+            <rect y="100" width="100" height="100" fill="url(#patt)"/>
+        </svg>"##,
+    );
+
+    assert!(pdf.contains("/ShadingType 2"));
+    assert!(pdf.contains("/ShadingType 3"));
+    assert!(pdf.contains("/PatternType 1"));
+    assert!(pdf.contains("/FunctionType 3"));
+    // Transparent gradient stops are applied via a soft mask.
+    assert!(pdf.contains("/S /Luminosity"));
+}
+
+#[test]
+fn conic_gradient() {
+    let pdf = render_pdf(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="200">
+            <conicGradient id="cg" startAngle="-90" endAngle="90">
+                <stop offset="0" stop-color="red"/>
+                <stop offset="1" stop-color="blue" stop-opacity="0.5"/>
+            </conicGradient>
+            <rect width="200" height="200" fill="url(#cg)"/>
+        </svg>"##,
+    );
+
+    // Conic gradients are converted into Coons patch meshes.
+    assert!(pdf.contains("/ShadingType 6"));
+    assert!(pdf.contains("/S /Luminosity"));
+}
+
+#[test]
+fn groups() {
+    let pdf = render_pdf(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="200">
+            <clipPath id="clip">
+                <circle cx="100" cy="100" r="50"/>
+            </clipPath>
+            <mask id="mask">
+                <rect width="200" height="100" fill="white"/>
+            </mask>
+            <g opacity="0.5" style="mix-blend-mode:multiply" clip-path="url(#clip)">
+                <rect width="200" height="200" fill="green"/>
+            </g>
+            <rect width="200" height="200" fill="blue" mask="url(#mask)"/>
+        </svg>"##,
+    );
+
+    assert!(pdf.contains("/BM /Multiply"));
+    assert!(pdf.contains("/ca 0.5"));
+    assert!(pdf.contains("/S /Alpha"));
+    assert!(pdf.contains("/S /Luminosity"));
+    assert!(pdf.contains("/S /Transparency"));
+}
+
+#[test]
+fn filters_are_rasterized() {
+    let pdf = render_pdf(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="200">
+            <filter id="blur">
+                <feGaussianBlur stdDeviation="5"/>
+            </filter>
+            <rect x="50" y="50" width="100" height="100" filter="url(#blur)"/>
+        </svg>"##,
+    );
+
+    assert!(pdf.contains("/Subtype /Image"));
+}
+
+#[test]
+fn mask_layers_are_rasterized() {
+    let pdf = render_pdf(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="200">
+            <rect width="200" height="200" fill="green"
+                  style="mask-image: linear-gradient(black, transparent), radial-gradient(black, transparent);
+                         mask-composite: exclude"/>
+        </svg>"##,
+    );
+
+    // Only the mask is rasterized, which is then used as an alpha soft mask.
+    assert!(pdf.contains("/Subtype /Image"));
+    assert!(pdf.contains("/S /Alpha"));
+}
+
+#[test]
+fn text_is_embedded() {
+    let pdf = render_pdf(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="100">
+            <text x="10" y="50" font-family="Noto Sans" font-size="32">Text</text>
+        </svg>"#,
+    );
+
+    assert!(pdf.contains("/Subtype /Type0"));
+    assert!(pdf.contains("/Encoding /Identity-H"));
+    assert!(pdf.contains("/FontFile2"));
+    assert!(pdf.contains("/ToUnicode"));
+}
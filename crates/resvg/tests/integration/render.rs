@@ -496,6 +496,70 @@ use crate::render;
 #[test] fn masking_mask_with_opacity_1() { assert_eq!(render("tests/masking/mask/with-opacity-1"), 0); }
 #[test] fn masking_mask_with_opacity_2() { assert_eq!(render("tests/masking/mask/with-opacity-2"), 0); }
 #[test] fn masking_mask_with_opacity_3() { assert_eq!(render("tests/masking/mask/with-opacity-3"), 0); }
+#[test] fn masking_mask_composite_add() { assert_eq!(render("tests/masking/mask-composite/add"), 0); }
+#[test] fn masking_mask_composite_exclude() { assert_eq!(render("tests/masking/mask-composite/exclude"), 0); }
+#[test] fn masking_mask_composite_intersect() { assert_eq!(render("tests/masking/mask-composite/intersect"), 0); }
+#[test] fn masking_mask_composite_invalid() { assert_eq!(render("tests/masking/mask-composite/invalid"), 0); }
+#[test] fn masking_mask_composite_multiple_values() { assert_eq!(render("tests/masking/mask-composite/multiple-values"), 0); }
+#[test] fn masking_mask_composite_subtract() { assert_eq!(render("tests/masking/mask-composite/subtract"), 0); }
+#[test] fn masking_mask_image_as_attribute() { assert_eq!(render("tests/masking/mask-image/as-attribute"), 0); }
+#[test] fn masking_mask_image_conic_gradient_with_position() { assert_eq!(render("tests/masking/mask-image/conic-gradient-with-position"), 0); }
+#[test] fn masking_mask_image_conic_gradient() { assert_eq!(render("tests/masking/mask-image/conic-gradient"), 0); }
+#[test] fn masking_mask_image_external_png() { assert_eq!(render("tests/masking/mask-image/external-png"), 0); }
+#[test] fn masking_mask_image_invalid_reference() { assert_eq!(render("tests/masking/mask-image/invalid-reference"), 0); }
+#[test] fn masking_mask_image_linear_gradient_to_corner() { assert_eq!(render("tests/masking/mask-image/linear-gradient-to-corner"), 0); }
+#[test] fn masking_mask_image_linear_gradient_with_angle() { assert_eq!(render("tests/masking/mask-image/linear-gradient-with-angle"), 0); }
+#[test] fn masking_mask_image_linear_gradient_with_hard_stops() { assert_eq!(render("tests/masking/mask-image/linear-gradient-with-hard-stops"), 0); }
+#[test] fn masking_mask_image_linear_gradient() { assert_eq!(render("tests/masking/mask-image/linear-gradient"), 0); }
+#[test] fn masking_mask_image_mask_element() { assert_eq!(render("tests/masking/mask-image/mask-element"), 0); }
+#[test] fn masking_mask_image_multiple_layers() { assert_eq!(render("tests/masking/mask-image/multiple-layers"), 0); }
+#[test] fn masking_mask_image_none() { assert_eq!(render("tests/masking/mask-image/none"), 0); }
+#[test] fn masking_mask_image_on_group() { assert_eq!(render("tests/masking/mask-image/on-group"), 0); }
+#[test] fn masking_mask_image_overrides_mask() { assert_eq!(render("tests/masking/mask-image/overrides-mask"), 0); }
+#[test] fn masking_mask_image_radial_gradient_with_position() { assert_eq!(render("tests/masking/mask-image/radial-gradient-with-position"), 0); }
+#[test] fn masking_mask_image_radial_gradient() { assert_eq!(render("tests/masking/mask-image/radial-gradient"), 0); }
+#[test] fn masking_mask_image_recursive() { assert_eq!(render("tests/masking/mask-image/recursive"), 0); }
+#[test] fn masking_mask_image_repeating_linear_gradient() { assert_eq!(render("tests/masking/mask-image/repeating-linear-gradient"), 0); }
+#[test] fn masking_mask_image_repeating_radial_gradient() { assert_eq!(render("tests/masking/mask-image/repeating-radial-gradient"), 0); }
+#[test] fn masking_mask_mode_alpha() { assert_eq!(render("tests/masking/mask-mode/alpha"), 0); }
+#[test] fn masking_mask_mode_luminance() { assert_eq!(render("tests/masking/mask-mode/luminance"), 0); }
+#[test] fn masking_mask_mode_on_mask_attribute() { assert_eq!(render("tests/masking/mask-mode/on-mask-attribute"), 0); }
+#[test] fn masking_mask_position_center() { assert_eq!(render("tests/masking/mask-position/center"), 0); }
+#[test] fn masking_mask_position_length() { assert_eq!(render("tests/masking/mask-position/length"), 0); }
+#[test] fn masking_mask_position_swapped_keywords() { assert_eq!(render("tests/masking/mask-position/swapped-keywords"), 0); }
+#[test] fn masking_mask_repeat_no_repeat() { assert_eq!(render("tests/masking/mask-repeat/no-repeat"), 0); }
+#[test] fn masking_mask_repeat_repeat_x() { assert_eq!(render("tests/masking/mask-repeat/repeat-x"), 0); }
+#[test] fn masking_mask_repeat_repeat() { assert_eq!(render("tests/masking/mask-repeat/repeat"), 0); }
+#[test] fn masking_mask_repeat_round() { assert_eq!(render("tests/masking/mask-repeat/round"), 0); }
+#[test] fn masking_mask_repeat_space() { assert_eq!(render("tests/masking/mask-repeat/space"), 0); }
+#[test] fn masking_mask_size_contain() { assert_eq!(render("tests/masking/mask-size/contain"), 0); }
+#[test] fn masking_mask_size_cover() { assert_eq!(render("tests/masking/mask-size/cover"), 0); }
+#[test] fn masking_mask_size_length_and_auto() { assert_eq!(render("tests/masking/mask-size/length-and-auto"), 0); }
+#[test] fn paint_servers_conicGradient_attributes_via_href() { assert_eq!(render("tests/paint-servers/conicGradient/attributes-via-href"), 0); }
+#[test] fn paint_servers_conicGradient_endAngle_less_than_startAngle() { assert_eq!(render("tests/paint-servers/conicGradient/endAngle-less-than-startAngle"), 0); }
+#[test] fn paint_servers_conicGradient_equal_angles() { assert_eq!(render("tests/paint-servers/conicGradient/equal-angles"), 0); }
+#[test] fn paint_servers_conicGradient_gradientTransform() { assert_eq!(render("tests/paint-servers/conicGradient/gradientTransform"), 0); }
+#[test] fn paint_servers_conicGradient_gradientUnits_eq_userSpaceOnUse() { assert_eq!(render("tests/paint-servers/conicGradient/gradientUnits=userSpaceOnUse"), 0); }
+#[test] fn paint_servers_conicGradient_hard_stops() { assert_eq!(render("tests/paint-servers/conicGradient/hard-stops"), 0); }
+#[test] fn paint_servers_conicGradient_on_stroke() { assert_eq!(render("tests/paint-servers/conicGradient/on-stroke"), 0); }
+#[test] fn paint_servers_conicGradient_simple_case() { assert_eq!(render("tests/paint-servers/conicGradient/simple-case"), 0); }
+#[test] fn paint_servers_conicGradient_single_stop() { assert_eq!(render("tests/paint-servers/conicGradient/single-stop"), 0); }
+#[test] fn paint_servers_conicGradient_spreadMethod_eq_reflect() { assert_eq!(render("tests/paint-servers/conicGradient/spreadMethod=reflect"), 0); }
+#[test] fn paint_servers_conicGradient_spreadMethod_eq_repeat() { assert_eq!(render("tests/paint-servers/conicGradient/spreadMethod=repeat"), 0); }
+#[test] fn paint_servers_conicGradient_startAngle_and_endAngle() { assert_eq!(render("tests/paint-servers/conicGradient/startAngle-and-endAngle"), 0); }
+#[test] fn paint_servers_conicGradient_with_stop_opacity() { assert_eq!(render("tests/paint-servers/conicGradient/with-stop-opacity"), 0); }
+#[test] fn paint_servers_hatch_attributes_via_href() { assert_eq!(render("tests/paint-servers/hatch/attributes-via-href"), 0); }
+#[test] fn paint_servers_hatch_hatchContentUnits_eq_objectBoundingBox() { assert_eq!(render("tests/paint-servers/hatch/hatchContentUnits=objectBoundingBox"), 0); }
+#[test] fn paint_servers_hatch_hatchUnits_eq_objectBoundingBox() { assert_eq!(render("tests/paint-servers/hatch/hatchUnits=objectBoundingBox"), 0); }
+#[test] fn paint_servers_hatch_multiple_hatchpaths() { assert_eq!(render("tests/paint-servers/hatch/multiple-hatchpaths"), 0); }
+#[test] fn paint_servers_hatch_no_hatchpaths() { assert_eq!(render("tests/paint-servers/hatch/no-hatchpaths"), 0); }
+#[test] fn paint_servers_hatch_on_stroke() { assert_eq!(render("tests/paint-servers/hatch/on-stroke"), 0); }
+#[test] fn paint_servers_hatch_pitch_eq_0() { assert_eq!(render("tests/paint-servers/hatch/pitch=0"), 0); }
+#[test] fn paint_servers_hatch_rotate() { assert_eq!(render("tests/paint-servers/hatch/rotate"), 0); }
+#[test] fn paint_servers_hatch_simple_case() { assert_eq!(render("tests/paint-servers/hatch/simple-case"), 0); }
+#[test] fn paint_servers_hatch_with_d() { assert_eq!(render("tests/paint-servers/hatch/with-d"), 0); }
+#[test] fn paint_servers_hatch_with_stroke_dasharray() { assert_eq!(render("tests/paint-servers/hatch/with-stroke-dasharray"), 0); }
+#[test] fn paint_servers_hatch_with_transform() { assert_eq!(render("tests/paint-servers/hatch/with-transform"), 0); }
 #[test] fn paint_servers_linearGradient_attributes_via_xlink_href_complex_order() { assert_eq!(render("tests/paint-servers/linearGradient/attributes-via-xlink-href-complex-order"), 0); }
 #[test] fn paint_servers_linearGradient_attributes_via_xlink_href_from_radialGradient() { assert_eq!(render("tests/paint-servers/linearGradient/attributes-via-xlink-href-from-radialGradient"), 0); }
 #[test] fn paint_servers_linearGradient_attributes_via_xlink_href_from_rect() { assert_eq!(render("tests/paint-servers/linearGradient/attributes-via-xlink-href-from-rect"), 0); }
@@ -534,6 +598,15 @@ use crate::render;
 #[test] fn paint_servers_linearGradient_stops_via_xlink_href_from_rect() { assert_eq!(render("tests/paint-servers/linearGradient/stops-via-xlink-href-from-rect"), 0); }
 #[test] fn paint_servers_linearGradient_stops_via_xlink_href() { assert_eq!(render("tests/paint-servers/linearGradient/stops-via-xlink-href"), 0); }
 #[test] fn paint_servers_linearGradient_unresolved_xlink_href() { assert_eq!(render("tests/paint-servers/linearGradient/unresolved-xlink-href"), 0); }
+#[test] fn paint_servers_meshgradient_curved_edges() { assert_eq!(render("tests/paint-servers/meshgradient/curved-edges"), 0); }
+#[test] fn paint_servers_meshgradient_gradientTransform() { assert_eq!(render("tests/paint-servers/meshgradient/gradientTransform"), 0); }
+#[test] fn paint_servers_meshgradient_gradientUnits_eq_objectBoundingBox() { assert_eq!(render("tests/paint-servers/meshgradient/gradientUnits=objectBoundingBox"), 0); }
+#[test] fn paint_servers_meshgradient_invalid_row_length() { assert_eq!(render("tests/paint-servers/meshgradient/invalid-row-length"), 0); }
+#[test] fn paint_servers_meshgradient_multiple_patches() { assert_eq!(render("tests/paint-servers/meshgradient/multiple-patches"), 0); }
+#[test] fn paint_servers_meshgradient_on_stroke() { assert_eq!(render("tests/paint-servers/meshgradient/on-stroke"), 0); }
+#[test] fn paint_servers_meshgradient_simple_case() { assert_eq!(render("tests/paint-servers/meshgradient/simple-case"), 0); }
+#[test] fn paint_servers_meshgradient_type_eq_bicubic() { assert_eq!(render("tests/paint-servers/meshgradient/type=bicubic"), 0); }
+#[test] fn paint_servers_meshgradient_with_stop_opacity() { assert_eq!(render("tests/paint-servers/meshgradient/with-stop-opacity"), 0); }
 #[test] fn paint_servers_pattern_attributes_via_xlink_href() { assert_eq!(render("tests/paint-servers/pattern/attributes-via-xlink-href"), 0); }
 #[test] fn paint_servers_pattern_child_with_invalid_FuncIRI() { assert_eq!(render("tests/paint-servers/pattern/child-with-invalid-FuncIRI"), 0); }
 #[test] fn paint_servers_pattern_children_via_xlink_href() { assert_eq!(render("tests/paint-servers/pattern/children-via-xlink-href"), 0); }
@@ -571,7 +644,14 @@ use crate::render;
 #[test] fn paint_servers_radialGradient_attributes_via_xlink_href_only_required() { assert_eq!(render("tests/paint-servers/radialGradient/attributes-via-xlink-href-only-required"), 0); }
 #[test] fn paint_servers_radialGradient_attributes_via_xlink_href() { assert_eq!(render("tests/paint-servers/radialGradient/attributes-via-xlink-href"), 0); }
 #[test] fn paint_servers_radialGradient_default_attributes() { assert_eq!(render("tests/paint-servers/radialGradient/default-attributes"), 0); }
+#[test] fn paint_servers_radialGradient_fr_on_stroke() { assert_eq!(render("tests/paint-servers/radialGradient/fr-on-stroke"), 0); }
+#[test] fn paint_servers_radialGradient_fr_via_xlink_href() { assert_eq!(render("tests/paint-servers/radialGradient/fr-via-xlink-href"), 0); }
+#[test] fn paint_servers_radialGradient_fr_with_focal_point_outside_the_circle() { assert_eq!(render("tests/paint-servers/radialGradient/fr-with-focal-point-outside-the-circle"), 0); }
+#[test] fn paint_servers_radialGradient_fr_with_gradientTransform() { assert_eq!(render("tests/paint-servers/radialGradient/fr-with-gradientTransform"), 0); }
+#[test] fn paint_servers_radialGradient_fr_with_spreadMethod_eq_reflect() { assert_eq!(render("tests/paint-servers/radialGradient/fr-with-spreadMethod=reflect"), 0); }
+#[test] fn paint_servers_radialGradient_fr_with_spreadMethod_eq_repeat() { assert_eq!(render("tests/paint-servers/radialGradient/fr-with-spreadMethod=repeat"), 0); }
 #[test] fn paint_servers_radialGradient_fr_eq__1() { assert_eq!(render("tests/paint-servers/radialGradient/fr=-1"), 0); }
+#[test] fn paint_servers_radialGradient_fr_eq_0_2_with_focal_point() { assert_eq!(render("tests/paint-servers/radialGradient/fr=0.2-with-focal-point"), 0); }
 #[test] fn paint_servers_radialGradient_fr_eq_0_2() { assert_eq!(render("tests/paint-servers/radialGradient/fr=0.2"), 0); }
 #[test] fn paint_servers_radialGradient_fr_eq_0_5() { assert_eq!(render("tests/paint-servers/radialGradient/fr=0.5"), 0); }
 #[test] fn paint_servers_radialGradient_fr_eq_0_7() { assert_eq!(render("tests/paint-servers/radialGradient/fr=0.7"), 0); }
@@ -943,6 +1023,16 @@ use crate::render;
 #[test] fn painting_stroke_width_negative() { assert_eq!(render("tests/painting/stroke-width/negative"), 0); }
 #[test] fn painting_stroke_width_percentage() { assert_eq!(render("tests/painting/stroke-width/percentage"), 0); }
 #[test] fn painting_stroke_width_zero() { assert_eq!(render("tests/painting/stroke-width/zero"), 0); }
+#[test] fn painting_vector_effect_inherit() { assert_eq!(render("tests/painting/vector-effect/inherit"), 0); }
+#[test] fn painting_vector_effect_invalid_value() { assert_eq!(render("tests/painting/vector-effect/invalid-value"), 0); }
+#[test] fn painting_vector_effect_non_scaling_stroke() { assert_eq!(render("tests/painting/vector-effect/non-scaling-stroke"), 0); }
+#[test] fn painting_vector_effect_non_uniform_scale() { assert_eq!(render("tests/painting/vector-effect/non-uniform-scale"), 0); }
+#[test] fn painting_vector_effect_none() { assert_eq!(render("tests/painting/vector-effect/none"), 0); }
+#[test] fn painting_vector_effect_on_line() { assert_eq!(render("tests/painting/vector-effect/on-line"), 0); }
+#[test] fn painting_vector_effect_with_dasharray() { assert_eq!(render("tests/painting/vector-effect/with-dasharray"), 0); }
+#[test] fn painting_vector_effect_with_gradient() { assert_eq!(render("tests/painting/vector-effect/with-gradient"), 0); }
+#[test] fn painting_vector_effect_with_pattern() { assert_eq!(render("tests/painting/vector-effect/with-pattern"), 0); }
+#[test] fn painting_vector_effect_with_skew() { assert_eq!(render("tests/painting/vector-effect/with-skew"), 0); }
 #[test] fn painting_visibility_bbox_impact_1() { assert_eq!(render("tests/painting/visibility/bbox-impact-1"), 0); }
 #[test] fn painting_visibility_bbox_impact_2() { assert_eq!(render("tests/painting/visibility/bbox-impact-2"), 0); }
 #[test] fn painting_visibility_bbox_impact_3() { assert_eq!(render("tests/painting/visibility/bbox-impact-3"), 0); }
@@ -1380,6 +1470,7 @@ use crate::render;
 #[test] fn text_baseline_shift_with_rotate() { assert_eq!(render("tests/text/baseline-shift/with-rotate"), 0); }
 #[test] fn text_color_font_cbdt() { assert_eq!(render("tests/text/color-font/cbdt"), 0); }
 #[test] fn text_color_font_colrv0() { assert_eq!(render("tests/text/color-font/colrv0"), 0); }
+#[test] fn text_color_font_colrv1_sweep_gradient() { assert_eq!(render("tests/text/color-font/colrv1-sweep-gradient"), 0); }
 #[test] fn text_color_font_colrv1() { assert_eq!(render("tests/text/color-font/colrv1"), 0); }
 #[test] fn text_color_font_compound_emojis_and_coordinates_list() { assert_eq!(render("tests/text/color-font/compound-emojis-and-coordinates-list"), 0); }
 #[test] fn text_color_font_compound_emojis() { assert_eq!(render("tests/text/color-font/compound-emojis"), 0); }
@@ -1388,6 +1479,9 @@ use crate::render;
 #[test] fn text_color_font_sbix() { assert_eq!(render("tests/text/color-font/sbix"), 0); }
 #[test] fn text_color_font_svg() { assert_eq!(render("tests/text/color-font/svg"), 0); }
 #[test] fn text_color_font_writing_mode_eq_tb() { assert_eq!(render("tests/text/color-font/writing-mode=tb"), 0); }
+#[test] fn text_direction_rtl_on_tspan() { assert_eq!(render("tests/text/direction/rtl-on-tspan"), 0); }
+#[test] fn text_direction_rtl_with_inline_size() { assert_eq!(render("tests/text/direction/rtl-with-inline-size"), 0); }
+#[test] fn text_direction_rtl_with_text_anchor_end() { assert_eq!(render("tests/text/direction/rtl-with-text-anchor-end"), 0); }
 #[test] fn text_direction_rtl_with_vertical_writing_mode() { assert_eq!(render("tests/text/direction/rtl-with-vertical-writing-mode"), 0); }
 #[test] fn text_direction_rtl() { assert_eq!(render("tests/text/direction/rtl"), 0); }
 #[test] fn text_dominant_baseline_alignment_baseline_and_baseline_shift_on_tspans() { assert_eq!(render("tests/text/dominant-baseline/alignment-baseline-and-baseline-shift-on-tspans"), 0); }
@@ -1413,6 +1507,19 @@ use crate::render;
 #[test] fn text_dominant_baseline_use_script() { assert_eq!(render("tests/text/dominant-baseline/use-script"), 0); }
 #[test] fn text_font_font_shorthand() { assert_eq!(render("tests/text/font/font-shorthand"), 0); }
 #[test] fn text_font_simple_case() { assert_eq!(render("tests/text/font/simple-case"), 0); }
+#[test] fn text_font_face_data_url() { assert_eq!(render("tests/text/font-face/data-url"), 0); }
+#[test] fn text_font_face_fallback_source() { assert_eq!(render("tests/text/font-face/fallback-source"), 0); }
+#[test] fn text_font_face_in_css_font_family() { assert_eq!(render("tests/text/font-face/in-css-font-family"), 0); }
+#[test] fn text_font_face_local() { assert_eq!(render("tests/text/font-face/local"), 0); }
+#[test] fn text_font_face_missing_source() { assert_eq!(render("tests/text/font-face/missing-source"), 0); }
+#[test] fn text_font_face_truetype() { assert_eq!(render("tests/text/font-face/truetype"), 0); }
+#[test] fn text_font_face_unsupported_format() { assert_eq!(render("tests/text/font-face/unsupported-format"), 0); }
+#[test] fn text_font_face_with_font_style() { assert_eq!(render("tests/text/font-face/with-font-style"), 0); }
+#[test] fn text_font_face_with_font_weight() { assert_eq!(render("tests/text/font-face/with-font-weight"), 0); }
+#[test] fn text_font_face_with_other_rules() { assert_eq!(render("tests/text/font-face/with-other-rules"), 0); }
+#[test] fn text_font_face_without_src() { assert_eq!(render("tests/text/font-face/without-src"), 0); }
+#[test] fn text_font_face_woff() { assert_eq!(render("tests/text/font-face/woff"), 0); }
+#[test] fn text_font_face_woff2() { assert_eq!(render("tests/text/font-face/woff2"), 0); }
 #[test] fn text_font_family_bold_sans_serif() { assert_eq!(render("tests/text/font-family/bold-sans-serif"), 0); }
 #[test] fn text_font_family_cursive() { assert_eq!(render("tests/text/font-family/cursive"), 0); }
 #[test] fn text_font_family_double_quoted() { assert_eq!(render("tests/text/font-family/double-quoted"), 0); }
@@ -1425,6 +1532,16 @@ use crate::render;
 #[test] fn text_font_family_sans_serif() { assert_eq!(render("tests/text/font-family/sans-serif"), 0); }
 #[test] fn text_font_family_serif() { assert_eq!(render("tests/text/font-family/serif"), 0); }
 #[test] fn text_font_family_source_sans_pro() { assert_eq!(render("tests/text/font-family/source-sans-pro"), 0); }
+#[test] fn text_font_feature_settings_inherited() { assert_eq!(render("tests/text/font-feature-settings/inherited"), 0); }
+#[test] fn text_font_feature_settings_invalid_tag() { assert_eq!(render("tests/text/font-feature-settings/invalid-tag"), 0); }
+#[test] fn text_font_feature_settings_liga_off_keyword() { assert_eq!(render("tests/text/font-feature-settings/liga-off-keyword"), 0); }
+#[test] fn text_font_feature_settings_liga_off() { assert_eq!(render("tests/text/font-feature-settings/liga-off"), 0); }
+#[test] fn text_font_feature_settings_multiple() { assert_eq!(render("tests/text/font-feature-settings/multiple"), 0); }
+#[test] fn text_font_feature_settings_normal() { assert_eq!(render("tests/text/font-feature-settings/normal"), 0); }
+#[test] fn text_font_feature_settings_onum() { assert_eq!(render("tests/text/font-feature-settings/onum"), 0); }
+#[test] fn text_font_feature_settings_overrides_font_variant() { assert_eq!(render("tests/text/font-feature-settings/overrides-font-variant"), 0); }
+#[test] fn text_font_feature_settings_pnum() { assert_eq!(render("tests/text/font-feature-settings/pnum"), 0); }
+#[test] fn text_font_feature_settings_unquoted_tag() { assert_eq!(render("tests/text/font-feature-settings/unquoted-tag"), 0); }
 #[test] fn text_font_kerning_arabic_script() { assert_eq!(render("tests/text/font-kerning/arabic-script"), 0); }
 #[test] fn text_font_kerning_as_property() { assert_eq!(render("tests/text/font-kerning/as-property"), 0); }
 #[test] fn text_font_kerning_none() { assert_eq!(render("tests/text/font-kerning/none"), 0); }
@@ -1456,7 +1573,30 @@ use crate::render;
 #[test] fn text_font_style_italic() { assert_eq!(render("tests/text/font-style/italic"), 0); }
 #[test] fn text_font_style_oblique() { assert_eq!(render("tests/text/font-style/oblique"), 0); }
 #[test] fn text_font_variant_inherit() { assert_eq!(render("tests/text/font-variant/inherit"), 0); }
+#[test] fn text_font_variant_longhand_overrides_shorthand() { assert_eq!(render("tests/text/font-variant/longhand-overrides-shorthand"), 0); }
+#[test] fn text_font_variant_none() { assert_eq!(render("tests/text/font-variant/none"), 0); }
+#[test] fn text_font_variant_shorthand_on_child_resets_longhand() { assert_eq!(render("tests/text/font-variant/shorthand-on-child-resets-longhand"), 0); }
+#[test] fn text_font_variant_shorthand_with_multiple_values() { assert_eq!(render("tests/text/font-variant/shorthand-with-multiple-values"), 0); }
 #[test] fn text_font_variant_small_caps() { assert_eq!(render("tests/text/font-variant/small-caps"), 0); }
+#[test] fn text_font_variant_caps_all_small_caps() { assert_eq!(render("tests/text/font-variant-caps/all-small-caps"), 0); }
+#[test] fn text_font_variant_caps_small_caps() { assert_eq!(render("tests/text/font-variant-caps/small-caps"), 0); }
+#[test] fn text_font_variant_east_asian_full_width() { assert_eq!(render("tests/text/font-variant-east-asian/full-width"), 0); }
+#[test] fn text_font_variant_ligatures_common_ligatures() { assert_eq!(render("tests/text/font-variant-ligatures/common-ligatures"), 0); }
+#[test] fn text_font_variant_ligatures_invalid_value() { assert_eq!(render("tests/text/font-variant-ligatures/invalid-value"), 0); }
+#[test] fn text_font_variant_ligatures_no_common_ligatures() { assert_eq!(render("tests/text/font-variant-ligatures/no-common-ligatures"), 0); }
+#[test] fn text_font_variant_ligatures_none() { assert_eq!(render("tests/text/font-variant-ligatures/none"), 0); }
+#[test] fn text_font_variant_ligatures_normal() { assert_eq!(render("tests/text/font-variant-ligatures/normal"), 0); }
+#[test] fn text_font_variant_numeric_conflicting_values() { assert_eq!(render("tests/text/font-variant-numeric/conflicting-values"), 0); }
+#[test] fn text_font_variant_numeric_diagonal_fractions() { assert_eq!(render("tests/text/font-variant-numeric/diagonal-fractions"), 0); }
+#[test] fn text_font_variant_numeric_multiple_values() { assert_eq!(render("tests/text/font-variant-numeric/multiple-values"), 0); }
+#[test] fn text_font_variant_numeric_oldstyle_nums() { assert_eq!(render("tests/text/font-variant-numeric/oldstyle-nums"), 0); }
+#[test] fn text_font_variant_numeric_ordinal() { assert_eq!(render("tests/text/font-variant-numeric/ordinal"), 0); }
+#[test] fn text_font_variant_numeric_proportional_nums() { assert_eq!(render("tests/text/font-variant-numeric/proportional-nums"), 0); }
+#[test] fn text_font_variant_numeric_slashed_zero() { assert_eq!(render("tests/text/font-variant-numeric/slashed-zero"), 0); }
+#[test] fn text_font_variant_numeric_tabular_nums() { assert_eq!(render("tests/text/font-variant-numeric/tabular-nums"), 0); }
+#[test] fn text_font_variant_position_on_tspan() { assert_eq!(render("tests/text/font-variant-position/on-tspan"), 0); }
+#[test] fn text_font_variant_position_sub() { assert_eq!(render("tests/text/font-variant-position/sub"), 0); }
+#[test] fn text_font_variant_position_super() { assert_eq!(render("tests/text/font-variant-position/super"), 0); }
 #[test] fn text_font_weight_650() { assert_eq!(render("tests/text/font-weight/650"), 0); }
 #[test] fn text_font_weight_700() { assert_eq!(render("tests/text/font-weight/700"), 0); }
 #[test] fn text_font_weight_bold() { assert_eq!(render("tests/text/font-weight/bold"), 0); }
@@ -1470,7 +1610,19 @@ use crate::render;
 #[test] fn text_font_weight_lighter() { assert_eq!(render("tests/text/font-weight/lighter"), 0); }
 #[test] fn text_font_weight_normal() { assert_eq!(render("tests/text/font-weight/normal"), 0); }
 #[test] fn text_glyph_orientation_horizontal_simple_case() { assert_eq!(render("tests/text/glyph-orientation-horizontal/simple-case"), 0); }
+#[test] fn text_glyph_orientation_vertical_90deg() { assert_eq!(render("tests/text/glyph-orientation-vertical/90deg"), 0); }
+#[test] fn text_glyph_orientation_vertical_auto() { assert_eq!(render("tests/text/glyph-orientation-vertical/auto"), 0); }
 #[test] fn text_glyph_orientation_vertical_simple_case() { assert_eq!(render("tests/text/glyph-orientation-vertical/simple-case"), 0); }
+#[test] fn text_inline_size_in_style() { assert_eq!(render("tests/text/inline-size/in-style"), 0); }
+#[test] fn text_inline_size_long_word() { assert_eq!(render("tests/text/inline-size/long-word"), 0); }
+#[test] fn text_inline_size_negative() { assert_eq!(render("tests/text/inline-size/negative"), 0); }
+#[test] fn text_inline_size_simple_case() { assert_eq!(render("tests/text/inline-size/simple-case"), 0); }
+#[test] fn text_inline_size_text_anchor_eq_end() { assert_eq!(render("tests/text/inline-size/text-anchor=end"), 0); }
+#[test] fn text_inline_size_text_anchor_eq_middle() { assert_eq!(render("tests/text/inline-size/text-anchor=middle"), 0); }
+#[test] fn text_inline_size_with_transform() { assert_eq!(render("tests/text/inline-size/with-transform"), 0); }
+#[test] fn text_inline_size_with_tspan() { assert_eq!(render("tests/text/inline-size/with-tspan"), 0); }
+#[test] fn text_inline_size_writing_mode_eq_tb() { assert_eq!(render("tests/text/inline-size/writing-mode=tb"), 0); }
+#[test] fn text_inline_size_zero() { assert_eq!(render("tests/text/inline-size/zero"), 0); }
 #[test] fn text_kerning_0() { assert_eq!(render("tests/text/kerning/0"), 0); }
 #[test] fn text_kerning_10percent() { assert_eq!(render("tests/text/kerning/10percent"), 0); }
 #[test] fn text_lengthAdjust_spacingAndGlyphs() { assert_eq!(render("tests/text/lengthAdjust/spacingAndGlyphs"), 0); }
@@ -1489,6 +1641,32 @@ use crate::render;
 #[test] fn text_letter_spacing_non_ASCII_character() { assert_eq!(render("tests/text/letter-spacing/non-ASCII-character"), 0); }
 #[test] fn text_letter_spacing_normal() { assert_eq!(render("tests/text/letter-spacing/normal"), 0); }
 #[test] fn text_letter_spacing_on_Arabic() { assert_eq!(render("tests/text/letter-spacing/on-Arabic"), 0); }
+#[test] fn text_line_height_em() { assert_eq!(render("tests/text/line-height/em"), 0); }
+#[test] fn text_line_height_in_style() { assert_eq!(render("tests/text/line-height/in-style"), 0); }
+#[test] fn text_line_height_inherited_number() { assert_eq!(render("tests/text/line-height/inherited-number"), 0); }
+#[test] fn text_line_height_inherited_percent() { assert_eq!(render("tests/text/line-height/inherited-percent"), 0); }
+#[test] fn text_line_height_length() { assert_eq!(render("tests/text/line-height/length"), 0); }
+#[test] fn text_line_height_negative() { assert_eq!(render("tests/text/line-height/negative"), 0); }
+#[test] fn text_line_height_normal() { assert_eq!(render("tests/text/line-height/normal"), 0); }
+#[test] fn text_line_height_number() { assert_eq!(render("tests/text/line-height/number"), 0); }
+#[test] fn text_line_height_percent() { assert_eq!(render("tests/text/line-height/percent"), 0); }
+#[test] fn text_line_height_with_pre() { assert_eq!(render("tests/text/line-height/with-pre"), 0); }
+#[test] fn text_shape_inside_circle() { assert_eq!(render("tests/text/shape-inside/circle"), 0); }
+#[test] fn text_shape_inside_invalid_reference() { assert_eq!(render("tests/text/shape-inside/invalid-reference"), 0); }
+#[test] fn text_shape_inside_multiple_shapes() { assert_eq!(render("tests/text/shape-inside/multiple-shapes"), 0); }
+#[test] fn text_shape_inside_overflow() { assert_eq!(render("tests/text/shape-inside/overflow"), 0); }
+#[test] fn text_shape_inside_polygon() { assert_eq!(render("tests/text/shape-inside/polygon"), 0); }
+#[test] fn text_shape_inside_rect() { assert_eq!(render("tests/text/shape-inside/rect"), 0); }
+#[test] fn text_shape_inside_reference_to_non_shape() { assert_eq!(render("tests/text/shape-inside/reference-to-non-shape"), 0); }
+#[test] fn text_shape_inside_transformed_shape() { assert_eq!(render("tests/text/shape-inside/transformed-shape"), 0); }
+#[test] fn text_shape_inside_with_shape_margin() { assert_eq!(render("tests/text/shape-inside/with-shape-margin"), 0); }
+#[test] fn text_shape_inside_with_shape_padding() { assert_eq!(render("tests/text/shape-inside/with-shape-padding"), 0); }
+#[test] fn text_shape_inside_with_shape_subtract() { assert_eq!(render("tests/text/shape-inside/with-shape-subtract"), 0); }
+#[test] fn text_shape_inside_with_text_anchor_eq_middle() { assert_eq!(render("tests/text/shape-inside/with-text-anchor=middle"), 0); }
+#[test] fn text_svg_font_kerning() { assert_eq!(render("tests/text/svg-font/kerning"), 0); }
+#[test] fn text_svg_font_ligature() { assert_eq!(render("tests/text/svg-font/ligature"), 0); }
+#[test] fn text_svg_font_missing_glyph() { assert_eq!(render("tests/text/svg-font/missing-glyph"), 0); }
+#[test] fn text_svg_font_simple_case() { assert_eq!(render("tests/text/svg-font/simple-case"), 0); }
 #[test] fn text_text_bidi_reordering() { assert_eq!(render("tests/text/text/bidi-reordering"), 0); }
 #[test] fn text_text_complex_grapheme_split_by_tspan() { assert_eq!(render("tests/text/text/complex-grapheme-split-by-tspan"), 0); }
 #[test] fn text_text_complex_graphemes_and_coordinates_list() { assert_eq!(render("tests/text/text/complex-graphemes-and-coordinates-list"), 0); }
@@ -1550,15 +1728,33 @@ use crate::render;
 #[test] fn text_text_decoration_all_types_inline_no_spaces() { assert_eq!(render("tests/text/text-decoration/all-types-inline-no-spaces"), 0); }
 #[test] fn text_text_decoration_all_types_inline() { assert_eq!(render("tests/text/text-decoration/all-types-inline"), 0); }
 #[test] fn text_text_decoration_all_types_nested() { assert_eq!(render("tests/text/text-decoration/all-types-nested"), 0); }
+#[test] fn text_text_decoration_in_style_attribute() { assert_eq!(render("tests/text/text-decoration/in-style-attribute"), 0); }
 #[test] fn text_text_decoration_indirect_with_multiple_colors() { assert_eq!(render("tests/text/text-decoration/indirect-with-multiple-colors"), 0); }
 #[test] fn text_text_decoration_indirect() { assert_eq!(render("tests/text/text-decoration/indirect"), 0); }
 #[test] fn text_text_decoration_line_through() { assert_eq!(render("tests/text/text-decoration/line-through"), 0); }
+#[test] fn text_text_decoration_longhands_override_shorthand() { assert_eq!(render("tests/text/text-decoration/longhands-override-shorthand"), 0); }
 #[test] fn text_text_decoration_outside_the_text_element() { assert_eq!(render("tests/text/text-decoration/outside-the-text-element"), 0); }
 #[test] fn text_text_decoration_overline() { assert_eq!(render("tests/text/text-decoration/overline"), 0); }
+#[test] fn text_text_decoration_shorthand_with_invalid_value() { assert_eq!(render("tests/text/text-decoration/shorthand-with-invalid-value"), 0); }
+#[test] fn text_text_decoration_shorthand_with_style_and_color() { assert_eq!(render("tests/text/text-decoration/shorthand-with-style-and-color"), 0); }
+#[test] fn text_text_decoration_skip_ink_none() { assert_eq!(render("tests/text/text-decoration/skip-ink-none"), 0); }
+#[test] fn text_text_decoration_skip_ink_with_wavy_style() { assert_eq!(render("tests/text/text-decoration/skip-ink-with-wavy-style"), 0); }
+#[test] fn text_text_decoration_skip_ink() { assert_eq!(render("tests/text/text-decoration/skip-ink"), 0); }
+#[test] fn text_text_decoration_style_dashed() { assert_eq!(render("tests/text/text-decoration/style-dashed"), 0); }
+#[test] fn text_text_decoration_style_dotted() { assert_eq!(render("tests/text/text-decoration/style-dotted"), 0); }
+#[test] fn text_text_decoration_style_double() { assert_eq!(render("tests/text/text-decoration/style-double"), 0); }
 #[test] fn text_text_decoration_style_resolving_1() { assert_eq!(render("tests/text/text-decoration/style-resolving-1"), 0); }
 #[test] fn text_text_decoration_style_resolving_2() { assert_eq!(render("tests/text/text-decoration/style-resolving-2"), 0); }
 #[test] fn text_text_decoration_style_resolving_3() { assert_eq!(render("tests/text/text-decoration/style-resolving-3"), 0); }
 #[test] fn text_text_decoration_style_resolving_4() { assert_eq!(render("tests/text/text-decoration/style-resolving-4"), 0); }
+#[test] fn text_text_decoration_style_wavy() { assert_eq!(render("tests/text/text-decoration/style-wavy"), 0); }
+#[test] fn text_text_decoration_text_decoration_color_on_tspan() { assert_eq!(render("tests/text/text-decoration/text-decoration-color-on-tspan"), 0); }
+#[test] fn text_text_decoration_text_decoration_color() { assert_eq!(render("tests/text/text-decoration/text-decoration-color"), 0); }
+#[test] fn text_text_decoration_text_decoration_fill_and_stroke() { assert_eq!(render("tests/text/text-decoration/text-decoration-fill-and-stroke"), 0); }
+#[test] fn text_text_decoration_text_decoration_fill_none() { assert_eq!(render("tests/text/text-decoration/text-decoration-fill-none"), 0); }
+#[test] fn text_text_decoration_text_decoration_fill_overrides_color() { assert_eq!(render("tests/text/text-decoration/text-decoration-fill-overrides-color"), 0); }
+#[test] fn text_text_decoration_text_underline_position_right() { assert_eq!(render("tests/text/text-decoration/text-underline-position-right"), 0); }
+#[test] fn text_text_decoration_text_underline_position_under() { assert_eq!(render("tests/text/text-decoration/text-underline-position-under"), 0); }
 #[test] fn text_text_decoration_tspan_decoration() { assert_eq!(render("tests/text/text-decoration/tspan-decoration"), 0); }
 #[test] fn text_text_decoration_underline_with_dy_list_1() { assert_eq!(render("tests/text/text-decoration/underline-with-dy-list-1"), 0); }
 #[test] fn text_text_decoration_underline_with_dy_list_2() { assert_eq!(render("tests/text/text-decoration/underline-with-dy-list-2"), 0); }
@@ -1567,11 +1763,25 @@ use crate::render;
 #[test] fn text_text_decoration_underline_with_y_list() { assert_eq!(render("tests/text/text-decoration/underline-with-y-list"), 0); }
 #[test] fn text_text_decoration_underline() { assert_eq!(render("tests/text/text-decoration/underline"), 0); }
 #[test] fn text_text_decoration_with_textLength_on_a_single_character() { assert_eq!(render("tests/text/text-decoration/with-textLength-on-a-single-character"), 0); }
+#[test] fn text_text_orientation_mixed() { assert_eq!(render("tests/text/text-orientation/mixed"), 0); }
+#[test] fn text_text_orientation_on_tspan() { assert_eq!(render("tests/text/text-orientation/on-tspan"), 0); }
+#[test] fn text_text_orientation_overrides_glyph_orientation_vertical() { assert_eq!(render("tests/text/text-orientation/overrides-glyph-orientation-vertical"), 0); }
+#[test] fn text_text_orientation_sideways() { assert_eq!(render("tests/text/text-orientation/sideways"), 0); }
+#[test] fn text_text_orientation_upright() { assert_eq!(render("tests/text/text-orientation/upright"), 0); }
+#[test] fn text_text_orientation_with_horizontal_writing_mode() { assert_eq!(render("tests/text/text-orientation/with-horizontal-writing-mode"), 0); }
 #[test] fn text_text_rendering_geometricPrecision() { assert_eq!(render("tests/text/text-rendering/geometricPrecision"), 0); }
 #[test] fn text_text_rendering_on_tspan() { assert_eq!(render("tests/text/text-rendering/on-tspan"), 0); }
 #[test] fn text_text_rendering_optimizeLegibility() { assert_eq!(render("tests/text/text-rendering/optimizeLegibility"), 0); }
 #[test] fn text_text_rendering_optimizeSpeed() { assert_eq!(render("tests/text/text-rendering/optimizeSpeed"), 0); }
 #[test] fn text_text_rendering_with_underline() { assert_eq!(render("tests/text/text-rendering/with-underline"), 0); }
+#[test] fn text_text_transform_capitalize_with_tspan() { assert_eq!(render("tests/text/text-transform/capitalize-with-tspan"), 0); }
+#[test] fn text_text_transform_capitalize() { assert_eq!(render("tests/text/text-transform/capitalize"), 0); }
+#[test] fn text_text_transform_in_style() { assert_eq!(render("tests/text/text-transform/in-style"), 0); }
+#[test] fn text_text_transform_inherited() { assert_eq!(render("tests/text/text-transform/inherited"), 0); }
+#[test] fn text_text_transform_lowercase() { assert_eq!(render("tests/text/text-transform/lowercase"), 0); }
+#[test] fn text_text_transform_none() { assert_eq!(render("tests/text/text-transform/none"), 0); }
+#[test] fn text_text_transform_on_tspan() { assert_eq!(render("tests/text/text-transform/on-tspan"), 0); }
+#[test] fn text_text_transform_uppercase() { assert_eq!(render("tests/text/text-transform/uppercase"), 0); }
 #[test] fn text_textLength_150_on_parent() { assert_eq!(render("tests/text/textLength/150-on-parent"), 0); }
 #[test] fn text_textLength_150_on_tspan() { assert_eq!(render("tests/text/textLength/150-on-tspan"), 0); }
 #[test] fn text_textLength_150() { assert_eq!(render("tests/text/textLength/150"), 0); }
@@ -1600,6 +1810,9 @@ use crate::render;
 #[test] fn text_textPath_path_with_ClosePath() { assert_eq!(render("tests/text/textPath/path-with-ClosePath"), 0); }
 #[test] fn text_textPath_path_with_subpaths_and_startOffset() { assert_eq!(render("tests/text/textPath/path-with-subpaths-and-startOffset"), 0); }
 #[test] fn text_textPath_path_with_subpaths() { assert_eq!(render("tests/text/textPath/path-with-subpaths"), 0); }
+#[test] fn text_textPath_side_eq_left() { assert_eq!(render("tests/text/textPath/side=left"), 0); }
+#[test] fn text_textPath_side_eq_right_on_circle() { assert_eq!(render("tests/text/textPath/side=right-on-circle"), 0); }
+#[test] fn text_textPath_side_eq_right_with_path() { assert_eq!(render("tests/text/textPath/side=right-with-path"), 0); }
 #[test] fn text_textPath_side_eq_right() { assert_eq!(render("tests/text/textPath/side=right"), 0); }
 #[test] fn text_textPath_simple_case() { assert_eq!(render("tests/text/textPath/simple-case"), 0); }
 #[test] fn text_textPath_spacing_eq_auto() { assert_eq!(render("tests/text/textPath/spacing=auto"), 0); }
@@ -1620,6 +1833,7 @@ use crate::render;
 #[test] fn text_textPath_with_filter() { assert_eq!(render("tests/text/textPath/with-filter"), 0); }
 #[test] fn text_textPath_with_invalid_path_and_xlink_href() { assert_eq!(render("tests/text/textPath/with-invalid-path-and-xlink-href"), 0); }
 #[test] fn text_textPath_with_letter_spacing() { assert_eq!(render("tests/text/textPath/with-letter-spacing"), 0); }
+#[test] fn text_textPath_with_path_and_startOffset() { assert_eq!(render("tests/text/textPath/with-path-and-startOffset"), 0); }
 #[test] fn text_textPath_with_path_and_xlink_href() { assert_eq!(render("tests/text/textPath/with-path-and-xlink-href"), 0); }
 #[test] fn text_textPath_with_path() { assert_eq!(render("tests/text/textPath/with-path"), 0); }
 #[test] fn text_textPath_with_rotate() { assert_eq!(render("tests/text/textPath/with-rotate"), 0); }
@@ -1670,7 +1884,23 @@ use crate::render;
 #[test] fn text_tspan_without_attributes() { assert_eq!(render("tests/text/tspan/without-attributes"), 0); }
 #[test] fn text_tspan_xml_space_1() { assert_eq!(render("tests/text/tspan/xml-space-1"), 0); }
 #[test] fn text_tspan_xml_space_2() { assert_eq!(render("tests/text/tspan/xml-space-2"), 0); }
+#[test] fn text_unicode_bidi_bidi_override_on_tspan() { assert_eq!(render("tests/text/unicode-bidi/bidi-override-on-tspan"), 0); }
 #[test] fn text_unicode_bidi_bidi_override() { assert_eq!(render("tests/text/unicode-bidi/bidi-override"), 0); }
+#[test] fn text_unicode_bidi_embed() { assert_eq!(render("tests/text/unicode-bidi/embed"), 0); }
+#[test] fn text_unicode_bidi_isolate_override() { assert_eq!(render("tests/text/unicode-bidi/isolate-override"), 0); }
+#[test] fn text_unicode_bidi_isolate() { assert_eq!(render("tests/text/unicode-bidi/isolate"), 0); }
+#[test] fn text_unicode_bidi_nested() { assert_eq!(render("tests/text/unicode-bidi/nested"), 0); }
+#[test] fn text_unicode_bidi_plaintext() { assert_eq!(render("tests/text/unicode-bidi/plaintext"), 0); }
+#[test] fn text_white_space_break_spaces() { assert_eq!(render("tests/text/white-space/break-spaces"), 0); }
+#[test] fn text_white_space_in_style() { assert_eq!(render("tests/text/white-space/in-style"), 0); }
+#[test] fn text_white_space_inherited() { assert_eq!(render("tests/text/white-space/inherited"), 0); }
+#[test] fn text_white_space_invalid_value() { assert_eq!(render("tests/text/white-space/invalid-value"), 0); }
+#[test] fn text_white_space_normal() { assert_eq!(render("tests/text/white-space/normal"), 0); }
+#[test] fn text_white_space_nowrap() { assert_eq!(render("tests/text/white-space/nowrap"), 0); }
+#[test] fn text_white_space_overrides_xml_space() { assert_eq!(render("tests/text/white-space/overrides-xml-space"), 0); }
+#[test] fn text_white_space_pre_line() { assert_eq!(render("tests/text/white-space/pre-line"), 0); }
+#[test] fn text_white_space_pre_wrap() { assert_eq!(render("tests/text/white-space/pre-wrap"), 0); }
+#[test] fn text_white_space_pre() { assert_eq!(render("tests/text/white-space/pre"), 0); }
 #[test] fn text_word_spacing__5() { assert_eq!(render("tests/text/word-spacing/-5"), 0); }
 #[test] fn text_word_spacing_0() { assert_eq!(render("tests/text/word-spacing/0"), 0); }
 #[test] fn text_word_spacing_10() { assert_eq!(render("tests/text/word-spacing/10"), 0); }
@@ -0,0 +1,241 @@
+// Copyright 2025 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use crate::GLOBAL_FONTDB;
+
+fn parse(text: &str) -> usvg::Tree {
+    usvg::Tree::from_str(text, &usvg::Options::default()).unwrap()
+}
+
+fn load(name: &str) -> usvg::Tree {
+    let path = format!("tests/tests/{}.svg", name);
+    let opt = usvg::Options {
+        resources_dir: Some(std::path::PathBuf::from(&path).parent().unwrap().to_owned()),
+        fontdb: GLOBAL_FONTDB.clone(),
+        ..usvg::Options::default()
+    };
+    usvg::Tree::from_data(&std::fs::read(&path).unwrap(), &opt).unwrap()
+}
+
+/// Renders a tree as a whole and tile by tile and makes sure the results are identical.
+fn check_tiles(tree: &usvg::Tree, scale: f32, tile_size: u32) {
+    let size = tree.size().to_int_size().scale_by(scale).unwrap();
+    let transform = tiny_skia::Transform::from_scale(scale, scale);
+
+    let mut full = tiny_skia::Pixmap::new(size.width(), size.height()).unwrap();
+    resvg::render(tree, transform, &mut full.as_mut());
+
+    for y in (0..size.height()).step_by(tile_size as usize) {
+        for x in (0..size.width()).step_by(tile_size as usize) {
+            let region = tiny_skia::IntRect::from_xywh(
+                x as i32,
+                y as i32,
+                tile_size.min(size.width() - x),
+                tile_size.min(size.height() - y),
+            )
+            .unwrap();
+
+            let mut tile = tiny_skia::Pixmap::new(region.width(), region.height()).unwrap();
+            resvg::render_region(tree, transform, size, region, &mut tile.as_mut());
+
+            let expected = full.clone_rect(region).unwrap();
+            assert!(
+                tile.data() == expected.data(),
+                "tile at {}x{} differs",
+                x,
+                y
+            );
+        }
+    }
+}
+
+#[test]
+fn shapes() {
+    check_tiles(&load("shapes/rect/simple-case"), 1.0, 64);
+}
+
+#[test]
+fn mask() {
+    check_tiles(&load("masking/mask/color-interpolation=linearRGB"), 1.0, 32);
+}
+
+#[test]
+fn clip_path() {
+    check_tiles(
+        &load("masking/clipPath/circle-shorthand-with-stroke-box"),
+        1.0,
+        70,
+    );
+}
+
+#[test]
+fn pattern() {
+    check_tiles(
+        &load("paint-servers/pattern/attributes-via-xlink-href"),
+        2.0,
+        100,
+    );
+}
+
+#[test]
+fn blend_mode() {
+    check_tiles(&load("painting/mix-blend-mode/color-burn"), 1.0, 45);
+}
+
+#[test]
+fn group_opacity() {
+    let tree = parse(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="200">
+            <g opacity="0.5">
+                <rect x="-20" y="20" width="120" height="100" fill="green"/>
+                <rect x="60.5" y="60.5" width="160" height="100" fill="blue"/>
+            </g>
+        </svg>"##,
+    );
+
+    check_tiles(&tree, 1.0, 33);
+}
+
+#[test]
+fn filters() {
+    let tree = parse(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="200">
+            <filter id="filter1">
+                <feGaussianBlur stdDeviation="10 5"/>
+            </filter>
+            <filter id="filter2">
+                <feDropShadow dx="30" dy="20" stdDeviation="3"/>
+            </filter>
+            <rect x="40" y="40" width="120" height="60" fill="green" filter="url(#filter1)"/>
+            <rect x="20" y="120" width="60" height="60" fill="blue" filter="url(#filter2)"/>
+        </svg>"##,
+    );
+
+    check_tiles(&tree, 1.0, 50);
+    check_tiles(&tree, 3.0, 128);
+}
+
+#[test]
+fn slanted_shapes() {
+    let tree = parse(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="200">
+            <polygon points="10.3,20.7 190.2,40.1 120.6,185.9 30.4,150.2" fill="green"/>
+            <circle cx="101.3" cy="99.6" r="63.7" fill="blue" fill-opacity="0.7"/>
+            <path d="M 5 195 C 60 -40 140 240 195 5" fill="none" stroke="black"
+                  stroke-width="7.3" stroke-dasharray="17 5"/>
+            <path d="M 15 5 Q 100 250 185 15" fill="none" stroke="red" stroke-width="0.6"/>
+        </svg>"##,
+    );
+
+    check_tiles(&tree, 1.0, 32);
+    check_tiles(&tree, 1.0, 57);
+    check_tiles(&tree, 2.7, 100);
+}
+
+#[test]
+fn slanted_group_opacity() {
+    let tree = parse(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="200">
+            <g opacity="0.5" transform="rotate(30 100 100)">
+                <ellipse cx="100" cy="100" rx="120" ry="50" fill="green"/>
+                <rect x="-30" y="-10" width="80" height="50" fill="blue"/>
+            </g>
+        </svg>"##,
+    );
+
+    check_tiles(&tree, 1.0, 40);
+    check_tiles(&tree, 1.5, 64);
+}
+
+#[test]
+fn slanted_blur() {
+    let tree = parse(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="200">
+            <filter id="filter1">
+                <feGaussianBlur stdDeviation="4"/>
+                <feOffset dx="-7.5" dy="-3"/>
+                <feMorphology operator="dilate" radius="2"/>
+            </filter>
+            <g filter="url(#filter1)">
+                <rect x="50" y="50" width="100" height="80" fill="green"
+                      transform="rotate(-20 100 100)"/>
+                <circle cx="60" cy="150" r="30.5" fill="blue"/>
+            </g>
+        </svg>"##,
+    );
+
+    check_tiles(&tree, 1.0, 30);
+    check_tiles(&tree, 1.0, 64);
+    check_tiles(&tree, 2.0, 128);
+}
+
+#[test]
+fn uncropped_filters() {
+    let tree = parse(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="200">
+            <filter id="filter1">
+                <feGaussianBlur stdDeviation="1"/>
+            </filter>
+            <filter id="filter2">
+                <feTurbulence baseFrequency="0.05" result="noise"/>
+                <feDisplacementMap in="SourceGraphic" in2="noise" scale="20"/>
+            </filter>
+            <circle cx="70" cy="70" r="50" fill="green" filter="url(#filter1)"/>
+            <rect x="80" y="80" width="100" height="100" fill="blue" filter="url(#filter2)"/>
+        </svg>"##,
+    );
+
+    check_tiles(&tree, 1.0, 48);
+}
+
+#[test]
+fn hairlines() {
+    let tree = parse(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="200">
+            <path d="M 3 197 L 197 4.5 M 2 60 L 198 61.3" fill="none" stroke="black"
+                  stroke-width="0.3"/>
+            <path d="M 10 190 C 20 -80 180 280 190 10" fill="none" stroke="green"
+                  stroke-width="0.25" stroke-linecap="round"/>
+            <path d="M 20 20 Q 180 30 100 180 T 30 100" fill="none" stroke="blue"
+                  stroke-width="0.2" stroke-linecap="square" stroke-dasharray="9 3"/>
+            <ellipse cx="100" cy="100" rx="95" ry="40" fill="none" stroke="red"
+                     stroke-width="0.33" transform="rotate(-35 100 100)"/>
+        </svg>"##,
+    );
+
+    check_tiles(&tree, 1.0, 33);
+    check_tiles(&tree, 3.0, 100);
+}
+
+#[test]
+fn large_canvas() {
+    // tiny-skia draws onto canvases larger than 8191 pixels in tiles.
+    let tree = parse(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="8300" height="100">
+            <circle cx="4150" cy="4060" r="4000" fill="green"/>
+            <polygon points="8000.3,10.5 8290.7,30.2 8100.1,95.4" fill="blue"/>
+            <path d="M 7900 90 C 8100 -50 8200 150 8295 5" fill="none" stroke="black"
+                  stroke-width="3.5"/>
+            <path d="M 5 95 C 3000 -30 6000 130 8290 50" fill="none" stroke="red"
+                  stroke-width="0.5"/>
+        </svg>"##,
+    );
+
+    check_tiles(&tree, 1.0, 256);
+}
+
+#[test]
+#[should_panic]
+fn region_size_mismatch() {
+    let tree = parse(r#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100"/>"#);
+    let size = tiny_skia::IntSize::from_wh(100, 100).unwrap();
+    let region = tiny_skia::IntRect::from_xywh(10, 10, 50, 50).unwrap();
+    let mut pixmap = tiny_skia::Pixmap::new(40, 50).unwrap();
+    resvg::render_region(
+        &tree,
+        tiny_skia::Transform::default(),
+        size,
+        region,
+        &mut pixmap.as_mut(),
+    );
+}
@@ -17,7 +17,8 @@ and can focus just on the rendering part.
   No need to worry about inheritable, implicit and default attributes
 - CSS will be applied
 - Only simple paths
-  - Basic shapes (like `rect` and `circle`) will be converted into paths
+  - Basic shapes (like `rect` and `circle`) are preserved as primitive nodes with their
+    original geometry, unless they have markers
   - Paths contain only absolute *MoveTo*, *LineTo*, *QuadTo*, *CurveTo* and *ClosePath* segments.
     ArcTo, implicit and relative segments will be converted
 - `use` will be resolved and replaced with the reference content
@@ -432,6 +432,8 @@ fn process(args: Args) -> Result<(), String> {
         font_resolver: usvg::FontResolver::default(),
         fontdb: Arc::new(fontdb),
         style_sheet,
+        limits: usvg::Limits::default(),
+        animation_time: None,
     };
 
     let input_svg = match in_svg {
@@ -6,7 +6,7 @@ use std::sync::Arc;
 
 use super::converter;
 use super::svgtree::{AId, EId, SvgNode};
-use crate::{ClipPath, Group, NonEmptyString, NonZeroRect, Transform, Units};
+use crate::{ClipPath, DiagnosticCode, Group, NonEmptyString, NonZeroRect, Transform, Units};
 
 pub(crate) fn convert(
     node: SvgNode,
@@ -42,7 +42,10 @@ pub(crate) fn convert(
         let object_bbox = match object_bbox {
             Some(v) => v,
             None => {
-                log::warn!("Clipping of zero-sized shapes is not allowed.");
+                node.skip(
+                    DiagnosticCode::ZeroSizedShape,
+                    "Clipping of zero-sized shapes is not allowed.".to_string(),
+                );
                 return None;
             }
         };
@@ -103,7 +106,10 @@ fn resolve_clip_path_transform(node: SvgNode, state: &converter::State) -> Optio
     let ts = match svgtypes::Transform::from_str(value) {
         Ok(v) => v,
         Err(_) => {
-            log::warn!("Failed to parse {} value: '{}'.", AId::Transform, value);
+            node.skip(
+                DiagnosticCode::InvalidValue,
+                format!("Failed to parse {} value: '{}'.", AId::Transform, value),
+            );
             return None;
         }
     };
@@ -42,6 +42,10 @@ pub struct State<'a> {
     pub(crate) opt: &'a Options<'a>,
 }
 
+/// Outlines depend on variable font axis values as well.
+#[cfg(feature = "text")]
+type OutlineKey = (ID, GlyphId, Arc<[FontVariation]>);
+
 #[derive(Clone)]
 pub struct Cache {
     /// This fontdb is initialized from [`Options::fontdb`] and then populated
@@ -50,7 +54,7 @@ pub struct Cache {
     pub fontdb: Arc<Database>,
 
     #[cfg(feature = "text")]
-    cache_outline: HashMap<(ID, GlyphId), Option<tiny_skia_path::Path>>,
+    cache_outline: HashMap<OutlineKey, Option<tiny_skia_path::Path>>,
     #[cfg(feature = "text")]
     cache_colr: HashMap<(ID, GlyphId), Option<Tree>>,
     #[cfg(feature = "text")]
@@ -67,11 +71,20 @@ pub struct Cache {
     all_ids: HashSet<u64>,
     linear_gradient_index: usize,
     radial_gradient_index: usize,
+    conic_gradient_index: usize,
     pattern_index: usize,
+    mesh_gradient_index: usize,
+    hatch_index: usize,
     clip_path_index: usize,
     mask_index: usize,
     filter_index: usize,
     image_index: usize,
+    #[cfg(feature = "text")]
+    text_path_index: usize,
+
+    /// Problems found after the SVG tree was converted,
+    /// when SVG elements are no longer available.
+    pub(crate) detached_diagnostics: Vec<Diagnostic>,
 }
 
 macro_rules! font_lookup {
@@ -114,14 +127,37 @@ impl Cache {
             all_ids: HashSet::new(),
             linear_gradient_index: 0,
             radial_gradient_index: 0,
+            conic_gradient_index: 0,
             pattern_index: 0,
+            mesh_gradient_index: 0,
+            hatch_index: 0,
             clip_path_index: 0,
             mask_index: 0,
             filter_index: 0,
             image_index: 0,
+            #[cfg(feature = "text")]
+            text_path_index: 0,
+
+            detached_diagnostics: Vec::new(),
         }
     }
 
+    /// Reports a problem that caused an element to be skipped after the conversion.
+    pub(crate) fn skip_detached(
+        &mut self,
+        code: DiagnosticCode,
+        element_id: &str,
+        message: String,
+    ) {
+        self.detached_diagnostics.push(Diagnostic {
+            code,
+            severity: Severity::Error,
+            element_id: element_id.to_string(),
+            pos: None,
+            message,
+        });
+    }
+
     // TODO: macros?
     pub(crate) fn gen_linear_gradient_id(&mut self) -> NonEmptyString {
         loop {
@@ -145,6 +181,17 @@ impl Cache {
         }
     }
 
+    pub(crate) fn gen_conic_gradient_id(&mut self) -> NonEmptyString {
+        loop {
+            self.conic_gradient_index += 1;
+            let new_id = format!("conicGradient{}", self.conic_gradient_index);
+            let new_hash = string_hash(&new_id);
+            if !self.all_ids.contains(&new_hash) {
+                return NonEmptyString::new(new_id).unwrap();
+            }
+        }
+    }
+
     pub(crate) fn gen_pattern_id(&mut self) -> NonEmptyString {
         loop {
             self.pattern_index += 1;
@@ -156,6 +203,28 @@ impl Cache {
         }
     }
 
+    pub(crate) fn gen_mesh_gradient_id(&mut self) -> NonEmptyString {
+        loop {
+            self.mesh_gradient_index += 1;
+            let new_id = format!("meshgradient{}", self.mesh_gradient_index);
+            let new_hash = string_hash(&new_id);
+            if !self.all_ids.contains(&new_hash) {
+                return NonEmptyString::new(new_id).unwrap();
+            }
+        }
+    }
+
+    pub(crate) fn gen_hatch_id(&mut self) -> NonEmptyString {
+        loop {
+            self.hatch_index += 1;
+            let new_id = format!("hatch{}", self.hatch_index);
+            let new_hash = string_hash(&new_id);
+            if !self.all_ids.contains(&new_hash) {
+                return NonEmptyString::new(new_id).unwrap();
+            }
+        }
+    }
+
     pub(crate) fn gen_clip_path_id(&mut self) -> NonEmptyString {
         loop {
             self.clip_path_index += 1;
@@ -200,7 +269,36 @@ impl Cache {
         }
     }
 
-    font_lookup!(fontdb_outline, cache_outline, outline, tiny_skia_path::Path);
+    #[cfg(feature = "text")]
+    pub(crate) fn gen_text_path_id(&mut self) -> NonEmptyString {
+        loop {
+            self.text_path_index += 1;
+            let new_id = format!("textPath{}", self.text_path_index);
+            let new_hash = string_hash(&new_id);
+            if !self.all_ids.contains(&new_hash) {
+                return NonEmptyString::new(new_id).unwrap();
+            }
+        }
+    }
+
+    #[cfg(feature = "text")]
+    pub(crate) fn fontdb_outline(
+        &mut self,
+        font: ID,
+        glyph: GlyphId,
+        variations: &Arc<[FontVariation]>,
+    ) -> Option<tiny_skia_path::Path> {
+        let key = (font, glyph, variations.clone());
+        match self.cache_outline.get(&key) {
+            Some(cache_hit) => cache_hit.clone(),
+            None => {
+                let lookup = self.fontdb.outline(font, glyph, variations);
+                self.cache_outline.insert(key, lookup.clone());
+                lookup
+            }
+        }
+    }
+
     font_lookup!(fontdb_colr, cache_colr, colr, Tree);
     font_lookup!(fontdb_svg, cache_svg, svg, Node);
     font_lookup!(fontdb_raster, cache_raster, raster, BitmapImage);
@@ -355,12 +453,16 @@ pub(crate) fn convert_doc(svg_doc: &svgtree::Document, opt: &Options) -> Result<
         root: Group::empty(),
         linear_gradients: Vec::new(),
         radial_gradients: Vec::new(),
+        conic_gradients: Vec::new(),
         patterns: Vec::new(),
+        mesh_gradients: Vec::new(),
+        hatches: Vec::new(),
         clip_paths: Vec::new(),
         masks: Vec::new(),
         filters: Vec::new(),
         #[cfg(feature = "text")]
         fontdb: opt.fontdb.clone(),
+        diagnostics: Vec::new(),
     };
 
     if !svg.is_visible_element(opt) {
@@ -382,17 +484,33 @@ pub(crate) fn convert_doc(svg_doc: &svgtree::Document, opt: &Options) -> Result<
         opt.fontdb.clone(),
     );
 
+    #[cfg(feature = "text")]
+    super::font_face::load_font_faces(svg_doc, opt, &mut cache.fontdb);
+    #[cfg(feature = "text")]
+    super::svg_font::load_svg_fonts(svg_doc, &mut cache.fontdb);
+
     for node in svg_doc.descendants() {
         if let Some(tag) = node.tag_name() {
             if matches!(
                 tag,
                 EId::ClipPath
+                    | EId::ConicGradient
                     | EId::Filter
+                    | EId::Hatch
                     | EId::LinearGradient
                     | EId::Mask
+                    | EId::Meshgradient
                     | EId::Pattern
                     | EId::RadialGradient
                     | EId::Image
+                    // Text paths use shape IDs.
+                    | EId::Circle
+                    | EId::Ellipse
+                    | EId::Line
+                    | EId::Path
+                    | EId::Polygon
+                    | EId::Polyline
+                    | EId::Rect
             ) {
                 if !node.element_id().is_empty() {
                     cache.all_ids.insert(string_hash(node.element_id()));
@@ -433,6 +551,10 @@ pub(crate) fn convert_doc(svg_doc: &svgtree::Document, opt: &Options) -> Result<
         None,
         &mut cache,
     );
+    for diagnostic in cache.detached_diagnostics.drain(..) {
+        svg_doc.report(diagnostic);
+    }
+
     tree.collect_paint_servers();
     tree.root.collect_clip_paths(&mut tree.clip_paths);
     tree.root.collect_masks(&mut tree.masks);
@@ -609,7 +731,16 @@ fn convert_element_impl(
         EId::Polygon => {
             convert_polygon_node(node, state, cache, parent);
         }
-        EId::Circle | EId::Line | EId::Polyline | EId::Path => {
+        EId::Circle => {
+            convert_circle_node(node, state, cache, parent);
+        }
+        EId::Line => {
+            convert_line_node(node, state, cache, parent);
+        }
+        EId::Polyline => {
+            convert_polyline_node(node, state, cache, parent);
+        }
+        EId::Path => {
             if let Some(path) = super::shapes::convert(node, state) {
                 convert_path(node, path, state, cache, parent);
             }
@@ -694,7 +825,13 @@ fn convert_clip_path_elements_impl(
         EId::Polygon => {
             convert_polygon_node(node, state, cache, parent);
         }
-        EId::Circle | EId::Polyline | EId::Path => {
+        EId::Circle => {
+            convert_circle_node(node, state, cache, parent);
+        }
+        EId::Polyline => {
+            convert_polyline_node(node, state, cache, parent);
+        }
+        EId::Path => {
             if let Some(path) = super::shapes::convert(node, state) {
                 convert_path(node, path, state, cache, parent);
             }
@@ -706,7 +843,10 @@ fn convert_clip_path_elements_impl(
             }
         }
         _ => {
-            log::warn!("'{}' is no a valid 'clip-path' child.", tag_name);
+            node.skip(
+                DiagnosticCode::InvalidChild,
+                format!("'{}' is no a valid 'clip-path' child.", tag_name),
+            );
         }
     }
 }
@@ -773,7 +913,7 @@ pub(crate) fn convert_group(
         blend_mode,
         isolate,
         clip_path: None,
-        mask: None,
+        masks: Vec::new(),
         filters: Vec::new(),
         is_context_element: false,
         bounding_box: dummy,
@@ -801,14 +941,9 @@ pub(crate) fn convert_group(
         }
     }
 
-    let mut mask = None;
+    let mut masks = Vec::new();
     if state.parent_clip_path.is_none() {
-        if let Some(link) = node.attribute::<SvgNode>(AId::Mask) {
-            mask = super::mask::convert(link, state, object_bbox, cache);
-            if mask.is_none() {
-                return None;
-            }
-        }
+        masks = super::mask::convert_layers(node, state, object_bbox, cache)?;
     }
 
     let filters = {
@@ -842,7 +977,7 @@ pub(crate) fn convert_group(
 
     let required = opacity.get().approx_ne_ulps(&1.0, 4)
         || clip_path.is_some()
-        || mask.is_some()
+        || !masks.is_empty()
         || !filters.is_empty()
         || !transform.is_identity()
         || blend_mode != BlendMode::Normal
@@ -856,7 +991,7 @@ pub(crate) fn convert_group(
     }
 
     g.clip_path = clip_path;
-    g.mask = mask;
+    g.masks = masks;
     g.filters = filters;
 
     // Must be called after we set Group::filters
@@ -986,11 +1121,16 @@ fn convert_path(
         path_transform,
     );
 
-    let path = match path {
+    let mut path = match path {
         Some(v) => v,
         None => return,
     };
 
+    let vector_effect: VectorEffect = node.attribute(AId::VectorEffect).unwrap_or_default();
+    if vector_effect != VectorEffect::None {
+        path.set_vector_effect(vector_effect);
+    }
+
     match (raw_paint_order.order, marker) {
         ([PaintOrderKind::Markers, _, _], Some(markers_node)) => {
             parent.children.push(Node::Group(Box::new(markers_node)));
@@ -1009,55 +1149,36 @@ fn convert_path(
     }
 }
 
-fn convert_rect_node(node: SvgNode, state: &State, cache: &mut Cache, parent: &mut Group) {
-    use super::shapes;
-    use svgtypes::Length;
-
-    // Extract rect attributes
-    let width = node.convert_user_length(AId::Width, state, Length::zero());
-    let height = node.convert_user_length(AId::Height, state, Length::zero());
-    if !width.is_valid_length() {
-        log::warn!(
-            "Rect '{}' has an invalid 'width' value. Skipped.",
-            node.element_id()
-        );
-        return;
-    }
-    if !height.is_valid_length() {
-        log::warn!(
-            "Rect '{}' has an invalid 'height' value. Skipped.",
-            node.element_id()
-        );
-        return;
-    }
-
-    let x = node.convert_user_length(AId::X, state, Length::zero());
-    let y = node.convert_user_length(AId::Y, state, Length::zero());
-
-    let (mut rx, mut ry) = shapes::resolve_rx_ry(node, state);
-
-    // Clamp rx/ry to the half of the width/height.
-    if rx > width / 2.0 {
-        rx = width / 2.0;
-    }
-    if ry > height / 2.0 {
-        ry = height / 2.0;
-    }
-
-    // Convert to path for stroke bounding box calculation
-    let path_for_bbox = match shapes::convert_rect(node, state) {
-        Some(p) => p,
-        None => return,
-    };
-
-    // Calculate bounding box
-    let bbox = match Rect::from_xywh(x, y, width, height) {
-        Some(b) => b,
-        None => return,
-    };
+/// Style and bounding boxes shared by all primitive shape nodes.
+struct ShapeAttributes {
+    id: String,
+    visible: bool,
+    fill: Option<Fill>,
+    stroke: Option<Stroke>,
+    paint_order: PaintOrder,
+    rendering_mode: ShapeRendering,
+    vector_effect: VectorEffect,
+    abs_transform: Transform,
+    bounding_box: Rect,
+    abs_bounding_box: Rect,
+    stroke_bounding_box: Rect,
+    abs_stroke_bounding_box: Rect,
+}
 
-    // Resolve fill, stroke, visibility, etc. (reuse logic from convert_path)
-    let has_bbox = width > 0.0 && height > 0.0;
+/// Resolves a primitive shape style and bounding boxes.
+///
+/// `path` is the shape outline and is used only for bounding boxes calculation.
+///
+/// Returns `None` when the shape should be skipped or when it had to be converted
+/// into a regular `Path` instead.
+fn resolve_shape_attributes(
+    node: SvgNode,
+    path: Arc<tiny_skia_path::Path>,
+    has_bbox: bool,
+    state: &State,
+    cache: &mut Cache,
+    parent: &mut Group,
+) -> Option<ShapeAttributes> {
     let mut fill = super::style::resolve_fill(node, has_bbox, state, cache);
     let mut stroke = super::style::resolve_stroke(node, has_bbox, state, cache);
     let visibility: Visibility = node.find_attribute(AId::Visibility).unwrap_or_default();
@@ -1069,6 +1190,7 @@ fn convert_rect_node(node: SvgNode, state: &State, cache: &mut Cache, parent: &m
     let raw_paint_order: svgtypes::PaintOrder =
         node.find_attribute(AId::PaintOrder).unwrap_or_default();
     let paint_order = svg_paint_order_to_usvg(raw_paint_order);
+    let vector_effect: VectorEffect = node.attribute(AId::VectorEffect).unwrap_or_default();
     let shape_transform = parent.abs_transform;
 
     // If a shape doesn't have a fill or a stroke then it's invisible.
@@ -1076,7 +1198,6 @@ fn convert_rect_node(node: SvgNode, state: &State, cache: &mut Cache, parent: &m
         visible = false;
     }
 
-    // Process paint (similar to convert_path)
     if let Some(fill) = fill.as_mut() {
         if let Some(ContextElement::PathNode(context_transform, context_bbox)) =
             fill.context_element
@@ -1087,7 +1208,7 @@ fn convert_rect_node(node: SvgNode, state: &State, cache: &mut Cache, parent: &m
                 context_transform,
                 context_bbox.map(|r| r.to_rect()),
                 shape_transform,
-                path_for_bbox.bounds(),
+                path.bounds(),
                 cache,
             );
             fill.context_element = None;
@@ -1104,403 +1225,322 @@ fn convert_rect_node(node: SvgNode, state: &State, cache: &mut Cache, parent: &m
                 context_transform,
                 context_bbox.map(|r| r.to_rect()),
                 shape_transform,
-                path_for_bbox.bounds(),
+                path.bounds(),
                 cache,
             );
             stroke.context_element = None;
         }
     }
 
-    // Calculate bounding boxes
-    let bounding_box = bbox;
-    let stroke_bounding_box =
-        Path::calculate_stroke_bbox(stroke.as_ref(), &path_for_bbox).unwrap_or(bounding_box);
-
-    let abs_bounding_box: Rect;
-    let abs_stroke_bounding_box: Rect;
-    if shape_transform.has_skew() {
-        let path2 = path_for_bbox.as_ref().clone();
-        if let Some(path2) = path2.transform(shape_transform) {
-            if let Some(bbox) = path2.compute_tight_bounds() {
-                abs_bounding_box = bbox;
-                abs_stroke_bounding_box = Path::calculate_stroke_bbox(stroke.as_ref(), &path2)
-                    .unwrap_or(abs_bounding_box);
-            } else {
-                return;
-            }
-        } else {
-            return;
-        }
-    } else {
-        abs_bounding_box = match bounding_box.transform(shape_transform) {
-            Some(b) => b,
-            None => return,
-        };
-        abs_stroke_bounding_box = match stroke_bounding_box.transform(shape_transform) {
-            Some(b) => b,
-            None => return,
-        };
-    }
+    let bboxes =
+        ShapeBoundingBoxes::calculate(&path, stroke.as_ref(), vector_effect, shape_transform)?;
 
-    // If markers are present, we need to fall back to Path node for proper marker handling.
-    // Markers require path data to determine placement and proper paint order handling.
-    // This is a limitation: shapes with markers are converted to paths rather than preserved
-    // as primitive nodes.
+    // Markers require path data to determine their placement,
+    // therefore shapes with markers are converted into paths
+    // rather than preserved as primitive nodes.
     if super::marker::is_valid(node) && visible {
-        // Convert to path and use the existing convert_path logic
-        convert_path(node, path_for_bbox, state, cache, parent);
-        return;
+        convert_path(node, path, state, cache, parent);
+        return None;
     }
 
-    // Nodes generated by markers must not have an ID.
+    // Nodes generated by markers must not have an ID. Otherwise we would have duplicates.
     let id = if state.parent_markers.is_empty() {
         node.element_id().to_string()
     } else {
         String::new()
     };
 
-    let rect = Rectangle {
+    Some(ShapeAttributes {
         id,
         visible,
         fill,
         stroke,
         paint_order,
         rendering_mode,
-        x,
-        y,
-        width,
-        height,
-        rx,
-        ry,
+        vector_effect,
         abs_transform: shape_transform,
-        bounding_box,
-        abs_bounding_box,
-        stroke_bounding_box,
-        abs_stroke_bounding_box,
-    };
-
-    parent.children.push(Node::Rectangle(Box::new(rect)));
+        bounding_box: bboxes.bounding_box,
+        abs_bounding_box: bboxes.abs_bounding_box,
+        stroke_bounding_box: bboxes.stroke_bounding_box,
+        abs_stroke_bounding_box: bboxes.abs_stroke_bounding_box,
+    })
 }
 
-fn convert_ellipse_node(node: SvgNode, state: &State, cache: &mut Cache, parent: &mut Group) {
+fn convert_rect_node(node: SvgNode, state: &State, cache: &mut Cache, parent: &mut Group) {
     use super::shapes;
     use svgtypes::Length;
 
-    // Extract ellipse attributes
-    let cx = node.convert_user_length(AId::Cx, state, Length::zero());
-    let cy = node.convert_user_length(AId::Cy, state, Length::zero());
-    let (rx, ry) = shapes::resolve_rx_ry(node, state);
-
-    if !rx.is_valid_length() {
-        log::warn!(
-            "Ellipse '{}' has an invalid 'rx' value. Skipped.",
-            node.element_id()
+    let width = node.convert_user_length(AId::Width, state, Length::zero());
+    let height = node.convert_user_length(AId::Height, state, Length::zero());
+    if !width.is_valid_length() {
+        node.skip(
+            DiagnosticCode::InvalidSize,
+            format!(
+                "Rect '{}' has an invalid 'width' value. Skipped.",
+                node.element_id()
+            ),
         );
         return;
     }
-
-    if !ry.is_valid_length() {
-        log::warn!(
-            "Ellipse '{}' has an invalid 'ry' value. Skipped.",
-            node.element_id()
+    if !height.is_valid_length() {
+        node.skip(
+            DiagnosticCode::InvalidSize,
+            format!(
+                "Rect '{}' has an invalid 'height' value. Skipped.",
+                node.element_id()
+            ),
         );
         return;
     }
 
-    // Convert to path for bounding box calculation
-    let path_for_bbox = match shapes::convert_ellipse(node, state) {
+    let x = node.convert_user_length(AId::X, state, Length::zero());
+    let y = node.convert_user_length(AId::Y, state, Length::zero());
+
+    let (mut rx, mut ry) = shapes::resolve_rx_ry(node, state);
+
+    // Clamp rx/ry to the half of the width/height.
+    if rx > width / 2.0 {
+        rx = width / 2.0;
+    }
+    if ry > height / 2.0 {
+        ry = height / 2.0;
+    }
+
+    let path = match shapes::convert_rect(node, state) {
         Some(p) => p,
         None => return,
     };
 
-    // Calculate bounding box
-    let bbox = match path_for_bbox.compute_tight_bounds() {
-        Some(b) => b,
+    let has_bbox = width > 0.0 && height > 0.0;
+    let attrs = match resolve_shape_attributes(node, path, has_bbox, state, cache, parent) {
+        Some(v) => v,
         None => return,
     };
 
-    // Resolve fill, stroke, visibility, etc.
-    let has_bbox = rx > 0.0 && ry > 0.0;
-    let mut fill = super::style::resolve_fill(node, has_bbox, state, cache);
-    let mut stroke = super::style::resolve_stroke(node, has_bbox, state, cache);
-    let visibility: Visibility = node.find_attribute(AId::Visibility).unwrap_or_default();
-    let mut visible = visibility == Visibility::Visible;
-    let rendering_mode: ShapeRendering = node
-        .find_attribute(AId::ShapeRendering)
-        .unwrap_or(state.opt.shape_rendering);
-
-    let raw_paint_order: svgtypes::PaintOrder =
-        node.find_attribute(AId::PaintOrder).unwrap_or_default();
-    let paint_order = svg_paint_order_to_usvg(raw_paint_order);
-    let shape_transform = parent.abs_transform;
-
-    if fill.is_none() && stroke.is_none() {
-        visible = false;
-    }
+    let rect = Rectangle {
+        id: attrs.id,
+        visible: attrs.visible,
+        fill: attrs.fill,
+        stroke: attrs.stroke,
+        paint_order: attrs.paint_order,
+        rendering_mode: attrs.rendering_mode,
+        vector_effect: attrs.vector_effect,
+        x,
+        y,
+        width,
+        height,
+        rx,
+        ry,
+        abs_transform: attrs.abs_transform,
+        bounding_box: attrs.bounding_box,
+        abs_bounding_box: attrs.abs_bounding_box,
+        stroke_bounding_box: attrs.stroke_bounding_box,
+        abs_stroke_bounding_box: attrs.abs_stroke_bounding_box,
+    };
 
-    // Process paint
-    if let Some(fill) = fill.as_mut() {
-        if let Some(ContextElement::PathNode(context_transform, context_bbox)) =
-            fill.context_element
-        {
-            process_paint(
-                &mut fill.paint,
-                true,
-                context_transform,
-                context_bbox.map(|r| r.to_rect()),
-                shape_transform,
-                path_for_bbox.bounds(),
-                cache,
-            );
-            fill.context_element = None;
-        }
-    }
+    parent.children.push(Node::Rectangle(Box::new(rect)));
+}
 
-    if let Some(stroke) = stroke.as_mut() {
-        if let Some(ContextElement::PathNode(context_transform, context_bbox)) =
-            stroke.context_element
-        {
-            process_paint(
-                &mut stroke.paint,
-                true,
-                context_transform,
-                context_bbox.map(|r| r.to_rect()),
-                shape_transform,
-                path_for_bbox.bounds(),
-                cache,
-            );
-            stroke.context_element = None;
-        }
-    }
+fn convert_ellipse_node(node: SvgNode, state: &State, cache: &mut Cache, parent: &mut Group) {
+    use super::shapes;
+    use svgtypes::Length;
 
-    // Calculate bounding boxes
-    let bounding_box = bbox;
-    let stroke_bounding_box =
-        Path::calculate_stroke_bbox(stroke.as_ref(), &path_for_bbox).unwrap_or(bounding_box);
-
-    let abs_bounding_box: Rect;
-    let abs_stroke_bounding_box: Rect;
-    if shape_transform.has_skew() {
-        let path2 = path_for_bbox.as_ref().clone();
-        if let Some(path2) = path2.transform(shape_transform) {
-            if let Some(bbox) = path2.compute_tight_bounds() {
-                abs_bounding_box = bbox;
-                abs_stroke_bounding_box = Path::calculate_stroke_bbox(stroke.as_ref(), &path2)
-                    .unwrap_or(abs_bounding_box);
-            } else {
-                return;
-            }
-        } else {
-            return;
-        }
-    } else {
-        abs_bounding_box = match bounding_box.transform(shape_transform) {
-            Some(b) => b,
-            None => return,
-        };
-        abs_stroke_bounding_box = match stroke_bounding_box.transform(shape_transform) {
-            Some(b) => b,
-            None => return,
-        };
-    }
+    let cx = node.convert_user_length(AId::Cx, state, Length::zero());
+    let cy = node.convert_user_length(AId::Cy, state, Length::zero());
+    let (rx, ry) = shapes::resolve_rx_ry(node, state);
 
-    // If markers are present, we need to fall back to Path node for proper marker handling.
-    // Markers require path data to determine placement and proper paint order handling.
-    // This is a limitation: shapes with markers are converted to paths rather than preserved
-    // as primitive nodes.
-    if super::marker::is_valid(node) && visible {
-        // Convert to path and use the existing convert_path logic
-        convert_path(node, path_for_bbox, state, cache, parent);
-        return;
-    }
+    // `convert_ellipse` will validate `rx` and `ry`.
+    let path = match shapes::convert_ellipse(node, state) {
+        Some(p) => p,
+        None => return,
+    };
 
-    let id = if state.parent_markers.is_empty() {
-        node.element_id().to_string()
-    } else {
-        String::new()
+    let has_bbox = rx > 0.0 && ry > 0.0;
+    let attrs = match resolve_shape_attributes(node, path, has_bbox, state, cache, parent) {
+        Some(v) => v,
+        None => return,
     };
 
     let ellipse = Ellipse {
-        id,
-        visible,
-        fill,
-        stroke,
-        paint_order,
-        rendering_mode,
+        id: attrs.id,
+        visible: attrs.visible,
+        fill: attrs.fill,
+        stroke: attrs.stroke,
+        paint_order: attrs.paint_order,
+        rendering_mode: attrs.rendering_mode,
+        vector_effect: attrs.vector_effect,
         cx,
         cy,
         rx,
         ry,
-        abs_transform: shape_transform,
-        bounding_box,
-        abs_bounding_box,
-        stroke_bounding_box,
-        abs_stroke_bounding_box,
+        abs_transform: attrs.abs_transform,
+        bounding_box: attrs.bounding_box,
+        abs_bounding_box: attrs.abs_bounding_box,
+        stroke_bounding_box: attrs.stroke_bounding_box,
+        abs_stroke_bounding_box: attrs.abs_stroke_bounding_box,
     };
 
     parent.children.push(Node::Ellipse(Box::new(ellipse)));
 }
 
-fn convert_polygon_node(node: SvgNode, state: &State, cache: &mut Cache, parent: &mut Group) {
+fn convert_circle_node(node: SvgNode, state: &State, cache: &mut Cache, parent: &mut Group) {
     use super::shapes;
-    use svgtypes::PointsParser;
-
-    // Extract polygon points
-    let mut points = Vec::new();
-    match node.attribute::<&str>(AId::Points) {
-        Some(text) => {
-            for (x, y) in PointsParser::from(text) {
-                points.push((x as f32, y as f32));
-            }
-        }
-        _ => {
-            log::warn!(
-                "Polygon '{}' has an invalid 'points' value. Skipped.",
-                node.element_id()
-            );
-            return;
-        }
-    }
+    use svgtypes::Length;
 
-    if points.len() < 2 {
-        log::warn!(
-            "Polygon '{}' has less than 2 points. Skipped.",
-            node.element_id()
-        );
-        return;
-    }
+    let cx = node.convert_user_length(AId::Cx, state, Length::zero());
+    let cy = node.convert_user_length(AId::Cy, state, Length::zero());
+    let r = node.convert_user_length(AId::R, state, Length::zero());
 
-    // Convert to path for bounding box calculation
-    let path_for_bbox = match shapes::convert_polygon(node) {
+    // `convert_circle` will validate `r`.
+    let path = match shapes::convert_circle(node, state) {
         Some(p) => p,
         None => return,
     };
 
-    // Calculate bounding box
-    let bbox = match path_for_bbox.compute_tight_bounds() {
-        Some(b) => b,
+    let has_bbox = r > 0.0;
+    let attrs = match resolve_shape_attributes(node, path, has_bbox, state, cache, parent) {
+        Some(v) => v,
         None => return,
     };
 
-    // Resolve fill, stroke, visibility, etc.
-    let has_bbox = bbox.width() > 0.0 && bbox.height() > 0.0;
-    let mut fill = super::style::resolve_fill(node, has_bbox, state, cache);
-    let mut stroke = super::style::resolve_stroke(node, has_bbox, state, cache);
-    let visibility: Visibility = node.find_attribute(AId::Visibility).unwrap_or_default();
-    let mut visible = visibility == Visibility::Visible;
-    let rendering_mode: ShapeRendering = node
-        .find_attribute(AId::ShapeRendering)
-        .unwrap_or(state.opt.shape_rendering);
+    let circle = Circle {
+        id: attrs.id,
+        visible: attrs.visible,
+        fill: attrs.fill,
+        stroke: attrs.stroke,
+        paint_order: attrs.paint_order,
+        rendering_mode: attrs.rendering_mode,
+        vector_effect: attrs.vector_effect,
+        cx,
+        cy,
+        r,
+        abs_transform: attrs.abs_transform,
+        bounding_box: attrs.bounding_box,
+        abs_bounding_box: attrs.abs_bounding_box,
+        stroke_bounding_box: attrs.stroke_bounding_box,
+        abs_stroke_bounding_box: attrs.abs_stroke_bounding_box,
+    };
 
-    let raw_paint_order: svgtypes::PaintOrder =
-        node.find_attribute(AId::PaintOrder).unwrap_or_default();
-    let paint_order = svg_paint_order_to_usvg(raw_paint_order);
-    let shape_transform = parent.abs_transform;
+    parent.children.push(Node::Circle(Box::new(circle)));
+}
 
-    if fill.is_none() && stroke.is_none() {
-        visible = false;
-    }
+fn convert_line_node(node: SvgNode, state: &State, cache: &mut Cache, parent: &mut Group) {
+    use super::shapes;
+    use svgtypes::Length;
 
-    // Process paint
-    if let Some(fill) = fill.as_mut() {
-        if let Some(ContextElement::PathNode(context_transform, context_bbox)) =
-            fill.context_element
-        {
-            process_paint(
-                &mut fill.paint,
-                true,
-                context_transform,
-                context_bbox.map(|r| r.to_rect()),
-                shape_transform,
-                path_for_bbox.bounds(),
-                cache,
-            );
-            fill.context_element = None;
-        }
-    }
+    let x1 = node.convert_user_length(AId::X1, state, Length::zero());
+    let y1 = node.convert_user_length(AId::Y1, state, Length::zero());
+    let x2 = node.convert_user_length(AId::X2, state, Length::zero());
+    let y2 = node.convert_user_length(AId::Y2, state, Length::zero());
 
-    if let Some(stroke) = stroke.as_mut() {
-        if let Some(ContextElement::PathNode(context_transform, context_bbox)) =
-            stroke.context_element
-        {
-            process_paint(
-                &mut stroke.paint,
-                true,
-                context_transform,
-                context_bbox.map(|r| r.to_rect()),
-                shape_transform,
-                path_for_bbox.bounds(),
-                cache,
-            );
-            stroke.context_element = None;
-        }
-    }
+    let path = match shapes::convert_line(node, state) {
+        Some(p) => p,
+        None => return,
+    };
 
-    // Calculate bounding boxes
-    let bounding_box = bbox;
-    let stroke_bounding_box =
-        Path::calculate_stroke_bbox(stroke.as_ref(), &path_for_bbox).unwrap_or(bounding_box);
-
-    let abs_bounding_box: Rect;
-    let abs_stroke_bounding_box: Rect;
-    if shape_transform.has_skew() {
-        let path2 = path_for_bbox.as_ref().clone();
-        if let Some(path2) = path2.transform(shape_transform) {
-            if let Some(bbox) = path2.compute_tight_bounds() {
-                abs_bounding_box = bbox;
-                abs_stroke_bounding_box = Path::calculate_stroke_bbox(stroke.as_ref(), &path2)
-                    .unwrap_or(abs_bounding_box);
-            } else {
-                return;
-            }
-        } else {
-            return;
-        }
-    } else {
-        abs_bounding_box = match bounding_box.transform(shape_transform) {
-            Some(b) => b,
-            None => return,
-        };
-        abs_stroke_bounding_box = match stroke_bounding_box.transform(shape_transform) {
-            Some(b) => b,
-            None => return,
-        };
-    }
+    let has_bbox = path.bounds().width() > 0.0 && path.bounds().height() > 0.0;
+    let attrs = match resolve_shape_attributes(node, path, has_bbox, state, cache, parent) {
+        Some(v) => v,
+        None => return,
+    };
 
-    // If markers are present, we need to fall back to Path node for proper marker handling.
-    // Markers require path data to determine placement and proper paint order handling.
-    // This is a limitation: shapes with markers are converted to paths rather than preserved
-    // as primitive nodes.
-    if super::marker::is_valid(node) && visible {
-        // Convert to path and use the existing convert_path logic
-        convert_path(node, path_for_bbox, state, cache, parent);
-        return;
-    }
+    let line = Line {
+        id: attrs.id,
+        visible: attrs.visible,
+        fill: attrs.fill,
+        stroke: attrs.stroke,
+        paint_order: attrs.paint_order,
+        rendering_mode: attrs.rendering_mode,
+        vector_effect: attrs.vector_effect,
+        x1,
+        y1,
+        x2,
+        y2,
+        abs_transform: attrs.abs_transform,
+        bounding_box: attrs.bounding_box,
+        abs_bounding_box: attrs.abs_bounding_box,
+        stroke_bounding_box: attrs.stroke_bounding_box,
+        abs_stroke_bounding_box: attrs.abs_stroke_bounding_box,
+    };
 
-    let id = if state.parent_markers.is_empty() {
-        node.element_id().to_string()
-    } else {
-        String::new()
+    parent.children.push(Node::Line(Box::new(line)));
+}
+
+fn convert_polygon_node(node: SvgNode, state: &State, cache: &mut Cache, parent: &mut Group) {
+    // `convert_polygon` will validate `points`.
+    let path = match super::shapes::convert_polygon(node) {
+        Some(p) => p,
+        None => return,
+    };
+
+    let points = parse_points(node);
+
+    let has_bbox = path.bounds().width() > 0.0 && path.bounds().height() > 0.0;
+    let attrs = match resolve_shape_attributes(node, path, has_bbox, state, cache, parent) {
+        Some(v) => v,
+        None => return,
     };
 
     let polygon = Polygon {
-        id,
-        visible,
-        fill,
-        stroke,
-        paint_order,
-        rendering_mode,
+        id: attrs.id,
+        visible: attrs.visible,
+        fill: attrs.fill,
+        stroke: attrs.stroke,
+        paint_order: attrs.paint_order,
+        rendering_mode: attrs.rendering_mode,
+        vector_effect: attrs.vector_effect,
         points,
-        abs_transform: shape_transform,
-        bounding_box,
-        abs_bounding_box,
-        stroke_bounding_box,
-        abs_stroke_bounding_box,
+        abs_transform: attrs.abs_transform,
+        bounding_box: attrs.bounding_box,
+        abs_bounding_box: attrs.abs_bounding_box,
+        stroke_bounding_box: attrs.stroke_bounding_box,
+        abs_stroke_bounding_box: attrs.abs_stroke_bounding_box,
     };
 
     parent.children.push(Node::Polygon(Box::new(polygon)));
 }
 
+fn convert_polyline_node(node: SvgNode, state: &State, cache: &mut Cache, parent: &mut Group) {
+    // `convert_polyline` will validate `points`.
+    let path = match super::shapes::convert_polyline(node) {
+        Some(p) => p,
+        None => return,
+    };
+
+    let points = parse_points(node);
+
+    let has_bbox = path.bounds().width() > 0.0 && path.bounds().height() > 0.0;
+    let attrs = match resolve_shape_attributes(node, path, has_bbox, state, cache, parent) {
+        Some(v) => v,
+        None => return,
+    };
+
+    let polyline = Polyline {
+        id: attrs.id,
+        visible: attrs.visible,
+        fill: attrs.fill,
+        stroke: attrs.stroke,
+        paint_order: attrs.paint_order,
+        rendering_mode: attrs.rendering_mode,
+        vector_effect: attrs.vector_effect,
+        points,
+        abs_transform: attrs.abs_transform,
+        bounding_box: attrs.bounding_box,
+        abs_bounding_box: attrs.abs_bounding_box,
+        stroke_bounding_box: attrs.stroke_bounding_box,
+        abs_stroke_bounding_box: attrs.abs_stroke_bounding_box,
+    };
+
+    parent.children.push(Node::Polyline(Box::new(polyline)));
+}
+
+fn parse_points(node: SvgNode) -> Vec<(f32, f32)> {
+    let text: &str = node.attribute(AId::Points).unwrap_or_default();
+    svgtypes::PointsParser::from(text)
+        .map(|(x, y)| (x as f32, y as f32))
+        .collect()
+}
+
 fn append_single_paint_path(paint_order_kind: PaintOrderKind, path: &Path, parent: &mut Group) {
     match paint_order_kind {
         PaintOrderKind::Fill => {
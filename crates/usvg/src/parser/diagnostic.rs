@@ -0,0 +1,105 @@
+// Copyright 2025 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+/// A problem found during SVG parsing.
+///
+/// Invalid or unsupported content is never a parsing error in SVG.
+/// Instead, it is either replaced by a fallback value or skipped,
+/// and reported via a diagnostic.
+///
+/// Diagnostics are available via [`Tree::diagnostics`](crate::Tree::diagnostics).
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Diagnostic {
+    /// A problem kind.
+    pub code: DiagnosticCode,
+
+    /// What happened to the affected content.
+    pub severity: Severity,
+
+    /// ID of the element that caused the problem.
+    ///
+    /// Empty when an element doesn't have an ID.
+    /// Elements instantiated by `use` never have one.
+    pub element_id: String,
+
+    /// Element position in the SVG source.
+    ///
+    /// `None` when the problem is not related to a specific element.
+    pub pos: Option<roxmltree::TextPos>,
+
+    /// A human-readable description.
+    pub message: String,
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self.pos {
+            Some(pos) => write!(f, "{}: {}", pos, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// A diagnostic severity.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Severity {
+    /// Invalid content was replaced by a fallback.
+    Warning,
+
+    /// Content was skipped.
+    Error,
+}
+
+/// A diagnostic kind.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum DiagnosticCode {
+    /// An attribute or a CSS property has an invalid value.
+    InvalidValue,
+
+    /// A required attribute is missing.
+    MissingAttribute,
+
+    /// An element has an invalid size or region.
+    InvalidSize,
+
+    /// An element is not allowed inside its parent.
+    InvalidChild,
+
+    /// A link references an element of a wrong type or itself.
+    InvalidReference,
+
+    /// A link creates a recursion.
+    RecursiveReference,
+
+    /// An object bounding box based element was applied to a zero-sized shape.
+    ZeroSizedShape,
+
+    /// An external resource cannot be loaded.
+    ResourceLoadingFailed,
+
+    /// A valid, but unsupported feature.
+    Unsupported,
+}
+
+impl DiagnosticCode {
+    /// Returns a stable string representation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DiagnosticCode::InvalidValue => "invalid-value",
+            DiagnosticCode::MissingAttribute => "missing-attribute",
+            DiagnosticCode::InvalidSize => "invalid-size",
+            DiagnosticCode::InvalidChild => "invalid-child",
+            DiagnosticCode::InvalidReference => "invalid-reference",
+            DiagnosticCode::RecursiveReference => "recursive-reference",
+            DiagnosticCode::ZeroSizedShape => "zero-sized-shape",
+            DiagnosticCode::ResourceLoadingFailed => "resource-loading-failed",
+            DiagnosticCode::Unsupported => "unsupported",
+        }
+    }
+}
+
+impl std::fmt::Display for DiagnosticCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
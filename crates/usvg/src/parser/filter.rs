@@ -12,8 +12,8 @@ use svgtypes::{AspectRatio, Length, LengthUnit as Unit};
 
 use crate::{
     filter::{self, *},
-    ApproxZeroUlps, Color, Group, Node, NonEmptyString, NonZeroF32, NonZeroRect, Opacity, Size,
-    Units,
+    ApproxZeroUlps, Color, DiagnosticCode, Group, Node, NonEmptyString, NonZeroF32, NonZeroRect,
+    Opacity, Size, Units,
 };
 
 use super::converter::{self, SvgColorExt};
@@ -61,9 +61,12 @@ pub(crate) fn convert(
             let object_bbox = match object_bbox {
                 Some(v) => v,
                 None => {
-                    log::warn!(
-                        "Filter '{}' has an invalid region. Skipped.",
-                        node.element_id()
+                    node.skip(
+                        DiagnosticCode::InvalidSize,
+                        format!(
+                            "Filter '{}' has an invalid region. Skipped.",
+                            node.element_id()
+                        ),
                     );
                     return;
                 }
@@ -89,7 +92,10 @@ pub(crate) fn convert(
             Ok(v) => v,
             Err(e) => {
                 // Skip the whole attribute list on error.
-                log::warn!("Failed to parse a filter value cause {}. Skipping.", e);
+                node.skip(
+                    DiagnosticCode::InvalidValue,
+                    format!("Failed to parse a filter value cause {}. Skipping.", e),
+                );
                 return Ok(Vec::new());
             }
         };
@@ -215,9 +221,12 @@ fn convert_url(
 
     let mut rect = rect
         .log_none(|| {
-            log::warn!(
-                "Filter '{}' has an invalid region. Skipped.",
-                node.element_id()
+            node.skip(
+                DiagnosticCode::InvalidSize,
+                format!(
+                    "Filter '{}' has an invalid region. Skipped.",
+                    node.element_id()
+                ),
             );
         })
         .ok_or(())?;
@@ -226,7 +235,10 @@ fn convert_url(
         if let Some(object_bbox) = object_bbox {
             rect = rect.bbox_transform(object_bbox);
         } else {
-            log::warn!("Filters on zero-sized shapes are not allowed.");
+            node.skip(
+                DiagnosticCode::ZeroSizedShape,
+                "Filters on zero-sized shapes are not allowed.".to_string(),
+            );
             return Err(());
         }
     }
@@ -268,10 +280,13 @@ fn convert_url(
 fn find_filter_with_primitives<'a>(node: SvgNode<'a, 'a>) -> Option<SvgNode<'a, 'a>> {
     for link in node.href_iter() {
         if link.tag_name() != Some(EId::Filter) {
-            log::warn!(
-                "Filter '{}' cannot reference '{}' via 'xlink:href'.",
-                node.element_id(),
-                link.tag_name().unwrap()
+            node.skip(
+                DiagnosticCode::InvalidReference,
+                format!(
+                    "Filter '{}' cannot reference '{}' via 'xlink:href'.",
+                    node.element_id(),
+                    link.tag_name().unwrap()
+                ),
             );
             return None;
         }
@@ -356,7 +371,10 @@ fn collect_children(
                 EId::FeSpecularLighting => convert_specular_lighting(child, &primitives)
                     .unwrap_or_else(create_dummy_primitive),
                 tag_name => {
-                    log::warn!("'{}' is not a valid filter primitive. Skipped.", tag_name);
+                    child.skip(
+                        DiagnosticCode::InvalidChild,
+                        format!("'{}' is not a valid filter primitive. Skipped.", tag_name),
+                    );
                     continue;
                 }
             };
@@ -451,7 +469,7 @@ pub(crate) fn create_dummy_primitive() -> Kind {
 fn resolve_input(node: SvgNode, aid: AId, primitives: &[Primitive]) -> Input {
     match node.attribute(aid) {
         Some(s) => {
-            let input = parse_in(s);
+            let input = parse_in(node, s);
 
             // If `in` references an unknown `result` than fallback
             // to previous result or `SourceGraphic`.
@@ -481,12 +499,15 @@ fn resolve_input(node: SvgNode, aid: AId, primitives: &[Primitive]) -> Input {
     }
 }
 
-fn parse_in(s: &str) -> Input {
+fn parse_in(node: SvgNode, s: &str) -> Input {
     match s {
         "SourceGraphic" => Input::SourceGraphic,
         "SourceAlpha" => Input::SourceAlpha,
         "BackgroundImage" | "BackgroundAlpha" | "FillPaint" | "StrokePaint" => {
-            log::warn!("{} filter input isn't supported and not planed.", s);
+            node.warn(
+                DiagnosticCode::Unsupported,
+                format!("{} filter input isn't supported and not planed.", s),
+            );
             Input::SourceGraphic
         }
         _ => Input::Reference(s.to_string()),
@@ -845,6 +866,9 @@ fn convert_image_inner(
                         Node::Rectangle(ref mut rect) => rect.id.clear(),
                         Node::Ellipse(ref mut ellipse) => ellipse.id.clear(),
                         Node::Polygon(ref mut polygon) => polygon.id.clear(),
+                        Node::Circle(ref mut circle) => circle.id.clear(),
+                        Node::Line(ref mut line) => line.id.clear(),
+                        Node::Polyline(ref mut polyline) => polyline.id.clear(),
                         Node::Image(ref mut image) => image.id.clear(),
                         Node::Text(ref mut text) => text.id.clear(),
                     }
@@ -858,9 +882,17 @@ fn convert_image_inner(
     }
 
     let href = fe.try_attribute(AId::Href).log_none(|| {
-        log::warn!("The 'feImage' element lacks the 'xlink:href' attribute. Skipped.");
+        fe.skip(
+            DiagnosticCode::MissingAttribute,
+            "The 'feImage' element lacks the 'xlink:href' attribute. Skipped.".to_string(),
+        );
+    })?;
+    let img_data = super::image::get_href_data(href, fe, state).log_none(|| {
+        fe.skip(
+            DiagnosticCode::ResourceLoadingFailed,
+            "The 'feImage' element image cannot be loaded. Skipped.".to_string(),
+        )
     })?;
-    let img_data = super::image::get_href_data(href, state)?;
     let actual_size = img_data.actual_size()?;
 
     let aspect: AspectRatio = fe.attribute(AId::PreserveAspectRatio).unwrap_or_default();
@@ -929,7 +961,10 @@ fn convert_lighting_color(node: SvgNode) -> Color {
             if let Ok(c) = svgtypes::Color::from_str(value) {
                 c.split_alpha().0
             } else {
-                log::warn!("Failed to parse lighting-color value: '{}'.", value);
+                node.warn(
+                    DiagnosticCode::InvalidValue,
+                    format!("Failed to parse lighting-color value: '{}'.", value),
+                );
                 Color::white()
             }
         }
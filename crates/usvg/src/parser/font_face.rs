@@ -0,0 +1,261 @@
+// Copyright 2025 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! `@font-face` rules loading.
+
+use std::sync::Arc;
+
+use fontdb::Database;
+use svgtypes::{parse_font_families, FontFamily};
+
+use super::svgtree;
+use super::{woff, DiagnosticCode, Options};
+use crate::{FontStretch, FontStyle};
+
+/// Loads fonts declared via `@font-face` rules into the database.
+///
+/// Loaded faces are renamed to the family specified by the rule,
+/// so they can be referenced by `font-family`.
+pub(crate) fn load_font_faces(doc: &svgtree::Document, opt: &Options, fontdb: &mut Arc<Database>) {
+    let root = doc.root_element();
+    for block in doc.font_faces() {
+        let mut family = None;
+        let mut src = None;
+        let mut style = None;
+        let mut weight = None;
+        let mut stretch = None;
+        for declaration in simplecss::DeclarationTokenizer::from(block.as_str()) {
+            match declaration.name {
+                "font-family" => family = parse_family(declaration.value),
+                "src" => src = Some(declaration.value),
+                "font-style" => style = parse_style(declaration.value),
+                "font-weight" => weight = parse_weight(declaration.value),
+                "font-stretch" => stretch = parse_stretch(declaration.value),
+                _ => {}
+            }
+        }
+
+        // Both descriptors are required.
+        let (family, src) = match (family, src) {
+            (Some(family), Some(src)) => (family, src),
+            _ => {
+                root.warn(
+                    DiagnosticCode::MissingAttribute,
+                    "A @font-face rule without font-family or src. Skipped.".to_string(),
+                );
+                continue;
+            }
+        };
+
+        // Sources are tried in order until one of them loads.
+        let ids = split_list(src)
+            .into_iter()
+            .find_map(|source| load_source(source, opt, fontdb));
+        let ids = match ids {
+            Some(ids) => ids,
+            None => {
+                root.warn(
+                    DiagnosticCode::ResourceLoadingFailed,
+                    format!("Failed to load the '{}' font from @font-face.", family),
+                );
+                continue;
+            }
+        };
+
+        let db = Arc::make_mut(fontdb);
+        for id in ids {
+            let mut info = match db.face(id) {
+                Some(info) => info.clone(),
+                None => continue,
+            };
+            db.remove_face(id);
+
+            info.id = fontdb::ID::dummy();
+            info.families = vec![(family.clone(), fontdb::Language::English_UnitedStates)];
+            if let Some(style) = style {
+                info.style = style.into();
+            }
+            if let Some(weight) = weight {
+                info.weight = fontdb::Weight(weight);
+            }
+            if let Some(stretch) = stretch {
+                info.stretch = stretch.into();
+            }
+
+            db.push_face_info(info);
+        }
+    }
+}
+
+/// Loads a single `src` entry.
+///
+/// Returns IDs of all loaded faces or `None` when nothing was loaded.
+fn load_source(source: &str, opt: &Options, fontdb: &mut Arc<Database>) -> Option<Vec<fontdb::ID>> {
+    let (func, args) = parse_function(source)?;
+    match func {
+        "url" => {
+            // Skip formats we cannot load without fetching the data.
+            let format = args.1.and_then(|s| parse_function(s.trim()));
+            if let Some(("format", (format, _))) = format {
+                let format = unquote(format);
+                if !matches!(
+                    format,
+                    "woff" | "woff2" | "truetype" | "opentype" | "collection"
+                ) {
+                    return None;
+                }
+            }
+
+            let data = load_url(unquote(args.0), opt)?;
+            let data = if woff::is_woff(&data) {
+                woff::decode(&data)?
+            } else {
+                data
+            };
+
+            let ids =
+                Arc::make_mut(fontdb).load_font_source(fontdb::Source::Binary(Arc::new(data)));
+            if ids.is_empty() {
+                None
+            } else {
+                Some(ids.to_vec())
+            }
+        }
+        "local" => {
+            // Clone an already available face, which will be renamed afterwards.
+            let name = unquote(args.0);
+            let info = fontdb.faces().find(|face| {
+                face.post_script_name == name || face.families.iter().any(|(f, _)| f == name)
+            })?;
+            let info = info.clone();
+            Some(vec![Arc::make_mut(fontdb).push_face_info(info)])
+        }
+        _ => None,
+    }
+}
+
+fn load_url(url: &str, opt: &Options) -> Option<Vec<u8>> {
+    if let Ok(url) = data_url::DataUrl::process(url) {
+        return url.decode_to_vec().ok().map(|(data, _)| data);
+    }
+
+    let path = opt.get_abs_path(std::path::Path::new(url));
+    match std::fs::read(&path) {
+        Ok(data) => Some(data),
+        Err(_) => {
+            log::warn!("Failed to load '{}'.", url);
+            None
+        }
+    }
+}
+
+/// Parses a CSS function like `url(...) format(...)`.
+///
+/// Returns the function name, its arguments and the rest of the string.
+fn parse_function(text: &str) -> Option<(&str, (&str, Option<&str>))> {
+    let start = text.find('(')?;
+    let name = text[..start].trim();
+
+    // Quoted arguments can contain parentheses.
+    let args = &text[start + 1..];
+    let end = match args.trim_start().chars().next() {
+        Some(quote @ ('"' | '\'')) => {
+            let offset = args.find(quote)? + 1;
+            let closing = args[offset..].find(quote)? + offset;
+            args[closing..].find(')')? + closing
+        }
+        _ => args.find(')')?,
+    };
+
+    let rest = args[end + 1..].trim();
+    let rest = if rest.is_empty() { None } else { Some(rest) };
+    Some((name, (args[..end].trim(), rest)))
+}
+
+fn unquote(text: &str) -> &str {
+    let text = text.trim();
+    for quote in ['"', '\''] {
+        if let Some(inner) = text.strip_prefix(quote).and_then(|s| s.strip_suffix(quote)) {
+            return inner;
+        }
+    }
+
+    text
+}
+
+/// Splits a comma-separated list, ignoring commas inside parentheses and strings.
+fn split_list(text: &str) -> Vec<&str> {
+    let mut items = Vec::new();
+    let mut depth = 0;
+    let mut quote = None;
+    let mut start = 0;
+    for (i, c) in text.char_indices() {
+        match (quote, c) {
+            (Some(q), _) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '(') => depth += 1,
+            (None, ')') => depth -= 1,
+            (None, ',') if depth == 0 => {
+                items.push(text[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+
+    items.push(text[start..].trim());
+    items
+}
+
+pub(crate) fn parse_family(value: &str) -> Option<String> {
+    match parse_font_families(value).ok()?.into_iter().next()? {
+        FontFamily::Named(name) => Some(name),
+        // Generic families cannot be redefined.
+        _ => None,
+    }
+}
+
+pub(crate) fn parse_style(value: &str) -> Option<FontStyle> {
+    // `oblique` can be followed by an angle.
+    match value.split_ascii_whitespace().next()? {
+        "normal" => Some(FontStyle::Normal),
+        "italic" => Some(FontStyle::Italic),
+        "oblique" => Some(FontStyle::Oblique),
+        _ => None,
+    }
+}
+
+pub(crate) fn parse_weight(value: &str) -> Option<u16> {
+    // Variable fonts can specify a range. Use its start for matching.
+    match value.split_ascii_whitespace().next()? {
+        "normal" => Some(400),
+        "bold" => Some(700),
+        n => n
+            .parse::<f32>()
+            .ok()
+            .filter(|n| (1.0..=1000.0).contains(n))
+            .map(|n| n.round() as u16),
+    }
+}
+
+pub(crate) fn parse_stretch(value: &str) -> Option<FontStretch> {
+    // Variable fonts can specify a range. Use its start for matching.
+    let stretch = match value.split_ascii_whitespace().next()? {
+        "ultra-condensed" => FontStretch::UltraCondensed,
+        "extra-condensed" => FontStretch::ExtraCondensed,
+        "condensed" => FontStretch::Condensed,
+        "semi-condensed" => FontStretch::SemiCondensed,
+        "normal" => FontStretch::Normal,
+        "semi-expanded" => FontStretch::SemiExpanded,
+        "expanded" => FontStretch::Expanded,
+        "extra-expanded" => FontStretch::ExtraExpanded,
+        "ultra-expanded" => FontStretch::UltraExpanded,
+        n => {
+            let n = n.strip_suffix('%')?.parse::<f32>().ok()?;
+            super::text::stretch_from_percentage(n).0
+        }
+    };
+
+    Some(stretch)
+}
@@ -1,6 +1,7 @@
 // Copyright 2018 the Resvg Authors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+use std::cell::RefCell;
 use std::sync::Arc;
 
 use svgtypes::{AspectRatio, Length};
@@ -8,10 +9,28 @@ use svgtypes::{AspectRatio, Length};
 use super::svgtree::{AId, SvgNode};
 use super::{converter, OptionLog, Options};
 use crate::{
-    ClipPath, Group, Image, ImageKind, ImageRendering, Node, NonZeroRect, Path, Size, Transform,
-    Tree, Visibility,
+    ClipPath, DiagnosticCode, Group, Image, ImageKind, ImageRendering, Node, NonZeroRect, Path,
+    Size, Transform, Tree, Visibility,
 };
 
+thread_local! {
+    /// Problems reported by the default resolvers, one list per active `get_href_data` call.
+    ///
+    /// Resolvers don't have access to the element being converted,
+    /// so their problems are collected here and reported by the caller.
+    static RESOLVER_PROBLEMS: RefCell<Vec<Vec<String>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Reports a problem found by a default resolver.
+///
+/// Outside of SVG parsing the message is only logged.
+fn report_resolver_problem(message: String) {
+    RESOLVER_PROBLEMS.with(|problems| match problems.borrow_mut().last_mut() {
+        Some(list) => list.push(message),
+        None => log::warn!("{}", message),
+    });
+}
+
 /// A shorthand for [ImageHrefResolver]'s data function.
 pub type ImageHrefDataResolverFn<'a> =
     Box<dyn Fn(&str, Arc<Vec<u8>>, &Options) -> Option<ImageKind> + Send + Sync + 'a>;
@@ -90,7 +109,7 @@ impl ImageHrefResolver<'_> {
                 let data = match std::fs::read(&path) {
                     Ok(data) => data,
                     Err(_) => {
-                        log::warn!("Failed to load '{}'. Skipped.", href);
+                        report_resolver_problem(format!("Failed to load '{}'. Skipped.", href));
                         return None;
                     }
                 };
@@ -102,12 +121,15 @@ impl ImageHrefResolver<'_> {
                     Some(ImageFormat::WEBP) => Some(ImageKind::WEBP(Arc::new(data))),
                     Some(ImageFormat::SVG) => load_sub_svg(&data, opts),
                     _ => {
-                        log::warn!("'{}' is not a PNG, JPEG, GIF, WebP or SVG(Z) image.", href);
+                        report_resolver_problem(format!(
+                            "'{}' is not a PNG, JPEG, GIF, WebP or SVG(Z) image.",
+                            href
+                        ));
                         None
                     }
                 }
             } else {
-                log::warn!("'{}' is not a path to an image.", href);
+                report_resolver_problem(format!("'{}' is not a path to an image.", href));
                 None
             }
         })
@@ -135,11 +157,19 @@ pub(crate) fn convert(
     cache: &mut converter::Cache,
     parent: &mut Group,
 ) -> Option<()> {
-    let href = node
-        .try_attribute(AId::Href)
-        .log_none(|| log::warn!("Image lacks the 'xlink:href' attribute. Skipped."))?;
+    let href = node.try_attribute(AId::Href).log_none(|| {
+        node.skip(
+            DiagnosticCode::MissingAttribute,
+            "Image lacks the 'xlink:href' attribute. Skipped.".to_string(),
+        )
+    })?;
 
-    let kind = get_href_data(href, state)?;
+    let kind = get_href_data(href, node, state).log_none(|| {
+        node.skip(
+            DiagnosticCode::ResourceLoadingFailed,
+            format!("Image '{}' cannot be loaded. Skipped.", node.element_id()),
+        )
+    })?;
 
     let visibility: Visibility = node.find_attribute(AId::Visibility).unwrap_or_default();
     let visible = visibility == Visibility::Visible;
@@ -188,7 +218,12 @@ pub(crate) fn convert(
     let aspect: AspectRatio = node.attribute(AId::PreserveAspectRatio).unwrap_or_default();
 
     let rect = NonZeroRect::from_xywh(x, y, width, height);
-    let rect = rect.log_none(|| log::warn!("Image has an invalid size. Skipped."))?;
+    let rect = rect.log_none(|| {
+        node.skip(
+            DiagnosticCode::InvalidSize,
+            "Image has an invalid size. Skipped.".to_string(),
+        )
+    })?;
 
     convert_inner(
         kind,
@@ -285,20 +320,33 @@ pub(crate) fn convert_inner(
     Some(())
 }
 
-pub(crate) fn get_href_data(href: &str, state: &converter::State) -> Option<ImageKind> {
-    if let Ok(url) = data_url::DataUrl::process(href) {
-        let (data, _) = url.decode_to_vec().ok()?;
-
-        let mime = format!(
-            "{}/{}",
-            url.mime_type().type_.as_str(),
-            url.mime_type().subtype.as_str()
-        );
-
-        (state.opt.image_href_resolver.resolve_data)(&mime, Arc::new(data), state.opt)
+pub(crate) fn get_href_data(
+    href: &str,
+    node: SvgNode,
+    state: &converter::State,
+) -> Option<ImageKind> {
+    RESOLVER_PROBLEMS.with(|problems| problems.borrow_mut().push(Vec::new()));
+
+    let kind = if let Ok(url) = data_url::DataUrl::process(href) {
+        url.decode_to_vec().ok().and_then(|(data, _)| {
+            let mime = format!(
+                "{}/{}",
+                url.mime_type().type_.as_str(),
+                url.mime_type().subtype.as_str()
+            );
+
+            (state.opt.image_href_resolver.resolve_data)(&mime, Arc::new(data), state.opt)
+        })
     } else {
         (state.opt.image_href_resolver.resolve_string)(href, state.opt)
+    };
+
+    let problems = RESOLVER_PROBLEMS.with(|problems| problems.borrow_mut().pop());
+    for message in problems.unwrap_or_default() {
+        node.warn(DiagnosticCode::ResourceLoadingFailed, message);
     }
+
+    kind
 }
 
 /// Checks that file has a PNG, a GIF, a JPEG or a WebP magic bytes.
@@ -328,7 +376,7 @@ pub(crate) fn load_sub_svg(data: &[u8], opt: &Options) -> Option<ImageKind> {
     match Tree::from_data_nested(data, opt) {
         Ok(tree) => Some(ImageKind::SVG(tree)),
         Err(_) => {
-            log::warn!("Failed to load nested SVG image.");
+            report_resolver_problem("Failed to load nested SVG image.".to_string());
             None
         }
     }
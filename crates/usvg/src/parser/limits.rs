@@ -0,0 +1,176 @@
+// Copyright 2025 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use super::Error;
+use crate::{Fill, Group, ImageKind, Node, Paint, Rect, Stroke, Transform, Tree};
+
+/// Resource limits.
+///
+/// Protect against hostile SVG input, which can otherwise require
+/// a lot of memory and time to process or render.
+///
+/// Each limit has its own [`Error`] variant.
+/// Nested SVG images are checked against the same limits.
+#[derive(Clone, Copy, Debug)]
+pub struct Limits {
+    /// Maximum `use` elements nesting depth.
+    ///
+    /// Default: 64
+    pub max_use_depth: u32,
+
+    /// Maximum number of elements after `use` elements resolving.
+    ///
+    /// Default: 1_000_000
+    pub max_elements: usize,
+
+    /// Maximum filter region area in canvas coordinates.
+    ///
+    /// Only the part of the region inside the canvas is counted,
+    /// since the rest is never rendered.
+    ///
+    /// Default: 100_000_000 (10000x10000)
+    pub max_filter_region_area: f32,
+
+    /// Maximum number of pattern tiles required to fill an element.
+    ///
+    /// Default: 10_000_000
+    pub max_pattern_tiles: u64,
+
+    /// Maximum nesting depth of SVG images.
+    ///
+    /// `0` disallows SVG images.
+    ///
+    /// Default: 8
+    pub max_nested_images_depth: u32,
+
+    /// Maximum number of pixels in a single raster image.
+    ///
+    /// Images are not decoded by `usvg`, but their dimensions are read from the header.
+    /// The size at which an image is drawn doesn't matter.
+    ///
+    /// Default: 268_435_456 (16384x16384)
+    pub max_image_pixels: u64,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits {
+            max_use_depth: 64,
+            max_elements: 1_000_000,
+            max_filter_region_area: 100_000_000.0,
+            max_pattern_tiles: 10_000_000,
+            max_nested_images_depth: 8,
+            max_image_pixels: 16384 * 16384,
+        }
+    }
+}
+
+/// Checks limits that can be checked only on a resolved tree.
+pub(crate) fn check(tree: &Tree, limits: &Limits) -> Result<(), Error> {
+    check_group(&tree.root, tree.size.to_rect(0.0, 0.0), limits, 0)
+}
+
+fn check_group(
+    parent: &Group,
+    canvas: Option<Rect>,
+    limits: &Limits,
+    images_depth: u32,
+) -> Result<(), Error> {
+    for node in &parent.children {
+        if let Node::Group(ref group) = node {
+            for filter in &group.filters {
+                let area = filter
+                    .rect
+                    .transform(group.abs_transform)
+                    .and_then(|r| r.to_rect().intersect(&canvas?))
+                    .map_or(0.0, |r| r.width() * r.height());
+                if area > limits.max_filter_region_area {
+                    return Err(Error::FilterRegionLimitReached);
+                }
+            }
+
+            check_group(group, canvas, limits, images_depth)?;
+        }
+
+        let (fill, stroke) = paints(node);
+        let paints = [fill.map(|f| &f.paint), stroke.map(|s| &s.paint)];
+        for paint in paints.into_iter().flatten() {
+            if let Paint::Pattern(ref pattern) = paint {
+                let bbox = node.abs_stroke_bounding_box();
+                let ts = node.abs_transform().pre_concat(pattern.transform);
+                let tile_area = pattern.rect.width() * pattern.rect.height() * determinant(ts);
+                // A pattern with a degenerate transform will not be rendered at all.
+                if tile_area <= 0.0 {
+                    continue;
+                }
+
+                let tiles = (bbox.width() as f64 * bbox.height() as f64) / tile_area as f64;
+                if tiles > limits.max_pattern_tiles as f64 {
+                    return Err(Error::PatternTilesLimitReached);
+                }
+            }
+        }
+
+        if let Node::Image(ref image) = node {
+            match image.kind {
+                ImageKind::SVG(ref tree) => {
+                    if images_depth + 1 > limits.max_nested_images_depth {
+                        return Err(Error::NestedImagesLimitReached);
+                    }
+
+                    check_group(
+                        &tree.root,
+                        tree.size.to_rect(0.0, 0.0),
+                        limits,
+                        images_depth + 1,
+                    )?;
+                }
+                ImageKind::JPEG(ref data)
+                | ImageKind::PNG(ref data)
+                | ImageKind::GIF(ref data)
+                | ImageKind::WEBP(ref data) => {
+                    // Use the header size and not `Image::size`,
+                    // which can come from somewhere else, like a font table.
+                    // An image with an unreadable header will not be decoded anyway.
+                    if let Ok(size) = imagesize::blob_size(data) {
+                        let pixels = size.width as u64 * size.height as u64;
+                        if pixels > limits.max_image_pixels {
+                            return Err(Error::ImagePixelsLimitReached);
+                        }
+                    }
+                }
+            }
+
+            // The only image subroot is a nested SVG, which was already checked.
+            continue;
+        }
+
+        let mut result = Ok(());
+        node.subroots(|subroot| {
+            if result.is_ok() {
+                result = check_group(subroot, canvas, limits, images_depth);
+            }
+        });
+        result?;
+    }
+
+    Ok(())
+}
+
+fn paints(node: &Node) -> (Option<&Fill>, Option<&Stroke>) {
+    match node {
+        Node::Path(ref path) => (path.fill(), path.stroke()),
+        Node::Rectangle(ref rect) => (rect.fill(), rect.stroke()),
+        Node::Ellipse(ref ellipse) => (ellipse.fill(), ellipse.stroke()),
+        Node::Polygon(ref polygon) => (polygon.fill(), polygon.stroke()),
+        Node::Circle(ref circle) => (circle.fill(), circle.stroke()),
+        Node::Line(ref line) => (line.fill(), line.stroke()),
+        Node::Polyline(ref polyline) => (polyline.fill(), polyline.stroke()),
+        // Text is checked via its flattened representation.
+        Node::Group(_) | Node::Image(_) | Node::Text(_) => (None, None),
+    }
+}
+
+fn determinant(ts: Transform) -> f32 {
+    (ts.sx * ts.sy - ts.kx * ts.ky).abs()
+}
@@ -10,8 +10,8 @@ use tiny_skia_path::Point;
 use super::converter;
 use super::svgtree::{AId, EId, SvgNode};
 use crate::{
-    ApproxEqUlps, ApproxZeroUlps, ClipPath, Fill, Group, Node, NonZeroRect, Path, Size, Transform,
-    ViewBox,
+    ApproxEqUlps, ApproxZeroUlps, ClipPath, DiagnosticCode, Fill, Group, Node, NonZeroRect, Path,
+    Size, Transform, ViewBox,
 };
 
 // Similar to `tiny_skia_path::PathSegment`, but without the `QuadTo`.
@@ -63,7 +63,10 @@ pub(crate) fn convert(
             // TODO: move to svgtree
             // Check for recursive marker.
             if state.parent_markers.contains(&marker) {
-                log::warn!("Recursive marker detected: {}", marker.element_id());
+                marker.skip(
+                    DiagnosticCode::RecursiveReference,
+                    format!("Recursive marker detected: {}", marker.element_id()),
+                );
                 continue;
             }
 
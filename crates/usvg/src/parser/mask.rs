@@ -1,13 +1,20 @@
 // Copyright 2018 the Resvg Authors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+use std::str::FromStr;
 use std::sync::Arc;
 
-use svgtypes::{Length, LengthUnit as Unit};
+use strict_num::PositiveF32;
+use svgtypes::{Align, AspectRatio, Length, LengthUnit as Unit};
 
+use super::converter::{self, SvgColorExt};
 use super::svgtree::{AId, EId, SvgNode};
-use super::{converter, OptionLog};
-use crate::{Group, Mask, MaskType, Node, NonEmptyString, NonZeroRect, Transform, Units};
+use super::{image, paint_server, units, OptionLog};
+use crate::{
+    BaseGradient, ConicGradient, DiagnosticCode, Fill, Group, ImageKind, LinearGradient, Mask,
+    MaskComposite, MaskLayer, MaskType, Node, NonEmptyString, NonZeroRect, Paint, Path, Pattern,
+    RadialGradient, Size, SpreadMethod, Stop, StopOffset, Transform, Units,
+};
 
 pub(crate) fn convert(
     node: SvgNode,
@@ -46,8 +53,12 @@ pub(crate) fn convert(
         node.convert_length(AId::Width, units, state, Length::new(120.0, Unit::Percent)),
         node.convert_length(AId::Height, units, state, Length::new(120.0, Unit::Percent)),
     );
-    let mut rect =
-        rect.log_none(|| log::warn!("Mask '{}' has an invalid size. Skipped.", node.element_id()))?;
+    let mut rect = rect.log_none(|| {
+        node.skip(
+            DiagnosticCode::InvalidSize,
+            format!("Mask '{}' has an invalid size. Skipped.", node.element_id()),
+        )
+    })?;
 
     let mut mask_all = false;
     if units == Units::ObjectBoundingBox {
@@ -112,7 +123,10 @@ pub(crate) fn convert(
         let object_bbox = match object_bbox {
             Some(v) => v,
             None => {
-                log::warn!("Masking of zero-sized shapes is not allowed.");
+                node.skip(
+                    DiagnosticCode::ZeroSizedShape,
+                    "Masking of zero-sized shapes is not allowed.".to_string(),
+                );
                 return None;
             }
         };
@@ -148,3 +162,844 @@ pub(crate) fn convert(
     cache.masks.insert(id_copy, mask.clone());
     Some(mask)
 }
+
+/// Converts element's mask layers.
+///
+/// Layers are set via `mask-image`. When it's not set, the `mask` attribute is used instead.
+///
+/// Returns `None` when the `mask` attribute references an invalid mask,
+/// in which case the element should not be rendered.
+pub(crate) fn convert_layers(
+    node: SvgNode,
+    state: &converter::State,
+    object_bbox: Option<NonZeroRect>,
+    cache: &mut converter::Cache,
+) -> Option<Vec<MaskLayer>> {
+    let images = layer_values(node, AId::MaskImage);
+    let modes = layer_values(node, AId::MaskMode);
+
+    // `mask-image: none` disables masking, unless there are other layers.
+    if images.iter().all(|image| *image == "none") {
+        let mut layers = Vec::new();
+        if let Some(link) = node.attribute::<SvgNode>(AId::Mask) {
+            let mask = convert(link, state, object_bbox, cache)?;
+            let mode = layer_value(node, AId::MaskMode, &modes, 0, parse_mode).flatten();
+            let mask = apply_mode(mask, mode, cache);
+            layers.push(MaskLayer::new(mask, MaskComposite::Add));
+        }
+
+        return Some(layers);
+    }
+
+    let positions = layer_values(node, AId::MaskPosition);
+    let sizes = layer_values(node, AId::MaskSize);
+    let repeats = layer_values(node, AId::MaskRepeat);
+    let composites = layer_values(node, AId::MaskComposite);
+
+    let mut layers = Vec::with_capacity(images.len());
+    for (idx, image) in images.iter().enumerate() {
+        let style = LayerStyle {
+            mode: layer_value(node, AId::MaskMode, &modes, idx, parse_mode).flatten(),
+            position: layer_value(node, AId::MaskPosition, &positions, idx, |value| {
+                parse_position(&split_tokens(value))
+            })
+            .unwrap_or((Length::zero(), Length::zero())),
+            size: layer_value(node, AId::MaskSize, &sizes, idx, parse_size)
+                .unwrap_or(LayerSize::Explicit(None, None)),
+            repeat: layer_value(node, AId::MaskRepeat, &repeats, idx, parse_repeat)
+                .unwrap_or((RepeatStyle::Repeat, RepeatStyle::Repeat)),
+        };
+
+        // A layer that cannot be displayed still counts as a transparent black layer.
+        let mask = match convert_layer(node, image, &style, state, object_bbox, cache) {
+            Some(mask) => mask,
+            None => Arc::new(Mask {
+                id: cache.gen_mask_id(),
+                rect: object_bbox.unwrap_or(state.view_box),
+                kind: MaskType::Luminance,
+                mask: None,
+                root: Group::empty(),
+            }),
+        };
+
+        let composite = layer_value(node, AId::MaskComposite, &composites, idx, parse_composite)
+            .unwrap_or_default();
+
+        layers.push(MaskLayer::new(mask, composite));
+    }
+
+    Some(layers)
+}
+
+/// Splits a CSS layers list, like `mask-image`, into separate layers.
+pub(crate) fn split_layers(value: &str) -> Vec<&str> {
+    split_top_level(value, |c| c == ',')
+}
+
+/// Extracts an URL from a CSS `url()` function.
+pub(crate) fn parse_url(value: &str) -> Option<&str> {
+    let url = value.strip_prefix("url(")?.strip_suffix(')')?.trim();
+    let url = url
+        .strip_prefix('"')
+        .and_then(|url| url.strip_suffix('"'))
+        .or_else(|| {
+            url.strip_prefix('\'')
+                .and_then(|url| url.strip_suffix('\''))
+        })
+        .unwrap_or(url);
+    Some(url)
+}
+
+struct LayerStyle {
+    /// `None` indicates `match-source`.
+    mode: Option<MaskType>,
+    position: (Length, Length),
+    size: LayerSize,
+    repeat: (RepeatStyle, RepeatStyle),
+}
+
+#[derive(Clone, Copy)]
+enum LayerSize {
+    Contain,
+    Cover,
+    /// `None` indicates `auto`.
+    Explicit(Option<Length>, Option<Length>),
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum RepeatStyle {
+    Repeat,
+    Space,
+    Round,
+    NoRepeat,
+}
+
+enum LayerContent<'a> {
+    Image(Box<ImageKind>),
+    Gradient(&'a str),
+}
+
+fn convert_layer(
+    node: SvgNode,
+    image: &str,
+    style: &LayerStyle,
+    state: &converter::State,
+    object_bbox: Option<NonZeroRect>,
+    cache: &mut converter::Cache,
+) -> Option<Arc<Mask>> {
+    if image == "none" {
+        return None;
+    }
+
+    let url = parse_url(image);
+    if let Some(id) = url.and_then(|url| url.strip_prefix('#')) {
+        let link = node.document().element_by_id(id).log_none(|| {
+            node.warn(
+                DiagnosticCode::InvalidReference,
+                format!("Mask layer references a non-existing element '{}'.", id),
+            )
+        })?;
+
+        let mask = convert(link, state, object_bbox, cache)?;
+        return Some(apply_mode(mask, style.mode, cache));
+    }
+
+    // Images are positioned relative to the element's fill box.
+    let area = object_bbox?;
+
+    let content = match url {
+        Some(href) => {
+            let kind = image::get_href_data(href, node, state).log_none(|| {
+                node.warn(
+                    DiagnosticCode::ResourceLoadingFailed,
+                    format!("Mask image '{}' cannot be loaded.", href),
+                )
+            })?;
+            LayerContent::Image(Box::new(kind))
+        }
+        None => LayerContent::Gradient(image),
+    };
+
+    let intrinsic_size = match content {
+        LayerContent::Image(ref kind) => Some(kind.actual_size()?),
+        LayerContent::Gradient(_) => None,
+    };
+
+    let mut size = resolve_size(style.size, area, intrinsic_size, node, state)?;
+    if style.repeat.0 == RepeatStyle::Round {
+        let count = (area.width() / size.width()).round().max(1.0);
+        size = Size::from_wh(area.width() / count, size.height())?;
+    }
+    if style.repeat.1 == RepeatStyle::Round {
+        let count = (area.height() / size.height()).round().max(1.0);
+        size = Size::from_wh(size.width(), area.height() / count)?;
+    }
+
+    let x = resolve_length(style.position.0, area.width() - size.width(), node, state);
+    let y = resolve_length(style.position.1, area.height() - size.height(), node, state);
+    let (x, step_x) = layout_axis(style.repeat.0, area.width(), size.width(), x);
+    let (y, step_y) = layout_axis(style.repeat.1, area.height(), size.height(), y);
+
+    let mut tile = Group::empty();
+    match content {
+        LayerContent::Image(kind) => {
+            let rendering_mode = node
+                .find_attribute(AId::ImageRendering)
+                .unwrap_or(state.opt.image_rendering);
+            let aspect = AspectRatio {
+                defer: false,
+                align: Align::None,
+                slice: false,
+            };
+            image::convert_inner(
+                *kind,
+                String::new(),
+                true,
+                rendering_mode,
+                aspect,
+                intrinsic_size?,
+                size.to_non_zero_rect(0.0, 0.0),
+                cache,
+                &mut tile,
+            )?;
+        }
+        LayerContent::Gradient(value) => {
+            let paint = convert_gradient(node, value, size, state, cache)?;
+            tile.children.push(Node::Path(Box::new(rect_path(
+                size.to_non_zero_rect(0.0, 0.0),
+                paint,
+            )?)));
+        }
+    }
+
+    let mut root = Group::empty();
+    if step_x.is_none() && step_y.is_none() {
+        tile.transform = Transform::from_translate(area.x() + x, area.y() + y);
+        root.children.push(Node::Group(Box::new(tile)));
+    } else {
+        // Repeated tiles are rendered using a pattern,
+        // which covers the whole area along the repeated axes.
+        let pattern_rect = NonZeroRect::from_xywh(
+            area.x() + x,
+            area.y() + y,
+            step_x.unwrap_or(size.width()),
+            step_y.unwrap_or(size.height()),
+        )?;
+        let (fill_x, fill_width) = match step_x {
+            Some(_) => (area.x(), area.width()),
+            None => (area.x() + x, size.width()),
+        };
+        let (fill_y, fill_height) = match step_y {
+            Some(_) => (area.y(), area.height()),
+            None => (area.y() + y, size.height()),
+        };
+
+        tile.recalculate(Transform::default());
+        let pattern = Pattern {
+            id: cache.gen_pattern_id(),
+            units: Units::UserSpaceOnUse,
+            content_units: Units::UserSpaceOnUse,
+            transform: Transform::default(),
+            rect: pattern_rect,
+            view_box: None,
+            root: tile,
+        };
+
+        let fill_rect = NonZeroRect::from_xywh(fill_x, fill_y, fill_width, fill_height)?;
+        root.children.push(Node::Path(Box::new(rect_path(
+            fill_rect,
+            Paint::Pattern(Arc::new(pattern)),
+        )?)));
+    }
+    root.recalculate(Transform::default());
+
+    Some(Arc::new(Mask {
+        id: cache.gen_mask_id(),
+        rect: area,
+        // Images and gradients are alpha masks by default.
+        kind: style.mode.unwrap_or(MaskType::Alpha),
+        mask: None,
+        root,
+    }))
+}
+
+/// Overrides mask's type with the one set via `mask-mode`.
+fn apply_mode(mask: Arc<Mask>, mode: Option<MaskType>, cache: &mut converter::Cache) -> Arc<Mask> {
+    match mode {
+        Some(kind) if kind != mask.kind => Arc::new(Mask {
+            id: cache.gen_mask_id(),
+            rect: mask.rect,
+            kind,
+            mask: mask.mask.clone(),
+            root: mask.root.clone(),
+        }),
+        _ => mask,
+    }
+}
+
+fn rect_path(rect: NonZeroRect, paint: Paint) -> Option<Path> {
+    let data = tiny_skia_path::PathBuilder::from_rect(rect.to_rect());
+    let mut path = Path::new_simple(Arc::new(data))?;
+    path.fill = Some(Fill {
+        paint,
+        ..Fill::default()
+    });
+    Some(path)
+}
+
+fn resolve_size(
+    size: LayerSize,
+    area: NonZeroRect,
+    intrinsic_size: Option<Size>,
+    node: SvgNode,
+    state: &converter::State,
+) -> Option<Size> {
+    let (width, height) = match size {
+        LayerSize::Contain | LayerSize::Cover => match intrinsic_size {
+            Some(s) => {
+                let sx = area.width() / s.width();
+                let sy = area.height() / s.height();
+                let scale = if matches!(size, LayerSize::Contain) {
+                    sx.min(sy)
+                } else {
+                    sx.max(sy)
+                };
+                (s.width() * scale, s.height() * scale)
+            }
+            None => (area.width(), area.height()),
+        },
+        LayerSize::Explicit(width, height) => {
+            let width = width.map(|w| resolve_length(w, area.width(), node, state));
+            let height = height.map(|h| resolve_length(h, area.height(), node, state));
+            match (width, height, intrinsic_size) {
+                (Some(w), Some(h), _) => (w, h),
+                (Some(w), None, Some(s)) => (w, w * s.height() / s.width()),
+                (None, Some(h), Some(s)) => (h * s.width() / s.height(), h),
+                (None, None, Some(s)) => (s.width(), s.height()),
+                (w, h, None) => (w.unwrap_or(area.width()), h.unwrap_or(area.height())),
+            }
+        }
+    };
+
+    Size::from_wh(width, height)
+}
+
+/// Returns the first tile offset and the distance between tiles along an axis.
+///
+/// The distance is `None` when only one tile is visible.
+fn layout_axis(repeat: RepeatStyle, area: f32, size: f32, position: f32) -> (f32, Option<f32>) {
+    match repeat {
+        RepeatStyle::NoRepeat => (position, None),
+        RepeatStyle::Repeat | RepeatStyle::Round => {
+            let offset = position - (position / size).ceil() * size;
+            if offset + size >= area {
+                (offset, None)
+            } else {
+                (offset, Some(size))
+            }
+        }
+        RepeatStyle::Space => {
+            let count = (area / size).floor();
+            if count >= 2.0 {
+                let gap = (area - count * size) / (count - 1.0);
+                (0.0, Some(size + gap))
+            } else {
+                (position, None)
+            }
+        }
+    }
+}
+
+/// Converts a CSS length, resolving percentages against `reference`.
+fn resolve_length(length: Length, reference: f32, node: SvgNode, state: &converter::State) -> f32 {
+    if length.unit == Unit::Percent {
+        reference * length.number as f32 / 100.0
+    } else {
+        units::convert_user_length(length, node, AId::MaskPosition, state)
+    }
+}
+
+/// Converts a CSS gradient function into a paint server.
+///
+/// The gradient is resolved for a box of `size` placed at the origin.
+fn convert_gradient(
+    node: SvgNode,
+    value: &str,
+    size: Size,
+    state: &converter::State,
+    cache: &mut converter::Cache,
+) -> Option<Paint> {
+    let (name, args) = value.strip_suffix(')')?.split_once('(')?;
+    let name = name.trim();
+    let (name, spread_method) = match name.strip_prefix("repeating-") {
+        Some(name) => (name, SpreadMethod::Repeat),
+        None => (name, SpreadMethod::Pad),
+    };
+
+    // The first argument is optional and is the only one that isn't a color stop.
+    let mut args = split_layers(args);
+    let mut config = Vec::new();
+    if let Some(first) = args.first() {
+        let tokens = split_tokens(first);
+        if tokens
+            .first()
+            .is_some_and(|t| parse_color(node, t).is_none())
+        {
+            config = tokens;
+            args.remove(0);
+        }
+    }
+
+    let (w, h) = (size.width(), size.height());
+    let at = config.iter().position(|t| *t == "at");
+    let (center, config) = match at {
+        Some(idx) => {
+            let (x, y) = parse_position(&config[idx + 1..])?;
+            let center = (
+                resolve_length(x, w, node, state),
+                resolve_length(y, h, node, state),
+            );
+            (center, &config[..idx])
+        }
+        None => ((w / 2.0, h / 2.0), &config[..]),
+    };
+    let (cx, cy) = center;
+
+    let paint = match name {
+        "linear-gradient" => {
+            if at.is_some() {
+                return None;
+            }
+
+            let angle = match *config {
+                [] => 180.0,
+                ["to", ref sides @ ..] => side_angle(sides, w, h)?,
+                [angle] => svgtypes::Angle::from_str(angle).ok()?.to_degrees() as f32,
+                _ => return None,
+            };
+
+            // The gradient line goes through the center and its ends are
+            // at the lines perpendicular to it, which go through the corners.
+            let (sin, cos) = angle.to_radians().sin_cos();
+            let len = (w * sin).abs() + (h * cos).abs();
+            let stops = parse_stops(node, &args, &|v| {
+                Some(resolve_length(Length::from_str(v).ok()?, len, node, state) / len)
+            })?;
+            let (stops, first, last) = normalize_stops(stops);
+
+            let (dx, dy) = (sin * len, -cos * len);
+            let (x1, y1) = (w / 2.0 - dx / 2.0, h / 2.0 - dy / 2.0);
+            Paint::LinearGradient(Arc::new(LinearGradient {
+                x1: x1 + dx * first,
+                y1: y1 + dy * first,
+                x2: x1 + dx * last,
+                y2: y1 + dy * last,
+                base: BaseGradient {
+                    id: cache.gen_linear_gradient_id(),
+                    units: Units::UserSpaceOnUse,
+                    transform: Transform::default(),
+                    spread_method,
+                    stops,
+                },
+            }))
+        }
+        "radial-gradient" => {
+            let mut circle = None;
+            let mut extent = "farthest-corner";
+            let mut lengths = Vec::new();
+            for token in config {
+                match *token {
+                    "circle" => circle = Some(true),
+                    "ellipse" => circle = Some(false),
+                    "closest-side" | "farthest-side" | "closest-corner" | "farthest-corner" => {
+                        extent = token
+                    }
+                    _ => lengths.push(Length::from_str(token).ok()?),
+                }
+            }
+            let circle = circle.unwrap_or(lengths.len() == 1);
+
+            let (rx, ry) = match (circle, &lengths[..]) {
+                (true, [r]) if r.unit != Unit::Percent => {
+                    let r = resolve_length(*r, w, node, state);
+                    (r, r)
+                }
+                (false, [rx, ry]) => (
+                    resolve_length(*rx, w, node, state),
+                    resolve_length(*ry, h, node, state),
+                ),
+                (_, []) => {
+                    let (x1, x2) = (cx.abs(), (w - cx).abs());
+                    let (y1, y2) = (cy.abs(), (h - cy).abs());
+                    let (dx, dy) = if extent.starts_with("closest") {
+                        (x1.min(x2), y1.min(y2))
+                    } else {
+                        (x1.max(x2), y1.max(y2))
+                    };
+
+                    match (circle, extent) {
+                        (true, "closest-side") => (dx.min(dy), dx.min(dy)),
+                        (true, "farthest-side") => (dx.max(dy), dx.max(dy)),
+                        (true, _) => (dx.hypot(dy), dx.hypot(dy)),
+                        (false, "closest-side" | "farthest-side") => (dx, dy),
+                        (false, _) => {
+                            (dx * std::f32::consts::SQRT_2, dy * std::f32::consts::SQRT_2)
+                        }
+                    }
+                }
+                _ => return None,
+            };
+
+            if !(rx > 0.0 && ry > 0.0) {
+                return None;
+            }
+
+            let stops = parse_stops(node, &args, &|v| {
+                Some(resolve_length(Length::from_str(v).ok()?, rx, node, state) / rx)
+            })?;
+            // Negative radii are not allowed.
+            let stops = stops.into_iter().map(|(p, c)| (p.max(0.0), c)).collect();
+            let (stops, first, last) = normalize_stops(stops);
+
+            // Elliptical gradients are circular gradients scaled along the Y axis.
+            let transform = if rx != ry {
+                Transform::from_translate(cx, cy)
+                    .pre_scale(1.0, ry / rx)
+                    .pre_translate(-cx, -cy)
+            } else {
+                Transform::default()
+            };
+
+            Paint::RadialGradient(Arc::new(RadialGradient {
+                cx,
+                cy,
+                r: PositiveF32::new(rx * last)?,
+                fx: cx,
+                fy: cy,
+                fr: PositiveF32::new(rx * first)?,
+                base: BaseGradient {
+                    id: cache.gen_radial_gradient_id(),
+                    units: Units::UserSpaceOnUse,
+                    transform,
+                    spread_method,
+                    stops,
+                },
+            }))
+        }
+        "conic-gradient" => {
+            let from = match *config {
+                [] => 0.0,
+                ["from", angle] => svgtypes::Angle::from_str(angle).ok()?.to_degrees() as f32,
+                _ => return None,
+            };
+
+            let stops = parse_stops(node, &args, &|v| {
+                if let Some(v) = v.strip_suffix('%') {
+                    Some(f32::from_str(v).ok()? / 100.0)
+                } else {
+                    Some(svgtypes::Angle::from_str(v).ok()?.to_degrees() as f32 / 360.0)
+                }
+            })?;
+            let (stops, first, last) = normalize_stops(stops);
+
+            // CSS angles start at the top, while `ConicGradient` angles start on the right.
+            Paint::ConicGradient(Arc::new(ConicGradient {
+                cx,
+                cy,
+                start_angle: first * 360.0,
+                end_angle: last * 360.0,
+                base: BaseGradient {
+                    id: cache.gen_conic_gradient_id(),
+                    units: Units::UserSpaceOnUse,
+                    transform: Transform::from_rotate_at(from - 90.0, cx, cy),
+                    spread_method,
+                    stops,
+                },
+            }))
+        }
+        _ => {
+            node.warn(
+                DiagnosticCode::Unsupported,
+                format!("'{}' is not a supported mask image.", value),
+            );
+            return None;
+        }
+    };
+
+    Some(paint)
+}
+
+/// Returns the angle of a `to <side-or-corner>` linear gradient direction.
+fn side_angle(sides: &[&str], w: f32, h: f32) -> Option<f32> {
+    // Corner gradients are perpendicular to the diagonal between the two other corners.
+    let corner = h.atan2(w).to_degrees();
+    let angle = match *sides {
+        ["top"] => 0.0,
+        ["right"] => 90.0,
+        ["bottom"] => 180.0,
+        ["left"] => 270.0,
+        ["top", "right"] | ["right", "top"] => corner,
+        ["bottom", "right"] | ["right", "bottom"] => 180.0 - corner,
+        ["bottom", "left"] | ["left", "bottom"] => 180.0 + corner,
+        ["top", "left"] | ["left", "top"] => 360.0 - corner,
+        _ => return None,
+    };
+    Some(angle)
+}
+
+/// Parses a list of CSS color stops.
+///
+/// Missing positions are resolved according to CSS Images.
+fn parse_stops(
+    node: SvgNode,
+    args: &[&str],
+    position: &dyn Fn(&str) -> Option<f32>,
+) -> Option<Vec<(f32, svgtypes::Color)>> {
+    let mut stops = Vec::new();
+    for arg in args {
+        let tokens = split_tokens(arg);
+        let color = parse_color(node, tokens.first()?)?;
+        match tokens[1..] {
+            [] => stops.push((None, color)),
+            [p] => stops.push((Some(position(p)?), color)),
+            [p1, p2] => {
+                stops.push((Some(position(p1)?), color));
+                stops.push((Some(position(p2)?), color));
+            }
+            _ => return None,
+        }
+    }
+
+    if stops.len() < 2 {
+        return None;
+    }
+
+    let last_idx = stops.len() - 1;
+    stops[0].0.get_or_insert(0.0);
+    stops[last_idx].0.get_or_insert(1.0);
+
+    // A position cannot be smaller than the previous one.
+    let mut max = 0.0f32;
+    for (idx, stop) in stops.iter_mut().enumerate() {
+        if let Some(ref mut p) = stop.0 {
+            if idx != 0 && *p < max {
+                *p = max;
+            }
+            max = *p;
+        }
+    }
+
+    // Stops without a position are spread evenly between the positioned ones.
+    let mut idx = 1;
+    while idx < last_idx {
+        if stops[idx].0.is_some() {
+            idx += 1;
+            continue;
+        }
+
+        let start = idx - 1;
+        let end = (idx..=last_idx).find(|i| stops[*i].0.is_some())?;
+        let (p1, p2) = (stops[start].0?, stops[end].0?);
+        for (i, stop) in stops.iter_mut().enumerate().take(end).skip(idx) {
+            let t = (i - start) as f32 / (end - start) as f32;
+            stop.0 = Some(p1 + (p2 - p1) * t);
+        }
+        idx = end;
+    }
+
+    stops.into_iter().map(|(p, c)| Some((p?, c))).collect()
+}
+
+/// Converts stop positions into offsets relative to the first and the last stop.
+///
+/// Returns the stops and the first and the last positions.
+fn normalize_stops(stops: Vec<(f32, svgtypes::Color)>) -> (Vec<Stop>, f32, f32) {
+    let first = stops[0].0;
+    // Stops at the same position are still a hard transition.
+    let range = (stops[stops.len() - 1].0 - first).max(0.0001);
+
+    let stops = stops
+        .into_iter()
+        .map(|(p, c)| {
+            let (color, opacity) = c.split_alpha();
+            Stop {
+                offset: StopOffset::new_clamped((p - first) / range),
+                color,
+                opacity,
+            }
+        })
+        .collect();
+
+    (paint_server::normalize_stops(stops), first, first + range)
+}
+
+fn parse_color(node: SvgNode, value: &str) -> Option<svgtypes::Color> {
+    if value.eq_ignore_ascii_case("currentColor") {
+        Some(
+            node.find_attribute(AId::Color)
+                .unwrap_or_else(svgtypes::Color::black),
+        )
+    } else {
+        svgtypes::Color::from_str(value).ok()
+    }
+}
+
+/// Parses a one or two values CSS position.
+///
+/// Keywords are converted into percentages.
+fn parse_position(tokens: &[&str]) -> Option<(Length, Length)> {
+    fn horizontal(value: &str) -> Option<Length> {
+        match value {
+            "left" => Some(Length::new(0.0, Unit::Percent)),
+            "center" => Some(Length::new(50.0, Unit::Percent)),
+            "right" => Some(Length::new(100.0, Unit::Percent)),
+            "top" | "bottom" => None,
+            _ => Length::from_str(value).ok(),
+        }
+    }
+
+    fn vertical(value: &str) -> Option<Length> {
+        match value {
+            "top" => Some(Length::new(0.0, Unit::Percent)),
+            "center" => Some(Length::new(50.0, Unit::Percent)),
+            "bottom" => Some(Length::new(100.0, Unit::Percent)),
+            "left" | "right" => None,
+            _ => Length::from_str(value).ok(),
+        }
+    }
+
+    let center = Length::new(50.0, Unit::Percent);
+    match *tokens {
+        ["top" | "bottom"] => Some((center, vertical(tokens[0])?)),
+        [x] => Some((horizontal(x)?, center)),
+        [x, y] => match (horizontal(x), vertical(y)) {
+            (Some(x), Some(y)) => Some((x, y)),
+            // Keywords can be swapped, like `top left`.
+            _ => Some((horizontal(y)?, vertical(x)?)),
+        },
+        _ => None,
+    }
+}
+
+fn parse_size(value: &str) -> Option<LayerSize> {
+    fn component(value: &str) -> Option<Option<Length>> {
+        if value == "auto" {
+            return Some(None);
+        }
+
+        let length = Length::from_str(value).ok()?;
+        if length.number < 0.0 {
+            return None;
+        }
+
+        Some(Some(length))
+    }
+
+    match *split_tokens(value) {
+        ["contain"] => Some(LayerSize::Contain),
+        ["cover"] => Some(LayerSize::Cover),
+        [w] => Some(LayerSize::Explicit(component(w)?, None)),
+        [w, h] => Some(LayerSize::Explicit(component(w)?, component(h)?)),
+        _ => None,
+    }
+}
+
+fn parse_repeat(value: &str) -> Option<(RepeatStyle, RepeatStyle)> {
+    fn keyword(value: &str) -> Option<RepeatStyle> {
+        match value {
+            "repeat" => Some(RepeatStyle::Repeat),
+            "space" => Some(RepeatStyle::Space),
+            "round" => Some(RepeatStyle::Round),
+            "no-repeat" => Some(RepeatStyle::NoRepeat),
+            _ => None,
+        }
+    }
+
+    match *split_tokens(value) {
+        ["repeat-x"] => Some((RepeatStyle::Repeat, RepeatStyle::NoRepeat)),
+        ["repeat-y"] => Some((RepeatStyle::NoRepeat, RepeatStyle::Repeat)),
+        [v] => keyword(v).map(|v| (v, v)),
+        [x, y] => Some((keyword(x)?, keyword(y)?)),
+        _ => None,
+    }
+}
+
+fn parse_mode(value: &str) -> Option<Option<MaskType>> {
+    match value {
+        "alpha" => Some(Some(MaskType::Alpha)),
+        "luminance" => Some(Some(MaskType::Luminance)),
+        "match-source" => Some(None),
+        _ => None,
+    }
+}
+
+fn parse_composite(value: &str) -> Option<MaskComposite> {
+    match value {
+        "add" => Some(MaskComposite::Add),
+        "subtract" => Some(MaskComposite::Subtract),
+        "intersect" => Some(MaskComposite::Intersect),
+        "exclude" => Some(MaskComposite::Exclude),
+        _ => None,
+    }
+}
+
+fn layer_values<'a>(node: SvgNode<'a, '_>, aid: AId) -> Vec<&'a str> {
+    node.attribute::<&str>(aid)
+        .map(split_layers)
+        .unwrap_or_default()
+}
+
+/// Returns a parsed value for the layer at `idx`.
+///
+/// Values are repeated when there are fewer values than layers.
+fn layer_value<T>(
+    node: SvgNode,
+    aid: AId,
+    values: &[&str],
+    idx: usize,
+    parse: impl Fn(&str) -> Option<T>,
+) -> Option<T> {
+    if values.is_empty() {
+        return None;
+    }
+
+    let value = values[idx % values.len()];
+    parse(value).log_none(|| {
+        node.warn(
+            DiagnosticCode::InvalidValue,
+            format!("Failed to parse {} value: '{}'.", aid, value),
+        )
+    })
+}
+
+fn split_tokens(value: &str) -> Vec<&str> {
+    split_top_level(value, char::is_whitespace)
+        .into_iter()
+        .filter(|token| !token.is_empty())
+        .collect()
+}
+
+/// Splits a CSS value ignoring separators inside functions and strings.
+fn split_top_level(value: &str, is_separator: fn(char) -> bool) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0u32;
+    let mut quote = None;
+    let mut start = 0;
+    for (idx, c) in value.char_indices() {
+        match c {
+            '"' | '\'' if quote == Some(c) => quote = None,
+            _ if quote.is_some() => {}
+            '"' | '\'' => quote = Some(c),
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            _ if depth == 0 && is_separator(c) => {
+                parts.push(value[start..idx].trim());
+                start = idx + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+
+    parts.push(value[start..].trim());
+    parts
+}
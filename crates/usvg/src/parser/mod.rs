@@ -3,8 +3,10 @@
 
 mod clippath;
 mod converter;
+mod diagnostic;
 mod filter;
 mod image;
+mod limits;
 mod marker;
 mod mask;
 mod options;
@@ -16,15 +18,26 @@ mod switch;
 mod units;
 mod use_node;
 
+#[cfg(feature = "text")]
+mod font_face;
+#[cfg(feature = "text")]
+mod sfnt;
+#[cfg(feature = "text")]
+mod svg_font;
 #[cfg(feature = "text")]
 mod text;
 #[cfg(feature = "text")]
+mod woff;
+#[cfg(feature = "text")]
 pub(crate) use converter::Cache;
+pub use diagnostic::{Diagnostic, DiagnosticCode, Severity};
 pub use image::{ImageHrefDataResolverFn, ImageHrefResolver, ImageHrefStringResolverFn};
+pub use limits::Limits;
 pub use options::Options;
 pub(crate) use svgtree::{AId, EId};
 // Re-export PathBuilderExt for use in writer module
 pub(crate) use shapes::PathBuilderExt;
+pub(crate) use shapes::{ellipse_to_path, line_to_path, points_to_path, rect_to_path};
 
 /// List of all errors.
 #[derive(Debug)]
@@ -35,9 +48,24 @@ pub enum Error {
     /// Compressed SVG must use the GZip algorithm.
     MalformedGZip,
 
-    /// We do not allow SVG with more than 1_000_000 elements for security reasons.
+    /// The number of elements after `use` resolving exceeds [`Limits::max_elements`].
     ElementsLimitReached,
 
+    /// `use` elements nesting exceeds [`Limits::max_use_depth`].
+    UseDepthLimitReached,
+
+    /// A filter region area exceeds [`Limits::max_filter_region_area`].
+    FilterRegionLimitReached,
+
+    /// A pattern requires more tiles than [`Limits::max_pattern_tiles`].
+    PatternTilesLimitReached,
+
+    /// SVG images nesting exceeds [`Limits::max_nested_images_depth`].
+    NestedImagesLimitReached,
+
+    /// A raster image has more pixels than [`Limits::max_image_pixels`].
+    ImagePixelsLimitReached,
+
     /// SVG doesn't have a valid size.
     ///
     /// Occurs when width and/or height are <= 0.
@@ -67,6 +95,21 @@ impl std::fmt::Display for Error {
             Error::ElementsLimitReached => {
                 write!(f, "the maximum number of SVG elements has been reached")
             }
+            Error::UseDepthLimitReached => {
+                write!(f, "the maximum 'use' nesting depth has been reached")
+            }
+            Error::FilterRegionLimitReached => {
+                write!(f, "the maximum filter region area has been reached")
+            }
+            Error::PatternTilesLimitReached => {
+                write!(f, "the maximum number of pattern tiles has been reached")
+            }
+            Error::NestedImagesLimitReached => {
+                write!(f, "the maximum SVG images nesting depth has been reached")
+            }
+            Error::ImagePixelsLimitReached => {
+                write!(f, "the maximum number of image pixels has been reached")
+            }
             Error::InvalidSize => {
                 write!(f, "SVG has an invalid size")
             }
@@ -98,20 +141,19 @@ impl crate::Tree {
     ///
     /// Can contain an SVG string or a gzip compressed data.
     pub fn from_data(data: &[u8], opt: &Options) -> Result<Self, Error> {
-        if data.starts_with(&[0x1f, 0x8b]) {
-            let data = decompress_svgz(data)?;
-            let text = std::str::from_utf8(&data).map_err(|_| Error::NotAnUtf8Str)?;
-            Self::from_str(text, opt)
-        } else {
-            let text = std::str::from_utf8(data).map_err(|_| Error::NotAnUtf8Str)?;
-            Self::from_str(text, opt)
-        }
+        let tree = parse_data(data, opt)?;
+        limits::check(&tree, &opt.limits)?;
+        Ok(tree)
     }
 
     /// Similar to the `from_data` method, except that it ignores all `image` elements linking to
     /// external files, as required by the SVG specification when SVG files are loaded
     /// for `<image href="..." />` tags.
     pub fn from_data_nested(data: &[u8], opt: &Options) -> Result<Self, Error> {
+        // Each nesting level reduces the allowed depth.
+        // A document at the maximum depth can still be parsed, so the top-level document
+        // would detect that the depth was exceeded, but its images will not be loaded.
+        let is_last_level = opt.limits.max_nested_images_depth == 0;
         let nested_opt = Options {
             resources_dir: None,
             dpi: opt.dpi,
@@ -122,7 +164,13 @@ impl crate::Tree {
             image_rendering: opt.image_rendering,
             default_size: opt.default_size,
             image_href_resolver: ImageHrefResolver {
-                resolve_data: Box::new(|a, b, c| (opt.image_href_resolver.resolve_data)(a, b, c)),
+                resolve_data: Box::new(move |a, b, c| {
+                    if is_last_level {
+                        return None;
+                    }
+
+                    (opt.image_href_resolver.resolve_data)(a, b, c)
+                }),
                 // External images should be ignored.
                 resolve_string: Box::new(|_, _| None),
             },
@@ -138,32 +186,70 @@ impl crate::Tree {
                     (opt.font_resolver.select_fallback)(c, used_fonts, db)
                 }),
             },
+            limits: Limits {
+                max_nested_images_depth: opt.limits.max_nested_images_depth.saturating_sub(1),
+                ..opt.limits
+            },
+            // Nested images share the document timeline.
+            animation_time: opt.animation_time,
             ..Options::default()
         };
 
-        Self::from_data(data, &nested_opt)
+        // Limits that require a resolved tree are checked by the top-level document,
+        // which includes all nested images.
+        parse_data(data, &nested_opt)
     }
 
     /// Parses `Tree` from an SVG string.
     pub fn from_str(text: &str, opt: &Options) -> Result<Self, Error> {
-        let xml_opt = roxmltree::ParsingOptions {
-            allow_dtd: true,
-            ..Default::default()
-        };
-
-        let doc =
-            roxmltree::Document::parse_with_options(text, xml_opt).map_err(Error::ParsingFailed)?;
-
-        Self::from_xmltree(&doc, opt)
+        let tree = parse_str(text, opt)?;
+        limits::check(&tree, &opt.limits)?;
+        Ok(tree)
     }
 
     /// Parses `Tree` from `roxmltree::Document`.
     pub fn from_xmltree(doc: &roxmltree::Document, opt: &Options) -> Result<Self, Error> {
-        let doc = svgtree::Document::parse_tree(doc, opt.style_sheet.as_deref())?;
-        self::converter::convert_doc(&doc, opt)
+        let tree = parse_xmltree(doc, opt)?;
+        limits::check(&tree, &opt.limits)?;
+        Ok(tree)
     }
 }
 
+fn parse_data(data: &[u8], opt: &Options) -> Result<crate::Tree, Error> {
+    if data.starts_with(&[0x1f, 0x8b]) {
+        let data = decompress_svgz(data)?;
+        let text = std::str::from_utf8(&data).map_err(|_| Error::NotAnUtf8Str)?;
+        parse_str(text, opt)
+    } else {
+        let text = std::str::from_utf8(data).map_err(|_| Error::NotAnUtf8Str)?;
+        parse_str(text, opt)
+    }
+}
+
+fn parse_str(text: &str, opt: &Options) -> Result<crate::Tree, Error> {
+    let xml_opt = roxmltree::ParsingOptions {
+        allow_dtd: true,
+        ..Default::default()
+    };
+
+    let doc =
+        roxmltree::Document::parse_with_options(text, xml_opt).map_err(Error::ParsingFailed)?;
+
+    parse_xmltree(&doc, opt)
+}
+
+fn parse_xmltree(doc: &roxmltree::Document, opt: &Options) -> Result<crate::Tree, Error> {
+    let doc = svgtree::Document::parse_tree(
+        doc,
+        opt.style_sheet.as_deref(),
+        &opt.limits,
+        opt.animation_time,
+    )?;
+    let mut tree = self::converter::convert_doc(&doc, opt)?;
+    tree.diagnostics = doc.into_diagnostics();
+    Ok(tree)
+}
+
 /// Decompresses an SVGZ file.
 pub fn decompress_svgz(data: &[u8]) -> Result<Vec<u8>, Error> {
     use std::io::Read;
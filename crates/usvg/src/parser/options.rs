@@ -6,7 +6,7 @@ use std::sync::Arc;
 
 #[cfg(feature = "text")]
 use crate::FontResolver;
-use crate::{ImageHrefResolver, ImageRendering, ShapeRendering, Size, TextRendering};
+use crate::{ImageHrefResolver, ImageRendering, Limits, ShapeRendering, Size, TextRendering};
 
 /// Processing options.
 #[derive(Debug)]
@@ -98,6 +98,19 @@ pub struct Options<'a> {
     /// A CSS stylesheet that should be injected into the SVG. Can be used to overwrite
     /// certain attributes.
     pub style_sheet: Option<String>,
+
+    /// Resource limits for untrusted input.
+    ///
+    /// Default: see type's documentation for details
+    pub limits: Limits,
+
+    /// A time in seconds to sample SMIL animations at.
+    ///
+    /// Animated attributes are replaced with their values at this time.
+    /// When `None`, animations are ignored and the static document is used.
+    ///
+    /// Default: `None`
+    pub animation_time: Option<f32>,
 }
 
 impl Default for Options<'_> {
@@ -119,6 +132,8 @@ impl Default for Options<'_> {
             #[cfg(feature = "text")]
             fontdb: Arc::new(fontdb::Database::new()),
             style_sheet: None,
+            limits: Limits::default(),
+            animation_time: None,
         }
     }
 }
@@ -6,10 +6,11 @@ use std::sync::Arc;
 
 use strict_num::PositiveF32;
 use svgtypes::{Length, LengthUnit as Unit};
+use tiny_skia_path::Point;
 
 use super::converter::{self, Cache, SvgColorExt};
 use super::svgtree::{AId, EId, SvgNode};
-use super::OptionLog;
+use super::{shapes, style, OptionLog};
 use crate::*;
 
 pub(crate) enum ServerOrColor {
@@ -31,7 +32,10 @@ pub(crate) fn convert(
     let paint = match node.tag_name().unwrap() {
         EId::LinearGradient => convert_linear(node, state),
         EId::RadialGradient => convert_radial(node, state),
+        EId::ConicGradient => convert_conic(node, state),
         EId::Pattern => convert_pattern(node, state, cache),
+        EId::Meshgradient => convert_mesh(node, state),
+        EId::Hatch => convert_hatch(node, state, cache),
         _ => unreachable!(),
     };
 
@@ -122,6 +126,9 @@ fn convert_radial(node: SvgNode, state: &converter::State) -> Option<ServerOrCol
     );
     let fx = resolve_number(node, AId::Fx, units, state, Length::new_number(cx as f64));
     let fy = resolve_number(node, AId::Fy, units, state, Length::new_number(cy as f64));
+    // A negative focal radius is an error. Fallback to the default one.
+    let fr = resolve_number(node, AId::Fr, units, state, Length::zero());
+    let fr = PositiveF32::new(fr).unwrap_or(PositiveF32::ZERO);
     let transform = node.resolve_transform(AId::GradientTransform, state);
 
     let gradient = RadialGradient {
@@ -130,6 +137,7 @@ fn convert_radial(node: SvgNode, state: &converter::State) -> Option<ServerOrCol
         r: PositiveF32::new(r).unwrap(),
         fx,
         fy,
+        fr,
         base: BaseGradient {
             id,
             units,
@@ -144,6 +152,53 @@ fn convert_radial(node: SvgNode, state: &converter::State) -> Option<ServerOrCol
     ))))
 }
 
+#[inline(never)]
+fn convert_conic(node: SvgNode, state: &converter::State) -> Option<ServerOrColor> {
+    let id = NonEmptyString::new(node.element_id().to_string())?;
+
+    let stops = convert_stops(find_gradient_with_stops(node)?);
+    if stops.len() < 2 {
+        return stops_to_color(&stops);
+    }
+
+    let units = convert_units(node, AId::GradientUnits, Units::ObjectBoundingBox);
+    let transform = node.resolve_transform(AId::GradientTransform, state);
+
+    let gradient = ConicGradient {
+        cx: resolve_number(
+            node,
+            AId::Cx,
+            units,
+            state,
+            Length::new(50.0, Unit::Percent),
+        ),
+        cy: resolve_number(
+            node,
+            AId::Cy,
+            units,
+            state,
+            Length::new(50.0, Unit::Percent),
+        ),
+        start_angle: resolve_attr(node, AId::StartAngle)
+            .attribute(AId::StartAngle)
+            .unwrap_or(0.0),
+        end_angle: resolve_attr(node, AId::EndAngle)
+            .attribute(AId::EndAngle)
+            .unwrap_or(360.0),
+        base: BaseGradient {
+            id,
+            units,
+            transform,
+            spread_method: convert_spread_method(node),
+            stops,
+        },
+    };
+
+    Some(ServerOrColor::Server(Paint::ConicGradient(Arc::new(
+        gradient,
+    ))))
+}
+
 #[inline(never)]
 fn convert_pattern(
     node: SvgNode,
@@ -175,9 +230,12 @@ fn convert_pattern(
         resolve_number(node, AId::Height, units, state, Length::zero()),
     );
     let rect = rect.log_none(|| {
-        log::warn!(
-            "Pattern '{}' has an invalid size. Skipped.",
-            node.element_id()
+        node.skip(
+            DiagnosticCode::InvalidSize,
+            format!(
+                "Pattern '{}' has an invalid size. Skipped.",
+                node.element_id()
+            ),
         );
     })?;
 
@@ -220,6 +278,306 @@ fn convert_pattern(
     Some(ServerOrColor::Server(Paint::Pattern(Arc::new(patt))))
 }
 
+#[inline(never)]
+fn convert_mesh(node: SvgNode, state: &converter::State) -> Option<ServerOrColor> {
+    let id = NonEmptyString::new(node.element_id().to_string())?;
+
+    let units = convert_units(node, AId::GradientUnits, Units::ObjectBoundingBox);
+    let transform = node.resolve_transform(AId::GradientTransform, state);
+    let kind = match node.attribute(AId::Type) {
+        Some("bicubic") => MeshGradientType::Bicubic,
+        _ => MeshGradientType::Bilinear,
+    };
+
+    let start = Point::from_xy(
+        resolve_number(node, AId::X, units, state, Length::zero()),
+        resolve_number(node, AId::Y, units, state, Length::zero()),
+    );
+
+    let rows = convert_mesh_rows(node, start).log_none(|| {
+        node.skip(
+            DiagnosticCode::InvalidValue,
+            format!(
+                "Mesh gradient '{}' has an invalid mesh. Skipped.",
+                node.element_id()
+            ),
+        );
+    })?;
+
+    let gradient = MeshGradient {
+        id,
+        units,
+        transform,
+        kind,
+        rows,
+    };
+
+    Some(ServerOrColor::Server(Paint::MeshGradient(Arc::new(
+        gradient,
+    ))))
+}
+
+fn convert_mesh_rows(node: SvgNode, start: Point) -> Option<Vec<Vec<MeshPatch>>> {
+    let mut rows: Vec<Vec<MeshPatch>> = Vec::new();
+    for row_node in node.children() {
+        if row_node.tag_name() != Some(EId::Meshrow) {
+            row_node.skip(
+                DiagnosticCode::InvalidChild,
+                format!(
+                    "Invalid mesh gradient child: '{:?}'.",
+                    row_node.tag_name().unwrap()
+                ),
+            );
+            continue;
+        }
+
+        let mut row: Vec<MeshPatch> = Vec::new();
+        for patch_node in row_node.children() {
+            if patch_node.tag_name() != Some(EId::Meshpatch) {
+                patch_node.skip(
+                    DiagnosticCode::InvalidChild,
+                    format!(
+                        "Invalid mesh row child: '{:?}'.",
+                        patch_node.tag_name().unwrap()
+                    ),
+                );
+                continue;
+            }
+
+            let above = match rows.last() {
+                Some(prev_row) => Some(prev_row.get(row.len())?),
+                None => None,
+            };
+            let patch = convert_mesh_patch(patch_node, start, above, row.last())?;
+            row.push(patch);
+        }
+
+        // All rows must have the same number of patches.
+        if row.is_empty() || rows.first().is_some_and(|first| first.len() != row.len()) {
+            return None;
+        }
+
+        rows.push(row);
+    }
+
+    if rows.is_empty() {
+        return None;
+    }
+
+    Some(rows)
+}
+
+// Patches share edges and corners with the patches above and to the left of them.
+// Therefore only the first patch has all four `stop` elements, while the rest have
+// only the ones for the edges that are not shared.
+// Each stop defines an edge and the color of the corner the edge starts at.
+fn convert_mesh_patch(
+    node: SvgNode,
+    start: Point,
+    above: Option<&MeshPatch>,
+    left: Option<&MeshPatch>,
+) -> Option<MeshPatch> {
+    let mut points = [start; 12];
+    let mut colors = [Color::black(); 4];
+    let mut opacities = [Opacity::ONE; 4];
+    let mut shared_edges = [false; 4];
+    let mut shared_corners = [false; 4];
+
+    if let Some(above) = above {
+        // The top edge is the bottom edge of the patch above, but reversed.
+        points[0] = above.points[9];
+        points[1] = above.points[8];
+        points[2] = above.points[7];
+        points[3] = above.points[6];
+        colors[0] = above.colors[3];
+        colors[1] = above.colors[2];
+        opacities[0] = above.opacities[3];
+        opacities[1] = above.opacities[2];
+        shared_edges[0] = true;
+        shared_corners[0] = true;
+        shared_corners[1] = true;
+    }
+
+    if let Some(left) = left {
+        // The left edge is the right edge of the patch to the left, but reversed.
+        points[9] = left.points[6];
+        points[10] = left.points[5];
+        points[11] = left.points[4];
+        points[0] = left.points[3];
+        colors[0] = left.colors[1];
+        colors[3] = left.colors[2];
+        opacities[0] = left.opacities[1];
+        opacities[3] = left.opacities[2];
+        shared_edges[3] = true;
+        shared_corners[0] = true;
+        shared_corners[3] = true;
+    }
+
+    let mut stops = node.children().filter(|n| n.tag_name() == Some(EId::Stop));
+    for edge in 0..4 {
+        if shared_edges[edge] {
+            continue;
+        }
+
+        let stop = stops.next()?;
+        let from = points[edge * 3];
+        let (control_points, to) = parse_mesh_edge(stop.attribute(AId::Path)?, from)?;
+
+        // An edge that ends at an already known corner cannot move it.
+        // Which is always the case for the edge that closes the patch.
+        let next = (edge + 1) % 4;
+        let to = if shared_corners[next] || next == 0 {
+            points[next * 3]
+        } else {
+            to
+        };
+
+        let (p1, p2) = control_points.unwrap_or_else(|| {
+            (
+                Point::from_xy(
+                    from.x + (to.x - from.x) / 3.0,
+                    from.y + (to.y - from.y) / 3.0,
+                ),
+                Point::from_xy(to.x + (from.x - to.x) / 3.0, to.y + (from.y - to.y) / 3.0),
+            )
+        });
+        points[edge * 3 + 1] = p1;
+        points[edge * 3 + 2] = p2;
+        points[next * 3] = to;
+
+        if !shared_corners[edge] {
+            let (color, opacity) = convert_stop_color(stop);
+            colors[edge] = color;
+            opacities[edge] = opacity;
+        }
+    }
+
+    Some(MeshPatch {
+        points,
+        colors,
+        opacities,
+    })
+}
+
+/// Parses a mesh patch edge.
+///
+/// The `path` attribute contains a single line or a cubic curve, starting at `from`.
+/// Control points are not set for lines.
+fn parse_mesh_edge(value: &str, from: Point) -> Option<(Option<(Point, Point)>, Point)> {
+    let data = format!("M {} {} {}", from.x, from.y, value);
+    let mut segments = svgtypes::SimplifyingPathParser::from(data.as_str());
+    segments.next()?.ok()?;
+
+    match segments.next()?.ok()? {
+        svgtypes::SimplePathSegment::LineTo { x, y } => {
+            Some((None, Point::from_xy(x as f32, y as f32)))
+        }
+        svgtypes::SimplePathSegment::CurveTo {
+            x1,
+            y1,
+            x2,
+            y2,
+            x,
+            y,
+        } => Some((
+            Some((
+                Point::from_xy(x1 as f32, y1 as f32),
+                Point::from_xy(x2 as f32, y2 as f32),
+            )),
+            Point::from_xy(x as f32, y as f32),
+        )),
+        _ => None,
+    }
+}
+
+#[inline(never)]
+fn convert_hatch(
+    node: SvgNode,
+    state: &converter::State,
+    cache: &mut converter::Cache,
+) -> Option<ServerOrColor> {
+    let node_with_children = find_hatch_with_children(node)?;
+
+    let id = NonEmptyString::new(node.element_id().to_string())?;
+
+    let units = convert_units(node, AId::HatchUnits, Units::ObjectBoundingBox);
+    let content_units = convert_units(node, AId::HatchContentUnits, Units::UserSpaceOnUse);
+
+    let transform = node.resolve_transform(AId::Transform, state);
+
+    // A zero pitch disables rendering, while a negative one is an error.
+    let pitch = resolve_number(node, AId::Pitch, units, state, Length::zero());
+    let pitch = NonZeroPositiveF32::new(pitch).log_none(|| {
+        node.skip(
+            DiagnosticCode::InvalidSize,
+            format!(
+                "Hatch '{}' has an invalid pitch. Skipped.",
+                node.element_id()
+            ),
+        );
+    })?;
+
+    let rotate = resolve_attr(node, AId::Rotate)
+        .attribute::<f32>(AId::Rotate)
+        .unwrap_or(0.0);
+
+    let mut paths = Vec::new();
+    for child in node_with_children.children() {
+        if child.tag_name() != Some(EId::Hatchpath) {
+            child.skip(
+                DiagnosticCode::InvalidChild,
+                format!("Invalid hatch child: '{:?}'.", child.tag_name().unwrap()),
+            );
+            continue;
+        }
+
+        if let Some(path) = convert_hatch_path(child, content_units, state, cache) {
+            paths.push(path);
+        }
+    }
+
+    if paths.is_empty() {
+        return None;
+    }
+
+    let hatch = Hatch {
+        id,
+        units,
+        content_units,
+        transform,
+        x: resolve_number(node, AId::X, units, state, Length::zero()),
+        y: resolve_number(node, AId::Y, units, state, Length::zero()),
+        pitch,
+        rotate,
+        paths,
+    };
+
+    Some(ServerOrColor::Server(Paint::Hatch(Arc::new(hatch))))
+}
+
+fn convert_hatch_path(
+    node: SvgNode,
+    content_units: Units,
+    state: &converter::State,
+    cache: &mut converter::Cache,
+) -> Option<HatchPath> {
+    // Hatch paths have no bounding box, therefore object bounding box units are not allowed.
+    let stroke = style::resolve_stroke(node, false, state, cache)?;
+
+    let data = match node.attribute::<&str>(AId::D) {
+        // Path data without an initial moveto starts at the origin.
+        Some(d) if d.trim_start().starts_with(['M', 'm']) => Some(shapes::convert_path_data(d)?),
+        Some(d) => Some(shapes::convert_path_data(&format!("M 0 0 {}", d))?),
+        None => None,
+    };
+
+    Some(HatchPath {
+        offset: node.convert_length(AId::Offset, content_units, state, Length::zero()),
+        data,
+        stroke,
+    })
+}
+
 fn convert_spread_method(node: SvgNode) -> SpreadMethod {
     let node = resolve_attr(node, AId::SpreadMethod);
     node.attribute(AId::SpreadMethod).unwrap_or_default()
@@ -235,10 +593,13 @@ fn find_gradient_with_stops<'a, 'input: 'a>(
 ) -> Option<SvgNode<'a, 'input>> {
     for link in node.href_iter() {
         if !link.tag_name().unwrap().is_gradient() {
-            log::warn!(
-                "Gradient '{}' cannot reference '{}' via 'xlink:href'.",
-                node.element_id(),
-                link.tag_name().unwrap()
+            node.skip(
+                DiagnosticCode::InvalidReference,
+                format!(
+                    "Gradient '{}' cannot reference '{}' via 'xlink:href'.",
+                    node.element_id(),
+                    link.tag_name().unwrap()
+                ),
             );
             return None;
         }
@@ -256,10 +617,37 @@ fn find_pattern_with_children<'a, 'input: 'a>(
 ) -> Option<SvgNode<'a, 'input>> {
     for link in node.href_iter() {
         if link.tag_name() != Some(EId::Pattern) {
-            log::warn!(
-                "Pattern '{}' cannot reference '{}' via 'xlink:href'.",
-                node.element_id(),
-                link.tag_name().unwrap()
+            node.skip(
+                DiagnosticCode::InvalidReference,
+                format!(
+                    "Pattern '{}' cannot reference '{}' via 'xlink:href'.",
+                    node.element_id(),
+                    link.tag_name().unwrap()
+                ),
+            );
+            return None;
+        }
+
+        if link.has_children() {
+            return Some(link);
+        }
+    }
+
+    None
+}
+
+fn find_hatch_with_children<'a, 'input: 'a>(
+    node: SvgNode<'a, 'input>,
+) -> Option<SvgNode<'a, 'input>> {
+    for link in node.href_iter() {
+        if link.tag_name() != Some(EId::Hatch) {
+            node.skip(
+                DiagnosticCode::InvalidReference,
+                format!(
+                    "Hatch '{}' cannot reference '{}' via 'xlink:href'.",
+                    node.element_id(),
+                    link.tag_name().unwrap()
+                ),
             );
             return None;
         }
@@ -279,7 +667,10 @@ fn convert_stops(grad: SvgNode) -> Vec<Stop> {
         let mut prev_offset = Length::zero();
         for stop in grad.children() {
             if stop.tag_name() != Some(EId::Stop) {
-                log::warn!("Invalid gradient child: '{:?}'.", stop.tag_name().unwrap());
+                stop.skip(
+                    DiagnosticCode::InvalidChild,
+                    format!("Invalid gradient child: '{:?}'.", stop.tag_name().unwrap()),
+                );
                 continue;
             }
 
@@ -293,33 +684,20 @@ fn convert_stops(grad: SvgNode) -> Vec<Stop> {
             prev_offset = Length::new_number(offset);
             let offset = crate::f32_bound(0.0, offset as f32, 1.0);
 
-            let (color, opacity) = match stop.attribute(AId::StopColor) {
-                Some("currentColor") => stop
-                    .find_attribute(AId::Color)
-                    .unwrap_or_else(svgtypes::Color::black),
-                Some(value) => {
-                    if let Ok(c) = svgtypes::Color::from_str(value) {
-                        c
-                    } else {
-                        log::warn!("Failed to parse stop-color value: '{}'.", value);
-                        svgtypes::Color::black()
-                    }
-                }
-                _ => svgtypes::Color::black(),
-            }
-            .split_alpha();
-
-            let stop_opacity = stop
-                .attribute::<Opacity>(AId::StopOpacity)
-                .unwrap_or(Opacity::ONE);
+            let (color, opacity) = convert_stop_color(stop);
             stops.push(Stop {
                 offset: StopOffset::new_clamped(offset),
                 color,
-                opacity: opacity * stop_opacity,
+                opacity,
             });
         }
     }
 
+    normalize_stops(stops)
+}
+
+/// Makes stop offsets strictly increasing.
+pub(crate) fn normalize_stops(mut stops: Vec<Stop>) -> Vec<Stop> {
     // Remove stops with equal offset.
     //
     // Example:
@@ -401,6 +779,34 @@ fn convert_stops(grad: SvgNode) -> Vec<Stop> {
     stops
 }
 
+/// Resolves `stop-color` and `stop-opacity`.
+fn convert_stop_color(stop: SvgNode) -> (Color, Opacity) {
+    let (color, opacity) = match stop.attribute(AId::StopColor) {
+        Some("currentColor") => stop
+            .find_attribute(AId::Color)
+            .unwrap_or_else(svgtypes::Color::black),
+        Some(value) => {
+            if let Ok(c) = svgtypes::Color::from_str(value) {
+                c
+            } else {
+                stop.warn(
+                    DiagnosticCode::InvalidValue,
+                    format!("Failed to parse stop-color value: '{}'.", value),
+                );
+                svgtypes::Color::black()
+            }
+        }
+        _ => svgtypes::Color::black(),
+    }
+    .split_alpha();
+
+    let stop_opacity = stop
+        .attribute::<Opacity>(AId::StopOpacity)
+        .unwrap_or(Opacity::ONE);
+
+    (color, opacity * stop_opacity)
+}
+
 #[inline(never)]
 pub(crate) fn resolve_number(
     node: SvgNode,
@@ -420,7 +826,9 @@ fn resolve_attr<'a, 'input: 'a>(node: SvgNode<'a, 'input>, name: AId) -> SvgNode
     match node.tag_name().unwrap() {
         EId::LinearGradient => resolve_lg_attr(node, name),
         EId::RadialGradient => resolve_rg_attr(node, name),
+        EId::ConicGradient => resolve_cg_attr(node, name),
         EId::Pattern => resolve_pattern_attr(node, name),
+        EId::Hatch => resolve_hatch_attr(node, name),
         EId::Filter => resolve_filter_attr(node, name),
         _ => node,
     }
@@ -444,10 +852,13 @@ fn resolve_lg_attr<'a, 'input: 'a>(node: SvgNode<'a, 'input>, name: AId) -> SvgN
             // from any kind of gradient.
             | (AId::GradientUnits, EId::LinearGradient)
             | (AId::GradientUnits, EId::RadialGradient)
+            | (AId::GradientUnits, EId::ConicGradient)
             | (AId::SpreadMethod, EId::LinearGradient)
             | (AId::SpreadMethod, EId::RadialGradient)
+            | (AId::SpreadMethod, EId::ConicGradient)
             | (AId::GradientTransform, EId::LinearGradient)
-            | (AId::GradientTransform, EId::RadialGradient) => {
+            | (AId::GradientTransform, EId::RadialGradient)
+            | (AId::GradientTransform, EId::ConicGradient) => {
                 if link.has_attribute(name) {
                     return link;
                 }
@@ -474,14 +885,54 @@ fn resolve_rg_attr<'a, 'input>(node: SvgNode<'a, 'input>, name: AId) -> SvgNode<
             | (AId::R,  EId::RadialGradient)
             | (AId::Fx, EId::RadialGradient)
             | (AId::Fy, EId::RadialGradient)
+            | (AId::Fr, EId::RadialGradient)
+            // Other attributes can be resolved
+            // from any kind of gradient.
+            | (AId::GradientUnits, EId::LinearGradient)
+            | (AId::GradientUnits, EId::RadialGradient)
+            | (AId::GradientUnits, EId::ConicGradient)
+            | (AId::SpreadMethod, EId::LinearGradient)
+            | (AId::SpreadMethod, EId::RadialGradient)
+            | (AId::SpreadMethod, EId::ConicGradient)
+            | (AId::GradientTransform, EId::LinearGradient)
+            | (AId::GradientTransform, EId::RadialGradient)
+            | (AId::GradientTransform, EId::ConicGradient) => {
+                if link.has_attribute(name) {
+                    return link;
+                }
+            }
+            _ => break,
+        }
+    }
+
+    node
+}
+
+fn resolve_cg_attr<'a, 'input>(node: SvgNode<'a, 'input>, name: AId) -> SvgNode<'a, 'input> {
+    for link in node.href_iter() {
+        let tag_name = match link.tag_name() {
+            Some(v) => v,
+            None => return node,
+        };
+
+        match (name, tag_name) {
+            // Coordinates can be resolved only from
+            // ref element with the same type.
+              (AId::Cx, EId::ConicGradient)
+            | (AId::Cy, EId::ConicGradient)
+            | (AId::StartAngle, EId::ConicGradient)
+            | (AId::EndAngle, EId::ConicGradient)
             // Other attributes can be resolved
             // from any kind of gradient.
             | (AId::GradientUnits, EId::LinearGradient)
             | (AId::GradientUnits, EId::RadialGradient)
+            | (AId::GradientUnits, EId::ConicGradient)
             | (AId::SpreadMethod, EId::LinearGradient)
             | (AId::SpreadMethod, EId::RadialGradient)
+            | (AId::SpreadMethod, EId::ConicGradient)
             | (AId::GradientTransform, EId::LinearGradient)
-            | (AId::GradientTransform, EId::RadialGradient) => {
+            | (AId::GradientTransform, EId::RadialGradient)
+            | (AId::GradientTransform, EId::ConicGradient) => {
                 if link.has_attribute(name) {
                     return link;
                 }
@@ -515,6 +966,25 @@ fn resolve_pattern_attr<'a, 'input: 'a>(
     node
 }
 
+fn resolve_hatch_attr<'a, 'input: 'a>(node: SvgNode<'a, 'input>, name: AId) -> SvgNode<'a, 'input> {
+    for link in node.href_iter() {
+        let tag_name = match link.tag_name() {
+            Some(v) => v,
+            None => return node,
+        };
+
+        if tag_name != EId::Hatch {
+            break;
+        }
+
+        if link.has_attribute(name) {
+            return link;
+        }
+    }
+
+    node
+}
+
 fn resolve_filter_attr<'a, 'input: 'a>(node: SvgNode<'a, 'input>, aid: AId) -> SvgNode<'a, 'input> {
     for link in node.href_iter() {
         let tag_name = match link.tag_name() {
@@ -586,8 +1056,8 @@ fn node_to_user_coordinates(
     match node {
         Node::Group(ref mut g) => {
             // No need to check clip paths, because they cannot have paint servers.
-            if let Some(ref mut mask) = g.mask {
-                if let Some(ref mut mask) = Arc::get_mut(mask) {
+            for layer in &mut g.masks {
+                if let Some(ref mut mask) = Arc::get_mut(&mut layer.mask) {
                     update_paint_servers(
                         &mut mask.root,
                         context_transform,
@@ -710,6 +1180,66 @@ fn node_to_user_coordinates(
                 cache,
             );
         }
+        Node::Circle(ref mut circle) => {
+            let bbox = text_bbox.unwrap_or(circle.bounding_box);
+
+            process_fill(
+                &mut circle.fill,
+                circle.abs_transform,
+                context_transform,
+                context_bbox,
+                bbox,
+                cache,
+            );
+            process_stroke(
+                &mut circle.stroke,
+                circle.abs_transform,
+                context_transform,
+                context_bbox,
+                bbox,
+                cache,
+            );
+        }
+        Node::Line(ref mut line) => {
+            let bbox = text_bbox.unwrap_or(line.bounding_box);
+
+            process_fill(
+                &mut line.fill,
+                line.abs_transform,
+                context_transform,
+                context_bbox,
+                bbox,
+                cache,
+            );
+            process_stroke(
+                &mut line.stroke,
+                line.abs_transform,
+                context_transform,
+                context_bbox,
+                bbox,
+                cache,
+            );
+        }
+        Node::Polyline(ref mut polyline) => {
+            let bbox = text_bbox.unwrap_or(polyline.bounding_box);
+
+            process_fill(
+                &mut polyline.fill,
+                polyline.abs_transform,
+                context_transform,
+                context_bbox,
+                bbox,
+                cache,
+            );
+            process_stroke(
+                &mut polyline.stroke,
+                polyline.abs_transform,
+                context_transform,
+                context_bbox,
+                bbox,
+                cache,
+            );
+        }
         Node::Image(ref mut image) => {
             if let ImageKind::SVG(ref mut tree) = image.kind {
                 update_paint_servers(&mut tree.root, context_transform, context_bbox, None, cache);
@@ -915,6 +1445,7 @@ fn process_context_paint(
                 r: rg.r,
                 fx: rg.fx,
                 fy: rg.fy,
+                fr: rg.fr,
                 base: BaseGradient {
                     id: cache.gen_radial_gradient_id(),
                     units: rg.units,
@@ -924,6 +1455,22 @@ fn process_context_paint(
                 },
             }));
         }
+        Paint::ConicGradient(ref cg) => {
+            let transform = cg.transform.post_concat(rev_transform);
+            *paint = Paint::ConicGradient(Arc::new(ConicGradient {
+                cx: cg.cx,
+                cy: cg.cy,
+                start_angle: cg.start_angle,
+                end_angle: cg.end_angle,
+                base: BaseGradient {
+                    id: cache.gen_conic_gradient_id(),
+                    units: cg.units,
+                    transform,
+                    spread_method: cg.spread_method,
+                    stops: cg.stops.clone(),
+                },
+            }));
+        }
         Paint::Pattern(ref pat) => {
             let transform = pat.transform.post_concat(rev_transform);
             *paint = Paint::Pattern(Arc::new(Pattern {
@@ -936,6 +1483,30 @@ fn process_context_paint(
                 root: pat.root.clone(),
             }));
         }
+        Paint::MeshGradient(ref mg) => {
+            let transform = mg.transform.post_concat(rev_transform);
+            *paint = Paint::MeshGradient(Arc::new(MeshGradient {
+                id: cache.gen_mesh_gradient_id(),
+                units: mg.units,
+                transform,
+                kind: mg.kind,
+                rows: mg.rows.clone(),
+            }));
+        }
+        Paint::Hatch(ref hatch) => {
+            let transform = hatch.transform.post_concat(rev_transform);
+            *paint = Paint::Hatch(Arc::new(Hatch {
+                id: cache.gen_hatch_id(),
+                units: hatch.units,
+                content_units: hatch.content_units,
+                transform,
+                x: hatch.x,
+                y: hatch.y,
+                pitch: hatch.pitch,
+                rotate: hatch.rotate,
+                paths: hatch.paths.clone(),
+            }));
+        }
     }
 
     Some(())
@@ -973,6 +1544,22 @@ pub(crate) fn process_paint(
         }
     }
 
+    if let Paint::Hatch(ref mut hatch) = paint {
+        if let Some(ref mut hatch) = Arc::get_mut(hatch) {
+            for path in &mut hatch.paths {
+                process_paint(
+                    &mut path.stroke.paint,
+                    false,
+                    Transform::default(),
+                    None,
+                    Transform::default(),
+                    bbox,
+                    cache,
+                );
+            }
+        }
+    }
+
     if has_context {
         process_context_paint(paint, context_transform, path_transform, cache);
     }
@@ -1003,14 +1590,23 @@ fn process_text_decoration(style: &mut Option<TextDecorationStyle>, bbox: Rect,
 
 impl Paint {
     fn to_user_coordinates(&mut self, bbox: Rect, cache: &mut Cache) -> Option<()> {
-        let name = if matches!(self, Paint::Pattern(_)) {
-            "Pattern"
-        } else {
-            "Gradient"
+        let (name, id) = match self {
+            Paint::Color(_) => ("Color", ""),
+            Paint::LinearGradient(ref lg) => ("Gradient", lg.id()),
+            Paint::RadialGradient(ref rg) => ("Gradient", rg.id()),
+            Paint::ConicGradient(ref cg) => ("Gradient", cg.id()),
+            Paint::MeshGradient(ref mg) => ("Gradient", mg.id()),
+            Paint::Pattern(ref patt) => ("Pattern", patt.id()),
+            Paint::Hatch(ref hatch) => ("Hatch", hatch.id()),
+        };
+        let Some(bbox) = bbox.to_non_zero_rect() else {
+            cache.skip_detached(
+                DiagnosticCode::ZeroSizedShape,
+                id,
+                format!("{} on zero-sized shapes is not allowed.", name),
+            );
+            return None;
         };
-        let bbox = bbox
-            .to_non_zero_rect()
-            .log_none(|| log::warn!("{} on zero-sized shapes is not allowed.", name))?;
 
         // `Arc::get_mut()` allow us to modify some paint servers in-place.
         // This reduces the amount of cloning and preserves the original ID as well.
@@ -1049,6 +1645,7 @@ impl Paint {
                         r: rg.r,
                         fx: rg.fx,
                         fy: rg.fy,
+                        fr: rg.fr,
                         base: BaseGradient {
                             id: cache.gen_radial_gradient_id(),
                             units: Units::UserSpaceOnUse,
@@ -1059,6 +1656,27 @@ impl Paint {
                     });
                 }
             }
+            Paint::ConicGradient(ref mut cg) => {
+                let transform = cg.transform.post_concat(Transform::from_bbox(bbox));
+                if let Some(ref mut cg) = Arc::get_mut(cg) {
+                    cg.base.transform = transform;
+                    cg.base.units = Units::UserSpaceOnUse;
+                } else {
+                    *cg = Arc::new(ConicGradient {
+                        cx: cg.cx,
+                        cy: cg.cy,
+                        start_angle: cg.start_angle,
+                        end_angle: cg.end_angle,
+                        base: BaseGradient {
+                            id: cache.gen_conic_gradient_id(),
+                            units: Units::UserSpaceOnUse,
+                            transform,
+                            spread_method: cg.spread_method,
+                            stops: cg.stops.clone(),
+                        },
+                    });
+                }
+            }
             Paint::Pattern(ref mut patt) => {
                 let rect = if patt.units == Units::ObjectBoundingBox {
                     patt.rect.bbox_transform(bbox)
@@ -1110,15 +1728,101 @@ impl Paint {
                     });
                 }
             }
+            Paint::MeshGradient(ref mut mg) => {
+                let transform = mg.transform.post_concat(Transform::from_bbox(bbox));
+                if let Some(ref mut mg) = Arc::get_mut(mg) {
+                    mg.transform = transform;
+                    mg.units = Units::UserSpaceOnUse;
+                } else {
+                    *mg = Arc::new(MeshGradient {
+                        id: cache.gen_mesh_gradient_id(),
+                        units: Units::UserSpaceOnUse,
+                        transform,
+                        kind: mg.kind,
+                        rows: mg.rows.clone(),
+                    });
+                }
+            }
+            Paint::Hatch(ref mut hatch) => {
+                let (x, y, pitch) = if hatch.units == Units::ObjectBoundingBox {
+                    (
+                        bbox.x() + hatch.x * bbox.width(),
+                        bbox.y() + hatch.y * bbox.height(),
+                        NonZeroPositiveF32::new(hatch.pitch.get() * bbox.width())?,
+                    )
+                } else {
+                    (hatch.x, hatch.y, hatch.pitch)
+                };
+
+                let paths = if hatch.content_units == Units::ObjectBoundingBox {
+                    let paths: Option<Vec<_>> = hatch
+                        .paths
+                        .iter()
+                        .map(|path| path.bbox_transform(bbox))
+                        .collect();
+                    Some(paths?)
+                } else {
+                    None
+                };
+
+                if let Some(ref mut hatch) = Arc::get_mut(hatch) {
+                    hatch.x = x;
+                    hatch.y = y;
+                    hatch.pitch = pitch;
+                    if let Some(paths) = paths {
+                        hatch.paths = paths;
+                    }
+                    hatch.units = Units::UserSpaceOnUse;
+                    hatch.content_units = Units::UserSpaceOnUse;
+                } else {
+                    *hatch = Arc::new(Hatch {
+                        id: cache.gen_hatch_id(),
+                        units: Units::UserSpaceOnUse,
+                        content_units: Units::UserSpaceOnUse,
+                        transform: hatch.transform,
+                        x,
+                        y,
+                        pitch,
+                        rotate: hatch.rotate,
+                        paths: paths.unwrap_or_else(|| hatch.paths.clone()),
+                    });
+                }
+            }
         }
 
         Some(())
     }
 }
 
+impl HatchPath {
+    fn bbox_transform(&self, bbox: NonZeroRect) -> Option<Self> {
+        let ts = Transform::from_scale(bbox.width(), bbox.height());
+        // Strokes cannot be scaled non-uniformly, so the average scale is used instead.
+        let scale = (bbox.width() * bbox.height()).sqrt();
+
+        let mut stroke = self.stroke.clone();
+        stroke.width = StrokeWidth::new(stroke.width.get() * scale)?;
+        stroke.dasharray = stroke
+            .dasharray
+            .map(|list| list.iter().map(|n| n * scale).collect());
+        stroke.dashoffset *= scale;
+
+        let data = match self.data {
+            Some(ref data) => Some(Arc::new(data.as_ref().clone().transform(ts)?)),
+            None => None,
+        };
+
+        Some(HatchPath {
+            offset: self.offset * bbox.width(),
+            data,
+            stroke,
+        })
+    }
+}
+
 fn push_pattern_transform(root: &mut Group, transform: Transform) {
     // TODO: we should update abs_transform in all descendants as well
-    let mut g = std::mem::replace(root, Group::empty());
+    let mut g = std::mem::take(root);
     g.transform = transform;
     g.abs_transform = transform;
 
@@ -1133,7 +1837,10 @@ impl Paint {
             Self::Color(_) => Units::UserSpaceOnUse,
             Self::LinearGradient(ref lg) => lg.units,
             Self::RadialGradient(ref rg) => rg.units,
+            Self::ConicGradient(ref cg) => cg.units,
             Self::Pattern(ref patt) => patt.units,
+            Self::MeshGradient(ref mg) => mg.units,
+            Self::Hatch(ref hatch) => hatch.units,
         }
     }
 
@@ -1141,6 +1848,7 @@ impl Paint {
     pub(crate) fn content_units(&self) -> Units {
         match self {
             Self::Pattern(ref patt) => patt.content_units,
+            Self::Hatch(ref hatch) => hatch.content_units,
             _ => Units::UserSpaceOnUse,
         }
     }
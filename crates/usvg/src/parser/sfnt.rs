@@ -0,0 +1,140 @@
+// Copyright 2025 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! OpenType font writing.
+//!
+//! Used to reconstruct WOFF fonts and to convert SVG fonts.
+
+/// Writes tables into an OpenType font file.
+pub(crate) fn build(flavor: u32, mut tables: Vec<([u8; 4], Vec<u8>)>) -> Vec<u8> {
+    // Table records must be sorted by tag.
+    tables.sort_by_key(|t| t.0);
+
+    let num_tables = tables.len() as u16;
+    let entry_selector = if num_tables == 0 {
+        0
+    } else {
+        15 - num_tables.leading_zeros() as u16
+    };
+    let search_range = (1u16 << entry_selector).wrapping_mul(16);
+    let range_shift = num_tables.wrapping_mul(16).wrapping_sub(search_range);
+
+    let size = tables
+        .iter()
+        .fold(12 + tables.len() * 16, |size, (_, table)| {
+            size + table.len().next_multiple_of(4)
+        });
+
+    let mut data = Vec::with_capacity(size);
+    data.extend_from_slice(&flavor.to_be_bytes());
+    data.extend_from_slice(&num_tables.to_be_bytes());
+    data.extend_from_slice(&search_range.to_be_bytes());
+    data.extend_from_slice(&entry_selector.to_be_bytes());
+    data.extend_from_slice(&range_shift.to_be_bytes());
+
+    let mut offset = 12 + tables.len() * 16;
+    for (tag, table) in &tables {
+        data.extend_from_slice(tag);
+        data.extend_from_slice(&checksum(table).to_be_bytes());
+        data.extend_from_slice(&(offset as u32).to_be_bytes());
+        data.extend_from_slice(&(table.len() as u32).to_be_bytes());
+        offset += table.len().next_multiple_of(4);
+    }
+
+    for (_, table) in &tables {
+        data.extend_from_slice(table);
+        data.resize(data.len().next_multiple_of(4), 0);
+    }
+
+    data
+}
+
+fn checksum(data: &[u8]) -> u32 {
+    data.chunks(4).fold(0u32, |sum, chunk| {
+        let mut word = [0; 4];
+        word[..chunk.len()].copy_from_slice(chunk);
+        sum.wrapping_add(u32::from_be_bytes(word))
+    })
+}
+
+/// Writes simple glyph flags and coordinates in the TrueType format.
+pub(crate) fn write_simple_glyph_points(
+    coords: &[(i32, i32, bool)],
+    overlap: bool,
+    glyf: &mut Vec<u8>,
+) {
+    const ON_CURVE_POINT: u8 = 0x01;
+    const X_SHORT_VECTOR: u8 = 0x02;
+    const Y_SHORT_VECTOR: u8 = 0x04;
+    const X_IS_SAME_OR_POSITIVE: u8 = 0x10;
+    const Y_IS_SAME_OR_POSITIVE: u8 = 0x20;
+    const OVERLAP_SIMPLE: u8 = 0x40;
+
+    let mut xs = Vec::new();
+    let mut ys = Vec::new();
+    let (mut prev_x, mut prev_y) = (0, 0);
+    for (i, &(x, y, on_curve)) in coords.iter().enumerate() {
+        let mut flag = 0;
+        if on_curve {
+            flag |= ON_CURVE_POINT;
+        }
+
+        if overlap && i == 0 {
+            flag |= OVERLAP_SIMPLE;
+        }
+
+        let dx = x - prev_x;
+        if dx == 0 {
+            flag |= X_IS_SAME_OR_POSITIVE;
+        } else if dx.abs() < 256 {
+            flag |= X_SHORT_VECTOR;
+            if dx > 0 {
+                flag |= X_IS_SAME_OR_POSITIVE;
+            }
+            xs.push(dx.unsigned_abs() as u8);
+        } else {
+            xs.extend_from_slice(&(dx as i16).to_be_bytes());
+        }
+
+        let dy = y - prev_y;
+        if dy == 0 {
+            flag |= Y_IS_SAME_OR_POSITIVE;
+        } else if dy.abs() < 256 {
+            flag |= Y_SHORT_VECTOR;
+            if dy > 0 {
+                flag |= Y_IS_SAME_OR_POSITIVE;
+            }
+            ys.push(dy.unsigned_abs() as u8);
+        } else {
+            ys.extend_from_slice(&(dy as i16).to_be_bytes());
+        }
+
+        glyf.push(flag);
+        prev_x = x;
+        prev_y = y;
+    }
+
+    glyf.extend_from_slice(&xs);
+    glyf.extend_from_slice(&ys);
+}
+
+/// Big-endian numbers writing.
+pub(crate) trait WriteExt {
+    fn write_u16(&mut self, n: u16);
+    fn write_i16(&mut self, n: i16);
+    fn write_u32(&mut self, n: u32);
+}
+
+impl WriteExt for Vec<u8> {
+    fn write_u16(&mut self, n: u16) {
+        self.extend_from_slice(&n.to_be_bytes());
+    }
+
+    fn write_i16(&mut self, n: i16) {
+        self.extend_from_slice(&n.to_be_bytes());
+    }
+
+    fn write_u32(&mut self, n: u32) {
+        self.extend_from_slice(&n.to_be_bytes());
+    }
+}
@@ -8,7 +8,7 @@ use tiny_skia_path::Path;
 
 use super::svgtree::{AId, EId, SvgNode};
 use super::{converter, units};
-use crate::{ApproxEqUlps, IsValidLength, Rect};
+use crate::{ApproxEqUlps, DiagnosticCode, IsValidLength, Rect};
 
 pub(crate) fn convert(node: SvgNode, state: &converter::State) -> Option<Arc<Path>> {
     match node.tag_name()? {
@@ -25,6 +25,11 @@ pub(crate) fn convert(node: SvgNode, state: &converter::State) -> Option<Arc<Pat
 
 pub(crate) fn convert_path(node: SvgNode) -> Option<Arc<Path>> {
     let value: &str = node.attribute(AId::D)?;
+    convert_path_data(value)
+}
+
+/// Converts SVG path data, like the one from the `d` attribute.
+pub(crate) fn convert_path_data(value: &str) -> Option<Arc<Path>> {
     let mut builder = tiny_skia_path::PathBuilder::new();
     for segment in svgtypes::SimplifyingPathParser::from(value) {
         let segment = match segment {
@@ -68,16 +73,22 @@ pub(crate) fn convert_rect(node: SvgNode, state: &converter::State) -> Option<Ar
     let width = node.convert_user_length(AId::Width, state, Length::zero());
     let height = node.convert_user_length(AId::Height, state, Length::zero());
     if !width.is_valid_length() {
-        log::warn!(
-            "Rect '{}' has an invalid 'width' value. Skipped.",
-            node.element_id()
+        node.skip(
+            DiagnosticCode::InvalidSize,
+            format!(
+                "Rect '{}' has an invalid 'width' value. Skipped.",
+                node.element_id()
+            ),
         );
         return None;
     }
     if !height.is_valid_length() {
-        log::warn!(
-            "Rect '{}' has an invalid 'height' value. Skipped.",
-            node.element_id()
+        node.skip(
+            DiagnosticCode::InvalidSize,
+            format!(
+                "Rect '{}' has an invalid 'height' value. Skipped.",
+                node.element_id()
+            ),
         );
         return None;
     }
@@ -97,6 +108,20 @@ pub(crate) fn convert_rect(node: SvgNode, state: &converter::State) -> Option<Ar
         ry = height / 2.0;
     }
 
+    rect_to_path(x, y, width, height, rx, ry)
+}
+
+/// Converts an already resolved `rect` into a path.
+///
+/// `rx` and `ry` must be already clamped.
+pub(crate) fn rect_to_path(
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+    rx: f32,
+    ry: f32,
+) -> Option<Arc<Path>> {
     // Conversion according to https://www.w3.org/TR/SVG11/shapes.html#RectElement
     let path = if rx.approx_eq_ulps(&0.0, 4) {
         tiny_skia_path::PathBuilder::from_rect(Rect::from_xywh(x, y, width, height)?)
@@ -159,30 +184,53 @@ pub(crate) fn resolve_rx_ry(node: SvgNode, state: &converter::State) -> (f32, f3
     }
 }
 
-fn convert_line(node: SvgNode, state: &converter::State) -> Option<Arc<Path>> {
+pub(crate) fn convert_line(node: SvgNode, state: &converter::State) -> Option<Arc<Path>> {
     let x1 = node.convert_user_length(AId::X1, state, Length::zero());
     let y1 = node.convert_user_length(AId::Y1, state, Length::zero());
     let x2 = node.convert_user_length(AId::X2, state, Length::zero());
     let y2 = node.convert_user_length(AId::Y2, state, Length::zero());
 
+    line_to_path(x1, y1, x2, y2)
+}
+
+/// Converts an already resolved `line` into a path.
+pub(crate) fn line_to_path(x1: f32, y1: f32, x2: f32, y2: f32) -> Option<Arc<Path>> {
     let mut builder = tiny_skia_path::PathBuilder::new();
     builder.move_to(x1, y1);
     builder.line_to(x2, y2);
     builder.finish().map(Arc::new)
 }
 
-fn convert_polyline(node: SvgNode) -> Option<Arc<Path>> {
-    let builder = points_to_path(node, "Polyline")?;
+/// Converts `polyline` or `polygon` points into a path.
+pub(crate) fn points_to_path(points: &[(f32, f32)], close: bool) -> Option<Arc<Path>> {
+    let mut builder = tiny_skia_path::PathBuilder::new();
+    for &(x, y) in points {
+        if builder.is_empty() {
+            builder.move_to(x, y);
+        } else {
+            builder.line_to(x, y);
+        }
+    }
+
+    if close {
+        builder.close();
+    }
+
+    builder.finish().map(Arc::new)
+}
+
+pub(crate) fn convert_polyline(node: SvgNode) -> Option<Arc<Path>> {
+    let builder = parse_points_path(node, "Polyline")?;
     builder.finish().map(Arc::new)
 }
 
 pub(crate) fn convert_polygon(node: SvgNode) -> Option<Arc<Path>> {
-    let mut builder = points_to_path(node, "Polygon")?;
+    let mut builder = parse_points_path(node, "Polygon")?;
     builder.close();
     builder.finish().map(Arc::new)
 }
 
-fn points_to_path(node: SvgNode, eid: &str) -> Option<tiny_skia_path::PathBuilder> {
+fn parse_points_path(node: SvgNode, eid: &str) -> Option<tiny_skia_path::PathBuilder> {
     use svgtypes::PointsParser;
 
     let mut builder = tiny_skia_path::PathBuilder::new();
@@ -197,10 +245,13 @@ fn points_to_path(node: SvgNode, eid: &str) -> Option<tiny_skia_path::PathBuilde
             }
         }
         _ => {
-            log::warn!(
-                "{} '{}' has an invalid 'points' value. Skipped.",
-                eid,
-                node.element_id()
+            node.skip(
+                DiagnosticCode::InvalidValue,
+                format!(
+                    "{} '{}' has an invalid 'points' value. Skipped.",
+                    eid,
+                    node.element_id()
+                ),
             );
             return None;
         }
@@ -208,10 +259,13 @@ fn points_to_path(node: SvgNode, eid: &str) -> Option<tiny_skia_path::PathBuilde
 
     // 'polyline' and 'polygon' elements must contain at least 2 points.
     if builder.len() < 2 {
-        log::warn!(
-            "{} '{}' has less than 2 points. Skipped.",
-            eid,
-            node.element_id()
+        node.skip(
+            DiagnosticCode::InvalidValue,
+            format!(
+                "{} '{}' has less than 2 points. Skipped.",
+                eid,
+                node.element_id()
+            ),
         );
         return None;
     }
@@ -219,15 +273,18 @@ fn points_to_path(node: SvgNode, eid: &str) -> Option<tiny_skia_path::PathBuilde
     Some(builder)
 }
 
-fn convert_circle(node: SvgNode, state: &converter::State) -> Option<Arc<Path>> {
+pub(crate) fn convert_circle(node: SvgNode, state: &converter::State) -> Option<Arc<Path>> {
     let cx = node.convert_user_length(AId::Cx, state, Length::zero());
     let cy = node.convert_user_length(AId::Cy, state, Length::zero());
     let r = node.convert_user_length(AId::R, state, Length::zero());
 
     if !r.is_valid_length() {
-        log::warn!(
-            "Circle '{}' has an invalid 'r' value. Skipped.",
-            node.element_id()
+        node.skip(
+            DiagnosticCode::InvalidSize,
+            format!(
+                "Circle '{}' has an invalid 'r' value. Skipped.",
+                node.element_id()
+            ),
         );
         return None;
     }
@@ -241,17 +298,23 @@ pub(crate) fn convert_ellipse(node: SvgNode, state: &converter::State) -> Option
     let (rx, ry) = resolve_rx_ry(node, state);
 
     if !rx.is_valid_length() {
-        log::warn!(
-            "Ellipse '{}' has an invalid 'rx' value. Skipped.",
-            node.element_id()
+        node.skip(
+            DiagnosticCode::InvalidSize,
+            format!(
+                "Ellipse '{}' has an invalid 'rx' value. Skipped.",
+                node.element_id()
+            ),
         );
         return None;
     }
 
     if !ry.is_valid_length() {
-        log::warn!(
-            "Ellipse '{}' has an invalid 'ry' value. Skipped.",
-            node.element_id()
+        node.skip(
+            DiagnosticCode::InvalidSize,
+            format!(
+                "Ellipse '{}' has an invalid 'ry' value. Skipped.",
+                node.element_id()
+            ),
         );
         return None;
     }
@@ -259,7 +322,8 @@ pub(crate) fn convert_ellipse(node: SvgNode, state: &converter::State) -> Option
     ellipse_to_path(cx, cy, rx, ry)
 }
 
-fn ellipse_to_path(cx: f32, cy: f32, rx: f32, ry: f32) -> Option<Arc<Path>> {
+/// Converts an already resolved `ellipse` or `circle` into a path.
+pub(crate) fn ellipse_to_path(cx: f32, cy: f32, rx: f32, ry: f32) -> Option<Arc<Path>> {
     let mut builder = tiny_skia_path::PathBuilder::new();
     builder.move_to(cx + rx, cy);
     builder.arc_to(rx, ry, 0.0, false, true, cx, cy + ry);
@@ -6,7 +6,7 @@ use super::paint_server;
 use super::svgtree::{AId, FromValue, SvgNode};
 use crate::tree::ContextElement;
 use crate::{
-    ApproxEqUlps, Color, Fill, FillRule, LineCap, LineJoin, Opacity, Paint, Stroke,
+    ApproxEqUlps, Color, DiagnosticCode, Fill, FillRule, LineCap, LineJoin, Opacity, Paint, Stroke,
     StrokeMiterlimit, Units,
 };
 
@@ -48,6 +48,19 @@ pub(crate) fn resolve_fill(
     has_bbox: bool,
     state: &converter::State,
     cache: &mut converter::Cache,
+) -> Option<Fill> {
+    resolve_fill_with_paint(node, AId::Fill, has_bbox, state, cache)
+}
+
+/// Resolves a fill with a paint from the specified attribute instead of `fill`.
+///
+/// Used by `text-decoration-fill`.
+pub(crate) fn resolve_fill_with_paint(
+    node: SvgNode,
+    paint_aid: AId,
+    has_bbox: bool,
+    state: &converter::State,
+    cache: &mut converter::Cache,
 ) -> Option<Fill> {
     if state.parent_clip_path.is_some() {
         // A `clipPath` child can be filled only with a black color.
@@ -61,12 +74,12 @@ pub(crate) fn resolve_fill(
 
     let mut sub_opacity = Opacity::ONE;
     let (paint, context_element) =
-        if let Some(n) = node.ancestors().find(|n| n.has_attribute(AId::Fill)) {
-            let value: &str = n.attribute(AId::Fill)?;
+        if let Some(n) = node.ancestors().find(|n| n.has_attribute(paint_aid)) {
+            let value: &str = n.attribute(paint_aid)?;
             convert_paint(
                 node,
                 value,
-                AId::Fill,
+                paint_aid,
                 has_bbox,
                 state,
                 &mut sub_opacity,
@@ -93,6 +106,19 @@ pub(crate) fn resolve_stroke(
     has_bbox: bool,
     state: &converter::State,
     cache: &mut converter::Cache,
+) -> Option<Stroke> {
+    resolve_stroke_with_paint(node, AId::Stroke, has_bbox, state, cache)
+}
+
+/// Resolves a stroke with a paint from the specified attribute instead of `stroke`.
+///
+/// Used by `text-decoration-stroke`.
+pub(crate) fn resolve_stroke_with_paint(
+    node: SvgNode,
+    paint_aid: AId,
+    has_bbox: bool,
+    state: &converter::State,
+    cache: &mut converter::Cache,
 ) -> Option<Stroke> {
     if state.parent_clip_path.is_some() {
         // A `clipPath` child cannot be stroked.
@@ -101,13 +127,13 @@ pub(crate) fn resolve_stroke(
 
     let mut sub_opacity = Opacity::ONE;
     let (paint, context_element) =
-        if let Some(n) = node.ancestors().find(|n| n.has_attribute(AId::Stroke)) {
-            let value: &str = n.attribute(AId::Stroke)?;
+        if let Some(n) = node.ancestors().find(|n| n.has_attribute(paint_aid)) {
+            let value: &str = n.attribute(paint_aid)?;
 
             convert_paint(
                 node,
                 value,
-                AId::Stroke,
+                paint_aid,
                 has_bbox,
                 state,
                 &mut sub_opacity,
@@ -156,15 +182,21 @@ fn convert_paint(
         Ok(v) => v,
         Err(_) => {
             if aid == AId::Fill {
-                log::warn!(
-                    "Failed to parse fill value: '{}'. Fallback to black.",
-                    value
+                node.warn(
+                    DiagnosticCode::InvalidValue,
+                    format!(
+                        "Failed to parse fill value: '{}'. Fallback to black.",
+                        value
+                    ),
                 );
                 svgtypes::Paint::Color(svgtypes::Color::black())
             } else if aid == AId::Stroke {
-                log::warn!(
-                    "Failed to parse stroke value: '{}'. Fallback to no stroke.",
-                    value
+                node.warn(
+                    DiagnosticCode::InvalidValue,
+                    format!(
+                        "Failed to parse stroke value: '{}'. Fallback to no stroke.",
+                        value
+                    ),
                 );
                 return None;
             } else {
@@ -223,7 +255,10 @@ fn convert_paint(
                         None => from_fallback(node, fallback, opacity).map(|p| (p, None)),
                     }
                 } else {
-                    log::warn!("'{}' cannot be used to {} a shape.", tag_name, aid);
+                    node.skip(
+                        DiagnosticCode::InvalidReference,
+                        format!("'{}' cannot be used to {} a shape.", tag_name, aid),
+                    );
                     None
                 }
             } else {
@@ -0,0 +1,784 @@
+// Copyright 2025 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! SVG fonts conversion.
+//!
+//! SVG fonts are converted into TrueType fonts and loaded into the tree's fontdb.
+//! This way text layout and outlining work the same way as for any other font.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use fontdb::Database;
+
+use super::font_face::{parse_family, parse_stretch, parse_style, parse_weight};
+use super::sfnt::{self, WriteExt};
+use super::svgtree::{self, SvgFont, SvgGlyph, SvgKerning};
+use super::DiagnosticCode;
+use crate::FontStyle;
+
+/// Loads fonts defined via `font` elements into the database.
+pub(crate) fn load_svg_fonts(doc: &svgtree::Document, fontdb: &mut Arc<Database>) {
+    let root = doc.root_element();
+    for font in doc.svg_fonts() {
+        let family = match font.family.as_deref().and_then(parse_family) {
+            Some(family) => family,
+            None => {
+                root.warn(
+                    DiagnosticCode::MissingAttribute,
+                    "An SVG font without a font-face font-family. Skipped.".to_string(),
+                );
+                continue;
+            }
+        };
+
+        let ids = match convert(font, &family) {
+            Some(data) => {
+                Arc::make_mut(fontdb).load_font_source(fontdb::Source::Binary(Arc::new(data)))
+            }
+            None => Default::default(),
+        };
+
+        if ids.is_empty() {
+            root.warn(
+                DiagnosticCode::InvalidValue,
+                format!("Failed to convert the '{}' SVG font. Skipped.", family),
+            );
+        }
+    }
+}
+
+/// Font-wide values in font units.
+struct Metrics {
+    units_per_em: u16,
+    ascent: i16,
+    descent: i16,
+    x_height: i16,
+    cap_height: i16,
+    underline_position: i16,
+    underline_thickness: i16,
+    strikeout_position: i16,
+    strikeout_thickness: i16,
+    weight: u16,
+    style: FontStyle,
+    width: u16,
+}
+
+/// A converted glyph.
+#[derive(Default)]
+struct Glyph {
+    advance: u16,
+    /// `xMin`, `yMin`, `xMax`, `yMax`. `None` for empty glyphs.
+    bbox: Option<[i16; 4]>,
+    num_points: u16,
+    num_contours: u16,
+}
+
+fn convert(font: &SvgFont, family: &str) -> Option<Vec<u8>> {
+    let units_per_em = font.units_per_em.filter(|n| *n > 0.0).unwrap_or(1000.0);
+    // TrueType requires an integer in a 16..=16384 range.
+    // Fonts with a small or a fractional units per em are rescaled to preserve precision.
+    let (scale, upem) = if units_per_em.fract() == 0.0 && (1000.0..=16384.0).contains(&units_per_em)
+    {
+        (1.0, units_per_em as u16)
+    } else {
+        (2048.0 / units_per_em, 2048)
+    };
+    let to_units = |n: f32| (n * scale).round().clamp(i16::MIN as f32, i16::MAX as f32) as i16;
+
+    // Glyph 0 is reserved for the missing glyph.
+    let empty = SvgGlyph::default();
+    let mut glyphs = vec![font.missing_glyph.as_ref().unwrap_or(&empty)];
+    glyphs.extend(font.glyphs.iter());
+    if glyphs.len() > u16::MAX as usize {
+        return None;
+    }
+
+    let mut glyf = Vec::new();
+    let mut loca = Vec::new();
+    let mut converted = Vec::with_capacity(glyphs.len());
+    for glyph in &glyphs {
+        loca.write_u32(glyf.len() as u32);
+        let advance = glyph.horiz_adv_x.unwrap_or(font.horiz_adv_x) * scale;
+        let mut glyph_info = Glyph {
+            advance: advance.round().clamp(0.0, u16::MAX as f32) as u16,
+            ..Glyph::default()
+        };
+
+        let origin = (font.horiz_origin_x, font.horiz_origin_y);
+        let contours = glyph
+            .path
+            .as_deref()
+            .map(|d| convert_path(d, origin, scale))
+            .unwrap_or_default();
+        write_glyph(&contours, &mut glyph_info, &mut glyf);
+        converted.push(glyph_info);
+    }
+    loca.write_u32(glyf.len() as u32);
+
+    // Only single character glyphs are mapped directly. The first glyph wins.
+    let mut cmap = BTreeMap::new();
+    for (id, glyph) in glyphs.iter().enumerate().skip(1) {
+        if let Some(c) = glyph.unicode.as_deref().and_then(single_char) {
+            cmap.entry(c as u32).or_insert(id as u16);
+        }
+    }
+
+    let ascent = to_units(font.ascent.unwrap_or(units_per_em));
+    let descent = -to_units(font.descent.unwrap_or(0.0));
+    let x_height = font.x_height.map(to_units).unwrap_or(0);
+    // Fallback to the same values that are used for fonts without these metrics.
+    let underline_position = font
+        .underline_position
+        .map(to_units)
+        .unwrap_or(-(upem as i16) / 9);
+    let underline_thickness = font
+        .underline_thickness
+        .map(to_units)
+        .unwrap_or(upem as i16 / 12);
+    let strikeout_position = font
+        .strikethrough_position
+        .map(to_units)
+        .unwrap_or_else(|| {
+            if x_height > 0 {
+                x_height / 2
+            } else {
+                ((ascent as f32 - descent as f32) * 0.45 / 2.0) as i16
+            }
+        });
+
+    let metrics = Metrics {
+        units_per_em: upem,
+        ascent,
+        descent,
+        x_height,
+        cap_height: font.cap_height.map(to_units).unwrap_or(0),
+        underline_position,
+        underline_thickness,
+        strikeout_position,
+        strikeout_thickness: font
+            .strikethrough_thickness
+            .map(to_units)
+            .unwrap_or(underline_thickness),
+        weight: descriptor(font.font_weight.as_deref())
+            .and_then(parse_weight)
+            .unwrap_or(400),
+        style: descriptor(font.font_style.as_deref())
+            .and_then(parse_style)
+            .unwrap_or(FontStyle::Normal),
+        width: descriptor(font.font_stretch.as_deref())
+            .and_then(parse_stretch)
+            .map(|s| fontdb::Stretch::from(s).to_number())
+            .unwrap_or(5),
+    };
+
+    let mut tables = vec![
+        (*b"head", write_head(&metrics, &converted)),
+        (*b"hhea", write_hhea(&metrics, &converted)),
+        (*b"maxp", write_maxp(&converted)),
+        (*b"OS/2", write_os2(&metrics, &converted, &cmap)),
+        (*b"hmtx", write_hmtx(&converted)),
+        (*b"cmap", write_cmap(&cmap)),
+        (*b"loca", loca),
+        (*b"glyf", glyf),
+        (*b"name", write_name(family, &metrics)),
+        (*b"post", write_post(&metrics)),
+    ];
+
+    let kerning = resolve_kerning(&font.kerning, &glyphs, to_units);
+    if !kerning.is_empty() {
+        tables.push((*b"kern", write_kern(&kerning)));
+    }
+
+    if let Some(gsub) = write_gsub(&glyphs, &cmap) {
+        tables.push((*b"GSUB", gsub));
+    }
+
+    Some(sfnt::build(0x00010000, tables))
+}
+
+/// Returns the first value of a descriptor.
+///
+/// `font-face` descriptors can contain a list of values.
+fn descriptor(value: Option<&str>) -> Option<&str> {
+    let value = value?.split(',').next()?.trim();
+    (value != "all").then_some(value)
+}
+
+fn single_char(s: &str) -> Option<char> {
+    let mut chars = s.chars();
+    let c = chars.next()?;
+    chars.next().is_none().then_some(c)
+}
+
+/// Converts path data into TrueType contours.
+///
+/// Cubic curves are approximated by quadratic ones.
+fn convert_path(d: &str, origin: (f32, f32), scale: f32) -> Vec<Vec<(i32, i32, bool)>> {
+    let (ox, oy, scale) = (origin.0 as f64, origin.1 as f64, scale as f64);
+    let to_point = |p: kurbo::Point, on_curve: bool| {
+        let clamp = |n: f64| n.round().clamp(i16::MIN as f64, i16::MAX as f64) as i32;
+        (
+            clamp((p.x - ox) * scale),
+            clamp((p.y - oy) * scale),
+            on_curve,
+        )
+    };
+
+    let mut contours = Vec::new();
+    let mut contour = Vec::new();
+    let mut prev = kurbo::Point::ZERO;
+    for segment in svgtypes::SimplifyingPathParser::from(d) {
+        let segment = match segment {
+            Ok(v) => v,
+            Err(_) => break,
+        };
+
+        match segment {
+            svgtypes::SimplePathSegment::MoveTo { x, y } => {
+                finish_contour(&mut contour, &mut contours);
+                prev = kurbo::Point::new(x, y);
+                contour.push(to_point(prev, true));
+            }
+            svgtypes::SimplePathSegment::LineTo { x, y } => {
+                prev = kurbo::Point::new(x, y);
+                contour.push(to_point(prev, true));
+            }
+            svgtypes::SimplePathSegment::Quadratic { x1, y1, x, y } => {
+                contour.push(to_point(kurbo::Point::new(x1, y1), false));
+                prev = kurbo::Point::new(x, y);
+                contour.push(to_point(prev, true));
+            }
+            svgtypes::SimplePathSegment::CurveTo {
+                x1,
+                y1,
+                x2,
+                y2,
+                x,
+                y,
+            } => {
+                let curve = kurbo::CubicBez::new(
+                    prev,
+                    kurbo::Point::new(x1, y1),
+                    kurbo::Point::new(x2, y2),
+                    kurbo::Point::new(x, y),
+                );
+                // Half a font unit is precise enough.
+                for (_, _, quad) in curve.to_quads(0.5 / scale) {
+                    contour.push(to_point(quad.p1, false));
+                    contour.push(to_point(quad.p2, true));
+                }
+                prev = kurbo::Point::new(x, y);
+            }
+            svgtypes::SimplePathSegment::ClosePath => {
+                finish_contour(&mut contour, &mut contours);
+            }
+        }
+    }
+
+    finish_contour(&mut contour, &mut contours);
+    contours
+}
+
+fn finish_contour(contour: &mut Vec<(i32, i32, bool)>, contours: &mut Vec<Vec<(i32, i32, bool)>>) {
+    // TrueType contours are always closed, so the closing point is redundant.
+    if contour.len() > 1 && contour.first() == contour.last() {
+        contour.pop();
+    }
+
+    if contour.len() > 1 {
+        contours.push(std::mem::take(contour));
+    } else {
+        contour.clear();
+    }
+}
+
+fn write_glyph(contours: &[Vec<(i32, i32, bool)>], info: &mut Glyph, glyf: &mut Vec<u8>) {
+    let num_points: usize = contours.iter().map(Vec::len).sum();
+    if contours.is_empty() || num_points > u16::MAX as usize || contours.len() > i16::MAX as usize {
+        return;
+    }
+
+    let points: Vec<_> = contours.iter().flatten().copied().collect();
+    let mut bbox = [i16::MAX, i16::MAX, i16::MIN, i16::MIN];
+    for &(x, y, _) in &points {
+        bbox[0] = bbox[0].min(x as i16);
+        bbox[1] = bbox[1].min(y as i16);
+        bbox[2] = bbox[2].max(x as i16);
+        bbox[3] = bbox[3].max(y as i16);
+    }
+
+    glyf.write_i16(contours.len() as i16);
+    for n in bbox {
+        glyf.write_i16(n);
+    }
+
+    let mut end = 0;
+    for contour in contours {
+        end += contour.len();
+        glyf.write_u16(end as u16 - 1);
+    }
+
+    glyf.write_u16(0); // instructions length
+    sfnt::write_simple_glyph_points(&points, false, glyf);
+    glyf.resize(glyf.len().next_multiple_of(4), 0);
+
+    info.bbox = Some(bbox);
+    info.num_points = num_points as u16;
+    info.num_contours = contours.len() as u16;
+}
+
+/// Resolves `hkern` elements into glyph pairs.
+///
+/// The first definition of a pair wins.
+fn resolve_kerning(
+    kerning: &[SvgKerning],
+    glyphs: &[&SvgGlyph],
+    to_units: impl Fn(f32) -> i16,
+) -> BTreeMap<(u16, u16), i16> {
+    let mut pairs = BTreeMap::new();
+    for kern in kerning {
+        let left = kerning_glyphs(glyphs, kern.u1.as_deref(), kern.g1.as_deref());
+        let right = kerning_glyphs(glyphs, kern.u2.as_deref(), kern.g2.as_deref());
+        // `k` reduces the advance, while TrueType kerning is added to it.
+        let value = to_units(-kern.k);
+        for &l in &left {
+            for &r in &right {
+                pairs.entry((l, r)).or_insert(value);
+            }
+        }
+    }
+
+    pairs
+}
+
+/// Returns glyphs matching `u1`/`u2` and `g1`/`g2` values.
+fn kerning_glyphs(glyphs: &[&SvgGlyph], unicode: Option<&str>, names: Option<&str>) -> Vec<u16> {
+    let mut ids = Vec::new();
+    for item in unicode.into_iter().flat_map(|s| s.split(',')) {
+        let item = item.trim();
+        let range = item.strip_prefix("U+").and_then(parse_unicode_range);
+        for (id, glyph) in glyphs.iter().enumerate().skip(1) {
+            let matches = match (range, glyph.unicode.as_deref()) {
+                (Some((start, end)), Some(s)) => {
+                    single_char(s).is_some_and(|c| (start..=end).contains(&(c as u32)))
+                }
+                (None, Some(s)) => s == item,
+                _ => false,
+            };
+
+            if matches {
+                ids.push(id as u16);
+            }
+        }
+    }
+
+    for item in names.into_iter().flat_map(|s| s.split(',')) {
+        let item = item.trim();
+        for (id, glyph) in glyphs.iter().enumerate().skip(1) {
+            if glyph.name.as_deref() == Some(item) {
+                ids.push(id as u16);
+            }
+        }
+    }
+
+    ids
+}
+
+/// Parses a `unicode-range` value without the `U+` prefix.
+///
+/// Like `0041`, `0041-005A` or `00??`.
+fn parse_unicode_range(s: &str) -> Option<(u32, u32)> {
+    let (start, end) = match s.split_once('-') {
+        Some((start, end)) => (start.to_string(), end.to_string()),
+        None => (s.replace('?', "0"), s.replace('?', "F")),
+    };
+
+    let start = u32::from_str_radix(&start, 16).ok()?;
+    let end = u32::from_str_radix(&end, 16).ok()?;
+    (start <= end).then_some((start, end))
+}
+
+fn write_head(metrics: &Metrics, glyphs: &[Glyph]) -> Vec<u8> {
+    let bbox = glyphs
+        .iter()
+        .filter_map(|g| g.bbox)
+        .reduce(|a, b| {
+            [
+                a[0].min(b[0]),
+                a[1].min(b[1]),
+                a[2].max(b[2]),
+                a[3].max(b[3]),
+            ]
+        })
+        .unwrap_or_default();
+
+    let mut mac_style = 0;
+    if metrics.weight >= 700 {
+        mac_style |= 1;
+    }
+    if metrics.style != FontStyle::Normal {
+        mac_style |= 2;
+    }
+
+    let mut data = Vec::with_capacity(54);
+    data.write_u32(0x00010000); // version
+    data.write_u32(0x00010000); // font revision
+    data.write_u32(0); // checksum adjustment
+    data.write_u32(0x5F0F3CF5); // magic number
+    data.write_u16(1); // flags: baseline at y=0
+    data.write_u16(metrics.units_per_em);
+    data.extend_from_slice(&[0; 16]); // created and modified dates
+    for n in bbox {
+        data.write_i16(n);
+    }
+    data.write_u16(mac_style);
+    data.write_u16(8); // lowest recommended PPEM
+    data.write_i16(2); // font direction hint
+    data.write_i16(1); // long loca offsets
+    data.write_i16(0); // glyph data format
+    data
+}
+
+fn write_hhea(metrics: &Metrics, glyphs: &[Glyph]) -> Vec<u8> {
+    let advance_max = glyphs.iter().map(|g| g.advance).max().unwrap_or(0);
+    let outlines = || glyphs.iter().filter_map(|g| Some((g.advance, g.bbox?)));
+    let min_lsb = outlines().map(|(_, b)| b[0]).min().unwrap_or(0);
+    let min_rsb = outlines()
+        .map(|(a, b)| (a as i32 - b[2] as i32).clamp(i16::MIN as i32, 0) as i16)
+        .min()
+        .unwrap_or(0);
+    let max_extent = outlines().map(|(_, b)| b[2]).max().unwrap_or(0);
+
+    let mut data = Vec::with_capacity(36);
+    data.write_u32(0x00010000); // version
+    data.write_i16(metrics.ascent);
+    data.write_i16(metrics.descent);
+    data.write_i16(0); // line gap
+    data.write_u16(advance_max);
+    data.write_i16(min_lsb);
+    data.write_i16(min_rsb);
+    data.write_i16(max_extent);
+    data.write_i16(1); // caret slope rise
+    data.write_i16(0); // caret slope run
+    data.write_i16(0); // caret offset
+    data.extend_from_slice(&[0; 8]); // reserved
+    data.write_i16(0); // metric data format
+    data.write_u16(glyphs.len() as u16); // number of long metrics
+    data
+}
+
+fn write_maxp(glyphs: &[Glyph]) -> Vec<u8> {
+    let mut data = Vec::with_capacity(32);
+    data.write_u32(0x00010000); // version
+    data.write_u16(glyphs.len() as u16);
+    data.write_u16(glyphs.iter().map(|g| g.num_points).max().unwrap_or(0));
+    data.write_u16(glyphs.iter().map(|g| g.num_contours).max().unwrap_or(0));
+    data.write_u16(0); // max composite points
+    data.write_u16(0); // max composite contours
+    data.write_u16(2); // max zones
+    data.extend_from_slice(&[0; 16]); // hinting and composite glyphs limits
+    data
+}
+
+fn write_os2(metrics: &Metrics, glyphs: &[Glyph], cmap: &BTreeMap<u32, u16>) -> Vec<u8> {
+    let upem = metrics.units_per_em as f32;
+    let scaled = |n: f32| (upem * n).round() as i16;
+    let avg_width = glyphs.iter().map(|g| g.advance as u32).sum::<u32>() / glyphs.len() as u32;
+
+    let mut selection = 0;
+    match metrics.style {
+        FontStyle::Normal => {}
+        FontStyle::Italic => selection |= 1,
+        FontStyle::Oblique => selection |= 1 | 1 << 9,
+    }
+    if metrics.weight >= 700 {
+        selection |= 1 << 5;
+    }
+    if selection == 0 {
+        selection = 1 << 6; // regular
+    }
+
+    let first_char = cmap.keys().next().copied().unwrap_or(0).min(0xFFFF) as u16;
+    let last_char = cmap.keys().next_back().copied().unwrap_or(0).min(0xFFFF) as u16;
+
+    let mut data = Vec::with_capacity(96);
+    data.write_u16(4); // version
+    data.write_i16(avg_width as i16);
+    data.write_u16(metrics.weight);
+    data.write_u16(metrics.width);
+    data.write_u16(0); // embedding permissions
+                       // Subscript and superscript metrics. Same defaults as font compilers use.
+    for (x_size, y_size, y_offset) in [(0.65, 0.6, 0.075), (0.65, 0.6, 0.35)] {
+        data.write_i16(scaled(x_size));
+        data.write_i16(scaled(y_size));
+        data.write_i16(0);
+        data.write_i16(scaled(y_offset));
+    }
+    data.write_i16(metrics.strikeout_thickness);
+    data.write_i16(metrics.strikeout_position);
+    data.write_i16(0); // family class
+    data.extend_from_slice(&[0; 10]); // panose
+    data.extend_from_slice(&[0; 16]); // unicode ranges
+    data.extend_from_slice(b"    "); // vendor ID
+    data.write_u16(selection);
+    data.write_u16(first_char);
+    data.write_u16(last_char);
+    data.write_i16(metrics.ascent);
+    data.write_i16(metrics.descent);
+    data.write_i16(0); // typographic line gap
+    data.write_u16(metrics.ascent.max(0) as u16);
+    data.write_u16(metrics.descent.min(0).unsigned_abs());
+    data.extend_from_slice(&[0; 8]); // code page ranges
+    data.write_i16(metrics.x_height);
+    data.write_i16(metrics.cap_height);
+    data.write_u16(0); // default char
+    data.write_u16(0x20); // break char
+    data.write_u16(0); // max context
+    data
+}
+
+fn write_hmtx(glyphs: &[Glyph]) -> Vec<u8> {
+    let mut data = Vec::with_capacity(glyphs.len() * 4);
+    for glyph in glyphs {
+        data.write_u16(glyph.advance);
+        data.write_i16(glyph.bbox.map(|b| b[0]).unwrap_or(0));
+    }
+
+    data
+}
+
+fn write_cmap(cmap: &BTreeMap<u32, u16>) -> Vec<u8> {
+    // Consecutive characters mapped to consecutive glyphs form a group.
+    let mut groups: Vec<(u32, u32, u16)> = Vec::new();
+    for (&c, &id) in cmap {
+        match groups.last_mut() {
+            Some((start, end, start_id))
+                if c == *end + 1 && id as u32 == *start_id as u32 + (c - *start) =>
+            {
+                *end = c;
+            }
+            _ => groups.push((c, c, id)),
+        }
+    }
+
+    let mut data = Vec::new();
+    data.write_u16(0); // version
+    data.write_u16(1); // number of subtables
+    data.write_u16(3); // Windows platform
+    data.write_u16(10); // Unicode full repertoire
+    data.write_u32(12); // subtable offset
+    data.write_u16(12); // format
+    data.write_u16(0); // reserved
+    data.write_u32(16 + groups.len() as u32 * 12);
+    data.write_u32(0); // language
+    data.write_u32(groups.len() as u32);
+    for (start, end, id) in groups {
+        data.write_u32(start);
+        data.write_u32(end);
+        data.write_u32(id as u32);
+    }
+
+    data
+}
+
+fn write_name(family: &str, metrics: &Metrics) -> Vec<u8> {
+    let bold = metrics.weight >= 700;
+    let italic = metrics.style != FontStyle::Normal;
+    let subfamily = match (bold, italic) {
+        (false, false) => "Regular",
+        (true, false) => "Bold",
+        (false, true) => "Italic",
+        (true, true) => "Bold Italic",
+    };
+
+    let mut post_script_name: String = family
+        .chars()
+        .filter(|c| c.is_ascii_graphic() && !"[](){}<>/%".contains(*c))
+        .take(63)
+        .collect();
+    if post_script_name.is_empty() {
+        post_script_name = "SvgFont".to_string();
+    }
+
+    let full_name = if subfamily == "Regular" {
+        family.to_string()
+    } else {
+        format!("{} {}", family, subfamily)
+    };
+
+    let names = [
+        (1, family),
+        (2, subfamily),
+        (4, full_name.as_str()),
+        (6, post_script_name.as_str()),
+    ];
+
+    let mut strings = Vec::new();
+    let mut data = Vec::new();
+    data.write_u16(0); // format
+    data.write_u16(names.len() as u16);
+    data.write_u16(6 + names.len() as u16 * 12); // strings offset
+    for (id, name) in names {
+        let offset = strings.len();
+        for c in name.encode_utf16() {
+            strings.write_u16(c);
+        }
+
+        data.write_u16(3); // Windows platform
+        data.write_u16(1); // Unicode BMP
+        data.write_u16(0x0409); // English (United States)
+        data.write_u16(id);
+        data.write_u16((strings.len() - offset) as u16);
+        data.write_u16(offset as u16);
+    }
+
+    data.extend_from_slice(&strings);
+    data
+}
+
+fn write_post(metrics: &Metrics) -> Vec<u8> {
+    let mut data = Vec::with_capacity(32);
+    data.write_u32(0x00030000); // version, without glyph names
+    data.write_u32(0); // italic angle
+    data.write_i16(metrics.underline_position);
+    data.write_i16(metrics.underline_thickness);
+    data.extend_from_slice(&[0; 20]); // fixed pitch flag and memory usage
+    data
+}
+
+fn write_kern(pairs: &BTreeMap<(u16, u16), i16>) -> Vec<u8> {
+    // The subtable length is 16-bit, which limits the number of pairs.
+    const MAX_PAIRS: usize = (u16::MAX as usize - 14) / 6;
+    if pairs.len() > MAX_PAIRS {
+        log::warn!("SVG font has too many kerning pairs. Some of them will be ignored.");
+    }
+
+    let num_pairs = pairs.len().min(MAX_PAIRS) as u16;
+    let entry_selector = 15 - num_pairs.leading_zeros() as u16;
+    let search_range = (1u16 << entry_selector) * 6;
+
+    let mut data = Vec::new();
+    data.write_u16(0); // version
+    data.write_u16(1); // number of subtables
+    data.write_u16(0); // subtable version
+    data.write_u16(14 + num_pairs * 6); // subtable length
+    data.write_u16(1); // horizontal, format 0
+    data.write_u16(num_pairs);
+    data.write_u16(search_range);
+    data.write_u16(entry_selector);
+    data.write_u16(num_pairs * 6 - search_range);
+    for (&(left, right), &value) in pairs.iter().take(num_pairs as usize) {
+        data.write_u16(left);
+        data.write_u16(right);
+        data.write_i16(value);
+    }
+
+    data
+}
+
+/// Writes multi-character glyphs as required ligatures.
+///
+/// Returns `None` when there are no ligatures.
+fn write_gsub(glyphs: &[&SvgGlyph], cmap: &BTreeMap<u32, u16>) -> Option<Vec<u8>> {
+    // Ligatures grouped by the first glyph. Only glyphs whose characters
+    // are all present in the font can form a ligature.
+    let mut ligatures: BTreeMap<u16, Vec<(Vec<u16>, u16)>> = BTreeMap::new();
+    for (id, glyph) in glyphs.iter().enumerate().skip(1) {
+        let unicode = match glyph.unicode.as_deref() {
+            Some(s) if single_char(s).is_none() => s,
+            _ => continue,
+        };
+
+        let components: Option<Vec<u16>> = unicode
+            .chars()
+            .map(|c| cmap.get(&(c as u32)).copied())
+            .collect();
+        if let Some(components) = components {
+            ligatures
+                .entry(components[0])
+                .or_default()
+                .push((components[1..].to_vec(), id as u16));
+        }
+    }
+
+    if ligatures.is_empty() {
+        return None;
+    }
+
+    // The longest match wins. Otherwise, the document order is preserved.
+    for set in ligatures.values_mut() {
+        set.sort_by_key(|(components, _)| std::cmp::Reverse(components.len()));
+    }
+
+    // Ligature substitution subtable.
+    let mut subtable = Vec::new();
+    let num_sets = ligatures.len();
+    let coverage_offset = 6 + num_sets * 2;
+    let mut sets = Vec::new();
+    let mut set_offsets = Vec::new();
+    for set in ligatures.values() {
+        set_offsets.push(coverage_offset + 4 + num_sets * 2 + sets.len());
+        let start = sets.len();
+        sets.write_u16(set.len() as u16);
+        let mut offset = 2 + set.len() * 2;
+        for (components, _) in set {
+            sets.write_u16(u16::try_from(offset).ok()?);
+            offset += 4 + components.len() * 2;
+        }
+        for (components, id) in set {
+            sets.write_u16(*id);
+            sets.write_u16(components.len() as u16 + 1);
+            for component in components {
+                sets.write_u16(*component);
+            }
+        }
+
+        debug_assert_eq!(sets.len() - start, offset);
+    }
+
+    subtable.write_u16(1); // format
+    subtable.write_u16(coverage_offset as u16);
+    subtable.write_u16(num_sets as u16);
+    for offset in set_offsets {
+        subtable.write_u16(u16::try_from(offset).ok()?);
+    }
+    subtable.write_u16(1); // coverage format
+    subtable.write_u16(num_sets as u16);
+    for id in ligatures.keys() {
+        subtable.write_u16(*id);
+    }
+    subtable.extend_from_slice(&sets);
+
+    let mut data = Vec::new();
+    data.write_u32(0x00010000); // version
+    data.write_u16(10); // script list offset
+    data.write_u16(30); // feature list offset
+    data.write_u16(44); // lookup list offset
+                        // Script list with a single default script.
+    data.extend_from_slice(&[0, 1]);
+    data.extend_from_slice(b"DFLT");
+    data.write_u16(8); // script offset
+    data.write_u16(4); // default language system offset
+    data.write_u16(0); // language systems count
+    data.write_u16(0); // lookup order
+    data.write_u16(0xFFFF); // no required feature
+    data.write_u16(1); // features count
+    data.write_u16(0); // feature index
+                       // Feature list with a single `rlig` feature, which is always enabled.
+    data.extend_from_slice(&[0, 1]);
+    data.extend_from_slice(b"rlig");
+    data.write_u16(8); // feature offset
+    data.write_u16(0); // feature params
+    data.write_u16(1); // lookups count
+    data.write_u16(0); // lookup index
+                       // Lookup list with a single ligature substitution lookup.
+    data.extend_from_slice(&[0, 1]);
+    data.write_u16(4); // lookup offset
+    data.write_u16(4); // ligature substitution
+    data.write_u16(0); // lookup flags
+    data.write_u16(1); // subtables count
+    data.write_u16(8); // subtable offset
+    data.extend_from_slice(&subtable);
+    Some(data)
+}
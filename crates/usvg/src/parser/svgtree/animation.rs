@@ -0,0 +1,893 @@
+// Copyright 2025 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! SMIL animations sampling.
+//!
+//! Animations are not preserved in the tree. Instead, animated attributes
+//! are replaced with their values at a specific time during parsing.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use kurbo::{ParamCurve, ParamCurveArclen, ParamCurveDeriv};
+
+use super::parse::{resolve_href, SVG_NS, XLINK_NS};
+use super::AId;
+
+/// Animations grouped by their target elements.
+pub(crate) struct Animations {
+    time: f32,
+    targets: HashMap<roxmltree::NodeId, Vec<roxmltree::NodeId>>,
+    /// `mpath` elements references resolved to `path` elements.
+    motion_paths: HashMap<roxmltree::NodeId, roxmltree::NodeId>,
+}
+
+impl Animations {
+    /// Collects all animation elements in a document.
+    pub(crate) fn collect<'a, 'input: 'a>(
+        xml: &'a roxmltree::Document<'input>,
+        id_map: &HashMap<&str, roxmltree::Node<'a, 'input>>,
+        time: f32,
+    ) -> Self {
+        let mut targets: HashMap<_, Vec<_>> = HashMap::new();
+        let mut motion_paths = HashMap::new();
+        for node in xml.descendants() {
+            if node.has_tag_name((SVG_NS, "mpath")) {
+                if let Some(path) = resolve_href(node, id_map) {
+                    motion_paths.insert(node.id(), path.id());
+                }
+                continue;
+            }
+
+            if Kind::from_node(node).is_none() {
+                continue;
+            }
+
+            let has_href =
+                node.attribute((XLINK_NS, "href")).is_some() || node.attribute("href").is_some();
+            let target = if has_href {
+                resolve_href(node, id_map)
+            } else {
+                node.parent_element()
+            };
+
+            if let Some(target) = target {
+                targets.entry(target.id()).or_default().push(node.id());
+            }
+        }
+
+        Animations {
+            time,
+            targets,
+            motion_paths,
+        }
+    }
+
+    /// Samples animations of an element.
+    ///
+    /// `base` returns the current attribute value, which is used by additive animations.
+    /// Animations are applied in the document order, so later ones take precedence.
+    pub(crate) fn sample(
+        &self,
+        node: roxmltree::Node,
+        base: impl Fn(AId) -> Option<String>,
+    ) -> Vec<(AId, String)> {
+        let mut values: Vec<(AId, String)> = Vec::new();
+        let ids = match self.targets.get(&node.id()) {
+            Some(ids) => ids,
+            None => return values,
+        };
+
+        let mut motion = None;
+        for id in ids {
+            // Unwrap is safe, because IDs were collected from the same document.
+            let anim = node.document().get_node(*id).unwrap();
+            // Unwrap is safe, because only animation elements were collected.
+            let kind = Kind::from_node(anim).unwrap();
+            let progress = match resolve_progress(anim, self.time) {
+                Some(v) => v,
+                None => continue,
+            };
+
+            if kind == Kind::Motion {
+                if let Some(ts) = self.sample_motion(anim, progress) {
+                    motion = Some(ts);
+                }
+                continue;
+            }
+
+            let aid = match kind {
+                Kind::Transform => anim
+                    .attribute("attributeName")
+                    .map_or(Some(AId::Transform), AId::from_str),
+                _ => anim.attribute("attributeName").and_then(AId::from_str),
+            };
+            let aid = match aid {
+                Some(v) => v,
+                None => continue,
+            };
+
+            let base_value = values
+                .iter()
+                .find(|(a, _)| *a == aid)
+                .map(|(_, v)| v.clone())
+                .or_else(|| base(aid));
+
+            let value = match kind {
+                Kind::Set => anim.attribute("to").map(|v| v.trim().to_string()),
+                Kind::Animate => sample_animate(anim, aid, progress, base_value.as_deref()),
+                Kind::Transform => sample_transform(anim, progress, base_value.as_deref()),
+                Kind::Motion => None,
+            };
+
+            if let Some(value) = value {
+                match values.iter_mut().find(|(a, _)| *a == aid) {
+                    Some(item) => item.1 = value,
+                    None => values.push((aid, value)),
+                }
+            }
+        }
+
+        // Motion is applied on top of the `transform` attribute and its animations.
+        if let Some(motion) = motion {
+            let ts = values
+                .iter()
+                .find(|(a, _)| *a == AId::Transform)
+                .map(|(_, v)| v.clone())
+                .or_else(|| base(AId::Transform))
+                .unwrap_or_default();
+            let ts = format!("{} {}", motion, ts);
+            match values.iter_mut().find(|(a, _)| *a == AId::Transform) {
+                Some(item) => item.1 = ts,
+                None => values.push((AId::Transform, ts)),
+            }
+        }
+
+        values
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Kind {
+    Animate,
+    Set,
+    Transform,
+    Motion,
+}
+
+impl Kind {
+    fn from_node(node: roxmltree::Node) -> Option<Self> {
+        if !node.is_element() || !matches!(node.tag_name().namespace(), None | Some(SVG_NS)) {
+            return None;
+        }
+
+        match node.tag_name().name() {
+            "animate" | "animateColor" => Some(Kind::Animate),
+            "set" => Some(Kind::Set),
+            "animateTransform" => Some(Kind::Transform),
+            "animateMotion" => Some(Kind::Motion),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum CalcMode {
+    Discrete,
+    Linear,
+    Paced,
+    Spline,
+}
+
+/// An animation state at a specific time.
+#[derive(Clone, Copy, Debug)]
+struct Progress {
+    /// A position inside the simple duration, in a 0..=1 range.
+    simple: f32,
+    /// The number of completed repeat iterations.
+    iteration: u32,
+}
+
+fn resolve_progress(node: roxmltree::Node, time: f32) -> Option<Progress> {
+    // Event and sync based begin values are not supported,
+    // which means that such animations never begin.
+    let begin = node
+        .attribute("begin")
+        .unwrap_or("0s")
+        .split(';')
+        .filter_map(parse_clock_value)
+        .filter(|begin| *begin <= time)
+        .reduce(f32::max)?;
+
+    let dur = node
+        .attribute("dur")
+        .and_then(parse_clock_value)
+        .filter(|dur| *dur > 0.0);
+    let repeat_count = node.attribute("repeatCount").and_then(|v| match v.trim() {
+        "indefinite" => Some(f32::INFINITY),
+        v => v.parse::<f32>().ok().filter(|n| *n > 0.0),
+    });
+    let repeat_dur = node.attribute("repeatDur").and_then(|v| match v.trim() {
+        "indefinite" => Some(f32::INFINITY),
+        v => parse_clock_value(v),
+    });
+
+    let mut active_dur = match (dur, repeat_count, repeat_dur) {
+        (None, _, None) => f32::INFINITY,
+        (None, _, Some(repeat_dur)) => repeat_dur,
+        (Some(dur), None, None) => dur,
+        (Some(dur), repeat_count, repeat_dur) => {
+            let by_count = repeat_count.map_or(f32::INFINITY, |n| n * dur);
+            by_count.min(repeat_dur.unwrap_or(f32::INFINITY))
+        }
+    };
+
+    if let Some(end) = node
+        .attribute("end")
+        .and_then(|v| v.split(';').filter_map(parse_clock_value).reduce(f32::min))
+    {
+        active_dur = active_dur.min((end - begin).max(0.0));
+    }
+
+    let local = time - begin;
+    if local < active_dur {
+        return Some(match dur {
+            Some(dur) => {
+                let iteration = (local / dur).floor();
+                Progress {
+                    simple: (local - iteration * dur) / dur,
+                    iteration: iteration as u32,
+                }
+            }
+            None => Progress {
+                simple: 0.0,
+                iteration: 0,
+            },
+        });
+    }
+
+    if node.attribute("fill") != Some("freeze") || !active_dur.is_finite() {
+        return None;
+    }
+
+    Some(match dur {
+        Some(dur) => {
+            let iteration = (active_dur / dur).floor();
+            let rest = active_dur - iteration * dur;
+            if rest.abs() < f32::EPSILON && iteration > 0.0 {
+                Progress {
+                    simple: 1.0,
+                    iteration: iteration as u32 - 1,
+                }
+            } else {
+                Progress {
+                    simple: rest / dur,
+                    iteration: iteration as u32,
+                }
+            }
+        }
+        None => Progress {
+            simple: 0.0,
+            iteration: 0,
+        },
+    })
+}
+
+/// Parses a SMIL clock value into seconds.
+///
+/// Supports full (`01:02:03.5`), partial (`02:03`) and timecount (`3s`, `200ms`) values.
+fn parse_clock_value(text: &str) -> Option<f32> {
+    let text = text.trim();
+    let (sign, text) = match text.as_bytes().first()? {
+        b'-' => (-1.0, &text[1..]),
+        b'+' => (1.0, &text[1..]),
+        _ => (1.0, text),
+    };
+    let text = text.trim_start();
+
+    let value = if text.contains(':') {
+        let mut value = 0.0;
+        for part in text.split(':') {
+            value = value * 60.0 + part.parse::<f32>().ok()?;
+        }
+        value
+    } else {
+        let split = text
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(text.len());
+        let (number, unit) = text.split_at(split);
+        let number = number.parse::<f32>().ok()?;
+        match unit {
+            "" | "s" => number,
+            "ms" => number / 1000.0,
+            "min" => number * 60.0,
+            "h" => number * 3600.0,
+            _ => return None,
+        }
+    };
+
+    Some(sign * value)
+}
+
+fn calc_mode(node: roxmltree::Node, default: CalcMode) -> CalcMode {
+    match node.attribute("calcMode") {
+        Some("discrete") => CalcMode::Discrete,
+        Some("linear") => CalcMode::Linear,
+        Some("paced") => CalcMode::Paced,
+        Some("spline") => CalcMode::Spline,
+        _ => default,
+    }
+}
+
+fn parse_list(text: &str) -> Vec<&str> {
+    text.split(';')
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .collect()
+}
+
+fn parse_numbers(text: &str) -> Vec<f32> {
+    text.split(|c: char| c.is_ascii_whitespace() || c == ',')
+        .filter(|v| !v.is_empty())
+        .filter_map(|v| v.parse().ok())
+        .collect()
+}
+
+/// Resolves animation keyframe values.
+///
+/// Returns values and a flag indicating that this is a `by` animation,
+/// which is always additive.
+fn keyframes(node: roxmltree::Node, is_color: bool, base: Option<&str>) -> (Vec<Value>, bool) {
+    let parse = |v: &str| Value::parse(v, is_color);
+
+    if let Some(values) = node.attribute("values") {
+        return (parse_list(values).into_iter().map(parse).collect(), false);
+    }
+
+    let from = node.attribute("from").map(parse);
+    let to = node.attribute("to").map(parse);
+    let by = node.attribute("by").map(parse);
+    match (from, to, by) {
+        (Some(from), Some(to), _) => (vec![from, to], false),
+        (Some(from), None, Some(by)) => {
+            let to = from.add(&by, 1.0);
+            (vec![from, to], false)
+        }
+        (None, Some(to), _) => match base {
+            Some(base) => (vec![parse(base), to], false),
+            None => (vec![to], false),
+        },
+        (None, None, Some(by)) => (vec![by.zero(), by], true),
+        _ => (Vec::new(), false),
+    }
+}
+
+fn sample_animate(
+    node: roxmltree::Node,
+    aid: AId,
+    progress: Progress,
+    base: Option<&str>,
+) -> Option<String> {
+    let is_color = matches!(
+        aid,
+        AId::Color
+            | AId::Fill
+            | AId::Stroke
+            | AId::StopColor
+            | AId::FloodColor
+            | AId::LightingColor
+    );
+
+    let (values, is_by) = keyframes(node, is_color, base);
+    let value = interpolate_keyframes(node, &values, progress)?;
+    let value = apply_additive(node, value, &values, progress, is_by, base, is_color);
+    Some(value.to_string())
+}
+
+fn sample_transform(
+    node: roxmltree::Node,
+    progress: Progress,
+    base: Option<&str>,
+) -> Option<String> {
+    let kind = match node.attribute("type").unwrap_or("translate") {
+        v @ ("translate" | "scale" | "rotate" | "skewX" | "skewY") => v,
+        _ => return None,
+    };
+
+    let (values, is_by) = keyframes(node, false, None);
+    let value = interpolate_keyframes(node, &values, progress)?;
+    let value = apply_accumulate(node, value, &values, progress);
+    let value = format!("{}({})", kind, value);
+
+    let additive = is_by || node.attribute("additive") == Some("sum");
+    match base {
+        Some(base) if additive => Some(format!("{} {}", base, value)),
+        _ => Some(value),
+    }
+}
+
+fn apply_accumulate(
+    node: roxmltree::Node,
+    value: Value,
+    values: &[Value],
+    progress: Progress,
+) -> Value {
+    if node.attribute("accumulate") != Some("sum") || progress.iteration == 0 {
+        return value;
+    }
+
+    match values.last() {
+        Some(last) => value.add(last, progress.iteration as f32),
+        None => value,
+    }
+}
+
+fn apply_additive(
+    node: roxmltree::Node,
+    value: Value,
+    values: &[Value],
+    progress: Progress,
+    is_by: bool,
+    base: Option<&str>,
+    is_color: bool,
+) -> Value {
+    // `to` animations are never additive.
+    let is_to = node.attribute("values").is_none()
+        && node.attribute("from").is_none()
+        && node.attribute("to").is_some();
+    if is_to {
+        return value;
+    }
+
+    let value = apply_accumulate(node, value, values, progress);
+    if !is_by && node.attribute("additive") != Some("sum") {
+        return value;
+    }
+
+    match base {
+        Some(base) => Value::parse(base, is_color).add(&value, 1.0),
+        None => value,
+    }
+}
+
+fn interpolate_keyframes(
+    node: roxmltree::Node,
+    values: &[Value],
+    progress: Progress,
+) -> Option<Value> {
+    if values.len() < 2 {
+        return values.first().cloned();
+    }
+
+    let mut mode = calc_mode(node, CalcMode::Linear);
+    if mode != CalcMode::Discrete && !values.windows(2).all(|w| w[0].is_compatible(&w[1])) {
+        // Values that cannot be interpolated are switched in the middle of an interval.
+        mode = CalcMode::Discrete;
+    }
+
+    let key_times = match mode {
+        CalcMode::Paced => {
+            let distances: Vec<f32> = values.windows(2).map(|w| w[0].distance(&w[1])).collect();
+            paced_key_times(&distances)
+        }
+        _ => node
+            .attribute("keyTimes")
+            .map(|v| {
+                parse_list(v)
+                    .iter()
+                    .filter_map(|t| t.parse().ok())
+                    .collect()
+            })
+            .filter(|times: &Vec<f32>| is_valid_key_times(times, values.len(), mode)),
+    };
+
+    if mode == CalcMode::Discrete {
+        let idx = match key_times {
+            Some(ref times) => times
+                .iter()
+                .rposition(|t| *t <= progress.simple)
+                .unwrap_or(0),
+            None => ((progress.simple * values.len() as f32) as usize).min(values.len() - 1),
+        };
+        return Some(values[idx].clone());
+    }
+
+    let (idx, mut t) = find_interval(key_times.as_deref(), values.len(), progress.simple);
+    if mode == CalcMode::Spline {
+        let splines: Vec<Vec<f32>> = node
+            .attribute("keySplines")
+            .map(|v| parse_list(v).into_iter().map(parse_numbers).collect())
+            .unwrap_or_default();
+        if splines.len() == values.len() - 1 && splines.iter().all(|s| s.len() == 4) {
+            let s = &splines[idx];
+            t = cubic_bezier(s[0], s[1], s[2], s[3], t);
+        }
+    }
+
+    Some(values[idx].interpolate(&values[idx + 1], t))
+}
+
+fn is_valid_key_times(times: &[f32], count: usize, mode: CalcMode) -> bool {
+    times.len() == count
+        && times.first() == Some(&0.0)
+        && (mode == CalcMode::Discrete || times.last() == Some(&1.0))
+        && times.windows(2).all(|w| w[0] <= w[1])
+}
+
+fn paced_key_times(distances: &[f32]) -> Option<Vec<f32>> {
+    let total: f32 = distances.iter().sum();
+    if total <= 0.0 {
+        return None;
+    }
+
+    let mut times = vec![0.0];
+    let mut sum = 0.0;
+    for d in distances {
+        sum += d;
+        times.push(sum / total);
+    }
+
+    Some(times)
+}
+
+/// Finds an interval between values and a position inside it.
+fn find_interval(key_times: Option<&[f32]>, count: usize, p: f32) -> (usize, f32) {
+    let last = count - 2;
+    match key_times {
+        Some(times) => {
+            let idx = times
+                .windows(2)
+                .position(|w| p < w[1])
+                .unwrap_or(last)
+                .min(last);
+            let len = times[idx + 1] - times[idx];
+            let t = if len > 0.0 {
+                (p - times[idx]) / len
+            } else {
+                1.0
+            };
+            (idx, t.clamp(0.0, 1.0))
+        }
+        None => {
+            let f = p * (count - 1) as f32;
+            let idx = (f as usize).min(last);
+            (idx, (f - idx as f32).clamp(0.0, 1.0))
+        }
+    }
+}
+
+/// Evaluates a `keySplines` timing function.
+fn cubic_bezier(x1: f32, y1: f32, x2: f32, y2: f32, x: f32) -> f32 {
+    let bezier = |a: f32, b: f32, t: f32| {
+        let mt = 1.0 - t;
+        3.0 * mt * mt * t * a + 3.0 * mt * t * t * b + t * t * t
+    };
+
+    // The curve is monotonic by x, so a bisection is enough.
+    let (mut lo, mut hi) = (0.0, 1.0);
+    let mut t = x;
+    for _ in 0..32 {
+        let v = bezier(x1, x2, t);
+        if (v - x).abs() < 1e-6 {
+            break;
+        }
+
+        if v < x {
+            lo = t;
+        } else {
+            hi = t;
+        }
+        t = (lo + hi) / 2.0;
+    }
+
+    bezier(y1, y2, t)
+}
+
+/// An animation value.
+#[derive(Clone, Debug)]
+enum Value {
+    Color(svgtypes::Color),
+    /// Numbers surrounded by text, like `10px` or `M 10 20 L 30 40`.
+    ///
+    /// The number of text parts is always the number of numbers plus one.
+    Numbers(Vec<String>, Vec<f32>),
+}
+
+impl Value {
+    fn parse(text: &str, is_color: bool) -> Self {
+        let text = text.trim();
+        if is_color {
+            if let Ok(color) = svgtypes::Color::from_str(text) {
+                return Value::Color(color);
+            }
+        }
+
+        let (parts, numbers) = split_numbers(text);
+        Value::Numbers(parts, numbers)
+    }
+
+    fn is_compatible(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Value::Color(_), Value::Color(_)) => true,
+            (Value::Numbers(parts1, _), Value::Numbers(parts2, _)) => {
+                // References are never interpolated.
+                parts1 == parts2 && !parts1.iter().any(|p| p.contains('#'))
+            }
+            _ => false,
+        }
+    }
+
+    fn zero(&self) -> Value {
+        match self {
+            Value::Color(_) => Value::Color(svgtypes::Color::new_rgba(0, 0, 0, 0)),
+            Value::Numbers(parts, numbers) => {
+                Value::Numbers(parts.clone(), vec![0.0; numbers.len()])
+            }
+        }
+    }
+
+    fn distance(&self, other: &Value) -> f32 {
+        match (self, other) {
+            (Value::Color(c1), Value::Color(c2)) => {
+                let d = |a: u8, b: u8| (a as f32 - b as f32).powi(2);
+                (d(c1.red, c2.red) + d(c1.green, c2.green) + d(c1.blue, c2.blue)).sqrt()
+            }
+            (Value::Numbers(_, n1), Value::Numbers(_, n2)) => n1
+                .iter()
+                .zip(n2)
+                .map(|(a, b)| (a - b).powi(2))
+                .sum::<f32>()
+                .sqrt(),
+            _ => 0.0,
+        }
+    }
+
+    fn interpolate(&self, other: &Value, t: f32) -> Value {
+        if !self.is_compatible(other) {
+            return if t < 0.5 { self.clone() } else { other.clone() };
+        }
+
+        match (self, other) {
+            (Value::Color(c1), Value::Color(c2)) => {
+                let f = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+                Value::Color(svgtypes::Color::new_rgba(
+                    f(c1.red, c2.red),
+                    f(c1.green, c2.green),
+                    f(c1.blue, c2.blue),
+                    f(c1.alpha, c2.alpha),
+                ))
+            }
+            (Value::Numbers(parts, n1), Value::Numbers(_, n2)) => {
+                let numbers = n1.iter().zip(n2).map(|(a, b)| a + (b - a) * t).collect();
+                Value::Numbers(parts.clone(), numbers)
+            }
+            _ => other.clone(),
+        }
+    }
+
+    /// Adds `other` multiplied by `k`.
+    ///
+    /// Incompatible values are not added. `other` is returned instead.
+    fn add(&self, other: &Value, k: f32) -> Value {
+        if !self.is_compatible(other) {
+            return other.clone();
+        }
+
+        match (self, other) {
+            (Value::Color(c1), Value::Color(c2)) => {
+                let f = |a: u8, b: u8| (a as f32 + b as f32 * k).clamp(0.0, 255.0) as u8;
+                Value::Color(svgtypes::Color::new_rgba(
+                    f(c1.red, c2.red),
+                    f(c1.green, c2.green),
+                    f(c1.blue, c2.blue),
+                    c1.alpha.max(c2.alpha),
+                ))
+            }
+            (Value::Numbers(parts, n1), Value::Numbers(_, n2)) => {
+                let numbers = n1.iter().zip(n2).map(|(a, b)| a + b * k).collect();
+                Value::Numbers(parts.clone(), numbers)
+            }
+            _ => other.clone(),
+        }
+    }
+}
+
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Value::Color(c) => {
+                if c.alpha == 255 {
+                    write!(f, "#{:02x}{:02x}{:02x}", c.red, c.green, c.blue)
+                } else {
+                    let alpha = c.alpha as f32 / 255.0;
+                    write!(f, "rgba({},{},{},{})", c.red, c.green, c.blue, alpha)
+                }
+            }
+            Value::Numbers(parts, numbers) => {
+                for (part, number) in parts.iter().zip(numbers) {
+                    write!(f, "{}{}", part, number)?;
+                }
+                // Unwrap is safe, because there is always one more part.
+                write!(f, "{}", parts.last().unwrap())
+            }
+        }
+    }
+}
+
+/// Splits a string into numbers and text between them.
+fn split_numbers(text: &str) -> (Vec<String>, Vec<f32>) {
+    let mut parts = Vec::new();
+    let mut numbers = Vec::new();
+
+    let bytes = text.as_bytes();
+    let mut part_start = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        let c = bytes[i];
+        let next = bytes.get(i + 1).copied().unwrap_or(0);
+        let starts_number = c.is_ascii_digit()
+            || (c == b'.' && next.is_ascii_digit())
+            || ((c == b'-' || c == b'+') && (next.is_ascii_digit() || next == b'.'));
+        if !starts_number {
+            i += 1;
+            continue;
+        }
+
+        let mut end = i + 1;
+        let mut has_dot = c == b'.';
+        while end < bytes.len() {
+            let c = bytes[end];
+            if c.is_ascii_digit() {
+                end += 1;
+            } else if c == b'.' && !has_dot {
+                has_dot = true;
+                end += 1;
+            } else if (c == b'e' || c == b'E')
+                && bytes
+                    .get(end + 1)
+                    .is_some_and(|n| n.is_ascii_digit() || *n == b'-' || *n == b'+')
+            {
+                end += 2;
+                while end < bytes.len() && bytes[end].is_ascii_digit() {
+                    end += 1;
+                }
+                break;
+            } else {
+                break;
+            }
+        }
+
+        if let Ok(n) = text[i..end].parse::<f32>() {
+            parts.push(text[part_start..i].to_string());
+            numbers.push(n);
+            part_start = end;
+        }
+        i = end;
+    }
+
+    parts.push(text[part_start..].to_string());
+    (parts, numbers)
+}
+
+impl Animations {
+    fn sample_motion(&self, node: roxmltree::Node, progress: Progress) -> Option<String> {
+        let path = self.motion_path(node)?;
+        let segments: Vec<_> = path.segments().collect();
+        let lengths: Vec<f64> = segments.iter().map(|s| s.arclen(0.01)).collect();
+        let total: f64 = lengths.iter().sum();
+
+        let key_points: Option<Vec<f32>> = node.attribute("keyPoints").map(|v| {
+            parse_list(v)
+                .iter()
+                .filter_map(|p| p.parse().ok())
+                .collect()
+        });
+        let key_times: Option<Vec<f32>> = node.attribute("keyTimes").map(|v| {
+            parse_list(v)
+                .iter()
+                .filter_map(|t| t.parse().ok())
+                .collect()
+        });
+
+        let mode = calc_mode(node, CalcMode::Paced);
+        let mut distance = progress.simple;
+        if let (Some(points), Some(times)) = (key_points, key_times) {
+            if points.len() >= 2 && is_valid_key_times(&times, points.len(), mode) {
+                if mode == CalcMode::Discrete {
+                    let idx = times
+                        .iter()
+                        .rposition(|t| *t <= progress.simple)
+                        .unwrap_or(0);
+                    distance = points[idx];
+                } else {
+                    let (idx, t) = find_interval(Some(&times), points.len(), progress.simple);
+                    distance = points[idx] + (points[idx + 1] - points[idx]) * t;
+                }
+            }
+        }
+        let mut distance = distance.clamp(0.0, 1.0) as f64 * total;
+
+        // Find a segment at the required distance.
+        let mut point = path.elements().first().and_then(|e| e.end_point())?;
+        let mut angle = 0.0;
+        for (segment, len) in segments.iter().zip(&lengths) {
+            let t = if distance <= *len {
+                segment.inv_arclen(distance, 0.01)
+            } else {
+                1.0
+            };
+            point = segment.eval(t);
+            let deriv = match segment {
+                kurbo::PathSeg::Line(line) => line.deriv().eval(t),
+                kurbo::PathSeg::Quad(quad) => quad.deriv().eval(t),
+                kurbo::PathSeg::Cubic(cubic) => cubic.deriv().eval(t),
+            };
+            angle = deriv.y.atan2(deriv.x).to_degrees();
+
+            if distance <= *len {
+                break;
+            }
+            distance -= len;
+        }
+
+        let rotate = match node.attribute("rotate").map(str::trim) {
+            Some("auto") => angle,
+            Some("auto-reverse") => angle + 180.0,
+            Some(v) => v.parse().unwrap_or(0.0),
+            None => 0.0,
+        };
+
+        Some(format!(
+            "translate({} {}) rotate({})",
+            point.x as f32, point.y as f32, rotate as f32
+        ))
+    }
+
+    fn motion_path(&self, node: roxmltree::Node) -> Option<kurbo::BezPath> {
+        // `mpath` takes precedence over all other attributes.
+        if let Some(mpath) = node.children().find(|n| n.has_tag_name((SVG_NS, "mpath"))) {
+            let path = node
+                .document()
+                .get_node(*self.motion_paths.get(&mpath.id())?)?;
+            return kurbo::BezPath::from_svg(path.attribute("d")?).ok();
+        }
+
+        if let Some(path) = node.attribute("path") {
+            return kurbo::BezPath::from_svg(path).ok();
+        }
+
+        let points: Vec<Vec<f32>> = match node.attribute("values") {
+            Some(values) => parse_list(values).into_iter().map(parse_numbers).collect(),
+            None => {
+                let from = node.attribute("from").map(parse_numbers);
+                let to = node.attribute("to").map(parse_numbers);
+                let by = node.attribute("by").map(parse_numbers);
+                match (from, to, by) {
+                    (from, Some(to), _) => vec![from.unwrap_or(vec![0.0, 0.0]), to],
+                    (from, None, Some(by)) => {
+                        let from = from.unwrap_or(vec![0.0, 0.0]);
+                        let to = from.iter().zip(&by).map(|(a, b)| a + b).collect();
+                        vec![from, to]
+                    }
+                    _ => return None,
+                }
+            }
+        };
+
+        let mut path = kurbo::BezPath::new();
+        for p in points.iter().filter(|p| p.len() == 2) {
+            let p = kurbo::Point::new(p[0] as f64, p[1] as f64);
+            if path.elements().is_empty() {
+                path.move_to(p);
+            } else {
+                path.line_to(p);
+            }
+        }
+
+        if path.elements().is_empty() {
+            return None;
+        }
+
+        Some(path)
+    }
+}
@@ -0,0 +1,144 @@
+// Copyright 2025 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! SVG fonts collection.
+//!
+//! `font` elements are not part of the tree. Instead, they are collected
+//! during parsing and converted into regular fonts before text conversion.
+
+use std::str::FromStr;
+
+use super::parse::SVG_NS;
+
+/// An SVG font defined by a `font` element.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SvgFont {
+    /// The `font-family` of the `font-face` child.
+    pub(crate) family: Option<String>,
+    pub(crate) font_style: Option<String>,
+    pub(crate) font_weight: Option<String>,
+    pub(crate) font_stretch: Option<String>,
+    pub(crate) units_per_em: Option<f32>,
+    pub(crate) ascent: Option<f32>,
+    pub(crate) descent: Option<f32>,
+    pub(crate) x_height: Option<f32>,
+    pub(crate) cap_height: Option<f32>,
+    pub(crate) underline_position: Option<f32>,
+    pub(crate) underline_thickness: Option<f32>,
+    pub(crate) strikethrough_position: Option<f32>,
+    pub(crate) strikethrough_thickness: Option<f32>,
+    /// The default advance of glyphs.
+    pub(crate) horiz_adv_x: f32,
+    pub(crate) horiz_origin_x: f32,
+    pub(crate) horiz_origin_y: f32,
+    pub(crate) missing_glyph: Option<SvgGlyph>,
+    /// Glyphs in the document order.
+    pub(crate) glyphs: Vec<SvgGlyph>,
+    /// `hkern` elements in the document order.
+    pub(crate) kerning: Vec<SvgKerning>,
+}
+
+/// A `glyph` or a `missing-glyph` element.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SvgGlyph {
+    pub(crate) unicode: Option<String>,
+    pub(crate) name: Option<String>,
+    /// Path data of the glyph outline.
+    pub(crate) path: Option<String>,
+    pub(crate) horiz_adv_x: Option<f32>,
+}
+
+/// An `hkern` element.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SvgKerning {
+    pub(crate) u1: Option<String>,
+    pub(crate) g1: Option<String>,
+    pub(crate) u2: Option<String>,
+    pub(crate) g2: Option<String>,
+    pub(crate) k: f32,
+}
+
+impl SvgFont {
+    /// Collects all `font` elements in a document.
+    pub(crate) fn collect(xml: &roxmltree::Document) -> Vec<Self> {
+        xml.descendants()
+            .filter(|n| is_element(*n, "font"))
+            .map(Self::parse)
+            .collect()
+    }
+
+    fn parse(node: roxmltree::Node) -> Self {
+        let mut font = SvgFont {
+            horiz_adv_x: number(node, "horiz-adv-x").unwrap_or(0.0),
+            horiz_origin_x: number(node, "horiz-origin-x").unwrap_or(0.0),
+            horiz_origin_y: number(node, "horiz-origin-y").unwrap_or(0.0),
+            ..SvgFont::default()
+        };
+
+        for child in node.children() {
+            if is_element(child, "font-face") {
+                // Only the first `font-face` is used.
+                if font.family.is_some() {
+                    continue;
+                }
+
+                let string = |name| child.attribute(name).map(|s: &str| s.trim().to_string());
+                font.family = string("font-family");
+                font.font_style = string("font-style");
+                font.font_weight = string("font-weight");
+                font.font_stretch = string("font-stretch");
+                font.units_per_em = number(child, "units-per-em");
+                font.ascent = number(child, "ascent");
+                font.descent = number(child, "descent");
+                font.x_height = number(child, "x-height");
+                font.cap_height = number(child, "cap-height");
+                font.underline_position = number(child, "underline-position");
+                font.underline_thickness = number(child, "underline-thickness");
+                font.strikethrough_position = number(child, "strikethrough-position");
+                font.strikethrough_thickness = number(child, "strikethrough-thickness");
+            } else if is_element(child, "missing-glyph") {
+                if font.missing_glyph.is_none() {
+                    font.missing_glyph = Some(SvgGlyph::parse(child));
+                }
+            } else if is_element(child, "glyph") {
+                font.glyphs.push(SvgGlyph::parse(child));
+            } else if is_element(child, "hkern") {
+                font.kerning.push(SvgKerning {
+                    u1: child.attribute("u1").map(String::from),
+                    g1: child.attribute("g1").map(String::from),
+                    u2: child.attribute("u2").map(String::from),
+                    g2: child.attribute("g2").map(String::from),
+                    k: number(child, "k").unwrap_or(0.0),
+                });
+            }
+        }
+
+        font
+    }
+}
+
+impl SvgGlyph {
+    fn parse(node: roxmltree::Node) -> Self {
+        SvgGlyph {
+            unicode: node
+                .attribute("unicode")
+                .filter(|s| !s.is_empty())
+                .map(String::from),
+            name: node.attribute("glyph-name").map(String::from),
+            path: node.attribute("d").map(String::from),
+            horiz_adv_x: number(node, "horiz-adv-x"),
+        }
+    }
+}
+
+fn is_element(node: roxmltree::Node, name: &str) -> bool {
+    node.is_element()
+        && node.tag_name().name() == name
+        && matches!(node.tag_name().namespace(), None | Some(SVG_NS))
+}
+
+fn number(node: roxmltree::Node, name: &str) -> Option<f32> {
+    let value = node.attribute(name)?;
+    let n = svgtypes::Number::from_str(value).ok()?.0 as f32;
+    n.is_finite().then_some(n)
+}
@@ -1,10 +1,14 @@
 // Copyright 2021 the Resvg Authors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-use std::collections::HashMap;
+use std::cell::{OnceCell, RefCell};
+use std::collections::{HashMap, HashSet};
 use std::num::NonZeroU32;
 use std::str::FromStr;
 
+mod animation;
+#[cfg(feature = "text")]
+mod font;
 #[rustfmt::skip] mod names;
 mod parse;
 mod text;
@@ -12,9 +16,11 @@ mod text;
 use tiny_skia_path::Transform;
 
 use crate::{
-    BlendMode, ImageRendering, Opacity, ShapeRendering, SpreadMethod, TextRendering, Units,
-    Visibility,
+    BlendMode, Diagnostic, DiagnosticCode, ImageRendering, Opacity, Severity, ShapeRendering,
+    SpreadMethod, TextRendering, Units, VectorEffect, Visibility,
 };
+#[cfg(feature = "text")]
+pub(crate) use font::{SvgFont, SvgGlyph, SvgKerning};
 pub use names::{AId, EId};
 
 /// An SVG tree container.
@@ -22,9 +28,17 @@ pub use names::{AId, EId};
 /// Contains only element and text nodes.
 /// Text nodes are present only inside the `text` element.
 pub struct Document<'input> {
+    text: &'input str,
     nodes: Vec<NodeData>,
     attrs: Vec<Attribute<'input>>,
     links: HashMap<String, NodeId>,
+    diagnostics: RefCell<Diagnostics>,
+    text_index: OnceCell<Vec<(usize, roxmltree::TextPos)>>,
+    animations: Option<animation::Animations>,
+    #[cfg(feature = "text")]
+    font_faces: Vec<String>,
+    #[cfg(feature = "text")]
+    svg_fonts: Vec<SvgFont>,
 }
 
 impl<'input> Document<'input> {
@@ -71,6 +85,81 @@ impl<'input> Document<'input> {
             doc: self,
         }
     }
+
+    /// Returns declaration blocks of all `@font-face` rules.
+    #[cfg(feature = "text")]
+    pub fn font_faces(&self) -> &[String] {
+        &self.font_faces
+    }
+
+    /// Returns all SVG fonts defined via `font` elements.
+    #[cfg(feature = "text")]
+    pub fn svg_fonts(&self) -> &[SvgFont] {
+        &self.svg_fonts
+    }
+
+    /// Returns all problems reported during parsing.
+    pub fn into_diagnostics(self) -> Vec<Diagnostic> {
+        self.diagnostics.into_inner().list
+    }
+
+    pub(crate) fn report(&self, diagnostic: Diagnostic) {
+        let mut diagnostics = self.diagnostics.borrow_mut();
+        // The same attribute can be resolved multiple times.
+        if !diagnostics.seen.insert(diagnostic.clone()) {
+            return;
+        }
+
+        log::warn!("{}", diagnostic.message);
+        diagnostics.list.push(diagnostic);
+    }
+
+    /// Converts a byte position into a row and a column. Both starting from 1.
+    fn text_pos_at(&self, pos: usize) -> roxmltree::TextPos {
+        let index = self.text_index.get_or_init(|| build_text_index(self.text));
+        // The first checkpoint is always at 0.
+        let idx = index.partition_point(|(start, _)| *start <= pos) - 1;
+        let (start, start_pos) = index[idx];
+        let col = start_pos.col as usize + self.text[start..pos].chars().count();
+        roxmltree::TextPos::new(start_pos.row, col as u32)
+    }
+}
+
+/// Reported diagnostics in the reporting order.
+#[derive(Default)]
+struct Diagnostics {
+    list: Vec<Diagnostic>,
+    seen: HashSet<Diagnostic>,
+}
+
+/// Maximum distance in bytes between two text index checkpoints.
+const TEXT_INDEX_STEP: usize = 1024;
+
+/// Builds a list of byte positions and their rows and columns.
+///
+/// Contains each line start, as well as intermediate positions on long lines,
+/// so a position lookup never has to scan more than a few bytes.
+fn build_text_index(text: &str) -> Vec<(usize, roxmltree::TextPos)> {
+    let mut index = vec![(0, roxmltree::TextPos::new(1, 1))];
+    let (mut row, mut col) = (1, 1);
+    let mut last = 0;
+    for (i, c) in text.char_indices() {
+        if i - last >= TEXT_INDEX_STEP {
+            index.push((i, roxmltree::TextPos::new(row, col)));
+            last = i;
+        }
+
+        if c == '\n' {
+            row += 1;
+            col = 1;
+            index.push((i + 1, roxmltree::TextPos::new(row, col)));
+            last = i + 1;
+        } else {
+            col += 1;
+        }
+    }
+
+    index
 }
 
 impl std::fmt::Debug for Document<'_> {
@@ -186,6 +275,8 @@ pub(crate) enum NodeKind {
     Element {
         tag_name: EId,
         attributes: ShortRange,
+        /// Byte position in the SVG source.
+        pos: u32,
     },
     Text(String),
 }
@@ -275,6 +366,35 @@ impl<'a, 'input: 'a> SvgNode<'a, 'input> {
         self.attribute(AId::Id).unwrap_or("")
     }
 
+    /// Reports a problem that was resolved by using a fallback.
+    ///
+    /// The message is logged as well.
+    pub fn warn(&self, code: DiagnosticCode, message: String) {
+        self.report(Severity::Warning, code, message);
+    }
+
+    /// Reports a problem that caused the current element or its part to be skipped.
+    ///
+    /// The message is logged as well.
+    pub fn skip(&self, code: DiagnosticCode, message: String) {
+        self.report(Severity::Error, code, message);
+    }
+
+    fn report(&self, severity: Severity, code: DiagnosticCode, message: String) {
+        let pos = match self.d.kind {
+            NodeKind::Element { pos, .. } => Some(self.doc.text_pos_at(pos as usize)),
+            _ => None,
+        };
+
+        self.doc.report(Diagnostic {
+            code,
+            severity,
+            element_id: self.element_id().to_string(),
+            pos,
+            message,
+        });
+    }
+
     /// Returns an attribute value.
     pub fn attribute<T: FromValue<'a, 'input>>(&self, aid: AId) -> Option<T> {
         let value = self
@@ -285,8 +405,10 @@ impl<'a, 'input: 'a> SvgNode<'a, 'input> {
         match T::parse(*self, aid, value) {
             Some(v) => Some(v),
             None => {
-                // TODO: show position in XML
-                log::warn!("Failed to parse {} value: '{}'.", aid, value);
+                self.warn(
+                    DiagnosticCode::InvalidValue,
+                    format!("Failed to parse {} value: '{}'.", aid, value),
+                );
                 None
             }
         }
@@ -618,9 +740,12 @@ impl<'a, 'input: 'a> Iterator for HrefIter<'a, 'input> {
 
         if let Some(link) = self.doc.get(self.curr).node_attribute(AId::Href) {
             if link.id() == self.curr || link.id() == self.origin {
-                log::warn!(
-                    "Element '#{}' cannot reference itself via 'xlink:href'.",
-                    self.doc.get(self.origin).element_id()
+                self.doc.get(self.origin).skip(
+                    DiagnosticCode::InvalidReference,
+                    format!(
+                        "Element '#{}' cannot reference itself via 'xlink:href'.",
+                        self.doc.get(self.origin).element_id()
+                    ),
                 );
                 self.is_finished = true;
                 return None;
@@ -656,7 +781,10 @@ impl EId {
     /// Checks if this is a
     /// [gradient element](https://www.w3.org/TR/SVG11/intro.html#TermGradientElement).
     pub fn is_gradient(&self) -> bool {
-        matches!(self, EId::LinearGradient | EId::RadialGradient)
+        matches!(
+            self,
+            EId::LinearGradient | EId::RadialGradient | EId::ConicGradient
+        )
     }
 
     /// Checks if this is a
@@ -664,7 +792,12 @@ impl EId {
     pub fn is_paint_server(&self) -> bool {
         matches!(
             self,
-            EId::LinearGradient | EId::RadialGradient | EId::Pattern
+            EId::LinearGradient
+                | EId::RadialGradient
+                | EId::ConicGradient
+                | EId::Pattern
+                | EId::Meshgradient
+                | EId::Hatch
         )
     }
 }
@@ -692,29 +825,48 @@ impl AId {
                 | AId::FloodColor
                 | AId::FloodOpacity
                 | AId::FontFamily
+                | AId::FontFeatureSettings
                 | AId::FontKerning // technically not presentation
                 | AId::FontSize
                 | AId::FontSizeAdjust
                 | AId::FontStretch
                 | AId::FontStyle
                 | AId::FontVariant
+                | AId::FontVariantCaps
+                | AId::FontVariantEastAsian
+                | AId::FontVariantLigatures
+                | AId::FontVariantNumeric
+                | AId::FontVariantPosition
+                | AId::FontVariationSettings
                 | AId::FontWeight
                 | AId::GlyphOrientationHorizontal
                 | AId::GlyphOrientationVertical
                 | AId::ImageRendering
+                | AId::InlineSize
                 | AId::Isolation // technically not presentation
                 | AId::LetterSpacing
                 | AId::LightingColor
+                | AId::LineHeight
                 | AId::MarkerEnd
                 | AId::MarkerMid
                 | AId::MarkerStart
                 | AId::Mask
+                | AId::MaskComposite
+                | AId::MaskImage
+                | AId::MaskMode
+                | AId::MaskPosition
+                | AId::MaskRepeat
+                | AId::MaskSize
                 | AId::MaskType
                 | AId::MixBlendMode // technically not presentation
                 | AId::Opacity
                 | AId::Overflow
                 | AId::PaintOrder
+                | AId::ShapeInside
+                | AId::ShapeMargin
+                | AId::ShapePadding
                 | AId::ShapeRendering
+                | AId::ShapeSubtract
                 | AId::StopColor
                 | AId::StopOpacity
                 | AId::Stroke
@@ -727,8 +879,17 @@ impl AId {
                 | AId::StrokeWidth
                 | AId::TextAnchor
                 | AId::TextDecoration
+                | AId::TextDecorationColor
+                | AId::TextDecorationFill
+                | AId::TextDecorationLine
+                | AId::TextDecorationSkipInk
+                | AId::TextDecorationStroke
+                | AId::TextDecorationStyle
+                | AId::TextOrientation
                 | AId::TextOverflow
                 | AId::TextRendering
+                | AId::TextTransform
+                | AId::TextUnderlinePosition
                 | AId::Transform
                 | AId::TransformOrigin
                 | AId::UnicodeBidi
@@ -768,19 +929,34 @@ impl AId {
                 | AId::FloodColor
                 | AId::FloodOpacity
                 | AId::FontFamily
+                | AId::FontFeatureSettings
                 | AId::FontKerning
                 | AId::FontSize
                 | AId::FontStretch
                 | AId::FontStyle
                 | AId::FontVariant
+                | AId::FontVariantCaps
+                | AId::FontVariantEastAsian
+                | AId::FontVariantLigatures
+                | AId::FontVariantNumeric
+                | AId::FontVariantPosition
+                | AId::FontVariationSettings
                 | AId::FontWeight
+                | AId::GlyphOrientationVertical
                 | AId::ImageRendering
                 | AId::Kerning
                 | AId::LetterSpacing
+                | AId::LineHeight
                 | AId::MarkerEnd
                 | AId::MarkerMid
                 | AId::MarkerStart
                 | AId::Mask
+                | AId::MaskComposite
+                | AId::MaskImage
+                | AId::MaskMode
+                | AId::MaskPosition
+                | AId::MaskRepeat
+                | AId::MaskSize
                 | AId::Opacity
                 | AId::Overflow
                 | AId::ShapeRendering
@@ -796,8 +972,20 @@ impl AId {
                 | AId::StrokeWidth
                 | AId::TextAnchor
                 | AId::TextDecoration
+                | AId::TextDecorationColor
+                | AId::TextDecorationFill
+                | AId::TextDecorationLine
+                | AId::TextDecorationSkipInk
+                | AId::TextDecorationStroke
+                | AId::TextDecorationStyle
+                | AId::TextOrientation
                 | AId::TextRendering
+                | AId::TextTransform
+                | AId::TextUnderlinePosition
+                | AId::UnicodeBidi
+                | AId::VectorEffect
                 | AId::Visibility
+                | AId::WhiteSpace
                 | AId::WordSpacing
                 | AId::WritingMode
         )
@@ -815,15 +1003,33 @@ fn is_non_inheritable(id: AId) -> bool {
             | AId::Filter
             | AId::FloodColor
             | AId::FloodOpacity
+            | AId::InlineSize
             | AId::Mask
+            | AId::MaskComposite
+            | AId::MaskImage
+            | AId::MaskMode
+            | AId::MaskPosition
+            | AId::MaskRepeat
+            | AId::MaskSize
             | AId::Opacity
             | AId::Overflow
             | AId::LightingColor
+            | AId::ShapeInside
+            | AId::ShapeMargin
+            | AId::ShapePadding
+            | AId::ShapeSubtract
             | AId::StopColor
             | AId::StopOpacity
             | AId::TextDecoration
+            | AId::TextDecorationColor
+            | AId::TextDecorationFill
+            | AId::TextDecorationLine
+            | AId::TextDecorationStroke
+            | AId::TextDecorationStyle
             | AId::Transform
             | AId::TransformOrigin
+            | AId::UnicodeBidi
+            | AId::VectorEffect
     )
 }
 
@@ -1005,6 +1211,16 @@ impl<'a, 'input: 'a> FromValue<'a, 'input> for ShapeRendering {
     }
 }
 
+impl<'a, 'input: 'a> FromValue<'a, 'input> for VectorEffect {
+    fn parse(_: SvgNode, _: AId, value: &str) -> Option<Self> {
+        match value {
+            "none" => Some(VectorEffect::None),
+            "non-scaling-stroke" => Some(VectorEffect::NonScalingStroke),
+            _ => None,
+        }
+    }
+}
+
 impl<'a, 'input: 'a> FromValue<'a, 'input> for TextRendering {
     fn parse(_: SvgNode, _: AId, value: &str) -> Option<Self> {
         match value {
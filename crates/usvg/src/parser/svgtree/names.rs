@@ -11,6 +11,7 @@ pub enum EId {
     A,
     Circle,
     ClipPath,
+    ConicGradient,
     Defs,
     Ellipse,
     FeBlend,
@@ -40,11 +41,16 @@ pub enum EId {
     FeTurbulence,
     Filter,
     G,
+    Hatch,
+    Hatchpath,
     Image,
     Line,
     LinearGradient,
     Marker,
     Mask,
+    Meshgradient,
+    Meshpatch,
+    Meshrow,
     Path,
     Pattern,
     Polygon,
@@ -66,72 +72,79 @@ pub enum EId {
 static ELEMENTS: Map<EId> = Map {
     key: 732231254413039614,
     disps: &[
-        (0, 12),
-        (1, 11),
-        (10, 26),
-        (2, 42),
-        (1, 19),
-        (0, 5),
-        (1, 13),
-        (8, 50),
+        (0, 9),
+        (17, 10),
+        (9, 46),
         (0, 0),
+        (0, 1),
+        (0, 40),
+        (0, 22),
+        (0, 10),
+        (11, 5),
         (1, 0),
-        (7, 45),
+        (0, 28),
+        (3, 9),
     ],
     entries: &[
-        ("feFlood", EId::FeFlood),
-        ("radialGradient", EId::RadialGradient),
-        ("feImage", EId::FeImage),
-        ("stop", EId::Stop),
-        ("fePointLight", EId::FePointLight),
-        ("feConvolveMatrix", EId::FeConvolveMatrix),
+        ("marker", EId::Marker),
+        ("feSpecularLighting", EId::FeSpecularLighting),
+        ("line", EId::Line),
+        ("feTile", EId::FeTile),
         ("feComposite", EId::FeComposite),
-        ("clipPath", EId::ClipPath),
-        ("feMerge", EId::FeMerge),
-        ("defs", EId::Defs),
-        ("mask", EId::Mask),
-        ("svg", EId::Svg),
+        ("meshpatch", EId::Meshpatch),
+        ("fePointLight", EId::FePointLight),
+        ("feFuncB", EId::FeFuncB),
+        ("ellipse", EId::Ellipse),
+        ("meshrow", EId::Meshrow),
         ("symbol", EId::Symbol),
+        ("hatchpath", EId::Hatchpath),
+        ("g", EId::G),
+        ("feDropShadow", EId::FeDropShadow),
+        ("feColorMatrix", EId::FeColorMatrix),
         ("linearGradient", EId::LinearGradient),
-        ("feSpecularLighting", EId::FeSpecularLighting),
-        ("feFuncB", EId::FeFuncB),
-        ("filter", EId::Filter),
+        ("meshgradient", EId::Meshgradient),
+        ("text", EId::Text),
         ("feFuncG", EId::FeFuncG),
-        ("circle", EId::Circle),
-        ("g", EId::G),
-        ("tref", EId::Tref),
-        ("feFuncA", EId::FeFuncA),
         ("image", EId::Image),
-        ("text", EId::Text),
-        ("line", EId::Line),
-        ("pattern", EId::Pattern),
-        ("use", EId::Use),
-        ("feDropShadow", EId::FeDropShadow),
         ("feSpotLight", EId::FeSpotLight),
-        ("marker", EId::Marker),
-        ("style", EId::Style),
-        ("switch", EId::Switch),
-        ("tspan", EId::Tspan),
-        ("feColorMatrix", EId::FeColorMatrix),
-        ("feOffset", EId::FeOffset),
-        ("path", EId::Path),
-        ("feGaussianBlur", EId::FeGaussianBlur),
-        ("feTile", EId::FeTile),
-        ("feTurbulence", EId::FeTurbulence),
-        ("feMergeNode", EId::FeMergeNode),
         ("feMorphology", EId::FeMorphology),
-        ("a", EId::A),
         ("textPath", EId::TextPath),
-        ("ellipse", EId::Ellipse),
+        ("mask", EId::Mask),
+        ("tref", EId::Tref),
         ("feComponentTransfer", EId::FeComponentTransfer),
+        ("feFuncA", EId::FeFuncA),
+        ("tspan", EId::Tspan),
+        ("circle", EId::Circle),
+        ("feConvolveMatrix", EId::FeConvolveMatrix),
+        ("switch", EId::Switch),
+        ("feGaussianBlur", EId::FeGaussianBlur),
         ("feDistantLight", EId::FeDistantLight),
+        ("stop", EId::Stop),
+        ("defs", EId::Defs),
+        ("a", EId::A),
+        ("clipPath", EId::ClipPath),
+        ("svg", EId::Svg),
+        ("radialGradient", EId::RadialGradient),
+        ("filter", EId::Filter),
+        ("feFlood", EId::FeFlood),
+        ("path", EId::Path),
+        ("feMerge", EId::FeMerge),
         ("polyline", EId::Polyline),
+        ("rect", EId::Rect),
+        ("feTurbulence", EId::FeTurbulence),
+        ("feMergeNode", EId::FeMergeNode),
+        ("feImage", EId::FeImage),
+        ("conicGradient", EId::ConicGradient),
+        ("hatch", EId::Hatch),
+        ("pattern", EId::Pattern),
+        ("feOffset", EId::FeOffset),
         ("polygon", EId::Polygon),
-        ("feBlend", EId::FeBlend),
+        ("use", EId::Use),
+        ("feFuncR", EId::FeFuncR),
+        ("style", EId::Style),
         ("feDisplacementMap", EId::FeDisplacementMap),
+        ("feBlend", EId::FeBlend),
         ("feDiffuseLighting", EId::FeDiffuseLighting),
-        ("rect", EId::Rect),
-        ("feFuncR", EId::FeFuncR),
     ],
 };
 
@@ -193,6 +206,7 @@ pub enum AId {
     EdgeMode,
     Elevation,
     EnableBackground,
+    EndAngle,
     Exponent,
     Fill,
     FillOpacity,
@@ -216,6 +230,7 @@ pub enum AId {
     FontVariantLigatures,
     FontVariantNumeric,
     FontVariantPosition,
+    FontVariationSettings,
     FontWeight,
     Fr,
     Fx,
@@ -224,6 +239,8 @@ pub enum AId {
     GlyphOrientationVertical,
     GradientTransform,
     GradientUnits,
+    HatchContentUnits,
+    HatchUnits,
     Height,
     Href,
     Id,
@@ -265,6 +282,7 @@ pub enum AId {
     MaskMode,
     MaskOrigin,
     MaskPosition,
+    MaskRepeat,
     MaskSize,
     MaskType,
     MaskContentUnits,
@@ -284,6 +302,7 @@ pub enum AId {
     PatternContentUnits,
     PatternTransform,
     PatternUnits,
+    Pitch,
     Points,
     PointsAtX,
     PointsAtY,
@@ -315,6 +334,7 @@ pub enum AId {
     SpecularConstant,
     SpecularExponent,
     SpreadMethod,
+    StartAngle,
     StartOffset,
     StdDeviation,
     StitchTiles,
@@ -341,12 +361,14 @@ pub enum AId {
     TextDecorationColor,
     TextDecorationFill,
     TextDecorationLine,
+    TextDecorationSkipInk,
     TextDecorationStroke,
     TextDecorationStyle,
     TextIndent,
     TextOrientation,
     TextOverflow,
     TextRendering,
+    TextTransform,
     TextUnderlinePosition,
     TextLength,
     Transform,
@@ -375,261 +397,272 @@ pub enum AId {
 }
 
 static ATTRIBUTES: Map<AId> = Map {
-    key: 3347381344252206323,
+    key: 3213172566270843353,
     disps: &[
-        (0, 111),
-        (0, 2),
-        (0, 45),
-        (0, 5),
+        (1, 20),
+        (0, 99),
+        (0, 75),
+        (0, 40),
+        (0, 183),
         (0, 1),
-        (2, 56),
-        (0, 5),
-        (2, 99),
-        (13, 198),
-        (0, 61),
-        (0, 52),
-        (1, 29),
-        (0, 21),
-        (0, 70),
-        (0, 164),
-        (2, 60),
-        (3, 52),
+        (3, 160),
+        (1, 20),
+        (0, 73),
+        (0, 181),
+        (0, 0),
+        (0, 0),
         (0, 1),
-        (0, 86),
+        (1, 28),
+        (0, 0),
+        (3, 188),
+        (3, 179),
         (0, 10),
+        (0, 146),
+        (0, 81),
+        (0, 65),
+        (0, 3),
+        (0, 87),
+        (13, 106),
+        (0, 9),
+        (0, 91),
+        (0, 5),
+        (0, 9),
+        (1, 197),
+        (15, 154),
+        (0, 99),
         (0, 0),
-        (0, 4),
-        (2, 175),
-        (6, 59),
-        (1, 14),
-        (0, 13),
-        (3, 175),
-        (1, 10),
-        (2, 76),
-        (0, 53),
-        (0, 24),
-        (123, 202),
-        (0, 14),
-        (0, 30),
-        (0, 62),
-        (0, 98),
-        (11, 193),
-        (8, 79),
-        (0, 17),
-        (22, 5),
-        (36, 106),
+        (0, 1),
+        (3, 41),
         (1, 1),
+        (1, 149),
+        (14, 151),
+        (10, 135),
+        (28, 56),
+        (10, 49),
+        (9, 61),
+        (1, 117),
+        (0, 173),
+        (0, 62),
     ],
     entries: &[
-        ("mask-border-source", AId::MaskBorderSource),
-        ("stop-opacity", AId::StopOpacity),
-        ("stroke-linejoin", AId::StrokeLinejoin),
-        ("dominant-baseline", AId::DominantBaseline),
-        ("spreadMethod", AId::SpreadMethod),
-        ("order", AId::Order),
-        ("stroke", AId::Stroke),
-        ("stitchTiles", AId::StitchTiles),
-        ("height", AId::Height),
-        ("font-size", AId::FontSize),
-        ("background-color", AId::BackgroundColor),
-        ("tableValues", AId::TableValues),
-        ("x1", AId::X1),
-        ("y", AId::Y),
-        ("width", AId::Width),
-        ("text-indent", AId::TextIndent),
-        ("fill-opacity", AId::FillOpacity),
-        ("word-spacing", AId::WordSpacing),
-        ("cy", AId::Cy),
-        ("scale", AId::Scale),
-        ("x2", AId::X2),
-        ("lengthAdjust", AId::LengthAdjust),
+        ("startOffset", AId::StartOffset),
+        ("gradientTransform", AId::GradientTransform),
+        ("unicode-bidi", AId::UnicodeBidi),
+        ("pitch", AId::Pitch),
         ("glyph-orientation-horizontal", AId::GlyphOrientationHorizontal),
+        ("in2", AId::In2),
+        ("stroke", AId::Stroke),
+        ("systemLanguage", AId::SystemLanguage),
+        ("mask-border-outset", AId::MaskBorderOutset),
+        ("baseline-shift", AId::BaselineShift),
+        ("color-interpolation-filters", AId::ColorInterpolationFilters),
         ("opacity", AId::Opacity),
-        ("mask-border", AId::MaskBorder),
-        ("font-stretch", AId::FontStretch),
-        ("stroke-dashoffset", AId::StrokeDashoffset),
+        ("z", AId::Z),
+        ("text-align", AId::TextAlign),
+        ("stop-opacity", AId::StopOpacity),
+        ("text-rendering", AId::TextRendering),
+        ("hatchUnits", AId::HatchUnits),
+        ("vector-effect", AId::VectorEffect),
+        ("baseFrequency", AId::BaseFrequency),
+        ("clip-path", AId::ClipPath),
+        ("order", AId::Order),
+        ("in", AId::In),
+        ("text-underline-position", AId::TextUnderlinePosition),
+        ("isolation", AId::Isolation),
+        ("mask-border-width", AId::MaskBorderWidth),
+        ("lighting-color", AId::LightingColor),
         ("fill", AId::Fill),
-        ("space", AId::Space),
-        ("baseline-shift", AId::BaselineShift),
-        ("text-align-last", AId::TextAlignLast),
-        ("font-variant-east-asian", AId::FontVariantEastAsian),
-        ("mask-border-mode", AId::MaskBorderMode),
+        ("surfaceScale", AId::SurfaceScale),
+        ("flood-color", AId::FloodColor),
+        ("result", AId::Result),
         ("font-variant-caps", AId::FontVariantCaps),
-        ("gradientUnits", AId::GradientUnits),
-        ("exponent", AId::Exponent),
-        ("text-decoration-color", AId::TextDecorationColor),
-        ("refX", AId::RefX),
-        ("enable-background", AId::EnableBackground),
-        ("mask-border-width", AId::MaskBorderWidth),
-        ("numOctaves", AId::NumOctaves),
+        ("font-variant-east-asian", AId::FontVariantEastAsian),
         ("kerning", AId::Kerning),
-        ("mix-blend-mode", AId::MixBlendMode),
-        ("mask-clip", AId::MaskClip),
-        ("mask-mode", AId::MaskMode),
-        ("type", AId::Type),
-        ("class", AId::Class),
+        ("marker-start", AId::MarkerStart),
+        ("color-interpolation", AId::ColorInterpolation),
+        ("textLength", AId::TextLength),
+        ("bias", AId::Bias),
         ("font", AId::Font),
-        ("mask-border-repeat", AId::MaskBorderRepeat),
-        ("stroke-miterlimit", AId::StrokeMiterlimit),
-        ("text-decoration-stroke", AId::TextDecorationStroke),
-        ("z", AId::Z),
-        ("dx", AId::Dx),
-        ("clip-path", AId::ClipPath),
+        ("fr", AId::Fr),
+        ("font-stretch", AId::FontStretch),
+        ("values", AId::Values),
+        ("text-decoration-line", AId::TextDecorationLine),
+        ("mask-clip", AId::MaskClip),
+        ("k3", AId::K3),
+        ("mask-size", AId::MaskSize),
+        ("font-family", AId::FontFamily),
+        ("shape-image-threshold", AId::ShapeImageThreshold),
+        ("intercept", AId::Intercept),
         ("markerHeight", AId::MarkerHeight),
-        ("text-underline-position", AId::TextUnderlinePosition),
-        ("stdDeviation", AId::StdDeviation),
+        ("slope", AId::Slope),
+        ("color", AId::Color),
+        ("cx", AId::Cx),
+        ("dominant-baseline", AId::DominantBaseline),
+        ("endAngle", AId::EndAngle),
+        ("refX", AId::RefX),
+        ("shape-rendering", AId::ShapeRendering),
+        ("markerUnits", AId::MarkerUnits),
+        ("mix-blend-mode", AId::MixBlendMode),
+        ("flood-opacity", AId::FloodOpacity),
+        ("side", AId::Side),
+        ("primitiveUnits", AId::PrimitiveUnits),
+        ("pointsAtX", AId::PointsAtX),
+        ("text-indent", AId::TextIndent),
+        ("k2", AId::K2),
         ("id", AId::Id),
-        ("paint-order", AId::PaintOrder),
-        ("elevation", AId::Elevation),
-        ("specularConstant", AId::SpecularConstant),
-        ("result", AId::Result),
-        ("font-size-adjust", AId::FontSizeAdjust),
-        ("mask-origin", AId::MaskOrigin),
-        ("direction", AId::Direction),
-        ("font-variant-numeric", AId::FontVariantNumeric),
-        ("startOffset", AId::StartOffset),
-        ("maskUnits", AId::MaskUnits),
-        ("font-variant", AId::FontVariant),
-        ("text-orientation", AId::TextOrientation),
-        ("amplitude", AId::Amplitude),
-        ("rx", AId::Rx),
-        ("mask-type", AId::MaskType),
-        ("filter", AId::Filter),
-        ("in", AId::In),
-        ("display", AId::Display),
-        ("seed", AId::Seed),
-        ("unicode-range", AId::UnicodeRange),
+        ("stroke-linecap", AId::StrokeLinecap),
+        ("inline-size", AId::InlineSize),
+        ("kernelMatrix", AId::KernelMatrix),
+        ("marker-end", AId::MarkerEnd),
+        ("text-decoration-stroke", AId::TextDecorationStroke),
+        ("text-transform", AId::TextTransform),
+        ("spreadMethod", AId::SpreadMethod),
+        ("mask-border-mode", AId::MaskBorderMode),
+        ("clip", AId::Clip),
+        ("scale", AId::Scale),
+        ("hatchContentUnits", AId::HatchContentUnits),
         ("color-profile", AId::ColorProfile),
-        ("x", AId::X),
-        ("href", AId::Href),
-        ("font-feature-settings", AId::FontFeatureSettings),
-        ("fill-rule", AId::FillRule),
-        ("fr", AId::Fr),
-        ("font-variant-ligatures", AId::FontVariantLigatures),
+        ("pointsAtZ", AId::PointsAtZ),
         ("text-decoration-style", AId::TextDecorationStyle),
-        ("radius", AId::Radius),
-        ("xChannelSelector", AId::XChannelSelector),
-        ("orient", AId::Orient),
-        ("isolation", AId::Isolation),
-        ("gradientTransform", AId::GradientTransform),
-        ("transform-box", AId::TransformBox),
-        ("pointsAtY", AId::PointsAtY),
-        ("text-decoration-line", AId::TextDecorationLine),
-        ("requiredFeatures", AId::RequiredFeatures),
-        ("patternContentUnits", AId::PatternContentUnits),
+        ("stop-color", AId::StopColor),
+        ("maskContentUnits", AId::MaskContentUnits),
+        ("gradientUnits", AId::GradientUnits),
+        ("fy", AId::Fy),
+        ("numOctaves", AId::NumOctaves),
+        ("patternUnits", AId::PatternUnits),
+        ("ry", AId::Ry),
+        ("space", AId::Space),
         ("shape-padding", AId::ShapePadding),
-        ("text-overflow", AId::TextOverflow),
-        ("clipPathUnits", AId::ClipPathUnits),
+        ("kernelUnitLength", AId::KernelUnitLength),
+        ("direction", AId::Direction),
         ("azimuth", AId::Azimuth),
-        ("line-height", AId::LineHeight),
+        ("paint-order", AId::PaintOrder),
+        ("shape-margin", AId::ShapeMargin),
+        ("glyph-orientation-vertical", AId::GlyphOrientationVertical),
+        ("shape-inside", AId::ShapeInside),
+        ("shape-subtract", AId::ShapeSubtract),
+        ("font-weight", AId::FontWeight),
+        ("markerWidth", AId::MarkerWidth),
         ("viewBox", AId::ViewBox),
-        ("preserveAspectRatio", AId::PreserveAspectRatio),
-        ("path", AId::Path),
-        ("k4", AId::K4),
-        ("systemLanguage", AId::SystemLanguage),
-        ("stroke-width", AId::StrokeWidth),
-        ("specularExponent", AId::SpecularExponent),
-        ("writing-mode", AId::WritingMode),
-        ("transform-origin", AId::TransformOrigin),
-        ("stroke-linecap", AId::StrokeLinecap),
-        ("points", AId::Points),
-        ("style", AId::Style),
-        ("pointsAtZ", AId::PointsAtZ),
-        ("targetX", AId::TargetX),
         ("font-synthesis", AId::FontSynthesis),
-        ("maskContentUnits", AId::MaskContentUnits),
-        ("text-align", AId::TextAlign),
-        ("cx", AId::Cx),
+        ("style", AId::Style),
         ("alignment-baseline", AId::AlignmentBaseline),
-        ("font-kerning", AId::FontKerning),
-        ("requiredExtensions", AId::RequiredExtensions),
-        ("clip-rule", AId::ClipRule),
-        ("mask-border-outset", AId::MaskBorderOutset),
-        ("primitiveUnits", AId::PrimitiveUnits),
-        ("textLength", AId::TextLength),
-        ("text-decoration-fill", AId::TextDecorationFill),
-        ("fy", AId::Fy),
-        ("mask-size", AId::MaskSize),
-        ("k3", AId::K3),
-        ("marker-start", AId::MarkerStart),
-        ("mode", AId::Mode),
-        ("k1", AId::K1),
-        ("refY", AId::RefY),
-        ("y1", AId::Y1),
-        ("shape-rendering", AId::ShapeRendering),
-        ("operator", AId::Operator),
-        ("mask-image", AId::MaskImage),
-        ("marker-end", AId::MarkerEnd),
-        ("rotate", AId::Rotate),
-        ("limitingConeAngle", AId::LimitingConeAngle),
-        ("surfaceScale", AId::SurfaceScale),
-        ("intercept", AId::Intercept),
-        ("font-variant-position", AId::FontVariantPosition),
-        ("clip", AId::Clip),
+        ("targetX", AId::TargetX),
+        ("exponent", AId::Exponent),
+        ("type", AId::Type),
+        ("d", AId::D),
+        ("preserveAspectRatio", AId::PreserveAspectRatio),
+        ("text-align-last", AId::TextAlignLast),
+        ("pathLength", AId::PathLength),
+        ("class", AId::Class),
+        ("text-overflow", AId::TextOverflow),
+        ("fill-opacity", AId::FillOpacity),
+        ("height", AId::Height),
         ("fx", AId::Fx),
         ("visibility", AId::Visibility),
-        ("shape-margin", AId::ShapeMargin),
-        ("font-style", AId::FontStyle),
-        ("y2", AId::Y2),
-        ("dy", AId::Dy),
-        ("yChannelSelector", AId::YChannelSelector),
-        ("ry", AId::Ry),
+        ("seed", AId::Seed),
+        ("limitingConeAngle", AId::LimitingConeAngle),
+        ("mask-type", AId::MaskType),
+        ("unicode-range", AId::UnicodeRange),
+        ("font-variant-ligatures", AId::FontVariantLigatures),
+        ("cy", AId::Cy),
+        ("text-decoration", AId::TextDecoration),
+        ("pointsAtY", AId::PointsAtY),
+        ("font-size", AId::FontSize),
+        ("tableValues", AId::TableValues),
+        ("diffuseConstant", AId::DiffuseConstant),
+        ("k4", AId::K4),
+        ("startAngle", AId::StartAngle),
         ("color-rendering", AId::ColorRendering),
-        ("white-space", AId::WhiteSpace),
-        ("patternUnits", AId::PatternUnits),
-        ("shape-subtract", AId::ShapeSubtract),
-        ("markerWidth", AId::MarkerWidth),
-        ("d", AId::D),
-        ("shape-inside", AId::ShapeInside),
-        ("preserveAlpha", AId::PreserveAlpha),
-        ("shape-image-threshold", AId::ShapeImageThreshold),
-        ("image-rendering", AId::ImageRendering),
-        ("marker-mid", AId::MarkerMid),
-        ("filterUnits", AId::FilterUnits),
-        ("bias", AId::Bias),
+        ("y", AId::Y),
+        ("requiredFeatures", AId::RequiredFeatures),
+        ("background-color", AId::BackgroundColor),
         ("mask-border-slice", AId::MaskBorderSlice),
-        ("pointsAtX", AId::PointsAtX),
-        ("kernelMatrix", AId::KernelMatrix),
-        ("color-interpolation", AId::ColorInterpolation),
-        ("glyph-orientation-vertical", AId::GlyphOrientationVertical),
-        ("color", AId::Color),
-        ("patternTransform", AId::PatternTransform),
-        ("kernelUnitLength", AId::KernelUnitLength),
-        ("markerUnits", AId::MarkerUnits),
-        ("font-weight", AId::FontWeight),
-        ("overflow", AId::Overflow),
-        ("stop-color", AId::StopColor),
-        ("r", AId::R),
-        ("k2", AId::K2),
+        ("rotate", AId::Rotate),
+        ("requiredExtensions", AId::RequiredExtensions),
+        ("transform-origin", AId::TransformOrigin),
+        ("y2", AId::Y2),
         ("text-anchor", AId::TextAnchor),
-        ("inline-size", AId::InlineSize),
-        ("unicode-bidi", AId::UnicodeBidi),
-        ("font-family", AId::FontFamily),
-        ("color-interpolation-filters", AId::ColorInterpolationFilters),
-        ("slope", AId::Slope),
-        ("baseFrequency", AId::BaseFrequency),
+        ("refY", AId::RefY),
+        ("font-feature-settings", AId::FontFeatureSettings),
+        ("rx", AId::Rx),
+        ("transform-box", AId::TransformBox),
+        ("font-variant", AId::FontVariant),
+        ("operator", AId::Operator),
+        ("text-decoration-skip-ink", AId::TextDecorationSkipInk),
+        ("stroke-miterlimit", AId::StrokeMiterlimit),
+        ("enable-background", AId::EnableBackground),
+        ("font-variation-settings", AId::FontVariationSettings),
+        ("marker-mid", AId::MarkerMid),
+        ("path", AId::Path),
+        ("filter", AId::Filter),
+        ("stroke-dasharray", AId::StrokeDasharray),
+        ("dx", AId::Dx),
+        ("mask-position", AId::MaskPosition),
+        ("dy", AId::Dy),
+        ("mode", AId::Mode),
+        ("x", AId::X),
+        ("mask-composite", AId::MaskComposite),
+        ("stroke-dashoffset", AId::StrokeDashoffset),
+        ("font-kerning", AId::FontKerning),
+        ("width", AId::Width),
+        ("amplitude", AId::Amplitude),
+        ("y1", AId::Y1),
+        ("points", AId::Points),
+        ("image-rendering", AId::ImageRendering),
+        ("display", AId::Display),
+        ("font-variant-position", AId::FontVariantPosition),
+        ("writing-mode", AId::WritingMode),
+        ("stdDeviation", AId::StdDeviation),
+        ("lengthAdjust", AId::LengthAdjust),
+        ("x2", AId::X2),
+        ("white-space", AId::WhiteSpace),
+        ("targetY", AId::TargetY),
+        ("font-variant-numeric", AId::FontVariantNumeric),
+        ("patternContentUnits", AId::PatternContentUnits),
+        ("href", AId::Href),
+        ("specularExponent", AId::SpecularExponent),
+        ("font-style", AId::FontStyle),
         ("transform", AId::Transform),
-        ("text-rendering", AId::TextRendering),
+        ("x1", AId::X1),
+        ("mask-border-repeat", AId::MaskBorderRepeat),
+        ("clipPathUnits", AId::ClipPathUnits),
+        ("xChannelSelector", AId::XChannelSelector),
+        ("letter-spacing", AId::LetterSpacing),
         ("divisor", AId::Divisor),
+        ("mask-origin", AId::MaskOrigin),
+        ("specularConstant", AId::SpecularConstant),
+        ("line-height", AId::LineHeight),
+        ("mask-image", AId::MaskImage),
+        ("mask", AId::Mask),
         ("edgeMode", AId::EdgeMode),
-        ("letter-spacing", AId::LetterSpacing),
-        ("flood-color", AId::FloodColor),
-        ("in2", AId::In2),
-        ("side", AId::Side),
-        ("mask-composite", AId::MaskComposite),
+        ("font-size-adjust", AId::FontSizeAdjust),
+        ("fill-rule", AId::FillRule),
+        ("mask-border", AId::MaskBorder),
         ("offset", AId::Offset),
-        ("values", AId::Values),
-        ("vector-effect", AId::VectorEffect),
-        ("mask", AId::Mask),
-        ("pathLength", AId::PathLength),
-        ("lighting-color", AId::LightingColor),
-        ("mask-position", AId::MaskPosition),
-        ("stroke-dasharray", AId::StrokeDasharray),
-        ("text-decoration", AId::TextDecoration),
+        ("patternTransform", AId::PatternTransform),
+        ("text-decoration-fill", AId::TextDecorationFill),
+        ("filterUnits", AId::FilterUnits),
+        ("preserveAlpha", AId::PreserveAlpha),
+        ("word-spacing", AId::WordSpacing),
+        ("stroke-width", AId::StrokeWidth),
+        ("mask-mode", AId::MaskMode),
+        ("k1", AId::K1),
         ("stroke-opacity", AId::StrokeOpacity),
-        ("targetY", AId::TargetY),
-        ("flood-opacity", AId::FloodOpacity),
-        ("diffuseConstant", AId::DiffuseConstant),
+        ("orient", AId::Orient),
+        ("elevation", AId::Elevation),
+        ("overflow", AId::Overflow),
+        ("text-orientation", AId::TextOrientation),
+        ("maskUnits", AId::MaskUnits),
+        ("mask-border-source", AId::MaskBorderSource),
+        ("radius", AId::Radius),
+        ("text-decoration-color", AId::TextDecorationColor),
+        ("stitchTiles", AId::StitchTiles),
+        ("yChannelSelector", AId::YChannelSelector),
+        ("clip-rule", AId::ClipRule),
+        ("stroke-linejoin", AId::StrokeLinejoin),
+        ("r", AId::R),
+        ("mask-repeat", AId::MaskRepeat),
     ],
 };
 
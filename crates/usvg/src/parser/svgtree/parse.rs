@@ -1,16 +1,18 @@
 // Copyright 2021 the Resvg Authors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+use std::cell::{OnceCell, RefCell};
 use std::collections::HashMap;
 
-use roxmltree::Error;
 use simplecss::Declaration;
 use svgtypes::FontShorthand;
 
-use super::{AId, Attribute, Document, EId, NodeData, NodeId, NodeKind, ShortRange};
+use super::animation::Animations;
+use super::{AId, Attribute, Document, EId, NodeData, NodeId, NodeKind, ShortRange, SvgNode};
+use crate::{DiagnosticCode, Error, Limits};
 
-const SVG_NS: &str = "http://www.w3.org/2000/svg";
-const XLINK_NS: &str = "http://www.w3.org/1999/xlink";
+pub(crate) const SVG_NS: &str = "http://www.w3.org/2000/svg";
+pub(crate) const XLINK_NS: &str = "http://www.w3.org/1999/xlink";
 const XML_NAMESPACE_NS: &str = "http://www.w3.org/XML/1998/namespace";
 
 impl<'input> Document<'input> {
@@ -18,8 +20,10 @@ impl<'input> Document<'input> {
     pub fn parse_tree(
         xml: &roxmltree::Document<'input>,
         injected_stylesheet: Option<&'input str>,
+        limits: &Limits,
+        animation_time: Option<f32>,
     ) -> Result<Document<'input>, Error> {
-        parse(xml, injected_stylesheet)
+        parse(xml, injected_stylesheet, limits, animation_time)
     }
 
     pub(crate) fn append(&mut self, parent_id: NodeId, kind: NodeKind) -> NodeId {
@@ -65,11 +69,21 @@ impl<'input> Document<'input> {
 fn parse<'input>(
     xml: &roxmltree::Document<'input>,
     injected_stylesheet: Option<&'input str>,
+    limits: &Limits,
+    animation_time: Option<f32>,
 ) -> Result<Document<'input>, Error> {
     let mut doc = Document {
+        text: xml.input_text(),
         nodes: Vec::new(),
         attrs: Vec::new(),
         links: HashMap::new(),
+        diagnostics: RefCell::default(),
+        text_index: OnceCell::new(),
+        animations: None,
+        #[cfg(feature = "text")]
+        font_faces: collect_font_faces(xml, injected_stylesheet),
+        #[cfg(feature = "text")]
+        svg_fonts: super::SvgFont::collect(xml),
     };
 
     // build a map of id -> node for resolve_href
@@ -82,6 +96,10 @@ fn parse<'input>(
         }
     }
 
+    if let Some(time) = animation_time {
+        doc.animations = Some(Animations::collect(xml, &id_map, time));
+    }
+
     // Add a root node.
     doc.nodes.push(NodeData {
         parent: None,
@@ -98,7 +116,8 @@ fn parse<'input>(
         doc.root().id,
         &style_sheet,
         false,
-        0,
+        Depth::default(),
+        limits,
         &mut doc,
         &id_map,
     )?;
@@ -107,10 +126,10 @@ fn parse<'input>(
     match doc.root().first_element_child() {
         Some(child) => {
             if child.tag_name() != Some(EId::Svg) {
-                return Err(roxmltree::Error::NoRootNode);
+                return Err(Error::ParsingFailed(roxmltree::Error::NoRootNode));
             }
         }
-        None => return Err(roxmltree::Error::NoRootNode),
+        None => return Err(Error::ParsingFailed(roxmltree::Error::NoRootNode)),
     }
 
     // Collect all elements with `id` attribute.
@@ -125,6 +144,7 @@ fn parse<'input>(
     fix_recursive_patterns(&mut doc);
     fix_recursive_links(EId::ClipPath, AId::ClipPath, &mut doc);
     fix_recursive_links(EId::Mask, AId::Mask, &mut doc);
+    fix_recursive_mask_images(&mut doc);
     fix_recursive_links(EId::Filter, AId::Filter, &mut doc);
     fix_recursive_fe_image(&mut doc);
 
@@ -143,13 +163,23 @@ pub(crate) fn parse_tag_name(node: roxmltree::Node) -> Option<EId> {
     EId::from_str(node.tag_name().name())
 }
 
+/// Nesting depth of a parsed node.
+#[derive(Clone, Copy, Default)]
+struct Depth {
+    /// Depth in the resulting tree.
+    nodes: u32,
+    /// Number of nested `use` elements that instantiated the node.
+    uses: u32,
+}
+
 fn parse_xml_node_children<'input>(
     parent: roxmltree::Node<'_, 'input>,
     origin: roxmltree::Node,
     parent_id: NodeId,
     style_sheet: &simplecss::StyleSheet,
     ignore_ids: bool,
-    depth: u32,
+    depth: Depth,
+    limits: &Limits,
     doc: &mut Document<'input>,
     id_map: &HashMap<&str, roxmltree::Node<'_, 'input>>,
 ) -> Result<(), Error> {
@@ -161,6 +191,7 @@ fn parse_xml_node_children<'input>(
             style_sheet,
             ignore_ids,
             depth,
+            limits,
             doc,
             id_map,
         )?;
@@ -175,12 +206,13 @@ fn parse_xml_node<'input>(
     parent_id: NodeId,
     style_sheet: &simplecss::StyleSheet,
     ignore_ids: bool,
-    depth: u32,
+    depth: Depth,
+    limits: &Limits,
     doc: &mut Document<'input>,
     id_map: &HashMap<&str, roxmltree::Node<'_, 'input>>,
 ) -> Result<(), Error> {
-    if depth > 1024 {
-        return Err(Error::NodesLimitReached);
+    if depth.nodes > 1024 {
+        return Err(Error::ParsingFailed(roxmltree::Error::NodesLimitReached));
     }
 
     let mut tag_name = match parse_tag_name(node) {
@@ -198,11 +230,34 @@ fn parse_xml_node<'input>(
         tag_name = EId::G;
     }
 
-    let node_id = parse_svg_element(node, parent_id, tag_name, style_sheet, ignore_ids, doc)?;
+    let node_id = parse_svg_element(
+        node,
+        parent_id,
+        tag_name,
+        style_sheet,
+        ignore_ids,
+        limits,
+        doc,
+    )?;
+
+    let depth = Depth {
+        nodes: depth.nodes + 1,
+        ..depth
+    };
+
     if tag_name == EId::Text {
-        super::text::parse_svg_text_element(node, node_id, style_sheet, doc)?;
+        super::text::parse_svg_text_element(node, node_id, style_sheet, limits, doc)?;
     } else if tag_name == EId::Use {
-        parse_svg_use_element(node, origin, node_id, style_sheet, depth + 1, doc, id_map)?;
+        parse_svg_use_element(
+            node,
+            origin,
+            node_id,
+            style_sheet,
+            depth,
+            limits,
+            doc,
+            id_map,
+        )?;
     } else {
         parse_xml_node_children(
             node,
@@ -210,7 +265,8 @@ fn parse_xml_node<'input>(
             node_id,
             style_sheet,
             ignore_ids,
-            depth + 1,
+            depth,
+            limits,
             doc,
             id_map,
         )?;
@@ -225,6 +281,7 @@ pub(crate) fn parse_svg_element<'input>(
     tag_name: EId,
     style_sheet: &simplecss::StyleSheet,
     ignore_ids: bool,
+    limits: &Limits,
     doc: &mut Document<'input>,
 ) -> Result<NodeId, Error> {
     let attrs_start_idx = doc.attrs.len();
@@ -270,52 +327,15 @@ pub(crate) fn parse_svg_element<'input>(
     }
 
     let mut insert_attribute = |aid, value: &str, important: bool| {
-        // Check that attribute already exists.
-        let idx = doc.attrs[attrs_start_idx..]
-            .iter_mut()
-            .position(|a| a.name == aid);
-
-        // Append an attribute as usual.
-        let added = append_attribute(
+        merge_attribute(
             parent_id,
             tag_name,
+            attrs_start_idx,
             aid,
-            roxmltree::StringStorage::new_owned(value),
+            value,
             important,
             doc,
         );
-
-        // Check that attribute was actually added, because it could be skipped.
-        if added {
-            if let Some(idx) = idx {
-                let last_idx = doc.attrs.len() - 1;
-                let existing_idx = attrs_start_idx + idx;
-
-                // See https://developer.mozilla.org/en-US/docs/Web/CSS/important
-                // When a declaration is important, the order of precedence is reversed.
-                // Declarations marked as important in the user-agent style sheets override
-                // all important declarations in the user style sheets. Similarly, all important
-                // declarations in the user style sheets override all important declarations in the
-                // author's style sheets. Finally, all important declarations take precedence over
-                // all animations.
-                //
-                // Which means:
-                // 1) Existing is not important, new is not important -> swap
-                // 2) Existing is important, new is not important -> don't swap
-                // 3) Existing is not important, new is important -> swap
-                // 4) Existing is important, new is important -> don't swap (since the order
-                // is reversed, so existing important attributes take precedence over new
-                // important attributes)
-                let has_precedence = !doc.attrs[existing_idx].important;
-
-                if has_precedence {
-                    doc.attrs.swap(existing_idx, last_idx);
-                }
-
-                // Remove last.
-                doc.attrs.pop();
-            }
-        }
     };
 
     let mut write_declaration = |declaration: &Declaration| {
@@ -350,9 +370,11 @@ pub(crate) fn parse_svg_element<'input>(
                 shorthand
                     .font_weight
                     .map(|s| insert_attribute(AId::FontWeight, s, imp));
-                shorthand
-                    .font_variant
-                    .map(|s| insert_attribute(AId::FontVariant, s, imp));
+                // Only `small-caps` is allowed here, which is a `font-variant-caps` value.
+                if let Some(s) = shorthand.font_variant {
+                    insert_attribute(AId::FontVariant, s, imp);
+                    insert_attribute(AId::FontVariantCaps, s, imp);
+                }
                 shorthand
                     .font_style
                     .map(|s| insert_attribute(AId::FontStyle, s, imp));
@@ -389,8 +411,30 @@ pub(crate) fn parse_svg_element<'input>(
         }
     }
 
-    if doc.nodes.len() > 1_000_000 {
-        return Err(Error::NodesLimitReached);
+    // Apply animations. They override everything except important declarations.
+    if let Some(ref animations) = doc.animations {
+        let values = animations.sample(xml_node, |aid| {
+            doc.attrs[attrs_start_idx..]
+                .iter()
+                .find(|a| a.name == aid)
+                .map(|a| a.value.to_string())
+        });
+
+        for (aid, value) in values {
+            merge_attribute(
+                parent_id,
+                tag_name,
+                attrs_start_idx,
+                aid,
+                &value,
+                false,
+                doc,
+            );
+        }
+    }
+
+    if doc.nodes.len() >= limits.max_elements {
+        return Err(Error::ElementsLimitReached);
     }
 
     let node_id = doc.append(
@@ -398,12 +442,71 @@ pub(crate) fn parse_svg_element<'input>(
         NodeKind::Element {
             tag_name,
             attributes: ShortRange::new(attrs_start_idx as u32, doc.attrs.len() as u32),
+            pos: xml_node.range().start as u32,
         },
     );
 
     Ok(node_id)
 }
 
+/// Inserts an attribute, replacing an existing one according to the precedence rules.
+fn merge_attribute<'input>(
+    parent_id: NodeId,
+    tag_name: EId,
+    attrs_start_idx: usize,
+    aid: AId,
+    value: &str,
+    important: bool,
+    doc: &mut Document<'input>,
+) {
+    // Check that attribute already exists.
+    let idx = doc.attrs[attrs_start_idx..]
+        .iter_mut()
+        .position(|a| a.name == aid);
+
+    // Append an attribute as usual.
+    let added = append_attribute(
+        parent_id,
+        tag_name,
+        aid,
+        roxmltree::StringStorage::new_owned(value),
+        important,
+        doc,
+    );
+
+    // Check that attribute was actually added, because it could be skipped.
+    if added {
+        if let Some(idx) = idx {
+            let last_idx = doc.attrs.len() - 1;
+            let existing_idx = attrs_start_idx + idx;
+
+            // See https://developer.mozilla.org/en-US/docs/Web/CSS/important
+            // When a declaration is important, the order of precedence is reversed.
+            // Declarations marked as important in the user-agent style sheets override
+            // all important declarations in the user style sheets. Similarly, all important
+            // declarations in the user style sheets override all important declarations in the
+            // author's style sheets. Finally, all important declarations take precedence over
+            // all animations.
+            //
+            // Which means:
+            // 1) Existing is not important, new is not important -> swap
+            // 2) Existing is important, new is not important -> don't swap
+            // 3) Existing is not important, new is important -> swap
+            // 4) Existing is important, new is important -> don't swap (since the order
+            // is reversed, so existing important attributes take precedence over new
+            // important attributes)
+            let has_precedence = !doc.attrs[existing_idx].important;
+
+            if has_precedence {
+                doc.attrs.swap(existing_idx, last_idx);
+            }
+
+            // Remove last.
+            doc.attrs.pop();
+        }
+    }
+}
+
 fn append_attribute<'input>(
     parent_id: NodeId,
     tag_name: EId,
@@ -530,7 +633,7 @@ fn resolve_inherit(parent_id: NodeId, aid: AId, doc: &mut Document) -> bool {
     true
 }
 
-fn resolve_href<'a, 'input: 'a>(
+pub(crate) fn resolve_href<'a, 'input: 'a>(
     node: roxmltree::Node<'a, 'input>,
     id_map: &HashMap<&str, roxmltree::Node<'a, 'input>>,
 ) -> Option<roxmltree::Node<'a, 'input>> {
@@ -548,7 +651,8 @@ fn parse_svg_use_element<'input>(
     origin: roxmltree::Node,
     parent_id: NodeId,
     style_sheet: &simplecss::StyleSheet,
-    depth: u32,
+    depth: Depth,
+    limits: &Limits,
     doc: &mut Document<'input>,
     id_map: &HashMap<&str, roxmltree::Node<'_, 'input>>,
 ) -> Result<(), Error> {
@@ -558,9 +662,12 @@ fn parse_svg_use_element<'input>(
     };
 
     if link == node || link == origin {
-        log::warn!(
-            "Recursive 'use' detected. '{}' will be skipped.",
-            node.attribute((SVG_NS, "id")).unwrap_or_default()
+        doc.get(parent_id).skip(
+            DiagnosticCode::RecursiveReference,
+            format!(
+                "Recursive 'use' detected. '{}' will be skipped.",
+                node.attribute((SVG_NS, "id")).unwrap_or_default()
+            ),
         );
         return Ok(());
     }
@@ -602,20 +709,33 @@ fn parse_svg_use_element<'input>(
     }
 
     if is_recursive {
-        log::warn!(
-            "Recursive 'use' detected. '{}' will be skipped.",
-            node.attribute((SVG_NS, "id")).unwrap_or_default()
+        doc.get(parent_id).skip(
+            DiagnosticCode::RecursiveReference,
+            format!(
+                "Recursive 'use' detected. '{}' will be skipped.",
+                node.attribute((SVG_NS, "id")).unwrap_or_default()
+            ),
         );
         return Ok(());
     }
 
+    if depth.uses >= limits.max_use_depth {
+        return Err(Error::UseDepthLimitReached);
+    }
+
+    let depth = Depth {
+        uses: depth.uses + 1,
+        ..depth
+    };
+
     parse_xml_node(
         link,
         node,
         parent_id,
         style_sheet,
         true,
-        depth + 1,
+        depth,
+        limits,
         doc,
         id_map,
     )
@@ -626,29 +746,113 @@ fn resolve_css<'a>(
     style_sheet: Option<&'a str>,
 ) -> simplecss::StyleSheet<'a> {
     let mut sheet = simplecss::StyleSheet::new();
+    for text in style_sheets(xml, style_sheet) {
+        sheet.parse_more(text);
+    }
+
+    sheet
+}
 
+/// Returns the text of all style sheets in the order they must be applied.
+fn style_sheets<'a>(
+    xml: &'a roxmltree::Document<'a>,
+    style_sheet: Option<&'a str>,
+) -> impl Iterator<Item = &'a str> {
     // Injected style sheets do not override internal ones (we mimic the logic of rsvg-convert),
     // so we need to parse it first.
-    if let Some(style_sheet) = style_sheet {
-        sheet.parse_more(style_sheet);
+    let styles = xml
+        .descendants()
+        .filter(|n| n.has_tag_name("style"))
+        .filter(|n| matches!(n.attribute("type"), Some("text/css") | None))
+        .filter_map(|n| n.text());
+
+    style_sheet.into_iter().chain(styles)
+}
+
+/// Collects declaration blocks of `@font-face` rules.
+///
+/// `simplecss` skips all at-rules, so they have to be extracted separately.
+#[cfg(feature = "text")]
+fn collect_font_faces(xml: &roxmltree::Document, style_sheet: Option<&str>) -> Vec<String> {
+    let mut font_faces = Vec::new();
+    for text in style_sheets(xml, style_sheet) {
+        let mut rest = text;
+        while let Some(idx) = find_at_rule(rest, "font-face") {
+            rest = &rest[idx..];
+            let (start, end) = match (rest.find('{'), find_block_end(rest)) {
+                (Some(start), Some(end)) if start < end => (start, end),
+                _ => break,
+            };
+
+            font_faces.push(rest[start + 1..end].to_string());
+            rest = &rest[end + 1..];
+        }
     }
 
-    for node in xml.descendants().filter(|n| n.has_tag_name("style")) {
-        match node.attribute("type") {
-            Some("text/css") => {}
-            Some(_) => continue,
-            None => {}
+    font_faces
+}
+
+/// Returns the position of the first at-rule with the specified name.
+///
+/// Comments and strings are skipped.
+#[cfg(feature = "text")]
+fn find_at_rule(text: &str, name: &str) -> Option<usize> {
+    let bytes = text.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = text[i + 2..]
+                    .find("*/")
+                    .map_or(bytes.len(), |n| i + 2 + n + 2);
+                continue;
+            }
+            quote @ (b'"' | b'\'') => {
+                i = text[i + 1..]
+                    .find(quote as char)
+                    .map_or(bytes.len(), |n| i + 1 + n + 1);
+                continue;
+            }
+            b'@' => {
+                let ident = text[i + 1..]
+                    .split(|c: char| !(c.is_ascii_alphanumeric() || c == '-'))
+                    .next()
+                    .unwrap_or("");
+                if ident.eq_ignore_ascii_case(name) {
+                    return Some(i);
+                }
+            }
+            _ => {}
         }
 
-        let text = match node.text() {
-            Some(v) => v,
-            None => continue,
-        };
+        i += 1;
+    }
 
-        sheet.parse_more(text);
+    None
+}
+
+/// Returns the position of the closing brace of the first block.
+#[cfg(feature = "text")]
+fn find_block_end(text: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut quote = None;
+    for (i, c) in text.char_indices() {
+        match (quote, c) {
+            (Some(q), _) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '{') => depth += 1,
+            (None, '}') => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
     }
 
-    sheet
+    None
 }
 
 struct XmlNode<'a, 'input: 'a>(roxmltree::Node<'a, 'input>);
@@ -698,7 +902,7 @@ fn find_recursive_pattern(aid: AId, doc: &mut Document) -> Option<NodeId> {
     for pattern_node in doc
         .root()
         .descendants()
-        .filter(|n| n.tag_name() == Some(EId::Pattern))
+        .filter(|n| matches!(n.tag_name(), Some(EId::Pattern) | Some(EId::Hatch)))
     {
         for node in pattern_node.descendants() {
             let value = match node.attribute(aid) {
@@ -775,6 +979,62 @@ fn find_recursive_link(eid: EId, aid: AId, doc: &Document) -> Option<NodeId> {
     None
 }
 
+fn fix_recursive_mask_images(doc: &mut Document) {
+    while let Some((node_id, aid)) = find_recursive_mask_image(doc) {
+        let idx = doc.get(node_id).attribute_id(aid).unwrap();
+        doc.attrs[idx].value = roxmltree::StringStorage::Borrowed("none");
+    }
+}
+
+/// Like `find_recursive_link`, but also follows masks referenced via `mask-image`.
+fn find_recursive_mask_image(doc: &Document) -> Option<(NodeId, AId)> {
+    for node in doc
+        .root()
+        .descendants()
+        .filter(|n| n.tag_name() == Some(EId::Mask))
+    {
+        for child in node.descendants() {
+            for link in mask_image_links(child) {
+                if link == node {
+                    return Some((child.id, AId::MaskImage));
+                }
+
+                for node2 in link.descendants() {
+                    if node2.node_attribute(AId::Mask) == Some(node) {
+                        return Some((node2.id, AId::Mask));
+                    }
+
+                    if mask_image_links(node2).any(|link2| link2 == node) {
+                        return Some((node2.id, AId::MaskImage));
+                    }
+                }
+            }
+
+            if let Some(link) = child.node_attribute(AId::Mask) {
+                for node2 in link.descendants() {
+                    if mask_image_links(node2).any(|link2| link2 == node) {
+                        return Some((node2.id, AId::MaskImage));
+                    }
+                }
+            }
+        }
+    }
+
+    None
+}
+
+fn mask_image_links<'a, 'input: 'a>(
+    node: SvgNode<'a, 'input>,
+) -> impl Iterator<Item = SvgNode<'a, 'input>> {
+    node.attribute::<&str>(AId::MaskImage)
+        .into_iter()
+        .flat_map(super::super::mask::split_layers)
+        .filter_map(move |layer| {
+            let id = super::super::mask::parse_url(layer)?.strip_prefix('#')?;
+            node.document().element_by_id(id)
+        })
+}
+
 /// Detects cases like:
 ///
 /// ```xml
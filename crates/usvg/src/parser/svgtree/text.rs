@@ -3,7 +3,7 @@
 
 #![allow(clippy::comparison_chain)]
 
-use roxmltree::Error;
+use crate::{Error, Limits};
 
 use super::{AId, Document, EId, NodeId, NodeKind, SvgNode};
 
@@ -13,25 +13,30 @@ pub(crate) fn parse_svg_text_element<'input>(
     parent: roxmltree::Node<'_, 'input>,
     parent_id: NodeId,
     style_sheet: &simplecss::StyleSheet,
+    limits: &Limits,
     doc: &mut Document<'input>,
 ) -> Result<(), Error> {
     debug_assert_eq!(parent.tag_name().name(), "text");
 
-    let space = if doc.get(parent_id).has_attribute(AId::Space) {
-        get_xmlspace(doc, parent_id, XmlSpace::Default)
-    } else {
-        if let Some(node) = doc
-            .get(parent_id)
-            .ancestors()
-            .find(|n| n.has_attribute(AId::Space))
-        {
-            get_xmlspace(doc, node.id, XmlSpace::Default)
-        } else {
-            XmlSpace::Default
-        }
+    let space = match doc
+        .get(parent_id)
+        .ancestors()
+        .find(|n| n.has_attribute(AId::WhiteSpace) || n.has_attribute(AId::Space))
+    {
+        Some(node) => get_space(doc, node.id, Space::Default),
+        None => Space::Default,
     };
 
-    parse_svg_text_element_impl(parent, parent_id, style_sheet, space, doc)?;
+    let mut prev_char = ' ';
+    parse_svg_text_element_impl(
+        parent,
+        parent_id,
+        style_sheet,
+        space,
+        &mut prev_char,
+        limits,
+        doc,
+    )?;
 
     trim_text_nodes(parent_id, space, doc);
     Ok(())
@@ -41,12 +46,15 @@ fn parse_svg_text_element_impl<'input>(
     parent: roxmltree::Node<'_, 'input>,
     parent_id: NodeId,
     style_sheet: &simplecss::StyleSheet,
-    space: XmlSpace,
+    space: Space,
+    prev_char: &mut char,
+    limits: &Limits,
     doc: &mut Document<'input>,
 ) -> Result<(), Error> {
     for node in parent.children() {
         if node.is_text() {
             let text = trim_text(node.text().unwrap(), space);
+            let text = transform_text(doc, parent_id, text, prev_char);
             doc.append(parent_id, NodeKind::Text(text));
             continue;
         }
@@ -77,9 +85,16 @@ fn parse_svg_text_element_impl<'input>(
             is_tref = true;
         }
 
-        let node_id =
-            super::parse::parse_svg_element(node, parent_id, tag_name, style_sheet, false, doc)?;
-        let space = get_xmlspace(doc, node_id, space);
+        let node_id = super::parse::parse_svg_element(
+            node,
+            parent_id,
+            tag_name,
+            style_sheet,
+            false,
+            limits,
+            doc,
+        )?;
+        let space = get_space(doc, node_id, space);
 
         if is_tref {
             let link_value = node
@@ -89,11 +104,12 @@ fn parse_svg_text_element_impl<'input>(
             if let Some(href) = link_value {
                 if let Some(text) = resolve_tref_text(node.document(), href) {
                     let text = trim_text(&text, space);
+                    let text = transform_text(doc, node_id, text, prev_char);
                     doc.append(node_id, NodeKind::Text(text));
                 }
             }
         } else {
-            parse_svg_text_element_impl(node, node_id, style_sheet, space, doc)?;
+            parse_svg_text_element_impl(node, node_id, style_sheet, space, prev_char, limits, doc)?;
         }
     }
 
@@ -128,20 +144,85 @@ fn resolve_tref_text(xml: &roxmltree::Document, href: &str) -> Option<String> {
     }
 }
 
+/// White space handling mode.
+///
+/// Set by `white-space` or by the deprecated `xml:space`.
 #[derive(Clone, Copy, PartialEq, Debug)]
-enum XmlSpace {
+enum Space {
+    /// Spaces are collapsed and line breaks are converted into spaces.
     Default,
+    /// Line breaks are converted into spaces.
+    ///
+    /// `xml:space="preserve"`.
     Preserve,
+    /// Spaces and line breaks are preserved.
+    Pre,
+    /// Spaces are collapsed, but line breaks are preserved.
+    PreLine,
 }
 
-fn get_xmlspace(doc: &Document, node_id: NodeId, default: XmlSpace) -> XmlSpace {
-    match doc.get(node_id).attribute(AId::Space) {
-        Some("preserve") => XmlSpace::Preserve,
-        Some(_) => XmlSpace::Default,
+impl Space {
+    fn collapses_spaces(self) -> bool {
+        matches!(self, Space::Default | Space::PreLine)
+    }
+}
+
+fn get_space(doc: &Document, node_id: NodeId, default: Space) -> Space {
+    let node = doc.get(node_id);
+
+    // `white-space` takes precedence over `xml:space`.
+    match node.attribute(AId::WhiteSpace) {
+        Some("normal") | Some("nowrap") => return Space::Default,
+        Some("pre") | Some("pre-wrap") | Some("break-spaces") => return Space::Pre,
+        Some("pre-line") => return Space::PreLine,
+        _ => {}
+    }
+
+    match node.attribute(AId::Space) {
+        Some("preserve") => Space::Preserve,
+        Some(_) => Space::Default,
         _ => default,
     }
 }
 
+/// Applies `text-transform`.
+///
+/// `prev_char` is the last character of the previous text node
+/// and is used to detect words split by `tspan`.
+fn transform_text(doc: &Document, node_id: NodeId, text: String, prev_char: &mut char) -> String {
+    let transform = doc
+        .get(node_id)
+        .ancestors()
+        .find(|n| n.has_attribute(AId::TextTransform))
+        .and_then(|n| n.attribute(AId::TextTransform));
+
+    let text = match transform {
+        Some("uppercase") => text.to_uppercase(),
+        Some("lowercase") => text.to_lowercase(),
+        Some("capitalize") => {
+            let mut s = String::with_capacity(text.len());
+            let mut prev = *prev_char;
+            for c in text.chars() {
+                if c.is_alphanumeric() && !prev.is_alphanumeric() && prev != '\'' {
+                    s.extend(c.to_uppercase());
+                } else {
+                    s.push(c);
+                }
+
+                prev = c;
+            }
+            s
+        }
+        _ => text,
+    };
+
+    if let Some(c) = text.chars().next_back() {
+        *prev_char = c;
+    }
+
+    text
+}
+
 trait StrTrim {
     fn remove_first_space(&mut self);
     fn remove_last_space(&mut self);
@@ -165,7 +246,7 @@ impl StrTrim for String {
 /// - 'xml:space' processing
 /// - tabs and newlines removing/replacing
 /// - spaces trimming
-fn trim_text_nodes(text_elem_id: NodeId, xmlspace: XmlSpace, doc: &mut Document) {
+fn trim_text_nodes(text_elem_id: NodeId, space: Space, doc: &mut Document) {
     let mut nodes = Vec::new(); // TODO: allocate only once
     collect_text_nodes(doc.get(text_elem_id), 0, &mut nodes);
 
@@ -177,7 +258,7 @@ fn trim_text_nodes(text_elem_id: NodeId, xmlspace: XmlSpace, doc: &mut Document)
 
         let node_id = nodes[0].0;
 
-        if xmlspace == XmlSpace::Default {
+        if space.collapses_spaces() {
             if let NodeKind::Text(ref mut text) = doc.nodes[node_id.get_usize()].kind {
                 match text.len() {
                     0 => {} // An empty string. Do nothing.
@@ -230,8 +311,8 @@ fn trim_text_nodes(text_elem_id: NodeId, xmlspace: XmlSpace, doc: &mut Document)
 
             // Parent of the text node is always an element node and always exist,
             // so unwrap is safe.
-            let xmlspace1 = get_xmlspace(doc, doc.get(node1_id).parent().unwrap().id, xmlspace);
-            let xmlspace2 = get_xmlspace(doc, doc.get(node2_id).parent().unwrap().id, xmlspace);
+            let space1 = get_space(doc, doc.get(node1_id).parent().unwrap().id, space);
+            let space2 = get_space(doc, doc.get(node2_id).parent().unwrap().id, space);
 
             // >text<..>text<
             //  1  2    3  4
@@ -261,7 +342,7 @@ fn trim_text_nodes(text_elem_id: NodeId, xmlspace: XmlSpace, doc: &mut Document)
             // See text-tspan-02-b.svg for details.
             if depth1 < depth2 {
                 if c3 == Some(b' ') {
-                    if xmlspace2 == XmlSpace::Default {
+                    if space2.collapses_spaces() {
                         if let NodeKind::Text(ref mut text) = doc.nodes[node2_id.get_usize()].kind {
                             text.remove_first_space();
                         }
@@ -269,12 +350,12 @@ fn trim_text_nodes(text_elem_id: NodeId, xmlspace: XmlSpace, doc: &mut Document)
                 }
             } else {
                 if c2 == Some(b' ') && c2 == c3 {
-                    if xmlspace1 == XmlSpace::Default && xmlspace2 == XmlSpace::Default {
+                    if space1.collapses_spaces() && space2.collapses_spaces() {
                         if let NodeKind::Text(ref mut text) = doc.nodes[node1_id.get_usize()].kind {
                             text.remove_last_space();
                         }
                     } else {
-                        if xmlspace1 == XmlSpace::Preserve && xmlspace2 == XmlSpace::Default {
+                        if !space1.collapses_spaces() && space2.collapses_spaces() {
                             if let NodeKind::Text(ref mut text) =
                                 doc.nodes[node2_id.get_usize()].kind
                             {
@@ -290,7 +371,7 @@ fn trim_text_nodes(text_elem_id: NodeId, xmlspace: XmlSpace, doc: &mut Document)
 
             if is_first
                 && c1 == Some(b' ')
-                && xmlspace1 == XmlSpace::Default
+                && space1.collapses_spaces()
                 && !doc.get(node1_id).text().is_empty()
             {
                 // Remove a leading space from a first text node.
@@ -300,7 +381,7 @@ fn trim_text_nodes(text_elem_id: NodeId, xmlspace: XmlSpace, doc: &mut Document)
             } else if is_last
                 && c4 == Some(b' ')
                 && !doc.get(node2_id).text().is_empty()
-                && xmlspace2 == XmlSpace::Default
+                && space2.collapses_spaces()
             {
                 // Remove a trailing space from a last text node.
                 // Also check that 'text2' is not empty already.
@@ -341,20 +422,46 @@ fn collect_text_nodes(parent: SvgNode, depth: usize, nodes: &mut Vec<(NodeId, us
     }
 }
 
-fn trim_text(text: &str, space: XmlSpace) -> String {
+fn trim_text(text: &str, space: Space) -> String {
     let mut s = String::with_capacity(text.len());
 
+    let keep_line_breaks = matches!(space, Space::Pre | Space::PreLine);
+
     let mut prev = '0';
-    for c in text.chars() {
-        // \r, \n and \t should be converted into spaces.
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        // \r\n and \r are normalized into \n when line breaks are preserved.
+        let c = if c == '\r' && keep_line_breaks {
+            if chars.peek() == Some(&'\n') {
+                continue;
+            }
+
+            '\n'
+        } else {
+            c
+        };
+
+        // \r, \n and \t should be converted into spaces, unless line breaks are preserved.
         let c = match c {
+            '\n' if keep_line_breaks => '\n',
             '\r' | '\n' | '\t' => ' ',
             _ => c,
         };
 
-        // Skip continuous spaces.
-        if space == XmlSpace::Default && c == ' ' && c == prev {
-            continue;
+        if space.collapses_spaces() {
+            // Skip continuous spaces.
+            if c == ' ' && c == prev {
+                continue;
+            }
+
+            // Spaces around preserved line breaks are removed.
+            if c == ' ' && prev == '\n' {
+                continue;
+            }
+
+            if c == '\n' && prev == ' ' {
+                s.pop();
+            }
         }
 
         prev = c;
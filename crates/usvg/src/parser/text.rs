@@ -1,11 +1,13 @@
 // Copyright 2019 the Resvg Authors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+use std::str::FromStr;
 use std::sync::Arc;
 
 use kurbo::{ParamCurve, ParamCurveArclen};
 use svgtypes::{parse_font_families, FontFamily, Length, LengthUnit};
 
+use super::converter::SvgColorExt;
 use super::svgtree::{AId, EId, FromValue, SvgNode};
 use super::{converter, style, OptionLog};
 use crate::*;
@@ -21,6 +23,32 @@ impl<'a, 'input: 'a> FromValue<'a, 'input> for TextAnchor {
     }
 }
 
+impl<'a, 'input: 'a> FromValue<'a, 'input> for TextDirection {
+    fn parse(_: SvgNode, _: AId, value: &str) -> Option<Self> {
+        match value {
+            "ltr" => Some(TextDirection::LeftToRight),
+            "rtl" => Some(TextDirection::RightToLeft),
+            _ => None,
+        }
+    }
+}
+
+impl<'a, 'input: 'a> FromValue<'a, 'input> for TextPathSide {
+    fn parse(_: SvgNode, _: AId, value: &str) -> Option<Self> {
+        match value {
+            "left" => Some(TextPathSide::Left),
+            "right" => Some(TextPathSide::Right),
+            _ => None,
+        }
+    }
+}
+
+impl<'a, 'input: 'a> FromValue<'a, 'input> for TextDecorationLineStyle {
+    fn parse(_: SvgNode, _: AId, value: &str) -> Option<Self> {
+        parse_decoration_style(value)
+    }
+}
+
 impl<'a, 'input: 'a> FromValue<'a, 'input> for AlignmentBaseline {
     fn parse(_: SvgNode, _: AId, value: &str) -> Option<Self> {
         match value {
@@ -71,6 +99,19 @@ impl<'a, 'input: 'a> FromValue<'a, 'input> for LengthAdjust {
     }
 }
 
+impl<'a, 'input: 'a> FromValue<'a, 'input> for WhiteSpace {
+    fn parse(_: SvgNode, _: AId, value: &str) -> Option<Self> {
+        match value {
+            "normal" => Some(WhiteSpace::Normal),
+            "nowrap" => Some(WhiteSpace::NoWrap),
+            "pre" => Some(WhiteSpace::Pre),
+            "pre-wrap" | "break-spaces" => Some(WhiteSpace::PreWrap),
+            "pre-line" => Some(WhiteSpace::PreLine),
+            _ => None,
+        }
+    }
+}
+
 impl<'a, 'input: 'a> FromValue<'a, 'input> for FontStyle {
     fn parse(_: SvgNode, _: AId, value: &str) -> Option<Self> {
         match value {
@@ -106,6 +147,8 @@ pub(crate) fn convert(
     let pos_list = resolve_positions_list(text_node, state);
     let rotate_list = resolve_rotate_list(text_node);
     let writing_mode = convert_writing_mode(text_node);
+    let direction = text_node.find_attribute(AId::Direction).unwrap_or_default();
+    let wrap = resolve_text_wrap(text_node, state);
 
     let chunks = collect_text_chunks(text_node, &pos_list, state, cache);
 
@@ -129,6 +172,8 @@ pub(crate) fn convert(
         dy: pos_list.iter().map(|v| v.dy.unwrap_or(0.0)).collect(),
         rotate: rotate_list,
         writing_mode,
+        direction,
+        wrap,
         chunks,
         abs_transform: parent.abs_transform,
         // All fields below will be reset by `text_to_paths`.
@@ -153,6 +198,9 @@ struct IterState {
     split_chunk: bool,
     text_flow: TextFlow,
     chunks: Vec<TextChunk>,
+    /// Currently open bidi embeddings, from the outermost one,
+    /// with an index in the last chunk embeddings, if any.
+    bidi_stack: Vec<(TextDirection, UnicodeBidi, Option<usize>)>,
 }
 
 fn collect_text_chunks(
@@ -167,8 +215,10 @@ fn collect_text_chunks(
         split_chunk: false,
         text_flow: TextFlow::Linear,
         chunks: Vec::new(),
+        bidi_stack: Vec::new(),
     };
 
+    push_bidi_embedding(text_node, &mut iter_state);
     collect_text_chunks_impl(text_node, pos_list, state, cache, &mut iter_state);
 
     iter_state.chunks
@@ -190,7 +240,7 @@ fn collect_text_chunks_impl(
                     continue;
                 }
 
-                match resolve_text_flow(child, state) {
+                match resolve_text_flow(child, state, cache) {
                     Some(v) => {
                         iter_state.text_flow = v;
                     }
@@ -206,7 +256,11 @@ fn collect_text_chunks_impl(
                 iter_state.split_chunk = true;
             }
 
+            let is_embedding = push_bidi_embedding(child, iter_state);
             collect_text_chunks_impl(child, pos_list, state, cache, iter_state);
+            if is_embedding {
+                iter_state.bidi_stack.pop();
+            }
 
             iter_state.text_flow = TextFlow::Linear;
 
@@ -274,6 +328,8 @@ fn collect_text_chunks_impl(
 
         let visibility: Visibility = parent.find_attribute(AId::Visibility).unwrap_or_default();
 
+        let (small_caps, font_features) = resolve_font_features(parent);
+
         let span = TextSpan {
             start: 0,
             end: 0,
@@ -282,8 +338,9 @@ fn collect_text_chunks_impl(
             paint_order,
             font,
             font_size,
-            small_caps: parent.find_attribute::<&str>(AId::FontVariant) == Some("small-caps"),
+            small_caps,
             apply_kerning,
+            font_features,
             decoration: resolve_decoration(parent, state, cache),
             visible: visibility == Visibility::Visible,
             dominant_baseline,
@@ -295,6 +352,9 @@ fn collect_text_chunks_impl(
             word_spacing: parent.resolve_length(AId::WordSpacing, state, 0.0),
             text_length,
             length_adjust: parent.find_attribute(AId::LengthAdjust).unwrap_or_default(),
+            white_space: parent.find_attribute(AId::WhiteSpace).unwrap_or_default(),
+            line_height: resolve_line_height(parent, font_size.get(), state),
+            text_orientation: convert_text_orientation(parent),
         };
 
         let mut is_new_span = true;
@@ -327,7 +387,13 @@ fn collect_text_chunks_impl(
                     spans: vec![span2],
                     text_flow: iter_state.text_flow.clone(),
                     text: c.to_string(),
+                    bidi_embeddings: Vec::new(),
                 });
+
+                // Embeddings are split between chunks.
+                for (_, _, idx) in &mut iter_state.bidi_stack {
+                    *idx = None;
+                }
             } else if is_new_span {
                 // Add this span to the last text chunk.
                 let mut span2 = span.clone();
@@ -349,6 +415,8 @@ fn collect_text_chunks_impl(
                 }
             }
 
+            extend_bidi_embeddings(iter_state, char_len);
+
             is_new_span = false;
             iter_state.chars_count += 1;
             iter_state.chunk_bytes_count += char_len;
@@ -356,18 +424,315 @@ fn collect_text_chunks_impl(
     }
 }
 
-fn resolve_text_flow(node: SvgNode, state: &converter::State) -> Option<TextFlow> {
-    let linked_node = node.attribute::<SvgNode>(AId::Href)?;
-    let path = super::shapes::convert(linked_node, state)?;
+/// Opens a bidi embedding when an element has a non-`normal` `unicode-bidi`.
+///
+/// Returns `true` when an embedding was opened.
+fn push_bidi_embedding(node: SvgNode, iter_state: &mut IterState) -> bool {
+    let unicode_bidi = match node.attribute(AId::UnicodeBidi) {
+        Some("embed") => UnicodeBidi::Embed,
+        Some("isolate") => UnicodeBidi::Isolate,
+        Some("bidi-override") => UnicodeBidi::BidiOverride,
+        Some("isolate-override") => UnicodeBidi::IsolateOverride,
+        Some("plaintext") => UnicodeBidi::Plaintext,
+        _ => return false,
+    };
+
+    let direction = node.find_attribute(AId::Direction).unwrap_or_default();
+    iter_state.bidi_stack.push((direction, unicode_bidi, None));
+    true
+}
+
+/// Extends all open bidi embeddings with the last character of the last chunk.
+fn extend_bidi_embeddings(iter_state: &mut IterState, char_len: usize) {
+    let Some(chunk) = iter_state.chunks.last_mut() else {
+        return;
+    };
+
+    let end = iter_state.chunk_bytes_count + char_len;
+    for (direction, unicode_bidi, idx) in &mut iter_state.bidi_stack {
+        match idx {
+            Some(idx) => chunk.bidi_embeddings[*idx].end = end,
+            None => {
+                *idx = Some(chunk.bidi_embeddings.len());
+                chunk.bidi_embeddings.push(BidiEmbedding {
+                    start: iter_state.chunk_bytes_count,
+                    end,
+                    direction: *direction,
+                    unicode_bidi: *unicode_bidi,
+                });
+            }
+        }
+    }
+}
+
+/// Resolves `line-height` to user units.
+///
+/// Returns `None` for `normal`.
+fn resolve_line_height(node: SvgNode, font_size: f32, state: &converter::State) -> Option<f32> {
+    let n = node
+        .ancestors()
+        .find(|n| n.has_attribute(AId::LineHeight))?;
+    let length = match n.attribute::<&str>(AId::LineHeight)? {
+        "normal" => return None,
+        value => match value.parse::<Length>() {
+            Ok(v) => v,
+            Err(_) => {
+                n.warn(
+                    DiagnosticCode::InvalidValue,
+                    format!("Failed to parse {} value: '{}'.", AId::LineHeight, value),
+                );
+                return None;
+            }
+        },
+    };
+
+    // Unlike lengths and percentages, numbers are inherited as is
+    // and are relative to the current font size.
+    let line_height = match length.unit {
+        LengthUnit::None => length.number as f32 * font_size,
+        LengthUnit::Percent => {
+            length.number as f32 * super::units::resolve_font_size(n, state) / 100.0
+        }
+        _ => super::units::convert_user_length(length, n, AId::LineHeight, state),
+    };
+
+    // Negative values are invalid.
+    if line_height < 0.0 {
+        return None;
+    }
+
+    Some(line_height)
+}
+
+/// Resolves `font-variant-*` and `font-feature-settings` into a list of OpenType features.
+///
+/// Returns the small caps flag separately, since it also affects font fallback.
+fn resolve_font_features(node: SvgNode) -> (bool, Vec<FontFeature>) {
+    let mut small_caps = false;
+    let mut features = Vec::new();
+
+    for aid in [
+        AId::FontVariantLigatures,
+        AId::FontVariantPosition,
+        AId::FontVariantCaps,
+        AId::FontVariantNumeric,
+        AId::FontVariantEastAsian,
+    ] {
+        // Each longhand is taken from the closest element that sets either it or the shorthand.
+        // The shorthand resets all longhands, even the ones it doesn't mention.
+        let n = match node
+            .ancestors()
+            .find(|n| n.has_attribute(aid) || n.has_attribute(AId::FontVariant))
+        {
+            Some(n) => n,
+            None => continue,
+        };
+
+        let declared = if n.has_attribute(aid) {
+            aid
+        } else {
+            AId::FontVariant
+        };
+        let value = n.attribute::<&str>(declared).unwrap_or("normal");
 
-    // The reference path's transform needs to be applied
-    let transform = linked_node.resolve_transform(AId::Transform, state);
-    let path = if !transform.is_identity() {
-        let mut path_copy = path.as_ref().clone();
-        path_copy = path_copy.transform(transform)?;
-        Arc::new(path_copy)
+        let mut list = Vec::new();
+        let mut is_small_caps = false;
+        let mut valid = true;
+        for keyword in value.split_ascii_whitespace() {
+            if keyword == "normal" {
+                continue;
+            }
+
+            let longhand = variant_longhand(keyword);
+            if longhand.is_none() || (declared != AId::FontVariant && longhand != Some(aid)) {
+                valid = false;
+                break;
+            }
+
+            // The shorthand contains keywords of all longhands.
+            if longhand != Some(aid) {
+                continue;
+            }
+
+            if keyword == "small-caps" {
+                is_small_caps = true;
+            } else {
+                push_variant_features(keyword, &mut list);
+            }
+        }
+
+        if !valid {
+            n.warn(
+                DiagnosticCode::InvalidValue,
+                format!("Failed to parse {} value: '{}'.", declared, value),
+            );
+            continue;
+        }
+
+        small_caps |= is_small_caps;
+        features.extend(list);
+    }
+
+    // `font-feature-settings` is applied last and overrides everything else.
+    if let Some(n) = node
+        .ancestors()
+        .find(|n| n.has_attribute(AId::FontFeatureSettings))
+    {
+        let value = n
+            .attribute::<&str>(AId::FontFeatureSettings)
+            .unwrap_or("normal");
+        match parse_font_feature_settings(value) {
+            Some(list) => features.extend(list),
+            None => n.warn(
+                DiagnosticCode::InvalidValue,
+                format!(
+                    "Failed to parse {} value: '{}'.",
+                    AId::FontFeatureSettings,
+                    value
+                ),
+            ),
+        }
+    }
+
+    (small_caps, features)
+}
+
+/// Returns a `font-variant-*` longhand a keyword belongs to.
+fn variant_longhand(keyword: &str) -> Option<AId> {
+    match keyword {
+        "none"
+        | "common-ligatures"
+        | "no-common-ligatures"
+        | "discretionary-ligatures"
+        | "no-discretionary-ligatures"
+        | "historical-ligatures"
+        | "no-historical-ligatures"
+        | "contextual"
+        | "no-contextual" => Some(AId::FontVariantLigatures),
+        "sub" | "super" => Some(AId::FontVariantPosition),
+        "small-caps" | "all-small-caps" | "petite-caps" | "all-petite-caps" | "unicase"
+        | "titling-caps" => Some(AId::FontVariantCaps),
+        "lining-nums" | "oldstyle-nums" | "proportional-nums" | "tabular-nums"
+        | "diagonal-fractions" | "stacked-fractions" | "ordinal" | "slashed-zero" => {
+            Some(AId::FontVariantNumeric)
+        }
+        "jis78" | "jis83" | "jis90" | "jis04" | "simplified" | "traditional" | "full-width"
+        | "proportional-width" | "ruby" => Some(AId::FontVariantEastAsian),
+        _ => None,
+    }
+}
+
+/// Appends features enabled by a single `font-variant-*` keyword.
+fn push_variant_features(keyword: &str, features: &mut Vec<FontFeature>) {
+    let list: &[(&[u8; 4], u32)] = match keyword {
+        // font-variant-ligatures
+        "none" => &[
+            (b"liga", 0),
+            (b"clig", 0),
+            (b"dlig", 0),
+            (b"hlig", 0),
+            (b"calt", 0),
+        ],
+        "common-ligatures" => &[(b"liga", 1), (b"clig", 1)],
+        "no-common-ligatures" => &[(b"liga", 0), (b"clig", 0)],
+        "discretionary-ligatures" => &[(b"dlig", 1)],
+        "no-discretionary-ligatures" => &[(b"dlig", 0)],
+        "historical-ligatures" => &[(b"hlig", 1)],
+        "no-historical-ligatures" => &[(b"hlig", 0)],
+        "contextual" => &[(b"calt", 1)],
+        "no-contextual" => &[(b"calt", 0)],
+        // font-variant-position
+        "sub" => &[(b"subs", 1)],
+        "super" => &[(b"sups", 1)],
+        // font-variant-caps
+        "all-small-caps" => &[(b"smcp", 1), (b"c2sc", 1)],
+        "petite-caps" => &[(b"pcap", 1)],
+        "all-petite-caps" => &[(b"pcap", 1), (b"c2pc", 1)],
+        "unicase" => &[(b"unic", 1)],
+        "titling-caps" => &[(b"titl", 1)],
+        // font-variant-numeric
+        "lining-nums" => &[(b"lnum", 1)],
+        "oldstyle-nums" => &[(b"onum", 1)],
+        "proportional-nums" => &[(b"pnum", 1)],
+        "tabular-nums" => &[(b"tnum", 1)],
+        "diagonal-fractions" => &[(b"frac", 1)],
+        "stacked-fractions" => &[(b"afrc", 1)],
+        "ordinal" => &[(b"ordn", 1)],
+        "slashed-zero" => &[(b"zero", 1)],
+        // font-variant-east-asian
+        "jis78" => &[(b"jp78", 1)],
+        "jis83" => &[(b"jp83", 1)],
+        "jis90" => &[(b"jp90", 1)],
+        "jis04" => &[(b"jp04", 1)],
+        "simplified" => &[(b"smpl", 1)],
+        "traditional" => &[(b"trad", 1)],
+        "full-width" => &[(b"fwid", 1)],
+        "proportional-width" => &[(b"pwid", 1)],
+        "ruby" => &[(b"ruby", 1)],
+        _ => return,
+    };
+
+    features.extend(
+        list.iter()
+            .map(|(tag, value)| FontFeature::new(**tag, *value)),
+    );
+}
+
+/// Parses a `font-feature-settings` value.
+///
+/// Returns `None` when any of the features is invalid,
+/// in which case the whole declaration must be ignored.
+fn parse_font_feature_settings(value: &str) -> Option<Vec<FontFeature>> {
+    let value = value.trim();
+    if value == "normal" {
+        return Some(Vec::new());
+    }
+
+    let mut features = Vec::new();
+    for item in value.split(',') {
+        let (tag, rest) = parse_feature_tag(item)?;
+        let value = match rest.trim() {
+            "" | "on" => 1,
+            "off" => 0,
+            n => n.parse::<u32>().ok()?,
+        };
+
+        features.push(FontFeature::new(tag, value));
+    }
+
+    Some(features)
+}
+
+fn resolve_text_flow(
+    node: SvgNode,
+    state: &converter::State,
+    cache: &mut converter::Cache,
+) -> Option<TextFlow> {
+    // The `path` attribute takes precedence over `href`,
+    // unless it contains an error.
+    let inline_path = node
+        .attribute::<&str>(AId::Path)
+        .filter(|value| svgtypes::PathParser::from(*value).all(|segment| segment.is_ok()))
+        .and_then(super::shapes::convert_path_data);
+
+    let (id, path) = if let Some(path) = inline_path {
+        (cache.gen_text_path_id(), path)
     } else {
-        path
+        let linked_node = node.attribute::<SvgNode>(AId::Href)?;
+        let path = super::shapes::convert(linked_node, state)?;
+
+        // The reference path's transform needs to be applied
+        let transform = linked_node.resolve_transform(AId::Transform, state);
+        let path = if !transform.is_identity() {
+            let mut path_copy = path.as_ref().clone();
+            path_copy = path_copy.transform(transform)?;
+            Arc::new(path_copy)
+        } else {
+            path
+        };
+
+        let id = NonEmptyString::new(linked_node.element_id().to_string())?;
+        (id, path)
     };
 
     let start_offset: Length = node.attribute(AId::StartOffset).unwrap_or_default();
@@ -380,19 +745,25 @@ fn resolve_text_flow(node: SvgNode, state: &converter::State) -> Option<TextFlow
         node.resolve_length(AId::StartOffset, state, 0.0)
     };
 
-    let id = NonEmptyString::new(linked_node.element_id().to_string())?;
     Some(TextFlow::Path(Arc::new(TextPath {
         id,
         start_offset,
+        side: node.attribute(AId::Side).unwrap_or_default(),
         path,
     })))
 }
 
 fn convert_font(node: SvgNode, state: &converter::State) -> Font {
     let style: FontStyle = node.find_attribute(AId::FontStyle).unwrap_or_default();
-    let stretch = conv_font_stretch(node);
+    let (stretch, stretch_percentage) = conv_font_stretch(node);
     let weight = resolve_font_weight(node);
 
+    let mut variations = Vec::new();
+    if let Some(percentage) = stretch_percentage {
+        variations.push(FontVariation::new(*b"wdth", percentage));
+    }
+    variations.extend(resolve_font_variations(node));
+
     let font_families = if let Some(n) = node.ancestors().find(|n| n.has_attribute(AId::FontFamily))
     {
         n.attribute(AId::FontFamily).unwrap_or("")
@@ -403,11 +774,14 @@ fn convert_font(node: SvgNode, state: &converter::State) -> Font {
     let mut families = parse_font_families(font_families)
         .ok()
         .log_none(|| {
-            log::warn!(
-                "Failed to parse {} value: '{}'. Falling back to {}.",
-                AId::FontFamily,
-                font_families,
-                state.opt.font_family
+            node.warn(
+                DiagnosticCode::InvalidValue,
+                format!(
+                    "Failed to parse {} value: '{}'. Falling back to {}.",
+                    AId::FontFamily,
+                    font_families,
+                    state.opt.font_family
+                ),
             );
         })
         .unwrap_or_default();
@@ -421,28 +795,142 @@ fn convert_font(node: SvgNode, state: &converter::State) -> Font {
         style,
         stretch,
         weight,
+        variations,
     }
 }
 
+/// Resolves `font-stretch`.
+///
+/// Percentages are mapped to the closest keyword, which is used for font matching.
+/// A percentage that doesn't match a keyword exactly is returned as well,
+/// so it can be applied to the `wdth` axis of a variable font.
 // TODO: properly resolve narrower/wider
-fn conv_font_stretch(node: SvgNode) -> FontStretch {
-    if let Some(n) = node.ancestors().find(|n| n.has_attribute(AId::FontStretch)) {
-        match n.attribute(AId::FontStretch).unwrap_or("") {
-            "narrower" | "condensed" => FontStretch::Condensed,
-            "ultra-condensed" => FontStretch::UltraCondensed,
-            "extra-condensed" => FontStretch::ExtraCondensed,
-            "semi-condensed" => FontStretch::SemiCondensed,
-            "semi-expanded" => FontStretch::SemiExpanded,
-            "wider" | "expanded" => FontStretch::Expanded,
-            "extra-expanded" => FontStretch::ExtraExpanded,
-            "ultra-expanded" => FontStretch::UltraExpanded,
-            _ => FontStretch::Normal,
+fn conv_font_stretch(node: SvgNode) -> (FontStretch, Option<f32>) {
+    let n = match node.ancestors().find(|n| n.has_attribute(AId::FontStretch)) {
+        Some(n) => n,
+        None => return (FontStretch::Normal, None),
+    };
+
+    let stretch = match n.attribute(AId::FontStretch).unwrap_or("") {
+        "narrower" | "condensed" => FontStretch::Condensed,
+        "ultra-condensed" => FontStretch::UltraCondensed,
+        "extra-condensed" => FontStretch::ExtraCondensed,
+        "semi-condensed" => FontStretch::SemiCondensed,
+        "semi-expanded" => FontStretch::SemiExpanded,
+        "wider" | "expanded" => FontStretch::Expanded,
+        "extra-expanded" => FontStretch::ExtraExpanded,
+        "ultra-expanded" => FontStretch::UltraExpanded,
+        value => {
+            let percentage = value
+                .strip_suffix('%')
+                .and_then(|v| v.trim().parse::<f32>().ok())
+                .filter(|v| v.is_finite() && *v >= 0.0);
+            match percentage {
+                Some(percentage) => return stretch_from_percentage(percentage),
+                None => FontStretch::Normal,
+            }
+        }
+    };
+
+    (stretch, None)
+}
+
+pub(crate) fn stretch_from_percentage(percentage: f32) -> (FontStretch, Option<f32>) {
+    const KEYWORDS: &[FontStretch] = &[
+        FontStretch::UltraCondensed,
+        FontStretch::ExtraCondensed,
+        FontStretch::Condensed,
+        FontStretch::SemiCondensed,
+        FontStretch::Normal,
+        FontStretch::SemiExpanded,
+        FontStretch::Expanded,
+        FontStretch::ExtraExpanded,
+        FontStretch::UltraExpanded,
+    ];
+
+    let mut stretch = FontStretch::Normal;
+    for keyword in KEYWORDS {
+        let distance = (keyword.to_percentage() - percentage).abs();
+        if distance < (stretch.to_percentage() - percentage).abs() {
+            stretch = *keyword;
         }
+    }
+
+    if stretch.to_percentage() == percentage {
+        (stretch, None)
     } else {
-        FontStretch::Normal
+        (stretch, Some(percentage))
+    }
+}
+
+/// Resolves `font-variation-settings`.
+fn resolve_font_variations(node: SvgNode) -> Vec<FontVariation> {
+    let n = match node
+        .ancestors()
+        .find(|n| n.has_attribute(AId::FontVariationSettings))
+    {
+        Some(n) => n,
+        None => return Vec::new(),
+    };
+
+    let value = n
+        .attribute::<&str>(AId::FontVariationSettings)
+        .unwrap_or("normal");
+    match parse_font_variation_settings(value) {
+        Some(list) => list,
+        None => {
+            n.warn(
+                DiagnosticCode::InvalidValue,
+                format!(
+                    "Failed to parse {} value: '{}'.",
+                    AId::FontVariationSettings,
+                    value
+                ),
+            );
+            Vec::new()
+        }
     }
 }
 
+/// Parses a `font-variation-settings` value.
+///
+/// Returns `None` when any of the axes is invalid,
+/// in which case the whole declaration must be ignored.
+fn parse_font_variation_settings(value: &str) -> Option<Vec<FontVariation>> {
+    let value = value.trim();
+    if value == "normal" {
+        return Some(Vec::new());
+    }
+
+    let mut variations = Vec::new();
+    for item in value.split(',') {
+        let (tag, rest) = parse_feature_tag(item)?;
+        let value = rest.trim().parse::<f32>().ok().filter(|v| v.is_finite())?;
+        variations.push(FontVariation::new(tag, value));
+    }
+
+    Some(variations)
+}
+
+/// Parses a quoted OpenType tag at the start of a `font-*-settings` item.
+///
+/// Returns the tag and the rest of the item.
+fn parse_feature_tag(item: &str) -> Option<([u8; 4], &str)> {
+    let item = item.trim();
+    let quote = item.chars().next()?;
+    if quote != '"' && quote != '\'' {
+        return None;
+    }
+
+    let end = item[1..].find(quote)? + 1;
+    let tag: [u8; 4] = item.as_bytes()[1..end].try_into().ok()?;
+    if !tag.iter().all(|c| (0x20..=0x7E).contains(c)) {
+        return None;
+    }
+
+    Some((tag, &item[end + 1..]))
+}
+
 fn resolve_font_weight(node: SvgNode) -> u16 {
     fn bound(min: usize, val: usize, max: usize) -> usize {
         std::cmp::max(min, std::cmp::min(max, val))
@@ -455,15 +943,6 @@ fn resolve_font_weight(node: SvgNode) -> u16 {
         weight = match n.attribute(AId::FontWeight).unwrap_or("") {
             "normal" => 400,
             "bold" => 700,
-            "100" => 100,
-            "200" => 200,
-            "300" => 300,
-            "400" => 400,
-            "500" => 500,
-            "600" => 600,
-            "700" => 700,
-            "800" => 800,
-            "900" => 900,
             "bolder" => {
                 // By the CSS2 spec the default value should be 400
                 // so `bolder` will result in 500.
@@ -482,9 +961,13 @@ fn resolve_font_weight(node: SvgNode) -> u16 {
                 // we will follow such behavior for now.
                 let step = if weight == 400 { 200 } else { 100 };
 
-                bound(100, weight - step, 900)
+                bound(100, weight.saturating_sub(step), 900)
             }
-            _ => weight,
+            // CSS Fonts 4 allows any number in the 1..=1000 range.
+            value => match value.parse::<f32>() {
+                Ok(n) if (1.0..=1000.0).contains(&n) => n.round() as usize,
+                _ => weight,
+            },
         };
     }
 
@@ -635,19 +1118,20 @@ fn resolve_rotate_list(text_node: SvgNode) -> Vec<f32> {
 }
 
 /// Resolves node's `text-decoration` property.
+///
+/// Supports both the SVG 1.1 syntax and the [CSS Text Decoration Level 3] one,
+/// i.e. the `text-decoration` shorthand and the `text-decoration-line`,
+/// `text-decoration-style` and `text-decoration-color` longhands.
+/// Longhands take precedence over the shorthand declared on the same element.
+///
+/// [CSS Text Decoration Level 3]: https://www.w3.org/TR/css-text-decor-3/
 fn resolve_decoration(
     tspan: SvgNode,
     state: &converter::State,
     cache: &mut converter::Cache,
 ) -> TextDecoration {
-    // Checks if a decoration is present in a single node.
-    fn find_decoration(node: SvgNode, value: &str) -> bool {
-        if let Some(str_value) = node.attribute::<&str>(AId::TextDecoration) {
-            str_value.split(' ').any(|v| v == value)
-        } else {
-            false
-        }
-    }
+    let text_node = tspan.ancestors().find(|n| n.tag_name() == Some(EId::Text));
+    let writing_mode = text_node.map_or(WritingMode::LeftToRight, convert_writing_mode);
 
     // The algorithm is as follows: First, we check whether the given text decoration appears in ANY
     // ancestor, i.e. it can also appear in ancestors outside of the <text> element. If the text
@@ -655,38 +1139,217 @@ fn resolve_decoration(
     // need to find the corresponding fill/stroke for it. To do this, we iterate through all
     // ancestors (i.e. tspans) until we find the text decoration declared. If not, we will
     // stop at latest at the text node, and use its fill/stroke.
-    let mut gen_style = |text_decoration: &str| {
-        if !tspan
+    //
+    // All other decoration properties are taken from the element that declared the decoration.
+    let mut gen_style = |line: DecorationLine| {
+        let (decl_node, decl) = tspan.ancestors().find_map(|n| {
+            let decl = parse_decoration_declaration(n);
+            decl.lines.contains(&line).then_some((n, decl))
+        })?;
+
+        let paint_node = tspan
             .ancestors()
-            .any(|n| find_decoration(n, text_decoration))
-        {
-            return None;
-        }
+            .find(|n| n == &decl_node || n.tag_name() == Some(EId::Text))
+            .unwrap_or(decl_node);
+
+        // Clip path children are always black, so the color is ignored there.
+        let color = decl.color.filter(|_| state.parent_clip_path.is_none());
+        let fill = if decl_node.has_attribute(AId::TextDecorationFill) {
+            style::resolve_fill_with_paint(decl_node, AId::TextDecorationFill, true, state, cache)
+        } else if let Some(color) = color {
+            let (color, alpha) = color.split_alpha();
+            let fill_opacity = decl_node
+                .find_attribute::<Opacity>(AId::FillOpacity)
+                .unwrap_or(Opacity::ONE);
+            Some(Fill {
+                paint: Paint::Color(color),
+                opacity: alpha * fill_opacity,
+                rule: FillRule::default(),
+                context_element: None,
+            })
+        } else {
+            style::resolve_fill(paint_node, true, state, cache)
+        };
 
-        let mut fill_node = None;
-        let mut stroke_node = None;
+        let stroke = if decl_node.has_attribute(AId::TextDecorationStroke) {
+            style::resolve_stroke_with_paint(
+                decl_node,
+                AId::TextDecorationStroke,
+                true,
+                state,
+                cache,
+            )
+        } else {
+            style::resolve_stroke(paint_node, true, state, cache)
+        };
 
-        for node in tspan.ancestors() {
-            if find_decoration(node, text_decoration) || node.tag_name() == Some(EId::Text) {
-                fill_node = fill_node.map_or(Some(node), Some);
-                stroke_node = stroke_node.map_or(Some(node), Some);
-                break;
+        let underline_position = match decl_node.find_attribute::<&str>(AId::TextUnderlinePosition)
+        {
+            Some(value) => {
+                let mut keywords = value.split_whitespace();
+                match writing_mode {
+                    WritingMode::LeftToRight if keywords.any(|v| v == "under") => {
+                        TextUnderlinePosition::Under
+                    }
+                    WritingMode::TopToBottom => {
+                        if keywords.clone().any(|v| v == "right") {
+                            TextUnderlinePosition::Right
+                        } else if keywords.any(|v| v == "left") {
+                            TextUnderlinePosition::Left
+                        } else {
+                            TextUnderlinePosition::Auto
+                        }
+                    }
+                    _ => TextUnderlinePosition::Auto,
+                }
             }
-        }
+            None => TextUnderlinePosition::Auto,
+        };
+
+        let skip_ink = decl_node.find_attribute::<&str>(AId::TextDecorationSkipInk) != Some("none");
 
         Some(TextDecorationStyle {
-            fill: fill_node.and_then(|node| style::resolve_fill(node, true, state, cache)),
-            stroke: stroke_node.and_then(|node| style::resolve_stroke(node, true, state, cache)),
+            fill,
+            stroke,
+            line_style: decl.style,
+            underline_position,
+            skip_ink,
         })
     };
 
     TextDecoration {
-        underline: gen_style("underline"),
-        overline: gen_style("overline"),
-        line_through: gen_style("line-through"),
+        underline: gen_style(DecorationLine::Underline),
+        overline: gen_style(DecorationLine::Overline),
+        line_through: gen_style(DecorationLine::LineThrough),
     }
 }
 
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum DecorationLine {
+    Underline,
+    Overline,
+    LineThrough,
+}
+
+/// A text decoration declared by a single element.
+#[derive(Default)]
+struct DecorationDeclaration {
+    lines: Vec<DecorationLine>,
+    style: TextDecorationLineStyle,
+    color: Option<svgtypes::Color>,
+}
+
+/// Parses the `text-decoration` shorthand and its longhands declared on the node.
+///
+/// An invalid shorthand is ignored as a whole.
+fn parse_decoration_declaration(node: SvgNode) -> DecorationDeclaration {
+    let mut decl = DecorationDeclaration::default();
+
+    if let Some(value) = node.attribute::<&str>(AId::TextDecoration) {
+        if let Some(shorthand) = parse_decoration_shorthand(value) {
+            decl = shorthand;
+        }
+    }
+
+    if let Some(value) = node.attribute::<&str>(AId::TextDecorationLine) {
+        let lines: Option<Vec<_>> = value
+            .split_whitespace()
+            .map(parse_decoration_line)
+            .collect();
+        decl.lines = lines.unwrap_or_default().into_iter().flatten().collect();
+    }
+
+    if let Some(style) = node.attribute(AId::TextDecorationStyle) {
+        decl.style = style;
+    }
+
+    if let Some(value) = node.attribute::<&str>(AId::TextDecorationColor) {
+        decl.color = parse_decoration_color(value).unwrap_or_default();
+    }
+
+    decl
+}
+
+fn parse_decoration_shorthand(value: &str) -> Option<DecorationDeclaration> {
+    let mut decl = DecorationDeclaration::default();
+    let mut has_style = false;
+    let mut has_color = false;
+
+    // Colors like `rgb(0, 0, 0)` can contain spaces.
+    let mut tokens = Vec::new();
+    let mut depth = 0;
+    let mut token_start = None;
+    for (i, c) in value.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            _ => {}
+        }
+
+        if c.is_whitespace() && depth == 0 {
+            if let Some(start) = token_start.take() {
+                tokens.push(&value[start..i]);
+            }
+        } else if token_start.is_none() {
+            token_start = Some(i);
+        }
+    }
+    if let Some(start) = token_start {
+        tokens.push(&value[start..]);
+    }
+
+    for token in tokens {
+        if let Some(line) = parse_decoration_line(token) {
+            decl.lines.extend(line);
+        } else if let (false, Some(style)) = (has_style, parse_decoration_style(token)) {
+            decl.style = style;
+            has_style = true;
+        } else if let (false, Some(color)) = (has_color, parse_decoration_color(token)) {
+            decl.color = color;
+            has_color = true;
+        } else {
+            return None;
+        }
+    }
+
+    Some(decl)
+}
+
+/// Parses a `text-decoration-line` keyword.
+///
+/// `none` and `blink` are valid, but do not produce any lines.
+fn parse_decoration_line(value: &str) -> Option<Option<DecorationLine>> {
+    match value {
+        "underline" => Some(Some(DecorationLine::Underline)),
+        "overline" => Some(Some(DecorationLine::Overline)),
+        "line-through" => Some(Some(DecorationLine::LineThrough)),
+        "none" | "blink" => Some(None),
+        _ => None,
+    }
+}
+
+fn parse_decoration_style(value: &str) -> Option<TextDecorationLineStyle> {
+    match value {
+        "solid" => Some(TextDecorationLineStyle::Solid),
+        "double" => Some(TextDecorationLineStyle::Double),
+        "dotted" => Some(TextDecorationLineStyle::Dotted),
+        "dashed" => Some(TextDecorationLineStyle::Dashed),
+        "wavy" => Some(TextDecorationLineStyle::Wavy),
+        _ => None,
+    }
+}
+
+/// Parses a `text-decoration-color` value.
+///
+/// `currentColor` is valid, but means that the decoration fill should be used.
+fn parse_decoration_color(value: &str) -> Option<Option<svgtypes::Color>> {
+    if value == "currentColor" {
+        return Some(None);
+    }
+
+    svgtypes::Color::from_str(value).ok().map(Some)
+}
+
 fn convert_baseline_shift(node: SvgNode, state: &converter::State) -> Vec<BaselineShift> {
     let mut shift = Vec::new();
     let nodes: Vec<_> = node
@@ -770,6 +1433,133 @@ fn convert_writing_mode(text_node: SvgNode) -> WritingMode {
     }
 }
 
+/// Resolves the `text-orientation` property.
+///
+/// The deprecated `glyph-orientation-vertical` is mapped onto it as well:
+/// `auto` is `mixed`, `0deg` is `upright` and `90deg` is `sideways`.
+/// Other angles are not supported and fallback to `mixed`.
+fn convert_text_orientation(node: SvgNode) -> TextOrientation {
+    let node = node.ancestors().find(|n| {
+        n.has_attribute(AId::TextOrientation) || n.has_attribute(AId::GlyphOrientationVertical)
+    });
+    let Some(node) = node else {
+        return TextOrientation::Mixed;
+    };
+
+    if let Some(value) = node.attribute(AId::TextOrientation) {
+        return match value {
+            "upright" => TextOrientation::Upright,
+            "sideways" | "sideways-right" => TextOrientation::Sideways,
+            _ => TextOrientation::Mixed,
+        };
+    }
+
+    match node.attribute(AId::GlyphOrientationVertical) {
+        Some("0" | "0deg") => TextOrientation::Upright,
+        Some("90" | "90deg") => TextOrientation::Sideways,
+        _ => TextOrientation::Mixed,
+    }
+}
+
+/// Resolves the `shape-inside` and `inline-size` properties.
+///
+/// `shape-inside` takes precedence, unless it has no valid shapes.
+fn resolve_text_wrap(text_node: SvgNode, state: &converter::State) -> Option<TextWrap> {
+    if let Some(inside) = resolve_text_shapes(text_node, AId::ShapeInside, state) {
+        let subtract = resolve_text_shapes(text_node, AId::ShapeSubtract, state);
+        return Some(TextWrap::Shape(Arc::new(TextShape::new(
+            inside,
+            subtract,
+            text_node.resolve_length(AId::ShapePadding, state, 0.0),
+            text_node.resolve_length(AId::ShapeMargin, state, 0.0),
+        ))));
+    }
+
+    let inline_size = text_node.resolve_length(AId::InlineSize, state, 0.0);
+    if inline_size > 0.0 {
+        return Some(TextWrap::InlineSize(inline_size));
+    }
+
+    None
+}
+
+/// Merges all shapes referenced by a `shape-inside` or `shape-subtract` list.
+///
+/// Only references to basic shapes and paths are supported.
+fn resolve_text_shapes(
+    text_node: SvgNode,
+    aid: AId,
+    state: &converter::State,
+) -> Option<Arc<tiny_skia_path::Path>> {
+    let value: &str = text_node.attribute(aid)?;
+
+    let mut builder = tiny_skia_path::PathBuilder::new();
+    let mut rest = value;
+    while let Some(start) = rest.find("url(") {
+        let end = match rest[start..].find(')') {
+            Some(end) => start + end + 1,
+            None => break,
+        };
+
+        let func = &rest[start..end];
+        rest = &rest[end..];
+
+        let link = svgtypes::FuncIRI::from_str(func)
+            .ok()
+            .and_then(|iri| text_node.document().element_by_id(iri.0));
+        let link = match link {
+            Some(v) => v,
+            None => {
+                text_node.warn(
+                    DiagnosticCode::InvalidReference,
+                    format!("'{}' in {} is not a valid link.", func, aid),
+                );
+                continue;
+            }
+        };
+
+        let is_shape = matches!(
+            link.tag_name(),
+            Some(
+                EId::Rect
+                    | EId::Circle
+                    | EId::Ellipse
+                    | EId::Line
+                    | EId::Polyline
+                    | EId::Polygon
+                    | EId::Path
+            )
+        );
+        if !is_shape {
+            text_node.warn(
+                DiagnosticCode::InvalidReference,
+                format!("{} can reference only shapes.", aid),
+            );
+            continue;
+        }
+
+        let path = match super::shapes::convert(link, state) {
+            Some(v) => v,
+            None => continue,
+        };
+
+        // The referenced shape transform must be applied.
+        let transform = link.resolve_transform(AId::Transform, state);
+        if let Some(path) = path.as_ref().clone().transform(transform) {
+            builder.push_path(&path);
+        }
+    }
+
+    if value.trim() != "none" && value.trim() != "auto" && !value.contains("url(") {
+        text_node.warn(
+            DiagnosticCode::Unsupported,
+            format!("{} value '{}' is not supported.", aid, value),
+        );
+    }
+
+    builder.finish().map(Arc::new)
+}
+
 fn path_length(path: &tiny_skia_path::Path) -> f64 {
     let mut prev_mx = path.points()[0].x;
     let mut prev_my = path.points()[0].y;
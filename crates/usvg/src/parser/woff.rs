@@ -0,0 +1,519 @@
+// Copyright 2025 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! WOFF and WOFF2 decoding.
+//!
+//! Both formats are converted back into plain TrueType/OpenType data,
+//! which can be loaded into `fontdb`.
+
+use std::io::Read;
+
+use super::sfnt;
+
+/// Decodes WOFF and WOFF2 fonts.
+///
+/// Returns `None` for malformed data or when `data` is not a WOFF font.
+pub(crate) fn decode(data: &[u8]) -> Option<Vec<u8>> {
+    match data.get(0..4)? {
+        b"wOFF" => decode_woff(data),
+        b"wOF2" => decode_woff2(data),
+        _ => None,
+    }
+}
+
+/// Checks that data starts with a WOFF or a WOFF2 signature.
+pub(crate) fn is_woff(data: &[u8]) -> bool {
+    data.starts_with(b"wOFF") || data.starts_with(b"wOF2")
+}
+
+// https://www.w3.org/TR/WOFF/
+fn decode_woff(data: &[u8]) -> Option<Vec<u8>> {
+    let mut s = Stream::new(data);
+    s.skip(4); // signature
+    let flavor = s.read_u32()?;
+    s.skip(4); // length
+    let num_tables = s.read_u16()?;
+    s.skip(2); // reserved
+    s.skip(4); // totalSfntSize
+    s.skip(24); // versions, metadata and private data
+
+    let mut tables = Vec::with_capacity(num_tables as usize);
+    for _ in 0..num_tables {
+        let tag = s.read_tag()?;
+        let offset = s.read_u32()? as usize;
+        let comp_length = s.read_u32()? as usize;
+        let orig_length = s.read_u32()? as usize;
+        s.skip(4); // checksum
+
+        let table_data = data.get(offset..offset.checked_add(comp_length)?)?;
+        let table_data = if comp_length < orig_length {
+            // `origLength` is not trusted, so the buffer grows with the actual output.
+            let mut decoded = Vec::new();
+            flate2::read::ZlibDecoder::new(table_data)
+                .take(orig_length as u64)
+                .read_to_end(&mut decoded)
+                .ok()?;
+            decoded
+        } else {
+            table_data.to_vec()
+        };
+
+        if table_data.len() != orig_length {
+            return None;
+        }
+
+        tables.push((tag, table_data));
+    }
+
+    Some(sfnt::build(flavor, tables))
+}
+
+// https://www.w3.org/TR/WOFF2/
+fn decode_woff2(data: &[u8]) -> Option<Vec<u8>> {
+    let mut s = Stream::new(data);
+    s.skip(4); // signature
+    let flavor = s.read_u32()?;
+    s.skip(4); // length
+    let num_tables = s.read_u16()?;
+    s.skip(2); // reserved
+    s.skip(4); // totalSfntSize
+    let total_compressed_size = s.read_u32()? as usize;
+    s.skip(24); // versions, metadata and private data
+
+    // Font collections are not supported.
+    if flavor == u32::from_be_bytes(*b"ttcf") {
+        log::warn!("WOFF2 font collections are not supported.");
+        return None;
+    }
+
+    struct Entry {
+        tag: [u8; 4],
+        transformed: bool,
+        length: usize,
+    }
+
+    let mut entries = Vec::with_capacity(num_tables as usize);
+    for _ in 0..num_tables {
+        let flags = s.read_u8()?;
+        let tag = match flags & 0x3F {
+            0x3F => s.read_tag()?,
+            n => *KNOWN_TAGS.get(n as usize)?,
+        };
+
+        // For `glyf` and `loca`, the version 0 indicates a transform, while 3 is a null one.
+        // For other tables, version 0 is a null transform.
+        let version = flags >> 6;
+        let transformed = if &tag == b"glyf" || &tag == b"loca" {
+            version != 3
+        } else {
+            version != 0
+        };
+
+        let orig_length = s.read_base128()? as usize;
+        let length = if transformed {
+            s.read_base128()? as usize
+        } else {
+            orig_length
+        };
+
+        entries.push(Entry {
+            tag,
+            transformed,
+            length,
+        });
+    }
+
+    let total_length = entries
+        .iter()
+        .try_fold(0usize, |sum, e| sum.checked_add(e.length))?;
+
+    let compressed = data.get(s.offset..s.offset.checked_add(total_compressed_size)?)?;
+    let mut decompressed = Vec::new();
+    brotli_decompressor::Decompressor::new(compressed, 4096)
+        .take(total_length as u64)
+        .read_to_end(&mut decompressed)
+        .ok()?;
+
+    let mut offset: usize = 0;
+    let mut raw_tables = Vec::with_capacity(entries.len());
+    for entry in &entries {
+        let end = offset.checked_add(entry.length)?;
+        raw_tables.push(decompressed.get(offset..end)?);
+        offset = end;
+    }
+
+    let find = |tag: &[u8; 4]| entries.iter().position(|e| &e.tag == tag);
+
+    // Reconstruct `glyf` and `loca` first, since `hmtx` depends on them.
+    let mut glyf = None;
+    if let Some(idx) = find(b"glyf") {
+        if entries[idx].transformed {
+            glyf = Some(reconstruct_glyf(raw_tables[idx])?);
+        }
+    }
+
+    let mut tables = Vec::with_capacity(entries.len());
+    for (entry, raw) in entries.iter().zip(raw_tables.iter()) {
+        let table_data = match (&entry.tag, entry.transformed) {
+            (_, false) => raw.to_vec(),
+            (b"glyf", true) => glyf.as_ref()?.glyf.clone(),
+            (b"loca", true) => glyf.as_ref()?.loca.clone(),
+            (b"hmtx", true) => {
+                let hhea = raw_tables[find(b"hhea")?];
+                let num_h_metrics = Stream::new(hhea.get(34..36)?).read_u16()?;
+                reconstruct_hmtx(raw, num_h_metrics, &glyf.as_ref()?.x_mins)?
+            }
+            _ => return None,
+        };
+
+        tables.push((entry.tag, table_data));
+    }
+
+    Some(sfnt::build(flavor, tables))
+}
+
+struct Glyf {
+    glyf: Vec<u8>,
+    loca: Vec<u8>,
+    /// Glyph bounding box minimum X per glyph. Used by `hmtx`.
+    x_mins: Vec<i16>,
+}
+
+fn reconstruct_glyf(data: &[u8]) -> Option<Glyf> {
+    let mut s = Stream::new(data);
+    s.skip(2); // reserved
+    let option_flags = s.read_u16()?;
+    let num_glyphs = s.read_u16()? as usize;
+    let index_format = s.read_u16()?;
+
+    let mut sizes = [0usize; 7];
+    for size in &mut sizes {
+        *size = s.read_u32()? as usize;
+    }
+
+    let mut streams = Vec::with_capacity(7);
+    for size in sizes {
+        streams.push(Stream::new(s.read_bytes(size)?));
+    }
+
+    let overlap_bitmap = if option_flags & 1 != 0 {
+        Some(s.read_bytes(num_glyphs.div_ceil(8))?)
+    } else {
+        None
+    };
+
+    let mut instructions = streams.pop()?;
+    let mut bboxes = streams.pop()?;
+    let mut composites = streams.pop()?;
+    let mut glyphs = streams.pop()?;
+    let mut flags = streams.pop()?;
+    let mut points = streams.pop()?;
+    let mut contours = streams.pop()?;
+
+    let bbox_bitmap = bboxes.read_bytes(num_glyphs.div_ceil(32) * 4)?;
+    let has_bit = |bitmap: &[u8], i: usize| bitmap[i >> 3] & (0x80 >> (i & 7)) != 0;
+
+    let mut glyf = Vec::new();
+    let mut loca = Vec::with_capacity((num_glyphs + 1) * 4);
+    let mut x_mins = Vec::with_capacity(num_glyphs);
+    for i in 0..num_glyphs {
+        write_loca(&mut loca, glyf.len(), index_format)?;
+
+        let has_bbox = has_bit(bbox_bitmap, i);
+        let num_contours = contours.read_i16()?;
+        if num_contours == 0 {
+            // An empty glyph cannot have a bounding box.
+            if has_bbox {
+                return None;
+            }
+
+            x_mins.push(0);
+            continue;
+        }
+
+        let start = glyf.len();
+        if num_contours < 0 {
+            // A composite glyph must have an explicit bounding box.
+            if !has_bbox {
+                return None;
+            }
+
+            let bbox = bboxes.read_bytes(8)?;
+            glyf.extend_from_slice(&num_contours.to_be_bytes());
+            glyf.extend_from_slice(bbox);
+            x_mins.push(i16::from_be_bytes([bbox[0], bbox[1]]));
+
+            let composite_start = composites.offset;
+            let mut has_instructions = false;
+            loop {
+                let flags = composites.read_u16()?;
+                has_instructions |= flags & 0x0100 != 0;
+
+                let mut len = 2; // glyph index
+                len += if flags & 0x0001 != 0 { 4 } else { 2 };
+                if flags & 0x0008 != 0 {
+                    len += 2;
+                } else if flags & 0x0040 != 0 {
+                    len += 4;
+                } else if flags & 0x0080 != 0 {
+                    len += 8;
+                }
+                composites.skip(len);
+
+                if flags & 0x0020 == 0 {
+                    break;
+                }
+            }
+
+            glyf.extend_from_slice(composites.data.get(composite_start..composites.offset)?);
+
+            if has_instructions {
+                let len = glyphs.read_255_u16()?;
+                glyf.extend_from_slice(&len.to_be_bytes());
+                glyf.extend_from_slice(instructions.read_bytes(len as usize)?);
+            }
+        } else {
+            let mut end_points = Vec::with_capacity(num_contours as usize);
+            let mut num_points = 0u16;
+            for _ in 0..num_contours {
+                num_points = num_points.checked_add(points.read_255_u16()?)?;
+                end_points.push(num_points.checked_sub(1)?);
+            }
+
+            let mut coords = Vec::with_capacity(num_points as usize);
+            let (mut x, mut y) = (0i32, 0i32);
+            for _ in 0..num_points {
+                let flag = flags.read_u8()?;
+                let (dx, dy) = read_triplet(flag & 0x7F, &mut glyphs)?;
+                x += dx;
+                y += dy;
+                coords.push((x, y, flag & 0x80 == 0));
+            }
+
+            let instructions_len = glyphs.read_255_u16()?;
+
+            let bbox = if has_bbox {
+                let mut s = Stream::new(bboxes.read_bytes(8)?);
+                [s.read_i16()?, s.read_i16()?, s.read_i16()?, s.read_i16()?]
+            } else {
+                let mut bbox = [i16::MAX, i16::MAX, i16::MIN, i16::MIN];
+                for &(x, y, _) in &coords {
+                    bbox[0] = bbox[0].min(x as i16);
+                    bbox[1] = bbox[1].min(y as i16);
+                    bbox[2] = bbox[2].max(x as i16);
+                    bbox[3] = bbox[3].max(y as i16);
+                }
+                bbox
+            };
+
+            glyf.extend_from_slice(&num_contours.to_be_bytes());
+            for n in bbox {
+                glyf.extend_from_slice(&n.to_be_bytes());
+            }
+            x_mins.push(bbox[0]);
+
+            for n in end_points {
+                glyf.extend_from_slice(&n.to_be_bytes());
+            }
+
+            glyf.extend_from_slice(&instructions_len.to_be_bytes());
+            glyf.extend_from_slice(instructions.read_bytes(instructions_len as usize)?);
+
+            let overlap = overlap_bitmap.is_some_and(|bitmap| has_bit(bitmap, i));
+            sfnt::write_simple_glyph_points(&coords, overlap, &mut glyf);
+        }
+
+        // Align glyphs to 4 bytes. Short offsets require only 2.
+        while (glyf.len() - start) % 4 != 0 {
+            glyf.push(0);
+        }
+    }
+
+    write_loca(&mut loca, glyf.len(), index_format)?;
+
+    Some(Glyf { glyf, loca, x_mins })
+}
+
+fn write_loca(loca: &mut Vec<u8>, offset: usize, index_format: u16) -> Option<()> {
+    if index_format == 0 {
+        loca.extend_from_slice(&u16::try_from(offset / 2).ok()?.to_be_bytes());
+    } else {
+        loca.extend_from_slice(&u32::try_from(offset).ok()?.to_be_bytes());
+    }
+
+    Some(())
+}
+
+/// Decodes a point delta using the WOFF2 triplet encoding.
+fn read_triplet(flag: u8, s: &mut Stream) -> Option<(i32, i32)> {
+    fn with_sign(flag: u8, base: i32) -> i32 {
+        if flag & 1 != 0 {
+            base
+        } else {
+            -base
+        }
+    }
+
+    let flag_i = flag as i32;
+    let delta = if flag < 10 {
+        let b0 = s.read_u8()? as i32;
+        (0, with_sign(flag, ((flag_i & 14) << 7) + b0))
+    } else if flag < 20 {
+        let b0 = s.read_u8()? as i32;
+        (with_sign(flag, (((flag_i - 10) & 14) << 7) + b0), 0)
+    } else if flag < 84 {
+        let n = flag_i - 20;
+        let b0 = s.read_u8()? as i32;
+        (
+            with_sign(flag, 1 + (n & 0x30) + (b0 >> 4)),
+            with_sign(flag >> 1, 1 + ((n & 0x0C) << 2) + (b0 & 0x0F)),
+        )
+    } else if flag < 120 {
+        let n = flag_i - 84;
+        let b0 = s.read_u8()? as i32;
+        let b1 = s.read_u8()? as i32;
+        (
+            with_sign(flag, 1 + ((n / 12) << 8) + b0),
+            with_sign(flag >> 1, 1 + (((n % 12) >> 2) << 8) + b1),
+        )
+    } else if flag < 124 {
+        let b0 = s.read_u8()? as i32;
+        let b1 = s.read_u8()? as i32;
+        let b2 = s.read_u8()? as i32;
+        (
+            with_sign(flag, (b0 << 4) + (b1 >> 4)),
+            with_sign(flag >> 1, ((b1 & 0x0F) << 8) + b2),
+        )
+    } else {
+        let x = s.read_u16()? as i32;
+        let y = s.read_u16()? as i32;
+        (with_sign(flag, x), with_sign(flag >> 1, y))
+    };
+
+    Some(delta)
+}
+
+fn reconstruct_hmtx(data: &[u8], num_h_metrics: u16, x_mins: &[i16]) -> Option<Vec<u8>> {
+    let mut s = Stream::new(data);
+    let flags = s.read_u8()?;
+    let num_h_metrics = num_h_metrics as usize;
+    let num_glyphs = x_mins.len();
+    if num_h_metrics == 0 || num_h_metrics > num_glyphs {
+        return None;
+    }
+
+    let mut advances = Vec::with_capacity(num_h_metrics);
+    for _ in 0..num_h_metrics {
+        advances.push(s.read_u16()?);
+    }
+
+    let mut hmtx = Vec::with_capacity(num_h_metrics * 4 + (num_glyphs - num_h_metrics) * 2);
+    for (i, advance) in advances.iter().enumerate() {
+        // Left side bearings are omitted when they are equal to `xMin`.
+        let lsb = if flags & 1 != 0 {
+            x_mins[i]
+        } else {
+            s.read_i16()?
+        };
+        hmtx.extend_from_slice(&advance.to_be_bytes());
+        hmtx.extend_from_slice(&lsb.to_be_bytes());
+    }
+
+    for x_min in &x_mins[num_h_metrics..] {
+        let lsb = if flags & 2 != 0 {
+            *x_min
+        } else {
+            s.read_i16()?
+        };
+        hmtx.extend_from_slice(&lsb.to_be_bytes());
+    }
+
+    Some(hmtx)
+}
+
+/// Tags used by the WOFF2 table directory.
+const KNOWN_TAGS: &[[u8; 4]] = &[
+    *b"cmap", *b"head", *b"hhea", *b"hmtx", *b"maxp", *b"name", *b"OS/2", *b"post", *b"cvt ",
+    *b"fpgm", *b"glyf", *b"loca", *b"prep", *b"CFF ", *b"VORG", *b"EBDT", *b"EBLC", *b"gasp",
+    *b"hdmx", *b"kern", *b"LTSH", *b"PCLT", *b"VDMX", *b"vhea", *b"vmtx", *b"BASE", *b"GDEF",
+    *b"GPOS", *b"GSUB", *b"EBSC", *b"JSTF", *b"MATH", *b"CBDT", *b"CBLC", *b"COLR", *b"CPAL",
+    *b"SVG ", *b"sbix", *b"acnt", *b"avar", *b"bdat", *b"bloc", *b"bsln", *b"cvar", *b"fdsc",
+    *b"feat", *b"fmtx", *b"fvar", *b"gvar", *b"hsty", *b"just", *b"lcar", *b"mort", *b"morx",
+    *b"opbd", *b"prop", *b"trak", *b"Zapf", *b"Silf", *b"Glat", *b"Gloc", *b"Feat", *b"Sill",
+];
+
+struct Stream<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Stream<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Stream { data, offset: 0 }
+    }
+
+    fn skip(&mut self, n: usize) {
+        self.offset = self.offset.saturating_add(n);
+    }
+
+    fn read_bytes(&mut self, n: usize) -> Option<&'a [u8]> {
+        let bytes = self.data.get(self.offset..self.offset.checked_add(n)?)?;
+        self.offset += n;
+        Some(bytes)
+    }
+
+    fn read_u8(&mut self) -> Option<u8> {
+        Some(self.read_bytes(1)?[0])
+    }
+
+    fn read_u16(&mut self) -> Option<u16> {
+        Some(u16::from_be_bytes(self.read_bytes(2)?.try_into().ok()?))
+    }
+
+    fn read_i16(&mut self) -> Option<i16> {
+        Some(i16::from_be_bytes(self.read_bytes(2)?.try_into().ok()?))
+    }
+
+    fn read_u32(&mut self) -> Option<u32> {
+        Some(u32::from_be_bytes(self.read_bytes(4)?.try_into().ok()?))
+    }
+
+    fn read_tag(&mut self) -> Option<[u8; 4]> {
+        self.read_bytes(4)?.try_into().ok()
+    }
+
+    /// Reads a `UIntBase128` number.
+    fn read_base128(&mut self) -> Option<u32> {
+        let mut value = 0u32;
+        for i in 0..5 {
+            let byte = self.read_u8()?;
+            // Leading zeros are not allowed.
+            if i == 0 && byte == 0x80 {
+                return None;
+            }
+
+            // Overflow check.
+            if value & 0xFE00_0000 != 0 {
+                return None;
+            }
+
+            value = (value << 7) | (byte & 0x7F) as u32;
+            if byte & 0x80 == 0 {
+                return Some(value);
+            }
+        }
+
+        None
+    }
+
+    /// Reads a `255UInt16` number.
+    fn read_255_u16(&mut self) -> Option<u16> {
+        const LOWEST_U_CODE: u16 = 253;
+        match self.read_u8()? {
+            253 => self.read_u16(),
+            254 => Some(self.read_u8()? as u16 + LOWEST_U_CODE * 2),
+            255 => Some(self.read_u8()? as u16 + LOWEST_U_CODE),
+            n => Some(n as u16),
+        }
+    }
+}
@@ -184,8 +184,41 @@ impl<'a> GlyphPainter<'a> {
         self.svg.end_element();
     }
 
-    fn paint_sweep_gradient(&mut self, _: ttf_parser::colr::SweepGradient<'a>) {
-        println!("Warning: sweep gradients are not supported.");
+    fn paint_sweep_gradient(&mut self, gradient: ttf_parser::colr::SweepGradient<'a>) {
+        let gradient_id = format!("cg{}", self.gradient_index);
+        self.gradient_index += 1;
+
+        let gradient_transform = paint_transform(self.outline_transform, self.transform);
+
+        // Angles are stored as multiples of 180 degrees with a bias of 180 degrees,
+        // so the full circle can be specified.
+        // They go counter-clockwise in the Y-up glyph space, i.e. from the X axis
+        // towards the Y axis, just like `conicGradient` angles.
+        let start_angle = gradient.start_angle * 180.0 + 180.0;
+        let end_angle = gradient.end_angle * 180.0 + 180.0;
+
+        self.svg.start_element("conicGradient");
+        self.svg.write_attribute("id", &gradient_id);
+        self.svg.write_attribute("cx", &gradient.center_x);
+        self.svg.write_attribute("cy", &gradient.center_y);
+        self.svg.write_attribute("startAngle", &start_angle);
+        self.svg.write_attribute("endAngle", &end_angle);
+        self.svg.write_attribute("gradientUnits", &"userSpaceOnUse");
+        self.svg.write_spread_method_attribute(gradient.extend);
+        self.svg
+            .write_transform_attribute("gradientTransform", gradient_transform);
+        self.write_gradient_stops(
+            gradient.stops(self.palette_index, self.face.variation_coordinates()),
+        );
+        self.svg.end_element();
+
+        self.svg.start_element("path");
+        self.svg
+            .write_attribute_fmt("fill", format_args!("url(#{})", gradient_id));
+        self.svg
+            .write_transform_attribute("transform", self.outline_transform);
+        self.svg.write_attribute("d", self.path_buf);
+        self.svg.end_element();
     }
 }
 
@@ -129,7 +129,7 @@ pub(crate) fn flatten(text: &mut Text, cache: &mut Cache) -> Option<(Group, NonZ
 
                 new_children.push(Node::Group(Box::new(group)));
             } else if let Some(outline) = cache
-                .fontdb_outline(glyph.font, glyph.id)
+                .fontdb_outline(glyph.font, glyph.id, &glyph.variations)
                 .and_then(|p| p.transform(glyph.outline_transform()))
             {
                 span_builder.push_path(&outline);
@@ -186,7 +186,12 @@ impl ttf_parser::OutlineBuilder for PathBuilder {
 }
 
 pub(crate) trait DatabaseExt {
-    fn outline(&self, id: ID, glyph_id: GlyphId) -> Option<tiny_skia_path::Path>;
+    fn outline(
+        &self,
+        id: ID,
+        glyph_id: GlyphId,
+        variations: &[FontVariation],
+    ) -> Option<tiny_skia_path::Path>;
     fn raster(&self, id: ID, glyph_id: GlyphId) -> Option<BitmapImage>;
     fn svg(&self, id: ID, glyph_id: GlyphId) -> Option<Node>;
     fn colr(&self, id: ID, glyph_id: GlyphId) -> Option<Tree>;
@@ -204,9 +209,17 @@ pub(crate) struct BitmapImage {
 
 impl DatabaseExt for Database {
     #[inline(never)]
-    fn outline(&self, id: ID, glyph_id: GlyphId) -> Option<tiny_skia_path::Path> {
+    fn outline(
+        &self,
+        id: ID,
+        glyph_id: GlyphId,
+        variations: &[FontVariation],
+    ) -> Option<tiny_skia_path::Path> {
         self.with_face_data(id, |data, face_index| -> Option<tiny_skia_path::Path> {
-            let font = ttf_parser::Face::parse(data, face_index).ok()?;
+            let mut font = ttf_parser::Face::parse(data, face_index).ok()?;
+            for variation in variations {
+                font.set_variation(ttf_parser::Tag::from_bytes(&variation.tag), variation.value);
+            }
 
             let mut builder = PathBuilder {
                 builder: tiny_skia_path::PathBuilder::new(),
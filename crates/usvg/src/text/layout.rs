@@ -13,11 +13,14 @@ use strict_num::NonZeroPositiveF32;
 use tiny_skia_path::{NonZeroRect, Transform};
 use unicode_script::UnicodeScript;
 
+use super::flatten::DatabaseExt as _;
 use crate::tree::{BBox, IsValidLength};
 use crate::{
     AlignmentBaseline, ApproxZeroUlps, BaselineShift, DominantBaseline, Fill, FillRule, Font,
-    FontResolver, LengthAdjust, PaintOrder, Path, ShapeRendering, Stroke, Text, TextAnchor,
-    TextChunk, TextDecorationStyle, TextFlow, TextPath, TextSpan, WritingMode,
+    FontResolver, FontVariation, LengthAdjust, PaintOrder, Path, ShapeRendering, Stroke, Text,
+    TextAnchor, TextChunk, TextDecorationLineStyle, TextDecorationStyle, TextDirection, TextFlow,
+    TextOrientation, TextPath, TextPathSide, TextSpan, TextUnderlinePosition, TextWrap,
+    UnicodeBidi, WhiteSpace, WritingMode,
 };
 
 /// A glyph that has already been positioned correctly.
@@ -45,9 +48,18 @@ pub struct PositionedGlyph {
     /// The ID of the font the glyph should be taken from. Can be used with the
     /// [font database of the tree](crate::Tree::fontdb) this glyph is part of.
     pub font: ID,
+    /// Variable font axis values. Empty for static fonts.
+    pub(crate) variations: Arc<[FontVariation]>,
 }
 
 impl PositionedGlyph {
+    /// Returns variable font axis values the glyph was shaped with.
+    ///
+    /// Empty for static fonts.
+    pub fn variations(&self) -> &[FontVariation] {
+        &self.variations
+    }
+
     /// Returns the transform of glyph.
     pub fn transform(&self) -> Transform {
         let sx = self.font_size / self.units_per_em as f32;
@@ -175,6 +187,10 @@ struct GlyphCluster {
     transform: Transform,
     path_transform: Transform,
     visible: bool,
+    /// Indicates that the cluster is set upright in the vertical text.
+    upright: bool,
+    vertical_advance: Option<f32>,
+    vertical_origin: Option<f32>,
 }
 
 impl GlyphCluster {
@@ -206,23 +222,74 @@ pub(crate) fn layout_text(
         }
     }
 
+    let chunks = match text_node.wrap {
+        Some(ref wrap) => {
+            layout_wrapped_chunks(text_node, Some(wrap), &fonts_cache, resolver, fontdb)
+        }
+        // Preserved line breaks turn a single text chunk into a paragraph.
+        None if text_node.chunks.len() == 1 && text_node.chunks[0].text.contains('\n') => {
+            layout_wrapped_chunks(text_node, None, &fonts_cache, resolver, fontdb)
+        }
+        None => None,
+    };
+    let chunks = chunks.unwrap_or_else(|| layout_chunks(text_node, &fonts_cache, resolver, fontdb));
+
     let mut spans = vec![];
+    let mut bbox = BBox::default();
+    for chunk in &chunks {
+        convert_chunk(
+            text_node,
+            chunk,
+            &fonts_cache,
+            fontdb,
+            &mut spans,
+            &mut bbox,
+        );
+    }
+
+    let bbox = bbox.to_non_zero_rect()?;
+
+    Some((spans, bbox))
+}
+
+/// A text chunk with positioned clusters.
+struct PositionedChunk<'a> {
+    chunk: &'a TextChunk,
+    clusters: Vec<GlyphCluster>,
+    /// A transform of the chunk start position.
+    transform: Transform,
+}
+
+fn layout_chunks<'a>(
+    text_node: &'a Text,
+    fonts_cache: &FontsCache,
+    resolver: &FontResolver,
+    fontdb: &mut Arc<fontdb::Database>,
+) -> Vec<PositionedChunk<'a>> {
+    let mut chunks = Vec::new();
     let mut char_offset = 0;
     let mut last_x = 0.0;
     let mut last_y = 0.0;
-    let mut bbox = BBox::default();
     for chunk in &text_node.chunks {
         let (x, y) = match chunk.text_flow {
             TextFlow::Linear => (chunk.x.unwrap_or(last_x), chunk.y.unwrap_or(last_y)),
             TextFlow::Path(_) => (0.0, 0.0),
         };
 
-        let mut clusters = process_chunk(chunk, &fonts_cache, resolver, fontdb);
+        let mut clusters = process_chunk(
+            chunk,
+            text_node.writing_mode,
+            text_node.direction,
+            fonts_cache,
+            resolver,
+            fontdb,
+        );
         if clusters.is_empty() {
             char_offset += chunk.text.chars().count();
             continue;
         }
 
+        hide_line_breaks(&mut clusters);
         apply_writing_mode(text_node.writing_mode, &mut clusters);
         apply_letter_spacing(chunk, &mut clusters);
         apply_word_spacing(chunk, &mut clusters);
@@ -233,140 +300,830 @@ pub(crate) fn layout_text(
             chunk,
             char_offset,
             text_node.writing_mode,
-            &fonts_cache,
+            fonts_cache,
             &mut clusters,
         );
 
-        let mut text_ts = Transform::default();
+        let mut transform = Transform::default();
         if text_node.writing_mode == WritingMode::TopToBottom {
             if let TextFlow::Linear = chunk.text_flow {
-                text_ts = text_ts.pre_rotate_at(90.0, x, y);
+                transform = transform.pre_rotate_at(90.0, x, y);
             }
         }
+        transform = transform.pre_translate(x, y);
 
-        for span in &chunk.spans {
-            let font = match fonts_cache.get(&span.font) {
+        chunks.push(PositionedChunk {
+            chunk,
+            clusters,
+            transform,
+        });
+
+        char_offset += chunk.text.chars().count();
+
+        if text_node.writing_mode == WritingMode::TopToBottom {
+            if let TextFlow::Linear = chunk.text_flow {
+                std::mem::swap(&mut curr_pos.0, &mut curr_pos.1);
+            }
+        }
+
+        last_x = x + curr_pos.0;
+        last_y = y + curr_pos.1;
+    }
+
+    chunks
+}
+
+fn convert_chunk(
+    text_node: &Text,
+    positioned: &PositionedChunk,
+    fonts_cache: &FontsCache,
+    fontdb: &Database,
+    spans: &mut Vec<Span>,
+    bbox: &mut BBox,
+) {
+    let chunk = positioned.chunk;
+    let clusters = &positioned.clusters;
+    for span in &chunk.spans {
+        let font = match fonts_cache.get(&span.font) {
+            Some(v) => v,
+            None => continue,
+        };
+
+        let decoration_spans = collect_decoration_spans(span, clusters);
+
+        let mut span_ts = positioned.transform;
+        if let TextFlow::Linear = chunk.text_flow {
+            let shift = resolve_baseline(span, font, text_node.writing_mode);
+
+            // In case of a horizontal flow, shift transform and not clusters,
+            // because clusters can be rotated and an additional shift will lead
+            // to invalid results.
+            span_ts = span_ts.pre_translate(0.0, shift);
+        }
+
+        // Glyph outlines are needed only to skip the ink.
+        let skips_ink = [&span.decoration.underline, &span.decoration.overline]
+            .iter()
+            .any(|d| d.as_ref().is_some_and(|d| d.skip_ink));
+        let ink = if skips_ink {
+            collect_ink(span, clusters, fontdb)
+        } else {
+            Vec::new()
+        };
+
+        let mut underline = None;
+        let mut overline = None;
+        let mut line_through = None;
+
+        if let Some(decoration) = span.decoration.underline.clone() {
+            let font_size = span.font_size.get();
+            let offset = match (text_node.writing_mode, decoration.underline_position) {
+                (WritingMode::LeftToRight, TextUnderlinePosition::Under) => {
+                    -font.descent(font_size) + font.underline_thickness(font_size) / 2.0
+                }
+                (WritingMode::LeftToRight, _) => -font.underline_position(font_size),
+                // Vertical text is laid out horizontally and rotated clockwise afterwards,
+                // so the bottom side becomes the left one.
+                (WritingMode::TopToBottom, TextUnderlinePosition::Right) => {
+                    -font.height(font_size) / 2.0
+                }
+                (WritingMode::TopToBottom, _) => font.height(font_size) / 2.0,
+            };
+
+            if let Some(path) = convert_decoration(
+                offset,
+                DecorationKind::Underline,
+                span,
+                font,
+                decoration,
+                &decoration_spans,
+                &ink,
+                span_ts,
+            ) {
+                *bbox = bbox.expand(path.data.bounds());
+                underline = Some(path);
+            }
+        }
+
+        if let Some(decoration) = span.decoration.overline.clone() {
+            let offset = match text_node.writing_mode {
+                WritingMode::LeftToRight => -font.ascent(span.font_size.get()),
+                WritingMode::TopToBottom => -font.height(span.font_size.get()) / 2.0,
+            };
+
+            if let Some(path) = convert_decoration(
+                offset,
+                DecorationKind::Overline,
+                span,
+                font,
+                decoration,
+                &decoration_spans,
+                &ink,
+                span_ts,
+            ) {
+                *bbox = bbox.expand(path.data.bounds());
+                overline = Some(path);
+            }
+        }
+
+        if let Some(decoration) = span.decoration.line_through.clone() {
+            let offset = match text_node.writing_mode {
+                WritingMode::LeftToRight => -font.line_through_position(span.font_size.get()),
+                WritingMode::TopToBottom => 0.0,
+            };
+
+            if let Some(path) = convert_decoration(
+                offset,
+                DecorationKind::LineThrough,
+                span,
+                font,
+                decoration,
+                &decoration_spans,
+                &ink,
+                span_ts,
+            ) {
+                *bbox = bbox.expand(path.data.bounds());
+                line_through = Some(path);
+            }
+        }
+
+        let mut fill = span.fill.clone();
+        if let Some(ref mut fill) = fill {
+            // The `fill-rule` should be ignored.
+            // https://www.w3.org/TR/SVG2/text.html#TextRenderingOrder
+            //
+            // 'Since the fill-rule property does not apply to SVG text elements,
+            // the specific order of the subpaths within the equivalent path does not matter.'
+            fill.rule = FillRule::NonZero;
+        }
+
+        if let Some((span_fragments, span_bbox)) = convert_span(span, clusters, span_ts) {
+            *bbox = bbox.expand(span_bbox);
+
+            let positioned_glyphs = span_fragments
+                .into_iter()
+                .flat_map(|mut gc| {
+                    let cluster_ts = gc.transform();
+                    gc.glyphs.iter_mut().for_each(|pg| {
+                        pg.cluster_ts = cluster_ts;
+                        pg.span_ts = span_ts;
+                    });
+                    gc.glyphs
+                })
+                .collect();
+
+            spans.push(Span {
+                fill,
+                stroke: span.stroke.clone(),
+                paint_order: span.paint_order,
+                font_size: span.font_size,
+                visible: span.visible,
+                positioned_glyphs,
+                underline,
+                overline,
+                line_through,
+            });
+        }
+    }
+}
+
+/// Lays out all text chunks as a single paragraph broken into lines.
+///
+/// When `wrap` is not set, lines are broken only at preserved line breaks.
+///
+/// Returns `None` when text cannot be wrapped, in which case it should be laid out as usual.
+fn layout_wrapped_chunks<'a>(
+    text_node: &'a Text,
+    wrap: Option<&TextWrap>,
+    fonts_cache: &FontsCache,
+    resolver: &FontResolver,
+    fontdb: &mut Arc<fontdb::Database>,
+) -> Option<Vec<PositionedChunk<'a>>> {
+    // Text-on-path cannot be wrapped.
+    if text_node
+        .chunks
+        .iter()
+        .any(|chunk| matches!(chunk.text_flow, TextFlow::Path(_)))
+    {
+        return None;
+    }
+
+    let first_chunk = text_node.chunks.first()?;
+    let (x, y) = match wrap {
+        Some(TextWrap::Shape(_)) => (0.0, 0.0),
+        // Only the first chunk position is used.
+        _ => (first_chunk.x.unwrap_or(0.0), first_chunk.y.unwrap_or(0.0)),
+    };
+
+    // Lines are laid out horizontally and then rotated in the vertical mode.
+    let mut transform = Transform::default();
+    if text_node.writing_mode == WritingMode::TopToBottom {
+        transform = transform.pre_rotate_at(90.0, x, y);
+    }
+    transform = transform.pre_translate(x, y);
+
+    let mut chunks = Vec::new();
+    let mut items = Vec::new();
+    // Clusters are in the visual order, so we have to remember the logical one as well.
+    let mut logical_keys = Vec::new();
+    for (chunk_idx, chunk) in text_node.chunks.iter().enumerate() {
+        let mut clusters = process_chunk(
+            chunk,
+            text_node.writing_mode,
+            text_node.direction,
+            fonts_cache,
+            resolver,
+            fontdb,
+        );
+        hide_line_breaks(&mut clusters);
+        apply_writing_mode(text_node.writing_mode, &mut clusters);
+        apply_letter_spacing(chunk, &mut clusters);
+        apply_word_spacing(chunk, &mut clusters);
+
+        for cluster in &clusters {
+            let span = chunk_span_at(chunk, cluster.byte_idx);
+            items.push(LineItem {
+                codepoint: cluster.codepoint,
+                advance: cluster.advance,
+                white_space: span.map_or(WhiteSpace::Normal, |span| span.white_space),
+                line_height: span
+                    .and_then(|span| span.line_height)
+                    .unwrap_or(cluster.ascent - cluster.descent),
+            });
+            logical_keys.push((chunk_idx, cluster.byte_idx.value()));
+        }
+
+        chunks.push(PositionedChunk {
+            chunk,
+            clusters,
+            transform,
+        });
+    }
+
+    let ascent = chunks
+        .iter()
+        .flat_map(|c| &c.clusters)
+        .fold(0.0f32, |a, c| a.max(c.ascent));
+    let descent = chunks
+        .iter()
+        .flat_map(|c| &c.clusters)
+        .fold(0.0f32, |d, c| d.min(c.descent));
+    let line_height = items.iter().fold(0.0f32, |h, item| h.max(item.line_height));
+    if !line_height.is_valid_length() {
+        return None;
+    }
+
+    // Glyphs are centered vertically inside a line box.
+    let baseline = (line_height - (ascent - descent)) / 2.0 + ascent;
+
+    let boxes = match wrap {
+        None => LineBoxes::Unbounded,
+        Some(TextWrap::InlineSize(size)) => {
+            let (start, end) = match resolve_anchor(first_chunk.anchor, text_node.direction) {
+                TextAnchor::Start => (0.0, *size),
+                TextAnchor::Middle => (-size / 2.0, size / 2.0),
+                TextAnchor::End => (-size, 0.0),
+            };
+            LineBoxes::InlineSize { start, end }
+        }
+        Some(TextWrap::Shape(ref shape)) => {
+            // Shapes are defined in text coordinates, while lines are laid out
+            // relative to the text transform.
+            let ts = transform.invert()?;
+            LineBoxes::Shape {
+                inside: shape.inside.as_ref().clone().transform(ts)?,
+                subtract: match shape.subtract {
+                    Some(ref path) => Some(path.as_ref().clone().transform(ts)?),
+                    None => None,
+                },
+                padding: shape.padding,
+                margin: shape.margin,
+            }
+        }
+    };
+
+    // Lines are broken in the logical order, so bidi reordering
+    // doesn't affect which characters end up on which line.
+    let mut logical_order: Vec<usize> = (0..items.len()).collect();
+    logical_order.sort_by_key(|&i| logical_keys[i]);
+    let logical_items: Vec<LineItem> = logical_order.iter().map(|&i| items[i]).collect();
+
+    let anchor = resolve_anchor(first_chunk.anchor, text_node.direction);
+    let placements = break_lines(&logical_items, &boxes, anchor, baseline, line_height);
+    let placements = reorder_lines(&items, &logical_order, &placements);
+
+    // Position clusters. Clusters that didn't fit are removed.
+    let mut char_offset = 0;
+    let mut item_idx = 0;
+    for chunk in &mut chunks {
+        let mut clusters = Vec::with_capacity(chunk.clusters.len());
+        for mut cluster in std::mem::take(&mut chunk.clusters) {
+            let placement = placements[item_idx];
+            item_idx += 1;
+
+            let (x, y, is_line_start) = match placement {
                 Some(v) => v,
                 None => continue,
             };
 
-            let decoration_spans = collect_decoration_spans(span, &clusters);
+            cluster.transform = cluster.transform.pre_translate(x, y);
+            // Decorations must not continue across lines.
+            cluster.has_relative_shift = is_line_start;
 
-            let mut span_ts = text_ts;
-            span_ts = span_ts.pre_translate(x, y);
-            if let TextFlow::Linear = chunk.text_flow {
-                let shift = resolve_baseline(span, font, text_node.writing_mode);
-
-                // In case of a horizontal flow, shift transform and not clusters,
-                // because clusters can be rotated and an additional shift will lead
-                // to invalid results.
-                span_ts = span_ts.pre_translate(0.0, shift);
-            }
-
-            let mut underline = None;
-            let mut overline = None;
-            let mut line_through = None;
-
-            if let Some(decoration) = span.decoration.underline.clone() {
-                // TODO: No idea what offset should be used for top-to-bottom layout.
-                // There is
-                // https://www.w3.org/TR/css-text-decor-3/#text-underline-position-property
-                // but it doesn't go into details.
-                let offset = match text_node.writing_mode {
-                    WritingMode::LeftToRight => -font.underline_position(span.font_size.get()),
-                    WritingMode::TopToBottom => font.height(span.font_size.get()) / 2.0,
+            let cp = char_offset + cluster.byte_idx.code_point_at(&chunk.chunk.text);
+            if let Some(angle) = text_node.rotate.get(cp).cloned() {
+                if !angle.approx_zero_ulps(4) {
+                    cluster.transform = cluster.transform.pre_rotate(angle);
+                    cluster.has_relative_shift = true;
+                }
+            }
+
+            clusters.push(cluster);
+        }
+
+        char_offset += chunk.chunk.text.chars().count();
+        chunk.clusters = clusters;
+    }
+
+    Some(chunks)
+}
+
+/// Preserved line breaks are not rendered.
+fn hide_line_breaks(clusters: &mut [GlyphCluster]) {
+    for cluster in clusters {
+        if cluster.codepoint == '\n' {
+            cluster.advance = 0.0;
+            cluster.visible = false;
+        }
+    }
+}
+
+/// A cluster in a paragraph.
+#[derive(Clone, Copy)]
+struct LineItem {
+    codepoint: char,
+    advance: f32,
+    white_space: WhiteSpace,
+    line_height: f32,
+}
+
+impl LineItem {
+    fn is_collapsible_space(&self) -> bool {
+        is_space(self.codepoint)
+            && matches!(
+                self.white_space,
+                WhiteSpace::Normal | WhiteSpace::NoWrap | WhiteSpace::PreLine
+            )
+    }
+
+    fn allows_soft_wrap(&self) -> bool {
+        matches!(
+            self.white_space,
+            WhiteSpace::Normal | WhiteSpace::PreWrap | WhiteSpace::PreLine
+        )
+    }
+}
+
+/// Areas lines can be placed into.
+enum LineBoxes {
+    /// Lines are broken only at preserved line breaks.
+    Unbounded,
+    InlineSize {
+        start: f32,
+        end: f32,
+    },
+    Shape {
+        inside: tiny_skia_path::Path,
+        subtract: Option<tiny_skia_path::Path>,
+        padding: f32,
+        margin: f32,
+    },
+}
+
+impl LineBoxes {
+    /// Returns a line baseline and horizontal intervals available for text.
+    ///
+    /// Returns `None` when there are no more lines.
+    fn line(
+        &self,
+        index: usize,
+        baseline: f32,
+        line_height: f32,
+    ) -> Option<(f32, Vec<(f32, f32)>)> {
+        match *self {
+            LineBoxes::Unbounded => Some((index as f32 * line_height, vec![(0.0, 0.0)])),
+            LineBoxes::InlineSize { start, end } => {
+                Some((index as f32 * line_height, vec![(start, end)]))
+            }
+            LineBoxes::Shape {
+                ref inside,
+                ref subtract,
+                padding,
+                margin,
+            } => {
+                let bounds = inside.bounds();
+                let top = bounds.top() + padding + index as f32 * line_height;
+                let bottom = top + line_height;
+                if bottom > bounds.bottom() - padding {
+                    return None;
+                }
+
+                let mut intervals: Vec<_> =
+                    band_intervals(inside, top - padding, bottom + padding, true)
+                        .into_iter()
+                        .map(|(l, r)| (l + padding, r - padding))
+                        .filter(|(l, r)| l < r)
+                        .collect();
+
+                if let Some(ref subtract) = subtract {
+                    let excluded: Vec<_> =
+                        band_intervals(subtract, top - margin, bottom + margin, false)
+                            .into_iter()
+                            .map(|(l, r)| (l - margin, r + margin))
+                            .collect();
+                    intervals = subtract_intervals(&intervals, &excluded);
+                }
+
+                Some((top + baseline, intervals))
+            }
+        }
+    }
+}
+
+/// Converts placements of clusters in the logical order into the visual one.
+///
+/// Each line segment keeps its position, while its clusters are laid out in the visual order.
+/// Since a line is a continuous logical range, its visual order is the same as in the paragraph.
+fn reorder_lines(
+    items: &[LineItem],
+    logical_order: &[usize],
+    placements: &[Option<(f32, f32, bool)>],
+) -> Vec<Option<(f32, f32, bool)>> {
+    let mut segments: Vec<(f32, f32, Vec<usize>)> = Vec::new();
+    for (&idx, placement) in logical_order.iter().zip(placements) {
+        let Some((x, y, is_line_start)) = *placement else {
+            continue;
+        };
+
+        if is_line_start || segments.is_empty() {
+            segments.push((x, y, Vec::new()));
+        }
+
+        if let Some(segment) = segments.last_mut() {
+            segment.2.push(idx);
+        }
+    }
+
+    let mut visual = vec![None; items.len()];
+    for (mut x, y, mut indices) in segments {
+        indices.sort_unstable();
+        for (i, idx) in indices.into_iter().enumerate() {
+            visual[idx] = Some((x, y, i == 0));
+            x += items[idx].advance;
+        }
+    }
+
+    visual
+}
+
+/// Breaks items into lines.
+///
+/// Returns a position and a line start flag for each item. Items that didn't fit have no position.
+fn break_lines(
+    items: &[LineItem],
+    boxes: &LineBoxes,
+    anchor: TextAnchor,
+    baseline: f32,
+    line_height: f32,
+) -> Vec<Option<(f32, f32, bool)>> {
+    let mut placements = vec![None; items.len()];
+
+    // A word longer than a line is allowed to overflow it only when it cannot be moved
+    // to the next line.
+    let allow_overflow = !matches!(boxes, LineBoxes::Shape { .. });
+
+    let mut idx = 0;
+    let mut line = 0;
+    while idx < items.len() {
+        let (line_baseline, intervals) = match boxes.line(line, baseline, line_height) {
+            Some(v) => v,
+            None => break,
+        };
+        line += 1;
+
+        // Spaces at the start of a line are removed.
+        while idx < items.len() && items[idx].is_collapsible_space() {
+            idx += 1;
+        }
+
+        let line_start = idx;
+        for (left, right) in intervals {
+            if idx == items.len()
+                || (idx > 0 && items[idx - 1].codepoint == '\n') && idx != line_start
+            {
+                break;
+            }
+
+            let force = allow_overflow && idx == line_start;
+            let max_width = match boxes {
+                LineBoxes::Unbounded => f32::INFINITY,
+                _ => right - left,
+            };
+
+            let (end, content_end, width) = fit_line(items, idx, max_width, force);
+            if end == idx {
+                continue;
+            }
+
+            let free = right - left - width;
+            let mut x = left
+                + match anchor {
+                    TextAnchor::Start => 0.0,
+                    TextAnchor::Middle => free / 2.0,
+                    TextAnchor::End => free,
                 };
 
-                if let Some(path) =
-                    convert_decoration(offset, span, font, decoration, &decoration_spans, span_ts)
-                {
-                    bbox = bbox.expand(path.data.bounds());
-                    underline = Some(path);
+            for (i, item) in items.iter().enumerate().take(content_end).skip(idx) {
+                placements[i] = Some((x, line_baseline, i == idx));
+                x += item.advance;
+            }
+
+            idx = end;
+        }
+    }
+
+    placements
+}
+
+/// Finds how many words can fit into a line.
+///
+/// Returns the end of the last word including trailing spaces,
+/// the end of the last word without them and its width.
+fn fit_line(items: &[LineItem], start: usize, max_width: f32, force: bool) -> (usize, usize, f32) {
+    let mut end = start;
+    let mut content_end = start;
+    let mut width = 0.0;
+    let mut total_width = 0.0;
+    while end < items.len() {
+        let word_end = next_word_end(items, end);
+        let word = &items[end..word_end];
+
+        // Trailing spaces are not taken into account.
+        let content_len = word
+            .iter()
+            .rposition(|item| !is_space(item.codepoint) && item.codepoint != '\n')
+            .map_or(0, |i| i + 1);
+        let word_width: f32 = word[..content_len].iter().map(|item| item.advance).sum();
+        let full_width: f32 = word.iter().map(|item| item.advance).sum();
+
+        let fits = total_width + word_width <= max_width + 0.001;
+        let is_forced = force && end == start;
+        if !fits && !is_forced {
+            break;
+        }
+
+        width = total_width + word_width;
+        total_width += full_width;
+        content_end = end + content_len;
+        end = word_end;
+
+        // A forced line break.
+        if word[word.len() - 1].codepoint == '\n' {
+            content_end = end;
+            break;
+        }
+    }
+
+    (end, content_end, width)
+}
+
+/// Returns the end of a word that starts at the specified item.
+///
+/// Trailing spaces are included.
+fn next_word_end(items: &[LineItem], start: usize) -> usize {
+    let mut i = start;
+    while i < items.len() {
+        let c = items[i].codepoint;
+        i += 1;
+
+        if c == '\n' {
+            return i;
+        }
+
+        match items.get(i) {
+            Some(next)
+                if items[i - 1].allows_soft_wrap()
+                    && is_line_break_opportunity(c, next.codepoint) =>
+            {
+                return i
+            }
+            Some(_) => {}
+            None => return i,
+        }
+    }
+
+    i
+}
+
+/// Checks that a line can be broken between two characters.
+///
+/// This is a simplified version of the
+/// [Unicode Line Breaking Algorithm](https://www.unicode.org/reports/tr14/).
+fn is_line_break_opportunity(c: char, next: char) -> bool {
+    if next == '\n' || is_space(next) {
+        return false;
+    }
+
+    if is_space(c) {
+        return true;
+    }
+
+    // After hyphens.
+    if matches!(c, '-' | '\u{00AD}' | '\u{2010}' | '\u{2012}' | '\u{2013}') {
+        return true;
+    }
+
+    // Around ideographic characters.
+    is_ideographic(c) || is_ideographic(next)
+}
+
+fn is_space(c: char) -> bool {
+    matches!(c, ' ' | '\t' | '\u{3000}')
+}
+
+fn is_ideographic(c: char) -> bool {
+    use unicode_script::Script;
+
+    matches!(
+        c.script(),
+        Script::Han | Script::Hiragana | Script::Katakana | Script::Yi
+    )
+}
+
+/// Returns horizontal intervals inside a path for a horizontal band.
+///
+/// When `all` is set, an interval must be inside the path along the whole band.
+/// Otherwise, it must be inside the path at any point of the band.
+fn band_intervals(
+    path: &tiny_skia_path::Path,
+    top: f32,
+    bottom: f32,
+    all: bool,
+) -> Vec<(f32, f32)> {
+    const SAMPLES: usize = 5;
+
+    let mut result: Option<Vec<(f32, f32)>> = None;
+    for i in 0..SAMPLES {
+        // Sample slightly inside the band, so edges exactly on the band border are ignored.
+        let t = (i as f32 / (SAMPLES - 1) as f32).clamp(0.001, 0.999);
+        let intervals = scanline_intervals(path, top + (bottom - top) * t);
+        result = Some(match result {
+            None => intervals,
+            Some(prev) if all => intersect_intervals(&prev, &intervals),
+            Some(prev) => union_intervals(&prev, &intervals),
+        });
+    }
+
+    result.unwrap_or_default()
+}
+
+/// Returns horizontal intervals inside a path at the specified Y coordinate.
+///
+/// Uses the nonzero fill rule.
+fn scanline_intervals(path: &tiny_skia_path::Path, y: f32) -> Vec<(f32, f32)> {
+    use tiny_skia_path::{PathSegment, Point};
+
+    let mut crossings: Vec<(f32, i32)> = Vec::new();
+    let mut add_line = |p0: Point, p1: Point| {
+        if (p0.y <= y) != (p1.y <= y) {
+            let t = (y - p0.y) / (p1.y - p0.y);
+            let dir = if p1.y > p0.y { 1 } else { -1 };
+            crossings.push((p0.x + (p1.x - p0.x) * t, dir));
+        }
+    };
+
+    // Curves are flattened, which is precise enough for text wrapping.
+    const STEPS: usize = 16;
+
+    let mut start = Point::zero();
+    let mut prev = Point::zero();
+    for segment in path.segments() {
+        match segment {
+            PathSegment::MoveTo(p) => {
+                add_line(prev, start);
+                start = p;
+                prev = p;
+            }
+            PathSegment::LineTo(p) => {
+                add_line(prev, p);
+                prev = p;
+            }
+            PathSegment::QuadTo(p1, p) => {
+                let quad = kurbo::QuadBez::new(to_kurbo(prev), to_kurbo(p1), to_kurbo(p));
+                for i in 1..=STEPS {
+                    let pt = quad.eval(i as f64 / STEPS as f64);
+                    let pt = Point::from_xy(pt.x as f32, pt.y as f32);
+                    add_line(prev, pt);
+                    prev = pt;
                 }
             }
+            PathSegment::CubicTo(p1, p2, p) => {
+                let cubic =
+                    kurbo::CubicBez::new(to_kurbo(prev), to_kurbo(p1), to_kurbo(p2), to_kurbo(p));
+                for i in 1..=STEPS {
+                    let pt = cubic.eval(i as f64 / STEPS as f64);
+                    let pt = Point::from_xy(pt.x as f32, pt.y as f32);
+                    add_line(prev, pt);
+                    prev = pt;
+                }
+            }
+            PathSegment::Close => {
+                add_line(prev, start);
+                prev = start;
+            }
+        }
+    }
+    add_line(prev, start);
+
+    crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mut intervals = Vec::new();
+    let mut winding = 0;
+    let mut interval_start = 0.0;
+    for (x, dir) in crossings {
+        let prev_winding = winding;
+        winding += dir;
+        if prev_winding == 0 && winding != 0 {
+            interval_start = x;
+        } else if prev_winding != 0 && winding == 0 && x > interval_start {
+            intervals.push((interval_start, x));
+        }
+    }
+
+    intervals
+}
 
-            if let Some(decoration) = span.decoration.overline.clone() {
-                let offset = match text_node.writing_mode {
-                    WritingMode::LeftToRight => -font.ascent(span.font_size.get()),
-                    WritingMode::TopToBottom => -font.height(span.font_size.get()) / 2.0,
-                };
+fn to_kurbo(p: tiny_skia_path::Point) -> kurbo::Point {
+    kurbo::Point::new(p.x as f64, p.y as f64)
+}
 
-                if let Some(path) =
-                    convert_decoration(offset, span, font, decoration, &decoration_spans, span_ts)
-                {
-                    bbox = bbox.expand(path.data.bounds());
-                    overline = Some(path);
-                }
+fn intersect_intervals(a: &[(f32, f32)], b: &[(f32, f32)]) -> Vec<(f32, f32)> {
+    let mut result = Vec::new();
+    for &(l1, r1) in a {
+        for &(l2, r2) in b {
+            let l = l1.max(l2);
+            let r = r1.min(r2);
+            if l < r {
+                result.push((l, r));
             }
+        }
+    }
 
-            if let Some(decoration) = span.decoration.line_through.clone() {
-                let offset = match text_node.writing_mode {
-                    WritingMode::LeftToRight => -font.line_through_position(span.font_size.get()),
-                    WritingMode::TopToBottom => 0.0,
-                };
+    result.sort_by(|a, b| a.0.total_cmp(&b.0));
+    result
+}
 
-                if let Some(path) =
-                    convert_decoration(offset, span, font, decoration, &decoration_spans, span_ts)
-                {
-                    bbox = bbox.expand(path.data.bounds());
-                    line_through = Some(path);
-                }
-            }
+fn union_intervals(a: &[(f32, f32)], b: &[(f32, f32)]) -> Vec<(f32, f32)> {
+    let mut all: Vec<_> = a.iter().chain(b).copied().collect();
+    all.sort_by(|a, b| a.0.total_cmp(&b.0));
 
-            let mut fill = span.fill.clone();
-            if let Some(ref mut fill) = fill {
-                // The `fill-rule` should be ignored.
-                // https://www.w3.org/TR/SVG2/text.html#TextRenderingOrder
-                //
-                // 'Since the fill-rule property does not apply to SVG text elements,
-                // the specific order of the subpaths within the equivalent path does not matter.'
-                fill.rule = FillRule::NonZero;
-            }
-
-            if let Some((span_fragments, span_bbox)) = convert_span(span, &clusters, span_ts) {
-                bbox = bbox.expand(span_bbox);
-
-                let positioned_glyphs = span_fragments
-                    .into_iter()
-                    .flat_map(|mut gc| {
-                        let cluster_ts = gc.transform();
-                        gc.glyphs.iter_mut().for_each(|pg| {
-                            pg.cluster_ts = cluster_ts;
-                            pg.span_ts = span_ts;
-                        });
-                        gc.glyphs
-                    })
-                    .collect();
-
-                spans.push(Span {
-                    fill,
-                    stroke: span.stroke.clone(),
-                    paint_order: span.paint_order,
-                    font_size: span.font_size,
-                    visible: span.visible,
-                    positioned_glyphs,
-                    underline,
-                    overline,
-                    line_through,
-                });
-            }
+    let mut result: Vec<(f32, f32)> = Vec::new();
+    for (l, r) in all {
+        match result.last_mut() {
+            Some(last) if l <= last.1 => last.1 = last.1.max(r),
+            _ => result.push((l, r)),
         }
+    }
 
-        char_offset += chunk.text.chars().count();
+    result
+}
 
-        if text_node.writing_mode == WritingMode::TopToBottom {
-            if let TextFlow::Linear = chunk.text_flow {
-                std::mem::swap(&mut curr_pos.0, &mut curr_pos.1);
+fn subtract_intervals(a: &[(f32, f32)], b: &[(f32, f32)]) -> Vec<(f32, f32)> {
+    let mut result = Vec::new();
+    for &(l, r) in a {
+        let mut parts = vec![(l, r)];
+        for &(sl, sr) in b {
+            let mut next = Vec::new();
+            for (pl, pr) in parts {
+                if sr <= pl || sl >= pr {
+                    next.push((pl, pr));
+                    continue;
+                }
+
+                if sl > pl {
+                    next.push((pl, sl));
+                }
+
+                if sr < pr {
+                    next.push((sr, pr));
+                }
             }
+            parts = next;
         }
-
-        last_x = x + curr_pos.0;
-        last_y = y + curr_pos.1;
+        result.extend(parts);
     }
 
-    let bbox = bbox.to_non_zero_rect()?;
-
-    Some((spans, bbox))
+    result
 }
 
 fn convert_span(
@@ -440,32 +1197,57 @@ fn collect_decoration_spans(span: &TextSpan, clusters: &[GlyphCluster]) -> Vec<D
     spans
 }
 
+/// A text decoration line.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(crate) enum DecorationKind {
+    Underline,
+    Overline,
+    LineThrough,
+}
+
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn convert_decoration(
     dy: f32,
+    kind: DecorationKind,
     span: &TextSpan,
     font: &ResolvedFont,
     mut decoration: TextDecorationStyle,
     decoration_spans: &[DecorationSpan],
+    ink: &[tiny_skia_path::Path],
     transform: Transform,
 ) -> Option<Path> {
-    debug_assert!(!decoration_spans.is_empty());
+    // Can be empty when all span clusters didn't fit into a text shape.
+    if decoration_spans.is_empty() {
+        return None;
+    }
 
     let thickness = font.underline_thickness(span.font_size.get());
+    let line = DecorationLine::new(decoration.line_style, kind, thickness);
 
     let mut builder = tiny_skia_path::PathBuilder::new();
     for dec_span in decoration_spans {
-        let rect = match NonZeroRect::from_xywh(0.0, -thickness / 2.0, dec_span.width, thickness) {
-            Some(v) => v,
-            None => {
-                log::warn!("a decoration span has a malformed bbox");
-                continue;
-            }
+        let ts = dec_span.transform.pre_translate(0.0, dy);
+
+        let gaps = if decoration.skip_ink && kind != DecorationKind::LineThrough {
+            ink_gaps(ink, ts, line.top(), line.bottom(), thickness)
+        } else {
+            Vec::new()
         };
 
-        let ts = dec_span.transform.pre_translate(0.0, dy);
+        let mut span_builder = tiny_skia_path::PathBuilder::new();
+        let mut x = 0.0;
+        for &(gap_start, gap_end) in gaps
+            .iter()
+            .chain(std::iter::once(&(dec_span.width, dec_span.width)))
+        {
+            let end = gap_start.min(dec_span.width);
+            if end > x {
+                line.push_segment(x, end, &mut span_builder);
+            }
+            x = f32::max(x, gap_end);
+        }
 
-        let mut path = tiny_skia_path::PathBuilder::from_rect(rect.to_rect());
-        path = match path.transform(ts) {
+        let path = match span_builder.finish().and_then(|p| p.transform(ts)) {
             Some(v) => v,
             None => continue,
         };
@@ -488,6 +1270,306 @@ pub(crate) fn convert_decoration(
     )
 }
 
+/// A decoration line geometry.
+///
+/// The line goes along the X axis and is centered at `y = 0`.
+struct DecorationLine {
+    style: TextDecorationLineStyle,
+    thickness: f32,
+    /// Offsets of the lines of a double decoration.
+    double_offsets: [f32; 2],
+}
+
+impl DecorationLine {
+    fn new(style: TextDecorationLineStyle, kind: DecorationKind, thickness: f32) -> Self {
+        // The second line of a double decoration is placed away from the text.
+        let double_offsets = match kind {
+            DecorationKind::Underline => [0.0, thickness * 2.0],
+            DecorationKind::Overline => [0.0, -thickness * 2.0],
+            DecorationKind::LineThrough => [-thickness, thickness],
+        };
+
+        DecorationLine {
+            style,
+            thickness,
+            double_offsets,
+        }
+    }
+
+    fn wave_amplitude(&self) -> f32 {
+        self.thickness
+    }
+
+    fn wave_length(&self) -> f32 {
+        self.thickness * 6.0
+    }
+
+    fn top(&self) -> f32 {
+        match self.style {
+            TextDecorationLineStyle::Double => {
+                self.double_offsets[0].min(self.double_offsets[1]) - self.thickness / 2.0
+            }
+            TextDecorationLineStyle::Wavy => -self.wave_amplitude() - self.thickness / 2.0,
+            _ => -self.thickness / 2.0,
+        }
+    }
+
+    fn bottom(&self) -> f32 {
+        match self.style {
+            TextDecorationLineStyle::Double => {
+                self.double_offsets[0].max(self.double_offsets[1]) + self.thickness / 2.0
+            }
+            TextDecorationLineStyle::Wavy => self.wave_amplitude() + self.thickness / 2.0,
+            _ => self.thickness / 2.0,
+        }
+    }
+
+    /// Appends a line segment between `x1` and `x2`.
+    ///
+    /// Patterns are aligned to `x = 0`, so segments interrupted by gaps stay in phase.
+    fn push_segment(&self, x1: f32, x2: f32, builder: &mut tiny_skia_path::PathBuilder) {
+        let t = self.thickness;
+        let push_rect = |builder: &mut tiny_skia_path::PathBuilder, x1: f32, x2: f32, y: f32| {
+            match tiny_skia_path::Rect::from_ltrb(x1, y - t / 2.0, x2, y + t / 2.0) {
+                Some(rect) => builder.push_rect(rect),
+                None => log::warn!("a decoration span has a malformed bbox"),
+            }
+        };
+
+        match self.style {
+            TextDecorationLineStyle::Solid => push_rect(builder, x1, x2, 0.0),
+            TextDecorationLineStyle::Double => {
+                for y in self.double_offsets {
+                    push_rect(builder, x1, x2, y);
+                }
+            }
+            TextDecorationLineStyle::Dashed => {
+                let (dash, period) = (t * 3.0, t * 6.0);
+                let mut x = (x1 / period).floor() * period;
+                while x < x2 {
+                    let (start, end) = (x.max(x1), (x + dash).min(x2));
+                    if end > start {
+                        push_rect(builder, start, end, 0.0);
+                    }
+                    x += period;
+                }
+            }
+            TextDecorationLineStyle::Dotted => {
+                let period = t * 2.0;
+                let mut x = (x1 / period).floor() * period + t / 2.0;
+                while x < x2 {
+                    if x >= x1 {
+                        builder.push_circle(x, 0.0, t / 2.0);
+                    }
+                    x += period;
+                }
+            }
+            TextDecorationLineStyle::Wavy => {
+                let amplitude = self.wave_amplitude();
+                let length = self.wave_length();
+                let wave_y = |x: f32| -amplitude * (x / length * 2.0 * std::f32::consts::PI).sin();
+
+                let mut wave = tiny_skia_path::PathBuilder::new();
+                wave.move_to(x1, wave_y(x1));
+                let step = length / 16.0;
+                let mut x = (x1 / step).floor() * step + step;
+                while x < x2 {
+                    wave.line_to(x, wave_y(x));
+                    x += step;
+                }
+                wave.line_to(x2, wave_y(x2));
+
+                let stroke = tiny_skia_path::Stroke {
+                    width: t,
+                    line_join: tiny_skia_path::LineJoin::Round,
+                    ..tiny_skia_path::Stroke::default()
+                };
+                if let Some(path) = wave.finish().and_then(|p| p.stroke(&stroke, 1.0)) {
+                    builder.push_path(&path);
+                }
+            }
+        }
+    }
+}
+
+/// Collects glyph outlines of the span in the span coordinates.
+fn collect_ink(
+    span: &TextSpan,
+    clusters: &[GlyphCluster],
+    fontdb: &Database,
+) -> Vec<tiny_skia_path::Path> {
+    let mut ink = Vec::new();
+    for cluster in clusters {
+        if !cluster.visible || !span_contains(span, cluster.byte_idx) {
+            continue;
+        }
+
+        for glyph in &cluster.glyphs {
+            let sx = glyph.font_size / glyph.units_per_em as f32;
+            let ts = cluster
+                .transform()
+                .pre_scale(sx, sx)
+                .pre_concat(glyph.glyph_ts)
+                .pre_scale(1.0, -1.0);
+
+            if let Some(path) = fontdb
+                .outline(glyph.font, glyph.id, &glyph.variations)
+                .and_then(|p| p.transform(ts))
+            {
+                ink.push(path);
+            }
+        }
+    }
+
+    ink
+}
+
+/// Returns sorted, non-overlapping horizontal ranges where glyphs cross the decoration line.
+///
+/// `line_ts` maps the decoration line coordinates, where the line lies on the X axis
+/// between `top` and `bottom`, to the span coordinates.
+fn ink_gaps(
+    ink: &[tiny_skia_path::Path],
+    line_ts: Transform,
+    top: f32,
+    bottom: f32,
+    padding: f32,
+) -> Vec<(f32, f32)> {
+    let ts = match line_ts.invert() {
+        Some(v) => v,
+        None => return Vec::new(),
+    };
+
+    let mut gaps: Vec<(f32, f32)> = Vec::new();
+    for path in ink {
+        let path = match path.clone().transform(ts) {
+            Some(v) => v,
+            None => continue,
+        };
+
+        let bounds = path.bounds();
+        if bounds.bottom() < top || bounds.top() > bottom {
+            continue;
+        }
+
+        let mut range: Option<(f32, f32)> = None;
+        for_each_line(&path, |p1, p2| {
+            if let Some((x1, x2)) = clip_line_vertically(p1, p2, top, bottom) {
+                range = Some(match range {
+                    Some((min, max)) => (min.min(x1), max.max(x2)),
+                    None => (x1, x2),
+                });
+            }
+        });
+
+        if let Some((min, max)) = range {
+            gaps.push((min - padding, max + padding));
+        }
+    }
+
+    gaps.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mut merged: Vec<(f32, f32)> = Vec::with_capacity(gaps.len());
+    for gap in gaps {
+        match merged.last_mut() {
+            Some(last) if gap.0 <= last.1 => last.1 = last.1.max(gap.1),
+            _ => merged.push(gap),
+        }
+    }
+
+    merged
+}
+
+/// Calls `f` for each line of a flattened path.
+fn for_each_line(
+    path: &tiny_skia_path::Path,
+    mut f: impl FnMut(tiny_skia_path::Point, tiny_skia_path::Point),
+) {
+    use tiny_skia_path::{PathSegment, Point};
+
+    // Glyph outlines are small, so a fixed number of steps is precise enough.
+    const STEPS: usize = 8;
+
+    let mut start = Point::zero();
+    let mut prev = Point::zero();
+    for segment in path.segments() {
+        match segment {
+            PathSegment::MoveTo(p) => {
+                start = p;
+                prev = p;
+            }
+            PathSegment::LineTo(p) => {
+                f(prev, p);
+                prev = p;
+            }
+            PathSegment::QuadTo(p1, p) => {
+                let curve = kurbo::QuadBez::new(
+                    (prev.x as f64, prev.y as f64),
+                    (p1.x as f64, p1.y as f64),
+                    (p.x as f64, p.y as f64),
+                );
+                for i in 1..=STEPS {
+                    let point = curve.eval(i as f64 / STEPS as f64);
+                    let point = Point::from_xy(point.x as f32, point.y as f32);
+                    f(prev, point);
+                    prev = point;
+                }
+            }
+            PathSegment::CubicTo(p1, p2, p) => {
+                let curve = kurbo::CubicBez::new(
+                    (prev.x as f64, prev.y as f64),
+                    (p1.x as f64, p1.y as f64),
+                    (p2.x as f64, p2.y as f64),
+                    (p.x as f64, p.y as f64),
+                );
+                for i in 1..=STEPS {
+                    let point = curve.eval(i as f64 / STEPS as f64);
+                    let point = Point::from_xy(point.x as f32, point.y as f32);
+                    f(prev, point);
+                    prev = point;
+                }
+            }
+            PathSegment::Close => {
+                f(prev, start);
+                prev = start;
+            }
+        }
+    }
+}
+
+/// Returns the horizontal range of a line part that lies between `top` and `bottom`.
+fn clip_line_vertically(
+    p1: tiny_skia_path::Point,
+    p2: tiny_skia_path::Point,
+    top: f32,
+    bottom: f32,
+) -> Option<(f32, f32)> {
+    if (p1.y < top && p2.y < top) || (p1.y > bottom && p2.y > bottom) {
+        return None;
+    }
+
+    let x_at = |y: f32| {
+        if p1.y == p2.y {
+            p1.x
+        } else {
+            p1.x + (p2.x - p1.x) * (y - p1.y) / (p2.y - p1.y)
+        }
+    };
+
+    let clip = |p: tiny_skia_path::Point| {
+        if p.y < top {
+            x_at(top)
+        } else if p.y > bottom {
+            x_at(bottom)
+        } else {
+            p.x
+        }
+    };
+
+    let (x1, x2) = (clip(p1), clip(p2));
+    Some((x1.min(x2), x1.max(x2)))
+}
+
 /// A text decoration span.
 ///
 /// Basically a horizontal line, that will be used for underline, overline and line-through.
@@ -538,7 +1620,8 @@ fn resolve_clusters_positions_horizontal(
     writing_mode: WritingMode,
     clusters: &mut [GlyphCluster],
 ) -> (f32, f32) {
-    let mut x = process_anchor(chunk.anchor, clusters_length(clusters));
+    let anchor = resolve_anchor(chunk.anchor, text.direction);
+    let mut x = process_anchor(anchor, clusters_length(clusters));
     let mut y = 0.0;
 
     for cluster in clusters {
@@ -636,7 +1719,19 @@ fn resolve_clusters_positions_path(
     let start_offset =
         chunk_offset + path.start_offset + process_anchor(chunk.anchor, clusters_length(clusters));
 
-    let normals = collect_normals(text, chunk, clusters, &path.path, char_offset, start_offset);
+    let reversed_path;
+    let path_data = match path.side {
+        TextPathSide::Left => &path.path,
+        TextPathSide::Right => match reverse_path(&path.path) {
+            Some(v) => {
+                reversed_path = Arc::new(v);
+                &reversed_path
+            }
+            None => &path.path,
+        },
+    };
+
+    let normals = collect_normals(text, chunk, clusters, path_data, char_offset, start_offset);
     for (cluster, normal) in clusters.iter_mut().zip(normals) {
         let (x, y, angle) = match normal {
             Some(normal) => (normal.x, normal.y, normal.angle),
@@ -696,6 +1791,73 @@ fn resolve_clusters_positions_path(
     (last_x, last_y)
 }
 
+/// Reverses the path direction.
+///
+/// Subpaths are reversed as well, while closed ones are closed by an explicit line.
+fn reverse_path(path: &tiny_skia_path::Path) -> Option<tiny_skia_path::Path> {
+    use tiny_skia_path::{PathSegment, Point};
+
+    // A subpath start point and its segments with their start points.
+    let mut subpaths: Vec<(Point, Vec<(Point, PathSegment)>)> = Vec::new();
+    let mut prev = Point::zero();
+    for segment in path.segments() {
+        match segment {
+            PathSegment::MoveTo(p) => {
+                subpaths.push((p, Vec::new()));
+                prev = p;
+            }
+            PathSegment::LineTo(p) | PathSegment::QuadTo(_, p) | PathSegment::CubicTo(_, _, p) => {
+                subpaths.last_mut()?.1.push((prev, segment));
+                prev = p;
+            }
+            PathSegment::Close => {
+                let (start, segments) = subpaths.last_mut()?;
+                let start = *start;
+                segments.push((prev, PathSegment::LineTo(start)));
+                prev = start;
+            }
+        }
+    }
+
+    let mut builder = tiny_skia_path::PathBuilder::new();
+    for (start, segments) in subpaths.iter().rev() {
+        let end = segments
+            .last()
+            .map_or(*start, |(_, segment)| match *segment {
+                PathSegment::LineTo(p)
+                | PathSegment::QuadTo(_, p)
+                | PathSegment::CubicTo(_, _, p) => p,
+                _ => *start,
+            });
+        builder.move_to(end.x, end.y);
+
+        for &(from, segment) in segments.iter().rev() {
+            match segment {
+                PathSegment::LineTo(_) => builder.line_to(from.x, from.y),
+                PathSegment::QuadTo(p1, _) => builder.quad_to(p1.x, p1.y, from.x, from.y),
+                PathSegment::CubicTo(p1, p2, _) => {
+                    builder.cubic_to(p2.x, p2.y, p1.x, p1.y, from.x, from.y)
+                }
+                _ => {}
+            }
+        }
+    }
+
+    builder.finish()
+}
+
+/// Converts a text anchor into a physical one.
+///
+/// `start` and `end` are swapped for the right-to-left text.
+/// Text on a path is not affected, since it always starts at the path start.
+fn resolve_anchor(anchor: TextAnchor, direction: TextDirection) -> TextAnchor {
+    match (anchor, direction) {
+        (TextAnchor::Start, TextDirection::RightToLeft) => TextAnchor::End,
+        (TextAnchor::End, TextDirection::RightToLeft) => TextAnchor::Start,
+        _ => anchor,
+    }
+}
+
 pub(crate) fn process_anchor(a: TextAnchor, text_width: f32) -> f32 {
     match a {
         TextAnchor::Start => 0.0, // Nothing.
@@ -847,6 +2009,8 @@ fn collect_normals(
 /// but not the text layouting. So all clusters are in the 0x0 position.
 fn process_chunk(
     chunk: &TextChunk,
+    writing_mode: WritingMode,
+    direction: TextDirection,
     fonts_cache: &FontsCache,
     resolver: &FontResolver,
     fontdb: &mut Arc<fontdb::Database>,
@@ -894,14 +2058,7 @@ fn process_chunk(
             None => continue,
         };
 
-        let tmp_glyphs = shape_text(
-            &chunk.text,
-            font,
-            span.small_caps,
-            span.apply_kerning,
-            resolver,
-            fontdb,
-        );
+        let tmp_glyphs = shape_text(chunk, font, span, writing_mode, direction, resolver, fontdb);
 
         // Do nothing with the first run.
         if glyphs.is_empty() {
@@ -1024,31 +2181,33 @@ fn apply_length_adjust(chunk: &TextChunk, clusters: &mut [GlyphCluster]) {
     }
 }
 
-/// Rotates clusters according to
-/// [Unicode Vertical_Orientation Property](https://www.unicode.org/reports/tr50/tr50-19.html).
+/// Rotates upright clusters back, since vertical text is laid out horizontally first.
+///
+/// Whether a cluster is upright is resolved during shaping, according to the `text-orientation`
+/// and [Unicode Vertical_Orientation Property](https://www.unicode.org/reports/tr50/tr50-19.html).
 fn apply_writing_mode(writing_mode: WritingMode, clusters: &mut [GlyphCluster]) {
     if writing_mode != WritingMode::TopToBottom {
         return;
     }
 
     for cluster in clusters {
-        let orientation = unicode_vo::char_orientation(cluster.codepoint);
-        if orientation == unicode_vo::Orientation::Upright {
-            let mut ts = Transform::default();
-            // Position glyph in the center of vertical axis.
-            ts = ts.pre_translate(0.0, (cluster.ascent + cluster.descent) / 2.0);
-            // Rotate by 90 degrees in the center.
-            ts = ts.pre_rotate_at(
-                -90.0,
-                cluster.width / 2.0,
-                -(cluster.ascent + cluster.descent) / 2.0,
-            );
+        if cluster.upright {
+            // Without vertical metrics, the font height is used as an advance
+            // and the glyph top is placed at the font ascent.
+            let advance = cluster.vertical_advance.unwrap_or(cluster.height());
+            let origin = cluster.vertical_origin.unwrap_or(cluster.ascent);
 
-            cluster.path_transform = ts;
+            // Rotate by -90 degrees, so the vertical origin,
+            // which is at the horizontal center of the glyph, ends up on the baseline.
+            cluster.path_transform =
+                Transform::from_row(0.0, -1.0, 1.0, 0.0, origin, cluster.width / 2.0);
 
             // Move "baseline" to the middle and make height equal to width.
             cluster.ascent = cluster.width / 2.0;
             cluster.descent = -cluster.width / 2.0;
+
+            cluster.width = advance;
+            cluster.advance = advance;
         } else {
             // Could not find a spec that explains this,
             // but this is how other applications are shifting the "rotated" characters
@@ -1155,6 +2314,7 @@ fn form_glyph_clusters(glyphs: &[Glyph], text: &str, font_size: f32) -> GlyphClu
             units_per_em: glyph.font.units_per_em.get(),
             font_size,
             font: glyph.font.id,
+            variations: glyph.variations.clone(),
             text: glyph.text.clone(),
             id: glyph.id,
         });
@@ -1169,6 +2329,7 @@ fn form_glyph_clusters(glyphs: &[Glyph], text: &str, font_size: f32) -> GlyphClu
 
     let byte_idx = glyphs[0].byte_idx;
     let font = glyphs[0].font.clone();
+    let sx = font.scale(font_size);
     GlyphCluster {
         byte_idx,
         codepoint: byte_idx.char_from(text),
@@ -1181,6 +2342,9 @@ fn form_glyph_clusters(glyphs: &[Glyph], text: &str, font_size: f32) -> GlyphClu
         path_transform: Transform::default(),
         glyphs: positioned_glyphs,
         visible: true,
+        upright: glyphs[0].upright,
+        vertical_advance: glyphs[0].vertical_advance.map(|v| v as f32 * sx),
+        vertical_origin: glyphs[0].vertical_origin.map(|v| v as f32 * sx),
     }
 }
 
@@ -1277,15 +2441,18 @@ impl DatabaseExt for Database {
 
 /// Text shaping with font fallback.
 pub(crate) fn shape_text(
-    text: &str,
+    chunk: &TextChunk,
     font: Arc<ResolvedFont>,
-    small_caps: bool,
-    apply_kerning: bool,
+    span: &TextSpan,
+    writing_mode: WritingMode,
+    direction: TextDirection,
     resolver: &FontResolver,
     fontdb: &mut Arc<fontdb::Database>,
 ) -> Vec<Glyph> {
-    let mut glyphs = shape_text_with_font(text, font.clone(), small_caps, apply_kerning, fontdb)
-        .unwrap_or_default();
+    let text = chunk.text.as_str();
+    let mut glyphs =
+        shape_text_with_font(chunk, font.clone(), span, writing_mode, direction, fontdb)
+            .unwrap_or_default();
 
     // Remember all fonts used for shaping.
     let mut used_fonts = vec![font.id];
@@ -1310,10 +2477,11 @@ pub(crate) fn shape_text(
 
             // Shape again, using a new font.
             let fallback_glyphs = shape_text_with_font(
-                text,
+                chunk,
                 fallback_font.clone(),
-                small_caps,
-                apply_kerning,
+                span,
+                writing_mode,
+                direction,
                 fontdb,
             )
             .unwrap_or_default();
@@ -1363,57 +2531,147 @@ pub(crate) fn shape_text(
     glyphs
 }
 
+/// Resolves variable font axis values.
+///
+/// `wght` and `wdth` are set from the font weight and stretch,
+/// unless overridden by explicit variations.
+fn resolve_variations(font: &Font) -> Vec<FontVariation> {
+    let mut variations = vec![
+        FontVariation::new(*b"wght", font.weight as f32),
+        FontVariation::new(*b"wdth", font.stretch.to_percentage()),
+    ];
+
+    for variation in &font.variations {
+        if let Some(v) = variations.iter_mut().find(|v| v.tag == variation.tag) {
+            v.value = variation.value;
+        } else {
+            variations.push(*variation);
+        }
+    }
+
+    variations
+}
+
 /// Converts a text into a list of glyph IDs.
 ///
 /// This function will do the BIDI reordering and text shaping.
 fn shape_text_with_font(
-    text: &str,
+    chunk: &TextChunk,
     font: Arc<ResolvedFont>,
-    small_caps: bool,
-    apply_kerning: bool,
+    span: &TextSpan,
+    writing_mode: WritingMode,
+    direction: TextDirection,
     fontdb: &fontdb::Database,
 ) -> Option<Vec<Glyph>> {
+    let text = chunk.text.as_str();
     fontdb.with_face_data(font.id, |font_data, face_index| -> Option<Vec<Glyph>> {
-        let rb_font = rustybuzz::Face::from_slice(font_data, face_index)?;
+        let mut rb_font = rustybuzz::Face::from_slice(font_data, face_index)?;
+
+        // Static fonts are shaped and outlined as is.
+        let variations: Arc<[FontVariation]> = if rb_font.is_variable() {
+            let variations = resolve_variations(&span.font);
+            for variation in &variations {
+                rb_font.set_variation(Tag::from_bytes(&variation.tag), variation.value);
+            }
+            variations.into()
+        } else {
+            Arc::from([])
+        };
+
+        // Text orientation affects only vertical text.
+        let orientation = match writing_mode {
+            WritingMode::LeftToRight => None,
+            WritingMode::TopToBottom => Some(span.text_orientation),
+        };
 
-        let bidi_info = unicode_bidi::BidiInfo::new(text, Some(unicode_bidi::Level::ltr()));
-        let paragraph = &bidi_info.paragraphs[0];
-        let line = paragraph.range.clone();
+        let bidi_info = resolve_bidi_levels(chunk, direction);
 
-        let mut glyphs = Vec::new();
+        // Preserved line breaks split text into multiple paragraphs.
+        let runs: Vec<_> = if orientation == Some(TextOrientation::Upright) {
+            // Upright text treats all characters as strong left-to-right.
+            bidi_info
+                .paragraphs
+                .iter()
+                .map(|paragraph| (unicode_bidi::Level::ltr(), paragraph.range.clone()))
+                .collect()
+        } else {
+            bidi_info
+                .paragraphs
+                .iter()
+                .flat_map(|paragraph| {
+                    let (levels, runs) = bidi_info.visual_runs(paragraph, paragraph.range.clone());
+                    runs.into_iter().map(move |run| (levels[run.start], run))
+                })
+                .collect()
+        };
 
-        let (levels, runs) = bidi_info.visual_runs(paragraph, line);
-        for run in runs.iter() {
+        let mut glyphs = Vec::new();
+        for (level, run) in runs {
             let sub_text = &text[run.clone()];
             if sub_text.is_empty() {
                 continue;
             }
 
-            let ltr = levels[run.start].is_ltr();
+            let ltr = level.is_ltr();
             let hb_direction = if ltr {
                 rustybuzz::Direction::LeftToRight
             } else {
                 rustybuzz::Direction::RightToLeft
             };
 
-            let mut buffer = rustybuzz::UnicodeBuffer::new();
-            buffer.push_str(sub_text);
-            buffer.set_direction(hb_direction);
+            let shape = |vertical_features: &[&[u8; 4]]| {
+                let mut buffer = rustybuzz::UnicodeBuffer::new();
+                buffer.push_str(sub_text);
+                buffer.set_direction(hb_direction);
 
-            let mut features = Vec::new();
-            if small_caps {
-                features.push(rustybuzz::Feature::new(Tag::from_bytes(b"smcp"), 1, ..));
-            }
+                let mut features = Vec::new();
+                if span.small_caps {
+                    features.push(rustybuzz::Feature::new(Tag::from_bytes(b"smcp"), 1, ..));
+                }
 
-            if !apply_kerning {
-                features.push(rustybuzz::Feature::new(Tag::from_bytes(b"kern"), 0, ..));
-            }
+                if !span.apply_kerning {
+                    features.push(rustybuzz::Feature::new(Tag::from_bytes(b"kern"), 0, ..));
+                }
+
+                for tag in vertical_features {
+                    features.push(rustybuzz::Feature::new(Tag::from_bytes(tag), 1, ..));
+                }
+
+                for feature in &span.font_features {
+                    features.push(rustybuzz::Feature::new(
+                        Tag::from_bytes(&feature.tag),
+                        feature.value,
+                        ..,
+                    ));
+                }
+
+                rustybuzz::shape(&rb_font, &features, buffer)
+            };
 
-            let output = rustybuzz::shape(&rb_font, &features, buffer);
+            // Glyphs are still shaped horizontally, but use vertical alternates when available.
+            // `vrt2` alternates are already rotated and must be set upright as well.
+            let output = match orientation {
+                Some(TextOrientation::Mixed) => shape(&[b"vert", b"vrt2"]),
+                Some(TextOrientation::Upright) => shape(&[b"vert"]),
+                _ => shape(&[]),
+            };
 
             let positions = output.glyph_positions();
             let infos = output.glyph_infos();
 
+            // In the mixed orientation, characters that are rotated by default
+            // are set upright only when the font has a vertical alternate for them.
+            // Which we can detect only by comparing the output with a regular one.
+            let mut alternates = HashSet::new();
+            if orientation == Some(TextOrientation::Mixed) {
+                let horizontal = group_clusters(shape(&[]).glyph_infos());
+                for (cluster, ids) in group_clusters(infos) {
+                    if horizontal.get(&cluster) != Some(&ids) {
+                        alternates.insert(cluster);
+                    }
+                }
+            }
+
             for i in 0..output.len() {
                 let pos = positions[i];
                 let info = infos[i];
@@ -1429,15 +2687,42 @@ fn shape_text_with_font(
                 .and_then(|last| infos.get(last))
                 .map_or(sub_text.len(), |info| info.cluster as usize);
 
+                let upright = match orientation {
+                    Some(TextOrientation::Mixed) => {
+                        let c = ByteIndex::new(start).char_from(sub_text);
+                        match unicode_vo::char_orientation(c) {
+                            unicode_vo::Orientation::Upright
+                            | unicode_vo::Orientation::TransformedOrUpright => true,
+                            _ => alternates.contains(&info.cluster),
+                        }
+                    }
+                    Some(TextOrientation::Upright) => true,
+                    Some(TextOrientation::Sideways) | None => false,
+                };
+
+                let id = GlyphId(info.glyph_id as u16);
+                let (vertical_advance, vertical_origin) = if upright {
+                    (
+                        rb_font.glyph_ver_advance(id),
+                        glyph_vertical_origin(&rb_font, id),
+                    )
+                } else {
+                    (None, None)
+                };
+
                 glyphs.push(Glyph {
                     byte_idx: ByteIndex::new(idx),
                     cluster_len: end.checked_sub(start).unwrap_or(0), // TODO: can fail?
                     text: sub_text[start..end].to_string(),
-                    id: GlyphId(info.glyph_id as u16),
+                    id,
                     dx: pos.x_offset,
                     dy: pos.y_offset,
                     width: pos.x_advance,
+                    upright,
+                    vertical_advance,
+                    vertical_origin,
                     font: font.clone(),
+                    variations: variations.clone(),
                 });
             }
         }
@@ -1446,6 +2731,91 @@ fn shape_text_with_font(
     })?
 }
 
+/// Resolves bidi embedding levels of a text chunk.
+///
+/// Explicit embeddings are resolved by inserting the matching Unicode formatting characters
+/// into a copy of the text, since the bidi algorithm doesn't accept them in any other way.
+fn resolve_bidi_levels(chunk: &TextChunk, direction: TextDirection) -> unicode_bidi::BidiInfo<'_> {
+    let level = match direction {
+        TextDirection::LeftToRight => unicode_bidi::Level::ltr(),
+        TextDirection::RightToLeft => unicode_bidi::Level::rtl(),
+    };
+
+    let mut bidi_info = unicode_bidi::BidiInfo::new(&chunk.text, Some(level));
+    if chunk.bidi_embeddings.is_empty() {
+        return bidi_info;
+    }
+
+    let embeddings = &chunk.bidi_embeddings;
+    let mut text = String::with_capacity(chunk.text.len());
+    // Byte offsets of the original text in the new one.
+    let mut offsets = Vec::with_capacity(chunk.text.len());
+    let close = |text: &mut String, idx: usize| {
+        // Starting from the innermost embedding.
+        for embedding in embeddings.iter().rev().filter(|e| e.end == idx) {
+            match embedding.unicode_bidi {
+                UnicodeBidi::Embed | UnicodeBidi::BidiOverride => text.push('\u{202C}'),
+                UnicodeBidi::Isolate | UnicodeBidi::Plaintext => text.push('\u{2069}'),
+                UnicodeBidi::IsolateOverride => text.push_str("\u{202C}\u{2069}"),
+            }
+        }
+    };
+
+    for (idx, c) in chunk.text.char_indices() {
+        close(&mut text, idx);
+
+        for embedding in embeddings.iter().filter(|e| e.start == idx) {
+            let rtl = embedding.direction == TextDirection::RightToLeft;
+            match (embedding.unicode_bidi, rtl) {
+                (UnicodeBidi::Embed, false) => text.push('\u{202A}'),
+                (UnicodeBidi::Embed, true) => text.push('\u{202B}'),
+                (UnicodeBidi::BidiOverride, false) => text.push('\u{202D}'),
+                (UnicodeBidi::BidiOverride, true) => text.push('\u{202E}'),
+                (UnicodeBidi::Isolate, false) => text.push('\u{2066}'),
+                (UnicodeBidi::Isolate, true) => text.push('\u{2067}'),
+                (UnicodeBidi::IsolateOverride, false) => text.push_str("\u{2066}\u{202D}"),
+                (UnicodeBidi::IsolateOverride, true) => text.push_str("\u{2067}\u{202E}"),
+                (UnicodeBidi::Plaintext, _) => text.push('\u{2068}'),
+            }
+        }
+
+        offsets.extend(text.len()..text.len() + c.len_utf8());
+        text.push(c);
+    }
+    close(&mut text, chunk.text.len());
+
+    let embedded = unicode_bidi::BidiInfo::new(&text, Some(level));
+    for (level, offset) in bidi_info.levels.iter_mut().zip(offsets) {
+        *level = embedded.levels[offset];
+    }
+
+    bidi_info
+}
+
+/// Groups glyph IDs by clusters.
+fn group_clusters(infos: &[rustybuzz::GlyphInfo]) -> HashMap<u32, Vec<u32>> {
+    let mut clusters: HashMap<u32, Vec<u32>> = HashMap::new();
+    for info in infos {
+        clusters
+            .entry(info.cluster)
+            .or_default()
+            .push(info.glyph_id);
+    }
+    clusters
+}
+
+/// Returns the Y coordinate of the glyph vertical origin in font units.
+///
+/// Uses the `VORG` table when present and the vertical metrics otherwise.
+fn glyph_vertical_origin(face: &ttf_parser::Face, id: GlyphId) -> Option<i16> {
+    if let Some(y) = face.glyph_y_origin(id) {
+        return Some(y);
+    }
+
+    let bbox = face.glyph_bounding_box(id)?;
+    face.glyph_ver_side_bearing(id)?.checked_add(bbox.y_max)
+}
+
 /// An iterator over glyph clusters.
 ///
 /// Input:  0 2 2 2 3 4 4 5 5
@@ -1540,15 +2910,34 @@ pub(crate) struct Glyph {
     /// The glyph width / X-advance in font units.
     pub(crate) width: i32,
 
+    /// Indicates that the glyph is set upright in the vertical text.
+    pub(crate) upright: bool,
+
+    /// The glyph Y-advance in font units.
+    ///
+    /// Set only for upright glyphs from fonts with vertical metrics.
+    pub(crate) vertical_advance: Option<u16>,
+
+    /// The glyph vertical origin Y coordinate in font units.
+    ///
+    /// Set only for upright glyphs from fonts with vertical metrics.
+    pub(crate) vertical_origin: Option<i16>,
+
     /// Reference to the source font.
     ///
     /// Each glyph can have it's own source font.
     pub(crate) font: Arc<ResolvedFont>,
+
+    /// Variable font axis values the glyph was shaped with.
+    ///
+    /// Empty for static fonts.
+    pub(crate) variations: Arc<[FontVariation]>,
 }
 
 impl Glyph {
     fn is_missing(&self) -> bool {
-        self.id.0 == 0
+        // Line breaks are never rendered, so a font doesn't have to support them.
+        self.id.0 == 0 && self.text != "\n"
     }
 }
 
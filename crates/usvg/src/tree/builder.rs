@@ -0,0 +1,1119 @@
+// Copyright 2025 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use std::sync::Arc;
+
+use super::*;
+
+/// A [`Tree`] builder.
+///
+/// Allows creating a tree without an SVG document.
+///
+/// All the data that `usvg` resolves during parsing, like absolute transforms,
+/// bounding boxes and the lists of paint servers, clip paths, masks and filters,
+/// will be calculated by [`TreeBuilder::build`].
+///
+/// All coordinates are in the user space, the same way as in a parsed tree.
+/// Meaning there are no `objectBoundingBox` units.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// let mut builder = usvg::tiny_skia_path::PathBuilder::new();
+/// builder.move_to(10.0, 10.0);
+/// builder.line_to(90.0, 90.0);
+/// let data = Arc::new(builder.finish().unwrap());
+///
+/// let path = usvg::PathBuilder::new(data)
+///     .stroke(usvg::Stroke::default())
+///     .build()
+///     .unwrap();
+///
+/// let group = usvg::GroupBuilder::new()
+///     .transform(usvg::Transform::from_translate(10.0, 0.0))
+///     .child(path)
+///     .build();
+///
+/// let tree = usvg::TreeBuilder::new(usvg::Size::from_wh(100.0, 100.0).unwrap())
+///     .child(group)
+///     .build();
+///
+/// assert_eq!(tree.root().abs_bounding_box().x(), 20.0);
+/// ```
+#[derive(Debug)]
+pub struct TreeBuilder {
+    size: Size,
+    root: Group,
+    #[cfg(feature = "text")]
+    fontdb: Arc<fontdb::Database>,
+}
+
+impl TreeBuilder {
+    /// Creates a new builder with an empty root group.
+    pub fn new(size: Size) -> Self {
+        TreeBuilder {
+            size,
+            root: Group::empty(),
+            #[cfg(feature = "text")]
+            fontdb: Arc::new(fontdb::Database::new()),
+        }
+    }
+
+    /// Sets a fonts database that will be available via [`Tree::fontdb`].
+    ///
+    /// Should be the same database that was used by [`TextBuilder::build`].
+    #[cfg(feature = "text")]
+    pub fn fontdb(mut self, fontdb: Arc<fontdb::Database>) -> Self {
+        self.fontdb = fontdb;
+        self
+    }
+
+    /// Appends a child to the root group.
+    pub fn child(mut self, node: impl Into<Node>) -> Self {
+        self.root.children.push(node.into());
+        self
+    }
+
+    /// Builds a tree.
+    pub fn build(self) -> Tree {
+        let mut tree = Tree {
+            size: self.size,
+            root: self.root,
+            linear_gradients: Vec::new(),
+            radial_gradients: Vec::new(),
+            conic_gradients: Vec::new(),
+            patterns: Vec::new(),
+            mesh_gradients: Vec::new(),
+            hatches: Vec::new(),
+            clip_paths: Vec::new(),
+            masks: Vec::new(),
+            filters: Vec::new(),
+            #[cfg(feature = "text")]
+            fontdb: self.fontdb,
+            diagnostics: Vec::new(),
+        };
+        tree.recalculate();
+        tree
+    }
+}
+
+/// A [`Group`] builder.
+#[derive(Default, Debug)]
+pub struct GroupBuilder {
+    group: Group,
+}
+
+impl GroupBuilder {
+    /// Creates a new builder for an empty group.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets element's ID.
+    ///
+    /// It's up to the caller to make sure that IDs are unique.
+    pub fn id(mut self, id: String) -> Self {
+        self.group.id = id;
+        self
+    }
+
+    /// Sets element's relative transform.
+    pub fn transform(mut self, transform: Transform) -> Self {
+        self.group.transform = transform;
+        self
+    }
+
+    /// Sets group opacity.
+    pub fn opacity(mut self, opacity: Opacity) -> Self {
+        self.group.opacity = opacity;
+        self
+    }
+
+    /// Sets group blend mode.
+    pub fn blend_mode(mut self, blend_mode: BlendMode) -> Self {
+        self.group.blend_mode = blend_mode;
+        self
+    }
+
+    /// Sets group isolation.
+    pub fn isolate(mut self, isolate: bool) -> Self {
+        self.group.isolate = isolate;
+        self
+    }
+
+    /// Sets element's clip path.
+    pub fn clip_path(mut self, clip_path: Arc<ClipPath>) -> Self {
+        self.group.clip_path = Some(clip_path);
+        self
+    }
+
+    /// Appends a mask layer.
+    ///
+    /// Layers are listed from top to bottom, like `mask-image` in CSS.
+    pub fn mask(mut self, mask: Arc<Mask>, composite: MaskComposite) -> Self {
+        self.group.masks.push(MaskLayer::new(mask, composite));
+        self
+    }
+
+    /// Appends a filter.
+    ///
+    /// Filters are applied in the same order they were added.
+    pub fn filter(mut self, filter: Arc<filter::Filter>) -> Self {
+        self.group.filters.push(filter);
+        self
+    }
+
+    /// Appends a child.
+    pub fn child(mut self, node: impl Into<Node>) -> Self {
+        self.group.children.push(node.into());
+        self
+    }
+
+    /// Builds a group.
+    ///
+    /// Bounding boxes are calculated as if the group is the root one.
+    /// They will be updated once the group is added to a tree.
+    pub fn build(mut self) -> Group {
+        self.group.recalculate(Transform::default());
+        self.group
+    }
+}
+
+/// A [`Path`] builder.
+///
+/// Paths have no fill and no stroke by default.
+#[derive(Debug)]
+pub struct PathBuilder {
+    id: String,
+    visible: bool,
+    fill: Option<Fill>,
+    stroke: Option<Stroke>,
+    paint_order: PaintOrder,
+    rendering_mode: ShapeRendering,
+    data: Arc<tiny_skia_path::Path>,
+}
+
+impl PathBuilder {
+    /// Creates a new builder for the specified path segments.
+    pub fn new(data: Arc<tiny_skia_path::Path>) -> Self {
+        PathBuilder {
+            id: String::new(),
+            visible: true,
+            fill: None,
+            stroke: None,
+            paint_order: PaintOrder::default(),
+            rendering_mode: ShapeRendering::default(),
+            data,
+        }
+    }
+
+    /// Sets element's ID.
+    ///
+    /// It's up to the caller to make sure that IDs are unique.
+    pub fn id(mut self, id: String) -> Self {
+        self.id = id;
+        self
+    }
+
+    /// Sets element visibility.
+    pub fn visible(mut self, visible: bool) -> Self {
+        self.visible = visible;
+        self
+    }
+
+    /// Sets fill style.
+    pub fn fill(mut self, fill: Fill) -> Self {
+        self.fill = Some(fill);
+        self
+    }
+
+    /// Sets stroke style.
+    pub fn stroke(mut self, stroke: Stroke) -> Self {
+        self.stroke = Some(stroke);
+        self
+    }
+
+    /// Sets fill and stroke paint order.
+    pub fn paint_order(mut self, paint_order: PaintOrder) -> Self {
+        self.paint_order = paint_order;
+        self
+    }
+
+    /// Sets rendering mode.
+    pub fn rendering_mode(mut self, rendering_mode: ShapeRendering) -> Self {
+        self.rendering_mode = rendering_mode;
+        self
+    }
+
+    /// Builds a path.
+    ///
+    /// Returns `None` when the path has no bounding box.
+    pub fn build(self) -> Option<Path> {
+        Path::new(
+            self.id,
+            self.visible,
+            self.fill,
+            self.stroke,
+            self.paint_order,
+            self.rendering_mode,
+            self.data,
+            Transform::default(),
+        )
+    }
+}
+
+/// An [`Image`] builder.
+///
+/// Images are always placed at the origin using their actual size.
+/// Use a parent group transform to position and scale them.
+#[derive(Debug)]
+pub struct ImageBuilder {
+    id: String,
+    visible: bool,
+    rendering_mode: ImageRendering,
+    kind: ImageKind,
+}
+
+impl ImageBuilder {
+    /// Creates a new builder for the specified image data.
+    pub fn new(kind: ImageKind) -> Self {
+        ImageBuilder {
+            id: String::new(),
+            visible: true,
+            rendering_mode: ImageRendering::default(),
+            kind,
+        }
+    }
+
+    /// Sets element's ID.
+    ///
+    /// It's up to the caller to make sure that IDs are unique.
+    pub fn id(mut self, id: String) -> Self {
+        self.id = id;
+        self
+    }
+
+    /// Sets element visibility.
+    pub fn visible(mut self, visible: bool) -> Self {
+        self.visible = visible;
+        self
+    }
+
+    /// Sets rendering mode.
+    pub fn rendering_mode(mut self, rendering_mode: ImageRendering) -> Self {
+        self.rendering_mode = rendering_mode;
+        self
+    }
+
+    /// Builds an image.
+    ///
+    /// Returns `None` when the image size cannot be determined.
+    pub fn build(self) -> Option<Image> {
+        let size = self.kind.actual_size()?;
+        Some(Image {
+            id: self.id,
+            visible: self.visible,
+            size,
+            rendering_mode: self.rendering_mode,
+            kind: self.kind,
+            abs_transform: Transform::default(),
+            abs_bounding_box: size.to_non_zero_rect(0.0, 0.0),
+        })
+    }
+}
+
+/// A [`Text`] builder.
+///
+/// Text is laid out and converted into paths by [`TextBuilder::build`].
+#[cfg(feature = "text")]
+#[derive(Debug)]
+pub struct TextBuilder {
+    id: String,
+    rendering_mode: TextRendering,
+    writing_mode: WritingMode,
+    direction: TextDirection,
+    wrap: Option<TextWrap>,
+    chunks: Vec<TextChunk>,
+}
+
+#[cfg(feature = "text")]
+impl Default for TextBuilder {
+    fn default() -> Self {
+        TextBuilder {
+            id: String::new(),
+            rendering_mode: TextRendering::default(),
+            writing_mode: WritingMode::LeftToRight,
+            direction: TextDirection::default(),
+            wrap: None,
+            chunks: Vec::new(),
+        }
+    }
+}
+
+#[cfg(feature = "text")]
+impl TextBuilder {
+    /// Creates a new builder for an empty text.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets element's ID.
+    ///
+    /// It's up to the caller to make sure that IDs are unique.
+    pub fn id(mut self, id: String) -> Self {
+        self.id = id;
+        self
+    }
+
+    /// Sets rendering mode.
+    pub fn rendering_mode(mut self, rendering_mode: TextRendering) -> Self {
+        self.rendering_mode = rendering_mode;
+        self
+    }
+
+    /// Sets a writing mode.
+    pub fn writing_mode(mut self, writing_mode: WritingMode) -> Self {
+        self.writing_mode = writing_mode;
+        self
+    }
+
+    /// Sets a base text direction.
+    pub fn direction(mut self, direction: TextDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Sets text wrapping.
+    pub fn wrap(mut self, wrap: TextWrap) -> Self {
+        self.wrap = Some(wrap);
+        self
+    }
+
+    /// Starts a new text chunk at the specified position.
+    pub fn chunk(mut self, x: f32, y: f32, anchor: TextAnchor) -> Self {
+        self.chunks.push(TextChunk {
+            x: Some(x),
+            y: Some(y),
+            anchor,
+            spans: Vec::new(),
+            text_flow: TextFlow::Linear,
+            text: String::new(),
+            bidi_embeddings: Vec::new(),
+        });
+        self
+    }
+
+    /// Appends a text span to the current text chunk.
+    ///
+    /// A chunk at the origin will be started if there are none yet.
+    pub fn span(
+        mut self,
+        text: &str,
+        font: Font,
+        font_size: NonZeroPositiveF32,
+        fill: Option<Fill>,
+        stroke: Option<Stroke>,
+    ) -> Self {
+        if self.chunks.is_empty() {
+            self = self.chunk(0.0, 0.0, TextAnchor::Start);
+        }
+
+        let chunk = self.chunks.last_mut().unwrap();
+        let start = chunk.text.len();
+        chunk.text.push_str(text);
+        chunk.spans.push(TextSpan {
+            start,
+            end: chunk.text.len(),
+            fill,
+            stroke,
+            paint_order: PaintOrder::default(),
+            font,
+            font_size,
+            small_caps: false,
+            apply_kerning: true,
+            font_features: Vec::new(),
+            decoration: TextDecoration {
+                underline: None,
+                overline: None,
+                line_through: None,
+            },
+            dominant_baseline: DominantBaseline::default(),
+            alignment_baseline: AlignmentBaseline::default(),
+            baseline_shift: Vec::new(),
+            visible: true,
+            letter_spacing: 0.0,
+            word_spacing: 0.0,
+            text_length: None,
+            length_adjust: LengthAdjust::default(),
+            white_space: WhiteSpace::default(),
+            line_height: None,
+            text_orientation: TextOrientation::default(),
+        });
+        self
+    }
+
+    /// Lays out a text using the default [`FontResolver`](crate::FontResolver).
+    ///
+    /// Returns `None` when the text is empty or when no fonts were found.
+    pub fn build(self, fontdb: &mut Arc<fontdb::Database>) -> Option<Text> {
+        self.build_with_resolver(&crate::FontResolver::default(), fontdb)
+    }
+
+    /// Lays out a text using the specified [`FontResolver`](crate::FontResolver).
+    ///
+    /// Fonts loaded by the resolver will be added to `fontdb`.
+    pub fn build_with_resolver(
+        self,
+        resolver: &crate::FontResolver,
+        fontdb: &mut Arc<fontdb::Database>,
+    ) -> Option<Text> {
+        let dummy = Rect::from_xywh(0.0, 0.0, 0.0, 0.0).unwrap();
+        let mut text = Text {
+            id: self.id,
+            rendering_mode: self.rendering_mode,
+            dx: Vec::new(),
+            dy: Vec::new(),
+            rotate: Vec::new(),
+            writing_mode: self.writing_mode,
+            direction: self.direction,
+            wrap: self.wrap,
+            chunks: self.chunks,
+            abs_transform: Transform::default(),
+            // All fields below will be reset by `text::convert`.
+            bounding_box: dummy,
+            abs_bounding_box: dummy,
+            stroke_bounding_box: dummy,
+            abs_stroke_bounding_box: dummy,
+            flattened: Box::new(Group::empty()),
+            layouted: Vec::new(),
+        };
+
+        let mut cache = crate::Cache::new(fontdb.clone());
+        let res = crate::text::convert(&mut text, resolver, &mut cache);
+        *fontdb = cache.fontdb;
+        res?;
+
+        Some(text)
+    }
+}
+
+impl Font {
+    /// Creates a new font.
+    ///
+    /// Returns `None` when `families` is empty.
+    pub fn new(
+        families: Vec<FontFamily>,
+        style: FontStyle,
+        stretch: FontStretch,
+        weight: u16,
+    ) -> Option<Self> {
+        if families.is_empty() {
+            return None;
+        }
+
+        Some(Font {
+            families,
+            style,
+            stretch,
+            weight,
+            variations: Vec::new(),
+        })
+    }
+
+    /// Sets variable font axis values.
+    pub fn with_variations(mut self, variations: Vec<FontVariation>) -> Self {
+        self.variations = variations;
+        self
+    }
+}
+
+macro_rules! impl_node_from {
+    ($($name:ident),+) => {
+        $(
+            impl From<$name> for Node {
+                fn from(value: $name) -> Self {
+                    Node::$name(Box::new(value))
+                }
+            }
+        )+
+    };
+}
+
+impl_node_from!(Group, Path, Rectangle, Ellipse, Polygon, Circle, Line, Polyline, Image, Text);
+
+impl Stop {
+    /// Creates a new gradient stop.
+    pub fn new(offset: StopOffset, color: Color, opacity: Opacity) -> Self {
+        Stop {
+            offset,
+            color,
+            opacity,
+        }
+    }
+}
+
+impl BaseGradient {
+    fn new(id: String, stops: Vec<Stop>) -> Option<Self> {
+        Some(BaseGradient {
+            id: NonEmptyString::new(id)?,
+            units: Units::UserSpaceOnUse,
+            transform: Transform::default(),
+            spread_method: SpreadMethod::default(),
+            stops,
+        })
+    }
+
+    /// Sets gradient transform.
+    pub fn set_transform(&mut self, transform: Transform) {
+        self.transform = transform;
+    }
+
+    /// Sets gradient spreading method.
+    pub fn set_spread_method(&mut self, spread_method: SpreadMethod) {
+        self.spread_method = spread_method;
+    }
+}
+
+impl LinearGradient {
+    /// Creates a new linear gradient.
+    ///
+    /// Coordinates are in the user space.
+    ///
+    /// Returns `None` when `id` is empty.
+    pub fn new(id: String, x1: f32, y1: f32, x2: f32, y2: f32, stops: Vec<Stop>) -> Option<Self> {
+        Some(LinearGradient {
+            base: BaseGradient::new(id, stops)?,
+            x1,
+            y1,
+            x2,
+            y2,
+        })
+    }
+}
+
+impl std::ops::DerefMut for LinearGradient {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl RadialGradient {
+    /// Creates a new radial gradient.
+    ///
+    /// Coordinates are in the user space.
+    ///
+    /// Returns `None` when `id` is empty.
+    pub fn new(
+        id: String,
+        cx: f32,
+        cy: f32,
+        r: PositiveF32,
+        fx: f32,
+        fy: f32,
+        stops: Vec<Stop>,
+    ) -> Option<Self> {
+        Some(RadialGradient {
+            base: BaseGradient::new(id, stops)?,
+            cx,
+            cy,
+            r,
+            fx,
+            fy,
+            fr: PositiveF32::ZERO,
+        })
+    }
+
+    /// Sets focal radius.
+    pub fn set_fr(&mut self, fr: PositiveF32) {
+        self.fr = fr;
+    }
+}
+
+impl std::ops::DerefMut for RadialGradient {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl ConicGradient {
+    /// Creates a new conic gradient.
+    ///
+    /// Coordinates are in the user space. Angles are in degrees.
+    ///
+    /// Returns `None` when `id` is empty.
+    pub fn new(
+        id: String,
+        cx: f32,
+        cy: f32,
+        start_angle: f32,
+        end_angle: f32,
+        stops: Vec<Stop>,
+    ) -> Option<Self> {
+        Some(ConicGradient {
+            base: BaseGradient::new(id, stops)?,
+            cx,
+            cy,
+            start_angle,
+            end_angle,
+        })
+    }
+}
+
+impl std::ops::DerefMut for ConicGradient {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl Pattern {
+    /// Creates a new pattern.
+    ///
+    /// `root` content is in the pattern tile coordinates.
+    ///
+    /// Returns `None` when `id` is empty.
+    pub fn new(id: String, rect: NonZeroRect, mut root: Group) -> Option<Self> {
+        root.recalculate(Transform::default());
+        Some(Pattern {
+            id: NonEmptyString::new(id)?,
+            units: Units::UserSpaceOnUse,
+            content_units: Units::UserSpaceOnUse,
+            transform: Transform::default(),
+            rect,
+            view_box: None,
+            root,
+        })
+    }
+
+    /// Sets pattern transform.
+    pub fn set_transform(&mut self, transform: Transform) {
+        self.transform = transform;
+    }
+}
+
+impl MeshGradient {
+    /// Creates a new mesh gradient.
+    ///
+    /// Coordinates are in the user space.
+    ///
+    /// Returns `None` when `id` is empty, when there are no patches
+    /// or when rows have different lengths.
+    pub fn new(id: String, kind: MeshGradientType, rows: Vec<Vec<MeshPatch>>) -> Option<Self> {
+        let columns = rows.first()?.len();
+        if columns == 0 || rows.iter().any(|row| row.len() != columns) {
+            return None;
+        }
+
+        Some(MeshGradient {
+            id: NonEmptyString::new(id)?,
+            units: Units::UserSpaceOnUse,
+            transform: Transform::default(),
+            kind,
+            rows,
+        })
+    }
+
+    /// Sets gradient transform.
+    pub fn set_transform(&mut self, transform: Transform) {
+        self.transform = transform;
+    }
+}
+
+impl MeshPatch {
+    /// Creates a new mesh patch.
+    ///
+    /// `points` contain the top, right, bottom and left edges as cubic curves,
+    /// starting from the top-left corner. `colors` and `opacities` are
+    /// for the top-left, top-right, bottom-right and bottom-left corners.
+    pub fn new(
+        points: [tiny_skia_path::Point; 12],
+        colors: [Color; 4],
+        opacities: [Opacity; 4],
+    ) -> Self {
+        MeshPatch {
+            points,
+            colors,
+            opacities,
+        }
+    }
+}
+
+impl Hatch {
+    /// Creates a new hatch.
+    ///
+    /// Coordinates are in the user space.
+    ///
+    /// Returns `None` when `id` is empty or when there are no paths.
+    pub fn new(
+        id: String,
+        x: f32,
+        y: f32,
+        pitch: NonZeroPositiveF32,
+        rotate: f32,
+        paths: Vec<HatchPath>,
+    ) -> Option<Self> {
+        if paths.is_empty() {
+            return None;
+        }
+
+        Some(Hatch {
+            id: NonEmptyString::new(id)?,
+            units: Units::UserSpaceOnUse,
+            content_units: Units::UserSpaceOnUse,
+            transform: Transform::default(),
+            x,
+            y,
+            pitch,
+            rotate,
+            paths,
+        })
+    }
+
+    /// Sets hatch transform.
+    pub fn set_transform(&mut self, transform: Transform) {
+        self.transform = transform;
+    }
+}
+
+impl HatchPath {
+    /// Creates a new hatch path.
+    ///
+    /// When `data` is `None`, an infinite vertical line is used.
+    pub fn new(offset: f32, data: Option<Arc<tiny_skia_path::Path>>, stroke: Stroke) -> Self {
+        HatchPath {
+            offset,
+            data,
+            stroke,
+        }
+    }
+}
+
+impl ClipPath {
+    /// Creates a new clip path.
+    ///
+    /// Only the shapes and the text inside `root` are used for clipping.
+    ///
+    /// Returns `None` when `id` is empty.
+    pub fn new(id: String, mut root: Group) -> Option<Self> {
+        root.recalculate(Transform::default());
+        let mut clip = ClipPath::empty(NonEmptyString::new(id)?);
+        clip.root = root;
+        Some(clip)
+    }
+
+    /// Sets clip path transform.
+    pub fn set_transform(&mut self, transform: Transform) {
+        self.transform = transform;
+    }
+
+    /// Sets an additional clip path.
+    pub fn set_clip_path(&mut self, clip_path: Option<Arc<ClipPath>>) {
+        self.clip_path = clip_path;
+    }
+}
+
+impl Mask {
+    /// Creates a new mask.
+    ///
+    /// Returns `None` when `id` is empty.
+    pub fn new(id: String, rect: NonZeroRect, kind: MaskType, mut root: Group) -> Option<Self> {
+        root.recalculate(Transform::default());
+        Some(Mask {
+            id: NonEmptyString::new(id)?,
+            rect,
+            kind,
+            mask: None,
+            root,
+        })
+    }
+
+    /// Sets an additional mask.
+    pub fn set_mask(&mut self, mask: Option<Arc<Mask>>) {
+        self.mask = mask;
+    }
+}
+
+impl filter::Filter {
+    /// Creates a new filter.
+    ///
+    /// `rect` is in the user space of the element the filter will be applied to.
+    ///
+    /// Returns `None` when `id` is empty.
+    pub fn new(id: String, rect: NonZeroRect, primitives: Vec<filter::Primitive>) -> Option<Self> {
+        Some(filter::Filter {
+            id: NonEmptyString::new(id)?,
+            rect,
+            primitives,
+        })
+    }
+}
+
+impl filter::Primitive {
+    /// Creates a new filter primitive.
+    ///
+    /// `result` must be unique inside the parent filter.
+    pub fn new(
+        rect: NonZeroRect,
+        color_interpolation: filter::ColorInterpolation,
+        result: String,
+        kind: filter::Kind,
+    ) -> Self {
+        filter::Primitive {
+            rect,
+            color_interpolation,
+            result,
+            kind,
+        }
+    }
+}
+
+impl filter::Blend {
+    /// Creates a new `feBlend`.
+    pub fn new(input1: filter::Input, input2: filter::Input, mode: BlendMode) -> Self {
+        filter::Blend {
+            input1,
+            input2,
+            mode,
+        }
+    }
+}
+
+impl filter::ColorMatrix {
+    /// Creates a new `feColorMatrix`.
+    ///
+    /// Returns `None` when a `ColorMatrixKind::Matrix` doesn't have exactly 20 numbers.
+    pub fn new(input: filter::Input, kind: filter::ColorMatrixKind) -> Option<Self> {
+        if let filter::ColorMatrixKind::Matrix(ref values) = kind {
+            if values.len() != 20 {
+                return None;
+            }
+        }
+
+        Some(filter::ColorMatrix { input, kind })
+    }
+}
+
+impl filter::ComponentTransfer {
+    /// Creates a new `feComponentTransfer`.
+    pub fn new(
+        input: filter::Input,
+        func_r: filter::TransferFunction,
+        func_g: filter::TransferFunction,
+        func_b: filter::TransferFunction,
+        func_a: filter::TransferFunction,
+    ) -> Self {
+        filter::ComponentTransfer {
+            input,
+            func_r,
+            func_g,
+            func_b,
+            func_a,
+        }
+    }
+}
+
+impl filter::Composite {
+    /// Creates a new `feComposite`.
+    pub fn new(
+        input1: filter::Input,
+        input2: filter::Input,
+        operator: filter::CompositeOperator,
+    ) -> Self {
+        filter::Composite {
+            input1,
+            input2,
+            operator,
+        }
+    }
+}
+
+impl filter::ConvolveMatrix {
+    /// Creates a new `feConvolveMatrix`.
+    pub fn new(
+        input: filter::Input,
+        matrix: filter::ConvolveMatrixData,
+        divisor: NonZeroF32,
+        bias: f32,
+        edge_mode: filter::EdgeMode,
+        preserve_alpha: bool,
+    ) -> Self {
+        filter::ConvolveMatrix {
+            input,
+            matrix,
+            divisor,
+            bias,
+            edge_mode,
+            preserve_alpha,
+        }
+    }
+}
+
+impl filter::DisplacementMap {
+    /// Creates a new `feDisplacementMap`.
+    pub fn new(
+        input1: filter::Input,
+        input2: filter::Input,
+        scale: f32,
+        x_channel_selector: filter::ColorChannel,
+        y_channel_selector: filter::ColorChannel,
+    ) -> Self {
+        filter::DisplacementMap {
+            input1,
+            input2,
+            scale,
+            x_channel_selector,
+            y_channel_selector,
+        }
+    }
+}
+
+impl filter::DropShadow {
+    /// Creates a new `feDropShadow`.
+    pub fn new(
+        input: filter::Input,
+        dx: f32,
+        dy: f32,
+        std_dev_x: PositiveF32,
+        std_dev_y: PositiveF32,
+        color: Color,
+        opacity: Opacity,
+    ) -> Self {
+        filter::DropShadow {
+            input,
+            dx,
+            dy,
+            std_dev_x,
+            std_dev_y,
+            color,
+            opacity,
+        }
+    }
+}
+
+impl filter::Flood {
+    /// Creates a new `feFlood`.
+    pub fn new(color: Color, opacity: Opacity) -> Self {
+        filter::Flood { color, opacity }
+    }
+}
+
+impl filter::GaussianBlur {
+    /// Creates a new `feGaussianBlur`.
+    pub fn new(input: filter::Input, std_dev_x: PositiveF32, std_dev_y: PositiveF32) -> Self {
+        filter::GaussianBlur {
+            input,
+            std_dev_x,
+            std_dev_y,
+        }
+    }
+}
+
+impl filter::Image {
+    /// Creates a new `feImage`.
+    ///
+    /// `root` content is in the user space of the element the filter will be applied to.
+    pub fn new(mut root: Group) -> Self {
+        root.recalculate(Transform::default());
+        filter::Image { root }
+    }
+}
+
+impl filter::DiffuseLighting {
+    /// Creates a new `feDiffuseLighting`.
+    pub fn new(
+        input: filter::Input,
+        surface_scale: f32,
+        diffuse_constant: f32,
+        lighting_color: Color,
+        light_source: filter::LightSource,
+    ) -> Self {
+        filter::DiffuseLighting {
+            input,
+            surface_scale,
+            diffuse_constant,
+            lighting_color,
+            light_source,
+        }
+    }
+}
+
+impl filter::SpecularLighting {
+    /// Creates a new `feSpecularLighting`.
+    pub fn new(
+        input: filter::Input,
+        surface_scale: f32,
+        specular_constant: f32,
+        specular_exponent: f32,
+        lighting_color: Color,
+        light_source: filter::LightSource,
+    ) -> Self {
+        filter::SpecularLighting {
+            input,
+            surface_scale,
+            specular_constant,
+            specular_exponent,
+            lighting_color,
+            light_source,
+        }
+    }
+}
+
+impl filter::Merge {
+    /// Creates a new `feMerge`.
+    pub fn new(inputs: Vec<filter::Input>) -> Self {
+        filter::Merge { inputs }
+    }
+}
+
+impl filter::Morphology {
+    /// Creates a new `feMorphology`.
+    pub fn new(
+        input: filter::Input,
+        operator: filter::MorphologyOperator,
+        radius_x: PositiveF32,
+        radius_y: PositiveF32,
+    ) -> Self {
+        filter::Morphology {
+            input,
+            operator,
+            radius_x,
+            radius_y,
+        }
+    }
+}
+
+impl filter::Offset {
+    /// Creates a new `feOffset`.
+    pub fn new(input: filter::Input, dx: f32, dy: f32) -> Self {
+        filter::Offset { input, dx, dy }
+    }
+}
+
+impl filter::Tile {
+    /// Creates a new `feTile`.
+    pub fn new(input: filter::Input) -> Self {
+        filter::Tile { input }
+    }
+}
+
+impl filter::Turbulence {
+    /// Creates a new `feTurbulence`.
+    pub fn new(
+        base_frequency_x: PositiveF32,
+        base_frequency_y: PositiveF32,
+        num_octaves: u32,
+        seed: i32,
+        stitch_tiles: bool,
+        kind: filter::TurbulenceKind,
+    ) -> Self {
+        filter::Turbulence {
+            base_frequency_x,
+            base_frequency_y,
+            num_octaves,
+            seed,
+            stitch_tiles,
+            kind,
+        }
+    }
+}
@@ -0,0 +1,537 @@
+// Copyright 2025 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use std::sync::Arc;
+
+use super::*;
+
+impl Tree {
+    /// Edits the tree.
+    ///
+    /// The closure receives the root group and can change it in any way.
+    ///
+    /// After the closure returns, all the data that `usvg` resolves during parsing
+    /// will be recalculated. This includes absolute transforms, bounding boxes
+    /// and the lists of paint servers, clip paths, masks and filters.
+    ///
+    /// Paint servers, clip paths, masks and filters are already resolved into
+    /// the user space of the node they were originally applied to.
+    /// Keep this in mind when applying them to other nodes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let svg = "<svg xmlns='http://www.w3.org/2000/svg' width='100' height='100'>
+    ///     <rect id='rect1' width='10' height='10'/>
+    /// </svg>";
+    /// let mut tree = usvg::Tree::from_str(svg, &usvg::Options::default()).unwrap();
+    ///
+    /// tree.edit(|root| {
+    ///     let mut group = usvg::Group::default();
+    ///     group.set_transform(usvg::Transform::from_translate(50.0, 50.0));
+    ///     group.push_child(root.remove_child(0));
+    ///     root.push_child(usvg::Node::Group(Box::new(group)));
+    /// });
+    ///
+    /// let rect = tree.node_by_id("rect1").unwrap();
+    /// assert_eq!(rect.abs_bounding_box().x(), 50.0);
+    /// ```
+    pub fn edit<F: FnOnce(&mut Group)>(&mut self, f: F) {
+        f(&mut self.root);
+        self.recalculate();
+    }
+
+    /// Recalculates all the data derived from the nodes tree.
+    pub(crate) fn recalculate(&mut self) {
+        self.root.recalculate(Transform::default());
+
+        self.linear_gradients.clear();
+        self.radial_gradients.clear();
+        self.conic_gradients.clear();
+        self.patterns.clear();
+        self.mesh_gradients.clear();
+        self.hatches.clear();
+        self.collect_paint_servers();
+
+        self.clip_paths.clear();
+        self.root.collect_clip_paths(&mut self.clip_paths);
+
+        self.masks.clear();
+        self.root.collect_masks(&mut self.masks);
+
+        self.filters.clear();
+        self.root.collect_filters(&mut self.filters);
+    }
+}
+
+impl Default for Group {
+    /// Creates an empty group.
+    fn default() -> Self {
+        Group::empty()
+    }
+}
+
+impl Group {
+    /// Sets element's ID.
+    ///
+    /// It's up to the caller to make sure that IDs are unique.
+    pub fn set_id(&mut self, id: String) {
+        self.id = id;
+    }
+
+    /// Sets element's relative transform.
+    pub fn set_transform(&mut self, transform: Transform) {
+        self.transform = transform;
+    }
+
+    /// Sets group opacity.
+    pub fn set_opacity(&mut self, opacity: Opacity) {
+        self.opacity = opacity;
+    }
+
+    /// Sets group blend mode.
+    pub fn set_blend_mode(&mut self, blend_mode: BlendMode) {
+        self.blend_mode = blend_mode;
+    }
+
+    /// Sets group isolation.
+    pub fn set_isolate(&mut self, isolate: bool) {
+        self.isolate = isolate;
+    }
+
+    /// Sets element's clip path.
+    pub fn set_clip_path(&mut self, clip_path: Option<Arc<ClipPath>>) {
+        self.clip_path = clip_path;
+    }
+
+    /// Sets element's mask layers.
+    ///
+    /// The first layer is the topmost one.
+    pub fn set_masks(&mut self, masks: Vec<MaskLayer>) {
+        self.masks = masks;
+    }
+
+    /// Sets element's filters.
+    pub fn set_filters(&mut self, filters: Vec<Arc<filter::Filter>>) {
+        self.filters = filters;
+    }
+
+    /// Group's mutable children.
+    ///
+    /// Can be used to edit or to reorder children in place.
+    pub fn children_mut(&mut self) -> &mut [Node] {
+        &mut self.children
+    }
+
+    /// Appends a child to the end of the children list.
+    pub fn push_child(&mut self, node: Node) {
+        self.children.push(node);
+    }
+
+    /// Inserts a child at the specified position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is bigger than the number of children.
+    pub fn insert_child(&mut self, index: usize, node: Node) {
+        self.children.insert(index, node);
+    }
+
+    /// Removes and returns a child at the specified position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove_child(&mut self, index: usize) -> Node {
+        self.children.remove(index)
+    }
+
+    /// Moves a child from one position to another.
+    ///
+    /// Children between `from` and `to` are shifted accordingly.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `from` or `to` are out of bounds.
+    pub fn move_child(&mut self, from: usize, to: usize) {
+        let node = self.children.remove(from);
+        self.children.insert(to, node);
+    }
+
+    /// Returns a mutable renderable node by ID.
+    ///
+    /// Searches this group descendants.
+    /// If an empty ID is provided, than this method will always return `None`.
+    pub fn node_by_id_mut(&mut self, id: &str) -> Option<&mut Node> {
+        if id.is_empty() {
+            return None;
+        }
+
+        for child in &mut self.children {
+            if child.id() == id {
+                return Some(child);
+            }
+
+            if let Node::Group(ref mut g) = child {
+                if let Some(n) = g.node_by_id_mut(id) {
+                    return Some(n);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Recalculates absolute transforms and bounding boxes of the whole subtree.
+    pub(crate) fn recalculate(&mut self, parent_abs_transform: Transform) {
+        self.abs_transform = parent_abs_transform.pre_concat(self.transform);
+
+        for child in &mut self.children {
+            child.set_abs_transform(self.abs_transform);
+        }
+
+        // `calculate_bounding_boxes` keeps previous values for empty groups.
+        let empty = Group::empty();
+        self.bounding_box = empty.bounding_box;
+        self.abs_bounding_box = empty.abs_bounding_box;
+        self.stroke_bounding_box = empty.stroke_bounding_box;
+        self.abs_stroke_bounding_box = empty.abs_stroke_bounding_box;
+        self.layer_bounding_box = empty.layer_bounding_box;
+        self.abs_layer_bounding_box = empty.abs_layer_bounding_box;
+        self.calculate_bounding_boxes();
+    }
+}
+
+impl Node {
+    /// Sets an absolute transform of the parent group and updates cached data.
+    fn set_abs_transform(&mut self, ts: Transform) {
+        match self {
+            Node::Group(ref mut group) => group.recalculate(ts),
+            Node::Path(ref mut path) => path.set_abs_transform(ts),
+            Node::Rectangle(ref mut rect) => rect.set_abs_transform(ts),
+            Node::Ellipse(ref mut ellipse) => ellipse.set_abs_transform(ts),
+            Node::Polygon(ref mut polygon) => polygon.set_abs_transform(ts),
+            Node::Circle(ref mut circle) => circle.set_abs_transform(ts),
+            Node::Line(ref mut line) => line.set_abs_transform(ts),
+            Node::Polyline(ref mut polyline) => polyline.set_abs_transform(ts),
+            Node::Image(ref mut image) => {
+                if image.abs_transform != ts {
+                    if let Some(r) = image.size.to_non_zero_rect(0.0, 0.0).transform(ts) {
+                        image.abs_transform = ts;
+                        image.abs_bounding_box = r;
+                    }
+                }
+            }
+            Node::Text(ref mut text) => {
+                if text.abs_transform != ts {
+                    text.abs_transform = ts;
+                    if let Some(r) = text.bounding_box.transform(ts) {
+                        text.abs_bounding_box = r;
+                    }
+                    if let Some(r) = text.stroke_bounding_box.transform(ts) {
+                        text.abs_stroke_bounding_box = r;
+                    }
+
+                    text.flattened.recalculate(ts);
+                }
+            }
+        }
+    }
+}
+
+impl Path {
+    /// Sets element's ID.
+    ///
+    /// It's up to the caller to make sure that IDs are unique.
+    pub fn set_id(&mut self, id: String) {
+        self.id = id;
+    }
+
+    /// Sets element visibility.
+    pub fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+
+    /// Sets fill style.
+    pub fn set_fill(&mut self, fill: Option<Fill>) {
+        self.fill = fill;
+    }
+
+    /// Sets stroke style.
+    ///
+    /// Stroke bounding boxes will be updated automatically.
+    pub fn set_stroke(&mut self, stroke: Option<Stroke>) {
+        self.stroke = stroke;
+        self.update_bounding_boxes();
+    }
+
+    /// Sets fill and stroke paint order.
+    pub fn set_paint_order(&mut self, paint_order: PaintOrder) {
+        self.paint_order = paint_order;
+    }
+
+    /// Sets rendering mode.
+    pub fn set_rendering_mode(&mut self, rendering_mode: ShapeRendering) {
+        self.rendering_mode = rendering_mode;
+    }
+
+    /// Sets vector effect.
+    ///
+    /// Stroke bounding boxes will be updated automatically.
+    pub fn set_vector_effect(&mut self, vector_effect: VectorEffect) {
+        self.vector_effect = vector_effect;
+        self.update_bounding_boxes();
+    }
+
+    /// Sets path segments.
+    ///
+    /// Bounding boxes will be updated automatically.
+    pub fn set_data(&mut self, data: Arc<tiny_skia_path::Path>) {
+        self.data = data;
+        self.update_bounding_boxes();
+    }
+
+    fn set_abs_transform(&mut self, ts: Transform) {
+        if self.abs_transform != ts {
+            self.abs_transform = ts;
+            self.update_bounding_boxes();
+        }
+    }
+
+    fn update_bounding_boxes(&mut self) {
+        let bboxes = ShapeBoundingBoxes::calculate(
+            &self.data,
+            self.stroke.as_ref(),
+            self.vector_effect,
+            self.abs_transform,
+        );
+        if let Some(bboxes) = bboxes {
+            self.bounding_box = bboxes.bounding_box;
+            self.abs_bounding_box = bboxes.abs_bounding_box;
+            self.stroke_bounding_box = bboxes.stroke_bounding_box;
+            self.abs_stroke_bounding_box = bboxes.abs_stroke_bounding_box;
+        }
+    }
+}
+
+// Primitive shapes share the same style properties and differ only in geometry.
+macro_rules! impl_shape_editing {
+    ($name:ident) => {
+        impl $name {
+            /// Sets element's ID.
+            ///
+            /// It's up to the caller to make sure that IDs are unique.
+            pub fn set_id(&mut self, id: String) {
+                self.id = id;
+            }
+
+            /// Sets element visibility.
+            pub fn set_visible(&mut self, visible: bool) {
+                self.visible = visible;
+            }
+
+            /// Sets fill style.
+            pub fn set_fill(&mut self, fill: Option<Fill>) {
+                self.fill = fill;
+            }
+
+            /// Sets stroke style.
+            ///
+            /// Stroke bounding boxes will be updated automatically.
+            pub fn set_stroke(&mut self, stroke: Option<Stroke>) {
+                self.stroke = stroke;
+                self.update_bounding_boxes();
+            }
+
+            /// Sets fill and stroke paint order.
+            pub fn set_paint_order(&mut self, paint_order: PaintOrder) {
+                self.paint_order = paint_order;
+            }
+
+            /// Sets rendering mode.
+            pub fn set_rendering_mode(&mut self, rendering_mode: ShapeRendering) {
+                self.rendering_mode = rendering_mode;
+            }
+
+            /// Sets vector effect.
+            ///
+            /// Stroke bounding boxes will be updated automatically.
+            pub fn set_vector_effect(&mut self, vector_effect: VectorEffect) {
+                self.vector_effect = vector_effect;
+                self.update_bounding_boxes();
+            }
+
+            fn set_abs_transform(&mut self, ts: Transform) {
+                if self.abs_transform != ts {
+                    self.abs_transform = ts;
+                    self.update_bounding_boxes();
+                }
+            }
+
+            fn update_bounding_boxes(&mut self) {
+                let bboxes = self.outline().and_then(|path| {
+                    ShapeBoundingBoxes::calculate(
+                        &path,
+                        self.stroke.as_ref(),
+                        self.vector_effect,
+                        self.abs_transform,
+                    )
+                });
+                if let Some(bboxes) = bboxes {
+                    self.bounding_box = bboxes.bounding_box;
+                    self.abs_bounding_box = bboxes.abs_bounding_box;
+                    self.stroke_bounding_box = bboxes.stroke_bounding_box;
+                    self.abs_stroke_bounding_box = bboxes.abs_stroke_bounding_box;
+                }
+            }
+        }
+    };
+}
+
+impl_shape_editing!(Rectangle);
+impl_shape_editing!(Ellipse);
+impl_shape_editing!(Polygon);
+impl_shape_editing!(Circle);
+impl_shape_editing!(Line);
+impl_shape_editing!(Polyline);
+
+impl Rectangle {
+    fn outline(&self) -> Option<Arc<tiny_skia_path::Path>> {
+        crate::parser::rect_to_path(self.x, self.y, self.width, self.height, self.rx, self.ry)
+    }
+}
+
+impl Ellipse {
+    fn outline(&self) -> Option<Arc<tiny_skia_path::Path>> {
+        crate::parser::ellipse_to_path(self.cx, self.cy, self.rx, self.ry)
+    }
+}
+
+impl Polygon {
+    fn outline(&self) -> Option<Arc<tiny_skia_path::Path>> {
+        crate::parser::points_to_path(&self.points, true)
+    }
+}
+
+impl Circle {
+    fn outline(&self) -> Option<Arc<tiny_skia_path::Path>> {
+        crate::parser::ellipse_to_path(self.cx, self.cy, self.r, self.r)
+    }
+}
+
+impl Line {
+    fn outline(&self) -> Option<Arc<tiny_skia_path::Path>> {
+        crate::parser::line_to_path(self.x1, self.y1, self.x2, self.y2)
+    }
+}
+
+impl Polyline {
+    fn outline(&self) -> Option<Arc<tiny_skia_path::Path>> {
+        crate::parser::points_to_path(&self.points, false)
+    }
+}
+
+impl Image {
+    /// Sets element's ID.
+    ///
+    /// It's up to the caller to make sure that IDs are unique.
+    pub fn set_id(&mut self, id: String) {
+        self.id = id;
+    }
+
+    /// Sets element visibility.
+    pub fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+
+    /// Sets rendering mode.
+    pub fn set_rendering_mode(&mut self, rendering_mode: ImageRendering) {
+        self.rendering_mode = rendering_mode;
+    }
+}
+
+impl Text {
+    /// Sets element's ID.
+    ///
+    /// It's up to the caller to make sure that IDs are unique.
+    pub fn set_id(&mut self, id: String) {
+        self.flattened.id = id.clone();
+        self.id = id;
+    }
+}
+
+impl Fill {
+    /// Sets fill paint.
+    pub fn set_paint(&mut self, paint: Paint) {
+        self.paint = paint;
+    }
+
+    /// Sets fill opacity.
+    pub fn set_opacity(&mut self, opacity: Opacity) {
+        self.opacity = opacity;
+    }
+
+    /// Sets fill rule.
+    pub fn set_rule(&mut self, rule: FillRule) {
+        self.rule = rule;
+    }
+}
+
+impl Default for Stroke {
+    /// Creates a black, 1px wide stroke.
+    fn default() -> Self {
+        Stroke {
+            paint: Paint::Color(Color::black()),
+            dasharray: None,
+            dashoffset: 0.0,
+            miterlimit: StrokeMiterlimit::default(),
+            opacity: Opacity::ONE,
+            width: StrokeWidth::new(1.0).unwrap(),
+            linecap: LineCap::default(),
+            linejoin: LineJoin::default(),
+            context_element: None,
+        }
+    }
+}
+
+impl Stroke {
+    /// Sets stroke paint.
+    pub fn set_paint(&mut self, paint: Paint) {
+        self.paint = paint;
+    }
+
+    /// Sets stroke dash array.
+    pub fn set_dasharray(&mut self, dasharray: Option<Vec<f32>>) {
+        self.dasharray = dasharray;
+    }
+
+    /// Sets stroke dash offset.
+    pub fn set_dashoffset(&mut self, dashoffset: f32) {
+        self.dashoffset = dashoffset;
+    }
+
+    /// Sets stroke miter limit.
+    pub fn set_miterlimit(&mut self, miterlimit: StrokeMiterlimit) {
+        self.miterlimit = miterlimit;
+    }
+
+    /// Sets stroke opacity.
+    pub fn set_opacity(&mut self, opacity: Opacity) {
+        self.opacity = opacity;
+    }
+
+    /// Sets stroke width.
+    pub fn set_width(&mut self, width: StrokeWidth) {
+        self.width = width;
+    }
+
+    /// Sets stroke linecap.
+    pub fn set_linecap(&mut self, linecap: LineCap) {
+        self.linecap = linecap;
+    }
+
+    /// Sets stroke linejoin.
+    pub fn set_linejoin(&mut self, linejoin: LineJoin) {
+        self.linejoin = linejoin;
+    }
+}
@@ -448,7 +448,7 @@ impl ConvolveMatrixData {
     /// - `columns` * `rows` != `data.len()`
     /// - `target_x` >= `columns`
     /// - `target_y` >= `rows`
-    pub(crate) fn new(
+    pub fn new(
         target_x: u32,
         target_y: u32,
         columns: u32,
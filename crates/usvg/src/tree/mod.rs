@@ -1,6 +1,8 @@
 // Copyright 2019 the Resvg Authors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+mod builder;
+mod edit;
 pub mod filter;
 mod geom;
 mod text;
@@ -11,6 +13,7 @@ pub use strict_num::{self, ApproxEqUlps, NonZeroPositiveF32, NormalizedF32, Posi
 
 pub use tiny_skia_path;
 
+pub use self::builder::*;
 pub use self::geom::*;
 pub use self::text::*;
 
@@ -337,6 +340,7 @@ pub struct RadialGradient {
     pub(crate) r: PositiveF32,
     pub(crate) fx: f32,
     pub(crate) fy: f32,
+    pub(crate) fr: PositiveF32,
 }
 
 impl RadialGradient {
@@ -364,6 +368,14 @@ impl RadialGradient {
     pub fn fy(&self) -> f32 {
         self.fy
     }
+
+    /// Focal radius.
+    ///
+    /// `fr` in SVG. When not zero, the gradient is a two-point conical one
+    /// that starts at the focal circle and ends at the gradient circle.
+    pub fn fr(&self) -> PositiveF32 {
+        self.fr
+    }
 }
 
 impl std::ops::Deref for RadialGradient {
@@ -374,6 +386,52 @@ impl std::ops::Deref for RadialGradient {
     }
 }
 
+/// A conic (sweep) gradient.
+///
+/// A non-standard `conicGradient` element in SVG.
+/// Used by COLRv1 sweep gradients as well.
+///
+/// Stop offsets are mapped to angles between `start_angle` and `end_angle`,
+/// which are in degrees and go from the positive X axis towards the positive Y axis.
+#[derive(Debug)]
+pub struct ConicGradient {
+    pub(crate) base: BaseGradient,
+    pub(crate) cx: f32,
+    pub(crate) cy: f32,
+    pub(crate) start_angle: f32,
+    pub(crate) end_angle: f32,
+}
+
+impl ConicGradient {
+    /// `cx` coordinate.
+    pub fn cx(&self) -> f32 {
+        self.cx
+    }
+
+    /// `cy` coordinate.
+    pub fn cy(&self) -> f32 {
+        self.cy
+    }
+
+    /// The angle of the first stop, in degrees.
+    pub fn start_angle(&self) -> f32 {
+        self.start_angle
+    }
+
+    /// The angle of the last stop, in degrees.
+    pub fn end_angle(&self) -> f32 {
+        self.end_angle
+    }
+}
+
+impl std::ops::Deref for ConicGradient {
+    type Target = BaseGradient;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
 /// An alias to `NormalizedF32`.
 pub type StopOffset = NormalizedF32;
 
@@ -453,6 +511,218 @@ impl Pattern {
     }
 }
 
+/// A mesh gradient colors interpolation.
+///
+/// `type` attribute of the `meshgradient` element in SVG.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum MeshGradientType {
+    /// Colors are interpolated bilinearly inside each patch.
+    #[default]
+    Bilinear,
+    /// Colors are interpolated smoothly across patch boundaries.
+    Bicubic,
+}
+
+/// A mesh gradient.
+///
+/// `meshgradient` element in SVG.
+#[derive(Debug)]
+pub struct MeshGradient {
+    pub(crate) id: NonEmptyString,
+    pub(crate) units: Units, // used only during parsing
+    pub(crate) transform: Transform,
+    pub(crate) kind: MeshGradientType,
+    pub(crate) rows: Vec<Vec<MeshPatch>>,
+}
+
+impl MeshGradient {
+    /// Element's ID.
+    ///
+    /// Taken from the SVG itself.
+    /// Used only during SVG writing. `resvg` doesn't rely on this property.
+    pub fn id(&self) -> &str {
+        self.id.get()
+    }
+
+    /// Gradient transform.
+    ///
+    /// `gradientTransform` in SVG.
+    pub fn transform(&self) -> Transform {
+        self.transform
+    }
+
+    /// Colors interpolation.
+    ///
+    /// `type` in SVG.
+    pub fn kind(&self) -> MeshGradientType {
+        self.kind
+    }
+
+    /// A list of `meshrow` elements.
+    ///
+    /// Each row has at least one patch and all rows have the same number of patches.
+    /// Adjacent patches share their edges and corners.
+    pub fn rows(&self) -> &[Vec<MeshPatch>] {
+        &self.rows
+    }
+}
+
+/// A mesh gradient patch.
+///
+/// `meshpatch` element in SVG. A Coons patch bounded by four cubic Bézier curves.
+#[derive(Clone, Copy, Debug)]
+pub struct MeshPatch {
+    pub(crate) points: [tiny_skia_path::Point; 12],
+    pub(crate) colors: [Color; 4],
+    pub(crate) opacities: [Opacity; 4],
+}
+
+impl MeshPatch {
+    /// Patch edges.
+    ///
+    /// The top, right, bottom and left edges, in this order. Each edge starts at a corner
+    /// and is followed by two control points, while the next corner starts the next edge.
+    /// Therefore, the top-left, top-right, bottom-right and bottom-left corners
+    /// are the points 0, 3, 6 and 9.
+    pub fn points(&self) -> &[tiny_skia_path::Point; 12] {
+        &self.points
+    }
+
+    /// Corners colors.
+    ///
+    /// `stop-color` in SVG. In the same order as the corners.
+    pub fn colors(&self) -> [Color; 4] {
+        self.colors
+    }
+
+    /// Corners opacities.
+    ///
+    /// `stop-opacity` in SVG. In the same order as the corners.
+    pub fn opacities(&self) -> [Opacity; 4] {
+        self.opacities
+    }
+}
+
+/// A hatch element.
+///
+/// `hatch` element in SVG.
+///
+/// Hatch paths are repeated every `pitch` along the x axis of the hatch coordinate system,
+/// which is established by `transform`, `x`, `y` and `rotate`.
+#[derive(Debug)]
+pub struct Hatch {
+    pub(crate) id: NonEmptyString,
+    pub(crate) units: Units,         // used only during parsing
+    pub(crate) content_units: Units, // used only during parsing
+    pub(crate) transform: Transform,
+    pub(crate) x: f32,
+    pub(crate) y: f32,
+    pub(crate) pitch: NonZeroPositiveF32,
+    pub(crate) rotate: f32,
+    pub(crate) paths: Vec<HatchPath>,
+}
+
+impl Hatch {
+    /// Element's ID.
+    ///
+    /// Taken from the SVG itself.
+    /// Used only during SVG writing. `resvg` doesn't rely on this property.
+    pub fn id(&self) -> &str {
+        self.id.get()
+    }
+
+    /// Hatch transform.
+    ///
+    /// `transform` in SVG.
+    pub fn transform(&self) -> Transform {
+        self.transform
+    }
+
+    /// Hatch origin x coordinate.
+    ///
+    /// `x` in SVG.
+    pub fn x(&self) -> f32 {
+        self.x
+    }
+
+    /// Hatch origin y coordinate.
+    ///
+    /// `y` in SVG.
+    pub fn y(&self) -> f32 {
+        self.y
+    }
+
+    /// Distance between repeated hatch paths.
+    ///
+    /// `pitch` in SVG.
+    pub fn pitch(&self) -> NonZeroPositiveF32 {
+        self.pitch
+    }
+
+    /// Hatch rotation angle in degrees.
+    ///
+    /// `rotate` in SVG.
+    pub fn rotate(&self) -> f32 {
+        self.rotate
+    }
+
+    /// A list of `hatchpath` elements.
+    pub fn paths(&self) -> &[HatchPath] {
+        &self.paths
+    }
+
+    /// Returns a transform from the hatch coordinate system to the user one.
+    pub fn hatch_transform(&self) -> Transform {
+        self.transform
+            .pre_translate(self.x, self.y)
+            .pre_concat(Transform::from_rotate(self.rotate))
+    }
+}
+
+/// A hatch path.
+///
+/// `hatchpath` element in SVG.
+#[derive(Clone, Debug)]
+pub struct HatchPath {
+    pub(crate) offset: f32,
+    pub(crate) data: Option<Arc<tiny_skia_path::Path>>,
+    pub(crate) stroke: Stroke,
+}
+
+impl HatchPath {
+    /// Path offset along the x axis.
+    ///
+    /// `offset` in SVG.
+    pub fn offset(&self) -> f32 {
+        self.offset
+    }
+
+    /// Path data, relative to `offset`.
+    ///
+    /// `d` in SVG. When not set, the path is an infinite vertical line.
+    ///
+    /// When the path ends lower than it starts, it's repeated along the y axis
+    /// with a period equal to the vertical distance between its end and start points.
+    pub fn data(&self) -> Option<&tiny_skia_path::Path> {
+        self.data.as_deref()
+    }
+
+    /// Path stroke.
+    pub fn stroke(&self) -> &Stroke {
+        &self.stroke
+    }
+
+    /// Returns the distance between repeated path instances along the y axis.
+    ///
+    /// `None` when the path is not repeated.
+    pub fn period(&self) -> Option<f32> {
+        let data = self.data.as_ref()?;
+        let first = data.points().first()?;
+        let last = data.points().last()?;
+        Some(last.y - first.y).filter(|period| *period > 0.0)
+    }
+}
+
 /// An alias to `NonZeroPositiveF32`.
 pub type StrokeWidth = NonZeroPositiveF32;
 
@@ -730,7 +1000,10 @@ pub enum Paint {
     Color(Color),
     LinearGradient(Arc<LinearGradient>),
     RadialGradient(Arc<RadialGradient>),
+    ConicGradient(Arc<ConicGradient>),
     Pattern(Arc<Pattern>),
+    MeshGradient(Arc<MeshGradient>),
+    Hatch(Arc<Hatch>),
 }
 
 impl PartialEq for Paint {
@@ -740,7 +1013,10 @@ impl PartialEq for Paint {
             (Self::Color(lc), Self::Color(rc)) => lc == rc,
             (Self::LinearGradient(ref lg1), Self::LinearGradient(ref lg2)) => Arc::ptr_eq(lg1, lg2),
             (Self::RadialGradient(ref rg1), Self::RadialGradient(ref rg2)) => Arc::ptr_eq(rg1, rg2),
+            (Self::ConicGradient(ref cg1), Self::ConicGradient(ref cg2)) => Arc::ptr_eq(cg1, cg2),
             (Self::Pattern(ref p1), Self::Pattern(ref p2)) => Arc::ptr_eq(p1, p2),
+            (Self::MeshGradient(ref mg1), Self::MeshGradient(ref mg2)) => Arc::ptr_eq(mg1, mg2),
+            (Self::Hatch(ref h1), Self::Hatch(ref h2)) => Arc::ptr_eq(h1, h2),
             _ => false,
         }
     }
@@ -860,6 +1136,59 @@ impl Mask {
     }
 }
 
+/// A mask compositing operator.
+///
+/// `mask-composite` in CSS.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum MaskComposite {
+    /// The mask layer is placed over the layers below it.
+    Add,
+    /// The mask layer is cut out by the layers below it.
+    Subtract,
+    /// Only the parts where the mask layer overlaps the layers below it are kept.
+    Intersect,
+    /// Only the non-overlapping parts of the mask layer and the layers below it are kept.
+    Exclude,
+}
+
+impl Default for MaskComposite {
+    fn default() -> Self {
+        Self::Add
+    }
+}
+
+/// A mask layer.
+///
+/// An element can have multiple mask layers, set via `mask-image` in CSS.
+/// Raster images and CSS gradients are converted into masks as well.
+#[derive(Clone, Debug)]
+pub struct MaskLayer {
+    pub(crate) mask: Arc<Mask>,
+    pub(crate) composite: MaskComposite,
+}
+
+impl MaskLayer {
+    /// Creates a new mask layer.
+    pub fn new(mask: Arc<Mask>, composite: MaskComposite) -> Self {
+        Self { mask, composite }
+    }
+
+    /// Layer's mask.
+    pub fn mask(&self) -> &Mask {
+        &self.mask
+    }
+
+    /// Layer's compositing operator.
+    ///
+    /// Defines how this layer is combined with the layers below it.
+    /// Ignored for the bottommost layer.
+    ///
+    /// `mask-composite` in CSS.
+    pub fn composite(&self) -> MaskComposite {
+        self.composite
+    }
+}
+
 /// Node's kind.
 #[allow(missing_docs)]
 #[derive(Clone, Debug)]
@@ -869,6 +1198,9 @@ pub enum Node {
     Rectangle(Box<Rectangle>),
     Ellipse(Box<Ellipse>),
     Polygon(Box<Polygon>),
+    Circle(Box<Circle>),
+    Line(Box<Line>),
+    Polyline(Box<Polyline>),
     Image(Box<Image>),
     Text(Box<Text>),
 }
@@ -882,6 +1214,9 @@ impl Node {
             Node::Rectangle(ref e) => e.id.as_str(),
             Node::Ellipse(ref e) => e.id.as_str(),
             Node::Polygon(ref e) => e.id.as_str(),
+            Node::Circle(ref e) => e.id.as_str(),
+            Node::Line(ref e) => e.id.as_str(),
+            Node::Polyline(ref e) => e.id.as_str(),
             Node::Image(ref e) => e.id.as_str(),
             Node::Text(ref e) => e.id.as_str(),
         }
@@ -897,6 +1232,9 @@ impl Node {
             Node::Rectangle(ref rect) => rect.abs_transform(),
             Node::Ellipse(ref ellipse) => ellipse.abs_transform(),
             Node::Polygon(ref polygon) => polygon.abs_transform(),
+            Node::Circle(ref circle) => circle.abs_transform(),
+            Node::Line(ref line) => line.abs_transform(),
+            Node::Polyline(ref polyline) => polyline.abs_transform(),
             Node::Image(ref image) => image.abs_transform(),
             Node::Text(ref text) => text.abs_transform(),
         }
@@ -910,6 +1248,9 @@ impl Node {
             Node::Rectangle(ref rect) => rect.bounding_box(),
             Node::Ellipse(ref ellipse) => ellipse.bounding_box(),
             Node::Polygon(ref polygon) => polygon.bounding_box(),
+            Node::Circle(ref circle) => circle.bounding_box(),
+            Node::Line(ref line) => line.bounding_box(),
+            Node::Polyline(ref polyline) => polyline.bounding_box(),
             Node::Image(ref image) => image.bounding_box(),
             Node::Text(ref text) => text.bounding_box(),
         }
@@ -923,6 +1264,9 @@ impl Node {
             Node::Rectangle(ref rect) => rect.abs_bounding_box(),
             Node::Ellipse(ref ellipse) => ellipse.abs_bounding_box(),
             Node::Polygon(ref polygon) => polygon.abs_bounding_box(),
+            Node::Circle(ref circle) => circle.abs_bounding_box(),
+            Node::Line(ref line) => line.abs_bounding_box(),
+            Node::Polyline(ref polyline) => polyline.abs_bounding_box(),
             Node::Image(ref image) => image.abs_bounding_box(),
             Node::Text(ref text) => text.abs_bounding_box(),
         }
@@ -936,6 +1280,9 @@ impl Node {
             Node::Rectangle(ref rect) => rect.stroke_bounding_box(),
             Node::Ellipse(ref ellipse) => ellipse.stroke_bounding_box(),
             Node::Polygon(ref polygon) => polygon.stroke_bounding_box(),
+            Node::Circle(ref circle) => circle.stroke_bounding_box(),
+            Node::Line(ref line) => line.stroke_bounding_box(),
+            Node::Polyline(ref polyline) => polyline.stroke_bounding_box(),
             // Image cannot be stroked.
             Node::Image(ref image) => image.bounding_box(),
             Node::Text(ref text) => text.stroke_bounding_box(),
@@ -950,6 +1297,9 @@ impl Node {
             Node::Rectangle(ref rect) => rect.abs_stroke_bounding_box(),
             Node::Ellipse(ref ellipse) => ellipse.abs_stroke_bounding_box(),
             Node::Polygon(ref polygon) => polygon.abs_stroke_bounding_box(),
+            Node::Circle(ref circle) => circle.abs_stroke_bounding_box(),
+            Node::Line(ref line) => line.abs_stroke_bounding_box(),
+            Node::Polyline(ref polyline) => polyline.abs_stroke_bounding_box(),
             // Image cannot be stroked.
             Node::Image(ref image) => image.abs_bounding_box(),
             Node::Text(ref text) => text.abs_stroke_bounding_box(),
@@ -970,6 +1320,9 @@ impl Node {
             Node::Rectangle(ref rect) => rect.abs_bounding_box().to_non_zero_rect(),
             Node::Ellipse(ref ellipse) => ellipse.abs_bounding_box().to_non_zero_rect(),
             Node::Polygon(ref polygon) => polygon.abs_bounding_box().to_non_zero_rect(),
+            Node::Circle(ref circle) => circle.abs_bounding_box().to_non_zero_rect(),
+            Node::Line(ref line) => line.abs_bounding_box().to_non_zero_rect(),
+            Node::Polyline(ref polyline) => polyline.abs_bounding_box().to_non_zero_rect(),
             Node::Image(ref image) => image.abs_bounding_box().to_non_zero_rect(),
             Node::Text(ref text) => text.abs_bounding_box().to_non_zero_rect(),
         }
@@ -1006,6 +1359,9 @@ impl Node {
             Node::Rectangle(ref rect) => rect.subroots(&mut f),
             Node::Ellipse(ref ellipse) => ellipse.subroots(&mut f),
             Node::Polygon(ref polygon) => polygon.subroots(&mut f),
+            Node::Circle(ref circle) => circle.subroots(&mut f),
+            Node::Line(ref line) => line.subroots(&mut f),
+            Node::Polyline(ref polyline) => polyline.subroots(&mut f),
             Node::Image(ref image) => image.subroots(&mut f),
             Node::Text(ref text) => text.subroots(&mut f),
         }
@@ -1029,7 +1385,7 @@ pub struct Group {
     pub(crate) clip_path: Option<Arc<ClipPath>>,
     /// Whether the group is a context element (i.e. a use node)
     pub(crate) is_context_element: bool,
-    pub(crate) mask: Option<Arc<Mask>>,
+    pub(crate) masks: Vec<MaskLayer>,
     pub(crate) filters: Vec<Arc<filter::Filter>>,
     pub(crate) bounding_box: Rect,
     pub(crate) abs_bounding_box: Rect,
@@ -1051,7 +1407,7 @@ impl Group {
             blend_mode: BlendMode::Normal,
             isolate: false,
             clip_path: None,
-            mask: None,
+            masks: Vec::new(),
             filters: Vec::new(),
             is_context_element: false,
             bounding_box: dummy,
@@ -1117,9 +1473,12 @@ impl Group {
         self.clip_path.as_deref()
     }
 
-    /// Element's mask.
-    pub fn mask(&self) -> Option<&Mask> {
-        self.mask.as_deref()
+    /// Element's mask layers.
+    ///
+    /// The first layer is the topmost one.
+    /// An empty list means the element is not masked.
+    pub fn masks(&self) -> &[MaskLayer] {
+        &self.masks
     }
 
     /// Element's filters.
@@ -1189,7 +1548,7 @@ impl Group {
         self.isolate
             || self.opacity != Opacity::ONE
             || self.clip_path.is_some()
-            || self.mask.is_some()
+            || !self.masks.is_empty()
             || !self.filters.is_empty()
             || self.blend_mode != BlendMode::Normal // TODO: probably not needed?
     }
@@ -1227,10 +1586,10 @@ impl Group {
             }
         }
 
-        if let Some(ref mask) = self.mask {
-            f(&mask.root);
+        for layer in &self.masks {
+            f(&layer.mask.root);
 
-            if let Some(ref sub_mask) = mask.mask {
+            if let Some(ref sub_mask) = layer.mask.mask {
                 f(&sub_mask.root);
             }
         }
@@ -1264,6 +1623,19 @@ impl Default for PaintOrder {
     }
 }
 
+/// A vector effect.
+///
+/// `vector-effect` attribute in the SVG.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum VectorEffect {
+    /// No effect.
+    #[default]
+    None,
+    /// Stroke is applied in the device space,
+    /// so its width is not affected by the element's transform.
+    NonScalingStroke,
+}
+
 /// A path element.
 #[derive(Clone, Debug)]
 pub struct Path {
@@ -1273,6 +1645,7 @@ pub struct Path {
     pub(crate) stroke: Option<Stroke>,
     pub(crate) paint_order: PaintOrder,
     pub(crate) rendering_mode: ShapeRendering,
+    pub(crate) vector_effect: VectorEffect,
     pub(crate) data: Arc<tiny_skia_path::Path>,
     pub(crate) abs_transform: Transform,
     pub(crate) bounding_box: Rect,
@@ -1312,24 +1685,12 @@ impl Path {
         data: Arc<tiny_skia_path::Path>,
         abs_transform: Transform,
     ) -> Option<Self> {
-        let bounding_box = data.compute_tight_bounds()?;
-        let stroke_bounding_box =
-            Path::calculate_stroke_bbox(stroke.as_ref(), &data).unwrap_or(bounding_box);
-
-        let abs_bounding_box: Rect;
-        let abs_stroke_bounding_box: Rect;
-        if abs_transform.has_skew() {
-            // TODO: avoid re-alloc
-            let path2 = data.as_ref().clone();
-            let path2 = path2.transform(abs_transform)?;
-            abs_bounding_box = path2.compute_tight_bounds()?;
-            abs_stroke_bounding_box =
-                Path::calculate_stroke_bbox(stroke.as_ref(), &path2).unwrap_or(abs_bounding_box);
-        } else {
-            // A transform without a skew can be performed just on a bbox.
-            abs_bounding_box = bounding_box.transform(abs_transform)?;
-            abs_stroke_bounding_box = stroke_bounding_box.transform(abs_transform)?;
-        }
+        let bboxes = ShapeBoundingBoxes::calculate(
+            &data,
+            stroke.as_ref(),
+            VectorEffect::None,
+            abs_transform,
+        )?;
 
         Some(Path {
             id,
@@ -1338,12 +1699,13 @@ impl Path {
             stroke,
             paint_order,
             rendering_mode,
+            vector_effect: VectorEffect::None,
             data,
             abs_transform,
-            bounding_box,
-            abs_bounding_box,
-            stroke_bounding_box,
-            abs_stroke_bounding_box,
+            bounding_box: bboxes.bounding_box,
+            abs_bounding_box: bboxes.abs_bounding_box,
+            stroke_bounding_box: bboxes.stroke_bounding_box,
+            abs_stroke_bounding_box: bboxes.abs_stroke_bounding_box,
         })
     }
 
@@ -1388,6 +1750,13 @@ impl Path {
         self.rendering_mode
     }
 
+    /// Vector effect.
+    ///
+    /// `vector-effect` in SVG.
+    pub fn vector_effect(&self) -> VectorEffect {
+        self.vector_effect
+    }
+
     // TODO: find a better name
     /// Segments list.
     ///
@@ -1434,7 +1803,17 @@ impl Path {
         self.abs_stroke_bounding_box
     }
 
-    pub(crate) fn calculate_stroke_bbox(stroke: Option<&Stroke>, path: &tiny_skia_path::Path) -> Option<Rect> {
+    pub(crate) fn calculate_stroke_bbox(
+        stroke: Option<&Stroke>,
+        path: &tiny_skia_path::Path,
+    ) -> Option<Rect> {
+        Self::stroke_for_bbox(stroke, path)?.compute_tight_bounds()
+    }
+
+    fn stroke_for_bbox(
+        stroke: Option<&Stroke>,
+        path: &tiny_skia_path::Path,
+    ) -> Option<tiny_skia_path::Path> {
         let mut stroke = stroke?.to_tiny_skia();
         // According to the spec, dash should not be accounted during bbox calculation.
         stroke.dash = None;
@@ -1442,11 +1821,7 @@ impl Path {
         // TODO: avoid for round and bevel caps
 
         // Expensive, but there is not much we can do about it.
-        if let Some(stroked_path) = path.stroke(&stroke, 1.0) {
-            return stroked_path.compute_tight_bounds();
-        }
-
-        None
+        path.stroke(&stroke, 1.0)
     }
 
     fn subroots(&self, f: &mut dyn FnMut(&Group)) {
@@ -1459,6 +1834,86 @@ impl Path {
     }
 }
 
+/// Bounding boxes of a shape outline.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct ShapeBoundingBoxes {
+    pub(crate) bounding_box: Rect,
+    pub(crate) abs_bounding_box: Rect,
+    pub(crate) stroke_bounding_box: Rect,
+    pub(crate) abs_stroke_bounding_box: Rect,
+}
+
+impl ShapeBoundingBoxes {
+    pub(crate) fn calculate(
+        path: &tiny_skia_path::Path,
+        stroke: Option<&Stroke>,
+        vector_effect: VectorEffect,
+        abs_transform: Transform,
+    ) -> Option<Self> {
+        let bounding_box = path.compute_tight_bounds()?;
+
+        if vector_effect == VectorEffect::NonScalingStroke && stroke.is_some() {
+            return Self::calculate_non_scaling(path, stroke, bounding_box, abs_transform);
+        }
+
+        let stroke_bounding_box = Path::calculate_stroke_bbox(stroke, path).unwrap_or(bounding_box);
+
+        let abs_bounding_box: Rect;
+        let abs_stroke_bounding_box: Rect;
+        if abs_transform.has_skew() {
+            // TODO: avoid re-alloc
+            let path2 = path.clone();
+            let path2 = path2.transform(abs_transform)?;
+            abs_bounding_box = path2.compute_tight_bounds()?;
+            abs_stroke_bounding_box =
+                Path::calculate_stroke_bbox(stroke, &path2).unwrap_or(abs_bounding_box);
+        } else {
+            // A transform without a skew can be performed just on a bbox.
+            abs_bounding_box = bounding_box.transform(abs_transform)?;
+            abs_stroke_bounding_box = stroke_bounding_box.transform(abs_transform)?;
+        }
+
+        Some(ShapeBoundingBoxes {
+            bounding_box,
+            abs_bounding_box,
+            stroke_bounding_box,
+            abs_stroke_bounding_box,
+        })
+    }
+
+    /// Calculates bounding boxes of a shape with a `non-scaling-stroke` vector effect.
+    ///
+    /// The stroke is applied in canvas coordinates, therefore the object stroke bounding box
+    /// is calculated by mapping the stroked outline back into the user space.
+    fn calculate_non_scaling(
+        path: &tiny_skia_path::Path,
+        stroke: Option<&Stroke>,
+        bounding_box: Rect,
+        abs_transform: Transform,
+    ) -> Option<Self> {
+        let abs_path = path.clone().transform(abs_transform)?;
+        let abs_bounding_box = abs_path.compute_tight_bounds()?;
+        let abs_stroked = Path::stroke_for_bbox(stroke, &abs_path);
+        let abs_stroke_bounding_box = abs_stroked
+            .as_ref()
+            .and_then(|p| p.compute_tight_bounds())
+            .unwrap_or(abs_bounding_box);
+
+        let stroke_bounding_box = abs_stroked
+            .zip(abs_transform.invert())
+            .and_then(|(p, ts)| p.transform(ts))
+            .and_then(|p| p.compute_tight_bounds())
+            .unwrap_or(bounding_box);
+
+        Some(ShapeBoundingBoxes {
+            bounding_box,
+            abs_bounding_box,
+            stroke_bounding_box,
+            abs_stroke_bounding_box,
+        })
+    }
+}
+
 /// A rectangle element.
 ///
 /// `rect` element in SVG.
@@ -1470,6 +1925,7 @@ pub struct Rectangle {
     pub(crate) stroke: Option<Stroke>,
     pub(crate) paint_order: PaintOrder,
     pub(crate) rendering_mode: ShapeRendering,
+    pub(crate) vector_effect: VectorEffect,
     pub(crate) x: f32,
     pub(crate) y: f32,
     pub(crate) width: f32,
@@ -1520,6 +1976,13 @@ impl Rectangle {
         self.rendering_mode
     }
 
+    /// Vector effect.
+    ///
+    /// `vector-effect` in SVG.
+    pub fn vector_effect(&self) -> VectorEffect {
+        self.vector_effect
+    }
+
     /// X coordinate.
     ///
     /// `x` in SVG.
@@ -1618,6 +2081,7 @@ pub struct Ellipse {
     pub(crate) stroke: Option<Stroke>,
     pub(crate) paint_order: PaintOrder,
     pub(crate) rendering_mode: ShapeRendering,
+    pub(crate) vector_effect: VectorEffect,
     pub(crate) cx: f32,
     pub(crate) cy: f32,
     pub(crate) rx: f32,
@@ -1666,6 +2130,13 @@ impl Ellipse {
         self.rendering_mode
     }
 
+    /// Vector effect.
+    ///
+    /// `vector-effect` in SVG.
+    pub fn vector_effect(&self) -> VectorEffect {
+        self.vector_effect
+    }
+
     /// Center X coordinate.
     ///
     /// `cx` in SVG.
@@ -1750,6 +2221,7 @@ pub struct Polygon {
     pub(crate) stroke: Option<Stroke>,
     pub(crate) paint_order: PaintOrder,
     pub(crate) rendering_mode: ShapeRendering,
+    pub(crate) vector_effect: VectorEffect,
     pub(crate) points: Vec<(f32, f32)>,
     pub(crate) abs_transform: Transform,
     pub(crate) bounding_box: Rect,
@@ -1795,6 +2267,13 @@ impl Polygon {
         self.rendering_mode
     }
 
+    /// Vector effect.
+    ///
+    /// `vector-effect` in SVG.
+    pub fn vector_effect(&self) -> VectorEffect {
+        self.vector_effect
+    }
+
     /// Polygon points.
     ///
     /// `points` in SVG.
@@ -1847,8 +2326,396 @@ impl Polygon {
     }
 }
 
-/// An embedded image kind.
-#[derive(Clone)]
+/// A circle element.
+///
+/// `circle` element in SVG.
+#[derive(Clone, Debug)]
+pub struct Circle {
+    pub(crate) id: String,
+    pub(crate) visible: bool,
+    pub(crate) fill: Option<Fill>,
+    pub(crate) stroke: Option<Stroke>,
+    pub(crate) paint_order: PaintOrder,
+    pub(crate) rendering_mode: ShapeRendering,
+    pub(crate) vector_effect: VectorEffect,
+    pub(crate) cx: f32,
+    pub(crate) cy: f32,
+    pub(crate) r: f32,
+    pub(crate) abs_transform: Transform,
+    pub(crate) bounding_box: Rect,
+    pub(crate) abs_bounding_box: Rect,
+    pub(crate) stroke_bounding_box: Rect,
+    pub(crate) abs_stroke_bounding_box: Rect,
+}
+
+impl Circle {
+    /// Element's ID.
+    ///
+    /// Taken from the SVG itself.
+    /// Isn't automatically generated.
+    /// Can be empty.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Element visibility.
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Fill style.
+    pub fn fill(&self) -> Option<&Fill> {
+        self.fill.as_ref()
+    }
+
+    /// Stroke style.
+    pub fn stroke(&self) -> Option<&Stroke> {
+        self.stroke.as_ref()
+    }
+
+    /// Fill and stroke paint order.
+    pub fn paint_order(&self) -> PaintOrder {
+        self.paint_order
+    }
+
+    /// Rendering mode.
+    ///
+    /// `shape-rendering` in SVG.
+    pub fn rendering_mode(&self) -> ShapeRendering {
+        self.rendering_mode
+    }
+
+    /// Vector effect.
+    ///
+    /// `vector-effect` in SVG.
+    pub fn vector_effect(&self) -> VectorEffect {
+        self.vector_effect
+    }
+
+    /// Center X coordinate.
+    ///
+    /// `cx` in SVG.
+    pub fn cx(&self) -> f32 {
+        self.cx
+    }
+
+    /// Center Y coordinate.
+    ///
+    /// `cy` in SVG.
+    pub fn cy(&self) -> f32 {
+        self.cy
+    }
+
+    /// Circle radius.
+    ///
+    /// `r` in SVG.
+    pub fn r(&self) -> f32 {
+        self.r
+    }
+
+    /// Element's absolute transform.
+    ///
+    /// Contains all ancestors transforms including elements's transform.
+    pub fn abs_transform(&self) -> Transform {
+        self.abs_transform
+    }
+
+    /// Element's object bounding box.
+    ///
+    /// `objectBoundingBox` in SVG terms. Meaning it doesn't affected by parent transforms.
+    pub fn bounding_box(&self) -> Rect {
+        self.bounding_box
+    }
+
+    /// Element's bounding box in canvas coordinates.
+    ///
+    /// `userSpaceOnUse` in SVG terms.
+    pub fn abs_bounding_box(&self) -> Rect {
+        self.abs_bounding_box
+    }
+
+    /// Element's object bounding box including stroke.
+    ///
+    /// Will have the same value as `bounding_box` when circle has no stroke.
+    pub fn stroke_bounding_box(&self) -> Rect {
+        self.stroke_bounding_box
+    }
+
+    /// Element's bounding box including stroke in canvas coordinates.
+    ///
+    /// Will have the same value as `abs_bounding_box` when circle has no stroke.
+    pub fn abs_stroke_bounding_box(&self) -> Rect {
+        self.abs_stroke_bounding_box
+    }
+
+    fn subroots(&self, f: &mut dyn FnMut(&Group)) {
+        if let Some(Paint::Pattern(ref patt)) = self.fill.as_ref().map(|f| &f.paint) {
+            f(patt.root());
+        }
+        if let Some(Paint::Pattern(ref patt)) = self.stroke.as_ref().map(|f| &f.paint) {
+            f(patt.root());
+        }
+    }
+}
+
+/// A line element.
+///
+/// `line` element in SVG.
+#[derive(Clone, Debug)]
+pub struct Line {
+    pub(crate) id: String,
+    pub(crate) visible: bool,
+    pub(crate) fill: Option<Fill>,
+    pub(crate) stroke: Option<Stroke>,
+    pub(crate) paint_order: PaintOrder,
+    pub(crate) rendering_mode: ShapeRendering,
+    pub(crate) vector_effect: VectorEffect,
+    pub(crate) x1: f32,
+    pub(crate) y1: f32,
+    pub(crate) x2: f32,
+    pub(crate) y2: f32,
+    pub(crate) abs_transform: Transform,
+    pub(crate) bounding_box: Rect,
+    pub(crate) abs_bounding_box: Rect,
+    pub(crate) stroke_bounding_box: Rect,
+    pub(crate) abs_stroke_bounding_box: Rect,
+}
+
+impl Line {
+    /// Element's ID.
+    ///
+    /// Taken from the SVG itself.
+    /// Isn't automatically generated.
+    /// Can be empty.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Element visibility.
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Fill style.
+    pub fn fill(&self) -> Option<&Fill> {
+        self.fill.as_ref()
+    }
+
+    /// Stroke style.
+    pub fn stroke(&self) -> Option<&Stroke> {
+        self.stroke.as_ref()
+    }
+
+    /// Fill and stroke paint order.
+    pub fn paint_order(&self) -> PaintOrder {
+        self.paint_order
+    }
+
+    /// Rendering mode.
+    ///
+    /// `shape-rendering` in SVG.
+    pub fn rendering_mode(&self) -> ShapeRendering {
+        self.rendering_mode
+    }
+
+    /// Vector effect.
+    ///
+    /// `vector-effect` in SVG.
+    pub fn vector_effect(&self) -> VectorEffect {
+        self.vector_effect
+    }
+
+    /// Start X coordinate.
+    ///
+    /// `x1` in SVG.
+    pub fn x1(&self) -> f32 {
+        self.x1
+    }
+
+    /// Start Y coordinate.
+    ///
+    /// `y1` in SVG.
+    pub fn y1(&self) -> f32 {
+        self.y1
+    }
+
+    /// End X coordinate.
+    ///
+    /// `x2` in SVG.
+    pub fn x2(&self) -> f32 {
+        self.x2
+    }
+
+    /// End Y coordinate.
+    ///
+    /// `y2` in SVG.
+    pub fn y2(&self) -> f32 {
+        self.y2
+    }
+
+    /// Element's absolute transform.
+    ///
+    /// Contains all ancestors transforms including elements's transform.
+    pub fn abs_transform(&self) -> Transform {
+        self.abs_transform
+    }
+
+    /// Element's object bounding box.
+    ///
+    /// `objectBoundingBox` in SVG terms. Meaning it doesn't affected by parent transforms.
+    pub fn bounding_box(&self) -> Rect {
+        self.bounding_box
+    }
+
+    /// Element's bounding box in canvas coordinates.
+    ///
+    /// `userSpaceOnUse` in SVG terms.
+    pub fn abs_bounding_box(&self) -> Rect {
+        self.abs_bounding_box
+    }
+
+    /// Element's object bounding box including stroke.
+    ///
+    /// Will have the same value as `bounding_box` when line has no stroke.
+    pub fn stroke_bounding_box(&self) -> Rect {
+        self.stroke_bounding_box
+    }
+
+    /// Element's bounding box including stroke in canvas coordinates.
+    ///
+    /// Will have the same value as `abs_bounding_box` when line has no stroke.
+    pub fn abs_stroke_bounding_box(&self) -> Rect {
+        self.abs_stroke_bounding_box
+    }
+
+    fn subroots(&self, f: &mut dyn FnMut(&Group)) {
+        if let Some(Paint::Pattern(ref patt)) = self.fill.as_ref().map(|f| &f.paint) {
+            f(patt.root());
+        }
+        if let Some(Paint::Pattern(ref patt)) = self.stroke.as_ref().map(|f| &f.paint) {
+            f(patt.root());
+        }
+    }
+}
+
+/// A polyline element.
+///
+/// `polyline` element in SVG.
+#[derive(Clone, Debug)]
+pub struct Polyline {
+    pub(crate) id: String,
+    pub(crate) visible: bool,
+    pub(crate) fill: Option<Fill>,
+    pub(crate) stroke: Option<Stroke>,
+    pub(crate) paint_order: PaintOrder,
+    pub(crate) rendering_mode: ShapeRendering,
+    pub(crate) vector_effect: VectorEffect,
+    pub(crate) points: Vec<(f32, f32)>,
+    pub(crate) abs_transform: Transform,
+    pub(crate) bounding_box: Rect,
+    pub(crate) abs_bounding_box: Rect,
+    pub(crate) stroke_bounding_box: Rect,
+    pub(crate) abs_stroke_bounding_box: Rect,
+}
+
+impl Polyline {
+    /// Element's ID.
+    ///
+    /// Taken from the SVG itself.
+    /// Isn't automatically generated.
+    /// Can be empty.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Element visibility.
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Fill style.
+    pub fn fill(&self) -> Option<&Fill> {
+        self.fill.as_ref()
+    }
+
+    /// Stroke style.
+    pub fn stroke(&self) -> Option<&Stroke> {
+        self.stroke.as_ref()
+    }
+
+    /// Fill and stroke paint order.
+    pub fn paint_order(&self) -> PaintOrder {
+        self.paint_order
+    }
+
+    /// Rendering mode.
+    ///
+    /// `shape-rendering` in SVG.
+    pub fn rendering_mode(&self) -> ShapeRendering {
+        self.rendering_mode
+    }
+
+    /// Vector effect.
+    ///
+    /// `vector-effect` in SVG.
+    pub fn vector_effect(&self) -> VectorEffect {
+        self.vector_effect
+    }
+
+    /// Polyline points.
+    ///
+    /// `points` in SVG.
+    pub fn points(&self) -> &[(f32, f32)] {
+        &self.points
+    }
+
+    /// Element's absolute transform.
+    ///
+    /// Contains all ancestors transforms including elements's transform.
+    pub fn abs_transform(&self) -> Transform {
+        self.abs_transform
+    }
+
+    /// Element's object bounding box.
+    ///
+    /// `objectBoundingBox` in SVG terms. Meaning it doesn't affected by parent transforms.
+    pub fn bounding_box(&self) -> Rect {
+        self.bounding_box
+    }
+
+    /// Element's bounding box in canvas coordinates.
+    ///
+    /// `userSpaceOnUse` in SVG terms.
+    pub fn abs_bounding_box(&self) -> Rect {
+        self.abs_bounding_box
+    }
+
+    /// Element's object bounding box including stroke.
+    ///
+    /// Will have the same value as `bounding_box` when polyline has no stroke.
+    pub fn stroke_bounding_box(&self) -> Rect {
+        self.stroke_bounding_box
+    }
+
+    /// Element's bounding box including stroke in canvas coordinates.
+    ///
+    /// Will have the same value as `abs_bounding_box` when polyline has no stroke.
+    pub fn abs_stroke_bounding_box(&self) -> Rect {
+        self.abs_stroke_bounding_box
+    }
+
+    fn subroots(&self, f: &mut dyn FnMut(&Group)) {
+        if let Some(Paint::Pattern(ref patt)) = self.fill.as_ref().map(|f| &f.paint) {
+            f(patt.root());
+        }
+        if let Some(Paint::Pattern(ref patt)) = self.stroke.as_ref().map(|f| &f.paint) {
+            f(patt.root());
+        }
+    }
+}
+
+/// An embedded image kind.
+#[derive(Clone)]
 pub enum ImageKind {
     /// A reference to raw JPEG data. Should be decoded by the caller.
     JPEG(Arc<Vec<u8>>),
@@ -1977,12 +2844,16 @@ pub struct Tree {
     pub(crate) root: Group,
     pub(crate) linear_gradients: Vec<Arc<LinearGradient>>,
     pub(crate) radial_gradients: Vec<Arc<RadialGradient>>,
+    pub(crate) conic_gradients: Vec<Arc<ConicGradient>>,
     pub(crate) patterns: Vec<Arc<Pattern>>,
+    pub(crate) mesh_gradients: Vec<Arc<MeshGradient>>,
+    pub(crate) hatches: Vec<Arc<Hatch>>,
     pub(crate) clip_paths: Vec<Arc<ClipPath>>,
     pub(crate) masks: Vec<Arc<Mask>>,
     pub(crate) filters: Vec<Arc<filter::Filter>>,
     #[cfg(feature = "text")]
     pub(crate) fontdb: Arc<fontdb::Database>,
+    pub(crate) diagnostics: Vec<crate::Diagnostic>,
 }
 
 impl Tree {
@@ -2026,11 +2897,26 @@ impl Tree {
         &self.radial_gradients
     }
 
+    /// Returns a list of all unique [`ConicGradient`]s in the tree.
+    pub fn conic_gradients(&self) -> &[Arc<ConicGradient>] {
+        &self.conic_gradients
+    }
+
     /// Returns a list of all unique [`Pattern`]s in the tree.
     pub fn patterns(&self) -> &[Arc<Pattern>] {
         &self.patterns
     }
 
+    /// Returns a list of all unique [`MeshGradient`]s in the tree.
+    pub fn mesh_gradients(&self) -> &[Arc<MeshGradient>] {
+        &self.mesh_gradients
+    }
+
+    /// Returns a list of all unique [`Hatch`]es in the tree.
+    pub fn hatches(&self) -> &[Arc<Hatch>] {
+        &self.hatches
+    }
+
     /// Returns a list of all unique [`ClipPath`]s in the tree.
     pub fn clip_paths(&self) -> &[Arc<ClipPath>] {
         &self.clip_paths
@@ -2052,6 +2938,14 @@ impl Tree {
         &self.fontdb
     }
 
+    /// Returns a list of problems found during parsing.
+    ///
+    /// Nested SVG images have their own diagnostics.
+    /// Always empty for trees created using [`TreeBuilder`].
+    pub fn diagnostics(&self) -> &[crate::Diagnostic] {
+        &self.diagnostics
+    }
+
     pub(crate) fn collect_paint_servers(&mut self) {
         loop_over_paint_servers(&self.root, &mut |paint| match paint {
             Paint::Color(_) => {}
@@ -2073,11 +2967,34 @@ impl Tree {
                     self.radial_gradients.push(rg.clone());
                 }
             }
+            Paint::ConicGradient(cg) => {
+                if !self
+                    .conic_gradients
+                    .iter()
+                    .any(|other| Arc::ptr_eq(cg, other))
+                {
+                    self.conic_gradients.push(cg.clone());
+                }
+            }
             Paint::Pattern(patt) => {
                 if !self.patterns.iter().any(|other| Arc::ptr_eq(patt, other)) {
                     self.patterns.push(patt.clone());
                 }
             }
+            Paint::MeshGradient(mg) => {
+                if !self
+                    .mesh_gradients
+                    .iter()
+                    .any(|other| Arc::ptr_eq(mg, other))
+                {
+                    self.mesh_gradients.push(mg.clone());
+                }
+            }
+            Paint::Hatch(hatch) => {
+                if !self.hatches.iter().any(|other| Arc::ptr_eq(hatch, other)) {
+                    self.hatches.push(hatch.clone());
+                }
+            }
         });
     }
 }
@@ -2128,6 +3045,13 @@ fn loop_over_paint_servers(parent: &Group, f: &mut dyn FnMut(&Paint)) {
     fn push(paint: Option<&Paint>, f: &mut dyn FnMut(&Paint)) {
         if let Some(paint) = paint {
             f(paint);
+
+            // Hatch paths can be stroked with paint servers as well.
+            if let Paint::Hatch(ref hatch) = paint {
+                for path in &hatch.paths {
+                    push(Some(&path.stroke.paint), f);
+                }
+            }
         }
     }
 
@@ -2150,6 +3074,18 @@ fn loop_over_paint_servers(parent: &Group, f: &mut dyn FnMut(&Paint)) {
                 push(polygon.fill.as_ref().map(|f| &f.paint), f);
                 push(polygon.stroke.as_ref().map(|f| &f.paint), f);
             }
+            Node::Circle(ref circle) => {
+                push(circle.fill.as_ref().map(|f| &f.paint), f);
+                push(circle.stroke.as_ref().map(|f| &f.paint), f);
+            }
+            Node::Line(ref line) => {
+                push(line.fill.as_ref().map(|f| &f.paint), f);
+                push(line.stroke.as_ref().map(|f| &f.paint), f);
+            }
+            Node::Polyline(ref polyline) => {
+                push(polyline.fill.as_ref().map(|f| &f.paint), f);
+                push(polyline.stroke.as_ref().map(|f| &f.paint), f);
+            }
             Node::Image(_) => {}
             // Flattened text would be used instead.
             Node::Text(_) => {}
@@ -2187,7 +3123,8 @@ impl Group {
     pub(crate) fn collect_masks(&self, masks: &mut Vec<Arc<Mask>>) {
         for node in self.children() {
             if let Node::Group(ref g) = node {
-                if let Some(ref mask) = g.mask {
+                for layer in &g.masks {
+                    let mask = &layer.mask;
                     if !masks.iter().any(|other| Arc::ptr_eq(mask, other)) {
                         masks.push(mask.clone());
                     }
@@ -32,6 +32,25 @@ impl Default for FontStretch {
     }
 }
 
+impl FontStretch {
+    /// Returns a width as a percentage of the normal width.
+    ///
+    /// Matches the `wdth` axis of variable fonts.
+    pub fn to_percentage(self) -> f32 {
+        match self {
+            FontStretch::UltraCondensed => 50.0,
+            FontStretch::ExtraCondensed => 62.5,
+            FontStretch::Condensed => 75.0,
+            FontStretch::SemiCondensed => 87.5,
+            FontStretch::Normal => 100.0,
+            FontStretch::SemiExpanded => 112.5,
+            FontStretch::Expanded => 125.0,
+            FontStretch::ExtraExpanded => 150.0,
+            FontStretch::UltraExpanded => 200.0,
+        }
+    }
+}
+
 #[cfg(feature = "text")]
 impl From<fontdb::Stretch> for FontStretch {
     fn from(stretch: fontdb::Stretch) -> Self {
@@ -106,6 +125,42 @@ impl From<FontStyle> for fontdb::Style {
     }
 }
 
+/// A variable font axis value.
+///
+/// Resolved from `font-variation-settings` and `font-stretch` percentages.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct FontVariation {
+    pub(crate) tag: [u8; 4],
+    pub(crate) value: f32,
+}
+
+impl FontVariation {
+    /// Creates a new axis value.
+    pub fn new(tag: [u8; 4], value: f32) -> Self {
+        FontVariation { tag, value }
+    }
+
+    /// An axis tag.
+    pub fn tag(&self) -> [u8; 4] {
+        self.tag
+    }
+
+    /// An axis value.
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+}
+
+// `Font` is used as a fonts cache key.
+impl Eq for FontVariation {}
+
+impl std::hash::Hash for FontVariation {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.tag.hash(state);
+        self.value.to_bits().hash(state);
+    }
+}
+
 /// Text font properties.
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
 pub struct Font {
@@ -113,6 +168,7 @@ pub struct Font {
     pub(crate) style: FontStyle,
     pub(crate) stretch: FontStretch,
     pub(crate) weight: u16,
+    pub(crate) variations: Vec<FontVariation>,
 }
 
 impl Font {
@@ -133,10 +189,21 @@ impl Font {
         self.stretch
     }
 
-    /// A font width.
+    /// A font weight.
+    ///
+    /// Any value in a 1..=1000 range.
     pub fn weight(&self) -> u16 {
         self.weight
     }
+
+    /// Variable font axis values.
+    ///
+    /// Applied in order, so later values override earlier ones.
+    /// `wght` and `wdth` axes are set from `weight` and `stretch` beforehand
+    /// and are present here only when overridden.
+    pub fn variations(&self) -> &[FontVariation] {
+        &self.variations
+    }
 }
 
 /// A dominant baseline property.
@@ -218,6 +285,115 @@ impl Default for LengthAdjust {
     }
 }
 
+/// A white space handling property.
+///
+/// Whitespace collapsing is already applied to the text chunk text,
+/// so this property affects only line breaking.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum WhiteSpace {
+    /// Lines can be broken automatically.
+    Normal,
+    /// Lines are broken only at preserved line breaks.
+    ///
+    /// Includes `nowrap`.
+    NoWrap,
+    /// Spaces and line breaks are preserved.
+    /// Lines are broken only at preserved line breaks.
+    Pre,
+    /// Spaces and line breaks are preserved.
+    /// Lines can be broken automatically.
+    ///
+    /// Includes `break-spaces`.
+    PreWrap,
+    /// Line breaks are preserved.
+    /// Lines can be broken automatically.
+    PreLine,
+}
+
+impl Default for WhiteSpace {
+    fn default() -> Self {
+        Self::Normal
+    }
+}
+
+/// A text orientation property.
+///
+/// Affects only vertical text. Resolved from `text-orientation`
+/// and the deprecated `glyph-orientation-vertical`.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum TextOrientation {
+    /// Characters from horizontal-only scripts are rotated,
+    /// while characters from vertical scripts are set upright.
+    #[default]
+    Mixed,
+    /// All characters are set upright.
+    Upright,
+    /// All characters are rotated 90° clockwise.
+    Sideways,
+}
+
+/// An OpenType font feature.
+///
+/// Resolved from `font-feature-settings` and `font-variant-*` properties.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct FontFeature {
+    pub(crate) tag: [u8; 4],
+    pub(crate) value: u32,
+}
+
+impl FontFeature {
+    /// Creates a new feature.
+    pub fn new(tag: [u8; 4], value: u32) -> Self {
+        FontFeature { tag, value }
+    }
+
+    /// A feature tag.
+    pub fn tag(&self) -> [u8; 4] {
+        self.tag
+    }
+
+    /// A feature value.
+    ///
+    /// `0` disables a feature and `1` enables it.
+    /// Larger values select an alternate glyph.
+    pub fn value(&self) -> u32 {
+        self.value
+    }
+}
+
+/// A text decoration line style.
+///
+/// `text-decoration-style` in CSS.
+#[allow(missing_docs)]
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum TextDecorationLineStyle {
+    #[default]
+    Solid,
+    Double,
+    Dotted,
+    Dashed,
+    Wavy,
+}
+
+/// An underline position.
+///
+/// `text-underline-position` in CSS.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum TextUnderlinePosition {
+    /// Uses the underline position from the font.
+    #[default]
+    Auto,
+    /// Places the underline below the font descent,
+    /// so it never crosses descenders.
+    Under,
+    /// Places the underline on the left side of vertical text.
+    ///
+    /// This is where `auto` places it as well.
+    Left,
+    /// Places the underline on the right side of vertical text.
+    Right,
+}
+
 /// A text span decoration style.
 ///
 /// In SVG, text decoration and text it's applied to can have different styles.
@@ -228,18 +404,46 @@ impl Default for LengthAdjust {
 pub struct TextDecorationStyle {
     pub(crate) fill: Option<Fill>,
     pub(crate) stroke: Option<Stroke>,
+    pub(crate) line_style: TextDecorationLineStyle,
+    pub(crate) underline_position: TextUnderlinePosition,
+    pub(crate) skip_ink: bool,
 }
 
 impl TextDecorationStyle {
     /// A fill style.
+    ///
+    /// Resolved from `text-decoration-fill`, `text-decoration-color`
+    /// or the `fill` of the element that declared the decoration.
     pub fn fill(&self) -> Option<&Fill> {
         self.fill.as_ref()
     }
 
     /// A stroke style.
+    ///
+    /// Resolved from `text-decoration-stroke`
+    /// or the `stroke` of the element that declared the decoration.
     pub fn stroke(&self) -> Option<&Stroke> {
         self.stroke.as_ref()
     }
+
+    /// A line style.
+    pub fn line_style(&self) -> TextDecorationLineStyle {
+        self.line_style
+    }
+
+    /// An underline position.
+    ///
+    /// Ignored by overlines and line-throughs.
+    pub fn underline_position(&self) -> TextUnderlinePosition {
+        self.underline_position
+    }
+
+    /// Indicates that the line is interrupted where it crosses glyphs.
+    ///
+    /// `text-decoration-skip-ink` in CSS. Ignored by line-throughs.
+    pub fn skip_ink(&self) -> bool {
+        self.skip_ink
+    }
 }
 
 /// A text span decoration.
@@ -281,6 +485,7 @@ pub struct TextSpan {
     pub(crate) font_size: NonZeroPositiveF32,
     pub(crate) small_caps: bool,
     pub(crate) apply_kerning: bool,
+    pub(crate) font_features: Vec<FontFeature>,
     pub(crate) decoration: TextDecoration,
     pub(crate) dominant_baseline: DominantBaseline,
     pub(crate) alignment_baseline: AlignmentBaseline,
@@ -290,6 +495,9 @@ pub struct TextSpan {
     pub(crate) word_spacing: f32,
     pub(crate) text_length: Option<f32>,
     pub(crate) length_adjust: LengthAdjust,
+    pub(crate) white_space: WhiteSpace,
+    pub(crate) line_height: Option<f32>,
+    pub(crate) text_orientation: TextOrientation,
 }
 
 impl TextSpan {
@@ -346,6 +554,14 @@ impl TextSpan {
         self.apply_kerning
     }
 
+    /// OpenType font features.
+    ///
+    /// Applied in order, so later features override earlier ones.
+    /// Small caps and kerning are not included.
+    pub fn font_features(&self) -> &[FontFeature] {
+        &self.font_features
+    }
+
     /// A span decorations.
     pub fn decoration(&self) -> &TextDecoration {
         &self.decoration
@@ -392,6 +608,26 @@ impl TextSpan {
     pub fn length_adjust(&self) -> LengthAdjust {
         self.length_adjust
     }
+
+    /// A white space property.
+    pub fn white_space(&self) -> WhiteSpace {
+        self.white_space
+    }
+
+    /// A line height in user units.
+    ///
+    /// Used only by multi-line text. `None` means `normal`,
+    /// i.e. the font's ascent and descent.
+    pub fn line_height(&self) -> Option<f32> {
+        self.line_height
+    }
+
+    /// A text orientation property.
+    ///
+    /// Used only by vertical text.
+    pub fn text_orientation(&self) -> TextOrientation {
+        self.text_orientation
+    }
 }
 
 /// A text chunk anchor property.
@@ -414,6 +650,7 @@ impl Default for TextAnchor {
 pub struct TextPath {
     pub(crate) id: NonEmptyString,
     pub(crate) start_offset: f32,
+    pub(crate) side: TextPathSide,
     pub(crate) path: Arc<tiny_skia_path::Path>,
 }
 
@@ -421,6 +658,7 @@ impl TextPath {
     /// Element's ID.
     ///
     /// Taken from the SVG itself.
+    /// Generated when the path is set via the `path` attribute.
     pub fn id(&self) -> &str {
         self.id.get()
     }
@@ -432,12 +670,31 @@ impl TextPath {
         self.start_offset
     }
 
+    /// A side of the path the text is placed on.
+    pub fn side(&self) -> TextPathSide {
+        self.side
+    }
+
     /// A path.
+    ///
+    /// Not reversed for [`TextPathSide::Right`].
     pub fn path(&self) -> &tiny_skia_path::Path {
         &self.path
     }
 }
 
+/// A `textPath` side.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum TextPathSide {
+    /// Text is placed on the left side of the path, relative to the path direction.
+    #[default]
+    Left,
+    /// Text is placed on the right side of the path.
+    ///
+    /// The path direction is reversed during layout.
+    Right,
+}
+
 /// A text chunk flow property.
 #[derive(Clone, Debug)]
 pub enum TextFlow {
@@ -449,6 +706,82 @@ pub enum TextFlow {
     Path(Arc<TextPath>),
 }
 
+/// Text wrapping.
+///
+/// When set, all text chunks are laid out as a single paragraph,
+/// which is broken into lines automatically.
+#[derive(Clone, Debug)]
+pub enum TextWrap {
+    /// Lines are broken at the specified length.
+    ///
+    /// Lines start at the first text chunk position and are aligned using its anchor.
+    ///
+    /// `inline-size` in SVG.
+    InlineSize(f32),
+    /// Text is flowed into a shape.
+    ///
+    /// Text chunk positions are ignored. Text that doesn't fit is not rendered.
+    ///
+    /// `shape-inside` in SVG.
+    Shape(Arc<TextShape>),
+}
+
+/// A shape text is flowed into.
+#[derive(Debug)]
+pub struct TextShape {
+    pub(crate) inside: Arc<tiny_skia_path::Path>,
+    pub(crate) subtract: Option<Arc<tiny_skia_path::Path>>,
+    pub(crate) padding: f32,
+    pub(crate) margin: f32,
+}
+
+impl TextShape {
+    /// Creates a new text shape.
+    ///
+    /// Negative `padding` and `margin` are treated as zero.
+    pub fn new(
+        inside: Arc<tiny_skia_path::Path>,
+        subtract: Option<Arc<tiny_skia_path::Path>>,
+        padding: f32,
+        margin: f32,
+    ) -> Self {
+        TextShape {
+            inside,
+            subtract,
+            padding: padding.max(0.0),
+            margin: margin.max(0.0),
+        }
+    }
+
+    /// A shape text is flowed into.
+    ///
+    /// All shapes from `shape-inside` merged into a single path in text coordinates.
+    pub fn inside(&self) -> &tiny_skia_path::Path {
+        &self.inside
+    }
+
+    /// A shape excluded from the flow area.
+    ///
+    /// All shapes from `shape-subtract` merged into a single path in text coordinates.
+    pub fn subtract(&self) -> Option<&tiny_skia_path::Path> {
+        self.subtract.as_deref()
+    }
+
+    /// An offset from the `inside` shape edges.
+    ///
+    /// `shape-padding` in SVG.
+    pub fn padding(&self) -> f32 {
+        self.padding
+    }
+
+    /// An offset from the `subtract` shape edges.
+    ///
+    /// `shape-margin` in SVG.
+    pub fn margin(&self) -> f32 {
+        self.margin
+    }
+}
+
 /// A text chunk.
 ///
 /// Text alignment and BIDI reordering can only be done inside a text chunk.
@@ -460,6 +793,7 @@ pub struct TextChunk {
     pub(crate) spans: Vec<TextSpan>,
     pub(crate) text_flow: TextFlow,
     pub(crate) text: String,
+    pub(crate) bidi_embeddings: Vec<BidiEmbedding>,
 }
 
 impl TextChunk {
@@ -492,6 +826,87 @@ impl TextChunk {
     pub fn text(&self) -> &str {
         &self.text
     }
+
+    /// A list of explicit bidi embeddings.
+    ///
+    /// Embeddings are properly nested and ordered from the outermost one.
+    pub fn bidi_embeddings(&self) -> &[BidiEmbedding] {
+        &self.bidi_embeddings
+    }
+}
+
+/// An explicit bidi embedding.
+///
+/// Created by an element with a non-`normal` `unicode-bidi` property.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct BidiEmbedding {
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+    pub(crate) direction: TextDirection,
+    pub(crate) unicode_bidi: UnicodeBidi,
+}
+
+impl BidiEmbedding {
+    /// Creates a new embedding.
+    ///
+    /// `start` and `end` are in bytes and relative to the parent text chunk.
+    pub fn new(
+        start: usize,
+        end: usize,
+        direction: TextDirection,
+        unicode_bidi: UnicodeBidi,
+    ) -> Self {
+        BidiEmbedding {
+            start,
+            end,
+            direction,
+            unicode_bidi,
+        }
+    }
+
+    /// An embedding start in bytes.
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    /// An embedding end in bytes.
+    pub fn end(&self) -> usize {
+        self.end
+    }
+
+    /// An embedding direction.
+    ///
+    /// Ignored by `plaintext`, which uses the direction of the embedded text.
+    pub fn direction(&self) -> TextDirection {
+        self.direction
+    }
+
+    /// An embedding kind.
+    pub fn unicode_bidi(&self) -> UnicodeBidi {
+        self.unicode_bidi
+    }
+}
+
+/// A `unicode-bidi` property.
+///
+/// `normal` is not listed, since it doesn't create an embedding.
+#[allow(missing_docs)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum UnicodeBidi {
+    Embed,
+    Isolate,
+    BidiOverride,
+    IsolateOverride,
+    Plaintext,
+}
+
+/// A text direction.
+#[allow(missing_docs)]
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum TextDirection {
+    #[default]
+    LeftToRight,
+    RightToLeft,
 }
 
 /// A writing mode.
@@ -513,6 +928,8 @@ pub struct Text {
     pub(crate) dy: Vec<f32>,
     pub(crate) rotate: Vec<f32>,
     pub(crate) writing_mode: WritingMode,
+    pub(crate) direction: TextDirection,
+    pub(crate) wrap: Option<TextWrap>,
     pub(crate) chunks: Vec<TextChunk>,
     pub(crate) abs_transform: Transform,
     pub(crate) bounding_box: Rect,
@@ -567,6 +984,18 @@ impl Text {
         self.writing_mode
     }
 
+    /// A base text direction.
+    ///
+    /// Defines the paragraph embedding level and the side `text-anchor` is relative to.
+    pub fn direction(&self) -> TextDirection {
+        self.direction
+    }
+
+    /// Text wrapping.
+    pub fn wrap(&self) -> Option<&TextWrap> {
+        self.wrap.as_ref()
+    }
+
     /// A list of text chunks.
     pub fn chunks(&self) -> &[TextChunk] {
         &self.chunks
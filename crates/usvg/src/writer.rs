@@ -12,6 +12,9 @@ use crate::{ApproxEqUlps, *};
 
 impl Tree {
     /// Writes `usvg::Tree` back to SVG.
+    ///
+    /// Conic gradients are not part of SVG, therefore they are written
+    /// as a solid color, averaged from the gradient stops.
     pub fn to_string(&self, opt: &WriteOptions) -> String {
         convert(self, opt)
     }
@@ -527,6 +530,9 @@ fn write_defs(tree: &Tree, opt: &WriteOptions, xml: &mut XmlWriter) {
         xml.write_svg_attribute(AId::R, &rg.r.get());
         xml.write_svg_attribute(AId::Fx, &rg.fx);
         xml.write_svg_attribute(AId::Fy, &rg.fy);
+        if rg.fr.get() > 0.0 {
+            xml.write_svg_attribute(AId::Fr, &rg.fr.get());
+        }
         write_base_grad(&rg.base, opt, xml);
         xml.end_element();
     }
@@ -548,6 +554,42 @@ fn write_defs(tree: &Tree, opt: &WriteOptions, xml: &mut XmlWriter) {
         xml.end_element();
     }
 
+    for mg in tree.mesh_gradients() {
+        write_mesh_gradient(mg, opt, xml);
+    }
+
+    for hatch in tree.hatches() {
+        xml.start_svg_element(EId::Hatch);
+        xml.write_id_attribute(hatch.id(), opt);
+        xml.write_svg_attribute(AId::X, &hatch.x);
+        xml.write_svg_attribute(AId::Y, &hatch.y);
+        xml.write_svg_attribute(AId::Pitch, &hatch.pitch.get());
+        if hatch.rotate != 0.0 {
+            xml.write_svg_attribute(AId::Rotate, &hatch.rotate);
+        }
+        xml.write_units(AId::HatchUnits, hatch.units, Units::ObjectBoundingBox);
+        xml.write_units(
+            AId::HatchContentUnits,
+            hatch.content_units,
+            Units::UserSpaceOnUse,
+        );
+        xml.write_transform(AId::Transform, hatch.transform, opt);
+
+        for path in &hatch.paths {
+            xml.start_svg_element(EId::Hatchpath);
+            if path.offset != 0.0 {
+                xml.write_svg_attribute(AId::Offset, &path.offset);
+            }
+            write_stroke(&Some(path.stroke.clone()), opt, xml);
+            if let Some(ref data) = path.data {
+                write_path_data(data, opt, xml);
+            }
+            xml.end_element();
+        }
+
+        xml.end_element();
+    }
+
     if tree.has_text_nodes() {
         write_text_path_paths(&tree.root, opt, xml);
     }
@@ -681,8 +723,10 @@ fn write_element(node: &Node, is_clip_path: bool, opt: &WriteOptions, xml: &mut
                 rect.abs_transform(),
             );
 
-            if let Some(ref path) = path {
-                write_path(path, is_clip_path, Transform::default(), None, opt, xml);
+            if let Some(mut path) = path {
+                // Shape-to-path conversion doesn't preserve the vector effect.
+                path.vector_effect = rect.vector_effect;
+                write_path(&path, is_clip_path, Transform::default(), None, opt, xml);
             }
         }
         Node::Ellipse(ref ellipse) => {
@@ -719,8 +763,9 @@ fn write_element(node: &Node, is_clip_path: bool, opt: &WriteOptions, xml: &mut
                     ellipse.abs_transform(),
                 );
 
-                if let Some(ref path) = path {
-                    write_path(path, is_clip_path, Transform::default(), None, opt, xml);
+                if let Some(mut path) = path {
+                    path.vector_effect = ellipse.vector_effect;
+                    write_path(&path, is_clip_path, Transform::default(), None, opt, xml);
                 }
             }
         }
@@ -750,8 +795,104 @@ fn write_element(node: &Node, is_clip_path: bool, opt: &WriteOptions, xml: &mut
                         polygon.abs_transform(),
                     );
 
-                    if let Some(ref path) = path {
-                        write_path(path, is_clip_path, Transform::default(), None, opt, xml);
+                    if let Some(mut path) = path {
+                        path.vector_effect = polygon.vector_effect;
+                        write_path(&path, is_clip_path, Transform::default(), None, opt, xml);
+                    }
+                }
+            }
+        }
+        Node::Circle(ref circle) => {
+            // Convert circle to path for writing
+            use crate::parser::PathBuilderExt;
+            use std::sync::Arc;
+            use tiny_skia_path::PathBuilder;
+
+            let cx = circle.cx();
+            let cy = circle.cy();
+            let r = circle.r();
+
+            let mut builder = PathBuilder::new();
+            builder.move_to(cx + r, cy);
+            builder.arc_to(r, r, 0.0, false, true, cx, cy + r);
+            builder.arc_to(r, r, 0.0, false, true, cx - r, cy);
+            builder.arc_to(r, r, 0.0, false, true, cx, cy - r);
+            builder.arc_to(r, r, 0.0, false, true, cx + r, cy);
+            builder.close();
+
+            if let Some(path_data) = builder.finish() {
+                let path = Path::new(
+                    circle.id().to_string(),
+                    circle.is_visible(),
+                    circle.fill().cloned(),
+                    circle.stroke().cloned(),
+                    circle.paint_order(),
+                    circle.rendering_mode(),
+                    Arc::new(path_data),
+                    circle.abs_transform(),
+                );
+
+                if let Some(mut path) = path {
+                    path.vector_effect = circle.vector_effect;
+                    write_path(&path, is_clip_path, Transform::default(), None, opt, xml);
+                }
+            }
+        }
+        Node::Line(ref line) => {
+            // Convert line to path for writing
+            use std::sync::Arc;
+            use tiny_skia_path::PathBuilder;
+
+            let mut builder = PathBuilder::new();
+            builder.move_to(line.x1(), line.y1());
+            builder.line_to(line.x2(), line.y2());
+
+            if let Some(path_data) = builder.finish() {
+                let path = Path::new(
+                    line.id().to_string(),
+                    line.is_visible(),
+                    line.fill().cloned(),
+                    line.stroke().cloned(),
+                    line.paint_order(),
+                    line.rendering_mode(),
+                    Arc::new(path_data),
+                    line.abs_transform(),
+                );
+
+                if let Some(mut path) = path {
+                    path.vector_effect = line.vector_effect;
+                    write_path(&path, is_clip_path, Transform::default(), None, opt, xml);
+                }
+            }
+        }
+        Node::Polyline(ref polyline) => {
+            // Convert polyline to path for writing
+            use std::sync::Arc;
+            use tiny_skia_path::PathBuilder;
+
+            let mut builder = PathBuilder::new();
+            let points = polyline.points();
+            if !points.is_empty() {
+                builder.move_to(points[0].0, points[0].1);
+                for &(x, y) in &points[1..] {
+                    builder.line_to(x, y);
+                }
+
+                if let Some(path_data) = builder.finish() {
+                    let path = Path::new(
+                        polyline.id().to_string(),
+                        polyline.is_visible(),
+                        polyline.fill().cloned(),
+                        polyline.stroke().cloned(),
+                        polyline.paint_order(),
+                        polyline.rendering_mode(),
+                        Arc::new(path_data),
+                        polyline.abs_transform(),
+                    );
+
+                    if let Some(mut path) = path {
+                        path.vector_effect = polyline.vector_effect;
+                        write_path(&path, is_clip_path, Transform::default(), None, opt, xml);
                     }
                 }
             }
@@ -794,7 +935,9 @@ fn write_element(node: &Node, is_clip_path: bool, opt: &WriteOptions, xml: &mut
             write_group_element(g, is_clip_path, opt, xml);
         }
         Node::Text(ref text) => {
-            if opt.preserve_text {
+            // Text shapes cannot be referenced, so shape-wrapped text is always flattened.
+            let is_shape_wrapped = matches!(text.wrap, Some(TextWrap::Shape(_)));
+            if opt.preserve_text && !is_shape_wrapped {
                 xml.start_svg_element(EId::Text);
 
                 if !text.id.is_empty() {
@@ -808,6 +951,10 @@ fn write_element(node: &Node, is_clip_path: bool, opt: &WriteOptions, xml: &mut
                     WritingMode::TopToBottom => xml.write_svg_attribute(AId::WritingMode, "tb"),
                 }
 
+                if text.direction == TextDirection::RightToLeft {
+                    xml.write_svg_attribute(AId::Direction, "rtl");
+                }
+
                 match text.rendering_mode {
                     TextRendering::OptimizeSpeed => {
                         xml.write_svg_attribute(AId::TextRendering, "optimizeSpeed");
@@ -830,6 +977,10 @@ fn write_element(node: &Node, is_clip_path: bool, opt: &WriteOptions, xml: &mut
                     xml.write_numbers(AId::Dy, &text.dy);
                 }
 
+                if let Some(TextWrap::InlineSize(size)) = text.wrap {
+                    xml.write_svg_attribute(AId::InlineSize, &size);
+                }
+
                 xml.set_preserve_whitespaces(true);
 
                 for chunk in &text.chunks {
@@ -845,6 +996,10 @@ fn write_element(node: &Node, is_clip_path: bool, opt: &WriteOptions, xml: &mut
                         if text_path.start_offset != 0.0 {
                             xml.write_svg_attribute(AId::StartOffset, &text_path.start_offset);
                         }
+
+                        if text_path.side == TextPathSide::Right {
+                            xml.write_svg_attribute(AId::Side, "right");
+                        }
                     }
 
                     xml.start_svg_element(EId::Tspan);
@@ -864,6 +1019,15 @@ fn write_element(node: &Node, is_clip_path: bool, opt: &WriteOptions, xml: &mut
                     }
 
                     for span in &chunk.spans {
+                        // Embeddings always start and end at span boundaries.
+                        for embedding in chunk
+                            .bidi_embeddings
+                            .iter()
+                            .filter(|e| e.start == span.start)
+                        {
+                            write_bidi_embedding(embedding, xml);
+                        }
+
                         let decorations: Vec<_> = [
                             ("underline", &span.decoration.underline),
                             ("line-through", &span.decoration.line_through),
@@ -882,6 +1046,7 @@ fn write_element(node: &Node, is_clip_path: bool, opt: &WriteOptions, xml: &mut
                         for (deco_name, deco) in &decorations {
                             xml.start_svg_element(EId::Tspan);
                             xml.write_svg_attribute(AId::TextDecoration, deco_name);
+                            write_decoration_style(deco, xml);
                             write_fill(&deco.fill, false, opt, xml);
                             write_stroke(&deco.stroke, opt, xml);
                         }
@@ -892,6 +1057,10 @@ fn write_element(node: &Node, is_clip_path: bool, opt: &WriteOptions, xml: &mut
                         for _ in &decorations {
                             xml.end_element();
                         }
+
+                        for _ in chunk.bidi_embeddings.iter().filter(|e| e.end == span.end) {
+                            xml.end_element();
+                        }
                     }
                     xml.end_element();
 
@@ -910,6 +1079,33 @@ fn write_element(node: &Node, is_clip_path: bool, opt: &WriteOptions, xml: &mut
     }
 }
 
+fn write_decoration_style(deco: &TextDecorationStyle, xml: &mut XmlWriter) {
+    let line_style = match deco.line_style {
+        TextDecorationLineStyle::Solid => None,
+        TextDecorationLineStyle::Double => Some("double"),
+        TextDecorationLineStyle::Dotted => Some("dotted"),
+        TextDecorationLineStyle::Dashed => Some("dashed"),
+        TextDecorationLineStyle::Wavy => Some("wavy"),
+    };
+    if let Some(line_style) = line_style {
+        xml.write_svg_attribute(AId::TextDecorationStyle, line_style);
+    }
+
+    let underline_position = match deco.underline_position {
+        TextUnderlinePosition::Auto => None,
+        TextUnderlinePosition::Under => Some("under"),
+        TextUnderlinePosition::Left => Some("left"),
+        TextUnderlinePosition::Right => Some("right"),
+    };
+    if let Some(underline_position) = underline_position {
+        xml.write_svg_attribute(AId::TextUnderlinePosition, underline_position);
+    }
+
+    if !deco.skip_ink {
+        xml.write_svg_attribute(AId::TextDecorationSkipInk, "none");
+    }
+}
+
 fn write_group_element(g: &Group, is_clip_path: bool, opt: &WriteOptions, xml: &mut XmlWriter) {
     if is_clip_path {
         // The `clipPath` element in SVG doesn't allow groups, only shapes and text.
@@ -959,8 +1155,33 @@ fn write_group_element(g: &Group, is_clip_path: bool, opt: &WriteOptions, xml: &
         xml.write_func_iri(AId::ClipPath, clip.id(), opt);
     }
 
-    if let Some(ref mask) = g.mask {
-        xml.write_func_iri(AId::Mask, mask.id(), opt);
+    if let [ref layer] = g.masks[..] {
+        xml.write_func_iri(AId::Mask, layer.mask.id(), opt);
+    } else if !g.masks.is_empty() {
+        let prefix = opt.id_prefix.as_deref().unwrap_or_default();
+        let ids: Vec<_> = g
+            .masks
+            .iter()
+            .map(|layer| format!("url(#{}{})", prefix, layer.mask.id()))
+            .collect();
+        xml.write_svg_attribute(AId::MaskImage, &ids.join(", "));
+
+        if g.masks
+            .iter()
+            .any(|layer| layer.composite != MaskComposite::Add)
+        {
+            let operators: Vec<_> = g
+                .masks
+                .iter()
+                .map(|layer| match layer.composite {
+                    MaskComposite::Add => "add",
+                    MaskComposite::Subtract => "subtract",
+                    MaskComposite::Intersect => "intersect",
+                    MaskComposite::Exclude => "exclude",
+                })
+                .collect();
+            xml.write_svg_attribute(AId::MaskComposite, &operators.join(", "));
+        }
     }
 
     if !g.filters.is_empty() {
@@ -1244,12 +1465,12 @@ fn has_xlink(parent: &Group) -> bool {
                     }
                 }
 
-                if let Some(ref mask) = g.mask {
-                    if has_xlink(mask.root()) {
+                for layer in &g.masks {
+                    if has_xlink(layer.mask.root()) {
                         return true;
                     }
 
-                    if let Some(ref sub_mask) = mask.mask {
+                    if let Some(ref sub_mask) = layer.mask.mask {
                         if has_xlink(&sub_mask.root) {
                             return true;
                         }
@@ -1307,6 +1528,67 @@ fn write_base_grad(g: &BaseGradient, opt: &WriteOptions, xml: &mut XmlWriter) {
     }
 }
 
+fn write_mesh_gradient(mg: &MeshGradient, opt: &WriteOptions, xml: &mut XmlWriter) {
+    xml.start_svg_element(EId::Meshgradient);
+    xml.write_id_attribute(mg.id(), opt);
+    let start = mg.rows[0][0].points[0];
+    xml.write_svg_attribute(AId::X, &start.x);
+    xml.write_svg_attribute(AId::Y, &start.y);
+    xml.write_units(AId::GradientUnits, mg.units, Units::ObjectBoundingBox);
+    xml.write_transform(AId::GradientTransform, mg.transform, opt);
+    if mg.kind == MeshGradientType::Bicubic {
+        xml.write_svg_attribute(AId::Type, "bicubic");
+    }
+
+    for (row_idx, row) in mg.rows.iter().enumerate() {
+        xml.start_svg_element(EId::Meshrow);
+        for (column_idx, patch) in row.iter().enumerate() {
+            xml.start_svg_element(EId::Meshpatch);
+
+            // Edges and corners shared with the patches above and to the left are omitted.
+            let has_above = row_idx > 0;
+            let has_left = column_idx > 0;
+            for edge in 0..4 {
+                if (edge == 0 && has_above) || (edge == 3 && has_left) {
+                    continue;
+                }
+
+                xml.start_svg_element(EId::Stop);
+                let p = &patch.points;
+                xml.write_attribute_raw(AId::Path.to_str(), |buf| {
+                    buf.extend_from_slice(b"C ");
+                    for point in [p[edge * 3 + 1], p[edge * 3 + 2], p[(edge * 3 + 3) % 12]] {
+                        write_num(point.x, buf, opt.coordinates_precision);
+                        buf.push(b' ');
+                        write_num(point.y, buf, opt.coordinates_precision);
+                        buf.push(b' ');
+                    }
+                    buf.pop();
+                });
+
+                let shared_corner = match edge {
+                    0 => has_above || has_left,
+                    1 => has_above,
+                    _ => false,
+                };
+                if !shared_corner {
+                    xml.write_color(AId::StopColor, patch.colors[edge]);
+                    if patch.opacities[edge] != Opacity::ONE {
+                        xml.write_svg_attribute(AId::StopOpacity, &patch.opacities[edge].get());
+                    }
+                }
+
+                xml.end_element();
+            }
+
+            xml.end_element();
+        }
+        xml.end_element();
+    }
+
+    xml.end_element();
+}
+
 fn write_path(
     path: &Path,
     is_clip_path: bool,
@@ -1337,16 +1619,26 @@ fn write_path(
         ShapeRendering::GeometricPrecision => {}
     }
 
+    if path.vector_effect == VectorEffect::NonScalingStroke {
+        xml.write_svg_attribute(AId::VectorEffect, "non-scaling-stroke");
+    }
+
     if let Some(id) = clip_path {
         xml.write_func_iri(AId::ClipPath, id, opt);
     }
 
     xml.write_transform(AId::Transform, path_transform, opt);
 
+    write_path_data(&path.data, opt, xml);
+
+    xml.end_element();
+}
+
+fn write_path_data(data: &tiny_skia_path::Path, opt: &WriteOptions, xml: &mut XmlWriter) {
     xml.write_attribute_raw("d", |buf| {
         use tiny_skia_path::PathSegment;
 
-        for seg in path.data.segments() {
+        for seg in data.segments() {
             match seg {
                 PathSegment::MoveTo(p) => {
                     buf.extend_from_slice(b"M ");
@@ -1396,16 +1688,15 @@ fn write_path(
 
         buf.pop();
     });
-
-    xml.end_element();
 }
 
 fn write_fill(fill: &Option<Fill>, is_clip_path: bool, opt: &WriteOptions, xml: &mut XmlWriter) {
     if let Some(ref fill) = fill {
         write_paint(AId::Fill, &fill.paint, opt, xml);
 
-        if fill.opacity != Opacity::ONE {
-            xml.write_svg_attribute(AId::FillOpacity, &fill.opacity.get());
+        let opacity = fill.opacity * paint_opacity(&fill.paint);
+        if opacity != Opacity::ONE {
+            xml.write_svg_attribute(AId::FillOpacity, &opacity.get());
         }
 
         if !fill.rule.is_default() {
@@ -1426,8 +1717,9 @@ fn write_stroke(stroke: &Option<Stroke>, opt: &WriteOptions, xml: &mut XmlWriter
     if let Some(ref stroke) = stroke {
         write_paint(AId::Stroke, &stroke.paint, opt, xml);
 
-        if stroke.opacity != Opacity::ONE {
-            xml.write_svg_attribute(AId::StrokeOpacity, &stroke.opacity.get());
+        let opacity = stroke.opacity * paint_opacity(&stroke.paint);
+        if opacity != Opacity::ONE {
+            xml.write_svg_attribute(AId::StrokeOpacity, &opacity.get());
         }
 
         if !stroke.dashoffset.approx_zero_ulps(4) {
@@ -1475,12 +1767,77 @@ fn write_paint(aid: AId, paint: &Paint, opt: &WriteOptions, xml: &mut XmlWriter)
         Paint::RadialGradient(ref rg) => {
             xml.write_func_iri(aid, rg.id(), opt);
         }
+        Paint::ConicGradient(ref cg) => {
+            xml.write_color(aid, average_stop(cg).0);
+        }
         Paint::Pattern(ref patt) => {
             xml.write_func_iri(aid, patt.id(), opt);
         }
+        Paint::MeshGradient(ref mg) => {
+            xml.write_func_iri(aid, mg.id(), opt);
+        }
+        Paint::Hatch(ref hatch) => {
+            xml.write_func_iri(aid, hatch.id(), opt);
+        }
     }
 }
 
+/// Returns an opacity that has to be applied in addition to the paint one.
+fn paint_opacity(paint: &Paint) -> Opacity {
+    match paint {
+        Paint::ConicGradient(ref cg) => average_stop(cg).1,
+        _ => Opacity::ONE,
+    }
+}
+
+/// Averages gradient stops over the whole gradient range.
+///
+/// Used as a fallback for conic gradients, which cannot be expressed in SVG.
+fn average_stop(gradient: &BaseGradient) -> (Color, Opacity) {
+    let stops = &gradient.stops;
+    let (Some(first), Some(last)) = (stops.first(), stops.last()) else {
+        return (Color::black(), Opacity::ONE);
+    };
+
+    // Premultiplied RGBA, since transparent stops should not affect the color.
+    let rgba = |stop: &Stop| {
+        let a = stop.opacity.get();
+        [
+            stop.color.red as f32 * a,
+            stop.color.green as f32 * a,
+            stop.color.blue as f32 * a,
+            a,
+        ]
+    };
+
+    // Colors are interpolated linearly between stops and padded outside of them.
+    let mut sum = [0.0; 4];
+    let mut add = |c1: [f32; 4], c2: [f32; 4], length: f32| {
+        for i in 0..4 {
+            sum[i] += (c1[i] + c2[i]) * 0.5 * length;
+        }
+    };
+
+    add(rgba(first), rgba(first), first.offset.get());
+    for pair in stops.windows(2) {
+        add(
+            rgba(&pair[0]),
+            rgba(&pair[1]),
+            pair[1].offset.get() - pair[0].offset.get(),
+        );
+    }
+    add(rgba(last), rgba(last), 1.0 - last.offset.get());
+
+    let alpha = sum[3];
+    if alpha.approx_zero_ulps(4) {
+        return (Color::black(), Opacity::ZERO);
+    }
+
+    let channel = |c: f32| (c / alpha).round().clamp(0.0, 255.0) as u8;
+    let color = Color::new_rgb(channel(sum[0]), channel(sum[1]), channel(sum[2]));
+    (color, Opacity::new_clamped(alpha))
+}
+
 fn write_light_source(light: &filter::LightSource, xml: &mut XmlWriter) {
     match light {
         filter::LightSource::DistantLight(ref light) => {
@@ -1548,6 +1905,24 @@ fn write_num(num: f32, buf: &mut Vec<u8>, precision: u8) {
 }
 
 /// Write all of the tspan attributes except for decorations.
+fn write_bidi_embedding(embedding: &BidiEmbedding, xml: &mut XmlWriter) {
+    xml.start_svg_element(EId::Tspan);
+
+    match embedding.direction {
+        TextDirection::LeftToRight => xml.write_svg_attribute(AId::Direction, "ltr"),
+        TextDirection::RightToLeft => xml.write_svg_attribute(AId::Direction, "rtl"),
+    }
+
+    let unicode_bidi = match embedding.unicode_bidi {
+        UnicodeBidi::Embed => "embed",
+        UnicodeBidi::Isolate => "isolate",
+        UnicodeBidi::BidiOverride => "bidi-override",
+        UnicodeBidi::IsolateOverride => "isolate-override",
+        UnicodeBidi::Plaintext => "plaintext",
+    };
+    xml.write_svg_attribute(AId::UnicodeBidi, unicode_bidi);
+}
+
 fn write_span(
     is_clip_path: bool,
     opt: &WriteOptions,
@@ -1614,6 +1989,22 @@ fn write_span(
         xml.write_svg_attribute(AId::FontStretch, name);
     }
 
+    if !span.font.variations.is_empty() {
+        let mut value = String::new();
+        for (i, variation) in span.font.variations.iter().enumerate() {
+            if i != 0 {
+                value.push_str(", ");
+            }
+
+            value.push('\'');
+            value.push_str(&String::from_utf8_lossy(&variation.tag));
+            value.push_str("' ");
+            value.push_str(&variation.value.to_string());
+        }
+
+        xml.write_svg_attribute(AId::FontVariationSettings, &value);
+    }
+
     xml.write_svg_attribute(AId::FontSize, &span.font_size);
 
     xml.write_visibility(span.visible);
@@ -1634,10 +2025,44 @@ fn write_span(
         xml.write_svg_attribute(AId::LengthAdjust, "spacingAndGlyphs");
     }
 
+    match span.white_space {
+        WhiteSpace::Normal => {}
+        WhiteSpace::NoWrap => xml.write_svg_attribute(AId::WhiteSpace, "nowrap"),
+        WhiteSpace::Pre => xml.write_svg_attribute(AId::WhiteSpace, "pre"),
+        WhiteSpace::PreWrap => xml.write_svg_attribute(AId::WhiteSpace, "pre-wrap"),
+        WhiteSpace::PreLine => xml.write_svg_attribute(AId::WhiteSpace, "pre-line"),
+    }
+
+    if let Some(line_height) = span.line_height {
+        xml.write_svg_attribute(AId::LineHeight, &line_height);
+    }
+
+    match span.text_orientation {
+        TextOrientation::Mixed => {}
+        TextOrientation::Upright => xml.write_svg_attribute(AId::TextOrientation, "upright"),
+        TextOrientation::Sideways => xml.write_svg_attribute(AId::TextOrientation, "sideways"),
+    }
+
     if span.small_caps {
         xml.write_svg_attribute(AId::FontVariant, "small-caps");
     }
 
+    if !span.font_features.is_empty() {
+        let mut value = String::new();
+        for (i, feature) in span.font_features.iter().enumerate() {
+            if i != 0 {
+                value.push_str(", ");
+            }
+
+            value.push('\'');
+            value.push_str(&String::from_utf8_lossy(&feature.tag));
+            value.push_str("' ");
+            value.push_str(&feature.value.to_string());
+        }
+
+        xml.write_svg_attribute(AId::FontFeatureSettings, &value);
+    }
+
     if span.paint_order == PaintOrder::StrokeAndFill {
         xml.write_svg_attribute(AId::PaintOrder, "stroke fill");
     }
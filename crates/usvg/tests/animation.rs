@@ -0,0 +1,198 @@
+// Copyright 2025 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use usvg::Color;
+
+fn parse(svg: &str, time: Option<f32>) -> usvg::Tree {
+    let opt = usvg::Options {
+        animation_time: time,
+        ..usvg::Options::default()
+    };
+    usvg::Tree::from_str(svg, &opt).unwrap()
+}
+
+fn rect(tree: &usvg::Tree) -> &usvg::Rectangle {
+    match tree.node_by_id("rect1") {
+        Some(usvg::Node::Rectangle(ref rect)) => rect,
+        _ => unreachable!(),
+    }
+}
+
+fn fill(tree: &usvg::Tree) -> Color {
+    match rect(tree).fill().unwrap().paint() {
+        usvg::Paint::Color(c) => *c,
+        _ => unreachable!(),
+    }
+}
+
+fn assert_transform(ts: usvg::Transform, expected: usvg::Transform) {
+    let a = [ts.sx, ts.ky, ts.kx, ts.sy, ts.tx, ts.ty];
+    let b = [
+        expected.sx,
+        expected.ky,
+        expected.kx,
+        expected.sy,
+        expected.tx,
+        expected.ty,
+    ];
+    for (a, b) in a.iter().zip(b) {
+        assert!((a - b).abs() < 0.01, "{:?} != {:?}", ts, expected);
+    }
+}
+
+#[test]
+fn ignored_by_default() {
+    let svg = "
+    <svg xmlns='http://www.w3.org/2000/svg'>
+        <rect id='rect1' width='10' height='10'>
+            <animate attributeName='x' from='0' to='100' dur='1s' fill='freeze'/>
+        </rect>
+    </svg>
+    ";
+
+    assert_eq!(rect(&parse(svg, None)).x(), 0.0);
+    assert_eq!(rect(&parse(svg, Some(2.0))).x(), 100.0);
+}
+
+#[test]
+fn linear_interpolation() {
+    let svg = "
+    <svg xmlns='http://www.w3.org/2000/svg'>
+        <rect id='rect1' x='5' width='10' height='10' fill='black'>
+            <animate attributeName='x' from='0' to='100' begin='1s' dur='2s'/>
+            <animate attributeName='fill' values='#000000;#ff0000;#0000ff' dur='2s'/>
+        </rect>
+    </svg>
+    ";
+
+    // Before the beginning.
+    assert_eq!(rect(&parse(svg, Some(0.5))).x(), 5.0);
+    assert_eq!(rect(&parse(svg, Some(1.5))).x(), 25.0);
+    // After the end without `fill='freeze'`.
+    assert_eq!(rect(&parse(svg, Some(3.0))).x(), 5.0);
+
+    assert_eq!(fill(&parse(svg, Some(0.5))), Color::new_rgb(128, 0, 0));
+    assert_eq!(fill(&parse(svg, Some(1.5))), Color::new_rgb(128, 0, 128));
+}
+
+#[test]
+fn repeat_and_freeze() {
+    let svg = "
+    <svg xmlns='http://www.w3.org/2000/svg'>
+        <rect id='rect1' width='10' height='10'>
+            <animate attributeName='x' from='0' to='10' dur='1s'
+                     repeatCount='2' accumulate='sum' fill='freeze'/>
+        </rect>
+    </svg>
+    ";
+
+    assert_eq!(rect(&parse(svg, Some(0.5))).x(), 5.0);
+    assert_eq!(rect(&parse(svg, Some(1.5))).x(), 15.0);
+    assert_eq!(rect(&parse(svg, Some(5.0))).x(), 20.0);
+}
+
+#[test]
+fn discrete_and_set() {
+    let svg = "
+    <svg xmlns='http://www.w3.org/2000/svg'>
+        <rect id='rect1' width='10' height='10'>
+            <animate attributeName='x' values='0;50;100' calcMode='discrete' dur='3s'/>
+            <set attributeName='height' to='40' begin='00:00:02'/>
+        </rect>
+    </svg>
+    ";
+
+    let tree = parse(svg, Some(1.5));
+    assert_eq!(rect(&tree).x(), 50.0);
+    assert_eq!(rect(&tree).height(), 10.0);
+
+    let tree = parse(svg, Some(2.5));
+    assert_eq!(rect(&tree).x(), 100.0);
+    assert_eq!(rect(&tree).height(), 40.0);
+}
+
+#[test]
+fn key_splines() {
+    let svg = "
+    <svg xmlns='http://www.w3.org/2000/svg'>
+        <rect id='rect1' width='10' height='10'>
+            <animate attributeName='x' values='0;100' keyTimes='0;1' keySplines='0 0 1 0'
+                     calcMode='spline' dur='1s'/>
+        </rect>
+    </svg>
+    ";
+
+    // An ease-in curve is below the linear one.
+    let x = rect(&parse(svg, Some(0.5))).x();
+    assert!(x > 0.0 && x < 50.0);
+}
+
+#[test]
+fn important_css_is_not_animated() {
+    let svg = "
+    <svg xmlns='http://www.w3.org/2000/svg'>
+        <style>#rect1 { fill: green !important }</style>
+        <rect id='rect1' width='10' height='10'>
+            <set attributeName='fill' to='red'/>
+        </rect>
+    </svg>
+    ";
+
+    assert_eq!(fill(&parse(svg, Some(0.0))), Color::new_rgb(0, 128, 0));
+}
+
+#[test]
+fn animate_transform() {
+    let svg = "
+    <svg xmlns='http://www.w3.org/2000/svg'>
+        <rect id='rect1' width='10' height='10' transform='translate(10 0)'>
+            <animateTransform attributeName='transform' type='scale' from='1' to='3'
+                              dur='2s' additive='sum'/>
+        </rect>
+    </svg>
+    ";
+
+    let tree = parse(svg, Some(1.0));
+    assert_transform(
+        rect(&tree).abs_transform(),
+        usvg::Transform::from_row(2.0, 0.0, 0.0, 2.0, 10.0, 0.0),
+    );
+}
+
+#[test]
+fn animate_motion() {
+    let svg = "
+    <svg xmlns='http://www.w3.org/2000/svg' xmlns:xlink='http://www.w3.org/1999/xlink'>
+        <path id='path1' d='M 0 0 L 100 0 L 100 100'/>
+        <rect id='rect1' width='10' height='10'>
+            <animateMotion dur='2s' rotate='auto'>
+                <mpath xlink:href='#path1'/>
+            </animateMotion>
+        </rect>
+    </svg>
+    ";
+
+    let tree = parse(svg, Some(0.5));
+    assert_transform(
+        rect(&tree).abs_transform(),
+        usvg::Transform::from_translate(50.0, 0.0),
+    );
+
+    let tree = parse(svg, Some(1.5));
+    assert_transform(
+        rect(&tree).abs_transform(),
+        usvg::Transform::from_row(0.0, 1.0, -1.0, 0.0, 100.0, 50.0),
+    );
+}
+
+#[test]
+fn animation_by_href() {
+    let svg = "
+    <svg xmlns='http://www.w3.org/2000/svg' xmlns:xlink='http://www.w3.org/1999/xlink'>
+        <rect id='rect1' width='10' height='10'/>
+        <animate xlink:href='#rect1' attributeName='width' by='10' dur='1s' fill='freeze'/>
+    </svg>
+    ";
+
+    assert_eq!(rect(&parse(svg, Some(1.0))).width(), 20.0);
+}
@@ -0,0 +1,178 @@
+// Copyright 2025 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use std::sync::Arc;
+
+use usvg::{
+    filter, ClipPath, Color, Fill, GroupBuilder, LinearGradient, NonZeroRect, Opacity, Paint,
+    PathBuilder, Size, Stop, StopOffset, Stroke, StrokeWidth, Transform, TreeBuilder,
+};
+
+fn rect_data(x: f32, y: f32, w: f32, h: f32) -> Arc<usvg::tiny_skia_path::Path> {
+    let rect = usvg::Rect::from_xywh(x, y, w, h).unwrap();
+    Arc::new(usvg::tiny_skia_path::PathBuilder::from_rect(rect))
+}
+
+#[test]
+fn builder_matches_parser() {
+    let svg = "
+    <svg xmlns='http://www.w3.org/2000/svg' width='200' height='200'>
+        <g id='g1' transform='translate(10 20) scale(2)'>
+            <path id='path1' d='M 10 10 H 50 V 30 H 10 Z' stroke='black' stroke-width='4'/>
+        </g>
+    </svg>
+    ";
+    let parsed = usvg::Tree::from_str(svg, &usvg::Options::default()).unwrap();
+
+    let mut stroke = Stroke::default();
+    stroke.set_width(StrokeWidth::new(4.0).unwrap());
+    let path = PathBuilder::new(rect_data(10.0, 10.0, 40.0, 20.0))
+        .id("path1".to_string())
+        .fill(Fill::default())
+        .stroke(stroke)
+        .build()
+        .unwrap();
+    let group = GroupBuilder::new()
+        .id("g1".to_string())
+        .transform(Transform::from_row(2.0, 0.0, 0.0, 2.0, 10.0, 20.0))
+        .child(path)
+        .build();
+    let built = TreeBuilder::new(Size::from_wh(200.0, 200.0).unwrap())
+        .child(group)
+        .build();
+
+    for id in ["g1", "path1"] {
+        let a = parsed.node_by_id(id).unwrap();
+        let b = built.node_by_id(id).unwrap();
+        assert_eq!(a.abs_transform(), b.abs_transform());
+        assert_eq!(a.bounding_box(), b.bounding_box());
+        assert_eq!(a.abs_bounding_box(), b.abs_bounding_box());
+        assert_eq!(a.stroke_bounding_box(), b.stroke_bounding_box());
+        assert_eq!(a.abs_stroke_bounding_box(), b.abs_stroke_bounding_box());
+    }
+
+    assert_eq!(
+        parsed.root().layer_bounding_box(),
+        built.root().layer_bounding_box()
+    );
+}
+
+#[test]
+fn builder_collects_resources() {
+    let gradient = LinearGradient::new(
+        "lg1".to_string(),
+        0.0,
+        0.0,
+        100.0,
+        0.0,
+        vec![
+            Stop::new(StopOffset::ZERO, Color::black(), Opacity::ONE),
+            Stop::new(StopOffset::ONE, Color::white(), Opacity::ONE),
+        ],
+    )
+    .unwrap();
+
+    let mut fill = Fill::default();
+    fill.set_paint(Paint::LinearGradient(Arc::new(gradient)));
+
+    let clip_path = ClipPath::new(
+        "clip1".to_string(),
+        GroupBuilder::new()
+            .child(
+                PathBuilder::new(rect_data(0.0, 0.0, 50.0, 50.0))
+                    .fill(Fill::default())
+                    .build()
+                    .unwrap(),
+            )
+            .build(),
+    )
+    .unwrap();
+
+    let blur = filter::Primitive::new(
+        NonZeroRect::from_xywh(-10.0, -10.0, 120.0, 120.0).unwrap(),
+        filter::ColorInterpolation::SRGB,
+        "result1".to_string(),
+        filter::Kind::GaussianBlur(filter::GaussianBlur::new(
+            filter::Input::SourceGraphic,
+            usvg::PositiveF32::new(2.0).unwrap(),
+            usvg::PositiveF32::new(2.0).unwrap(),
+        )),
+    );
+    let filter = filter::Filter::new(
+        "filter1".to_string(),
+        NonZeroRect::from_xywh(-10.0, -10.0, 120.0, 120.0).unwrap(),
+        vec![blur],
+    )
+    .unwrap();
+
+    let group = GroupBuilder::new()
+        .clip_path(Arc::new(clip_path))
+        .filter(Arc::new(filter))
+        .child(
+            PathBuilder::new(rect_data(0.0, 0.0, 100.0, 100.0))
+                .fill(fill)
+                .build()
+                .unwrap(),
+        )
+        .build();
+
+    let tree = TreeBuilder::new(Size::from_wh(100.0, 100.0).unwrap())
+        .child(group)
+        .build();
+
+    assert_eq!(tree.linear_gradients().len(), 1);
+    assert_eq!(tree.clip_paths().len(), 1);
+    assert_eq!(tree.filters().len(), 1);
+    assert_eq!(
+        tree.root().layer_bounding_box(),
+        NonZeroRect::from_xywh(-10.0, -10.0, 120.0, 120.0).unwrap()
+    );
+}
+
+#[test]
+fn builder_requires_resource_ids() {
+    assert!(ClipPath::new(String::new(), GroupBuilder::new().build()).is_none());
+    assert!(LinearGradient::new(String::new(), 0.0, 0.0, 1.0, 0.0, Vec::new()).is_none());
+}
+
+#[test]
+fn builder_lays_out_text() {
+    let mut fontdb = usvg::fontdb::Database::new();
+    fontdb.load_fonts_dir("../resvg/tests/fonts");
+    let mut fontdb = Arc::new(fontdb);
+
+    let font = usvg::Font::new(
+        vec![usvg::FontFamily::Named("Noto Sans".to_string())],
+        usvg::FontStyle::Normal,
+        usvg::FontStretch::Normal,
+        400,
+    )
+    .unwrap();
+
+    let text = usvg::TextBuilder::new()
+        .id("text1".to_string())
+        .chunk(10.0, 50.0, usvg::TextAnchor::Start)
+        .span(
+            "Text",
+            font,
+            usvg::NonZeroPositiveF32::new(24.0).unwrap(),
+            Some(Fill::default()),
+            None,
+        )
+        .build(&mut fontdb)
+        .unwrap();
+
+    let tree = TreeBuilder::new(Size::from_wh(100.0, 100.0).unwrap())
+        .fontdb(fontdb)
+        .child(
+            GroupBuilder::new()
+                .transform(Transform::from_translate(5.0, 0.0))
+                .child(text)
+                .build(),
+        )
+        .build();
+
+    let text = tree.node_by_id("text1").unwrap();
+    assert!(text.bounding_box().width() > 0.0);
+    assert_eq!(text.abs_bounding_box().x(), text.bounding_box().x() + 5.0);
+}
@@ -0,0 +1,155 @@
+// Copyright 2025 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+#[test]
+fn edit_moves_node_into_transformed_group() {
+    let svg = "
+    <svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 100 100'>
+        <rect id='rect1' x='10' y='20' width='30' height='40'/>
+    </svg>
+    ";
+
+    let mut tree = usvg::Tree::from_str(svg, &usvg::Options::default()).unwrap();
+    tree.edit(|root| {
+        let node = root.remove_child(0);
+        let mut group = usvg::Group::default();
+        group.set_id("g1".to_string());
+        group.set_transform(usvg::Transform::from_translate(5.0, 10.0));
+        group.push_child(node);
+        root.push_child(usvg::Node::Group(Box::new(group)));
+    });
+
+    let rect = tree.node_by_id("rect1").unwrap();
+    assert_eq!(
+        rect.abs_transform(),
+        usvg::Transform::from_translate(5.0, 10.0)
+    );
+    assert_eq!(
+        rect.bounding_box(),
+        usvg::Rect::from_xywh(10.0, 20.0, 30.0, 40.0).unwrap()
+    );
+    assert_eq!(
+        rect.abs_bounding_box(),
+        usvg::Rect::from_xywh(15.0, 30.0, 30.0, 40.0).unwrap()
+    );
+
+    let group = tree.node_by_id("g1").unwrap();
+    assert_eq!(
+        group.abs_bounding_box(),
+        usvg::Rect::from_xywh(15.0, 30.0, 30.0, 40.0).unwrap()
+    );
+    assert_eq!(
+        tree.root().abs_bounding_box(),
+        usvg::Rect::from_xywh(15.0, 30.0, 30.0, 40.0).unwrap()
+    );
+}
+
+#[test]
+fn edit_stroke_updates_bounding_boxes() {
+    let svg = "
+    <svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 100 100'>
+        <circle id='circle1' cx='50' cy='50' r='10'/>
+    </svg>
+    ";
+
+    let mut tree = usvg::Tree::from_str(svg, &usvg::Options::default()).unwrap();
+    tree.edit(|root| {
+        let Some(usvg::Node::Circle(ref mut circle)) = root.node_by_id_mut("circle1") else {
+            panic!("Expected Circle node");
+        };
+
+        let mut stroke = usvg::Stroke::default();
+        stroke.set_width(usvg::StrokeWidth::new(4.0).unwrap());
+        circle.set_stroke(Some(stroke));
+    });
+
+    let circle = tree.node_by_id("circle1").unwrap();
+    assert_eq!(
+        circle.stroke_bounding_box(),
+        usvg::Rect::from_xywh(38.0, 38.0, 24.0, 24.0).unwrap()
+    );
+    assert_eq!(
+        tree.root().stroke_bounding_box(),
+        usvg::Rect::from_xywh(38.0, 38.0, 24.0, 24.0).unwrap()
+    );
+}
+
+#[test]
+fn edit_recollects_paint_servers() {
+    let svg = "
+    <svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 100 100'>
+        <linearGradient id='lg1'>
+            <stop offset='0' stop-color='white'/>
+            <stop offset='1' stop-color='black'/>
+        </linearGradient>
+        <rect id='rect1' width='10' height='10' fill='url(#lg1)'/>
+        <rect id='rect2' width='10' height='10'/>
+    </svg>
+    ";
+
+    let mut tree = usvg::Tree::from_str(svg, &usvg::Options::default()).unwrap();
+    assert_eq!(tree.linear_gradients().len(), 1);
+
+    tree.edit(|root| {
+        root.remove_child(0);
+    });
+
+    assert!(tree.linear_gradients().is_empty());
+    assert_eq!(tree.root().children().len(), 1);
+}
+
+#[test]
+fn edit_updates_flattened_text() {
+    let svg = "
+    <svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 100 100'>
+        <g id='g1'>
+            <text id='text1' x='10' y='50' font-family='Noto Sans'>Text</text>
+        </g>
+    </svg>
+    ";
+
+    let mut fontdb = usvg::fontdb::Database::new();
+    fontdb.load_fonts_dir("../resvg/tests/fonts");
+    let opt = usvg::Options {
+        fontdb: std::sync::Arc::new(fontdb),
+        ..usvg::Options::default()
+    };
+    let mut tree = usvg::Tree::from_str(svg, &opt).unwrap();
+
+    let text_bbox = match tree.node_by_id("text1") {
+        Some(usvg::Node::Text(ref text)) => text.flattened().abs_bounding_box(),
+        _ => unreachable!(),
+    };
+
+    tree.edit(|root| {
+        let Some(usvg::Node::Group(ref mut group)) = root.node_by_id_mut("g1") else {
+            unreachable!()
+        };
+        group.set_transform(usvg::Transform::from_translate(5.0, 10.0));
+
+        let Some(usvg::Node::Text(ref mut text)) = group.node_by_id_mut("text1") else {
+            unreachable!()
+        };
+        text.set_id("text2".to_string());
+    });
+
+    let Some(usvg::Node::Text(ref text)) = tree.node_by_id("text2") else {
+        unreachable!()
+    };
+    let flattened = text.flattened();
+    assert_eq!(flattened.id(), "text2");
+    assert_eq!(
+        flattened.abs_transform(),
+        usvg::Transform::from_translate(5.0, 10.0)
+    );
+    assert_eq!(
+        flattened.abs_bounding_box(),
+        usvg::Rect::from_xywh(
+            text_bbox.x() + 5.0,
+            text_bbox.y() + 10.0,
+            text_bbox.width(),
+            text_bbox.height()
+        )
+        .unwrap()
+    );
+}
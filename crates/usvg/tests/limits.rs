@@ -0,0 +1,264 @@
+// Copyright 2025 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+fn parse(svg: &str, limits: usvg::Limits) -> Result<usvg::Tree, usvg::Error> {
+    let opt = usvg::Options {
+        limits,
+        ..usvg::Options::default()
+    };
+    usvg::Tree::from_str(svg, &opt)
+}
+
+/// Wraps an SVG into a data URL.
+fn data_url(svg: &str) -> String {
+    let mut url = "data:image/svg+xml,".to_string();
+    for c in svg.chars() {
+        match c {
+            '<' | '>' | '"' | '\'' | '#' | '%' => url.push_str(&format!("%{:02X}", c as u32)),
+            _ => url.push(c),
+        }
+    }
+    url
+}
+
+/// Creates an SVG with `depth` levels of nested SVG images.
+fn nested_images(depth: u32) -> String {
+    let mut svg = "<svg xmlns='http://www.w3.org/2000/svg' width='10' height='10'/>".to_string();
+    for _ in 0..depth {
+        svg = format!(
+            "<svg xmlns='http://www.w3.org/2000/svg' width='10' height='10'>\
+                <image width='10' height='10' href='{}'/>\
+            </svg>",
+            data_url(&svg)
+        );
+    }
+    svg
+}
+
+#[test]
+fn use_depth() {
+    let svg = "
+    <svg xmlns='http://www.w3.org/2000/svg' xmlns:xlink='http://www.w3.org/1999/xlink'>
+        <rect id='rect1' width='10' height='10'/>
+        <use id='use1' xlink:href='#rect1'/>
+        <use id='use2' xlink:href='#use1'/>
+        <use xlink:href='#use2'/>
+    </svg>
+    ";
+
+    let limits = usvg::Limits {
+        max_use_depth: 2,
+        ..usvg::Limits::default()
+    };
+    assert!(matches!(
+        parse(svg, limits),
+        Err(usvg::Error::UseDepthLimitReached)
+    ));
+
+    let limits = usvg::Limits {
+        max_use_depth: 3,
+        ..usvg::Limits::default()
+    };
+    assert!(parse(svg, limits).is_ok());
+}
+
+#[test]
+fn elements_after_use_resolving() {
+    let svg = "
+    <svg xmlns='http://www.w3.org/2000/svg' xmlns:xlink='http://www.w3.org/1999/xlink'>
+        <g id='g1'>
+            <rect width='10' height='10'/>
+            <rect width='10' height='10'/>
+        </g>
+        <use xlink:href='#g1'/>
+        <use xlink:href='#g1'/>
+    </svg>
+    ";
+
+    // 1 root + 1 svg + 3 elements + 2 * (1 use + 3 elements)
+    let limits = usvg::Limits {
+        max_elements: 12,
+        ..usvg::Limits::default()
+    };
+    assert!(matches!(
+        parse(svg, limits),
+        Err(usvg::Error::ElementsLimitReached)
+    ));
+
+    let limits = usvg::Limits {
+        max_elements: 13,
+        ..usvg::Limits::default()
+    };
+    assert!(parse(svg, limits).is_ok());
+}
+
+#[test]
+fn elements_in_text() {
+    let tspans = "<tspan>a</tspan>".repeat(50);
+    let svg = format!(
+        "<svg xmlns='http://www.w3.org/2000/svg'><text y='20'>{}</text></svg>",
+        tspans
+    );
+
+    let limits = usvg::Limits {
+        max_elements: 10,
+        ..usvg::Limits::default()
+    };
+    assert!(matches!(
+        parse(&svg, limits),
+        Err(usvg::Error::ElementsLimitReached)
+    ));
+}
+
+#[test]
+fn filter_region_area() {
+    let svg = "
+    <svg xmlns='http://www.w3.org/2000/svg' width='2000' height='2000' viewBox='0 0 200 200'>
+        <filter id='filter1' filterUnits='userSpaceOnUse' x='0' y='0' width='100' height='100'>
+            <feFlood flood-color='green'/>
+        </filter>
+        <g filter='url(#filter1)'>
+            <rect width='10' height='10'/>
+        </g>
+    </svg>
+    ";
+
+    let limits = usvg::Limits {
+        max_filter_region_area: 999_999.0,
+        ..usvg::Limits::default()
+    };
+    assert!(matches!(
+        parse(svg, limits),
+        Err(usvg::Error::FilterRegionLimitReached)
+    ));
+
+    let limits = usvg::Limits {
+        max_filter_region_area: 1_000_000.0,
+        ..usvg::Limits::default()
+    };
+    assert!(parse(svg, limits).is_ok());
+}
+
+#[test]
+fn filter_region_outside_canvas() {
+    let svg = "
+    <svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 200 200'>
+        <filter id='filter1' filterUnits='userSpaceOnUse' x='-10000' y='-10000' width='100000' height='100000'>
+            <feFlood flood-color='green'/>
+        </filter>
+        <rect width='10' height='10' filter='url(#filter1)'/>
+    </svg>
+    ";
+
+    let limits = usvg::Limits {
+        max_filter_region_area: 40_000.0,
+        ..usvg::Limits::default()
+    };
+    assert!(parse(svg, limits).is_ok());
+}
+
+#[test]
+fn pattern_tiles() {
+    let svg = "
+    <svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 100 100'>
+        <pattern id='patt1' patternUnits='userSpaceOnUse' width='0.1' height='0.1'>
+            <rect width='0.05' height='0.05'/>
+        </pattern>
+        <rect width='100' height='100' fill='url(#patt1)'/>
+    </svg>
+    ";
+
+    let limits = usvg::Limits {
+        max_pattern_tiles: 999_999,
+        ..usvg::Limits::default()
+    };
+    assert!(matches!(
+        parse(svg, limits),
+        Err(usvg::Error::PatternTilesLimitReached)
+    ));
+
+    let limits = usvg::Limits {
+        max_pattern_tiles: 1_000_001,
+        ..usvg::Limits::default()
+    };
+    assert!(parse(svg, limits).is_ok());
+}
+
+#[test]
+fn nested_images_depth() {
+    let svg = nested_images(3);
+
+    let limits = usvg::Limits {
+        max_nested_images_depth: 2,
+        ..usvg::Limits::default()
+    };
+    assert!(matches!(
+        parse(&svg, limits),
+        Err(usvg::Error::NestedImagesLimitReached)
+    ));
+
+    let limits = usvg::Limits {
+        max_nested_images_depth: 3,
+        ..usvg::Limits::default()
+    };
+    assert!(parse(&svg, limits).is_ok());
+}
+
+#[test]
+fn nested_images_are_not_loaded_past_the_limit() {
+    // Documents deeper than the limit must not be parsed at all.
+    let svg = nested_images(100);
+
+    let limits = usvg::Limits {
+        max_nested_images_depth: 2,
+        ..usvg::Limits::default()
+    };
+    assert!(matches!(
+        parse(&svg, limits),
+        Err(usvg::Error::NestedImagesLimitReached)
+    ));
+}
+
+#[test]
+fn image_pixels() {
+    // A 100x100 PNG.
+    let svg = "
+    <svg xmlns='http://www.w3.org/2000/svg' xmlns:xlink='http://www.w3.org/1999/xlink'>
+        <image width='10' height='10' xlink:href='data:image/png;base64,iVBORw0KGgoAAAANSUhEUgAAAGQAAABkCAYAAABw4pVUAAABb0lEQVR4Xu3VUQ0AIAzEUOZfA87wAgkq+vGmoGlz2exz73IZAyNIpsUHEaTVQ5BYD0EEqRmI8fghgsQMxHAsRJCYgRiOhQgSMxDDsRBBYgZiOBYiSMxADMdCBIkZiOFYiCAxAzEcCxEkZiCGYyGCxAzEcCxEkJiBGI6FCBIzEMOxEEFiBmI4FiJIzEAMx0IEiRmI4ViIIDEDMRwLESRmIIZjIYLEDMRwLESQmIEYjoUIEjMQw7EQQWIGYjgWIkjMQAzHQgSJGYjhWIggMQMxHAsRJGYghmMhgsQMxHAsRJCYgRiOhQgSMxDDsRBBYgZiOBYiSMxADMdCBIkZiOFYiCAxAzEcCxEkZiCGYyGCxAzEcCxEkJiBGI6FCBIzEMOxEEFiBmI4FiJIzEAMx0IEiRmI4ViIIDEDMRwLESRmIIZjIYLEDMRwLESQmIEYjoUIEjMQw7EQQWIGYjgWIkjMQAzHQgSJGYjhWIggMQMxnAdKSlrwlejIDgAAAABJRU5ErkJggg=='/>
+    </svg>
+    ";
+
+    let limits = usvg::Limits {
+        max_image_pixels: 9_999,
+        ..usvg::Limits::default()
+    };
+    assert!(matches!(
+        parse(svg, limits),
+        Err(usvg::Error::ImagePixelsLimitReached)
+    ));
+
+    let limits = usvg::Limits {
+        max_image_pixels: 10_000,
+        ..usvg::Limits::default()
+    };
+    assert!(parse(svg, limits).is_ok());
+}
+
+#[test]
+fn image_pixels_ignore_layout_size() {
+    // A 100x100 PNG drawn as a single pixel.
+    let svg = "
+    <svg xmlns='http://www.w3.org/2000/svg' xmlns:xlink='http://www.w3.org/1999/xlink'>
+        <image width='1' height='1' xlink:href='data:image/png;base64,iVBORw0KGgoAAAANSUhEUgAAAGQAAABkCAYAAABw4pVUAAABb0lEQVR4Xu3VUQ0AIAzEUOZfA87wAgkq+vGmoGlz2exz73IZAyNIpsUHEaTVQ5BYD0EEqRmI8fghgsQMxHAsRJCYgRiOhQgSMxDDsRBBYgZiOBYiSMxADMdCBIkZiOFYiCAxAzEcCxEkZiCGYyGCxAzEcCxEkJiBGI6FCBIzEMOxEEFiBmI4FiJIzEAMx0IEiRmI4ViIIDEDMRwLESRmIIZjIYLEDMRwLESQmIEYjoUIEjMQw7EQQWIGYjgWIkjMQAzHQgSJGYjhWIggMQMxHAsRJGYghmMhgsQMxHAsRJCYgRiOhQgSMxDDsRBBYgZiOBYiSMxADMdCBIkZiOFYiCAxAzEcCxEkZiCGYyGCxAzEcCxEkJiBGI6FCBIzEMOxEEFiBmI4FiJIzEAMx0IEiRmI4ViIIDEDMRwLESRmIIZjIYLEDMRwLESQmIEYjoUIEjMQw7EQQWIGYjgWIkjMQAzHQgSJGYjhWIggMQMxnAdKSlrwlejIDgAAAABJRU5ErkJggg=='/>
+    </svg>
+    ";
+
+    let limits = usvg::Limits {
+        max_image_pixels: 9_999,
+        ..usvg::Limits::default()
+    };
+    assert!(matches!(
+        parse(svg, limits),
+        Err(usvg::Error::ImagePixelsLimitReached)
+    ));
+}
@@ -547,3 +547,312 @@ fn no_text_nodes() {
     let tree = usvg::Tree::from_str(&svg, &usvg::Options::default()).unwrap();
     assert!(!tree.has_text_nodes());
 }
+
+#[test]
+fn diagnostics() {
+    let svg = "
+<svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 100 100'>
+    <ellipse id='ellipse1' rx='0' ry='10'/>
+    <rect id='rect1' width='10' height='10' fill='qwe'/>
+    <rect id='rect2' width='10' height='10'/>
+</svg>
+";
+
+    let tree = usvg::Tree::from_str(&svg, &usvg::Options::default()).unwrap();
+    let diagnostics = tree.diagnostics();
+    assert_eq!(diagnostics.len(), 2);
+
+    assert_eq!(diagnostics[0].code, usvg::DiagnosticCode::InvalidSize);
+    assert_eq!(diagnostics[0].severity, usvg::Severity::Error);
+    assert_eq!(diagnostics[0].element_id, "ellipse1");
+    assert_eq!(
+        diagnostics[0].pos,
+        Some(usvg::roxmltree::TextPos::new(3, 5))
+    );
+    assert_eq!(
+        diagnostics[0].message,
+        "Ellipse 'ellipse1' has an invalid 'rx' value. Skipped."
+    );
+
+    assert_eq!(diagnostics[1].code, usvg::DiagnosticCode::InvalidValue);
+    assert_eq!(diagnostics[1].severity, usvg::Severity::Warning);
+    assert_eq!(diagnostics[1].element_id, "rect1");
+    assert_eq!(
+        diagnostics[1].pos,
+        Some(usvg::roxmltree::TextPos::new(4, 5))
+    );
+}
+
+#[test]
+fn diagnostics_inside_use() {
+    let svg = "
+<svg xmlns='http://www.w3.org/2000/svg' xmlns:xlink='http://www.w3.org/1999/xlink'>
+    <g id='g1'>
+        <use id='use1' xlink:href='#g1'/>
+    </g>
+</svg>
+";
+
+    let tree = usvg::Tree::from_str(&svg, &usvg::Options::default()).unwrap();
+    let diagnostics = tree.diagnostics();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].code,
+        usvg::DiagnosticCode::RecursiveReference
+    );
+    assert_eq!(diagnostics[0].element_id, "use1");
+    assert_eq!(
+        diagnostics[0].pos,
+        Some(usvg::roxmltree::TextPos::new(4, 9))
+    );
+}
+
+#[test]
+fn diagnostics_from_image_resolver() {
+    let svg = "
+<svg xmlns='http://www.w3.org/2000/svg' xmlns:xlink='http://www.w3.org/1999/xlink'>
+    <image id='image1' width='10' height='10' xlink:href='missing.png'/>
+</svg>
+";
+
+    let tree = usvg::Tree::from_str(svg, &usvg::Options::default()).unwrap();
+    let diagnostics = tree.diagnostics();
+    assert_eq!(diagnostics.len(), 2);
+
+    assert_eq!(
+        diagnostics[0].code,
+        usvg::DiagnosticCode::ResourceLoadingFailed
+    );
+    assert_eq!(diagnostics[0].severity, usvg::Severity::Warning);
+    assert_eq!(diagnostics[0].element_id, "image1");
+    assert_eq!(
+        diagnostics[0].message,
+        "'missing.png' is not a path to an image."
+    );
+
+    assert_eq!(diagnostics[1].severity, usvg::Severity::Error);
+    assert_eq!(diagnostics[1].element_id, "image1");
+}
+
+#[test]
+fn diagnostics_for_zero_sized_paint() {
+    let svg = "
+<svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 100 100'>
+    <pattern id='patt1' width='20' height='20' patternUnits='userSpaceOnUse'
+             patternContentUnits='objectBoundingBox'>
+        <rect width='0.5' height='0.5' fill='green'/>
+    </pattern>
+    <path d='M 10 10 L 90 10' stroke='url(#patt1)'/>
+</svg>
+";
+
+    let tree = usvg::Tree::from_str(svg, &usvg::Options::default()).unwrap();
+    let diagnostics = tree.diagnostics();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code, usvg::DiagnosticCode::ZeroSizedShape);
+    assert_eq!(diagnostics[0].element_id, "patt1");
+    assert_eq!(diagnostics[0].pos, None);
+}
+
+#[test]
+fn diagnostics_on_a_long_line() {
+    let padding = " ".repeat(5000);
+    let svg = format!(
+        "<svg xmlns='http://www.w3.org/2000/svg'>\n{}<rect id='rect1' fill='qwe'/>{}ё<rect id='rect2' fill='qwe'/></svg>",
+        padding, padding
+    );
+
+    let tree = usvg::Tree::from_str(&svg, &usvg::Options::default()).unwrap();
+    let diagnostics = tree.diagnostics();
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(
+        diagnostics[0].pos,
+        Some(usvg::roxmltree::TextPos::new(2, 5001))
+    );
+    assert_eq!(
+        diagnostics[1].pos,
+        Some(usvg::roxmltree::TextPos::new(2, 10031))
+    );
+}
+
+#[test]
+fn no_diagnostics() {
+    let svg = "
+    <svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 100 100'>
+        <rect width='10' height='10' fill='green'/>
+    </svg>
+    ";
+
+    let tree = usvg::Tree::from_str(&svg, &usvg::Options::default()).unwrap();
+    assert!(tree.diagnostics().is_empty());
+}
+
+#[test]
+fn radial_gradient_fr() {
+    let svg = "
+    <svg xmlns='http://www.w3.org/2000/svg' xmlns:xlink='http://www.w3.org/1999/xlink'
+         viewBox='0 0 100 100'>
+        <radialGradient id='rg1' gradientUnits='userSpaceOnUse' fr='10'/>
+        <radialGradient id='rg2' xlink:href='#rg1' r='40'>
+            <stop offset='0' stop-color='black'/>
+            <stop offset='1' stop-color='white'/>
+        </radialGradient>
+        <radialGradient id='rg3' gradientUnits='userSpaceOnUse' r='40' fr='-10'>
+            <stop offset='0' stop-color='black'/>
+            <stop offset='1' stop-color='white'/>
+        </radialGradient>
+        <rect id='rect1' width='100' height='100' fill='url(#rg2)'/>
+        <rect id='rect2' width='100' height='100' fill='url(#rg3)'/>
+    </svg>
+    ";
+
+    let tree = usvg::Tree::from_str(&svg, &usvg::Options::default()).unwrap();
+    let fr = |id: &str| {
+        let Some(usvg::Node::Rectangle(rect)) = tree.node_by_id(id) else {
+            unreachable!()
+        };
+        let Some(usvg::Paint::RadialGradient(rg)) = rect.fill().map(|f| f.paint()) else {
+            unreachable!()
+        };
+        rg.fr().get()
+    };
+
+    // Resolved via `xlink:href`.
+    assert_eq!(fr("rect1"), 10.0);
+    // A negative value is an error.
+    assert_eq!(fr("rect2"), 0.0);
+}
+
+#[test]
+fn mesh_gradient_rows() {
+    let svg = "
+    <svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 100 100'>
+        <meshgradient id='mg1' x='10' y='10' gradientUnits='userSpaceOnUse'>
+            <meshrow>
+                <meshpatch>
+                    <stop path='l 40 0' stop-color='red'/>
+                    <stop path='l 0 40' stop-color='green'/>
+                    <stop path='l -40 0' stop-color='blue'/>
+                    <stop path='l 0 -40' stop-color='yellow'/>
+                </meshpatch>
+                <meshpatch>
+                    <stop path='l 40 0'/>
+                    <stop path='l 0 40' stop-color='white' stop-opacity='0.5'/>
+                    <stop path='l -40 0' stop-color='black'/>
+                </meshpatch>
+            </meshrow>
+        </meshgradient>
+        <rect id='rect1' width='100' height='100' fill='url(#mg1)'/>
+    </svg>
+    ";
+
+    let tree = usvg::Tree::from_str(svg, &usvg::Options::default()).unwrap();
+    let Some(usvg::Node::Rectangle(rect)) = tree.node_by_id("rect1") else {
+        unreachable!()
+    };
+    let Some(usvg::Paint::MeshGradient(mg)) = rect.fill().map(|f| f.paint()) else {
+        unreachable!()
+    };
+
+    assert_eq!(mg.kind(), usvg::MeshGradientType::Bilinear);
+    assert_eq!(mg.rows().len(), 1);
+    let row = &mg.rows()[0];
+    assert_eq!(row.len(), 2);
+
+    // The second patch shares its left edge with the first one.
+    assert_eq!(row[1].points()[0], row[0].points()[3]);
+    assert_eq!(row[1].points()[9], row[0].points()[6]);
+    assert_eq!(row[1].colors()[0], usvg::Color::new_rgb(0, 128, 0));
+    assert_eq!(row[1].colors()[3], usvg::Color::new_rgb(0, 0, 255));
+    assert_eq!(row[1].colors()[1], usvg::Color::white());
+    assert_eq!(row[1].opacities()[1].get(), 0.5);
+    assert_eq!(
+        row[1].points()[6],
+        usvg::tiny_skia_path::Point::from_xy(90.0, 50.0)
+    );
+}
+
+#[test]
+fn hatch_with_invalid_pitch() {
+    let svg = "
+    <svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 100 100'>
+        <hatch id='hatch1' pitch='-5'>
+            <hatchpath stroke='green'/>
+        </hatch>
+        <rect id='rect1' width='100' height='100' fill='url(#hatch1)'/>
+    </svg>
+    ";
+
+    let tree = usvg::Tree::from_str(svg, &usvg::Options::default()).unwrap();
+    assert!(tree.hatches().is_empty());
+    let diagnostics = tree.diagnostics();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code, usvg::DiagnosticCode::InvalidSize);
+    assert_eq!(diagnostics[0].element_id, "hatch1");
+}
+
+#[test]
+fn conic_gradient_angles() {
+    let svg = "
+    <svg xmlns='http://www.w3.org/2000/svg' xmlns:xlink='http://www.w3.org/1999/xlink'
+         viewBox='0 0 100 100'>
+        <conicGradient id='cg1' gradientUnits='userSpaceOnUse' startAngle='45'/>
+        <conicGradient id='cg2' xlink:href='#cg1'>
+            <stop offset='0' stop-color='black'/>
+            <stop offset='1' stop-color='white'/>
+        </conicGradient>
+        <rect id='rect1' width='100' height='100' fill='url(#cg2)'/>
+    </svg>
+    ";
+
+    let tree = usvg::Tree::from_str(svg, &usvg::Options::default()).unwrap();
+    let Some(usvg::Node::Rectangle(rect)) = tree.node_by_id("rect1") else {
+        unreachable!()
+    };
+    let Some(usvg::Paint::ConicGradient(cg)) = rect.fill().map(|f| f.paint()) else {
+        unreachable!()
+    };
+
+    // Resolved via `xlink:href`.
+    assert_eq!(cg.start_angle(), 45.0);
+    assert_eq!(cg.end_angle(), 360.0);
+    assert_eq!(cg.cx(), 50.0);
+    assert_eq!(cg.cy(), 50.0);
+}
+
+#[test]
+fn mask_layers() {
+    let svg = "
+    <svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 100 100'>
+        <mask id='mask1'>
+            <rect width='100' height='100' fill='white'/>
+        </mask>
+        <g id='g1' style='mask-image: url(#mask1), none, radial-gradient(black, white);
+                          mask-mode: alpha, luminance;
+                          mask-composite: subtract, intersect, exclude'>
+            <rect width='100' height='100'/>
+        </g>
+    </svg>
+    ";
+
+    let tree = usvg::Tree::from_str(svg, &usvg::Options::default()).unwrap();
+    let Some(usvg::Node::Group(group)) = tree.node_by_id("g1") else {
+        unreachable!()
+    };
+
+    let masks = group.masks();
+    assert_eq!(masks.len(), 3);
+
+    // `mask-mode` overrides `mask-type`, which creates a new mask.
+    assert_eq!(masks[0].mask().kind(), usvg::MaskType::Alpha);
+    assert_ne!(masks[0].mask().id(), "mask1");
+    assert_eq!(masks[0].composite(), usvg::MaskComposite::Subtract);
+
+    // `none` is a transparent black layer.
+    assert!(!masks[1].mask().root().has_children());
+    assert_eq!(masks[1].composite(), usvg::MaskComposite::Intersect);
+
+    // `mask-mode` values are repeated.
+    assert_eq!(masks[2].mask().kind(), usvg::MaskType::Alpha);
+    assert_eq!(masks[2].composite(), usvg::MaskComposite::Exclude);
+}
@@ -150,3 +150,108 @@ fn primitive_shape_bounding_boxes() {
         _ => panic!("Expected Rectangle node"),
     }
 }
+
+#[test]
+fn circle_preserved_as_circle_node() {
+    let svg = r#"
+    <svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 100 100'>
+        <circle id='circle1' cx='50' cy='40' r='20'/>
+    </svg>
+    "#;
+
+    let tree = usvg::Tree::from_str(&svg, &usvg::Options::default()).unwrap();
+    let root = tree.root();
+
+    assert_eq!(root.children().len(), 1);
+
+    match &root.children()[0] {
+        usvg::Node::Circle(ref circle) => {
+            assert_eq!(circle.id(), "circle1");
+            assert_eq!(circle.cx(), 50.0);
+            assert_eq!(circle.cy(), 40.0);
+            assert_eq!(circle.r(), 20.0);
+            assert!(circle.is_visible());
+
+            let bbox = circle.bounding_box();
+            assert_eq!(bbox.x(), 30.0);
+            assert_eq!(bbox.y(), 20.0);
+            assert_eq!(bbox.width(), 40.0);
+            assert_eq!(bbox.height(), 40.0);
+        }
+        _ => panic!("Expected Circle node, got something else"),
+    }
+}
+
+#[test]
+fn line_preserved_as_line_node() {
+    let svg = r#"
+    <svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 100 100'>
+        <line id='line1' x1='10' y1='20' x2='90' y2='20' stroke='black' stroke-width='4'/>
+    </svg>
+    "#;
+
+    let tree = usvg::Tree::from_str(&svg, &usvg::Options::default()).unwrap();
+    let root = tree.root();
+
+    assert_eq!(root.children().len(), 1);
+
+    match &root.children()[0] {
+        usvg::Node::Line(ref line) => {
+            assert_eq!(line.id(), "line1");
+            assert_eq!(line.x1(), 10.0);
+            assert_eq!(line.y1(), 20.0);
+            assert_eq!(line.x2(), 90.0);
+            assert_eq!(line.y2(), 20.0);
+            assert!(line.is_visible());
+
+            // A horizontal line has a zero-height bounding box, but not a stroke one.
+            assert_eq!(line.bounding_box().height(), 0.0);
+            assert_eq!(line.stroke_bounding_box().height(), 4.0);
+        }
+        _ => panic!("Expected Line node, got something else"),
+    }
+}
+
+#[test]
+fn polyline_preserved_as_polyline_node() {
+    let svg = r#"
+    <svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 100 100'>
+        <polyline id='polyline1' points='10,10 50,10 50,50' fill='none' stroke='black'/>
+    </svg>
+    "#;
+
+    let tree = usvg::Tree::from_str(&svg, &usvg::Options::default()).unwrap();
+    let root = tree.root();
+
+    assert_eq!(root.children().len(), 1);
+
+    match &root.children()[0] {
+        usvg::Node::Polyline(ref polyline) => {
+            assert_eq!(polyline.id(), "polyline1");
+            assert_eq!(
+                polyline.points(),
+                &[(10.0, 10.0), (50.0, 10.0), (50.0, 50.0)]
+            );
+            assert!(polyline.fill().is_none());
+            assert!(polyline.stroke().is_some());
+        }
+        _ => panic!("Expected Polyline node, got something else"),
+    }
+}
+
+#[test]
+fn shapes_with_markers_are_converted_into_paths() {
+    let svg = r#"
+    <svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 100 100'>
+        <marker id='marker1'>
+            <rect width='2' height='2'/>
+        </marker>
+        <line id='line1' x1='10' y1='20' x2='90' y2='20' stroke='black' marker-end='url(#marker1)'/>
+    </svg>
+    "#;
+
+    let tree = usvg::Tree::from_str(&svg, &usvg::Options::default()).unwrap();
+    let root = tree.root();
+
+    assert!(matches!(root.children()[0], usvg::Node::Path(_)));
+}
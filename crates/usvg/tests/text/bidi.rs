@@ -0,0 +1,125 @@
+// Copyright 2025 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use usvg::{TextDirection, UnicodeBidi};
+
+use crate::{parse, text};
+
+fn embeddings(chunk: &usvg::TextChunk) -> Vec<(usize, usize, TextDirection, UnicodeBidi)> {
+    chunk
+        .bidi_embeddings()
+        .iter()
+        .map(|e| (e.start(), e.end(), e.direction(), e.unicode_bidi()))
+        .collect()
+}
+
+#[test]
+fn direction() {
+    let tree = parse(
+        "<svg xmlns='http://www.w3.org/2000/svg' font-family='Noto Sans' direction='rtl'>
+            <text id='text1' y='20'>Text</text>
+        </svg>",
+    );
+    assert_eq!(text(&tree).direction(), TextDirection::RightToLeft);
+    assert!(text(&tree).chunks()[0].bidi_embeddings().is_empty());
+}
+
+#[test]
+fn nested_embeddings() {
+    let tree = parse(
+        "<svg xmlns='http://www.w3.org/2000/svg' font-family='Noto Sans'>
+            <text id='text1' y='20'>ab<tspan direction='rtl' unicode-bidi='embed'>cd<tspan
+                unicode-bidi='isolate-override' direction='ltr'>ef</tspan></tspan><tspan
+                unicode-bidi='normal'>gh</tspan></text>
+        </svg>",
+    );
+    assert_eq!(
+        embeddings(&text(&tree).chunks()[0]),
+        vec![
+            (2, 6, TextDirection::RightToLeft, UnicodeBidi::Embed),
+            (
+                4,
+                6,
+                TextDirection::LeftToRight,
+                UnicodeBidi::IsolateOverride
+            ),
+        ]
+    );
+}
+
+#[test]
+fn sibling_embeddings() {
+    let tree = parse(
+        "<svg xmlns='http://www.w3.org/2000/svg' font-family='Noto Sans'>
+            <text id='text1' y='20'><tspan unicode-bidi='isolate'>ab</tspan><tspan
+                unicode-bidi='isolate'>cd</tspan></text>
+        </svg>",
+    );
+    assert_eq!(
+        embeddings(&text(&tree).chunks()[0]),
+        vec![
+            (0, 2, TextDirection::LeftToRight, UnicodeBidi::Isolate),
+            (2, 4, TextDirection::LeftToRight, UnicodeBidi::Isolate),
+        ]
+    );
+}
+
+#[test]
+fn embedding_split_between_chunks() {
+    let tree = parse(
+        "<svg xmlns='http://www.w3.org/2000/svg' font-family='Noto Sans'>
+            <text id='text1' y='20'><tspan unicode-bidi='plaintext'>ab<tspan
+                x='50'>cd</tspan></tspan></text>
+        </svg>",
+    );
+    let chunks = text(&tree).chunks();
+    assert_eq!(
+        embeddings(&chunks[0]),
+        vec![(0, 2, TextDirection::LeftToRight, UnicodeBidi::Plaintext)]
+    );
+    assert_eq!(
+        embeddings(&chunks[1]),
+        vec![(0, 2, TextDirection::LeftToRight, UnicodeBidi::Plaintext)]
+    );
+}
+
+#[test]
+fn bidi_override() {
+    let glyphs = |svg: &str| -> Vec<u16> {
+        let tree = parse(svg);
+        text(&tree)
+            .layouted()
+            .iter()
+            .flat_map(|span| span.positioned_glyphs.iter())
+            .map(|glyph| glyph.id.0)
+            .collect()
+    };
+
+    let normal = glyphs(
+        "<svg xmlns='http://www.w3.org/2000/svg' font-family='Noto Sans'>
+            <text id='text1' y='20'>abc</text>
+        </svg>",
+    );
+    let reversed = glyphs(
+        "<svg xmlns='http://www.w3.org/2000/svg' font-family='Noto Sans'>
+            <text id='text1' y='20' direction='rtl' unicode-bidi='bidi-override'>cba</text>
+        </svg>",
+    );
+    assert_eq!(normal, reversed);
+}
+
+#[test]
+fn rtl_text_anchor() {
+    let bbox = |anchor: &str| {
+        let tree = parse(&format!(
+            "<svg xmlns='http://www.w3.org/2000/svg' font-family='Noto Sans'>
+                <text id='text1' x='100' y='20' direction='rtl' text-anchor='{}'>Text</text>
+            </svg>",
+            anchor
+        ));
+        text(&tree).bounding_box()
+    };
+
+    assert!((bbox("start").right() - 100.0).abs() < 0.5);
+    assert!((bbox("end").left() - 100.0).abs() < 0.5);
+}
@@ -0,0 +1,120 @@
+// Copyright 2025 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use usvg::{Color, Paint, TextDecorationLineStyle, TextUnderlinePosition};
+
+use crate::{parse, text};
+
+fn decoration(tree: &usvg::Tree) -> usvg::TextDecoration {
+    text(tree).chunks()[0].spans()[0].decoration().clone()
+}
+
+fn fill_color(style: &usvg::TextDecorationStyle) -> Option<Color> {
+    match style.fill().map(|fill| fill.paint()) {
+        Some(Paint::Color(c)) => Some(*c),
+        _ => None,
+    }
+}
+
+#[test]
+fn shorthand() {
+    let tree = parse(
+        "<svg xmlns='http://www.w3.org/2000/svg' font-family='Noto Sans'>
+            <text id='text1' y='20' text-decoration='wavy underline rgb(0, 128, 0) overline'>Text</text>
+        </svg>",
+    );
+    let decoration = decoration(&tree);
+    assert!(decoration.line_through().is_none());
+    let underline = decoration.underline().unwrap();
+    assert_eq!(underline.line_style(), TextDecorationLineStyle::Wavy);
+    assert_eq!(fill_color(underline), Some(Color::new_rgb(0, 128, 0)));
+    let overline = decoration.overline().unwrap();
+    assert_eq!(overline.line_style(), TextDecorationLineStyle::Wavy);
+}
+
+#[test]
+fn invalid_shorthand() {
+    let tree = parse(
+        "<svg xmlns='http://www.w3.org/2000/svg' font-family='Noto Sans'>
+            <text id='text1' y='20' text-decoration='underline dotted dashed'>Text</text>
+        </svg>",
+    );
+    assert!(decoration(&tree).underline().is_none());
+}
+
+#[test]
+fn longhands() {
+    let tree = parse(
+        "<svg xmlns='http://www.w3.org/2000/svg' font-family='Noto Sans'>
+            <text id='text1' y='20' text-decoration='underline wavy red'
+                  text-decoration-line='line-through' text-decoration-style='dotted'
+                  text-decoration-color='currentColor' fill='blue'>Text</text>
+        </svg>",
+    );
+    let decoration = decoration(&tree);
+    assert!(decoration.underline().is_none());
+    let line_through = decoration.line_through().unwrap();
+    assert_eq!(line_through.line_style(), TextDecorationLineStyle::Dotted);
+    assert_eq!(fill_color(line_through), Some(Color::new_rgb(0, 0, 255)));
+}
+
+#[test]
+fn fill_and_stroke() {
+    let tree = parse(
+        "<svg xmlns='http://www.w3.org/2000/svg' font-family='Noto Sans'>
+            <text id='text1' y='20' text-decoration='underline' text-decoration-color='red'
+                  text-decoration-fill='green' text-decoration-stroke='blue'>Text</text>
+        </svg>",
+    );
+    let decoration = decoration(&tree);
+    let underline = decoration.underline().unwrap();
+    assert_eq!(fill_color(underline), Some(Color::new_rgb(0, 128, 0)));
+    assert_eq!(
+        underline.stroke().map(|s| s.paint().clone()),
+        Some(Paint::Color(Color::new_rgb(0, 0, 255)))
+    );
+}
+
+#[test]
+fn declared_on_parent() {
+    let tree = parse(
+        "<svg xmlns='http://www.w3.org/2000/svg' font-family='Noto Sans'>
+            <text y='20' text-decoration='underline double' text-underline-position='under'
+                  text-decoration-skip-ink='none'><tspan id='tspan1'
+                  text-decoration-style='wavy'>Text</tspan></text>
+        </svg>",
+    );
+    let text = match tree.root().children()[0] {
+        usvg::Node::Text(ref text) => text,
+        _ => unreachable!(),
+    };
+    let decoration = text.chunks()[0].spans()[0].decoration();
+    let underline = decoration.underline().unwrap();
+    // Properties are taken from the element that declared the decoration.
+    assert_eq!(underline.line_style(), TextDecorationLineStyle::Double);
+    assert_eq!(underline.underline_position(), TextUnderlinePosition::Under);
+    assert!(!underline.skip_ink());
+}
+
+#[test]
+fn skip_ink() {
+    let underline_len = |skip_ink: &str| {
+        let tree = parse(&format!(
+            "<svg xmlns='http://www.w3.org/2000/svg' font-family='Noto Sans'>
+                <text id='text1' y='20' text-decoration='underline'
+                      text-decoration-skip-ink='{}'>gy</text>
+            </svg>",
+            skip_ink
+        ));
+        match tree.node_by_id("text1") {
+            Some(usvg::Node::Text(ref text)) => {
+                text.layouted()[0].underline.as_ref().unwrap().data().len()
+            }
+            _ => unreachable!(),
+        }
+    };
+
+    // A solid line is a single rectangle, unless it is interrupted by descenders.
+    assert_eq!(underline_len("none"), 5);
+    assert!(underline_len("auto") > 5);
+}
@@ -0,0 +1,231 @@
+// Copyright 2025 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use crate::{parse, text};
+
+fn first_span(svg: &str) -> usvg::TextSpan {
+    text(&parse(svg)).chunks()[0].spans()[0].clone()
+}
+
+fn features(svg: &str) -> (bool, Vec<([u8; 4], u32)>) {
+    let span = first_span(svg);
+    let list = span
+        .font_features()
+        .iter()
+        .map(|f| (f.tag(), f.value()))
+        .collect();
+    (span.small_caps(), list)
+}
+
+#[test]
+fn variant_longhands() {
+    let (small_caps, list) = features(
+        "<svg xmlns='http://www.w3.org/2000/svg' font-family='Noto Sans'>
+            <g style='font-variant-numeric:tabular-nums slashed-zero'>
+                <text id='text1' y='20' style='font-variant-ligatures:none;font-variant-caps:small-caps'>
+                    Text
+                </text>
+            </g>
+        </svg>",
+    );
+
+    assert!(small_caps);
+    assert_eq!(
+        list,
+        vec![
+            (*b"liga", 0),
+            (*b"clig", 0),
+            (*b"dlig", 0),
+            (*b"hlig", 0),
+            (*b"calt", 0),
+            (*b"tnum", 1),
+            (*b"zero", 1),
+        ]
+    );
+}
+
+#[test]
+fn feature_settings() {
+    let (small_caps, list) = features(
+        "<svg xmlns='http://www.w3.org/2000/svg' font-family='Noto Sans'>
+            <text id='text1' y='20' font-variant-position='super'
+                  font-feature-settings='\"tnum\", \"liga\" off, \"salt\" 2'>Text</text>
+        </svg>",
+    );
+
+    assert!(!small_caps);
+    assert_eq!(
+        list,
+        vec![(*b"sups", 1), (*b"tnum", 1), (*b"liga", 0), (*b"salt", 2)]
+    );
+}
+
+#[test]
+fn invalid_feature_settings() {
+    let (_, list) = features(
+        "<svg xmlns='http://www.w3.org/2000/svg' font-family='Noto Sans'>
+            <text id='text1' y='20' font-feature-settings='\"tnum\" 1, liga'>Text</text>
+        </svg>",
+    );
+
+    assert!(list.is_empty());
+}
+
+#[test]
+fn font_shorthand_keeps_small_caps() {
+    let (small_caps, list) = features(
+        "<svg xmlns='http://www.w3.org/2000/svg' font-family='Noto Sans'>
+            <text id='text1' y='20' style='font:small-caps 16px sans-serif'>Text</text>
+        </svg>",
+    );
+
+    assert!(small_caps);
+    assert!(list.is_empty());
+}
+
+#[test]
+fn variant_shorthand_resets_longhands() {
+    let (small_caps, list) = features(
+        "<svg xmlns='http://www.w3.org/2000/svg' font-family='Noto Sans'
+              font-variant-numeric='tabular-nums' font-variant-caps='small-caps'>
+            <g font-variant='normal'>
+                <text id='text1' y='20' font-variant-ligatures='no-contextual'>Text</text>
+            </g>
+        </svg>",
+    );
+
+    assert!(!small_caps);
+    assert_eq!(list, vec![(*b"calt", 0)]);
+}
+
+#[test]
+fn variant_shorthand_keywords() {
+    let (small_caps, list) = features(
+        "<svg xmlns='http://www.w3.org/2000/svg' font-family='Noto Sans'
+              font-variant-position='sub'>
+            <text id='text1' y='20' style='font-variant:slashed-zero none small-caps'>Text</text>
+        </svg>",
+    );
+
+    assert!(small_caps);
+    assert_eq!(
+        list,
+        vec![
+            (*b"liga", 0),
+            (*b"clig", 0),
+            (*b"dlig", 0),
+            (*b"hlig", 0),
+            (*b"calt", 0),
+            (*b"zero", 1),
+        ]
+    );
+}
+
+fn font(svg: &str) -> (u16, usvg::FontStretch, Vec<([u8; 4], f32)>) {
+    let span = first_span(svg);
+    let font = span.font();
+    let list = font
+        .variations()
+        .iter()
+        .map(|v| (v.tag(), v.value()))
+        .collect();
+    (font.weight(), font.stretch(), list)
+}
+
+#[test]
+fn numeric_font_weight() {
+    let (weight, _, _) = font(
+        "<svg xmlns='http://www.w3.org/2000/svg' font-family='Noto Sans'>
+            <text id='text1' y='20' font-weight='450.4'>Text</text>
+        </svg>",
+    );
+    assert_eq!(weight, 450);
+
+    let (weight, _, _) = font(
+        "<svg xmlns='http://www.w3.org/2000/svg' font-family='Noto Sans' font-weight='bold'>
+            <text id='text1' y='20' font-weight='1001'>Text</text>
+        </svg>",
+    );
+    assert_eq!(weight, 700);
+}
+
+#[test]
+fn font_stretch_percentage() {
+    let (_, stretch, list) = font(
+        "<svg xmlns='http://www.w3.org/2000/svg' font-family='Noto Sans'>
+            <text id='text1' y='20' font-stretch='80%'>Text</text>
+        </svg>",
+    );
+    assert_eq!(stretch, usvg::FontStretch::Condensed);
+    assert_eq!(list, vec![(*b"wdth", 80.0)]);
+
+    let (_, stretch, list) = font(
+        "<svg xmlns='http://www.w3.org/2000/svg' font-family='Noto Sans'>
+            <text id='text1' y='20' font-stretch='125%'>Text</text>
+        </svg>",
+    );
+    assert_eq!(stretch, usvg::FontStretch::Expanded);
+    assert!(list.is_empty());
+}
+
+#[test]
+fn variation_settings() {
+    let (_, _, list) = font(
+        "<svg xmlns='http://www.w3.org/2000/svg' font-family='Noto Sans'>
+            <g style=\"font-variation-settings:'wght' 650.5, 'slnt' -10\">
+                <text id='text1' y='20'>Text</text>
+            </g>
+        </svg>",
+    );
+    assert_eq!(list, vec![(*b"wght", 650.5), (*b"slnt", -10.0)]);
+
+    let (_, _, list) = font(
+        "<svg xmlns='http://www.w3.org/2000/svg' font-family='Noto Sans'>
+            <text id='text1' y='20' font-variation-settings=\"'wght'\">Text</text>
+        </svg>",
+    );
+    assert!(list.is_empty());
+}
+
+fn text_orientation(attributes: &str) -> usvg::TextOrientation {
+    let span = first_span(&format!(
+        "<svg xmlns='http://www.w3.org/2000/svg' font-family='Noto Sans'>
+            <g {}>
+                <text id='text1' y='20' writing-mode='tb'>Text</text>
+            </g>
+        </svg>",
+        attributes
+    ));
+    span.text_orientation()
+}
+
+#[test]
+fn text_orientation_and_glyph_orientation_vertical() {
+    use usvg::TextOrientation;
+
+    assert_eq!(text_orientation(""), TextOrientation::Mixed);
+    assert_eq!(
+        text_orientation("text-orientation='upright'"),
+        TextOrientation::Upright
+    );
+    assert_eq!(
+        text_orientation("style='text-orientation:sideways'"),
+        TextOrientation::Sideways
+    );
+    assert_eq!(
+        text_orientation("glyph-orientation-vertical='0'"),
+        TextOrientation::Upright
+    );
+    assert_eq!(
+        text_orientation("glyph-orientation-vertical='90deg'"),
+        TextOrientation::Sideways
+    );
+    assert_eq!(
+        text_orientation("glyph-orientation-vertical='180'"),
+        TextOrientation::Mixed
+    );
+    assert_eq!(
+        text_orientation("text-orientation='mixed' glyph-orientation-vertical='0'"),
+        TextOrientation::Mixed
+    );
+}
@@ -0,0 +1,153 @@
+// Copyright 2025 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use std::sync::Arc;
+
+use base64::Engine;
+use once_cell::sync::Lazy;
+
+use crate::{text, FONTS_DIR, GLOBAL_FONTDB};
+
+// A database without the font we are going to load via `@font-face`.
+static SMALL_FONTDB: Lazy<Arc<usvg::fontdb::Database>> = Lazy::new(|| {
+    let mut fontdb = usvg::fontdb::Database::new();
+    fontdb
+        .load_font_file(format!("{}/NotoSans-Regular.ttf", FONTS_DIR))
+        .unwrap();
+    fontdb.set_sans_serif_family("Noto Sans");
+    Arc::new(fontdb)
+});
+
+fn parse(svg: &str, fontdb: &Arc<usvg::fontdb::Database>) -> usvg::Tree {
+    let opt = usvg::Options {
+        resources_dir: Some(FONTS_DIR.into()),
+        fontdb: fontdb.clone(),
+        ..usvg::Options::default()
+    };
+    usvg::Tree::from_str(svg, &opt).unwrap()
+}
+
+fn text_bbox(tree: &usvg::Tree) -> usvg::Rect {
+    text(tree).bounding_box()
+}
+
+fn has_family(tree: &usvg::Tree, family: &str) -> bool {
+    tree.fontdb()
+        .faces()
+        .any(|face| face.families.iter().any(|(name, _)| name == family))
+}
+
+fn reference_bbox() -> usvg::Rect {
+    let tree = parse(
+        "<svg xmlns='http://www.w3.org/2000/svg'>
+            <text id='text1' y='40' font-family='Yellowtail' font-size='32'>Text</text>
+        </svg>",
+        &GLOBAL_FONTDB,
+    );
+    text_bbox(&tree)
+}
+
+fn web_font_bbox(src: &str) -> usvg::Rect {
+    let svg = format!(
+        "<svg xmlns='http://www.w3.org/2000/svg'>
+            <style>
+                @font-face {{ font-family: 'Web Font'; src: {}; }}
+            </style>
+            <text id='text1' y='40' font-family='Web Font' font-size='32'>Text</text>
+        </svg>",
+        src
+    );
+    let tree = parse(&svg, &SMALL_FONTDB);
+    assert!(has_family(&tree, "Web Font"));
+    text_bbox(&tree)
+}
+
+#[test]
+fn truetype_url() {
+    let bbox = web_font_bbox("url(Yellowtail-Regular.ttf) format('truetype')");
+    assert_eq!(bbox, reference_bbox());
+}
+
+#[test]
+fn woff_url() {
+    let bbox = web_font_bbox("url('Yellowtail-Regular.woff') format('woff')");
+    assert_eq!(bbox, reference_bbox());
+}
+
+#[test]
+fn woff2_url() {
+    let bbox = web_font_bbox("url(\"Yellowtail-Regular.woff2\") format(\"woff2\")");
+    assert_eq!(bbox, reference_bbox());
+}
+
+#[test]
+fn data_url() {
+    let data = std::fs::read(format!("{}/Yellowtail-Regular.woff2", FONTS_DIR)).unwrap();
+    let data = base64::engine::general_purpose::STANDARD.encode(data);
+    let bbox = web_font_bbox(&format!("url(data:font/woff2;base64,{})", data));
+    assert_eq!(bbox, reference_bbox());
+}
+
+#[test]
+fn src_fallback() {
+    let bbox = web_font_bbox(
+        "url(missing.woff2) format('woff2'), \
+         url(Yellowtail-Regular.eot) format('embedded-opentype'), \
+         url(Yellowtail-Regular.woff2) format('woff2')",
+    );
+    assert_eq!(bbox, reference_bbox());
+}
+
+#[test]
+fn local_alias() {
+    let tree = parse(
+        "<svg xmlns='http://www.w3.org/2000/svg'>
+            <style>
+                @font-face { font-family: Alias; src: local(Yellowtail); }
+            </style>
+            <text id='text1' y='40' font-family='Alias' font-size='32'>Text</text>
+        </svg>",
+        &GLOBAL_FONTDB,
+    );
+    assert!(has_family(&tree, "Alias"));
+    assert!(has_family(&tree, "Yellowtail"));
+    assert_eq!(text_bbox(&tree), reference_bbox());
+}
+
+#[test]
+fn descriptors() {
+    let tree = parse(
+        "<svg xmlns='http://www.w3.org/2000/svg'>
+            <style>
+                @font-face {
+                    font-family: Web;
+                    src: url(Yellowtail-Regular.woff2);
+                    font-weight: 700;
+                    font-style: italic;
+                }
+            </style>
+        </svg>",
+        &SMALL_FONTDB,
+    );
+    let face = tree
+        .fontdb()
+        .faces()
+        .find(|face| face.families[0].0 == "Web")
+        .unwrap();
+    assert_eq!(face.weight, usvg::fontdb::Weight::BOLD);
+    assert_eq!(face.style, usvg::fontdb::Style::Italic);
+}
+
+#[test]
+fn missing_src() {
+    let tree = parse(
+        "<svg xmlns='http://www.w3.org/2000/svg'>
+            <style>
+                @font-face { font-family: Web; src: url(missing.woff2); }
+                @font-face { font-family: Web2; }
+            </style>
+        </svg>",
+        &SMALL_FONTDB,
+    );
+    assert_eq!(tree.fontdb().len(), SMALL_FONTDB.len());
+}
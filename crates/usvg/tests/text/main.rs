@@ -0,0 +1,39 @@
+// Copyright 2025 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use std::sync::Arc;
+
+use once_cell::sync::Lazy;
+
+mod bidi;
+mod decoration;
+mod features;
+mod font_face;
+mod svg_font;
+mod text_path;
+mod wrap;
+
+const FONTS_DIR: &str = "../resvg/tests/fonts";
+
+static GLOBAL_FONTDB: Lazy<Arc<usvg::fontdb::Database>> = Lazy::new(|| {
+    let mut fontdb = usvg::fontdb::Database::new();
+    fontdb.load_fonts_dir(FONTS_DIR);
+    fontdb.set_sans_serif_family("Noto Sans");
+    Arc::new(fontdb)
+});
+
+fn parse(svg: &str) -> usvg::Tree {
+    let opt = usvg::Options {
+        fontdb: GLOBAL_FONTDB.clone(),
+        ..usvg::Options::default()
+    };
+    usvg::Tree::from_str(svg, &opt).unwrap()
+}
+
+/// Returns a text element with the `text1` ID.
+fn text(tree: &usvg::Tree) -> &usvg::Text {
+    match tree.node_by_id("text1") {
+        Some(usvg::Node::Text(ref text)) => text,
+        _ => unreachable!(),
+    }
+}
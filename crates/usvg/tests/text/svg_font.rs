@@ -0,0 +1,87 @@
+// Copyright 2025 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use crate::{text, GLOBAL_FONTDB};
+
+const FONT: &str = "
+    <font horiz-adv-x='1000'>
+        <font-face font-family='Shapes' units-per-em='1000' ascent='800' descent='200'
+                   font-weight='bold'/>
+        <glyph unicode='AB' d='M 500 0 A 400 400 0 1 1 500 800 A 400 400 0 1 1 500 0 Z'/>
+        <glyph unicode='A' glyph-name='triangle' d='M 0 0 L 500 800 L 1000 0 Z'/>
+        <glyph unicode='B' horiz-adv-x='600' d='M 0 0 L 400 0 C 600 200 600 600 400 800 L 0 800 Z'/>
+        <hkern u1='U+0041' g2='triangle' k='400'/>
+    </font>";
+
+fn parse(text: &str) -> usvg::Tree {
+    let svg = format!(
+        "<svg xmlns='http://www.w3.org/2000/svg'>
+            {}
+            <text id='text1' y='100' font-family='Shapes' font-size='10'>{}</text>
+        </svg>",
+        FONT, text
+    );
+    crate::parse(&svg)
+}
+
+/// Returns glyph IDs and their horizontal offsets.
+fn glyphs(tree: &usvg::Tree) -> Vec<(u16, f32)> {
+    text(tree)
+        .layouted()
+        .iter()
+        .flat_map(|span| span.positioned_glyphs.iter())
+        .map(|glyph| (glyph.id.0, glyph.transform().tx))
+        .collect()
+}
+
+#[test]
+fn face_info() {
+    let tree = parse("A");
+    let face = tree
+        .fontdb()
+        .faces()
+        .find(|face| face.families[0].0 == "Shapes")
+        .unwrap();
+    assert_eq!(face.weight, usvg::fontdb::Weight::BOLD);
+}
+
+#[test]
+fn advances() {
+    assert_eq!(glyphs(&parse("BB")), vec![(3, 0.0), (3, 6.0)]);
+}
+
+#[test]
+fn kerning() {
+    assert_eq!(glyphs(&parse("AAA")), vec![(2, 0.0), (2, 6.0), (2, 12.0)]);
+}
+
+#[test]
+fn ligature() {
+    assert_eq!(glyphs(&parse("BAB")), vec![(3, 0.0), (1, 6.0)]);
+}
+
+#[test]
+fn outline() {
+    let tree = parse("A");
+    let bbox = text(&tree).flattened().bounding_box();
+    assert_eq!(
+        (bbox.x(), bbox.y(), bbox.width(), bbox.height()),
+        (0.0, 92.0, 10.0, 8.0)
+    );
+}
+
+#[test]
+fn missing_family() {
+    let tree = crate::parse(
+        "<svg xmlns='http://www.w3.org/2000/svg'>
+            <font horiz-adv-x='1000'>
+                <glyph unicode='A' d='M 0 0 L 500 800 L 1000 0 Z'/>
+            </font>
+        </svg>",
+    );
+    assert_eq!(tree.fontdb().len(), GLOBAL_FONTDB.len());
+    assert_eq!(
+        tree.diagnostics()[0].code,
+        usvg::DiagnosticCode::MissingAttribute
+    );
+}
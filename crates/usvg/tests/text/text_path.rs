@@ -0,0 +1,79 @@
+// Copyright 2025 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use std::sync::Arc;
+
+use usvg::TextPathSide;
+
+use crate::{parse, text};
+
+fn text_paths(tree: &usvg::Tree) -> Vec<Arc<usvg::TextPath>> {
+    text(tree)
+        .chunks()
+        .iter()
+        .filter_map(|chunk| match chunk.text_flow() {
+            usvg::TextFlow::Path(ref path) => Some(path.clone()),
+            usvg::TextFlow::Linear => None,
+        })
+        .collect()
+}
+
+#[test]
+fn inline_path() {
+    let tree = parse(
+        "<svg xmlns='http://www.w3.org/2000/svg' font-family='Noto Sans'>
+            <path id='textPath1' d='M 0 0 L 10 0'/>
+            <text id='text1'><textPath path='M 10 20 L 110 20'>Text</textPath></text>
+        </svg>",
+    );
+    let paths = text_paths(&tree);
+    // The generated ID must not clash with the existing one.
+    assert_eq!(paths[0].id(), "textPath2");
+    let bounds = paths[0].path().bounds();
+    assert_eq!(
+        (bounds.left(), bounds.right(), bounds.top()),
+        (10.0, 110.0, 20.0)
+    );
+}
+
+#[test]
+fn inline_path_with_error() {
+    let tree = parse(
+        "<svg xmlns='http://www.w3.org/2000/svg' xmlns:xlink='http://www.w3.org/1999/xlink'
+              font-family='Noto Sans'>
+            <path id='path1' d='M 10 20 L 110 20'/>
+            <text id='text1'><textPath path='M 0 0 L 10' xlink:href='#path1'>Text</textPath></text>
+        </svg>",
+    );
+    assert_eq!(text_paths(&tree)[0].id(), "path1");
+}
+
+#[test]
+fn side() {
+    let tree = parse(
+        "<svg xmlns='http://www.w3.org/2000/svg' font-family='Noto Sans'>
+            <text id='text1'><textPath path='M 10 20 L 110 20'>Text</textPath><textPath
+                path='M 10 20 L 110 20' side='right'>Text</textPath></text>
+        </svg>",
+    );
+    let paths = text_paths(&tree);
+    assert_eq!(paths[0].side(), TextPathSide::Left);
+    assert_eq!(paths[1].side(), TextPathSide::Right);
+}
+
+#[test]
+fn side_right_reverses_path() {
+    let tree = parse(
+        "<svg xmlns='http://www.w3.org/2000/svg' font-family='Noto Sans'>
+            <text id='text1'><textPath path='M 10 20 L 190 20' side='right'>Text</textPath></text>
+        </svg>",
+    );
+    let text = match tree.node_by_id("text1") {
+        Some(usvg::Node::Text(ref text)) => text,
+        _ => unreachable!(),
+    };
+    // Text starts at the path end and is placed upside down below the path.
+    let bbox = text.bounding_box();
+    assert!(bbox.right() <= 190.5 && bbox.right() > 150.0);
+    assert!(bbox.top() + bbox.height() / 2.0 > 20.0);
+}
@@ -0,0 +1,151 @@
+// Copyright 2025 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use crate::{parse, text};
+
+fn text_bbox(svg: &str) -> usvg::Rect {
+    text(&parse(svg)).bounding_box()
+}
+
+fn text_string(svg: &str) -> String {
+    let tree = parse(svg);
+    text(&tree).chunks().iter().map(|c| c.text()).collect()
+}
+
+const TEXT: &str = "Lorem ipsum dolor sit amet, consectetur adipiscing elit.";
+
+#[test]
+fn inline_size() {
+    let single = text_bbox(&format!(
+        "<svg xmlns='http://www.w3.org/2000/svg' font-family='Noto Sans' font-size='16'>
+            <text id='text1' x='10' y='20'>{TEXT}</text>
+        </svg>"
+    ));
+
+    let wrapped = text_bbox(&format!(
+        "<svg xmlns='http://www.w3.org/2000/svg' font-family='Noto Sans' font-size='16'>
+            <text id='text1' x='10' y='20' style='inline-size:100px'>{TEXT}</text>
+        </svg>"
+    ));
+
+    assert!(single.width() > 100.0);
+    assert!(wrapped.left() >= 10.0);
+    assert!(wrapped.right() <= 110.0);
+    assert!(wrapped.height() > single.height() * 3.0);
+}
+
+#[test]
+fn inline_size_with_end_anchor() {
+    let bbox = text_bbox(&format!(
+        "<svg xmlns='http://www.w3.org/2000/svg' font-family='Noto Sans' font-size='16'>
+            <text id='text1' x='200' y='20' text-anchor='end' inline-size='100'>{TEXT}</text>
+        </svg>"
+    ));
+
+    assert!(bbox.left() >= 100.0);
+    assert!((bbox.right() - 200.0).abs() < 2.0);
+}
+
+#[test]
+fn shape_inside() {
+    let bbox = text_bbox(&format!(
+        "<svg xmlns='http://www.w3.org/2000/svg' font-family='Noto Sans' font-size='16'>
+            <rect id='rect1' x='20' y='30' width='120' height='100'/>
+            <text id='text1' style='shape-inside:url(#rect1);shape-padding:5'>{TEXT} {TEXT}</text>
+        </svg>"
+    ));
+
+    assert!(bbox.left() >= 25.0);
+    assert!(bbox.right() <= 135.0);
+    assert!(bbox.top() >= 35.0);
+    assert!(bbox.bottom() <= 125.0);
+    assert!(bbox.height() > 50.0);
+}
+
+#[test]
+fn shape_subtract() {
+    let bbox = text_bbox(&format!(
+        "<svg xmlns='http://www.w3.org/2000/svg' font-family='Noto Sans' font-size='16'>
+            <rect id='rect1' x='0' y='0' width='200' height='200'/>
+            <rect id='rect2' x='0' y='0' width='100' height='200'/>
+            <text id='text1' style='shape-inside:url(#rect1);shape-subtract:url(#rect2)'>{TEXT}</text>
+        </svg>"
+    ));
+
+    assert!(bbox.left() >= 100.0);
+    assert!(bbox.right() <= 200.0);
+}
+
+#[test]
+fn forced_line_break() {
+    let bbox = text_bbox(
+        "<svg xmlns='http://www.w3.org/2000/svg' font-family='Noto Sans' font-size='16'>
+            <text id='text1' y='20' style='white-space:pre'>Lorem&#10;ipsum</text>
+        </svg>",
+    );
+
+    assert!(bbox.height() > 30.0);
+    assert!(bbox.width() < 60.0);
+}
+
+#[test]
+fn line_height() {
+    let svg = |line_height: &str| {
+        format!(
+            "<svg xmlns='http://www.w3.org/2000/svg' font-family='Noto Sans' font-size='10'>
+                <text id='text1' y='20' style='white-space:pre;line-height:{line_height}'>A&#10;A&#10;A</text>
+            </svg>"
+        )
+    };
+
+    // Three lines are two line heights apart.
+    let height = |line_height| {
+        let bbox = text_bbox(&svg(line_height));
+        bbox.height() - text_bbox(&svg("0.1")).height()
+    };
+
+    assert!((height("3") - 2.0 * 29.0).abs() < 0.1);
+    assert!((height("30px") - 2.0 * 29.0).abs() < 0.1);
+    assert!((height("300%") - 2.0 * 29.0).abs() < 0.1);
+}
+
+#[test]
+fn white_space_collapsing() {
+    let svg = |white_space: &str| {
+        format!(
+            "<svg xmlns='http://www.w3.org/2000/svg' font-family='Noto Sans'>
+                <text id='text1' style='white-space:{white_space}'>  Lorem  \n  ipsum </text>
+            </svg>"
+        )
+    };
+
+    assert_eq!(text_string(&svg("normal")), "Lorem ipsum");
+    assert_eq!(text_string(&svg("pre")), "  Lorem  \n  ipsum ");
+    assert_eq!(text_string(&svg("pre-line")), "Lorem\nipsum");
+}
+
+#[test]
+fn white_space_overrides_xml_space() {
+    let text = text_string(
+        "<svg xmlns='http://www.w3.org/2000/svg' font-family='Noto Sans' xml:space='preserve'>
+            <text id='text1' style='white-space:normal'>  Lorem  ipsum </text>
+        </svg>",
+    );
+
+    assert_eq!(text, "Lorem ipsum");
+}
+
+#[test]
+fn text_transform() {
+    let svg = |transform: &str| {
+        format!(
+            "<svg xmlns='http://www.w3.org/2000/svg' font-family='Noto Sans'>
+                <text id='text1' text-transform='{transform}'>lorem IPSUM do<tspan>lor</tspan> <tspan>sit</tspan></text>
+            </svg>"
+        )
+    };
+
+    assert_eq!(text_string(&svg("uppercase")), "LOREM IPSUM DOLOR SIT");
+    assert_eq!(text_string(&svg("lowercase")), "lorem ipsum dolor sit");
+    assert_eq!(text_string(&svg("capitalize")), "Lorem IPSUM Dolor Sit");
+}
@@ -0,0 +1,96 @@
+// Copyright 2025 the Resvg Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use usvg::{Rect, VectorEffect};
+
+fn parse(svg: &str) -> usvg::Tree {
+    usvg::Tree::from_str(svg, &usvg::Options::default()).unwrap()
+}
+
+fn path<'a>(tree: &'a usvg::Tree, id: &str) -> &'a usvg::Path {
+    match tree.node_by_id(id) {
+        Some(usvg::Node::Path(ref path)) => path,
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn non_scaling_stroke_bbox() {
+    let tree = parse(
+        "<svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 200 200'>
+            <path id='path1' d='M 5 5 L 45 5 L 45 45 L 5 45 Z' transform='scale(4)'
+                  stroke='black' stroke-width='4' vector-effect='non-scaling-stroke'/>
+        </svg>",
+    );
+    let path = path(&tree, "path1");
+    assert_eq!(path.vector_effect(), VectorEffect::NonScalingStroke);
+    assert_eq!(
+        path.bounding_box(),
+        Rect::from_ltrb(5.0, 5.0, 45.0, 45.0).unwrap()
+    );
+    assert_eq!(
+        path.stroke_bounding_box(),
+        Rect::from_ltrb(4.5, 4.5, 45.5, 45.5).unwrap()
+    );
+    assert_eq!(
+        path.abs_stroke_bounding_box(),
+        Rect::from_ltrb(18.0, 18.0, 182.0, 182.0).unwrap()
+    );
+}
+
+#[test]
+fn non_scaling_stroke_on_shape() {
+    let tree = parse(
+        "<svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 200 200'>
+            <rect id='rect1' x='5' y='5' width='40' height='40' transform='scale(4 2)'
+                  stroke='black' stroke-width='4' vector-effect='non-scaling-stroke'/>
+        </svg>",
+    );
+    let rect = match tree.node_by_id("rect1") {
+        Some(usvg::Node::Rectangle(ref rect)) => rect,
+        _ => unreachable!(),
+    };
+    assert_eq!(rect.vector_effect(), VectorEffect::NonScalingStroke);
+    assert_eq!(
+        rect.stroke_bounding_box(),
+        Rect::from_ltrb(4.5, 4.0, 45.5, 46.0).unwrap()
+    );
+    assert_eq!(
+        rect.abs_stroke_bounding_box(),
+        Rect::from_ltrb(18.0, 8.0, 182.0, 92.0).unwrap()
+    );
+}
+
+#[test]
+fn not_inherited() {
+    let tree = parse(
+        "<svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 200 200'>
+            <g vector-effect='non-scaling-stroke'>
+                <path id='path1' d='M 5 5 L 45 5 L 45 45 L 5 45 Z' transform='scale(4)'
+                      stroke='black' stroke-width='4'/>
+            </g>
+        </svg>",
+    );
+    let path = path(&tree, "path1");
+    assert_eq!(path.vector_effect(), VectorEffect::None);
+    assert_eq!(
+        path.abs_stroke_bounding_box(),
+        Rect::from_ltrb(12.0, 12.0, 188.0, 188.0).unwrap()
+    );
+}
+
+#[test]
+fn set_vector_effect() {
+    let tree = parse(
+        "<svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 200 200'>
+            <path id='path1' d='M 5 5 L 45 5 L 45 45 L 5 45 Z' transform='scale(4)'
+                  stroke='black' stroke-width='4'/>
+        </svg>",
+    );
+    let mut path = path(&tree, "path1").clone();
+    path.set_vector_effect(VectorEffect::NonScalingStroke);
+    assert_eq!(
+        path.abs_stroke_bounding_box(),
+        Rect::from_ltrb(18.0, 18.0, 182.0, 182.0).unwrap()
+    );
+}
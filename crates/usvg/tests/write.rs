@@ -204,7 +204,52 @@ fn preserve_text_with_nested_baseline_shift() {
     resave_with_text("preserve-text-with-nested-baseline-shift");
 }
 
+#[test]
+fn preserve_text_on_inline_path() {
+    resave_with_text("preserve-text-on-inline-path");
+}
+
+#[test]
+fn preserve_text_with_bidi() {
+    resave_with_text("preserve-text-with-bidi");
+}
+
+#[test]
+fn preserve_text_with_decoration_style() {
+    resave_with_text("preserve-text-with-decoration-style");
+}
+
 #[test]
 fn optimize_paths_without_markers() {
     resave("optimize-paths-without-markers");
 }
+
+#[test]
+fn preserve_vector_effect() {
+    resave("preserve-vector-effect");
+}
+
+#[test]
+fn radial_gradient_with_fr() {
+    resave("radial-gradient-with-fr");
+}
+
+#[test]
+fn mesh_gradient() {
+    resave("mesh-gradient");
+}
+
+#[test]
+fn hatch() {
+    resave("hatch");
+}
+
+#[test]
+fn conic_gradient() {
+    resave("conic-gradient");
+}
+
+#[test]
+fn mask_layers() {
+    resave("mask-layers");
+}